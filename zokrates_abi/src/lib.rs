@@ -5,7 +5,7 @@ pub enum Inputs<T> {
     Abi(CheckedValues<T>),
 }
 
-impl<T: From<usize>> Encode<T> for Inputs<T> {
+impl<T: From<usize> + Add<Output = T> + Mul<Output = T>> Encode<T> for Inputs<T> {
     fn encode(self) -> Vec<T> {
         match self {
             Inputs::Raw(v) => v,
@@ -17,6 +17,7 @@ impl<T: From<usize>> Encode<T> for Inputs<T> {
 use std::collections::BTreeMap;
 use std::convert::TryFrom;
 use std::fmt;
+use std::ops::{Add, Mul};
 use zokrates_core::typed_absy::{Type, UBitwidth};
 
 use zokrates_field::Field;
@@ -45,6 +46,8 @@ enum Value<T> {
     U8(u8),
     U16(u16),
     U32(u32),
+    U64(u64),
+    Int(i128),
     Field(T),
     Boolean(bool),
     Array(Vec<Value<T>>),
@@ -56,10 +59,15 @@ enum CheckedValue<T> {
     U8(u8),
     U16(u16),
     U32(u32),
+    U64(u64),
+    I8(i8),
+    I16(i16),
+    I32(i32),
     Field(T),
     Boolean(bool),
     Array(Vec<CheckedValue<T>>),
     Struct(Vec<(String, CheckedValue<T>)>),
+    Tuple(Vec<CheckedValue<T>>),
 }
 
 #[derive(PartialEq, Debug)]
@@ -72,6 +80,8 @@ impl<T: Field> fmt::Display for Value<T> {
             Value::U8(v) => write!(f, "{:#04x}", v),
             Value::U16(v) => write!(f, "{:#06x}", v),
             Value::U32(v) => write!(f, "{:#010x}", v),
+            Value::U64(v) => write!(f, "{:#018x}", v),
+            Value::Int(v) => write!(f, "{}", v),
             Value::Boolean(v) => write!(f, "{}", v),
             Value::Array(v) => write!(
                 f,
@@ -100,6 +110,22 @@ impl<T: Field> Value<T> {
             (Value::U8(f), Type::Uint(UBitwidth::B8)) => Ok(CheckedValue::U8(f)),
             (Value::U16(f), Type::Uint(UBitwidth::B16)) => Ok(CheckedValue::U16(f)),
             (Value::U32(f), Type::Uint(UBitwidth::B32)) => Ok(CheckedValue::U32(f)),
+            (Value::U64(f), Type::Uint(UBitwidth::B64)) => Ok(CheckedValue::U64(f)),
+            // non-negative decimal values are parsed as field elements
+            (Value::Field(f), Type::Int(bitwidth)) => f
+                .to_dec_string()
+                .parse::<i128>()
+                .map_err(|_| format!("Value `{}` doesn't match expected type `i{}`", f, bitwidth))
+                .and_then(|v| Value::<T>::Int(v).check(Type::Int(bitwidth))),
+            (Value::Int(v), Type::Int(UBitwidth::B8)) => i8::try_from(v)
+                .map(CheckedValue::I8)
+                .map_err(|_| format!("Value `{}` doesn't match expected type `i8`", v)),
+            (Value::Int(v), Type::Int(UBitwidth::B16)) => i16::try_from(v)
+                .map(CheckedValue::I16)
+                .map_err(|_| format!("Value `{}` doesn't match expected type `i16`", v)),
+            (Value::Int(v), Type::Int(UBitwidth::B32)) => i32::try_from(v)
+                .map(CheckedValue::I32)
+                .map_err(|_| format!("Value `{}` doesn't match expected type `i32`", v)),
             (Value::Boolean(b), Type::Boolean) => Ok(CheckedValue::Boolean(b)),
             (Value::Array(a), Type::Array(array_type)) => {
                 if a.len() != array_type.size {
@@ -137,6 +163,23 @@ impl<T: Field> Value<T> {
                     Ok(CheckedValue::Struct(s))
                 }
             }
+            // tuples are represented as arrays of values of possibly different types
+            (Value::Array(a), Type::Tuple(tuple_type)) => {
+                if a.len() != tuple_type.len() {
+                    Err(format!(
+                        "Expected tuple of size {}, found array of size {}",
+                        tuple_type.len(),
+                        a.len()
+                    ))
+                } else {
+                    let a = a
+                        .into_iter()
+                        .zip(tuple_type.elements.into_iter())
+                        .map(|(val, ty)| val.check(ty))
+                        .collect::<Result<Vec<_>, _>>()?;
+                    Ok(CheckedValue::Tuple(a))
+                }
+            }
             (v, t) => Err(format!("Value `{}` doesn't match expected type `{}`", v, t)),
         }
     }
@@ -152,16 +195,29 @@ pub trait Decode<T> {
     fn decode(raw: Vec<T>, expected: Self::Expected) -> Self;
 }
 
-impl<T: From<usize>> Encode<T> for CheckedValue<T> {
+/// Encode a value which may not fit in a `usize`, 16 bits at a time
+fn encode_wide<T: From<usize> + Add<Output = T> + Mul<Output = T>>(v: u64) -> T {
+    (0..4).rev().fold(T::from(0), |acc, i| {
+        acc * T::from(1 << 16) + T::from((v >> (16 * i)) as usize & 0xffff)
+    })
+}
+
+impl<T: From<usize> + Add<Output = T> + Mul<Output = T>> Encode<T> for CheckedValue<T> {
     fn encode(self) -> Vec<T> {
         match self {
             CheckedValue::Field(t) => vec![t],
             CheckedValue::U8(t) => vec![T::from(t as usize)],
             CheckedValue::U16(t) => vec![T::from(t as usize)],
             CheckedValue::U32(t) => vec![T::from(t as usize)],
+            CheckedValue::U64(t) => vec![encode_wide(t)],
+            // signed integers are encoded as their two's complement bit pattern
+            CheckedValue::I8(t) => vec![T::from(t as u8 as usize)],
+            CheckedValue::I16(t) => vec![T::from(t as u16 as usize)],
+            CheckedValue::I32(t) => vec![T::from(t as u32 as usize)],
             CheckedValue::Boolean(b) => vec![if b { 1.into() } else { 0.into() }],
             CheckedValue::Array(a) => a.into_iter().flat_map(|v| v.encode()).collect(),
             CheckedValue::Struct(s) => s.into_iter().flat_map(|(_, v)| v.encode()).collect(),
+            CheckedValue::Tuple(t) => t.into_iter().flat_map(|v| v.encode()).collect(),
         }
     }
 }
@@ -201,6 +257,19 @@ impl<T: Field> Decode<T> for CheckedValue<T> {
             Type::Uint(UBitwidth::B32) => CheckedValue::U32(
                 u32::from_str_radix(&raw.pop().unwrap().to_dec_string(), 10).unwrap(),
             ),
+            Type::Uint(UBitwidth::B64) => CheckedValue::U64(
+                u64::from_str_radix(&raw.pop().unwrap().to_dec_string(), 10).unwrap(),
+            ),
+            Type::Int(UBitwidth::B8) => CheckedValue::I8(
+                u8::from_str_radix(&raw.pop().unwrap().to_dec_string(), 10).unwrap() as i8,
+            ),
+            Type::Int(UBitwidth::B16) => CheckedValue::I16(
+                u16::from_str_radix(&raw.pop().unwrap().to_dec_string(), 10).unwrap() as i16,
+            ),
+            Type::Int(UBitwidth::B32) => CheckedValue::I32(
+                u32::from_str_radix(&raw.pop().unwrap().to_dec_string(), 10).unwrap() as i32,
+            ),
+            Type::Int(bitwidth) => unreachable!("type i{} is not supported", bitwidth),
             Type::Boolean => {
                 let v = raw.pop().unwrap();
                 CheckedValue::Boolean(if v == 0.into() {
@@ -227,11 +296,23 @@ impl<T: Field> Decode<T> for CheckedValue<T> {
                     })
                     .collect(),
             ),
+            Type::Tuple(tuple_type) => CheckedValue::Tuple(
+                tuple_type
+                    .elements
+                    .into_iter()
+                    .scan(0, |state, ty| {
+                        let new_state = *state + ty.get_primitive_count();
+                        let res = CheckedValue::decode(raw[*state..new_state].to_vec(), ty);
+                        *state = new_state;
+                        Some(res)
+                    })
+                    .collect(),
+            ),
         }
     }
 }
 
-impl<T: From<usize>> Encode<T> for CheckedValues<T> {
+impl<T: From<usize> + Add<Output = T> + Mul<Output = T>> Encode<T> for CheckedValues<T> {
     fn encode(self) -> Vec<T> {
         self.0.into_iter().flat_map(|v| v.encode()).collect()
     }
@@ -259,6 +340,11 @@ impl<T: Field> TryFrom<serde_json::Value> for Value<T> {
     type Error = String;
     fn try_from(v: serde_json::Value) -> Result<Value<T>, Self::Error> {
         match v {
+            // negative values can only be signed integers
+            serde_json::Value::String(ref s) if s.starts_with('-') => s
+                .parse::<i128>()
+                .map(|v| Value::Int(v))
+                .map_err(|_| format!("Expected signed integer value, found {}", s)),
             serde_json::Value::String(s) => T::try_from_dec_str(&s)
                 .map(|v| Value::Field(v))
                 .or_else(|_| match s.len() {
@@ -271,6 +357,9 @@ impl<T: Field> TryFrom<serde_json::Value> for Value<T> {
                     10 => u32::from_str_radix(&s[2..], 16)
                         .map(|v| Value::U32(v))
                         .map_err(|_| format!("Expected u32 value, found {}", s)),
+                    18 => u64::from_str_radix(&s[2..], 16)
+                        .map(|v| Value::U64(v))
+                        .map_err(|_| format!("Expected u64 value, found {}", s)),
                     _ => Err(format!("Cannot parse {} to any type", s)),
                 }),
             serde_json::Value::Bool(b) => Ok(Value::Boolean(b)),
@@ -300,6 +389,10 @@ impl<T: Field> Into<serde_json::Value> for CheckedValue<T> {
             CheckedValue::U8(u) => serde_json::Value::String(format!("{:#04x}", u)),
             CheckedValue::U16(u) => serde_json::Value::String(format!("{:#06x}", u)),
             CheckedValue::U32(u) => serde_json::Value::String(format!("{:#010x}", u)),
+            CheckedValue::U64(u) => serde_json::Value::String(format!("{:#018x}", u)),
+            CheckedValue::I8(i) => serde_json::Value::String(i.to_string()),
+            CheckedValue::I16(i) => serde_json::Value::String(i.to_string()),
+            CheckedValue::I32(i) => serde_json::Value::String(i.to_string()),
             CheckedValue::Boolean(b) => serde_json::Value::Bool(b),
             CheckedValue::Array(a) => {
                 serde_json::Value::Array(a.into_iter().map(|e| e.into()).collect())
@@ -307,6 +400,9 @@ impl<T: Field> Into<serde_json::Value> for CheckedValue<T> {
             CheckedValue::Struct(s) => {
                 serde_json::Value::Object(s.into_iter().map(|(k, v)| (k, v.into())).collect())
             }
+            CheckedValue::Tuple(t) => {
+                serde_json::Value::Array(t.into_iter().map(|e| e.into()).collect())
+            }
         }
     }
 }
@@ -376,6 +472,29 @@ mod tests {
         );
     }
 
+    #[test]
+    fn uints() {
+        let s = r#"["0x01", "0x0002", "0x00000003", "0x0000000000000004"]"#;
+        assert_eq!(
+            parse::<Bn128Field>(s).unwrap(),
+            Values(vec![
+                Value::U8(1),
+                Value::U16(2),
+                Value::U32(3),
+                Value::U64(4)
+            ])
+        );
+    }
+
+    #[test]
+    fn ints() {
+        let s = r#"["-1", "42"]"#;
+        assert_eq!(
+            parse::<Bn128Field>(s).unwrap(),
+            Values(vec![Value::Int(-1), Value::Field(42.into())])
+        );
+    }
+
     #[test]
     fn array() {
         let s = "[[true, false]]";
@@ -430,6 +549,26 @@ mod tests {
             );
         }
 
+        #[test]
+        fn ints() {
+            let s = r#"["-128", "127", "-1"]"#;
+            assert_eq!(
+                parse_strict::<Bn128Field>(s, vec![Type::int(8), Type::int(8), Type::int(32)])
+                    .unwrap(),
+                CheckedValues(vec![
+                    CheckedValue::I8(-128),
+                    CheckedValue::I8(127),
+                    CheckedValue::I32(-1)
+                ])
+            );
+
+            let s = r#"["128"]"#;
+            assert_eq!(
+                parse_strict::<Bn128Field>(s, vec![Type::int(8)]).unwrap_err(),
+                Error::Type("Value `128` doesn't match expected type `i8`".into())
+            );
+        }
+
         #[test]
         fn array() {
             let s = "[[true, false]]";
@@ -504,6 +643,42 @@ mod tests {
                 Error::Type("Value `false` doesn't match expected type `field`".into())
             );
         }
+
+        #[test]
+        fn tuple() {
+            let s = r#"[["42", true]]"#;
+            assert_eq!(
+                parse_strict::<Bn128Field>(
+                    s,
+                    vec![Type::tuple(vec![Type::FieldElement, Type::Boolean])]
+                )
+                .unwrap(),
+                CheckedValues(vec![CheckedValue::Tuple(vec![
+                    CheckedValue::Field(42.into()),
+                    CheckedValue::Boolean(true)
+                ])])
+            );
+
+            let s = r#"[["42"]]"#;
+            assert_eq!(
+                parse_strict::<Bn128Field>(
+                    s,
+                    vec![Type::tuple(vec![Type::FieldElement, Type::Boolean])]
+                )
+                .unwrap_err(),
+                Error::Type("Expected tuple of size 2, found array of size 1".into())
+            );
+
+            let s = r#"[[true, "42"]]"#;
+            assert_eq!(
+                parse_strict::<Bn128Field>(
+                    s,
+                    vec![Type::tuple(vec![Type::FieldElement, Type::Boolean])]
+                )
+                .unwrap_err(),
+                Error::Type("Value `true` doesn't match expected type `field`".into())
+            );
+        }
     }
 
     mod encode {
@@ -548,5 +723,66 @@ mod tests {
             )]);
             assert_eq!(v.encode(), vec![42]);
         }
+
+        #[test]
+        fn u64s() {
+            let v = CheckedValues::<usize>(vec![CheckedValue::U64(1), CheckedValue::U64(2)]);
+            assert_eq!(v.encode(), vec![1, 2]);
+
+            let v = CheckedValues::<Bn128Field>(vec![
+                CheckedValue::U64(1),
+                CheckedValue::U64(u64::MAX),
+            ]);
+            assert_eq!(
+                v.encode(),
+                vec![Bn128Field::from(1), Bn128Field::from(u64::MAX as u128)]
+            );
+        }
+
+        #[test]
+        fn ints() {
+            let v = CheckedValues::<Bn128Field>(vec![CheckedValue::I8(-1), CheckedValue::I16(2)]);
+            assert_eq!(v.encode(), vec![Bn128Field::from(255), Bn128Field::from(2)]);
+        }
+
+        #[test]
+        fn tuple() {
+            let v: CheckedValues<Bn128Field> = CheckedValues(vec![CheckedValue::Tuple(vec![
+                CheckedValue::Field(Bn128Field::from(42)),
+                CheckedValue::Boolean(true),
+            ])]);
+            assert_eq!(v.encode(), vec![Bn128Field::from(42), Bn128Field::from(1)]);
+        }
+    }
+
+    mod decode {
+        use super::*;
+
+        #[test]
+        fn tuple() {
+            let v = CheckedValues::decode(
+                vec![
+                    Bn128Field::from(42),
+                    Bn128Field::from(1),
+                    Bn128Field::from(3),
+                ],
+                vec![
+                    Type::tuple(vec![Type::FieldElement, Type::Boolean]),
+                    Type::uint(8),
+                ],
+            );
+            assert_eq!(
+                v,
+                CheckedValues(vec![
+                    CheckedValue::Tuple(vec![
+                        CheckedValue::Field(Bn128Field::from(42)),
+                        CheckedValue::Boolean(true)
+                    ]),
+                    CheckedValue::U8(3)
+                ])
+            );
+            let json: serde_json::Value = v.into();
+            assert_eq!(json.to_string(), r#"[["42",true],"0x03"]"#);
+        }
     }
 }
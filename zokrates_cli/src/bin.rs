@@ -19,11 +19,18 @@ use std::io::{stdin, BufReader, BufWriter, Read, Write};
 use std::path::{Path, PathBuf};
 use std::string::String;
 use zokrates_abi::Encode;
-use zokrates_core::compile::{check, compile, CompilationArtifacts, CompileConfig, CompileError};
+use zokrates_core::cache::Cache;
+use zokrates_core::compile::{
+    check, compile, compile_entries, CompilationArtifacts, CompileConfig, CompileError,
+    CompileErrors, CompileWarnings,
+};
 use zokrates_core::ir::{self, ProgEnum};
+use zokrates_core::proof_system::bellman::gm17::GM17;
 use zokrates_core::proof_system::bellman::groth16::G16;
+use zokrates_core::proof_system::bellman::mpc::{self, MPCParameters};
+use zokrates_core::proof_system::bellman::plonk::PLONK;
 #[cfg(feature = "libsnark")]
-use zokrates_core::proof_system::libsnark::gm17::GM17;
+use zokrates_core::proof_system::libsnark::gm17::GM17 as LibsnarkGM17;
 #[cfg(feature = "libsnark")]
 use zokrates_core::proof_system::libsnark::pghr13::PGHR13;
 use zokrates_core::proof_system::*;
@@ -67,7 +74,7 @@ fn cli_generate_proof<T: Field, P: ProofSystem<T>>(
         .read_to_end(&mut pk)
         .map_err(|why| format!("Couldn't read {}: {}", pk_path.display(), why))?;
 
-    let proof = P::generate_proof(program, witness, pk);
+    let proof = P::generate_proof(program, witness, pk, randomness(sub_matches))?;
     let mut proof_file = File::create(proof_path).unwrap();
 
     let proof = serde_json::to_string_pretty(&proof).unwrap();
@@ -124,13 +131,83 @@ fn cli_setup<T: Field, P: ProofSystem<T>>(
         println!("{}", program);
     }
 
+    // run setup phase
+    let keypair = P::setup(program, randomness(sub_matches))?;
+
+    write_keypair::<T, P>(keypair, sub_matches)?;
+
+    println!("Setup completed.");
+
+    Ok(())
+}
+
+/// A PLONK setup, whose keys are derived from a universal setup when one is given
+fn cli_plonk_setup<T: Field>(program: ir::Prog<T>, sub_matches: &ArgMatches) -> Result<(), String> {
+    let srs_path = match sub_matches.value_of("universal-setup-path") {
+        Some(path) => Path::new(path),
+        None => return cli_setup::<T, PLONK>(program, sub_matches),
+    };
+
+    println!("Performing setup from {}...", srs_path.display());
+
+    if !sub_matches.is_present("light") {
+        println!("{}", program);
+    }
+
+    let mut srs = Vec::new();
+    File::open(srs_path)
+        .and_then(|mut file| file.read_to_end(&mut srs))
+        .map_err(|why| format!("Couldn't read {}: {}", srs_path.display(), why))?;
+
+    let keypair = PLONK::setup_with_srs(program, &srs)?;
+
+    write_keypair::<T, PLONK>(keypair, sub_matches)?;
+
+    println!("Setup completed.");
+
+    Ok(())
+}
+
+fn cli_universal_setup<T: Field>(sub_matches: &ArgMatches) -> Result<(), String> {
+    let size = sub_matches.value_of("size").unwrap();
+    let exponent: u32 = size.parse().map_err(|_| format!("Invalid size {}", size))?;
+
+    println!(
+        "Performing universal setup for up to 2^{} gates...",
+        exponent
+    );
+
+    let srs = PLONK::universal_setup::<T>(exponent, randomness(sub_matches));
+
+    let output_path = Path::new(sub_matches.value_of("output").unwrap());
+    File::create(output_path)
+        .and_then(|mut file| file.write_all(&srs))
+        .map_err(|why| format!("Couldn't write to {}: {}", output_path.display(), why))?;
+
+    println!("Universal setup written to '{}'", output_path.display());
+
+    Ok(())
+}
+
+/// The randomness of a setup or a proof, which is only seeded for reproducible tests
+fn randomness<'a>(sub_matches: &'a ArgMatches) -> Randomness<'a> {
+    match sub_matches.value_of("seed") {
+        Some(seed) => {
+            println!("WARNING: You are using a seed, so anyone who knows it can forge proofs. Only use this for testing.");
+            Randomness::InsecureSeed(seed.as_bytes())
+        }
+        None => Randomness::System,
+    }
+}
+
+fn write_keypair<T: Field, P: ProofSystem<T>>(
+    keypair: SetupKeypair<P::VerificationKey>,
+    sub_matches: &ArgMatches,
+) -> Result<(), String> {
     // get paths for proving and verification keys
     let pk_path = Path::new(sub_matches.value_of("proving-key-path").unwrap());
     let vk_path = Path::new(sub_matches.value_of("verification-key-path").unwrap());
 
-    // run setup phase
-    let keypair = P::setup(program);
-
     // write verification key
     let mut vk_file = File::create(vk_path)
         .map_err(|why| format!("couldn't create {}: {}", vk_path.display(), why))?;
@@ -149,8 +226,6 @@ fn cli_setup<T: Field, P: ProofSystem<T>>(
         .write(keypair.pk.as_ref())
         .map_err(|why| format!("couldn't write to {}: {}", pk_path.display(), why))?;
 
-    println!("Setup completed.");
-
     Ok(())
 }
 
@@ -272,10 +347,10 @@ fn cli_compile<T: Field>(sub_matches: &ArgMatches) -> Result<(), String> {
 
     let abi_spec_path = Path::new(sub_matches.value_of("abi_spec").unwrap());
 
-    let hr_output_path = bin_output_path.to_path_buf().with_extension("ztf");
-
     let is_release = sub_matches.occurrences_of("release") > 0;
 
+    let json_errors = sub_matches.value_of("error-format") == Some("json");
+
     let file = File::open(path.clone())
         .map_err(|why| format!("Couldn't open input file {}: {}", path.display(), why))?;
 
@@ -294,11 +369,27 @@ fn cli_compile<T: Field>(sub_matches: &ArgMatches) -> Result<(), String> {
         )
     };
 
-    let compilation_config = CompileConfig::default().with_is_release(is_release);
+    let compilation_config = CompileConfig::default()
+        .with_is_release(is_release)
+        .with_deny_warnings(sub_matches.occurrences_of("deny-warnings") > 0);
+
+    let compilation_config = match sub_matches.value_of("cache-dir") {
+        Some(dir) => compilation_config.with_cache(Cache::new(dir)),
+        None => compilation_config,
+    };
+
+    let compilation_config = match sub_matches.values_of("entry") {
+        Some(entries) => compilation_config.with_entries(entries.map(String::from).collect()),
+        None => compilation_config,
+    };
 
     let resolver = FileSystemResolver::new();
-    let artifacts: CompilationArtifacts<T> =
-        compile(source, path, Some(&resolver), &compilation_config).map_err(|e| {
+    let artifacts: Vec<CompilationArtifacts<T>> =
+        compile_entries(source, path, Some(&resolver), &compilation_config).map_err(|e| {
+            if json_errors {
+                emit_diagnostics(&e);
+                return "Compilation failed".to_string();
+            }
             format!(
                 "Compilation failed:\n\n{}",
                 e.0.iter()
@@ -308,55 +399,120 @@ fn cli_compile<T: Field>(sub_matches: &ArgMatches) -> Result<(), String> {
             )
         })?;
 
-    let program_flattened = artifacts.prog();
+    // the warnings are those of the whole program, so they are the same for all entry points
+    emit_warnings(artifacts[0].warnings(), json_errors);
+
+    let entries = compilation_config.entries();
 
-    // number of constraints the flattened program will translate to.
-    let num_constraints = program_flattened.constraint_count();
+    for (entry, artifacts) in entries.iter().zip(&artifacts) {
+        // when compiling several entry points, suffix the outputs with the name of their entry point
+        let (bin_output_path, abi_spec_path) = match entries.len() {
+            1 => (bin_output_path.to_path_buf(), abi_spec_path.to_path_buf()),
+            _ => (
+                entry_path(bin_output_path, entry),
+                entry_path(abi_spec_path, entry),
+            ),
+        };
 
-    // serialize flattened program and write to binary file
-    let bin_output_file = File::create(&bin_output_path)
-        .map_err(|why| format!("Couldn't create {}: {}", bin_output_path.display(), why))?;
+        let hr_output_path = bin_output_path.with_extension("ztf");
 
-    let mut writer = BufWriter::new(bin_output_file);
+        let program_flattened = artifacts.prog();
 
-    program_flattened.serialize(&mut writer);
+        // number of constraints the flattened program will translate to.
+        let num_constraints = program_flattened.constraint_count();
 
-    // serialize ABI spec and write to JSON file
-    let abi_spec_file = File::create(&abi_spec_path)
-        .map_err(|why| format!("Couldn't create {}: {}", abi_spec_path.display(), why))?;
+        // serialize flattened program and write to binary file
+        let bin_output_file = File::create(&bin_output_path)
+            .map_err(|why| format!("Couldn't create {}: {}", bin_output_path.display(), why))?;
 
-    let abi = artifacts.abi();
+        let mut writer = BufWriter::new(bin_output_file);
 
-    let mut writer = BufWriter::new(abi_spec_file);
+        program_flattened.serialize(&mut writer);
 
-    to_writer_pretty(&mut writer, &abi).map_err(|_| "Unable to write data to file.".to_string())?;
+        // serialize ABI spec and write to JSON file
+        let abi_spec_file = File::create(&abi_spec_path)
+            .map_err(|why| format!("Couldn't create {}: {}", abi_spec_path.display(), why))?;
 
-    if !light {
-        // write human-readable output file
-        let hr_output_file = File::create(&hr_output_path)
-            .map_err(|why| format!("Couldn't create {}: {}", hr_output_path.display(), why))?;
+        let abi = artifacts.abi();
 
-        let mut hrofb = BufWriter::new(hr_output_file);
-        write!(&mut hrofb, "{}\n", program_flattened)
+        let mut writer = BufWriter::new(abi_spec_file);
+
+        to_writer_pretty(&mut writer, &abi)
             .map_err(|_| "Unable to write data to file.".to_string())?;
-        hrofb
-            .flush()
-            .map_err(|_| "Unable to flush buffer.".to_string())?;
+
+        if !light {
+            // write human-readable output file
+            let hr_output_file = File::create(&hr_output_path)
+                .map_err(|why| format!("Couldn't create {}: {}", hr_output_path.display(), why))?;
+
+            let mut hrofb = BufWriter::new(hr_output_file);
+            write!(&mut hrofb, "{}\n", program_flattened)
+                .map_err(|_| "Unable to write data to file.".to_string())?;
+            hrofb
+                .flush()
+                .map_err(|_| "Unable to flush buffer.".to_string())?;
+        }
+
+        if entries.len() > 1 {
+            println!("Entry point `{}`:", entry);
+        }
+
+        if !light {
+            // debugging output
+            println!("Compiled program:\n{}", program_flattened);
+        }
+
+        println!("Compiled code written to '{}'", bin_output_path.display());
+
+        if !light {
+            println!("Human readable code to '{}'", hr_output_path.display());
+        }
+
+        println!("Number of constraints: {}", num_constraints);
     }
 
-    if !light {
-        // debugging output
-        println!("Compiled program:\n{}", program_flattened);
+    Ok(())
+}
+
+/// The path of an output of the entry point `entry`, for example `out_verify` for `out` and `abi_verify.json` for
+/// `abi.json`
+fn entry_path(path: &Path, entry: &str) -> PathBuf {
+    let mut name = path.file_stem().unwrap_or_default().to_os_string();
+    name.push("_");
+    name.push(entry);
+    if let Some(extension) = path.extension() {
+        name.push(".");
+        name.push(extension);
     }
+    path.with_file_name(name)
+}
 
-    println!("Compiled code written to '{}'", bin_output_path.display());
+// print one JSON object per diagnostic on stderr, for tools to consume
+fn emit_diagnostics(errors: &CompileErrors) {
+    for diagnostic in errors.diagnostics() {
+        eprintln!("{}", serde_json::to_string(&diagnostic).unwrap());
+    }
+}
 
-    if !light {
-        println!("Human readable code to '{}'", hr_output_path.display());
+fn emit_warnings(warnings: &CompileWarnings, json: bool) {
+    if json {
+        for diagnostic in warnings.diagnostics() {
+            eprintln!("{}", serde_json::to_string(&diagnostic).unwrap());
+        }
+        return;
     }
 
-    println!("Number of constraints: {}", num_constraints);
-    Ok(())
+    for w in &warnings.0 {
+        // the file of a warning may not resolve on disk, for example if it was imported with a custom resolver
+        let file = w.file().canonicalize().unwrap_or_else(|_| w.file().clone());
+        println!(
+            "Warning: {}:{}\n",
+            file.strip_prefix(std::env::current_dir().unwrap())
+                .unwrap_or(file.as_path())
+                .display(),
+            w.value()
+        );
+    }
 }
 
 fn cli_check<T: Field>(sub_matches: &ArgMatches) -> Result<(), String> {
@@ -381,8 +537,22 @@ fn cli_check<T: Field>(sub_matches: &ArgMatches) -> Result<(), String> {
         )
     };
 
+    let json_errors = sub_matches.value_of("error-format") == Some("json");
+
+    let config = CompileConfig::default()
+        .with_deny_warnings(sub_matches.occurrences_of("deny-warnings") > 0);
+
+    let config = match sub_matches.values_of("entry") {
+        Some(entries) => config.with_entries(entries.map(String::from).collect()),
+        None => config,
+    };
+
     let resolver = FileSystemResolver::new();
-    let _ = check::<T, _>(source, path, Some(&resolver)).map_err(|e| {
+    let warnings = check::<T, _>(source, path, Some(&resolver), &config).map_err(|e| {
+        if json_errors {
+            emit_diagnostics(&e);
+            return "Check failed".to_string();
+        }
         format!(
             "Check failed:\n\n{}",
             e.0.iter()
@@ -392,11 +562,113 @@ fn cli_check<T: Field>(sub_matches: &ArgMatches) -> Result<(), String> {
         )
     })?;
 
+    emit_warnings(&warnings, json_errors);
+
     println!("Program checked, no errors found.");
 
     Ok(())
 }
 
+fn cli_fmt(sub_matches: &ArgMatches) -> Result<(), String> {
+    let check = sub_matches.occurrences_of("check") > 0;
+
+    let mut unformatted = vec![];
+
+    for input in sub_matches.values_of("input").unwrap() {
+        let path = Path::new(input);
+
+        let file = File::open(path)
+            .map_err(|why| format!("Couldn't open input file {}: {}", path.display(), why))?;
+
+        let mut reader = BufReader::new(file);
+        let mut source = String::new();
+        reader.read_to_string(&mut source).unwrap();
+
+        let formatted = zokrates_pest_ast::format(&source)
+            .map_err(|e| format!("Formatting failed:\n\n{}:{}", path.display(), e))?;
+
+        if formatted == source {
+            continue;
+        }
+
+        if check {
+            unformatted.push(path.display().to_string());
+        } else {
+            let mut file = File::create(path)
+                .map_err(|why| format!("Couldn't create {}: {}", path.display(), why))?;
+            file.write_all(formatted.as_bytes())
+                .map_err(|_| "Unable to write data to file.".to_string())?;
+            println!("Formatted {}", path.display());
+        }
+    }
+
+    match unformatted.len() {
+        0 => Ok(()),
+        _ => Err(format!(
+            "The following files are not formatted:\n\n{}",
+            unformatted.join("\n")
+        )),
+    }
+}
+
+fn cli_profile<T: Field>(sub_matches: &ArgMatches) -> Result<(), String> {
+    println!("Profiling {}\n", sub_matches.value_of("input").unwrap());
+    let path = PathBuf::from(sub_matches.value_of("input").unwrap());
+
+    let is_release = sub_matches.occurrences_of("release") > 0;
+
+    let file = File::open(path.clone())
+        .map_err(|why| format!("Couldn't open input file {}: {}", path.display(), why))?;
+
+    let mut reader = BufReader::new(file);
+    let mut source = String::new();
+    reader.read_to_string(&mut source).unwrap();
+
+    let fmt_error = |e: &CompileError| {
+        let file = e.file().canonicalize().unwrap();
+        format!(
+            "{}:{}",
+            file.strip_prefix(std::env::current_dir().unwrap())
+                .unwrap_or(file.as_path())
+                .display(),
+            e.value()
+        )
+    };
+
+    let compilation_config = CompileConfig::default()
+        .with_is_release(is_release)
+        .with_profile(true);
+
+    let resolver = FileSystemResolver::new();
+    let artifacts: CompilationArtifacts<T> =
+        compile(source, path, Some(&resolver), &compilation_config).map_err(|e| {
+            format!(
+                "Compilation failed:\n\n{}",
+                e.0.iter()
+                    .map(|e| fmt_error(e))
+                    .collect::<Vec<_>>()
+                    .join("\n\n")
+            )
+        })?;
+
+    let profile = artifacts.prog().profile();
+
+    println!("{}", profile);
+
+    if let Some(folded_path) = sub_matches.value_of("folded") {
+        let folded_path = Path::new(folded_path);
+        let mut folded_file = File::create(&folded_path)
+            .map_err(|why| format!("Couldn't create {}: {}", folded_path.display(), why))?;
+        folded_file
+            .write_all(profile.folded().as_bytes())
+            .map_err(|_| "Unable to write data to file.".to_string())?;
+
+        println!("\nFolded stacks written to '{}'", folded_path.display());
+    }
+
+    Ok(())
+}
+
 fn cli_verify<T: Field, P: ProofSystem<T>>(sub_matches: &ArgMatches) -> Result<(), String> {
     let vk_path = Path::new(sub_matches.value_of("verification-key-path").unwrap());
     let vk_file = File::open(&vk_path)
@@ -406,18 +678,29 @@ fn cli_verify<T: Field, P: ProofSystem<T>>(sub_matches: &ArgMatches) -> Result<(
     let vk = serde_json::from_reader(vk_reader)
         .map_err(|why| format!("Couldn't deserialize verification key: {}", why))?;
 
-    let proof_path = Path::new(sub_matches.value_of("proof-path").unwrap());
-    let proof_file = File::open(&proof_path)
-        .map_err(|why| format!("Couldn't open {}: {}", proof_path.display(), why))?;
+    let result = match sub_matches.values_of("batch") {
+        Some(proof_paths) => {
+            let proofs = proof_paths
+                .map(|path| read_proof::<T, P>(Path::new(path)))
+                .collect::<Result<Vec<_>, _>>()?;
+
+            println!(
+                "Performing batch verification of {} proofs...",
+                proofs.len()
+            );
+            P::verify_batch(vk, proofs)
+        }
+        None => {
+            let proof = read_proof::<T, P>(Path::new(sub_matches.value_of("proof-path").unwrap()))?;
 
-    let proof_reader = BufReader::new(proof_file);
-    let proof = serde_json::from_reader(proof_reader)
-        .map_err(|why| format!("Couldn't deserialize proof: {}", why))?;
+            println!("Performing verification...");
+            P::verify(vk, proof)
+        }
+    };
 
-    println!("Performing verification...");
     println!(
         "The verification result is: {}",
-        match P::verify(vk, proof) {
+        match result {
             true => "PASS",
             false => "FAIL",
         }
@@ -426,6 +709,131 @@ fn cli_verify<T: Field, P: ProofSystem<T>>(sub_matches: &ArgMatches) -> Result<(
     Ok(())
 }
 
+fn read_proof<T: Field, P: ProofSystem<T>>(
+    proof_path: &Path,
+) -> Result<Proof<P::ProofPoints>, String> {
+    let proof_file = File::open(&proof_path)
+        .map_err(|why| format!("Couldn't open {}: {}", proof_path.display(), why))?;
+
+    let proof_reader = BufReader::new(proof_file);
+    serde_json::from_reader(proof_reader).map_err(|why| {
+        format!(
+            "Couldn't deserialize proof {}: {}",
+            proof_path.display(),
+            why
+        )
+    })
+}
+
+fn cli_mpc_init<T: Field>(program: ir::Prog<T>, sub_matches: &ArgMatches) -> Result<(), String> {
+    let radix_path = Path::new(sub_matches.value_of("radix-path").unwrap());
+    let radix_file = File::open(&radix_path)
+        .map_err(|why| format!("Couldn't open {}: {}", radix_path.display(), why))?;
+
+    println!("Initializing MPC...");
+
+    let params = MPCParameters::new(program, BufReader::new(radix_file))?;
+
+    write_mpc_parameters(&params, sub_matches)?;
+
+    println!("Parameters initialized.");
+
+    Ok(())
+}
+
+fn cli_mpc_contribute<T: Field>(sub_matches: &ArgMatches) -> Result<(), String> {
+    let mut params = read_mpc_parameters::<T>(sub_matches)?;
+
+    println!("Contributing...");
+
+    let entropy = sub_matches.value_of("entropy").unwrap();
+    let hash = params.contribute(&mut mpc::rng(entropy.as_bytes())?);
+
+    write_mpc_parameters(&params, sub_matches)?;
+
+    println!("The hash of your contribution is: {}", to_hex(&hash));
+
+    Ok(())
+}
+
+fn cli_mpc_verify<T: Field>(program: ir::Prog<T>, sub_matches: &ArgMatches) -> Result<(), String> {
+    let params = read_mpc_parameters::<T>(sub_matches)?;
+
+    let radix_path = Path::new(sub_matches.value_of("radix-path").unwrap());
+    let radix_file = File::open(&radix_path)
+        .map_err(|why| format!("Couldn't open {}: {}", radix_path.display(), why))?;
+
+    println!("Verifying contributions...");
+
+    let hashes = params.verify(program, BufReader::new(radix_file))?;
+
+    for (i, hash) in hashes.iter().enumerate() {
+        println!("Contribution {}: {}", i + 1, to_hex(hash));
+    }
+
+    println!("Contributions verified.");
+
+    Ok(())
+}
+
+fn cli_mpc_export<T: Field>(sub_matches: &ArgMatches) -> Result<(), String> {
+    let params = read_mpc_parameters::<T>(sub_matches)?;
+
+    println!("Exporting keys...");
+
+    write_keypair::<T, G16>(params.keypair()?, sub_matches)?;
+
+    println!("Keys exported.");
+
+    Ok(())
+}
+
+/// The curve of MPC parameters, which is written as the first four bytes of the file
+fn mpc_curve(sub_matches: &ArgMatches) -> Result<Curve, String> {
+    let path = Path::new(sub_matches.value_of("input").unwrap());
+    let mut id = [0; 4];
+    File::open(&path)
+        .and_then(|mut file| file.read_exact(&mut id))
+        .map_err(|why| format!("Couldn't read {}: {}", path.display(), why))?;
+
+    match id {
+        id if id == Bn128Field::id() => Ok(Curve::Bn128),
+        id if id == Bls12Field::id() => Ok(Curve::Bls12),
+        _ => Err(format!("Unknown curve in {}", path.display())),
+    }
+}
+
+fn read_mpc_parameters<T: Field>(sub_matches: &ArgMatches) -> Result<MPCParameters<T>, String> {
+    let path = Path::new(sub_matches.value_of("input").unwrap());
+    let file =
+        File::open(&path).map_err(|why| format!("Couldn't open {}: {}", path.display(), why))?;
+
+    MPCParameters::read(BufReader::new(file), true).map_err(|why| {
+        format!(
+            "Couldn't read MPC parameters from {}: {}",
+            path.display(),
+            why
+        )
+    })
+}
+
+fn write_mpc_parameters<T: Field>(
+    params: &MPCParameters<T>,
+    sub_matches: &ArgMatches,
+) -> Result<(), String> {
+    let path = Path::new(sub_matches.value_of("output").unwrap());
+    let file = File::create(&path)
+        .map_err(|why| format!("couldn't create {}: {}", path.display(), why))?;
+
+    params
+        .write(BufWriter::new(file))
+        .map_err(|why| format!("couldn't write to {}: {}", path.display(), why))
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
 fn cli() -> Result<(), String> {
     const FLATTENED_CODE_DEFAULT_PATH: &str = "out";
     const ABI_SPEC_DEFAULT_PATH: &str = "abi.json";
@@ -434,6 +842,8 @@ fn cli() -> Result<(), String> {
     const VERIFICATION_CONTRACT_DEFAULT_PATH: &str = "verifier.sol";
     const WITNESS_DEFAULT_PATH: &str = "witness";
     const JSON_PROOF_PATH: &str = "proof.json";
+    const MPC_PARAMETERS_DEFAULT_PATH: &str = "mpc.params";
+    const UNIVERSAL_SETUP_DEFAULT_PATH: &str = "universal_setup.dat";
     let default_curve = env::var("ZOKRATES_CURVE").unwrap_or(constants::BN128.into());
     let default_backend = env::var("ZOKRATES_BACKEND").unwrap_or(constants::BELLMAN.into());
     let default_scheme = env::var("ZOKRATES_PROVING_SCHEME").unwrap_or(constants::G16.into());
@@ -486,6 +896,31 @@ fn cli() -> Result<(), String> {
             .long("release")
             .help("Apply release optimisations to minimise constraint count. This increases compilation time.")
             .required(false)
+        ).arg(Arg::with_name("error-format")
+            .long("error-format")
+            .help("Format of the errors. With `json`, each error is printed on stderr as a JSON object on its own line")
+            .takes_value(true)
+            .required(false)
+            .possible_values(&["human", "json"])
+            .default_value("human")
+        ).arg(Arg::with_name("deny-warnings")
+            .long("deny-warnings")
+            .help("Fail if the program has warnings, such as unused variables")
+            .required(false)
+        ).arg(Arg::with_name("cache-dir")
+            .long("cache-dir")
+            .help("Directory in which checked modules are cached, so that imported modules whose sources did not change are not parsed and checked again")
+            .value_name("DIR")
+            .takes_value(true)
+            .required(false)
+        ).arg(Arg::with_name("entry")
+            .long("entry")
+            .help("Function of the main module to compile instead of `main`. Repeat to compile several circuits, whose outputs are suffixed with the name of their entry point")
+            .value_name("FUNCTION")
+            .takes_value(true)
+            .multiple(true)
+            .number_of_values(1)
+            .required(false)
         )
      )
     .subcommand(SubCommand::with_name("check")
@@ -505,6 +940,71 @@ fn cli() -> Result<(), String> {
             .required(false)
             .possible_values(CURVES)
             .default_value(&default_curve)
+        ).arg(Arg::with_name("error-format")
+            .long("error-format")
+            .help("Format of the errors. With `json`, each error is printed on stderr as a JSON object on its own line")
+            .takes_value(true)
+            .required(false)
+            .possible_values(&["human", "json"])
+            .default_value("human")
+        ).arg(Arg::with_name("deny-warnings")
+            .long("deny-warnings")
+            .help("Fail if the program has warnings, such as unused variables")
+            .required(false)
+        ).arg(Arg::with_name("entry")
+            .long("entry")
+            .help("Function of the main module to check instead of `main`. Repeat to check several entry points")
+            .value_name("FUNCTION")
+            .takes_value(true)
+            .multiple(true)
+            .number_of_values(1)
+            .required(false)
+        )
+     )
+    .subcommand(SubCommand::with_name("profile")
+        .about("Breaks down the constraints and directives of a program by function and by source line")
+        .arg(Arg::with_name("input")
+            .short("i")
+            .long("input")
+            .help("Path of the source code")
+            .value_name("FILE")
+            .takes_value(true)
+            .required(true)
+        ).arg(Arg::with_name("folded")
+            .long("folded")
+            .help("Path of a file to write the constraint counts to, in the folded stack format used by flamegraph tools")
+            .value_name("FILE")
+            .takes_value(true)
+            .required(false)
+        ).arg(Arg::with_name("curve")
+            .short("c")
+            .long("curve")
+            .help("Curve to be used in the compilation")
+            .takes_value(true)
+            .required(false)
+            .possible_values(CURVES)
+            .default_value(&default_curve)
+        ).arg(Arg::with_name("release")
+            .long("release")
+            .help("Apply release optimisations to minimise constraint count. This increases compilation time.")
+            .required(false)
+        )
+     )
+    .subcommand(SubCommand::with_name("fmt")
+        .about("Formats source files with canonical indentation, spacing and import ordering")
+        .arg(Arg::with_name("input")
+            .short("i")
+            .long("input")
+            .help("Path of the source code. Can be repeated to format several files")
+            .value_name("FILE")
+            .takes_value(true)
+            .multiple(true)
+            .number_of_values(1)
+            .required(true)
+        ).arg(Arg::with_name("check")
+            .long("check")
+            .help("Do not write the files, fail if any of them is not formatted")
+            .required(false)
         )
      )
     .subcommand(SubCommand::with_name("setup")
@@ -553,6 +1053,157 @@ fn cli() -> Result<(), String> {
             .long("light")
             .help("Skip logging the human-readable program and writing it to a file")
             .required(false)
+        ).arg(Arg::with_name("seed")
+            .long("seed")
+            .help("Insecure: derive the randomness from a seed instead of the system, so that the output is reproducible. Only use this for testing")
+            .value_name("STRING")
+            .takes_value(true)
+            .required(false)
+        ).arg(Arg::with_name("universal-setup-path")
+            .short("u")
+            .long("universal-setup-path")
+            .help("Path of a universal setup to derive the keys from, instead of running a setup specific to the program. Only supported by the PLONK scheme")
+            .value_name("FILE")
+            .takes_value(true)
+            .required(false)
+        )
+    )
+    .subcommand(SubCommand::with_name("universal-setup")
+        .about("Performs a universal setup for the PLONK scheme, which can be reused by all programs up to a given size")
+        .arg(Arg::with_name("output")
+            .short("o")
+            .long("output")
+            .help("Path of the universal setup")
+            .value_name("FILE")
+            .takes_value(true)
+            .required(false)
+            .default_value(UNIVERSAL_SETUP_DEFAULT_PATH)
+        ).arg(Arg::with_name("size")
+            .short("n")
+            .long("size")
+            .help("Base-2 logarithm of the maximum number of gates of the programs which can use the setup")
+            .value_name("EXPONENT")
+            .takes_value(true)
+            .required(false)
+            .default_value("16")
+        ).arg(Arg::with_name("seed")
+            .long("seed")
+            .help("Insecure: derive the randomness from a seed instead of the system, so that the output is reproducible. Only use this for testing")
+            .value_name("STRING")
+            .takes_value(true)
+            .required(false)
+        )
+    )
+    .subcommand(SubCommand::with_name("mpc")
+        .about("Runs a multi-party trusted setup ceremony for the G16 scheme")
+        .setting(AppSettings::SubcommandRequiredElseHelp)
+        .subcommand(SubCommand::with_name("init")
+            .about("Initializes the parameters of a program from the output of a powers of tau ceremony")
+            .arg(Arg::with_name("input")
+                .short("i")
+                .long("input")
+                .help("Path of the binary")
+                .value_name("FILE")
+                .takes_value(true)
+                .required(false)
+                .default_value(FLATTENED_CODE_DEFAULT_PATH)
+            ).arg(Arg::with_name("radix-path")
+                .short("r")
+                .long("radix-path")
+                .help("Path of the phase-1 parameters, prepared for the size of the program")
+                .value_name("FILE")
+                .takes_value(true)
+                .required(true)
+            ).arg(Arg::with_name("output")
+                .short("o")
+                .long("output")
+                .help("Path of the initialized parameters")
+                .value_name("FILE")
+                .takes_value(true)
+                .required(false)
+                .default_value(MPC_PARAMETERS_DEFAULT_PATH)
+            )
+        )
+        .subcommand(SubCommand::with_name("contribute")
+            .about("Contributes randomness to the parameters")
+            .arg(Arg::with_name("input")
+                .short("i")
+                .long("input")
+                .help("Path of the parameters to contribute to")
+                .value_name("FILE")
+                .takes_value(true)
+                .required(false)
+                .default_value(MPC_PARAMETERS_DEFAULT_PATH)
+            ).arg(Arg::with_name("output")
+                .short("o")
+                .long("output")
+                .help("Path of the parameters after the contribution")
+                .value_name("FILE")
+                .takes_value(true)
+                .required(true)
+            ).arg(Arg::with_name("entropy")
+                .short("e")
+                .long("entropy")
+                .help("User-provided randomness, mixed with randomness from the system")
+                .value_name("STRING")
+                .takes_value(true)
+                .required(true)
+            )
+        )
+        .subcommand(SubCommand::with_name("verify")
+            .about("Verifies all contributions to the parameters of a program")
+            .arg(Arg::with_name("input")
+                .short("i")
+                .long("input")
+                .help("Path of the parameters")
+                .value_name("FILE")
+                .takes_value(true)
+                .required(false)
+                .default_value(MPC_PARAMETERS_DEFAULT_PATH)
+            ).arg(Arg::with_name("circuit")
+                .short("c")
+                .long("circuit")
+                .help("Path of the binary")
+                .value_name("FILE")
+                .takes_value(true)
+                .required(false)
+                .default_value(FLATTENED_CODE_DEFAULT_PATH)
+            ).arg(Arg::with_name("radix-path")
+                .short("r")
+                .long("radix-path")
+                .help("Path of the phase-1 parameters the ceremony was initialized from")
+                .value_name("FILE")
+                .takes_value(true)
+                .required(true)
+            )
+        )
+        .subcommand(SubCommand::with_name("export")
+            .about("Exports the proving and verification keys from the parameters")
+            .arg(Arg::with_name("input")
+                .short("i")
+                .long("input")
+                .help("Path of the parameters")
+                .value_name("FILE")
+                .takes_value(true)
+                .required(false)
+                .default_value(MPC_PARAMETERS_DEFAULT_PATH)
+            ).arg(Arg::with_name("proving-key-path")
+                .short("p")
+                .long("proving-key-path")
+                .help("Path of the generated proving key file")
+                .value_name("FILE")
+                .takes_value(true)
+                .required(false)
+                .default_value(PROVING_KEY_DEFAULT_PATH)
+            ).arg(Arg::with_name("verification-key-path")
+                .short("v")
+                .long("verification-key-path")
+                .help("Path of the generated verification key file")
+                .value_name("FILE")
+                .takes_value(true)
+                .required(false)
+                .default_value(VERIFICATION_KEY_DEFAULT_PATH)
+            )
         )
     )
     .subcommand(SubCommand::with_name("export-verifier")
@@ -710,6 +1361,12 @@ fn cli() -> Result<(), String> {
             .required(false)
             .possible_values(SCHEMES)
             .default_value(&default_scheme)
+        ).arg(Arg::with_name("seed")
+            .long("seed")
+            .help("Insecure: derive the randomness from a seed instead of the system, so that the output is reproducible. Only use this for testing")
+            .value_name("STRING")
+            .takes_value(true)
+            .required(false)
         )
     )
      .subcommand(SubCommand::with_name("print-proof")
@@ -742,6 +1399,13 @@ fn cli() -> Result<(), String> {
             .takes_value(true)
             .required(false)
             .default_value(JSON_PROOF_PATH)
+        ).arg(Arg::with_name("batch")
+            .long("batch")
+            .help("Paths of JSON proof files to verify at once against the same verification key, instead of the proof at `proof-path`")
+            .value_name("FILE")
+            .takes_value(true)
+            .multiple(true)
+            .required(false)
         ).arg(Arg::with_name("verification-key-path")
             .short("v")
             .long("verification-key-path")
@@ -761,7 +1425,7 @@ fn cli() -> Result<(), String> {
         ).arg(Arg::with_name("proving-scheme")
             .short("s")
             .long("proving-scheme")
-            .help("Proving scheme to use in the setup. Available options are G16 (default), PGHR13 and GM17")
+            .help("Proving scheme to use in the setup. Available options are G16 (default), PLONK, PGHR13 and GM17")
             .value_name("FILE")
             .takes_value(true)
             .required(false)
@@ -795,6 +1459,14 @@ fn cli() -> Result<(), String> {
                 _ => unreachable!(),
             }
         }
+        ("profile", Some(sub_matches)) => {
+            let curve = Curve::try_from(sub_matches.value_of("curve").unwrap())?;
+            match curve {
+                Curve::Bn128 => cli_profile::<Bn128Field>(sub_matches)?,
+                Curve::Bls12 => cli_profile::<Bls12Field>(sub_matches)?,
+            }
+        }
+        ("fmt", Some(sub_matches)) => cli_fmt(sub_matches)?,
         ("compute-witness", Some(sub_matches)) => {
             // read compiled program
             let path = Path::new(sub_matches.value_of("input").unwrap());
@@ -826,14 +1498,30 @@ fn cli() -> Result<(), String> {
                 sub_matches.value_of("proving-scheme").unwrap(),
             ))?;
 
+            if sub_matches.is_present("universal-setup-path")
+                && sub_matches.value_of("proving-scheme") != Some(constants::PLONK)
+            {
+                return Err(String::from(
+                    "A universal setup can only be used with the PLONK scheme",
+                ));
+            }
+
             match dimensions {
                 Dimensions(Backend::Bellman, _, ProvingScheme::G16) => match prog {
                     ProgEnum::Bn128Program(p) => cli_setup::<_, G16>(p, sub_matches),
                     ProgEnum::Bls12Program(p) => cli_setup::<_, G16>(p, sub_matches),
                 },
+                Dimensions(Backend::Bellman, Curve::Bn128, ProvingScheme::PLONK) => match prog {
+                    ProgEnum::Bn128Program(p) => cli_plonk_setup(p, sub_matches),
+                    _ => unreachable!(),
+                },
+                Dimensions(Backend::Bellman, _, ProvingScheme::GM17) => match prog {
+                    ProgEnum::Bn128Program(p) => cli_setup::<_, GM17>(p, sub_matches),
+                    ProgEnum::Bls12Program(p) => cli_setup::<_, GM17>(p, sub_matches),
+                },
                 #[cfg(feature = "libsnark")]
                 Dimensions(Backend::Libsnark, Curve::Bn128, ProvingScheme::GM17) => match prog {
-                    ProgEnum::Bn128Program(p) => cli_setup::<_, GM17>(p, sub_matches),
+                    ProgEnum::Bn128Program(p) => cli_setup::<_, LibsnarkGM17>(p, sub_matches),
                     _ => unreachable!(),
                 },
                 #[cfg(feature = "libsnark")]
@@ -841,10 +1529,41 @@ fn cli() -> Result<(), String> {
                     ProgEnum::Bn128Program(p) => cli_setup::<_, PGHR13>(p, sub_matches),
                     _ => unreachable!(),
                 },
-                #[cfg(feature = "libsnark")]
                 _ => unreachable!(),
             }?
         }
+        ("universal-setup", Some(sub_matches)) => cli_universal_setup::<Bn128Field>(sub_matches)?,
+        ("mpc", Some(sub_matches)) => match sub_matches.subcommand() {
+            ("init", Some(sub_matches)) => {
+                let path = Path::new(sub_matches.value_of("input").unwrap());
+                let file = File::open(&path)
+                    .map_err(|why| format!("Couldn't open {}: {}", path.display(), why))?;
+
+                match ProgEnum::deserialize(&mut BufReader::new(file))? {
+                    ProgEnum::Bn128Program(p) => cli_mpc_init(p, sub_matches)?,
+                    ProgEnum::Bls12Program(p) => cli_mpc_init(p, sub_matches)?,
+                }
+            }
+            ("contribute", Some(sub_matches)) => match mpc_curve(sub_matches)? {
+                Curve::Bn128 => cli_mpc_contribute::<Bn128Field>(sub_matches)?,
+                Curve::Bls12 => cli_mpc_contribute::<Bls12Field>(sub_matches)?,
+            },
+            ("verify", Some(sub_matches)) => {
+                let path = Path::new(sub_matches.value_of("circuit").unwrap());
+                let file = File::open(&path)
+                    .map_err(|why| format!("Couldn't open {}: {}", path.display(), why))?;
+
+                match ProgEnum::deserialize(&mut BufReader::new(file))? {
+                    ProgEnum::Bn128Program(p) => cli_mpc_verify(p, sub_matches)?,
+                    ProgEnum::Bls12Program(p) => cli_mpc_verify(p, sub_matches)?,
+                }
+            }
+            ("export", Some(sub_matches)) => match mpc_curve(sub_matches)? {
+                Curve::Bn128 => cli_mpc_export::<Bn128Field>(sub_matches)?,
+                Curve::Bls12 => cli_mpc_export::<Bls12Field>(sub_matches)?,
+            },
+            _ => unreachable!(),
+        },
         ("export-verifier", Some(sub_matches)) => {
             let dimensions = Dimensions::try_from((
                 sub_matches.value_of("backend").unwrap(),
@@ -859,15 +1578,23 @@ fn cli() -> Result<(), String> {
                 Dimensions(Backend::Bellman, Curve::Bls12, ProvingScheme::G16) => {
                     cli_export_verifier::<Bls12Field, G16>(sub_matches)
                 }
+                Dimensions(Backend::Bellman, Curve::Bn128, ProvingScheme::PLONK) => {
+                    cli_export_verifier::<Bn128Field, PLONK>(sub_matches)
+                }
+                Dimensions(Backend::Bellman, Curve::Bn128, ProvingScheme::GM17) => {
+                    cli_export_verifier::<Bn128Field, GM17>(sub_matches)
+                }
+                Dimensions(Backend::Bellman, Curve::Bls12, ProvingScheme::GM17) => {
+                    cli_export_verifier::<Bls12Field, GM17>(sub_matches)
+                }
                 #[cfg(feature = "libsnark")]
                 Dimensions(Backend::Libsnark, Curve::Bn128, ProvingScheme::GM17) => {
-                    cli_export_verifier::<Bn128Field, GM17>(sub_matches)
+                    cli_export_verifier::<Bn128Field, LibsnarkGM17>(sub_matches)
                 }
                 #[cfg(feature = "libsnark")]
                 Dimensions(Backend::Libsnark, Curve::Bn128, ProvingScheme::PGHR13) => {
                     cli_export_verifier::<Bn128Field, PGHR13>(sub_matches)
                 }
-                #[cfg(feature = "libsnark")]
                 _ => unreachable!(),
             }?
         }
@@ -893,9 +1620,19 @@ fn cli() -> Result<(), String> {
                     ProgEnum::Bn128Program(p) => cli_generate_proof::<_, G16>(p, sub_matches),
                     ProgEnum::Bls12Program(p) => cli_generate_proof::<_, G16>(p, sub_matches),
                 },
+                Dimensions(Backend::Bellman, Curve::Bn128, ProvingScheme::PLONK) => match prog {
+                    ProgEnum::Bn128Program(p) => cli_generate_proof::<_, PLONK>(p, sub_matches),
+                    _ => unreachable!(),
+                },
+                Dimensions(Backend::Bellman, _, ProvingScheme::GM17) => match prog {
+                    ProgEnum::Bn128Program(p) => cli_generate_proof::<_, GM17>(p, sub_matches),
+                    ProgEnum::Bls12Program(p) => cli_generate_proof::<_, GM17>(p, sub_matches),
+                },
                 #[cfg(feature = "libsnark")]
                 Dimensions(Backend::Libsnark, Curve::Bn128, ProvingScheme::GM17) => match prog {
-                    ProgEnum::Bn128Program(p) => cli_generate_proof::<_, GM17>(p, sub_matches),
+                    ProgEnum::Bn128Program(p) => {
+                        cli_generate_proof::<_, LibsnarkGM17>(p, sub_matches)
+                    }
                     _ => unreachable!(),
                 },
                 #[cfg(feature = "libsnark")]
@@ -903,7 +1640,6 @@ fn cli() -> Result<(), String> {
                     ProgEnum::Bn128Program(p) => cli_generate_proof::<_, PGHR13>(p, sub_matches),
                     _ => unreachable!(),
                 },
-                #[cfg(feature = "libsnark")]
                 _ => unreachable!(),
             }?
         }
@@ -957,15 +1693,23 @@ fn cli() -> Result<(), String> {
                 Dimensions(Backend::Bellman, Curve::Bls12, ProvingScheme::G16) => {
                     cli_verify::<Bls12Field, G16>(sub_matches)
                 }
+                Dimensions(Backend::Bellman, Curve::Bn128, ProvingScheme::PLONK) => {
+                    cli_verify::<Bn128Field, PLONK>(sub_matches)
+                }
+                Dimensions(Backend::Bellman, Curve::Bn128, ProvingScheme::GM17) => {
+                    cli_verify::<Bn128Field, GM17>(sub_matches)
+                }
+                Dimensions(Backend::Bellman, Curve::Bls12, ProvingScheme::GM17) => {
+                    cli_verify::<Bls12Field, GM17>(sub_matches)
+                }
                 #[cfg(feature = "libsnark")]
                 Dimensions(Backend::Libsnark, Curve::Bn128, ProvingScheme::GM17) => {
-                    cli_verify::<Bn128Field, GM17>(sub_matches)
+                    cli_verify::<Bn128Field, LibsnarkGM17>(sub_matches)
                 }
                 #[cfg(feature = "libsnark")]
                 Dimensions(Backend::Libsnark, Curve::Bn128, ProvingScheme::PGHR13) => {
                     cli_verify::<Bn128Field, PGHR13>(sub_matches)
                 }
-                #[cfg(feature = "libsnark")]
                 _ => unreachable!(),
             }?
         }
@@ -1008,11 +1752,41 @@ mod tests {
             reader.read_to_string(&mut source).unwrap();
 
             let resolver = FileSystemResolver::new();
-            let _: CompilationArtifacts<Bn128Field> =
+            let artifacts: CompilationArtifacts<Bn128Field> =
                 compile(source, path, Some(&resolver), &CompileConfig::default()).unwrap();
+
+            // the human-readable output can be read back
+            let text = format!("{}", artifacts.prog());
+            assert_eq!(
+                ProgEnum::deserialize(text.as_bytes()).unwrap(),
+                ProgEnum::Bn128Program(artifacts.prog().clone())
+            );
         }
     }
 
+    #[test]
+    fn stdlib_warnings() {
+        // `ecc/proofOfOwnership` imports `ecc/edwardsAdd` without using it, which is not reported
+        let path = PathBuf::from("./examples/imports/import_stdlib.zok");
+
+        let mut source = String::new();
+        File::open(path.clone())
+            .unwrap()
+            .read_to_string(&mut source)
+            .unwrap();
+
+        let resolver = FileSystemResolver::new();
+        let warnings = check::<Bn128Field, _>(
+            source,
+            path,
+            Some(&resolver),
+            &CompileConfig::default().with_deny_warnings(true),
+        )
+        .unwrap();
+
+        assert!(warnings.diagnostics().is_empty());
+    }
+
     #[test]
     fn examples_with_input_success() {
         //these examples should compile and run
@@ -15,7 +15,7 @@ pub enum Backend {
 
 pub enum ProvingScheme {
     G16,
-    #[cfg(feature = "libsnark")]
+    PLONK,
     GM17,
     #[cfg(feature = "libsnark")]
     PGHR13,
@@ -52,7 +52,7 @@ impl TryFrom<&str> for ProvingScheme {
     fn try_from(s: &str) -> Result<Self, Self::Error> {
         match s {
             G16 => Ok(ProvingScheme::G16),
-            #[cfg(feature = "libsnark")]
+            PLONK => Ok(ProvingScheme::PLONK),
             GM17 => Ok(ProvingScheme::GM17),
             #[cfg(feature = "libsnark")]
             PGHR13 => Ok(ProvingScheme::PGHR13),
@@ -78,6 +78,15 @@ impl TryFrom<(&str, &str, &str)> for Dimensions {
             (Backend::Bellman, Curve::Bls12, ProvingScheme::G16) => {
                 Ok(Dimensions(backend, curve, proving_scheme))
             }
+            (Backend::Bellman, Curve::Bn128, ProvingScheme::PLONK) => {
+                Ok(Dimensions(backend, curve, proving_scheme))
+            }
+            (Backend::Bellman, Curve::Bn128, ProvingScheme::GM17) => {
+                Ok(Dimensions(backend, curve, proving_scheme))
+            }
+            (Backend::Bellman, Curve::Bls12, ProvingScheme::GM17) => {
+                Ok(Dimensions(backend, curve, proving_scheme))
+            }
             #[cfg(feature = "libsnark")]
             (Backend::Libsnark, Curve::Bn128, ProvingScheme::GM17) => {
                 Ok(Dimensions(backend, curve, proving_scheme))
@@ -86,7 +95,6 @@ impl TryFrom<(&str, &str, &str)> for Dimensions {
             (Backend::Libsnark, Curve::Bn128, ProvingScheme::PGHR13) => {
                 Ok(Dimensions(backend, curve, proving_scheme))
             }
-            #[cfg(feature = "libsnark")]
             _ => Err(format!(
                 "Unsupported combination of dimensions (backend: {}, curve: {}, proving scheme: {})",
                 s.0, s.1, s.2
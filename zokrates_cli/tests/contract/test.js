@@ -92,6 +92,9 @@ let jsonInterface = JSON.parse(solc.compile(jsonContractSource));
             } else if (format == "pghr13") {
                 return verifyTxPGHR13_ABIV1(proof, account, correct).on('receipt', handleReceipt)
                     .catch(handleError);
+            } else if (format == "plonk") {
+                return verifyTxPLONK_ABIV1(proof, account, correct).on('receipt', handleReceipt)
+                    .catch(handleError);
             }
         } else {
             return verifyTxABIV2(proof, account, correct).on('receipt', handleReceipt)
@@ -135,6 +138,18 @@ let jsonInterface = JSON.parse(solc.compile(jsonContractSource));
         })
     }
 
+    function verifyTxPLONK_ABIV1(proof, account, correct) {
+        // the first nine values are the points of the proof, the last seven the evaluations
+        return contract.methods.verifyTx(
+            proof[0].slice(0, 9),
+            proof[0].slice(9),
+            proof[1]
+        ).send({
+            from: account,
+            gas: 5000000
+        })
+    }
+
     function getProof(correct) {
         let json = JSON.parse(fs.readFileSync(proofPath));
         let inputs = json["inputs"];
@@ -87,6 +87,10 @@ mod integration {
             .join(program_name)
             .join("verifier")
             .with_extension("sol");
+        let universal_setup_path = tmp_base
+            .join(program_name)
+            .join("universal_setup")
+            .with_extension("dat");
 
         // create a tmp folder to store artifacts
         fs::create_dir(test_case_path).unwrap();
@@ -206,17 +210,17 @@ mod integration {
 
         #[cfg(feature = "libsnark")]
         let backends = map! {
-            "bellman" => ["g16"],
-            "libsnark" => ["gm17", "pghr13"]
+            "bellman" => vec!["g16", "plonk", "gm17"],
+            "libsnark" => vec!["gm17", "pghr13"]
         };
 
         #[cfg(not(feature = "libsnark"))]
-        let backends = map! {"bellman" => ["g16"]};
+        let backends = map! {"bellman" => vec!["g16", "plonk", "gm17"]};
 
         for (backend, schemes) in backends {
             for scheme in &schemes {
                 // SETUP
-                assert_cli::Assert::command(&[
+                let mut setup = vec![
                     "../target/release/zokrates",
                     "setup",
                     "-i",
@@ -228,10 +232,26 @@ mod integration {
                     "--backend",
                     backend,
                     "--proving-scheme",
-                    scheme,
-                ])
-                .succeeds()
-                .unwrap();
+                    *scheme,
+                ];
+
+                // UNIVERSAL SETUP
+                if *scheme == "plonk" {
+                    assert_cli::Assert::command(&[
+                        "../target/release/zokrates",
+                        "universal-setup",
+                        "-o",
+                        universal_setup_path.to_str().unwrap(),
+                        "--size",
+                        "10",
+                    ])
+                    .succeeds()
+                    .unwrap();
+
+                    setup.extend(&["-u", universal_setup_path.to_str().unwrap()]);
+                }
+
+                assert_cli::Assert::command(&setup).succeeds().unwrap();
 
                 // EXPORT-VERIFIER
                 assert_cli::Assert::command(&[
@@ -285,6 +305,25 @@ mod integration {
                 .succeeds()
                 .unwrap();
 
+                // CLI BATCH VERIFICATION
+                assert_cli::Assert::command(&[
+                    "../target/release/zokrates",
+                    "verify",
+                    "--backend",
+                    backend,
+                    "--proving-scheme",
+                    scheme,
+                    "-v",
+                    verification_key_path.to_str().unwrap(),
+                    "--batch",
+                    proof_path.to_str().unwrap(),
+                    proof_path.to_str().unwrap(),
+                ])
+                .succeeds()
+                .stdout()
+                .contains("PASS")
+                .unwrap();
+
                 // TEST VERIFIER
                 assert_cli::Assert::command(&[
                     "node",
@@ -5,16 +5,12 @@ use zokrates_pest_ast as pest;
 
 impl<'ast, T: Field> From<pest::File<'ast>> for absy::Module<'ast, T> {
     fn from(prog: pest::File<'ast>) -> absy::Module<T> {
-        absy::Module::with_symbols(
-            prog.structs
-                .into_iter()
-                .map(|t| absy::SymbolDeclarationNode::from(t))
-                .chain(
-                    prog.functions
-                        .into_iter()
-                        .map(|f| absy::SymbolDeclarationNode::from(f)),
-                ),
-        )
+        absy::Module::with_symbols(prog.declarations.into_iter().map(|d| match d {
+            pest::SymbolDeclaration::Constant(c) => absy::SymbolDeclarationNode::from(c),
+            pest::SymbolDeclaration::Type(t) => absy::SymbolDeclarationNode::from(t),
+            pest::SymbolDeclaration::Struct(s) => absy::SymbolDeclarationNode::from(s),
+            pest::SymbolDeclaration::Function(f) => absy::SymbolDeclarationNode::from(f),
+        }))
         .imports(prog.imports.into_iter().map(|i| absy::ImportNode::from(i)))
     }
 }
@@ -44,6 +40,44 @@ impl<'ast> From<pest::ImportDirective<'ast>> for absy::ImportNode<'ast> {
     }
 }
 
+impl<'ast, T: Field> From<pest::ConstantDefinition<'ast>> for absy::SymbolDeclarationNode<'ast, T> {
+    fn from(definition: pest::ConstantDefinition<'ast>) -> absy::SymbolDeclarationNode<'ast, T> {
+        use absy::NodeValue;
+
+        let span = definition.span;
+
+        let id = definition.id.span.as_str();
+
+        let c = absy::ConstantDefinition {
+            ty: absy::UnresolvedTypeNode::from(definition.ty),
+            expression: absy::ExpressionNode::from(definition.expression),
+        }
+        .span(span.clone());
+
+        absy::SymbolDeclaration {
+            id,
+            symbol: absy::Symbol::HereConstant(c),
+        }
+        .span(span)
+    }
+}
+
+impl<'ast, T: Field> From<pest::TypeDefinition<'ast>> for absy::SymbolDeclarationNode<'ast, T> {
+    fn from(definition: pest::TypeDefinition<'ast>) -> absy::SymbolDeclarationNode<'ast, T> {
+        use absy::NodeValue;
+
+        let span = definition.span;
+
+        let id = definition.id.span.as_str();
+
+        absy::SymbolDeclaration {
+            id,
+            symbol: absy::Symbol::HereAlias(absy::UnresolvedTypeNode::from(definition.ty)),
+        }
+        .span(span)
+    }
+}
+
 impl<'ast, T: Field> From<pest::StructDefinition<'ast>> for absy::SymbolDeclarationNode<'ast, T> {
     fn from(definition: pest::StructDefinition<'ast>) -> absy::SymbolDeclarationNode<'ast, T> {
         use absy::NodeValue;
@@ -90,6 +124,14 @@ impl<'ast, T: Field> From<pest::Function<'ast>> for absy::SymbolDeclarationNode<
         let span = function.span;
 
         let signature = absy::UnresolvedSignature::new()
+            .generics(
+                function
+                    .generics
+                    .clone()
+                    .into_iter()
+                    .map(|g| g.value.clone().span(g.span))
+                    .collect(),
+            )
             .inputs(
                 function
                     .parameters
@@ -160,8 +202,11 @@ fn statements_from_statement<'ast, T: Field>(
     match statement {
         pest::Statement::Definition(s) => statements_from_definition(s),
         pest::Statement::Iteration(s) => vec![absy::StatementNode::from(s)],
+        pest::Statement::IfElse(s) => vec![absy::StatementNode::from(s)],
         pest::Statement::Assertion(s) => vec![absy::StatementNode::from(s)],
         pest::Statement::Return(s) => vec![absy::StatementNode::from(s)],
+        pest::Statement::Break(s) => vec![absy::StatementNode::from(s)],
+        pest::Statement::Continue(s) => vec![absy::StatementNode::from(s)],
     }
 }
 
@@ -267,6 +312,24 @@ impl<'ast, T: Field> From<pest::AssertionStatement<'ast>> for absy::StatementNod
     }
 }
 
+impl<'ast, T: Field> From<pest::BreakStatement<'ast>> for absy::StatementNode<'ast, T> {
+    fn from(statement: pest::BreakStatement<'ast>) -> absy::StatementNode<T> {
+        use absy::NodeValue;
+
+        absy::Statement::Break(statement.condition.map(absy::ExpressionNode::from))
+            .span(statement.span)
+    }
+}
+
+impl<'ast, T: Field> From<pest::ContinueStatement<'ast>> for absy::StatementNode<'ast, T> {
+    fn from(statement: pest::ContinueStatement<'ast>) -> absy::StatementNode<T> {
+        use absy::NodeValue;
+
+        absy::Statement::Continue(statement.condition.map(absy::ExpressionNode::from))
+            .span(statement.span)
+    }
+}
+
 impl<'ast, T: Field> From<pest::IterationStatement<'ast>> for absy::StatementNode<'ast, T> {
     fn from(statement: pest::IterationStatement<'ast>) -> absy::StatementNode<T> {
         use absy::NodeValue;
@@ -286,6 +349,29 @@ impl<'ast, T: Field> From<pest::IterationStatement<'ast>> for absy::StatementNod
     }
 }
 
+impl<'ast, T: Field> From<pest::IfElseStatement<'ast>> for absy::StatementNode<'ast, T> {
+    fn from(statement: pest::IfElseStatement<'ast>) -> absy::StatementNode<T> {
+        use absy::NodeValue;
+        let condition = absy::ExpressionNode::from(statement.condition);
+        let consequence = statement
+            .consequence
+            .into_iter()
+            .flat_map(|s| statements_from_statement(s))
+            .collect();
+        let alternative = statement
+            .alternative
+            .map(|b| {
+                b.statements
+                    .into_iter()
+                    .flat_map(|s| statements_from_statement(s))
+                    .collect()
+            })
+            .unwrap_or(vec![]);
+
+        absy::Statement::IfElse(condition, consequence, alternative).span(statement.span)
+    }
+}
+
 impl<'ast, T: Field> From<pest::Expression<'ast>> for absy::ExpressionNode<'ast, T> {
     fn from(expression: pest::Expression<'ast>) -> absy::ExpressionNode<'ast, T> {
         match expression {
@@ -296,6 +382,7 @@ impl<'ast, T: Field> From<pest::Expression<'ast>> for absy::ExpressionNode<'ast,
             pest::Expression::Postfix(e) => absy::ExpressionNode::from(e),
             pest::Expression::InlineArray(e) => absy::ExpressionNode::from(e),
             pest::Expression::InlineStruct(e) => absy::ExpressionNode::from(e),
+            pest::Expression::InlineTuple(e) => absy::ExpressionNode::from(e),
             pest::Expression::ArrayInitializer(e) => absy::ExpressionNode::from(e),
             pest::Expression::Unary(e) => absy::ExpressionNode::from(e),
         }
@@ -322,6 +409,10 @@ impl<'ast, T: Field> From<pest::BinaryExpression<'ast>> for absy::ExpressionNode
                 box absy::ExpressionNode::from(*expression.left),
                 box absy::ExpressionNode::from(*expression.right),
             ),
+            pest::BinaryOperator::Rem => absy::Expression::Rem(
+                box absy::ExpressionNode::from(*expression.left),
+                box absy::ExpressionNode::from(*expression.right),
+            ),
             pest::BinaryOperator::Eq => absy::Expression::Eq(
                 box absy::ExpressionNode::from(*expression.left),
                 box absy::ExpressionNode::from(*expression.right),
@@ -484,6 +575,19 @@ impl<'ast, T: Field> From<pest::InlineStructExpression<'ast>> for absy::Expressi
     }
 }
 
+impl<'ast, T: Field> From<pest::InlineTupleExpression<'ast>> for absy::ExpressionNode<'ast, T> {
+    fn from(t: pest::InlineTupleExpression<'ast>) -> absy::ExpressionNode<'ast, T> {
+        use absy::NodeValue;
+        absy::Expression::InlineTuple(
+            t.elements
+                .into_iter()
+                .map(|e| absy::ExpressionNode::from(e))
+                .collect(),
+        )
+        .span(t.span)
+    }
+}
+
 impl<'ast, T: Field> From<pest::ArrayInitializerExpression<'ast>>
     for absy::ExpressionNode<'ast, T>
 {
@@ -519,6 +623,7 @@ impl<'ast, T: Field> From<pest::PostfixExpression<'ast>> for absy::ExpressionNod
         use absy::NodeValue;
 
         let id_str = expression.id.span.as_str();
+        let id_span = expression.id.span.clone();
         let id = absy::ExpressionNode::from(expression.id);
 
         // pest::PostFixExpression contains an array of "accesses": `a(34)[42]` is represented as `[a, [Call(34), Select(42)]]`, but absy::ExpressionNode
@@ -528,7 +633,7 @@ impl<'ast, T: Field> From<pest::PostfixExpression<'ast>> for absy::ExpressionNod
         expression.accesses.into_iter().fold(id, |acc, a| match a {
             pest::Access::Call(a) => match acc.value {
                 absy::Expression::Identifier(_) => absy::Expression::FunctionCall(
-                    &id_str,
+                    id_str.span(id_span.clone()),
                     a.expressions
                         .into_iter()
                         .map(|e| absy::ExpressionNode::from(e))
@@ -544,6 +649,9 @@ impl<'ast, T: Field> From<pest::PostfixExpression<'ast>> for absy::ExpressionNod
             pest::Access::Member(m) => {
                 absy::Expression::Member(box acc, box m.id.span.as_str()).span(m.span)
             }
+            pest::Access::Element(e) => {
+                absy::Expression::Element(box acc, e.index.span.as_str()).span(e.span)
+            }
         })
     }
 }
@@ -570,6 +678,26 @@ impl<'ast, T: Field> From<pest::ConstantExpression<'ast>> for absy::ExpressionNo
                 u32::from_str_radix(&n.value.trim_start_matches("0x"), 16).unwrap(),
             )
             .span(n.span),
+            pest::ConstantExpression::U64(n) => absy::Expression::U64Constant(
+                u64::from_str_radix(&n.value.trim_start_matches("0x"), 16).unwrap(),
+            )
+            .span(n.span),
+            pest::ConstantExpression::U128(n) => absy::Expression::U128Constant(
+                u128::from_str_radix(&n.value.trim_start_matches("0x"), 16).unwrap(),
+            )
+            .span(n.span),
+            pest::ConstantExpression::I8(n) => {
+                absy::Expression::IntConstant(n.span.as_str().trim_end_matches("i8"), 8)
+                    .span(n.span)
+            }
+            pest::ConstantExpression::I16(n) => {
+                absy::Expression::IntConstant(n.span.as_str().trim_end_matches("i16"), 16)
+                    .span(n.span)
+            }
+            pest::ConstantExpression::I32(n) => {
+                absy::Expression::IntConstant(n.span.as_str().trim_end_matches("i32"), 32)
+                    .span(n.span)
+            }
         }
     }
 }
@@ -604,6 +732,9 @@ impl<'ast, T: Field> From<pest::Assignee<'ast>> for absy::AssigneeNode<'ast, T>
                 pest::AssigneeAccess::Member(m) => {
                     absy::Assignee::Member(box acc, box m.id.span.as_str())
                 }
+                pest::AssigneeAccess::Element(e) => {
+                    absy::Assignee::Element(box acc, e.index.span.as_str())
+                }
             }
             .span(span.clone())
         })
@@ -621,6 +752,11 @@ impl<'ast> From<pest::Type<'ast>> for absy::UnresolvedTypeNode {
                 pest::BasicType::U8(t) => absy::UnresolvedType::Uint(8).span(t.span),
                 pest::BasicType::U16(t) => absy::UnresolvedType::Uint(16).span(t.span),
                 pest::BasicType::U32(t) => absy::UnresolvedType::Uint(32).span(t.span),
+                pest::BasicType::U64(t) => absy::UnresolvedType::Uint(64).span(t.span),
+                pest::BasicType::U128(t) => absy::UnresolvedType::Uint(128).span(t.span),
+                pest::BasicType::I8(t) => absy::UnresolvedType::Int(8).span(t.span),
+                pest::BasicType::I16(t) => absy::UnresolvedType::Int(16).span(t.span),
+                pest::BasicType::I32(t) => absy::UnresolvedType::Int(32).span(t.span),
             },
             pest::Type::Array(t) => {
                 let inner_type = match t.ty {
@@ -632,10 +768,16 @@ impl<'ast> From<pest::Type<'ast>> for absy::UnresolvedTypeNode {
                         pest::BasicType::U8(t) => absy::UnresolvedType::Uint(8).span(t.span),
                         pest::BasicType::U16(t) => absy::UnresolvedType::Uint(16).span(t.span),
                         pest::BasicType::U32(t) => absy::UnresolvedType::Uint(32).span(t.span),
+                        pest::BasicType::U64(t) => absy::UnresolvedType::Uint(64).span(t.span),
+                        pest::BasicType::U128(t) => absy::UnresolvedType::Uint(128).span(t.span),
+                        pest::BasicType::I8(t) => absy::UnresolvedType::Int(8).span(t.span),
+                        pest::BasicType::I16(t) => absy::UnresolvedType::Int(16).span(t.span),
+                        pest::BasicType::I32(t) => absy::UnresolvedType::Int(32).span(t.span),
                     },
                     pest::BasicOrStructType::Struct(t) => {
                         absy::UnresolvedType::User(t.span.as_str().to_string()).span(t.span)
                     }
+                    pest::BasicOrStructType::Tuple(t) => absy::UnresolvedTypeNode::from(t),
                 };
 
                 let span = t.span;
@@ -645,13 +787,16 @@ impl<'ast> From<pest::Type<'ast>> for absy::UnresolvedTypeNode {
                     .map(|s| match s {
                         pest::Expression::Constant(c) => match c {
                             pest::ConstantExpression::DecimalNumber(n) => {
-                                str::parse::<usize>(&n.value).unwrap()
+                                absy::ArraySize::Number(str::parse::<usize>(&n.value).unwrap())
                             }
                             _ => unimplemented!(
                                 "Array size should be a decimal number, found {}",
                                 c.span().as_str()
                             ),
                         },
+                        pest::Expression::Identifier(id) => {
+                            absy::ArraySize::Identifier(id.span.as_str().to_string())
+                        }
                         e => unimplemented!(
                             "Array size should be constant, found {}",
                             e.span().as_str()
@@ -668,10 +813,25 @@ impl<'ast> From<pest::Type<'ast>> for absy::UnresolvedTypeNode {
             pest::Type::Struct(s) => {
                 absy::UnresolvedType::User(s.id.span.as_str().to_string()).span(s.span)
             }
+            pest::Type::Tuple(t) => absy::UnresolvedTypeNode::from(t),
         }
     }
 }
 
+impl<'ast> From<pest::TupleType<'ast>> for absy::UnresolvedTypeNode {
+    fn from(t: pest::TupleType<'ast>) -> absy::UnresolvedTypeNode {
+        use absy::NodeValue;
+
+        absy::UnresolvedType::Tuple(
+            t.elements
+                .into_iter()
+                .map(|ty| absy::UnresolvedTypeNode::from(ty))
+                .collect(),
+        )
+        .span(t.span)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -834,17 +994,20 @@ mod tests {
                 ("bool", absy::UnresolvedType::Boolean),
                 (
                     "field[2]",
-                    absy::UnresolvedType::Array(box absy::UnresolvedType::FieldElement.mock(), 2),
+                    absy::UnresolvedType::Array(
+                        box absy::UnresolvedType::FieldElement.mock(),
+                        absy::ArraySize::Number(2),
+                    ),
                 ),
                 (
                     "field[2][3]",
                     absy::UnresolvedType::Array(
                         box absy::UnresolvedType::Array(
                             box absy::UnresolvedType::FieldElement.mock(),
-                            3,
+                            absy::ArraySize::Number(3),
                         )
                         .mock(),
-                        2,
+                        absy::ArraySize::Number(2),
                     ),
                 ),
                 (
@@ -852,10 +1015,17 @@ mod tests {
                     absy::UnresolvedType::Array(
                         box absy::UnresolvedType::Array(
                             box absy::UnresolvedType::Boolean.mock(),
-                            3,
+                            absy::ArraySize::Number(3),
                         )
                         .mock(),
-                        2,
+                        absy::ArraySize::Number(2),
+                    ),
+                ),
+                (
+                    "u32[N]",
+                    absy::UnresolvedType::Array(
+                        box absy::UnresolvedType::Uint(32).mock(),
+                        absy::ArraySize::Identifier("N".into()),
                     ),
                 ),
             ];
@@ -932,7 +1102,7 @@ mod tests {
                     "a(3)[4]",
                     absy::Expression::Select(
                         box absy::Expression::FunctionCall(
-                            "a",
+                            "a".into(),
                             vec![absy::Expression::FieldConstant(Bn128Field::from(3)).into()],
                         )
                         .into(),
@@ -947,7 +1117,7 @@ mod tests {
                     absy::Expression::Select(
                         box absy::Expression::Select(
                             box absy::Expression::FunctionCall(
-                                "a",
+                                "a".into(),
                                 vec![absy::Expression::FieldConstant(Bn128Field::from(3)).into()],
                             )
                             .into(),
@@ -13,12 +13,17 @@ pub mod variable;
 
 pub use crate::absy::node::{Node, NodeValue};
 pub use crate::absy::parameter::{Parameter, ParameterNode};
-use crate::absy::types::{FunctionIdentifier, UnresolvedSignature, UnresolvedType, UserTypeId};
+pub use crate::absy::types::ArraySize;
+use crate::absy::types::{
+    FunctionIdentifierNode, UnresolvedSignature, UnresolvedType, UserTypeId,
+};
 pub use crate::absy::variable::{Variable, VariableNode};
+use crate::parser::Position;
 use embed::FlatEmbed;
 use std::path::PathBuf;
 
 use crate::imports::ImportNode;
+use crate::semantics::CheckedModules;
 use std::fmt;
 use zokrates_field::Field;
 
@@ -40,6 +45,63 @@ pub type Declarations<'ast, T> = Vec<SymbolDeclarationNode<'ast, T>>;
 pub struct Program<'ast, T> {
     pub modules: HashMap<ModuleId, Module<'ast, T>>,
     pub main: ModuleId,
+    /// The modules which were checked before, and are not checked again
+    pub checked: CheckedModules<'ast, T>,
+}
+
+impl<'ast, T> Program<'ast, T> {
+    /// Returns whether the symbol `id` of the module `module_id` is a type alias, following imports
+    pub fn is_alias(&self, module_id: &ModuleId, id: &str) -> bool {
+        self.modules
+            .get(module_id)
+            .and_then(|m| m.symbols.iter().find(|s| s.value.id == id))
+            .map(|s| match &s.value.symbol {
+                Symbol::HereAlias(..) => true,
+                Symbol::There(import) => {
+                    self.is_alias(&import.value.module_id, import.value.symbol_id)
+                }
+                _ => false,
+            })
+            .or_else(|| self.checked.get(module_id).map(|m| m.aliases.contains(id)))
+            .unwrap_or(false)
+    }
+
+    /// Returns the alias names used as the types of the parameters and return values of the entry point `entry`, if
+    /// any. Aliases nested in array or tuple types are not returned
+    pub fn entry_aliases(&self, entry: &str) -> Option<(Vec<Option<String>>, Vec<Option<String>>)> {
+        let main = self.modules[&self.main]
+            .symbols
+            .iter()
+            .find(|s| s.value.id == entry)?;
+
+        let signature = match &main.value.symbol {
+            Symbol::HereFunction(f) => &f.value.signature,
+            _ => return None,
+        };
+
+        let alias = |t: &UnresolvedTypeNode| match &t.value {
+            UnresolvedType::User(id) if self.is_alias(&self.main, id) => Some(id.clone()),
+            _ => None,
+        };
+
+        Some((
+            signature.inputs.iter().map(alias).collect(),
+            signature.outputs.iter().map(alias).collect(),
+        ))
+    }
+
+    /// Returns the positions of the parameters of the entry point `entry`, if it is defined in the main module
+    pub fn entry_parameters(&self, entry: &str) -> Option<Vec<Position>> {
+        let main = self.modules[&self.main]
+            .symbols
+            .iter()
+            .find(|s| s.value.id == entry)?;
+
+        match &main.value.symbol {
+            Symbol::HereFunction(f) => Some(f.value.arguments.iter().map(|a| a.start).collect()),
+            _ => None,
+        }
+    }
 }
 
 /// A declaration of a `FunctionSymbol`, be it from an import or a function definition
@@ -51,7 +113,9 @@ pub struct SymbolDeclaration<'ast, T> {
 
 #[derive(PartialEq, Clone)]
 pub enum Symbol<'ast, T> {
+    HereConstant(ConstantDefinitionNode<'ast, T>),
     HereType(StructDefinitionNode<'ast>),
+    HereAlias(UnresolvedTypeNode),
     HereFunction(FunctionNode<'ast, T>),
     There(SymbolImportNode<'ast>),
     Flat(FlatEmbed),
@@ -60,7 +124,9 @@ pub enum Symbol<'ast, T> {
 impl<'ast, T: fmt::Debug> fmt::Debug for Symbol<'ast, T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
+            Symbol::HereConstant(c) => write!(f, "HereConstant({:?})", c),
             Symbol::HereType(t) => write!(f, "HereType({:?})", t),
+            Symbol::HereAlias(t) => write!(f, "HereAlias({:?})", t),
             Symbol::HereFunction(fun) => write!(f, "HereFunction({:?})", fun),
             Symbol::There(t) => write!(f, "There({:?})", t),
             Symbol::Flat(flat) => write!(f, "Flat({:?})", flat),
@@ -71,7 +137,15 @@ impl<'ast, T: fmt::Debug> fmt::Debug for Symbol<'ast, T> {
 impl<'ast, T: fmt::Display> fmt::Display for SymbolDeclaration<'ast, T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self.symbol {
+            Symbol::HereConstant(ref c) => {
+                write!(
+                    f,
+                    "const {} {} = {}",
+                    c.value.ty, self.id, c.value.expression
+                )
+            }
             Symbol::HereType(ref t) => write!(f, "struct {} {}", self.id, t),
+            Symbol::HereAlias(ref t) => write!(f, "type {} = {}", self.id, t),
             Symbol::HereFunction(ref fun) => write!(f, "def {}{}", self.id, fun),
             Symbol::There(ref import) => write!(f, "import {} as {}", import, self.id),
             Symbol::Flat(ref flat_fun) => {
@@ -107,6 +181,24 @@ impl<'ast, T: Field> Module<'ast, T> {
 
 pub type UnresolvedTypeNode = Node<UnresolvedType>;
 
+/// A generic parameter of a function, standing for an array size
+pub type ConstantGenericNode = Node<String>;
+
+/// A constant definition
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConstantDefinition<'ast, T> {
+    pub ty: UnresolvedTypeNode,
+    pub expression: ExpressionNode<'ast, T>,
+}
+
+pub type ConstantDefinitionNode<'ast, T> = Node<ConstantDefinition<'ast, T>>;
+
+impl<'ast, T: fmt::Display> fmt::Display for ConstantDefinition<'ast, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} = {}", self.ty, self.expression)
+    }
+}
+
 /// A struct type definition
 #[derive(Debug, Clone, PartialEq)]
 pub struct StructDefinition<'ast> {
@@ -269,6 +361,7 @@ pub enum Assignee<'ast, T> {
     Identifier(Identifier<'ast>),
     Select(Box<AssigneeNode<'ast, T>>, Box<RangeOrExpression<'ast, T>>),
     Member(Box<AssigneeNode<'ast, T>>, Box<Identifier<'ast>>),
+    Element(Box<AssigneeNode<'ast, T>>, &'ast str),
 }
 
 pub type AssigneeNode<'ast, T> = Node<Assignee<'ast, T>>;
@@ -279,6 +372,7 @@ impl<'ast, T: fmt::Debug> fmt::Debug for Assignee<'ast, T> {
             Assignee::Identifier(ref s) => write!(f, "Identifier({:?})", s),
             Assignee::Select(ref a, ref e) => write!(f, "Select({:?}[{:?}])", a, e),
             Assignee::Member(ref s, ref m) => write!(f, "Member({:?}.{:?})", s, m),
+            Assignee::Element(ref t, index) => write!(f, "Element({:?}.{})", t, index),
         }
     }
 }
@@ -289,6 +383,7 @@ impl<'ast, T: fmt::Display> fmt::Display for Assignee<'ast, T> {
             Assignee::Identifier(ref s) => write!(f, "{}", s),
             Assignee::Select(ref a, ref e) => write!(f, "{}[{}]", a, e),
             Assignee::Member(ref s, ref m) => write!(f, "{}.{}", s, m),
+            Assignee::Element(ref t, index) => write!(f, "{}.{}", t, index),
         }
     }
 }
@@ -306,6 +401,13 @@ pub enum Statement<'ast, T> {
         ExpressionNode<'ast, T>,
         Vec<StatementNode<'ast, T>>,
     ),
+    IfElse(
+        ExpressionNode<'ast, T>,
+        Vec<StatementNode<'ast, T>>,
+        Vec<StatementNode<'ast, T>>,
+    ),
+    Break(Option<ExpressionNode<'ast, T>>),
+    Continue(Option<ExpressionNode<'ast, T>>),
     MultipleDefinition(Vec<AssigneeNode<'ast, T>>, ExpressionNode<'ast, T>),
 }
 
@@ -325,6 +427,25 @@ impl<'ast, T: fmt::Display> fmt::Display for Statement<'ast, T> {
                 }
                 write!(f, "\tendfor")
             }
+            Statement::IfElse(ref condition, ref consequence, ref alternative) => {
+                write!(f, "if {} then\n", condition)?;
+                for l in consequence {
+                    write!(f, "\t\t{}\n", l)?;
+                }
+                write!(f, "\telse\n")?;
+                for l in alternative {
+                    write!(f, "\t\t{}\n", l)?;
+                }
+                write!(f, "\tfi")
+            }
+            Statement::Break(ref condition) => match condition {
+                Some(condition) => write!(f, "break if {}", condition),
+                None => write!(f, "break"),
+            },
+            Statement::Continue(ref condition) => match condition {
+                Some(condition) => write!(f, "continue if {}", condition),
+                None => write!(f, "continue"),
+            },
             Statement::MultipleDefinition(ref ids, ref rhs) => {
                 for (i, id) in ids.iter().enumerate() {
                     write!(f, "{}", id)?;
@@ -354,6 +475,15 @@ impl<'ast, T: fmt::Debug> fmt::Debug for Statement<'ast, T> {
                 }
                 write!(f, "\tendfor")
             }
+            Statement::IfElse(ref condition, ref consequence, ref alternative) => {
+                write!(
+                    f,
+                    "IfElse({:?}, {:?}, {:?})",
+                    condition, consequence, alternative
+                )
+            }
+            Statement::Break(ref condition) => write!(f, "Break({:?})", condition),
+            Statement::Continue(ref condition) => write!(f, "Continue({:?})", condition),
             Statement::MultipleDefinition(ref lhs, ref rhs) => {
                 write!(f, "MultipleDefinition({:?}, {:?})", lhs, rhs)
             }
@@ -477,18 +607,22 @@ pub enum Expression<'ast, T> {
     U8Constant(u8),
     U16Constant(u16),
     U32Constant(u32),
+    U64Constant(u64),
+    U128Constant(u128),
+    IntConstant(&'ast str, usize),
     Identifier(Identifier<'ast>),
     Add(Box<ExpressionNode<'ast, T>>, Box<ExpressionNode<'ast, T>>),
     Sub(Box<ExpressionNode<'ast, T>>, Box<ExpressionNode<'ast, T>>),
     Mult(Box<ExpressionNode<'ast, T>>, Box<ExpressionNode<'ast, T>>),
     Div(Box<ExpressionNode<'ast, T>>, Box<ExpressionNode<'ast, T>>),
+    Rem(Box<ExpressionNode<'ast, T>>, Box<ExpressionNode<'ast, T>>),
     Pow(Box<ExpressionNode<'ast, T>>, Box<ExpressionNode<'ast, T>>),
     IfElse(
         Box<ExpressionNode<'ast, T>>,
         Box<ExpressionNode<'ast, T>>,
         Box<ExpressionNode<'ast, T>>,
     ),
-    FunctionCall(FunctionIdentifier<'ast>, Vec<ExpressionNode<'ast, T>>),
+    FunctionCall(FunctionIdentifierNode<'ast>, Vec<ExpressionNode<'ast, T>>),
     Lt(Box<ExpressionNode<'ast, T>>, Box<ExpressionNode<'ast, T>>),
     Le(Box<ExpressionNode<'ast, T>>, Box<ExpressionNode<'ast, T>>),
     Eq(Box<ExpressionNode<'ast, T>>, Box<ExpressionNode<'ast, T>>),
@@ -498,11 +632,13 @@ pub enum Expression<'ast, T> {
     Not(Box<ExpressionNode<'ast, T>>),
     InlineArray(Vec<SpreadOrExpression<'ast, T>>),
     InlineStruct(UserTypeId, Vec<(Identifier<'ast>, ExpressionNode<'ast, T>)>),
+    InlineTuple(Vec<ExpressionNode<'ast, T>>),
     Select(
         Box<ExpressionNode<'ast, T>>,
         Box<RangeOrExpression<'ast, T>>,
     ),
     Member(Box<ExpressionNode<'ast, T>>, Box<Identifier<'ast>>),
+    Element(Box<ExpressionNode<'ast, T>>, &'ast str),
     Or(Box<ExpressionNode<'ast, T>>, Box<ExpressionNode<'ast, T>>),
     BitXor(Box<ExpressionNode<'ast, T>>, Box<ExpressionNode<'ast, T>>),
     BitAnd(Box<ExpressionNode<'ast, T>>, Box<ExpressionNode<'ast, T>>),
@@ -520,11 +656,15 @@ impl<'ast, T: fmt::Display> fmt::Display for Expression<'ast, T> {
             Expression::U8Constant(ref i) => write!(f, "{}", i),
             Expression::U16Constant(ref i) => write!(f, "{}", i),
             Expression::U32Constant(ref i) => write!(f, "{}", i),
+            Expression::U64Constant(ref i) => write!(f, "{}", i),
+            Expression::U128Constant(ref i) => write!(f, "{}", i),
+            Expression::IntConstant(ref i, ref bitwidth) => write!(f, "{}i{}", i, bitwidth),
             Expression::Identifier(ref var) => write!(f, "{}", var),
             Expression::Add(ref lhs, ref rhs) => write!(f, "({} + {})", lhs, rhs),
             Expression::Sub(ref lhs, ref rhs) => write!(f, "({} - {})", lhs, rhs),
             Expression::Mult(ref lhs, ref rhs) => write!(f, "({} * {})", lhs, rhs),
             Expression::Div(ref lhs, ref rhs) => write!(f, "({} / {})", lhs, rhs),
+            Expression::Rem(ref lhs, ref rhs) => write!(f, "({} % {})", lhs, rhs),
             Expression::Pow(ref lhs, ref rhs) => write!(f, "({}**{})", lhs, rhs),
             Expression::BooleanConstant(b) => write!(f, "{}", b),
             Expression::IfElse(ref condition, ref consequent, ref alternative) => write!(
@@ -569,8 +709,21 @@ impl<'ast, T: fmt::Display> fmt::Display for Expression<'ast, T> {
                 }
                 write!(f, "}}")
             }
+            Expression::InlineTuple(ref exprs) => match exprs.len() {
+                1 => write!(f, "({},)", exprs[0]),
+                _ => write!(
+                    f,
+                    "({})",
+                    exprs
+                        .iter()
+                        .map(|e| e.to_string())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+            },
             Expression::Select(ref array, ref index) => write!(f, "{}[{}]", array, index),
             Expression::Member(ref struc, ref id) => write!(f, "{}.{}", struc, id),
+            Expression::Element(ref tuple, index) => write!(f, "{}.{}", tuple, index),
             Expression::Or(ref lhs, ref rhs) => write!(f, "({} || {})", lhs, rhs),
             Expression::BitXor(ref lhs, ref rhs) => write!(f, "({} ^ {})", lhs, rhs),
             Expression::BitAnd(ref lhs, ref rhs) => write!(f, "({} & {})", lhs, rhs),
@@ -587,12 +740,16 @@ impl<'ast, T: fmt::Debug> fmt::Debug for Expression<'ast, T> {
             Expression::U8Constant(ref i) => write!(f, "{:x}", i),
             Expression::U16Constant(ref i) => write!(f, "{:x}", i),
             Expression::U32Constant(ref i) => write!(f, "{:x}", i),
+            Expression::U64Constant(ref i) => write!(f, "{:x}", i),
+            Expression::U128Constant(ref i) => write!(f, "{:x}", i),
+            Expression::IntConstant(ref i, ref bitwidth) => write!(f, "{}i{}", i, bitwidth),
             Expression::FieldConstant(ref i) => write!(f, "Num({:?})", i),
             Expression::Identifier(ref var) => write!(f, "Ide({})", var),
             Expression::Add(ref lhs, ref rhs) => write!(f, "Add({:?}, {:?})", lhs, rhs),
             Expression::Sub(ref lhs, ref rhs) => write!(f, "Sub({:?}, {:?})", lhs, rhs),
             Expression::Mult(ref lhs, ref rhs) => write!(f, "Mult({:?}, {:?})", lhs, rhs),
             Expression::Div(ref lhs, ref rhs) => write!(f, "Div({:?}, {:?})", lhs, rhs),
+            Expression::Rem(ref lhs, ref rhs) => write!(f, "Rem({:?}, {:?})", lhs, rhs),
             Expression::Pow(ref lhs, ref rhs) => write!(f, "Pow({:?}, {:?})", lhs, rhs),
             Expression::BooleanConstant(b) => write!(f, "{}", b),
             Expression::IfElse(ref condition, ref consequent, ref alternative) => write!(
@@ -622,10 +779,16 @@ impl<'ast, T: fmt::Debug> fmt::Debug for Expression<'ast, T> {
                 f.debug_list().entries(members.iter()).finish()?;
                 write!(f, "]")
             }
+            Expression::InlineTuple(ref exprs) => {
+                write!(f, "InlineTuple(")?;
+                f.debug_list().entries(exprs.iter()).finish()?;
+                write!(f, ")")
+            }
             Expression::Select(ref array, ref index) => {
                 write!(f, "Select({:?}, {:?})", array, index)
             }
             Expression::Member(ref struc, ref id) => write!(f, "Member({:?}, {:?})", struc, id),
+            Expression::Element(ref tuple, index) => write!(f, "Element({:?}, {})", tuple, index),
             Expression::Or(ref lhs, ref rhs) => write!(f, "Or({:?}, {:?})", lhs, rhs),
             Expression::BitXor(ref lhs, ref rhs) => write!(f, "BitXor({:?}, {:?})", lhs, rhs),
             Expression::BitAnd(ref lhs, ref rhs) => write!(f, "BitAnd({:?}, {:?})", lhs, rhs),
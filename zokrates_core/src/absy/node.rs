@@ -72,6 +72,7 @@ impl<V: NodeValue> From<V> for Node<V> {
     }
 }
 
+use crate::absy::types::FunctionIdentifier;
 use crate::absy::*;
 use crate::imports::*;
 use zokrates_field::Field;
@@ -82,6 +83,9 @@ impl<'ast, T: fmt::Display + fmt::Debug + PartialEq> NodeValue for Assignee<'ast
 impl<'ast, T: fmt::Display + fmt::Debug + PartialEq> NodeValue for Statement<'ast, T> {}
 impl<'ast, T: Field> NodeValue for SymbolDeclaration<'ast, T> {}
 impl NodeValue for UnresolvedType {}
+impl NodeValue for String {}
+impl<'ast> NodeValue for FunctionIdentifier<'ast> {}
+impl<'ast, T: fmt::Display + fmt::Debug + PartialEq> NodeValue for ConstantDefinition<'ast, T> {}
 impl<'ast> NodeValue for StructDefinition<'ast> {}
 impl<'ast> NodeValue for StructDefinitionField<'ast> {}
 impl<'ast, T: fmt::Display + fmt::Debug + PartialEq> NodeValue for Function<'ast, T> {}
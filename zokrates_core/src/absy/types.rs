@@ -1,4 +1,4 @@
-use absy::UnresolvedTypeNode;
+use absy::{Node, UnresolvedTypeNode};
 use std::fmt;
 
 pub type Identifier<'ast> = &'ast str;
@@ -12,8 +12,32 @@ pub enum UnresolvedType {
     FieldElement,
     Boolean,
     Uint(usize),
-    Array(Box<UnresolvedTypeNode>, usize),
+    Int(usize),
+    Array(Box<UnresolvedTypeNode>, ArraySize),
     User(UserTypeId),
+    Tuple(Vec<UnresolvedTypeNode>),
+}
+
+/// The size of an array type, which can refer to a constant
+#[derive(Clone, PartialEq, Serialize, Deserialize, Debug)]
+pub enum ArraySize {
+    Number(usize),
+    Identifier(String),
+}
+
+impl fmt::Display for ArraySize {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ArraySize::Number(size) => write!(f, "{}", size),
+            ArraySize::Identifier(id) => write!(f, "{}", id),
+        }
+    }
+}
+
+impl From<usize> for ArraySize {
+    fn from(size: usize) -> Self {
+        ArraySize::Number(size)
+    }
 }
 
 impl fmt::Display for UnresolvedType {
@@ -22,29 +46,47 @@ impl fmt::Display for UnresolvedType {
             UnresolvedType::FieldElement => write!(f, "field"),
             UnresolvedType::Boolean => write!(f, "bool"),
             UnresolvedType::Uint(bitwidth) => write!(f, "u{}", bitwidth),
+            UnresolvedType::Int(bitwidth) => write!(f, "i{}", bitwidth),
             UnresolvedType::Array(ref ty, ref size) => write!(f, "{}[{}]", ty, size),
             UnresolvedType::User(i) => write!(f, "{}", i),
+            UnresolvedType::Tuple(elements) => match elements.len() {
+                1 => write!(f, "({},)", elements[0]),
+                _ => write!(
+                    f,
+                    "({})",
+                    elements
+                        .iter()
+                        .map(|e| e.to_string())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+            },
         }
     }
 }
 
 impl UnresolvedType {
-    pub fn array(ty: UnresolvedTypeNode, size: usize) -> Self {
-        UnresolvedType::Array(box ty, size)
+    pub fn array<S: Into<ArraySize>>(ty: UnresolvedTypeNode, size: S) -> Self {
+        UnresolvedType::Array(box ty, size.into())
     }
 }
 
 pub type FunctionIdentifier<'ast> = &'ast str;
 
+/// The name of a called function, located so that editors can point at it
+pub type FunctionIdentifierNode<'ast> = Node<FunctionIdentifier<'ast>>;
+
 pub use self::signature::UnresolvedSignature;
 
 mod signature {
     use std::fmt;
 
-    use absy::UnresolvedTypeNode;
+    use absy::{ConstantGenericNode, UnresolvedTypeNode};
 
     #[derive(Clone, PartialEq, Serialize, Deserialize)]
     pub struct UnresolvedSignature {
+        /// The generic parameters, which can be used as array sizes
+        pub generics: Vec<ConstantGenericNode>,
         pub inputs: Vec<UnresolvedTypeNode>,
         pub outputs: Vec<UnresolvedTypeNode>,
     }
@@ -53,14 +95,25 @@ mod signature {
         fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
             write!(
                 f,
-                "Signature(inputs: {:?}, outputs: {:?})",
-                self.inputs, self.outputs
+                "Signature(generics: {:?}, inputs: {:?}, outputs: {:?})",
+                self.generics, self.inputs, self.outputs
             )
         }
     }
 
     impl fmt::Display for UnresolvedSignature {
         fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            if !self.generics.is_empty() {
+                write!(
+                    f,
+                    "<{}>",
+                    self.generics
+                        .iter()
+                        .map(|g| g.to_string())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )?;
+            }
             write!(f, "(")?;
             for (i, t) in self.inputs.iter().enumerate() {
                 write!(f, "{}", t)?;
@@ -82,11 +135,17 @@ mod signature {
     impl UnresolvedSignature {
         pub fn new() -> UnresolvedSignature {
             UnresolvedSignature {
+                generics: vec![],
                 inputs: vec![],
                 outputs: vec![],
             }
         }
 
+        pub fn generics(mut self, generics: Vec<ConstantGenericNode>) -> Self {
+            self.generics = generics;
+            self
+        }
+
         pub fn inputs(mut self, inputs: Vec<UnresolvedTypeNode>) -> Self {
             self.inputs = inputs;
             self
@@ -0,0 +1,349 @@
+//! Module containing a cache of checked modules, so that modules whose sources did not change are not parsed and
+//! checked again
+//!
+//! Each module other than the main one is stored once checked, keyed by its location, its source, the curve and the
+//! version of the compiler. An entry also records the modules it imports with their keys, and is only reused if its
+//! imports still resolve to the same modules, which are reused too. Modules declaring or importing generic functions
+//! are not stored, as generic functions are checked again for each instance the modules calling them create.
+
+use absy::ModuleId;
+use semantics::{CheckedModule, CheckedModules};
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
+use std::fs::{self, File};
+use std::io::{self, BufWriter};
+use std::path::{Path, PathBuf};
+use typed_arena::Arena;
+use zokrates_common::Resolver;
+use zokrates_field::Field;
+
+/// A directory in which checked modules are stored
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cache {
+    dir: PathBuf,
+}
+
+/// Identifies a checked module by everything it depends on, apart from the modules it imports
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Key(String);
+
+impl Key {
+    pub fn new<T: Field>(location: &Path, source: &str) -> Self {
+        let mut hasher = Sha256::new();
+        for input in &[
+            env!("CARGO_PKG_VERSION"),
+            T::name(),
+            &location.display().to_string(),
+            source,
+        ] {
+            // prefix with the length so that the boundaries between inputs are part of the key
+            hasher.input((input.len() as u64).to_le_bytes());
+            hasher.input(input.as_bytes());
+        }
+        Key(hex::encode(hasher.result()))
+    }
+}
+
+/// An import of a module: the imported path, the location it resolved to and the key of the imported module
+type Import = (PathBuf, ModuleId, Key);
+
+/// A checked module stored in the cache, with its imports
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[serde(bound(serialize = "T: Field", deserialize = "'de: 'ast, T: Field"))]
+pub struct Entry<'ast, T> {
+    pub imports: Vec<Import>,
+    pub module: CheckedModule<'ast, T>,
+}
+
+impl Cache {
+    pub fn new<P: Into<PathBuf>>(dir: P) -> Self {
+        Cache { dir: dir.into() }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    fn path(&self, key: &Key) -> PathBuf {
+        self.dir.join(format!("{}.json", key.0))
+    }
+
+    /// Returns the entry stored under `key`, if any. Entries which cannot be read are ignored. The identifiers of the
+    /// module borrow from its serialised form, which is kept in `arena`
+    pub fn get<'ast, T: Field>(
+        &self,
+        key: &Key,
+        arena: &'ast Arena<String>,
+    ) -> Option<Entry<'ast, T>> {
+        let entry = arena.alloc(fs::read_to_string(self.path(key)).ok()?);
+        serde_json::from_str(entry).ok()
+    }
+
+    pub fn insert<T: Field>(&self, key: &Key, entry: &Entry<T>) -> io::Result<()> {
+        fs::create_dir_all(&self.dir)?;
+
+        // write to a temporary file first, so that concurrent compilations never read a partial entry
+        let tmp = self
+            .dir
+            .join(format!("{}.{}.tmp", key.0, std::process::id()));
+        {
+            let mut writer = BufWriter::new(File::create(&tmp)?);
+            serde_json::to_writer(&mut writer, entry)
+                .map_err(|e| io::Error::new(io::ErrorKind::Other, e.to_string()))?;
+        }
+        fs::rename(tmp, self.path(key))
+    }
+}
+
+/// The modules of a program which were found in a `Cache` while resolving its imports, and what is needed to store
+/// the others once they are checked
+pub struct Session<'a, 'ast, T> {
+    cache: &'a Cache,
+    /// The modules found in the cache which were not added to the program yet
+    checked: CheckedModules<'ast, T>,
+    /// The modules found in the cache
+    loaded: HashSet<ModuleId>,
+    /// The modules which could not be found in the cache, and are parsed instead
+    missed: HashSet<ModuleId>,
+    /// The key of each imported module
+    keys: HashMap<ModuleId, Key>,
+    /// The imports of each module
+    imports: HashMap<ModuleId, Vec<Import>>,
+}
+
+impl<'a, 'ast, T: Field> Session<'a, 'ast, T> {
+    pub fn new(cache: &'a Cache) -> Self {
+        Session {
+            cache,
+            checked: HashMap::new(),
+            loaded: HashSet::new(),
+            missed: HashSet::new(),
+            keys: HashMap::new(),
+            imports: HashMap::new(),
+        }
+    }
+
+    /// Records that the module at `location` imports `path`, which resolved to `source` at `import_location`
+    pub fn import(&mut self, location: &Path, path: &Path, import_location: &Path, source: &str) {
+        let key = Key::new::<T>(import_location, source);
+        self.keys.insert(import_location.to_path_buf(), key.clone());
+        self.imports
+            .entry(location.to_path_buf())
+            .or_default()
+            .push((path.to_path_buf(), import_location.to_path_buf(), key));
+    }
+
+    /// Looks up the imported module at `location` in the cache, returning whether it can be reused. If it cannot, it
+    /// must be parsed
+    pub fn load<E>(
+        &mut self,
+        location: &Path,
+        resolver: &dyn Resolver<E>,
+        arena: &'ast Arena<String>,
+    ) -> bool {
+        match self.keys.get(location).cloned() {
+            Some(key) => self.fetch(location, &key, resolver, arena),
+            None => false,
+        }
+    }
+
+    fn fetch<E>(
+        &mut self,
+        location: &Path,
+        key: &Key,
+        resolver: &dyn Resolver<E>,
+        arena: &'ast Arena<String>,
+    ) -> bool {
+        if self.loaded.contains(location) {
+            return true;
+        }
+
+        // a module which is parsed cannot be reused, even if it was stored in the meantime
+        if self.missed.contains(location) {
+            return false;
+        }
+
+        let entry: Option<Entry<T>> = self.cache.get(key, arena);
+
+        // the module can only be reused if it imports the same modules, and they can be reused too
+        let entry = entry.filter(|entry| {
+            entry
+                .imports
+                .iter()
+                .all(|(path, import_location, import_key)| {
+                    match resolver.resolve(location.to_path_buf(), path.clone()) {
+                        Ok((source, l)) => {
+                            l == *import_location
+                                && Key::new::<T>(&l, &source) == *import_key
+                                && self.fetch(&l, import_key, resolver, arena)
+                        }
+                        Err(_) => false,
+                    }
+                })
+        });
+
+        match entry {
+            Some(entry) => {
+                self.loaded.insert(location.to_path_buf());
+                self.checked.insert(location.to_path_buf(), entry.module);
+                true
+            }
+            None => {
+                self.missed.insert(location.to_path_buf());
+                false
+            }
+        }
+    }
+
+    /// The modules found in the cache so far
+    pub fn take_checked(&mut self) -> CheckedModules<'ast, T> {
+        std::mem::take(&mut self.checked)
+    }
+
+    /// Stores the checked `modules` whose imports can be reused too. The cache is only an optimisation, so failing to
+    /// write to it does not fail the compilation
+    pub fn store(&self, mut modules: CheckedModules<'ast, T>) {
+        loop {
+            let incomplete: Vec<_> = modules
+                .keys()
+                .filter(|id| {
+                    self.imports
+                        .get(*id)
+                        .into_iter()
+                        .flatten()
+                        .any(|(_, l, _)| !self.loaded.contains(l) && !modules.contains_key(l))
+                })
+                .cloned()
+                .collect();
+
+            if incomplete.is_empty() {
+                break;
+            }
+
+            for id in incomplete {
+                modules.remove(&id);
+            }
+        }
+
+        for (id, module) in modules {
+            if let Some(key) = self.keys.get(&id) {
+                let entry = Entry {
+                    imports: self.imports.get(&id).cloned().unwrap_or_default(),
+                    module,
+                };
+                let _ = self.cache.insert(key, &entry);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeSet;
+    use typed_absy::types::{FunctionKey, Signature, Type};
+    use typed_absy::{FieldElementExpression, TypedExpression, TypedFunctionSymbol, TypedModule};
+    use zokrates_field::{Bls12Field, Bn128Field};
+
+    fn cache(name: &str) -> Cache {
+        Cache::new(std::env::temp_dir().join(format!(
+            "zokrates_cache_{}_{}",
+            name,
+            std::process::id()
+        )))
+    }
+
+    fn entry() -> Entry<'static, Bn128Field> {
+        Entry {
+            imports: vec![(
+                "./dep".into(),
+                "dep.zok".into(),
+                Key::new::<Bn128Field>(Path::new("dep.zok"), "source"),
+            )],
+            module: CheckedModule {
+                module: TypedModule {
+                    functions: vec![(
+                        FunctionKey::with_id("foo")
+                            .signature(Signature::new().inputs(vec![Type::FieldElement])),
+                        TypedFunctionSymbol::There(
+                            FunctionKey::with_id("bar")
+                                .signature(Signature::new().inputs(vec![Type::FieldElement])),
+                            "dep.zok".into(),
+                        ),
+                    )]
+                    .into_iter()
+                    .collect(),
+                },
+                types: vec![("Alias".to_string(), Type::Boolean)]
+                    .into_iter()
+                    .collect(),
+                constants: vec![(
+                    "N".to_string(),
+                    TypedExpression::FieldElement(FieldElementExpression::Number(
+                        Bn128Field::from(42),
+                    )),
+                )]
+                .into_iter()
+                .collect(),
+                aliases: vec!["Alias".to_string()]
+                    .into_iter()
+                    .collect::<BTreeSet<_>>(),
+            },
+        }
+    }
+
+    #[test]
+    fn key_stability() {
+        let key =
+            |location: &str, source: &str| Key::new::<Bn128Field>(Path::new(location), source);
+
+        assert_eq!(key("lib", "source"), key("lib", "source"));
+        assert_ne!(key("lib", "source"), key("lib", "other source"));
+        assert_ne!(key("lib", "source"), key("other", "source"));
+        assert_ne!(
+            key("lib", "source"),
+            Key::new::<Bls12Field>(Path::new("lib"), "source")
+        );
+
+        // the boundaries between inputs are part of the key
+        assert_ne!(key("ab", "c"), key("a", "bc"));
+    }
+
+    #[test]
+    fn round_trip() {
+        let cache = cache("round_trip");
+        let arena = Arena::new();
+        let key = Key::new::<Bn128Field>(Path::new("lib"), "source");
+
+        assert_eq!(cache.get::<Bn128Field>(&key, &arena), None);
+        cache.insert(&key, &entry()).unwrap();
+        assert_eq!(cache.get::<Bn128Field>(&key, &arena), Some(entry()));
+
+        fs::remove_dir_all(cache.dir()).unwrap();
+    }
+
+    #[test]
+    fn ignore_invalid_entries() {
+        let cache = cache("invalid_entries");
+        let arena = Arena::new();
+        let key = Key::new::<Bn128Field>(Path::new("lib"), "source");
+        fs::create_dir_all(cache.dir()).unwrap();
+
+        // an entry which is not a module
+        fs::write(cache.path(&key), b"not a module").unwrap();
+        assert_eq!(cache.get::<Bn128Field>(&key, &arena), None);
+
+        // an entry which was only partially written
+        cache.insert(&key, &entry()).unwrap();
+        let entry = fs::read(cache.path(&key)).unwrap();
+        fs::write(cache.path(&key), &entry[..entry.len() / 2]).unwrap();
+        assert_eq!(cache.get::<Bn128Field>(&key, &arena), None);
+
+        // an entry which cannot be opened
+        fs::remove_file(cache.path(&key)).unwrap();
+        fs::create_dir(cache.path(&key)).unwrap();
+        assert_eq!(cache.get::<Bn128Field>(&key, &arena), None);
+
+        fs::remove_dir_all(cache.dir()).unwrap();
+    }
+}
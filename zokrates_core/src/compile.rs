@@ -4,18 +4,23 @@
 //! @author Thibaut Schaeffer <thibaut@schaeff.fr>
 //! @date 2018
 use absy::{Module, ModuleId, Program};
+use cache::{Cache, Session};
+use diagnostics::{Diagnostic, ErrorCode, Position, Severity};
 use flatten::Flattener;
 use imports::{self, Importer};
 use ir;
 use macros;
 use macros::process_macros;
 use semantics::{self, Checker};
-use static_analysis::Analyse;
+use source_map::{self, Origin};
+use static_analysis::{self, Analyse, OriginRemover};
 use std::collections::HashMap;
 use std::fmt;
 use std::io;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use symbols::SymbolIndex;
 use typed_absy::abi::Abi;
+use typed_absy::TypedProgram;
 use typed_arena::Arena;
 use zir::ZirProgram;
 use zokrates_common::Resolver;
@@ -26,6 +31,7 @@ use zokrates_pest_ast as pest;
 pub struct CompilationArtifacts<T: Field> {
     prog: ir::Prog<T>,
     abi: Abi,
+    warnings: CompileWarnings,
 }
 
 impl<T: Field> CompilationArtifacts<T> {
@@ -36,6 +42,10 @@ impl<T: Field> CompilationArtifacts<T> {
     pub fn abi(&self) -> &Abi {
         &self.abi
     }
+
+    pub fn warnings(&self) -> &CompileWarnings {
+        &self.warnings
+    }
 }
 
 #[derive(Debug)]
@@ -54,6 +64,7 @@ pub enum CompileErrorInner {
     MacroError(macros::Error),
     SemanticError(semantics::ErrorInner),
     ReadError(io::Error),
+    AnalysisError(static_analysis::Error),
 }
 
 impl CompileErrorInner {
@@ -79,9 +90,49 @@ impl CompileError {
     pub fn value(&self) -> &CompileErrorInner {
         &self.value
     }
+
+    pub fn diagnostic(&self) -> Diagnostic {
+        let (code, pos, message) = match &self.value {
+            CompileErrorInner::ParserError(e) => {
+                let ((start_line, start_col), (end_line, end_col)) = e.line_col();
+                (
+                    ErrorCode::SyntaxError,
+                    Some((
+                        Position {
+                            line: start_line,
+                            col: start_col,
+                        },
+                        Position {
+                            line: end_line,
+                            col: end_col,
+                        },
+                    )),
+                    e.message(),
+                )
+            }
+            CompileErrorInner::ImportError(e) => (e.code(), e.pos(), e.message().to_string()),
+            CompileErrorInner::MacroError(e) => (ErrorCode::IncompatibleCurve, None, e.to_string()),
+            CompileErrorInner::SemanticError(e) => (e.code(), e.pos(), e.message().to_string()),
+            CompileErrorInner::ReadError(e) => (ErrorCode::ReadError, None, e.to_string()),
+            CompileErrorInner::AnalysisError(e) => (e.code(), e.pos(), e.to_string()),
+        };
+
+        Diagnostic {
+            severity: Severity::Error,
+            code,
+            file: self.file.display().to_string(),
+            start: pos.map(|p| p.0),
+            end: pos.map(|p| p.1),
+            message,
+        }
+    }
 }
 
 impl CompileErrors {
+    pub fn diagnostics(&self) -> Vec<Diagnostic> {
+        self.0.iter().map(|e| e.diagnostic()).collect()
+    }
+
     pub fn with_context(self, file: PathBuf) -> Self {
         CompileErrors(
             self.0
@@ -95,6 +146,68 @@ impl CompileErrors {
     }
 }
 
+#[derive(Debug, Default, Clone)]
+pub struct CompileWarnings(pub Vec<CompileWarning>);
+
+/// A problem which does not prevent the program from compiling, such as an unused variable
+#[derive(Debug, Clone)]
+pub struct CompileWarning {
+    file: PathBuf,
+    value: semantics::ErrorInner,
+}
+
+impl CompileWarning {
+    pub fn file(&self) -> &PathBuf {
+        &self.file
+    }
+
+    pub fn value(&self) -> &semantics::ErrorInner {
+        &self.value
+    }
+
+    pub fn diagnostic(&self) -> Diagnostic {
+        let pos = self.value.pos();
+
+        Diagnostic {
+            severity: Severity::Warning,
+            code: self.value.code(),
+            file: self.file.display().to_string(),
+            start: pos.map(|p| p.0),
+            end: pos.map(|p| p.1),
+            message: self.value.message().to_string(),
+        }
+    }
+
+    pub fn into_error(self) -> CompileError {
+        CompileErrorInner::SemanticError(self.value).in_file(&self.file)
+    }
+}
+
+impl CompileWarnings {
+    pub fn diagnostics(&self) -> Vec<Diagnostic> {
+        self.0.iter().map(|w| w.diagnostic()).collect()
+    }
+
+    pub fn into_errors(self) -> CompileErrors {
+        CompileErrors(self.0.into_iter().map(|w| w.into_error()).collect())
+    }
+}
+
+impl From<semantics::Warning> for CompileWarning {
+    fn from(warning: semantics::Warning) -> Self {
+        CompileWarning {
+            value: warning.inner,
+            file: warning.module_id,
+        }
+    }
+}
+
+impl From<Vec<semantics::Warning>> for CompileWarnings {
+    fn from(warnings: Vec<semantics::Warning>) -> Self {
+        CompileWarnings(warnings.into_iter().map(CompileWarning::from).collect())
+    }
+}
+
 impl From<pest::Error> for CompileErrorInner {
     fn from(error: pest::Error) -> Self {
         CompileErrorInner::ParserError(error)
@@ -128,6 +241,23 @@ impl From<semantics::Error> for CompileError {
     }
 }
 
+impl CompileError {
+    /// Create an error found during static analysis, in the file it originates from if it is known, in `location`
+    /// otherwise
+    fn analysis(error: static_analysis::Error, location: &Path) -> Self {
+        let file = error
+            .origin
+            .as_ref()
+            .map(|o| PathBuf::from(&o.file))
+            .unwrap_or_else(|| location.to_path_buf());
+
+        CompileError {
+            value: CompileErrorInner::AnalysisError(error),
+            file,
+        }
+    }
+}
+
 impl fmt::Display for CompileErrorInner {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
@@ -136,13 +266,32 @@ impl fmt::Display for CompileErrorInner {
             CompileErrorInner::SemanticError(ref e) => write!(f, "{}", e),
             CompileErrorInner::ReadError(ref e) => write!(f, "{}", e),
             CompileErrorInner::ImportError(ref e) => write!(f, "{}", e),
+            CompileErrorInner::AnalysisError(ref e) => {
+                let location = e
+                    .pos()
+                    .map(|p| format!("{}", p.0))
+                    .unwrap_or("?".to_string());
+                write!(f, "{}\n\t{}", location, e)
+            }
         }
     }
 }
 
-#[derive(Debug, Default, Serialize, Deserialize)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct CompileConfig {
     is_release: bool,
+    /// Keep track of the origin of the constraints, see `ir::Prog::profile`
+    #[serde(default)]
+    profile: bool,
+    /// Fail on warnings as if they were errors
+    #[serde(default)]
+    deny_warnings: bool,
+    /// Reuse the modules checked before from this cache, and store new ones in it
+    #[serde(skip)]
+    cache: Option<Cache>,
+    /// The functions of the main module to compile, `main` if none is set
+    #[serde(default)]
+    entries: Vec<String>,
 }
 
 impl CompileConfig {
@@ -154,88 +303,309 @@ impl CompileConfig {
     pub fn is_release(&self) -> bool {
         self.is_release
     }
+
+    pub fn with_profile(mut self, profile: bool) -> Self {
+        self.profile = profile;
+        self
+    }
+
+    pub fn profile(&self) -> bool {
+        self.profile
+    }
+
+    pub fn with_deny_warnings(mut self, deny_warnings: bool) -> Self {
+        self.deny_warnings = deny_warnings;
+        self
+    }
+
+    pub fn deny_warnings(&self) -> bool {
+        self.deny_warnings
+    }
+
+    pub fn with_cache(mut self, cache: Cache) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    pub fn cache(&self) -> Option<&Cache> {
+        self.cache.as_ref()
+    }
+
+    pub fn with_entries(mut self, entries: Vec<String>) -> Self {
+        self.entries = entries;
+        self
+    }
+
+    pub fn with_entry<S: Into<String>>(self, entry: S) -> Self {
+        self.with_entries(vec![entry.into()])
+    }
+
+    pub fn entries(&self) -> Vec<&str> {
+        match self.entries.is_empty() {
+            true => vec!["main"],
+            false => self.entries.iter().map(|e| e.as_str()).collect(),
+        }
+    }
 }
 
 type FilePath = PathBuf;
 
+/// Compile the first entry point of a program, which is `main` unless others are set in `config`. See
+/// `compile_entries` to compile all of them
 pub fn compile<T: Field, E: Into<imports::Error>>(
     source: String,
     location: FilePath,
     resolver: Option<&dyn Resolver<E>>,
     config: &CompileConfig,
 ) -> Result<CompilationArtifacts<T>, CompileErrors> {
+    let entries = config.entries();
+
+    compile_with_entries(source, location, resolver, config, &entries[..1])
+        .map(|mut artifacts| artifacts.remove(0))
+}
+
+/// Compile each entry point of a program to its own circuit, in the order of `config.entries()`. The modules are
+/// only parsed and checked once
+pub fn compile_entries<T: Field, E: Into<imports::Error>>(
+    source: String,
+    location: FilePath,
+    resolver: Option<&dyn Resolver<E>>,
+    config: &CompileConfig,
+) -> Result<Vec<CompilationArtifacts<T>>, CompileErrors> {
+    compile_with_entries(source, location, resolver, config, &config.entries())
+}
+
+fn compile_with_entries<T: Field, E: Into<imports::Error>>(
+    source: String,
+    location: FilePath,
+    resolver: Option<&dyn Resolver<E>>,
+    config: &CompileConfig,
+    entries: &[&str],
+) -> Result<Vec<CompilationArtifacts<T>>, CompileErrors> {
     let arena = Arena::new();
 
-    let (typed_ast, abi) = check_with_arena(source, location, resolver, &arena)?;
+    let (typed_ast, abis, warnings) =
+        check_with_arena(source, location.clone(), resolver, config, &arena)?;
 
-    // flatten input program
-    let program_flattened = Flattener::flatten(typed_ast);
+    entries
+        .iter()
+        .zip(abis)
+        .map(|(entry, (abi, parameters))| {
+            let typed_ast = analyse(typed_ast.clone(), entry, &location, config)?;
 
-    // analyse (constant propagation after call resolution)
-    let program_flattened = program_flattened.analyse();
+            // flatten input program
+            let program_flattened = Flattener::flatten(typed_ast);
 
-    // convert to ir
-    let ir_prog = ir::Prog::from(program_flattened);
+            // analyse (constant propagation after call resolution)
+            let program_flattened = program_flattened.analyse();
 
-    // optimize
-    let optimized_ir_prog = ir_prog.optimize(config);
+            // convert to ir
+            let ir_prog = ir::Prog::from(program_flattened);
 
-    // analyse (check for unused constraints)
-    let optimized_ir_prog = optimized_ir_prog.analyse();
+            // optimize
+            let optimized_ir_prog = ir_prog.optimize(config);
 
-    Ok(CompilationArtifacts {
-        prog: optimized_ir_prog,
-        abi,
-    })
+            // analyse (check for unconstrained private inputs)
+            let optimized_ir_prog = optimized_ir_prog
+                .analyse(&abi, &parameters)
+                .map_err(|e| CompileError::analysis(e, &location))?;
+
+            Ok(CompilationArtifacts {
+                prog: optimized_ir_prog,
+                abi,
+                warnings: warnings.clone(),
+            })
+        })
+        .collect()
 }
 
 pub fn check<'ast, T: Field, E: Into<imports::Error>>(
     source: String,
     location: FilePath,
     resolver: Option<&dyn Resolver<E>>,
-) -> Result<(), CompileErrors> {
+    config: &CompileConfig,
+) -> Result<CompileWarnings, CompileErrors> {
     let arena = Arena::new();
 
-    check_with_arena::<T, _>(source, location, resolver, &arena).map(|_| ())
+    let (typed_ast, _, warnings) =
+        check_with_arena::<T, _>(source, location.clone(), resolver, config, &arena)?;
+
+    // analyse each entry point, as some errors are only found during static analysis
+    for entry in config.entries() {
+        analyse(typed_ast.clone(), entry, &location, config)?;
+    }
+
+    Ok(warnings)
+}
+
+/// Check a program, indexing the symbols it declares and uses so that editors can navigate it. The index covers
+/// what could be checked even if checking fails. When `cache` is set, the imported modules found in it are reused
+/// rather than checked again, so the index has no entries for them
+pub fn check_with_index<T: Field, E: Into<imports::Error>>(
+    source: String,
+    location: FilePath,
+    resolver: Option<&dyn Resolver<E>>,
+    cache: Option<&Cache>,
+) -> (Result<CompileWarnings, CompileErrors>, SymbolIndex) {
+    let arena = Arena::new();
+
+    let mut session = cache.map(Session::new);
+
+    let source = arena.alloc(source);
+    let compiled = match compile_program(source, location, resolver, session.as_mut(), &arena) {
+        Ok(compiled) => compiled,
+        Err(e) => return (Err(e), SymbolIndex::default()),
+    };
+
+    let (res, index) = Checker::<T>::check_with_index(compiled);
+
+    (
+        res.map(|(_, warnings, checked)| {
+            if let Some(session) = session {
+                session.store(checked);
+            }
+            CompileWarnings::from(warnings)
+        })
+        .map_err(|errors| {
+            CompileErrors(errors.into_iter().map(|e| CompileError::from(e)).collect())
+        }),
+        index,
+    )
 }
 
 fn check_with_arena<'ast, T: Field, E: Into<imports::Error>>(
     source: String,
     location: FilePath,
     resolver: Option<&dyn Resolver<E>>,
+    config: &CompileConfig,
     arena: &'ast Arena<String>,
-) -> Result<(ZirProgram<'ast, T>, Abi), CompileErrors> {
+) -> Result<
+    (TypedProgram<'ast, T>, Vec<(Abi, Vec<Origin>)>, CompileWarnings),
+    CompileErrors,
+> {
+    let mut session = config.cache().map(Session::new);
+
     let source = arena.alloc(source);
-    let compiled = compile_program(source, location.clone(), resolver, &arena)?;
+    let compiled = compile_program(
+        source,
+        location.clone(),
+        resolver,
+        session.as_mut(),
+        &arena,
+    )?;
+
+    let entries = config.entries();
+
+    let aliases: Vec<_> = entries
+        .iter()
+        .map(|entry| compiled.entry_aliases(entry))
+        .collect();
 
-    // check semantics
-    let typed_ast = Checker::check(compiled).map_err(|errors| {
+    // the origins of the parameters of the entry points, used to locate unconstrained inputs
+    let parameters: Vec<Vec<_>> = entries
+        .iter()
+        .map(|entry| {
+            compiled
+                .entry_parameters(entry)
+                .unwrap_or_default()
+                .into_iter()
+                .map(|pos| Origin {
+                    stack: vec![entry.to_string()],
+                    file: source_map::normalize(&location),
+                    line: pos.line,
+                })
+                .collect()
+        })
+        .collect();
+
+    // check semantics, keeping track of origins in order to locate errors found during static analysis
+    let (typed_ast, warnings) = match session {
+        Some(session) => Checker::check_with_cache(compiled, &entries).map(
+            |(typed_ast, warnings, checked)| {
+                session.store(checked);
+                (typed_ast, warnings)
+            },
+        ),
+        None => Checker::check_with_origins(compiled, &entries),
+    }
+    .map_err(|errors| {
         CompileErrors(errors.into_iter().map(|e| CompileError::from(e)).collect())
     })?;
 
-    let abi = typed_ast.abi();
+    let warnings = CompileWarnings::from(warnings);
 
+    if config.deny_warnings() && !warnings.0.is_empty() {
+        return Err(warnings.into_errors());
+    }
+
+    let abis = entries
+        .iter()
+        .zip(aliases)
+        .zip(parameters)
+        .map(|((entry, aliases), parameters)| {
+            let mut abi = typed_ast.abi(entry);
+
+            // keep the names of the type aliases used in the signature of the entry point
+            if let Some((inputs, outputs)) = aliases {
+                for (i, alias) in abi.inputs.iter_mut().zip(inputs) {
+                    i.alias = alias;
+                }
+                for (o, alias) in abi.outputs.iter_mut().zip(outputs) {
+                    o.alias = alias;
+                }
+            }
+
+            (abi, parameters)
+        })
+        .collect();
+
+    Ok((typed_ast, abis, warnings))
+}
+
+fn analyse<'ast, T: Field>(
+    typed_ast: TypedProgram<'ast, T>,
+    entry: &str,
+    location: &Path,
+    config: &CompileConfig,
+) -> Result<ZirProgram<'ast, T>, CompileErrors> {
     // analyse (unroll and constant propagation)
-    let typed_ast = typed_ast.analyse();
+    let typed_ast = typed_ast
+        .analyse(entry)
+        .map_err(|e| CompileError::analysis(e, location))?;
 
-    Ok((typed_ast, abi))
+    // origins are only kept when profiling
+    Ok(match config.profile() {
+        true => typed_ast,
+        false => OriginRemover::remove(typed_ast),
+    })
 }
 
+/// Parse a program and the modules it imports. When `session` is set, the imported modules found in its cache are
+/// added to the program as they were checked instead
 pub fn compile_program<'ast, T: Field, E: Into<imports::Error>>(
     source: &'ast str,
     location: FilePath,
     resolver: Option<&dyn Resolver<E>>,
+    mut session: Option<&mut Session<'_, 'ast, T>>,
     arena: &'ast Arena<String>,
 ) -> Result<Program<'ast, T>, CompileErrors> {
     let mut modules = HashMap::new();
 
-    let main = compile_module(&source, location.clone(), resolver, &mut modules, &arena)?;
+    let main = compile_module(
+        &source,
+        location.clone(),
+        resolver,
+        &mut modules,
+        session.as_deref_mut(),
+        &arena,
+    )?;
 
     modules.insert(location.clone(), main);
 
     Ok(Program {
         main: location,
         modules,
+        checked: session.map(|s| s.take_checked()).unwrap_or_default(),
     })
 }
 
@@ -244,6 +614,7 @@ pub fn compile_module<'ast, T: Field, E: Into<imports::Error>>(
     location: FilePath,
     resolver: Option<&dyn Resolver<E>>,
     modules: &mut HashMap<ModuleId, Module<'ast, T>>,
+    session: Option<&mut Session<'_, 'ast, T>>,
     arena: &'ast Arena<String>,
 ) -> Result<Module<'ast, T>, CompileErrors> {
     let ast = pest::generate_ast(&source)
@@ -259,6 +630,7 @@ pub fn compile_module<'ast, T: Field, E: Into<imports::Error>>(
         location.clone(),
         resolver,
         modules,
+        session,
         &arena,
     )
 }
@@ -304,6 +676,710 @@ mod test {
         assert!(res.is_ok());
     }
 
+    #[test]
+    fn u128_not_supported() {
+        let source = r#"
+			def main(u128 a) -> u128:
+			   return a
+		"#
+        .to_string();
+        let res: Result<CompilationArtifacts<Bn128Field>, CompileErrors> = compile(
+            source,
+            "./path/to/file".into(),
+            None::<&dyn Resolver<io::Error>>,
+            &CompileConfig::default(),
+        );
+        assert!(res.unwrap_err().0[0]
+            .value()
+            .to_string()
+            .contains(&"Type u128 is reserved but not supported yet"));
+    }
+
+    #[test]
+    fn u128_not_supported_in_imported_module() {
+        struct CustomResolver;
+
+        impl<E> Resolver<E> for CustomResolver {
+            fn resolve(&self, _: PathBuf, _: PathBuf) -> Result<(String, PathBuf), E> {
+                Ok((
+                    "def foo(u128 a) -> u128:\n    return a\n".to_string(),
+                    "lib".into(),
+                ))
+            }
+        }
+
+        let source = r#"
+			from "lib" import foo
+			def main() -> field:
+			   return 1
+		"#
+        .to_string();
+        let res = compile::<Bn128Field, io::Error>(
+            source,
+            "./path/to/file".into(),
+            Some(&CustomResolver),
+            &CompileConfig::default(),
+        );
+        let errors = res.unwrap_err().0;
+        assert_eq!(errors[0].file(), &PathBuf::from("lib"));
+        assert!(errors[0]
+            .value()
+            .to_string()
+            .contains(&"Type u128 is reserved but not supported yet"));
+    }
+
+    #[test]
+    fn const_used_as_name() {
+        let source = r#"
+			const field N = 42
+			def const() -> field:
+			   field const = N
+			   return const
+			def main() -> field:
+			   return const()
+		"#
+        .to_string();
+        let res: Result<CompilationArtifacts<Bn128Field>, CompileErrors> = compile(
+            source,
+            "./path/to/file".into(),
+            None::<&dyn Resolver<io::Error>>,
+            &CompileConfig::default(),
+        );
+        assert!(res.is_ok());
+    }
+
+    #[test]
+    fn profile_attributes_constraints_to_origins() {
+        let source = r#"
+def foo(field a) -> field:
+    return a * a * a
+
+def main(field a) -> field:
+    field b = foo(a)
+    return b * a
+"#
+        .to_string();
+        let res: CompilationArtifacts<Bn128Field> = compile(
+            source,
+            "main".into(),
+            None::<&dyn Resolver<io::Error>>,
+            &CompileConfig::default().with_profile(true),
+        )
+        .unwrap();
+
+        let profile = res.prog().profile();
+        let by_function = profile.by_function();
+        assert_eq!(by_function[0].0, "main::foo");
+        assert_eq!(by_function[0].1.constraints, 2);
+        assert!(profile
+            .by_line()
+            .iter()
+            .any(|(line, cost)| line == "main:3" && cost.constraints == 2));
+        assert_eq!(profile.total().constraints, res.prog().constraint_count());
+    }
+
+    #[test]
+    fn diagnostics() {
+        let source = r#"
+def main() -> field:
+    return a
+"#
+        .to_string();
+        let res: Result<CompilationArtifacts<Bn128Field>, CompileErrors> = compile(
+            source,
+            "./path/to/file".into(),
+            None::<&dyn Resolver<io::Error>>,
+            &CompileConfig::default(),
+        );
+        let diagnostics = res.unwrap_err().diagnostics();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].severity, Severity::Error);
+        assert_eq!(diagnostics[0].code, ErrorCode::UndefinedIdentifier);
+        assert_eq!(diagnostics[0].file, "./path/to/file");
+        assert_eq!(diagnostics[0].start, Some(Position { line: 3, col: 12 }));
+        assert_eq!(
+            serde_json::to_value(&diagnostics[0]).unwrap(),
+            serde_json::json!({
+                "severity": "error",
+                "code": "E0302",
+                "file": "./path/to/file",
+                "start": { "line": 3, "col": 12 },
+                "end": { "line": 3, "col": 13 },
+                "message": "Identifier \"a\" is undefined"
+            })
+        );
+    }
+
+    #[test]
+    fn syntax_error_diagnostics() {
+        let source = r#"
+def main() -> field:
+    return 1 +
+"#
+        .to_string();
+        let res: Result<CompilationArtifacts<Bn128Field>, CompileErrors> = compile(
+            source,
+            "./path/to/file".into(),
+            None::<&dyn Resolver<io::Error>>,
+            &CompileConfig::default(),
+        );
+        let diagnostic = res.unwrap_err().0[0].diagnostic();
+        assert_eq!(diagnostic.code, ErrorCode::SyntaxError);
+        assert_eq!(diagnostic.start.unwrap().line, 3);
+    }
+
+    #[test]
+    fn index() {
+        let source = r#"
+def foo(field a) -> field:
+    return a
+
+def main(field a) -> field:
+    field b = foo(a)
+    return b
+"#
+        .to_string();
+        let (res, index) = check_with_index::<Bn128Field, io::Error>(
+            source,
+            "main".into(),
+            None::<&dyn Resolver<io::Error>>,
+            None,
+        );
+        assert!(res.is_ok());
+
+        let module = PathBuf::from("main");
+
+        // `b` in `return b` refers to its declaration
+        let b = index
+            .reference_at(&module, Position { line: 7, col: 12 })
+            .unwrap();
+        assert_eq!(b.name, "b");
+        assert_eq!(b.ty, "field");
+        assert_eq!(
+            index.definition(b).unwrap().start,
+            Position { line: 6, col: 11 }
+        );
+
+        // `foo` in the call refers to the function declaration
+        let foo = index
+            .reference_at(&module, Position { line: 6, col: 15 })
+            .unwrap();
+        assert_eq!(foo.ty, "foo(field) -> field");
+        assert_eq!(index.definition(foo).unwrap().start.line, 2);
+
+        // in `main`, `a` and `b` are visible but not the `a` of `foo`
+        let visible = index.visible_at(&module, Position { line: 7, col: 5 });
+        assert_eq!(
+            visible,
+            vec![
+                (String::from("a"), Some(String::from("field"))),
+                (String::from("b"), Some(String::from("field"))),
+                (String::from("foo"), None),
+                (String::from("main"), None)
+            ]
+        );
+    }
+
+    #[test]
+    fn warnings() {
+        let main = r#"
+from "lib" import double
+from "lib" import triple
+
+def helper(field a) -> field:
+    return a
+
+def dead_caller() -> field:
+    return helper(1)
+
+def first<N>(field[N] a) -> field:
+    field unused = 0
+    return a[0]
+
+def used(field x, field y) -> field:
+    field w = 3
+    return double(x)
+
+def main(field a) -> field:
+    for field i in 0..2 do
+    endfor
+    return used(a, a) + first([a]) + first([a, a])
+"#;
+
+        struct CustomResolver;
+
+        impl<E> Resolver<E> for CustomResolver {
+            fn resolve(
+                &self,
+                _: PathBuf,
+                import_location: PathBuf,
+            ) -> Result<(String, PathBuf), E> {
+                assert_eq!(import_location, PathBuf::from("lib"));
+                Ok((
+                    r#"
+def double(field x) -> field:
+    field unused = 0
+    return x * 2
+
+def triple(field x) -> field:
+    return x * 3
+"#
+                    .into(),
+                    "lib".into(),
+                ))
+            }
+        }
+
+        let warnings = check::<Bn128Field, io::Error>(
+            main.to_string(),
+            "main".into(),
+            Some(&CustomResolver),
+            &CompileConfig::default(),
+        )
+        .unwrap();
+
+        // the warnings of `lib` are not reported
+        let diagnostics = warnings.diagnostics();
+        assert!(diagnostics.iter().all(|d| d.severity == Severity::Warning));
+        assert_eq!(
+            diagnostics
+                .iter()
+                .map(|d| (d.code, d.start.unwrap().line, d.message.as_str()))
+                .collect::<Vec<_>>(),
+            vec![
+                (ErrorCode::UnusedImport, 3, "Import `triple` is never used"),
+                (
+                    ErrorCode::UnreachableFunction,
+                    5,
+                    "Function `helper` is never called from `main`"
+                ),
+                (
+                    ErrorCode::UnreachableFunction,
+                    8,
+                    "Function `dead_caller` is never called from `main`"
+                ),
+                // reported once even though `first` has two instances
+                (
+                    ErrorCode::UnusedVariable,
+                    12,
+                    "Variable `unused` is never used"
+                ),
+                (
+                    ErrorCode::UnusedParameter,
+                    15,
+                    "Parameter `y` is never used"
+                ),
+                (ErrorCode::UnusedVariable, 16, "Variable `w` is never used"),
+            ]
+        );
+
+        // warnings can be turned into errors
+        let errors = check::<Bn128Field, io::Error>(
+            main.to_string(),
+            "main".into(),
+            Some(&CustomResolver),
+            &CompileConfig::default().with_deny_warnings(true),
+        )
+        .unwrap_err();
+
+        assert_eq!(errors.0.len(), 6);
+        assert_eq!(errors.diagnostics()[0].severity, Severity::Error);
+        assert_eq!(errors.diagnostics()[0].code, ErrorCode::UnusedImport);
+    }
+
+    #[test]
+    fn entry_points() {
+        use ir::Interpreter;
+
+        let source = r#"
+def double(field a) -> field:
+    return a * 2
+
+def verify_transfer(field a) -> field:
+    return double(a) + 1
+
+def main(field a) -> field:
+    return a
+"#;
+
+        let run = |artifacts: &CompilationArtifacts<Bn128Field>, input: u32| {
+            Interpreter::default()
+                .execute(artifacts.prog(), &vec![Bn128Field::from(input)])
+                .unwrap()
+                .return_values()
+        };
+
+        // compile a single entry point other than `main`
+        let artifacts = compile::<Bn128Field, io::Error>(
+            source.to_string(),
+            "main".into(),
+            None,
+            &CompileConfig::default().with_entry("verify_transfer"),
+        )
+        .unwrap();
+        assert_eq!(run(&artifacts, 3), vec![Bn128Field::from(7)]);
+        assert_eq!(artifacts.abi().inputs.len(), 1);
+
+        // compile several entry points at once, none of which is unreachable
+        let artifacts = compile_entries::<Bn128Field, io::Error>(
+            source.to_string(),
+            "main".into(),
+            None,
+            &CompileConfig::default()
+                .with_entries(vec!["main".to_string(), "verify_transfer".to_string()]),
+        )
+        .unwrap();
+        assert_eq!(artifacts.len(), 2);
+        assert!(artifacts[0].warnings().0.is_empty());
+        assert_eq!(run(&artifacts[0], 3), vec![Bn128Field::from(3)]);
+        assert_eq!(run(&artifacts[1], 3), vec![Bn128Field::from(7)]);
+
+        // entry points which are not compiled are unreachable
+        let warnings = check::<Bn128Field, io::Error>(
+            source.to_string(),
+            "main".into(),
+            None,
+            &CompileConfig::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            warnings
+                .diagnostics()
+                .iter()
+                .map(|d| d.message.as_str())
+                .collect::<Vec<_>>(),
+            vec![
+                "Function `double` is never called from `main`",
+                "Function `verify_transfer` is never called from `main`"
+            ]
+        );
+
+        // entry points must exist in the main module
+        let errors = compile::<Bn128Field, io::Error>(
+            source.to_string(),
+            "main".into(),
+            None,
+            &CompileConfig::default().with_entry("verify_mint"),
+        )
+        .unwrap_err();
+        assert_eq!(errors.diagnostics()[0].code, ErrorCode::MainNotFound);
+        assert_eq!(
+            errors.diagnostics()[0].message,
+            "No function `verify_mint` found"
+        );
+    }
+
+    #[test]
+    fn analysis_errors() {
+        let compile_error = |source: &str| {
+            let errors = compile::<Bn128Field, io::Error>(
+                source.to_string(),
+                "main".into(),
+                None,
+                &CompileConfig::default(),
+            )
+            .unwrap_err();
+            let diagnostics = errors.diagnostics();
+            assert_eq!(diagnostics.len(), 1);
+            let d = diagnostics[0].clone();
+            (d.code, d.file, d.start.map(|p| p.line), d.message)
+        };
+
+        // out of bounds access after unrolling
+        assert_eq!(
+            compile_error(
+                r#"
+def main(field[2] a) -> field:
+    field res = 0
+    for field i in 0..3 do
+        res = res + a[i]
+    endfor
+    return res
+"#
+            ),
+            (
+                ErrorCode::OutOfBounds,
+                "main".to_string(),
+                Some(5),
+                "Index 2 is out of bounds for an array of size 2".to_string()
+            )
+        );
+
+        // an index which wraps around the field is too large for any array
+        assert_eq!(
+            compile_error(
+                r#"
+def main(field[2] a) -> field:
+    field res = 0
+    for field i in 0..2 do
+        res = res + a[i - 1]
+    endfor
+    return res
+"#
+            ),
+            (
+                ErrorCode::OutOfBounds,
+                "main".to_string(),
+                Some(5),
+                "Index -1 is out of bounds for an array of size 2".to_string()
+            )
+        );
+
+        // a negative index
+        assert_eq!(
+            compile_error(
+                r#"
+def main(field[2] a) -> field:
+    return a[0 - 1]
+"#
+            )
+            .3,
+            "Index -1 is out of bounds for an array of size 2".to_string()
+        );
+
+        // a field element divided by a constant zero
+        assert_eq!(
+            compile_error(
+                r#"
+def main(field a) -> field:
+    return a / 0
+"#
+            ),
+            (
+                ErrorCode::DivisionByZero,
+                "main".to_string(),
+                Some(3),
+                "Division by zero".to_string()
+            )
+        );
+
+        // including in the value of a constant
+        assert_eq!(
+            compile_error(
+                r#"
+const field INVALID = 1 / 0
+
+def main() -> field:
+    return INVALID
+"#
+            ),
+            (
+                ErrorCode::DivisionByZero,
+                "main".to_string(),
+                Some(2),
+                "Division by zero".to_string()
+            )
+        );
+
+        // accesses in branches which are not taken are not checked
+        assert!(compile::<Bn128Field, io::Error>(
+            r#"
+def main(field[2] a) -> field:
+    field res = 0
+    for field i in 0..3 do
+        res = res + if i < 2 then a[i] else 0 fi
+        if i < 2 then
+            assert(a[i] == 1)
+        fi
+    endfor
+    return res
+"#
+            .to_string(),
+            "main".into(),
+            None,
+            &CompileConfig::default(),
+        )
+        .is_ok());
+
+        // loop bound which is not constant
+        assert_eq!(
+            compile_error(
+                r#"
+def main(field n) -> field:
+    field res = 0
+    for field i in 0..n do
+        res = res + 1
+    endfor
+    return res
+"#
+            ),
+            (
+                ErrorCode::NonConstantBound,
+                "main".to_string(),
+                Some(4),
+                "Loop unrolling failed. This happened because a loop bound is not constant"
+                    .to_string()
+            )
+        );
+
+        // unconstrained private inputs are reported by name, at the first of them
+        assert_eq!(
+            compile_error(
+                r#"
+def main(field a, private field[2] b, private field c) -> field:
+    return a + c
+"#
+            ),
+            (
+                ErrorCode::UnconstrainedInput,
+                "main".to_string(),
+                Some(2),
+                "Private inputs must be constrained, found unconstrained inputs: `b`".to_string()
+            )
+        );
+
+        // functions which can fail cannot be called in a branch, even through another function
+        assert_eq!(
+            compile_error(
+                r#"
+def non_zero(field a) -> field:
+    assert(a != 0)
+    return a
+
+def id(field a) -> field:
+    return non_zero(a)
+
+def main(field a) -> field:
+    field res = 0
+    if a != 0 then
+        res = id(a)
+    fi
+    return res
+"#
+            ),
+            (
+                ErrorCode::GuardedCall,
+                "main".to_string(),
+                Some(12),
+                "Function `id` contains assertions or divisions and cannot be called in an if statement or in a loop with `break` or `continue`".to_string()
+            )
+        );
+
+        // nor in a loop with early exits
+        assert_eq!(
+            compile_error(
+                r#"
+def inverse(field a) -> field:
+    return 1 / a
+
+def main(field a) -> field:
+    field res = 0
+    for field i in 0..2 do
+        break if a == 0
+        res = res + inverse(a)
+    endfor
+    return res
+"#
+            )
+            .0,
+            ErrorCode::GuardedCall
+        );
+
+        // functions which cannot fail can be called anywhere
+        assert!(compile::<Bn128Field, io::Error>(
+            r#"
+def double(field a) -> field:
+    return a * 2
+
+def main(field a) -> field:
+    field res = 0
+    if a != 0 then
+        res = double(a)
+    fi
+    for field i in 0..2 do
+        break if a == 0
+        res = res + double(a)
+    endfor
+    return res
+"#
+            .to_string(),
+            "main".into(),
+            None,
+            &CompileConfig::default(),
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn cache() {
+        struct CustomResolver(Vec<(&'static str, &'static str)>);
+
+        impl<E> Resolver<E> for CustomResolver {
+            fn resolve(
+                &self,
+                _: PathBuf,
+                import_location: PathBuf,
+            ) -> Result<(String, PathBuf), E> {
+                let (location, source) = self
+                    .0
+                    .iter()
+                    .find(|(location, _)| import_location == Path::new(location))
+                    .unwrap();
+                Ok((source.to_string(), location.into()))
+            }
+        }
+
+        let dir = std::env::temp_dir().join(format!("zokrates_cache_{}", std::process::id()));
+        let config = CompileConfig::default().with_cache(Cache::new(&dir));
+
+        let compile_with = |lib: &'static str, dep: &'static str| {
+            compile::<Bn128Field, io::Error>(
+                "from \"lib\" import foo\ndef main() -> field:\n    return foo()\n".to_string(),
+                "main".into(),
+                Some(&CustomResolver(vec![("lib", lib), ("dep", dep)])),
+                &config,
+            )
+            .unwrap()
+        };
+        let entries = || {
+            let mut entries: Vec<_> = std::fs::read_dir(&dir)
+                .unwrap()
+                .map(|e| e.unwrap().path())
+                .collect();
+            entries.sort();
+            entries
+        };
+
+        let lib = "from \"dep\" import bar\ndef foo() -> field:\n    return bar()\n";
+        let one = "def bar() -> field:\n    return 1\n";
+        let two = "def bar() -> field:\n    return 2\n";
+
+        // the imported modules are stored, but not the main one
+        let first = compile_with(lib, one);
+        let first_entries = entries();
+        assert_eq!(first_entries.len(), 2);
+
+        // changing a module changes its key, and the modules importing it are checked again
+        let second = compile_with(lib, two);
+        assert_ne!(first.prog(), second.prog());
+        let new_entries: Vec<_> = entries()
+            .into_iter()
+            .filter(|e| !first_entries.contains(e))
+            .collect();
+        assert_eq!(new_entries.len(), 1);
+
+        // compiling the same sources again reuses the checked modules, which we replace to observe it
+        let first_dep_entry = first_entries
+            .iter()
+            .find(|e| {
+                std::fs::read_to_string(e)
+                    .unwrap()
+                    .starts_with("{\"imports\":[]")
+            })
+            .unwrap();
+        std::fs::copy(&new_entries[0], first_dep_entry).unwrap();
+        assert_eq!(compile_with(lib, one).prog(), second.prog());
+
+        // modules with generic functions are checked again for each instance, so they are not stored
+        compile_with(
+            "def bar<N>(field[N] a) -> field:\n    return a[0]\ndef foo() -> field:\n    return bar([1])\n",
+            one,
+        );
+        assert_eq!(entries().len(), 3);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
     mod abi {
         use super::*;
         use typed_absy::abi::*;
@@ -390,6 +1466,7 @@ struct Bar { field a }
                     inputs: vec![AbiInput {
                         name: "f".into(),
                         public: true,
+                        alias: None,
                         ty: Type::Struct(StructType {
                             module: "main".into(),
                             name: "FooMain".into(),
@@ -410,5 +1487,48 @@ struct Bar { field a }
                 }
             );
         }
+
+        #[test]
+        fn use_alias_names() {
+            let source = r#"
+type Digest = u32[8]
+def main(Digest d, private field x) -> Digest:
+    assert(x == 1)
+    return d
+"#
+            .to_string();
+
+            let artifacts = compile::<Bn128Field, io::Error>(
+                source,
+                "main".into(),
+                None,
+                &CompileConfig::default(),
+            )
+            .unwrap();
+
+            assert_eq!(
+                artifacts.abi,
+                Abi {
+                    inputs: vec![
+                        AbiInput {
+                            name: "d".into(),
+                            public: true,
+                            alias: Some("Digest".into()),
+                            ty: Type::array(Type::uint(32), 8)
+                        },
+                        AbiInput {
+                            name: "x".into(),
+                            public: false,
+                            alias: None,
+                            ty: Type::FieldElement
+                        }
+                    ],
+                    outputs: vec![AbiOutput {
+                        alias: Some("Digest".into()),
+                        ty: Type::array(Type::uint(32), 8)
+                    }]
+                }
+            );
+        }
     }
 }
@@ -0,0 +1,174 @@
+//! Machine-readable reports of the problems found in a program, for editors and CI tools
+
+pub use crate::parser::Position;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::str::FromStr;
+
+/// A stable identifier for a kind of problem. Codes are never reused once published, so that tools can rely on them
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorCode {
+    // Parsing and reading sources
+    SyntaxError,
+    ReadError,
+    // Resolving imports
+    EmbedNotFound,
+    ImportAlias,
+    ImportResolution,
+    MissingResolver,
+    // Processing macros
+    IncompatibleCurve,
+    // Semantic checks
+    SymbolConflict,
+    UndefinedIdentifier,
+    UndeclaredVariable,
+    UndefinedType,
+    SymbolNotFound,
+    MainNotFound,
+    MultipleMains,
+    GenericMain,
+    FunctionNotFound,
+    TypeMismatch,
+    InvalidOperands,
+    InvalidAccess,
+    OutOfBounds,
+    ValueOutOfRange,
+    DuplicateDeclaration,
+    NonConstantExpression,
+    UnusedGeneric,
+    UnsupportedType,
+    InvalidStatement,
+    // Static analysis
+    NonConstantBound,
+    UnconstrainedInput,
+    DivisionByZero,
+    GuardedCall,
+    // Warnings
+    UnusedVariable,
+    UnusedParameter,
+    UnusedImport,
+    UnreachableFunction,
+}
+
+impl ErrorCode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ErrorCode::SyntaxError => "E0001",
+            ErrorCode::ReadError => "E0002",
+            ErrorCode::EmbedNotFound => "E0101",
+            ErrorCode::ImportAlias => "E0102",
+            ErrorCode::ImportResolution => "E0103",
+            ErrorCode::MissingResolver => "E0104",
+            ErrorCode::IncompatibleCurve => "E0201",
+            ErrorCode::SymbolConflict => "E0301",
+            ErrorCode::UndefinedIdentifier => "E0302",
+            ErrorCode::UndeclaredVariable => "E0303",
+            ErrorCode::UndefinedType => "E0304",
+            ErrorCode::SymbolNotFound => "E0305",
+            ErrorCode::MainNotFound => "E0306",
+            ErrorCode::MultipleMains => "E0307",
+            ErrorCode::GenericMain => "E0308",
+            ErrorCode::FunctionNotFound => "E0309",
+            ErrorCode::TypeMismatch => "E0310",
+            ErrorCode::InvalidOperands => "E0311",
+            ErrorCode::InvalidAccess => "E0312",
+            ErrorCode::OutOfBounds => "E0313",
+            ErrorCode::ValueOutOfRange => "E0314",
+            ErrorCode::DuplicateDeclaration => "E0315",
+            ErrorCode::NonConstantExpression => "E0316",
+            ErrorCode::UnusedGeneric => "E0317",
+            ErrorCode::UnsupportedType => "E0318",
+            ErrorCode::InvalidStatement => "E0319",
+            ErrorCode::NonConstantBound => "E0402",
+            ErrorCode::UnconstrainedInput => "E0403",
+            ErrorCode::DivisionByZero => "E0404",
+            ErrorCode::GuardedCall => "E0405",
+            ErrorCode::UnusedVariable => "W0001",
+            ErrorCode::UnusedParameter => "W0002",
+            ErrorCode::UnusedImport => "W0003",
+            ErrorCode::UnreachableFunction => "W0004",
+        }
+    }
+}
+
+impl fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for ErrorCode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "E0001" => Ok(ErrorCode::SyntaxError),
+            "E0002" => Ok(ErrorCode::ReadError),
+            "E0101" => Ok(ErrorCode::EmbedNotFound),
+            "E0102" => Ok(ErrorCode::ImportAlias),
+            "E0103" => Ok(ErrorCode::ImportResolution),
+            "E0104" => Ok(ErrorCode::MissingResolver),
+            "E0201" => Ok(ErrorCode::IncompatibleCurve),
+            "E0301" => Ok(ErrorCode::SymbolConflict),
+            "E0302" => Ok(ErrorCode::UndefinedIdentifier),
+            "E0303" => Ok(ErrorCode::UndeclaredVariable),
+            "E0304" => Ok(ErrorCode::UndefinedType),
+            "E0305" => Ok(ErrorCode::SymbolNotFound),
+            "E0306" => Ok(ErrorCode::MainNotFound),
+            "E0307" => Ok(ErrorCode::MultipleMains),
+            "E0308" => Ok(ErrorCode::GenericMain),
+            "E0309" => Ok(ErrorCode::FunctionNotFound),
+            "E0310" => Ok(ErrorCode::TypeMismatch),
+            "E0311" => Ok(ErrorCode::InvalidOperands),
+            "E0312" => Ok(ErrorCode::InvalidAccess),
+            "E0313" => Ok(ErrorCode::OutOfBounds),
+            "E0314" => Ok(ErrorCode::ValueOutOfRange),
+            "E0315" => Ok(ErrorCode::DuplicateDeclaration),
+            "E0316" => Ok(ErrorCode::NonConstantExpression),
+            "E0317" => Ok(ErrorCode::UnusedGeneric),
+            "E0318" => Ok(ErrorCode::UnsupportedType),
+            "E0319" => Ok(ErrorCode::InvalidStatement),
+            "E0402" => Ok(ErrorCode::NonConstantBound),
+            "E0403" => Ok(ErrorCode::UnconstrainedInput),
+            "E0404" => Ok(ErrorCode::DivisionByZero),
+            "E0405" => Ok(ErrorCode::GuardedCall),
+            "W0001" => Ok(ErrorCode::UnusedVariable),
+            "W0002" => Ok(ErrorCode::UnusedParameter),
+            "W0003" => Ok(ErrorCode::UnusedImport),
+            "W0004" => Ok(ErrorCode::UnreachableFunction),
+            _ => Err(format!("Unknown error code {}", s)),
+        }
+    }
+}
+
+impl Serialize for ErrorCode {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for ErrorCode {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub code: ErrorCode,
+    pub file: String,
+    /// The span of the problem in the source, if it is known
+    pub start: Option<Position>,
+    pub end: Option<Position>,
+    pub message: String,
+}
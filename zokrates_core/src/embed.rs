@@ -9,15 +9,17 @@ use zokrates_field::Field;
 
 /// A low level function that contains non-deterministic introduction of variables. It is carried out as is until
 /// the flattening step when it can be inlined.
-#[derive(Debug, Clone, PartialEq, Hash)]
+#[derive(Debug, Clone, PartialEq, Hash, Serialize, Deserialize)]
 pub enum FlatEmbed {
     Unpack(usize),
     U8ToBits,
     U16ToBits,
     U32ToBits,
+    U64ToBits,
     U8FromBits,
     U16FromBits,
     U32FromBits,
+    U64FromBits,
 }
 
 impl FlatEmbed {
@@ -35,6 +37,9 @@ impl FlatEmbed {
             FlatEmbed::U32ToBits => Signature::new()
                 .inputs(vec![Type::uint(32)])
                 .outputs(vec![Type::array(Type::Boolean, 32)]),
+            FlatEmbed::U64ToBits => Signature::new()
+                .inputs(vec![Type::uint(64)])
+                .outputs(vec![Type::array(Type::Boolean, 64)]),
             FlatEmbed::U8FromBits => Signature::new()
                 .outputs(vec![Type::uint(8)])
                 .inputs(vec![Type::array(Type::Boolean, 8)]),
@@ -44,6 +49,9 @@ impl FlatEmbed {
             FlatEmbed::U32FromBits => Signature::new()
                 .outputs(vec![Type::uint(32)])
                 .inputs(vec![Type::array(Type::Boolean, 32)]),
+            FlatEmbed::U64FromBits => Signature::new()
+                .outputs(vec![Type::uint(64)])
+                .inputs(vec![Type::array(Type::Boolean, 64)]),
         }
     }
 
@@ -57,9 +65,11 @@ impl FlatEmbed {
             FlatEmbed::U8ToBits => "_U8_TO_BITS",
             FlatEmbed::U16ToBits => "_U16_TO_BITS",
             FlatEmbed::U32ToBits => "_U32_TO_BITS",
+            FlatEmbed::U64ToBits => "_U64_TO_BITS",
             FlatEmbed::U8FromBits => "_U8_FROM_BITS",
             FlatEmbed::U16FromBits => "_U16_FROM_BITS",
             FlatEmbed::U32FromBits => "_U32_FROM_BITS",
+            FlatEmbed::U64FromBits => "_U64_FROM_BITS",
         }
     }
 
@@ -133,6 +143,7 @@ pub fn unpack_to_bitwidth<T: Field>(bit_width: usize) -> FlatFunction<T> {
             FlatStatement::Condition(
                 bit.clone(),
                 FlatExpression::Mult(box bit.clone(), box bit.clone()),
+                None,
             )
         })
         .collect();
@@ -156,6 +167,7 @@ pub fn unpack_to_bitwidth<T: Field>(bit_width: usize) -> FlatFunction<T> {
             box FlatExpression::Identifier(FlatVariable::new(0)),
             box FlatExpression::Number(T::from(1)),
         ),
+        None,
     ));
 
     statements.insert(
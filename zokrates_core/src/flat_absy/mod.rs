@@ -12,6 +12,7 @@ pub use self::flat_parameter::FlatParameter;
 pub use self::flat_variable::FlatVariable;
 
 use solvers::Solver;
+use source_map::{Origin, SourceMetadata};
 use std::collections::HashMap;
 use std::fmt;
 use zokrates_field::Field;
@@ -89,9 +90,11 @@ impl<T: Field> fmt::Debug for FlatFunction<T> {
 #[derive(Clone, PartialEq)]
 pub enum FlatStatement<T: Field> {
     Return(FlatExpressionList<T>),
-    Condition(FlatExpression<T>, FlatExpression<T>),
+    Condition(FlatExpression<T>, FlatExpression<T>, Option<SourceMetadata>),
     Definition(FlatVariable, FlatExpression<T>),
     Directive(FlatDirective<T>),
+    /// Marks the origin of the statements which follow it, when profiling
+    Origin(Origin),
 }
 
 impl<T: Field> fmt::Display for FlatStatement<T> {
@@ -99,8 +102,9 @@ impl<T: Field> fmt::Display for FlatStatement<T> {
         match *self {
             FlatStatement::Definition(ref lhs, ref rhs) => write!(f, "{} = {}", lhs, rhs),
             FlatStatement::Return(ref expr) => write!(f, "return {}", expr),
-            FlatStatement::Condition(ref lhs, ref rhs) => write!(f, "{} == {}", lhs, rhs),
+            FlatStatement::Condition(ref lhs, ref rhs, _) => write!(f, "{} == {}", lhs, rhs),
             FlatStatement::Directive(ref d) => write!(f, "{}", d),
+            FlatStatement::Origin(ref origin) => write!(f, "// {}", origin),
         }
     }
 }
@@ -110,10 +114,11 @@ impl<T: Field> fmt::Debug for FlatStatement<T> {
         match *self {
             FlatStatement::Definition(ref lhs, ref rhs) => write!(f, "{} = {}", lhs, rhs),
             FlatStatement::Return(ref expr) => write!(f, "FlatReturn({:?})", expr),
-            FlatStatement::Condition(ref lhs, ref rhs) => {
-                write!(f, "FlatCondition({:?}, {:?})", lhs, rhs)
+            FlatStatement::Condition(ref lhs, ref rhs, ref metadata) => {
+                write!(f, "FlatCondition({:?}, {:?}, {:?})", lhs, rhs, metadata)
             }
             FlatStatement::Directive(ref d) => write!(f, "{:?}", d),
+            FlatStatement::Origin(ref origin) => write!(f, "Origin({:?})", origin),
         }
     }
 }
@@ -129,9 +134,10 @@ impl<T: Field> FlatStatement<T> {
                 x.apply_substitution(substitution),
             ),
             FlatStatement::Return(x) => FlatStatement::Return(x.apply_substitution(substitution)),
-            FlatStatement::Condition(x, y) => FlatStatement::Condition(
+            FlatStatement::Condition(x, y, metadata) => FlatStatement::Condition(
                 x.apply_substitution(substitution),
                 y.apply_substitution(substitution),
+                metadata,
             ),
             FlatStatement::Directive(d) => {
                 let outputs = d
@@ -151,6 +157,7 @@ impl<T: Field> FlatStatement<T> {
                     ..d
                 })
             }
+            FlatStatement::Origin(origin) => FlatStatement::Origin(origin),
         }
     }
 }
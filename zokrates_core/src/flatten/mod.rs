@@ -276,6 +276,7 @@ impl<'ast, T: Field> Flattener<'ast, T> {
                 statements_flattened.push(FlatStatement::Condition(
                     FlatExpression::Number(T::from(1)),
                     or,
+                    None,
                 ));
             }
         }
@@ -416,6 +417,7 @@ impl<'ast, T: Field> Flattener<'ast, T> {
                                 box FlatExpression::Identifier(lhs_bits_be[i]),
                                 box FlatExpression::Identifier(lhs_bits_be[i]),
                             ),
+                            None,
                         ));
                     }
 
@@ -435,6 +437,7 @@ impl<'ast, T: Field> Flattener<'ast, T> {
                     statements_flattened.push(FlatStatement::Condition(
                         FlatExpression::Identifier(lhs_id),
                         lhs_sum,
+                        None,
                     ));
                 }
 
@@ -462,6 +465,7 @@ impl<'ast, T: Field> Flattener<'ast, T> {
                                 box FlatExpression::Identifier(rhs_bits_be[i]),
                                 box FlatExpression::Identifier(rhs_bits_be[i]),
                             ),
+                            None,
                         ));
                     }
 
@@ -481,6 +485,7 @@ impl<'ast, T: Field> Flattener<'ast, T> {
                     statements_flattened.push(FlatStatement::Condition(
                         FlatExpression::Identifier(rhs_id),
                         rhs_sum,
+                        None,
                     ));
                 }
 
@@ -515,6 +520,7 @@ impl<'ast, T: Field> Flattener<'ast, T> {
                             box FlatExpression::Identifier(sub_bits_be[i]),
                             box FlatExpression::Identifier(sub_bits_be[i]),
                         ),
+                        None,
                     ));
                 }
 
@@ -538,7 +544,7 @@ impl<'ast, T: Field> Flattener<'ast, T> {
                     );
                 }
 
-                statements_flattened.push(FlatStatement::Condition(subtraction_result, expr));
+                statements_flattened.push(FlatStatement::Condition(subtraction_result, expr, None));
 
                 FlatExpression::Identifier(sub_bits_be[bit_width - 1])
             }
@@ -599,6 +605,7 @@ impl<'ast, T: Field> Flattener<'ast, T> {
                 statements_flattened.push(FlatStatement::Condition(
                     FlatExpression::Identifier(name_y),
                     FlatExpression::Mult(box x.clone(), box FlatExpression::Identifier(name_m)),
+                    None,
                 ));
 
                 let res = FlatExpression::Sub(
@@ -609,6 +616,7 @@ impl<'ast, T: Field> Flattener<'ast, T> {
                 statements_flattened.push(FlatStatement::Condition(
                     FlatExpression::Number(T::zero()),
                     FlatExpression::Mult(box res.clone(), box x),
+                    None,
                 ));
 
                 res
@@ -646,6 +654,7 @@ impl<'ast, T: Field> Flattener<'ast, T> {
                 statements_flattened.push(FlatStatement::Condition(
                     FlatExpression::Identifier(name_y),
                     FlatExpression::Mult(box x.clone(), box FlatExpression::Identifier(name_m)),
+                    None,
                 ));
 
                 let res = FlatExpression::Sub(
@@ -656,10 +665,39 @@ impl<'ast, T: Field> Flattener<'ast, T> {
                 statements_flattened.push(FlatStatement::Condition(
                     FlatExpression::Number(T::zero()),
                     FlatExpression::Mult(box res.clone(), box x),
+                    None,
                 ));
 
                 res
             }
+            BooleanExpression::UintLt(box lhs, box rhs) => self.flatten_uint_comparison(
+                symbols,
+                statements_flattened,
+                Comparison::Lt,
+                lhs,
+                rhs,
+            ),
+            BooleanExpression::UintLe(box lhs, box rhs) => self.flatten_uint_comparison(
+                symbols,
+                statements_flattened,
+                Comparison::Le,
+                lhs,
+                rhs,
+            ),
+            BooleanExpression::UintGe(box lhs, box rhs) => self.flatten_uint_comparison(
+                symbols,
+                statements_flattened,
+                Comparison::Ge,
+                lhs,
+                rhs,
+            ),
+            BooleanExpression::UintGt(box lhs, box rhs) => self.flatten_uint_comparison(
+                symbols,
+                statements_flattened,
+                Comparison::Gt,
+                lhs,
+                rhs,
+            ),
             BooleanExpression::Le(box lhs, box rhs) => {
                 let lt = self.flatten_boolean_expression(
                     symbols,
@@ -699,6 +737,7 @@ impl<'ast, T: Field> Flattener<'ast, T> {
                         box FlatExpression::Sub(box y.clone(), box name_x_or_y.clone().into()),
                     ),
                     FlatExpression::Mult(box x.clone(), box y.clone()),
+                    None,
                 ));
                 name_x_or_y.into()
             }
@@ -799,6 +838,12 @@ impl<'ast, T: Field> Flattener<'ast, T> {
         let funct = self.get_embed(&key, &symbols);
 
         match funct {
+            crate::embed::FlatEmbed::U64ToBits => self.flatten_u_to_bits(
+                symbols,
+                statements_flattened,
+                param_expressions[0].clone(),
+                64.into(),
+            ),
             crate::embed::FlatEmbed::U32ToBits => self.flatten_u_to_bits(
                 symbols,
                 statements_flattened,
@@ -817,6 +862,12 @@ impl<'ast, T: Field> Flattener<'ast, T> {
                 param_expressions[0].clone(),
                 8.into(),
             ),
+            crate::embed::FlatEmbed::U64FromBits => vec![self.flatten_bits_to_u(
+                symbols,
+                statements_flattened,
+                param_expressions,
+                64.into(),
+            )],
             crate::embed::FlatEmbed::U32FromBits => vec![self.flatten_bits_to_u(
                 symbols,
                 statements_flattened,
@@ -878,10 +929,10 @@ impl<'ast, T: Field> Flattener<'ast, T> {
                             let new_rhs = rhs.apply_substitution(&replacement_map);
                             FlatStatement::Definition(new_var, new_rhs)
                         }
-                        FlatStatement::Condition(lhs, rhs) => {
+                        FlatStatement::Condition(lhs, rhs, metadata) => {
                             let new_lhs = lhs.apply_substitution(&replacement_map);
                             let new_rhs = rhs.apply_substitution(&replacement_map);
-                            FlatStatement::Condition(new_lhs, new_rhs)
+                            FlatStatement::Condition(new_lhs, new_rhs, metadata)
                         }
                         FlatStatement::Directive(d) => {
                             let new_outputs = d
@@ -904,6 +955,7 @@ impl<'ast, T: Field> Flattener<'ast, T> {
                                 inputs: new_inputs,
                             })
                         }
+                        FlatStatement::Origin(origin) => FlatStatement::Origin(origin),
                     })
                     .collect();
 
@@ -1001,6 +1053,7 @@ impl<'ast, T: Field> Flattener<'ast, T> {
                                 box FlatExpression::Add(box x.clone(), box x.clone()),
                                 box y.clone(),
                             ),
+                            None,
                         ),
                     ]);
 
@@ -1012,6 +1065,212 @@ impl<'ast, T: Field> Flattener<'ast, T> {
         FlatUExpression::with_bits(xor)
     }
 
+    /// Flattens a comparison between two unsigned integers
+    ///
+    /// # Arguments
+    ///
+    /// * `symbols` - Available functions in this context
+    /// * `statements_flattened` - Vector where new flattened statements can be added.
+    /// * `op` - the comparison to apply
+    /// * `lhs` - `UExpression` of the left operand
+    /// * `rhs` - `UExpression` of the right operand
+    fn flatten_uint_comparison(
+        &mut self,
+        symbols: &ZirFunctionSymbols<'ast, T>,
+        statements_flattened: &mut FlatStatements<T>,
+        op: Comparison,
+        lhs: UExpression<'ast, T>,
+        rhs: UExpression<'ast, T>,
+    ) -> FlatExpression<T> {
+        // `lhs` and `rhs` were reduced to the target bitwidth, hence their bits are available
+        assert!(lhs.metadata.clone().unwrap().should_reduce.to_bool());
+        assert!(rhs.metadata.clone().unwrap().should_reduce.to_bool());
+
+        let lhs = self
+            .flatten_uint_expression(symbols, statements_flattened, lhs)
+            .bits
+            .unwrap();
+        let rhs = self
+            .flatten_uint_expression(symbols, statements_flattened, rhs)
+            .bits
+            .unwrap();
+
+        let not = |e| FlatExpression::Sub(box FlatExpression::Number(T::one()), box e);
+
+        match op {
+            Comparison::Lt => self.flatten_uint_lt(statements_flattened, lhs, rhs),
+            Comparison::Le => not(self.flatten_uint_lt(statements_flattened, rhs, lhs)),
+            Comparison::Ge => not(self.flatten_uint_lt(statements_flattened, lhs, rhs)),
+            Comparison::Gt => self.flatten_uint_lt(statements_flattened, rhs, lhs),
+        }
+    }
+
+    /// Flattens a strict comparison between two unsigned integers given by their bits
+    ///
+    /// # Arguments
+    ///
+    /// * `statements_flattened` - Vector where new flattened statements can be added.
+    /// * `lhs` - the bits of the left operand, most significant first
+    /// * `rhs` - the bits of the right operand, most significant first
+    fn flatten_uint_lt(
+        &mut self,
+        statements_flattened: &mut FlatStatements<T>,
+        lhs: Vec<FlatExpression<T>>,
+        rhs: Vec<FlatExpression<T>>,
+    ) -> FlatExpression<T> {
+        // Wanted: (Y = (a < b) ? 1 : 0)
+        // Going from the least significant bit, a < b holds on the bits seen so far if the current bits are
+        // equal and it held on the previous bits, or if they differ and the bit of b is set:
+        // Y_0 = 0
+        // EQ_i = 1 - a_i - b_i + 2 * a_i * b_i
+        // Y_{i+1} = b_i + EQ_i * (Y_i - b_i)
+        // The product of the bits is known when one of them is constant, so this costs at most two
+        // constraints per bit and no decomposition
+
+        assert_eq!(lhs.len(), rhs.len());
+
+        lhs.into_iter().zip(rhs.into_iter()).rev().fold(
+            FlatExpression::Number(T::zero()),
+            |lt, (a, b)| {
+                let and = match (&a, &b) {
+                    (FlatExpression::Number(n), e) | (e, FlatExpression::Number(n)) => {
+                        match *n == T::zero() {
+                            true => FlatExpression::Number(T::zero()),
+                            false => e.clone(),
+                        }
+                    }
+                    _ => self
+                        .define(
+                            FlatExpression::Mult(box a.clone(), box b.clone()),
+                            statements_flattened,
+                        )
+                        .into(),
+                };
+
+                let eq = FlatExpression::Add(
+                    box FlatExpression::Sub(
+                        box FlatExpression::Sub(box FlatExpression::Number(T::one()), box a),
+                        box b.clone(),
+                    ),
+                    box FlatExpression::Add(box and.clone(), box and),
+                );
+
+                let carry = self.define(
+                    FlatExpression::Mult(
+                        box eq,
+                        box FlatExpression::Sub(box lt, box b.clone()),
+                    ),
+                    statements_flattened,
+                );
+
+                FlatExpression::Add(box b, box carry.into())
+            },
+        )
+    }
+
+    /// Flattens the euclidean division of two unsigned integers
+    ///
+    /// # Arguments
+    ///
+    /// * `symbols` - Available functions in this context
+    /// * `statements_flattened` - Vector where new flattened statements can be added.
+    /// * `left` - `UExpression` of the dividend
+    /// * `right` - `UExpression` of the divisor
+    ///
+    /// # Returns
+    ///
+    /// * the quotient and the remainder, both decomposed on the bitwidth of the operands
+    fn flatten_uint_euclidean_div(
+        &mut self,
+        symbols: &ZirFunctionSymbols<'ast, T>,
+        statements_flattened: &mut FlatStatements<T>,
+        left: UExpression<'ast, T>,
+        right: UExpression<'ast, T>,
+    ) -> (FlatUExpression<T>, FlatUExpression<T>) {
+        // Wanted: (Q, R) such that L == Q * D + R and R < D
+        // # Q, R = euclidean_div(L, D)
+        // Q and R are decomposed on bitwidth bits
+        // L == Q * D + R
+        // R < D, which also rules out D == 0
+
+        let bitwidth = left.bitwidth;
+
+        assert!(left.metadata.clone().unwrap().should_reduce.to_bool());
+        assert!(right.metadata.clone().unwrap().should_reduce.to_bool());
+
+        let left_flattened = self
+            .flatten_uint_expression(symbols, statements_flattened, left)
+            .get_field_unchecked();
+        let right_flattened = self.flatten_uint_expression(symbols, statements_flattened, right);
+        let right_bits = right_flattened.bits.clone().unwrap();
+        let right_flattened = right_flattened.get_field_unchecked();
+        let new_left = if left_flattened.is_linear() {
+            left_flattened
+        } else {
+            let id = self.use_sym();
+            statements_flattened.push(FlatStatement::Definition(id, left_flattened));
+            FlatExpression::Identifier(id)
+        };
+        let new_right = if right_flattened.is_linear() {
+            right_flattened
+        } else {
+            let id = self.use_sym();
+            statements_flattened.push(FlatStatement::Definition(id, right_flattened));
+            FlatExpression::Identifier(id)
+        };
+
+        let q = self.use_sym();
+        let r = self.use_sym();
+
+        statements_flattened.push(FlatStatement::Directive(FlatDirective::new(
+            vec![q, r],
+            Solver::EuclideanDiv,
+            vec![new_left.clone(), new_right.clone()],
+        )));
+
+        let q = FlatExpression::Identifier(q);
+        let r = FlatExpression::Identifier(r);
+
+        // range checks
+        let q_bits = self.get_bits(
+            FlatUExpression::with_field(q.clone()),
+            bitwidth.to_usize(),
+            bitwidth,
+            statements_flattened,
+        );
+        let r_bits = self.get_bits(
+            FlatUExpression::with_field(r.clone()),
+            bitwidth.to_usize(),
+            bitwidth,
+            statements_flattened,
+        );
+
+        // L == Q * D + R
+        let qd = self.use_sym();
+        statements_flattened.push(FlatStatement::Definition(
+            qd,
+            FlatExpression::Mult(box q.clone(), box new_right.clone()),
+        ));
+        statements_flattened.push(FlatStatement::Condition(
+            new_left,
+            FlatExpression::Add(box FlatExpression::Identifier(qd), box r.clone()),
+            None,
+        ));
+
+        // R < D
+        let lt = self.flatten_uint_lt(statements_flattened, r_bits.clone(), right_bits);
+        statements_flattened.push(FlatStatement::Condition(
+            FlatExpression::Number(T::one()),
+            lt,
+            None,
+        ));
+
+        (
+            FlatUExpression::with_field(q).bits(q_bits),
+            FlatUExpression::with_field(r).bits(r_bits),
+        )
+    }
+
     /// Flattens a uint expression
     ///
     /// # Arguments
@@ -1040,7 +1299,7 @@ impl<'ast, T: Field> Flattener<'ast, T> {
 
         let res = match expr.into_inner() {
             UExpressionInner::Value(x) => {
-                FlatUExpression::with_field(FlatExpression::Number(T::from(x as usize)))
+                FlatUExpression::with_field(FlatExpression::Number(T::from(x)))
             } // force to be a field element
             UExpressionInner::Identifier(x) => {
                 let field = FlatExpression::Identifier(self.layout.get(&x).unwrap().clone());
@@ -1098,9 +1357,11 @@ impl<'ast, T: Field> Flattener<'ast, T> {
             }
             UExpressionInner::Sub(box left, box right) => {
                 // see uint optimizer for the reasoning here
-                let aux = FlatExpression::Number(
-                    T::from(2).pow(right.metadata.clone().unwrap().bitwidth() as usize),
-                );
+                // the offset must be a multiple of 2**target_bitwidth so that reducing the result is sound
+                let aux = FlatExpression::Number(T::from(2).pow(std::cmp::max(
+                    right.metadata.clone().unwrap().bitwidth() as usize,
+                    target_bitwidth.to_usize(),
+                )));
 
                 let left_flattened = self
                     .flatten_uint_expression(symbols, statements_flattened, left)
@@ -1208,6 +1469,14 @@ impl<'ast, T: Field> Flattener<'ast, T> {
 
                 FlatUExpression::with_field(FlatExpression::Identifier(res))
             }
+            UExpressionInner::Div(box left, box right) => {
+                self.flatten_uint_euclidean_div(symbols, statements_flattened, left, right)
+                    .0
+            }
+            UExpressionInner::Rem(box left, box right) => {
+                self.flatten_uint_euclidean_div(symbols, statements_flattened, left, right)
+                    .1
+            }
             UExpressionInner::IfElse(box condition, box consequence, box alternative) => self
                 .flatten_if_else_expression(
                     symbols,
@@ -1256,6 +1525,7 @@ impl<'ast, T: Field> Flattener<'ast, T> {
                                                 box a,
                                                 box FlatExpression::Sub(box b, box c),
                                             ),
+                                            None,
                                         ),
                                     ]);
                                     ch.into()
@@ -1330,6 +1600,7 @@ impl<'ast, T: Field> Flattener<'ast, T> {
                                                         box b.clone(),
                                                         box c.clone(),
                                                     ),
+                                                    None,
                                                 ),
                                                 FlatStatement::Condition(
                                                     FlatExpression::Sub(
@@ -1346,6 +1617,7 @@ impl<'ast, T: Field> Flattener<'ast, T> {
                                                         ),
                                                         box a,
                                                     ),
+                                                    None,
                                                 ),
                                             ]);
                                             maj.into()
@@ -1477,6 +1749,7 @@ impl<'ast, T: Field> Flattener<'ast, T> {
                                         ),
                                     ),
                                     FlatExpression::Mult(box x, box y),
+                                    None,
                                 ),
                             ]);
                             name.into()
@@ -1587,6 +1860,7 @@ impl<'ast, T: Field> Flattener<'ast, T> {
                                 box bits[i].clone().into(),
                                 box bits[i].clone().into(),
                             ),
+                            None,
                         )
                     }));
 
@@ -1596,6 +1870,7 @@ impl<'ast, T: Field> Flattener<'ast, T> {
                     statements_flattened.push(FlatStatement::Condition(
                         e.field.clone().unwrap(),
                         sum.clone(),
+                        None,
                     ));
 
                     // truncate to the `to` lowest bits
@@ -1725,6 +2000,7 @@ impl<'ast, T: Field> Flattener<'ast, T> {
                 statements_flattened.push(FlatStatement::Condition(
                     FlatExpression::Number(T::one()),
                     FlatExpression::Mult(box invb.into(), box new_right.clone().into()),
+                    None,
                 ));
 
                 // # c = a/b
@@ -1738,6 +2014,7 @@ impl<'ast, T: Field> Flattener<'ast, T> {
                 statements_flattened.push(FlatStatement::Condition(
                     new_left.into(),
                     FlatExpression::Mult(box new_right, box inverse.into()),
+                    None,
                 ));
 
                 inverse.into()
@@ -1848,6 +2125,9 @@ impl<'ast, T: Field> Flattener<'ast, T> {
         stat: ZirStatement<'ast, T>,
     ) {
         match stat {
+            ZirStatement::Origin(origin) => {
+                statements_flattened.push(FlatStatement::Origin(origin))
+            }
             ZirStatement::Return(exprs) => {
                 let flat_expressions = exprs
                     .into_iter()
@@ -1895,7 +2175,7 @@ impl<'ast, T: Field> Flattener<'ast, T> {
                     None => {}
                 }
             }
-            ZirStatement::Assertion(e) => {
+            ZirStatement::Assertion(e, metadata) => {
                 // naive approach: flatten the boolean to a single field element and constrain it to 1
 
                 let e = self.flatten_boolean_expression(symbols, statements_flattened, e);
@@ -1904,12 +2184,14 @@ impl<'ast, T: Field> Flattener<'ast, T> {
                     statements_flattened.push(FlatStatement::Condition(
                         e,
                         FlatExpression::Number(T::from(1)),
+                        metadata,
                     ));
                 } else {
                     // swap so that left side is linear
                     statements_flattened.push(FlatStatement::Condition(
                         FlatExpression::Number(T::from(1)),
                         e,
+                        metadata,
                     ));
                 }
             }
@@ -1947,7 +2229,14 @@ impl<'ast, T: Field> Flattener<'ast, T> {
                             })
                             .collect();
 
-                        if ["_U32_FROM_BITS", "_U16_FROM_BITS", "_U8_FROM_BITS"].contains(&key.id) {
+                        if [
+                            "_U64_FROM_BITS",
+                            "_U32_FROM_BITS",
+                            "_U16_FROM_BITS",
+                            "_U8_FROM_BITS",
+                        ]
+                        .contains(&key.id)
+                        {
                             let bits = exprs
                                 .into_iter()
                                 .map(|e| {
@@ -2073,6 +2362,7 @@ impl<'ast, T: Field> Flattener<'ast, T> {
                 statements_flattened.push(FlatStatement::Condition(
                     variable.into(),
                     FlatExpression::Mult(box variable.into(), box variable.into()),
+                    None,
                 ));
             }
             _ => {}
@@ -2475,6 +2765,7 @@ mod tests {
                 FlatStatement::Condition(
                     FlatExpression::Number(Bn128Field::from(1)),
                     FlatExpression::Mult(box invb0.into(), box b0.into()),
+                    None,
                 ),
                 // execute div
                 FlatStatement::Directive(FlatDirective::new(
@@ -2485,6 +2776,7 @@ mod tests {
                 FlatStatement::Condition(
                     five.into(),
                     FlatExpression::Mult(box b0.into(), box sym_0.into()),
+                    None,
                 ),
                 // inputs to second div (res/b)
                 FlatStatement::Definition(sym_1, sym_0.into()),
@@ -2498,6 +2790,7 @@ mod tests {
                 FlatStatement::Condition(
                     FlatExpression::Number(Bn128Field::from(1)),
                     FlatExpression::Mult(box invb1.into(), box b1.into()),
+                    None,
                 ),
                 // execute div
                 FlatStatement::Directive(FlatDirective::new(
@@ -2508,6 +2801,7 @@ mod tests {
                 FlatStatement::Condition(
                     sym_1.into(),
                     FlatExpression::Mult(box b1.into(), box sym_2.into()),
+                    None,
                 ),
             ]
         );
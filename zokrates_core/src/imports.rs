@@ -5,8 +5,10 @@
 //! @date 2018
 
 use crate::absy::*;
+use crate::cache::Session;
 use crate::compile::compile_module;
 use crate::compile::{CompileErrorInner, CompileErrors};
+use crate::diagnostics::ErrorCode;
 use crate::embed::FlatEmbed;
 use crate::parser::Position;
 use std::collections::HashMap;
@@ -21,20 +23,39 @@ use zokrates_field::Field;
 #[derive(PartialEq, Debug)]
 pub struct Error {
     pos: Option<(Position, Position)>,
+    code: ErrorCode,
     message: String,
 }
 
 impl Error {
+    /// Create an error which occurred while resolving an import
     pub fn new<T: Into<String>>(message: T) -> Error {
         Error {
             pos: None,
+            code: ErrorCode::ImportResolution,
             message: message.into(),
         }
     }
 
+    pub fn pos(&self) -> Option<(Position, Position)> {
+        self.pos
+    }
+
+    pub fn code(&self) -> ErrorCode {
+        self.code
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
     fn with_pos(self, pos: Option<(Position, Position)>) -> Error {
         Error { pos, ..self }
     }
+
+    fn with_code(self, code: ErrorCode) -> Error {
+        Error { code, ..self }
+    }
 }
 
 impl fmt::Display for Error {
@@ -51,6 +72,7 @@ impl From<io::Error> for Error {
     fn from(error: io::Error) -> Self {
         Error {
             pos: None,
+            code: ErrorCode::ImportResolution,
             message: format!("I/O Error: {}", error),
         }
     }
@@ -138,6 +160,7 @@ impl Importer {
         location: PathBuf,
         resolver: Option<&dyn Resolver<E>>,
         modules: &mut HashMap<ModuleId, Module<'ast, T>>,
+        mut session: Option<&mut Session<'_, 'ast, T>>,
         arena: &'ast Arena<String>,
     ) -> Result<Module<'ast, T>, CompileErrors> {
         let mut symbols: Vec<_> = vec![];
@@ -160,6 +183,17 @@ impl Importer {
                             .start_end(pos.0, pos.1),
                         );
                     }
+                    "EMBED/u64_to_bits" => {
+                        let alias = alias.unwrap_or("u64_to_bits");
+
+                        symbols.push(
+                            SymbolDeclaration {
+                                id: &alias,
+                                symbol: Symbol::Flat(FlatEmbed::U64ToBits),
+                            }
+                            .start_end(pos.0, pos.1),
+                        );
+                    }
                     "EMBED/u32_to_bits" => {
                         let alias = alias.unwrap_or("u32_to_bits");
 
@@ -193,6 +227,17 @@ impl Importer {
                             .start_end(pos.0, pos.1),
                         );
                     }
+                    "EMBED/u64_from_bits" => {
+                        let alias = alias.unwrap_or("u64_from_bits");
+
+                        symbols.push(
+                            SymbolDeclaration {
+                                id: &alias,
+                                symbol: Symbol::Flat(FlatEmbed::U64FromBits),
+                            }
+                            .start_end(pos.0, pos.1),
+                        );
+                    }
                     "EMBED/u32_from_bits" => {
                         let alias = alias.unwrap_or("u32_from_bits");
 
@@ -228,7 +273,9 @@ impl Importer {
                     }
                     s => {
                         return Err(CompileErrorInner::ImportError(
-                            Error::new(format!("Embed {} not found", s)).with_pos(Some(pos)),
+                            Error::new(format!("Embed {} not found", s))
+                                .with_code(ErrorCode::EmbedNotFound)
+                                .with_pos(Some(pos)),
                         )
                         .in_file(&location)
                         .into());
@@ -244,18 +291,30 @@ impl Importer {
                                 std::path::Path::new(import.source)
                                     .file_stem()
                                     .ok_or(CompileErrors::from(
-                                        CompileErrorInner::ImportError(Error::new(format!(
-                                            "Could not determine alias for import {}",
-                                            import.source.display()
-                                        )))
+                                        CompileErrorInner::ImportError(
+                                            Error::new(format!(
+                                                "Could not determine alias for import {}",
+                                                import.source.display()
+                                            ))
+                                            .with_code(ErrorCode::ImportAlias),
+                                        )
                                         .in_file(&location),
                                     ))?
                                     .to_str()
                                     .unwrap(),
                             );
 
+                            if let Some(session) = session.as_deref_mut() {
+                                session.import(&location, import.source, &new_location, &source);
+                            }
+
                             match modules.get(&new_location) {
                                 Some(_) => {}
+                                // reuse the module if it was checked before
+                                None if session
+                                    .as_deref_mut()
+                                    .map(|s| s.load(&new_location, res, arena))
+                                    .unwrap_or(false) => {}
                                 None => {
                                     let source = arena.alloc(source);
 
@@ -264,6 +323,7 @@ impl Importer {
                                         new_location.clone(),
                                         resolver,
                                         modules,
+                                        session.as_deref_mut(),
                                         &arena,
                                     )?;
 
@@ -296,9 +356,10 @@ impl Importer {
                         }
                     },
                     None => {
-                        return Err(CompileErrorInner::from(Error::new(
-                            "Can't resolve import without a resolver",
-                        ))
+                        return Err(CompileErrorInner::from(
+                            Error::new("Can't resolve import without a resolver")
+                                .with_code(ErrorCode::MissingResolver),
+                        )
                         .in_file(&location)
                         .into());
                     }
@@ -192,12 +192,14 @@ impl<T: Field> QuadComb<T> {
 
 impl<T: Field> fmt::Display for LinComb<T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        match self.is_zero() {
+        // terms can cancel out, so check for zero on the canonical form
+        let canonical = self.as_canonical();
+        match canonical.0.is_empty() {
             true => write!(f, "0"),
             false => write!(
                 f,
                 "{}",
-                self.as_canonical()
+                canonical
                     .0
                     .iter()
                     .map(|(k, v)| format!("{} * {}", v.to_compact_dec_string(), k))
@@ -306,6 +308,9 @@ mod tests {
             assert_eq!(&a.to_string(), "3 * _21 + 1 * _42");
             let zero: LinComb<Bn128Field> = LinComb::zero();
             assert_eq!(&zero.to_string(), "0");
+            let cancelled: LinComb<Bn128Field> =
+                LinComb::from(FlatVariable::new(42)) - LinComb::from(FlatVariable::new(42));
+            assert_eq!(&cancelled.to_string(), "0");
         }
     }
 
@@ -2,6 +2,7 @@
 
 use crate::flat_absy::flat_variable::FlatVariable;
 use crate::ir::*;
+use std::collections::BTreeMap;
 use zokrates_field::Field;
 
 pub trait Folder<T: Field>: Sized {
@@ -38,9 +39,68 @@ pub trait Folder<T: Field>: Sized {
     }
 }
 
+/// Fold the statements of `main` one by one, so that the metadata of a constraint follows it into the constraints it
+/// is folded into, and an origin moves to the next statement when the one it is attached to is removed
 pub fn fold_module<T: Field, F: Folder<T>>(f: &mut F, p: Prog<T>) -> Prog<T> {
+    let main = p.main;
+    let mut metadata = p.metadata;
+    let mut origins = p.origins;
+
+    let arguments = main
+        .arguments
+        .into_iter()
+        .map(|a| f.fold_argument(a))
+        .collect();
+
+    let mut statements = vec![];
+    let mut folded_metadata = BTreeMap::new();
+    let mut folded_origins = BTreeMap::new();
+    let mut origin = None;
+    let mut constraint_index = 0;
+    let mut folded_constraint_index = 0;
+
+    for (index, s) in main.statements.into_iter().enumerate() {
+        if let Some(o) = origins.remove(&index) {
+            origin = Some(o);
+        }
+
+        let m = match s {
+            Statement::Constraint(..) => {
+                constraint_index += 1;
+                metadata.remove(&(constraint_index - 1))
+            }
+            _ => None,
+        };
+
+        for s in f.fold_statement(s) {
+            if let Some(o) = origin.take() {
+                folded_origins.insert(statements.len(), o);
+            }
+            if let Statement::Constraint(..) = s {
+                if let Some(m) = &m {
+                    folded_metadata.insert(folded_constraint_index, m.clone());
+                }
+                folded_constraint_index += 1;
+            }
+            statements.push(s);
+        }
+    }
+
+    let returns = main
+        .returns
+        .into_iter()
+        .map(|v| f.fold_variable(v))
+        .collect();
+
     Prog {
-        main: f.fold_function(p.main),
+        main: Function {
+            arguments,
+            statements,
+            returns,
+            ..main
+        },
+        metadata: folded_metadata,
+        origins: folded_origins,
         ..p
     }
 }
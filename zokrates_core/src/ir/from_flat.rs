@@ -2,6 +2,7 @@ use crate::flat_absy::{
     FlatDirective, FlatExpression, FlatFunction, FlatProg, FlatStatement, FlatVariable,
 };
 use crate::ir::{Directive, Function, LinComb, Prog, QuadComb, Statement};
+use std::collections::BTreeMap;
 use zokrates_field::Field;
 
 impl<T: Field> From<FlatFunction<T>> for Function<T> {
@@ -27,7 +28,7 @@ impl<T: Field> From<FlatFunction<T>> for Function<T> {
                 .statements
                 .into_iter()
                 .filter_map(|s| match s {
-                    FlatStatement::Return(..) => None,
+                    FlatStatement::Return(..) | FlatStatement::Origin(..) => None,
                     s => Some(s.into()),
                 })
                 .chain(
@@ -69,9 +70,56 @@ impl<T: Field> From<FlatProg<T>> for Prog<T> {
         // get the interface of the program, i.e. which inputs are private and public
         let private = main.arguments.iter().map(|p| p.private).collect();
 
+        // get the metadata of the conditions, by index of the constraint they are turned into
+        let metadata = main
+            .statements
+            .iter()
+            .filter(|s| match s {
+                FlatStatement::Condition(..) | FlatStatement::Definition(..) => true,
+                _ => false,
+            })
+            .enumerate()
+            .filter_map(|(index, s)| match s {
+                FlatStatement::Condition(_, _, Some(metadata)) => Some((index, metadata.clone())),
+                _ => None,
+            })
+            .collect();
+
+        // get the origins, by index of the first statement they apply to
+        let mut origins = BTreeMap::new();
+        let mut origin = None;
+        let mut return_origin = None;
+        let mut index = 0;
+
+        for s in &main.statements {
+            match s {
+                FlatStatement::Origin(o) => origin = Some(o.clone()),
+                // the returned expressions are turned into constraints at the end of the function
+                FlatStatement::Return(r) if !r.expressions.is_empty() => {
+                    return_origin = origin.take()
+                }
+                FlatStatement::Return(..) => {}
+                _ => {
+                    if let Some(o) = origin.take() {
+                        origins.insert(index, o);
+                    }
+                    index += 1;
+                }
+            }
+        }
+
+        if let Some(o) = return_origin {
+            origins.insert(index, o);
+        }
+
         let main = main.into();
 
-        Prog { private, main }
+        Prog {
+            private,
+            main,
+            metadata,
+            origins,
+        }
     }
 }
 
@@ -99,7 +147,7 @@ impl<T: Field> From<FlatExpression<T>> for LinComb<T> {
 impl<T: Field> From<FlatStatement<T>> for Statement<T> {
     fn from(flat_statement: FlatStatement<T>) -> Statement<T> {
         match flat_statement {
-            FlatStatement::Condition(linear, quadratic) => match quadratic {
+            FlatStatement::Condition(linear, quadratic, _) => match quadratic {
                 FlatExpression::Mult(box lhs, box rhs) => Statement::Constraint(
                     QuadComb::from_linear_combinations(lhs.into(), rhs.into()),
                     linear.into(),
@@ -114,7 +162,7 @@ impl<T: Field> From<FlatStatement<T>> for Statement<T> {
                 e => Statement::Constraint(LinComb::from(e).into(), var.into()),
             },
             FlatStatement::Directive(ds) => Statement::Directive(ds.into()),
-            _ => panic!("return should be handled at the function level"),
+            _ => panic!("returns and origins should be handled at the function level"),
         }
     }
 }
@@ -2,6 +2,7 @@ use crate::flat_absy::flat_variable::FlatVariable;
 use crate::ir::{LinComb, Prog, QuadComb, Statement, Witness};
 use ir::Directive;
 use solvers::Solver;
+use source_map::SourceMetadata;
 use std::collections::BTreeMap;
 use std::fmt;
 use zokrates_field::Field;
@@ -42,24 +43,30 @@ impl Interpreter {
             witness.insert(arg.clone(), value.clone().into());
         }
 
+        let mut constraint_index = 0;
+
         for statement in main.statements.iter() {
             match statement {
-                Statement::Constraint(quad, lin) => match lin.is_assignee(&witness) {
-                    true => {
-                        let val = quad.evaluate(&witness).unwrap();
-                        witness.insert(lin.0.iter().next().unwrap().0.clone(), val);
-                    }
-                    false => {
-                        let lhs_value = quad.evaluate(&witness).unwrap();
-                        let rhs_value = lin.evaluate(&witness).unwrap();
-                        if lhs_value != rhs_value {
-                            return Err(Error::UnsatisfiedConstraint {
-                                left: lhs_value.to_dec_string(),
-                                right: rhs_value.to_dec_string(),
-                            });
+                Statement::Constraint(quad, lin) => {
+                    match lin.is_assignee(&witness) {
+                        true => {
+                            let val = quad.evaluate(&witness).unwrap();
+                            witness.insert(lin.0.iter().next().unwrap().0.clone(), val);
+                        }
+                        false => {
+                            let lhs_value = quad.evaluate(&witness).unwrap();
+                            let rhs_value = lin.evaluate(&witness).unwrap();
+                            if lhs_value != rhs_value {
+                                return Err(Error::UnsatisfiedConstraint {
+                                    left: lhs_value.to_dec_string(),
+                                    right: rhs_value.to_dec_string(),
+                                    metadata: program.metadata.get(&constraint_index).cloned(),
+                                });
+                            }
                         }
                     }
-                },
+                    constraint_index += 1;
+                }
                 Statement::Directive(ref d) => {
                     match (&d.solver, &d.inputs, self.should_try_out_of_range) {
                         (Solver::Bits(bitwidth), inputs, true)
@@ -185,6 +192,18 @@ impl Interpreter {
                 vec![a * (b - c.clone()) + c]
             }
             Solver::Div => vec![inputs[0].clone() / inputs[1].clone()],
+            Solver::EuclideanDiv => {
+                use num::traits::Zero;
+
+                let n = inputs[0].to_biguint();
+                let d = inputs[1].to_biguint();
+
+                if d.is_zero() {
+                    return Err(String::from("Division by zero"));
+                }
+
+                vec![T::from(n.clone() / d.clone()), T::from(n % d)]
+            }
         };
 
         assert_eq!(res.len(), expected_output_count);
@@ -219,9 +238,17 @@ impl<T: Field> QuadComb<T> {
 
 #[derive(PartialEq, Serialize, Deserialize, Clone)]
 pub enum Error {
-    UnsatisfiedConstraint { left: String, right: String },
+    UnsatisfiedConstraint {
+        left: String,
+        right: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        metadata: Option<SourceMetadata>,
+    },
     Solver,
-    WrongInputCount { expected: usize, received: usize },
+    WrongInputCount {
+        expected: usize,
+        received: usize,
+    },
 }
 
 impl fmt::Display for Error {
@@ -230,7 +257,12 @@ impl fmt::Display for Error {
             Error::UnsatisfiedConstraint {
                 ref left,
                 ref right,
+                metadata: None,
             } => write!(f, "Expected {} to equal {}", left, right),
+            Error::UnsatisfiedConstraint {
+                metadata: Some(ref metadata),
+                ..
+            } => write!(f, "Assertion failed at {}: {}", metadata, metadata.message),
             Error::Solver => write!(f, ""),
             Error::WrongInputCount { expected, received } => write!(
                 f,
@@ -294,6 +326,29 @@ mod tests {
         }
     }
 
+    mod euclidean_div {
+        use super::*;
+
+        #[test]
+        fn execute() {
+            let inputs = vec![Bn128Field::from(42), Bn128Field::from(5)];
+            let interpreter = Interpreter::default();
+            let res = interpreter
+                .execute_solver(&Solver::EuclideanDiv, &inputs)
+                .unwrap();
+            assert_eq!(res, vec![Bn128Field::from(8), Bn128Field::from(2)]);
+        }
+
+        #[test]
+        fn execute_by_zero() {
+            let inputs = vec![Bn128Field::from(42), Bn128Field::from(0)];
+            let interpreter = Interpreter::default();
+            assert!(interpreter
+                .execute_solver(&Solver::EuclideanDiv, &inputs)
+                .is_err());
+        }
+    }
+
     #[test]
     fn bits_of_one() {
         let inputs = vec![Bn128Field::from(1)];
@@ -322,4 +377,46 @@ mod tests {
         assert_eq!(res[248], Bn128Field::from(1));
         assert_eq!(res[247], Bn128Field::from(0));
     }
+
+    #[test]
+    fn unsatisfied_constraint_reports_source() {
+        let metadata = SourceMetadata {
+            file: "main.zok".into(),
+            line: 2,
+            col: 2,
+            message: "(a == 1)".into(),
+        };
+
+        let program: Prog<Bn128Field> = Prog {
+            main: crate::ir::Function {
+                id: String::from("main"),
+                arguments: vec![FlatVariable::new(0)],
+                returns: vec![],
+                statements: vec![
+                    Statement::definition(FlatVariable::new(1), FlatVariable::new(0)),
+                    Statement::constraint(FlatVariable::one(), FlatVariable::new(0)),
+                ],
+            },
+            private: vec![false],
+            origins: BTreeMap::new(),
+            metadata: vec![(1, metadata.clone())].into_iter().collect(),
+        };
+
+        let error = Interpreter::default()
+            .execute(&program, &vec![Bn128Field::from(0)])
+            .unwrap_err();
+
+        assert_eq!(
+            error,
+            Error::UnsatisfiedConstraint {
+                left: "1".into(),
+                right: "0".into(),
+                metadata: Some(metadata)
+            }
+        );
+        assert_eq!(
+            error.to_string(),
+            "Assertion failed at main.zok:2:2: (a == 1)"
+        );
+    }
 }
@@ -1,6 +1,8 @@
 use crate::flat_absy::flat_parameter::FlatParameter;
 use crate::flat_absy::FlatVariable;
 use crate::solvers::Solver;
+use crate::source_map::{Origin, SourceMetadata};
+use std::collections::BTreeMap;
 use std::fmt;
 use zokrates_field::Field;
 
@@ -8,14 +10,18 @@ mod expression;
 pub mod folder;
 mod from_flat;
 mod interpreter;
+mod parse;
+mod profile;
 mod serialize;
 mod witness;
 
 pub use self::expression::QuadComb;
 pub use self::expression::{CanonicalLinComb, LinComb};
-pub use self::serialize::ProgEnum;
+pub use self::serialize::{ProgEnum, FORMAT_VERSION};
 
 pub use self::interpreter::{Error, ExecutionResult, Interpreter};
+pub use self::parse::ParseError;
+pub use self::profile::{Cost, Profile};
 pub use self::witness::Witness;
 
 #[derive(Debug, Serialize, Deserialize, Clone, Hash)]
@@ -111,21 +117,61 @@ impl<T: Field> PartialEq for Function<T> {
     }
 }
 
-impl<T: Field> fmt::Display for Function<T> {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+impl<T: Field> Function<T> {
+    /// Write the function, marking the arguments which are private according to `private`, annotating the
+    /// constraints with their entry in `metadata` and preceding the statements with their entry in `origins`
+    fn fmt_with_private(
+        &self,
+        f: &mut fmt::Formatter,
+        private: &[bool],
+        metadata: &BTreeMap<usize, SourceMetadata>,
+        origins: &BTreeMap<usize, Origin>,
+    ) -> fmt::Result {
+        let mut constraint_index = 0;
         write!(
             f,
             "def {}({}) -> ({}):\n{}\n\t return {}",
             self.id,
             self.arguments
                 .iter()
-                .map(|v| format!("{}", v))
+                .enumerate()
+                .map(|(i, v)| match private.get(i) {
+                    Some(true) => format!("private {}", v),
+                    _ => format!("{}", v),
+                })
                 .collect::<Vec<_>>()
                 .join(", "),
             self.returns.len(),
             self.statements
                 .iter()
-                .map(|s| format!("\t{}", s))
+                .enumerate()
+                .map(|(index, s)| {
+                    let origin = match origins.get(&index) {
+                        Some(origin) => format!(
+                            "\t// {} {}\n",
+                            serde_json::to_string(&origin.stack).unwrap(),
+                            origin
+                        ),
+                        None => String::new(),
+                    };
+                    let metadata = match s {
+                        Statement::Constraint(..) => {
+                            constraint_index += 1;
+                            metadata.get(&(constraint_index - 1))
+                        }
+                        Statement::Directive(..) => None,
+                    };
+                    match metadata {
+                        Some(metadata) => format!(
+                            "{}\t{} // {} {}",
+                            origin,
+                            s,
+                            serde_json::to_string(&metadata.message).unwrap(),
+                            metadata
+                        ),
+                        None => format!("{}\t{}", origin, s),
+                    }
+                })
                 .collect::<Vec<_>>()
                 .join("\n"),
             self.returns
@@ -137,15 +183,28 @@ impl<T: Field> fmt::Display for Function<T> {
     }
 }
 
+impl<T: Field> fmt::Display for Function<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.fmt_with_private(f, &[], &BTreeMap::new(), &BTreeMap::new())
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Prog<T> {
     pub main: Function<T>,
     pub private: Vec<bool>,
+    /// The source location of the assertions which constraints come from, by constraint index
+    pub metadata: BTreeMap<usize, SourceMetadata>,
+    /// The origin of the statements from a given statement index on, only filled when profiling
+    pub origins: BTreeMap<usize, Origin>,
 }
 
 impl<T: Field> PartialEq for Prog<T> {
     fn eq(&self, other: &Self) -> bool {
-        self.main.eq(&other.main) && self.private.eq(&other.private)
+        self.main.eq(&other.main)
+            && self.private.eq(&other.private)
+            && self.metadata.eq(&other.metadata)
+            && self.origins.eq(&other.origins)
     }
 }
 
@@ -178,9 +237,12 @@ impl<T: Field> Prog<T> {
     }
 }
 
+/// Programs are displayed in a textual format which can be parsed back, see `ProgEnum::parse`
 impl<T: Field> fmt::Display for Prog<T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}", self.main)
+        writeln!(f, "#pragma curve {}", T::name())?;
+        self.main
+            .fmt_with_private(f, &self.private, &self.metadata, &self.origins)
     }
 }
 
@@ -0,0 +1,757 @@
+//! Module containing a parser for the textual format of `ir::Prog`, which is the format compiled programs are displayed in
+//!
+//! This makes it possible to write programs by hand or generate them with other tools, and to read back the
+//! human-readable output of `compile`. The format is described in the book.
+
+use crate::flat_absy::FlatVariable;
+use crate::ir::{Directive, Function, LinComb, Prog, ProgEnum, QuadComb, Statement};
+use crate::solvers::Solver;
+use crate::source_map::{Origin, SourceMetadata};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::str::FromStr;
+use zokrates_field::Field;
+
+/// An error found while parsing a program, on a given line
+#[derive(Debug, PartialEq)]
+pub struct ParseError {
+    line: usize,
+    message: String,
+}
+
+impl ParseError {
+    /// The line the error was found on, starting at 1
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+/// A cursor over a single line
+struct Cursor<'a> {
+    line: &'a str,
+    pos: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn new(line: &'a str) -> Self {
+        Cursor { line, pos: 0 }
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.line[self.pos..]
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    /// Consume `token` if the line continues with it
+    fn eat(&mut self, token: &str) -> bool {
+        self.skip_whitespace();
+        match self.rest().starts_with(token) {
+            true => {
+                self.pos += token.len();
+                true
+            }
+            false => false,
+        }
+    }
+
+    fn expect(&mut self, token: &str) -> Result<(), String> {
+        match self.eat(token) {
+            true => Ok(()),
+            false => Err(format!("Expected `{}`, found `{}`", token, self.rest())),
+        }
+    }
+
+    /// Consume the longest prefix whose characters satisfy `f`
+    fn take_while<F: Fn(char) -> bool>(&mut self, f: F) -> &'a str {
+        self.skip_whitespace();
+        let rest = self.rest();
+        let len = rest.find(|c| !f(c)).unwrap_or(rest.len());
+        self.pos += len;
+        &rest[..len]
+    }
+
+    fn word(&mut self) -> &'a str {
+        self.take_while(|c| c.is_ascii_alphanumeric() || c == '_' || c == '~')
+    }
+
+    fn number(&mut self) -> Result<usize, String> {
+        let digits = self.take_while(|c| c.is_ascii_digit());
+        digits
+            .parse()
+            .map_err(|_| format!("Expected a number, found `{}`", self.rest()))
+    }
+
+    fn end(&mut self) -> Result<(), String> {
+        self.skip_whitespace();
+        match self.rest() {
+            "" => Ok(()),
+            rest => Err(format!("Unexpected `{}`", rest)),
+        }
+    }
+
+    fn try_variable(&mut self) -> Option<FlatVariable> {
+        self.skip_whitespace();
+        match self.rest().chars().next() {
+            Some('_') | Some('~') => {
+                let start = self.pos;
+                let word = self.word();
+                match FlatVariable::try_from_human_readable(word) {
+                    Ok(v) => Some(v),
+                    Err(_) => {
+                        self.pos = start;
+                        None
+                    }
+                }
+            }
+            _ => None,
+        }
+    }
+
+    fn variable(&mut self) -> Result<FlatVariable, String> {
+        self.try_variable()
+            .ok_or_else(|| format!("Expected a variable, found `{}`", self.rest()))
+    }
+
+    /// A comma-separated list of variables, possibly empty
+    fn variables(&mut self) -> Result<Vec<FlatVariable>, String> {
+        let mut res = vec![];
+        if let Some(v) = self.try_variable() {
+            res.push(v);
+            while self.eat(",") {
+                res.push(self.variable()?);
+            }
+        }
+        Ok(res)
+    }
+
+    /// A field element, which is negative if it starts with `-`, for example `42`, `-1` or `(-1)`
+    fn coefficient<T: Field>(&mut self) -> Result<T, String> {
+        let parenthesized = self.eat("(");
+        let negative = self.eat("-");
+        let digits = self.take_while(|c| c.is_ascii_digit());
+        let value = T::try_from_dec_str(digits)
+            .map_err(|_| format!("Expected a number, found `{}`", self.rest()))?;
+        if parenthesized {
+            self.expect(")")?;
+        }
+        Ok(match negative {
+            true => T::zero() - value,
+            false => value,
+        })
+    }
+
+    /// A sum of terms, each of which is `c * v`, `v` or `c` as a shorthand for `c * ~one`
+    fn lin_comb<T: Field>(&mut self) -> Result<LinComb<T>, String> {
+        let mut res = vec![];
+        loop {
+            match self.try_variable() {
+                Some(v) => res.push((v, T::one())),
+                None => {
+                    let c = self.coefficient()?;
+                    if self.eat("*") {
+                        res.push((self.variable()?, c));
+                    } else if c != T::zero() {
+                        res.push((FlatVariable::one(), c));
+                    }
+                }
+            }
+            if !self.eat("+") {
+                return Ok(LinComb(res));
+            }
+        }
+    }
+
+    /// A product of two linear combinations `(a) * (b)`, or a single linear combination `a` as a shorthand for
+    /// `(1 * ~one) * (a)`
+    fn quad_comb<T: Field>(&mut self) -> Result<QuadComb<T>, String> {
+        let start = self.pos;
+        match self.product() {
+            Ok(q) => Ok(q),
+            Err(_) => {
+                self.pos = start;
+                self.lin_comb().map(QuadComb::from)
+            }
+        }
+    }
+
+    fn product<T: Field>(&mut self) -> Result<QuadComb<T>, String> {
+        self.expect("(")?;
+        let left = self.lin_comb()?;
+        self.expect(")")?;
+        self.expect("*")?;
+        self.expect("(")?;
+        let right = self.lin_comb()?;
+        self.expect(")")?;
+        Ok(QuadComb::from_linear_combinations(left, right))
+    }
+
+    /// A JSON value, such as the message of a constraint or the call stack of an origin
+    fn json<V: serde::de::DeserializeOwned>(&mut self) -> Result<V, String> {
+        self.skip_whitespace();
+        let mut stream = serde_json::Deserializer::from_str(self.rest()).into_iter::<V>();
+        let value = stream
+            .next()
+            .ok_or_else(|| format!("Expected a JSON value, found `{}`", self.rest()))?
+            .map_err(|e| e.to_string())?;
+        self.pos += stream.byte_offset();
+        Ok(value)
+    }
+
+    /// A location `file:n_1:...:n_count`, where `file` can contain `:`
+    fn location(&mut self, count: usize) -> Result<(String, Vec<usize>), String> {
+        self.skip_whitespace();
+        let location = self.rest().trim_end();
+        self.pos = self.line.len();
+
+        let mut parts = location.rsplitn(count + 1, ':').collect::<Vec<_>>();
+        parts.reverse();
+        match parts.len() == count + 1 {
+            true => Ok((
+                parts[0].to_string(),
+                parts[1..]
+                    .iter()
+                    .map(|n| {
+                        n.parse()
+                            .map_err(|_| format!("Invalid location `{}`", location))
+                    })
+                    .collect::<Result<_, _>>()?,
+            )),
+            false => Err(format!("Invalid location `{}`", location)),
+        }
+    }
+}
+
+fn solver(name: &str, parameter: Option<usize>) -> Result<Solver, String> {
+    match (name, parameter) {
+        ("ConditionEq", None) => Ok(Solver::ConditionEq),
+        ("Bits", Some(width)) => Ok(Solver::Bits(width)),
+        ("Div", None) => Ok(Solver::Div),
+        ("EuclideanDiv", None) => Ok(Solver::EuclideanDiv),
+        ("Xor", None) => Ok(Solver::Xor),
+        ("Or", None) => Ok(Solver::Or),
+        ("ShaAndXorAndXorAnd", None) => Ok(Solver::ShaAndXorAndXorAnd),
+        ("ShaCh", None) => Ok(Solver::ShaCh),
+        _ => Err(format!("Unknown solver `{}`", name)),
+    }
+}
+
+/// `# outputs = Solver(inputs)`, once `#` is consumed
+fn directive<T: Field>(c: &mut Cursor) -> Result<Directive<T>, String> {
+    let outputs = c.variables()?;
+    c.expect("=")?;
+
+    let name = c.word();
+    // solvers such as `Bits(254)` have a parameter, which is followed by the inputs in parentheses
+    let start = c.pos;
+    let parameter = match c.eat("(") {
+        true => match (c.number(), c.eat(")") && c.rest().starts_with('(')) {
+            (Ok(n), true) => Some(n),
+            _ => {
+                c.pos = start;
+                None
+            }
+        },
+        false => None,
+    };
+    let solver = solver(name, parameter)?;
+
+    c.expect("(")?;
+    let mut inputs = vec![];
+    if !c.eat(")") {
+        loop {
+            inputs.push(c.quad_comb()?);
+            if !c.eat(",") {
+                break;
+            }
+        }
+        c.expect(")")?;
+    }
+    c.end()?;
+
+    let (input_count, output_count) = solver.get_signature();
+    if (inputs.len(), outputs.len()) != (input_count, output_count) {
+        return Err(format!(
+            "Solver `{}` has {} inputs and {} outputs, found {} inputs and {} outputs",
+            solver,
+            input_count,
+            output_count,
+            inputs.len(),
+            outputs.len()
+        ));
+    }
+
+    Ok(Directive {
+        inputs,
+        outputs,
+        solver,
+    })
+}
+
+/// `quad == lin`, optionally followed by the metadata of the assertion it comes from
+fn constraint<T: Field>(c: &mut Cursor) -> Result<(Statement<T>, Option<SourceMetadata>), String> {
+    let quad = c.quad_comb()?;
+    c.expect("==")?;
+    let lin = c.lin_comb()?;
+    let metadata = match c.eat("//") {
+        true => {
+            let message = c.json()?;
+            let (file, position) = c.location(2)?;
+            Some(SourceMetadata {
+                file,
+                line: position[0],
+                col: position[1],
+                message,
+            })
+        }
+        false => {
+            c.end()?;
+            None
+        }
+    };
+    Ok((Statement::Constraint(quad, lin), metadata))
+}
+
+/// Check that `statement` only reads variables which are already `defined`, and define the variables it assigns. As in
+/// the interpreter, a constraint assigns its right-hand side if it is a single undefined variable with coefficient 1.
+fn define<T: Field>(
+    statement: &Statement<T>,
+    defined: &mut BTreeSet<FlatVariable>,
+) -> Result<(), String> {
+    let (reads, assigned): (Vec<&LinComb<T>>, Vec<FlatVariable>) = match statement {
+        Statement::Constraint(quad, lin) => match lin.0.as_slice() {
+            [(v, k)] if *k == T::one() && !defined.contains(v) => {
+                (vec![&quad.left, &quad.right], vec![*v])
+            }
+            _ => (vec![&quad.left, &quad.right, lin], vec![]),
+        },
+        Statement::Directive(d) => (
+            d.inputs
+                .iter()
+                .flat_map(|q| vec![&q.left, &q.right])
+                .collect(),
+            d.outputs.clone(),
+        ),
+    };
+
+    match reads
+        .into_iter()
+        .flat_map(|l| l.0.iter().map(|(v, _)| v))
+        .find(|v| !defined.contains(*v))
+    {
+        Some(v) => Err(format!("Variable `{}` is used before it is defined", v)),
+        None => {
+            defined.extend(assigned);
+            Ok(())
+        }
+    }
+}
+
+/// `["frame", ...] file:line`, once `//` is consumed
+fn origin(c: &mut Cursor) -> Result<Origin, String> {
+    let stack = c.json()?;
+    let (file, position) = c.location(1)?;
+    Ok(Origin {
+        stack,
+        file,
+        line: position[0],
+    })
+}
+
+/// `def id(args) -> (count):`, where arguments can be marked `private`
+fn header<T>(c: &mut Cursor) -> Result<(Function<T>, Vec<bool>, usize), String> {
+    c.expect("def")?;
+    let id = c.word().to_string();
+    c.expect("(")?;
+    let mut arguments = vec![];
+    let mut private = vec![];
+    if !c.eat(")") {
+        loop {
+            private.push(c.eat("private "));
+            arguments.push(c.variable()?);
+            if !c.eat(",") {
+                break;
+            }
+        }
+        c.expect(")")?;
+    }
+    c.expect("->")?;
+    c.expect("(")?;
+    let return_count = c.number()?;
+    c.expect(")")?;
+    c.expect(":")?;
+    c.end()?;
+
+    Ok((
+        Function {
+            id,
+            statements: vec![],
+            arguments,
+            returns: vec![],
+        },
+        private,
+        return_count,
+    ))
+}
+
+/// The curve set by a `#pragma curve` line, if any
+fn curve(c: &mut Cursor) -> Option<String> {
+    match c.eat("#pragma") {
+        true => {
+            c.eat("curve");
+            Some(c.word().to_string())
+        }
+        false => None,
+    }
+}
+
+impl<T: Field> FromStr for Prog<T> {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, ParseError> {
+        let mut lines = s
+            .lines()
+            .enumerate()
+            .map(|(i, line)| (i + 1, line))
+            .filter(|(_, line)| !line.trim().is_empty());
+
+        let error = |line: usize| move |message: String| ParseError { line, message };
+
+        let (mut line, mut text) = lines.next().ok_or(ParseError {
+            line: 1,
+            message: "Expected a program".to_string(),
+        })?;
+
+        if let Some(curve) = curve(&mut Cursor::new(text)) {
+            if curve != T::name() {
+                return Err(error(line)(format!(
+                    "Program is written for curve {}, expected {}",
+                    curve,
+                    T::name()
+                )));
+            }
+            let next = lines.next().ok_or(ParseError {
+                line: line + 1,
+                message: "Expected a function".to_string(),
+            })?;
+            line = next.0;
+            text = next.1;
+        }
+
+        let (mut main, private, return_count) =
+            header(&mut Cursor::new(text)).map_err(error(line))?;
+
+        let mut metadata = BTreeMap::new();
+        let mut origins = BTreeMap::new();
+        let mut constraint_count = 0;
+        let mut defined: BTreeSet<FlatVariable> = std::iter::once(FlatVariable::one())
+            .chain(main.arguments.iter().cloned())
+            .collect();
+
+        loop {
+            let (l, text) = lines.next().ok_or(ParseError {
+                line: line + 1,
+                message: "Expected `return`".to_string(),
+            })?;
+            line = l;
+
+            let mut c = Cursor::new(text);
+            if c.eat("return") {
+                main.returns = c.variables().map_err(error(line))?;
+                c.end().map_err(error(line))?;
+                if let Some(v) = main.returns.iter().find(|v| !defined.contains(*v)) {
+                    return Err(error(line)(format!(
+                        "Variable `{}` is returned but never defined",
+                        v
+                    )));
+                }
+                break;
+            }
+
+            let statement = match c.eat("#") {
+                true => directive(&mut c).map(Statement::Directive),
+                false => match c.eat("//") {
+                    // comments are ignored, except for the origins of the statements
+                    true => match c.rest().trim_start().starts_with('[') {
+                        true => {
+                            let origin = origin(&mut c).map_err(error(line))?;
+                            origins.insert(main.statements.len(), origin);
+                            continue;
+                        }
+                        false => continue,
+                    },
+                    false => constraint(&mut c).map(|(statement, m)| {
+                        if let Some(m) = m {
+                            metadata.insert(constraint_count, m);
+                        }
+                        constraint_count += 1;
+                        statement
+                    }),
+                },
+            }
+            .map_err(error(line))?;
+
+            define(&statement, &mut defined).map_err(error(line))?;
+            main.statements.push(statement);
+        }
+
+        if main.returns.len() != return_count {
+            return Err(error(line)(format!(
+                "Expected {} return values, found {}",
+                return_count,
+                main.returns.len()
+            )));
+        }
+
+        if let Some((line, text)) = lines.next() {
+            return Err(error(line)(format!("Unexpected `{}`", text.trim())));
+        }
+
+        Ok(Prog {
+            main,
+            private,
+            metadata,
+            origins,
+        })
+    }
+}
+
+impl ProgEnum {
+    /// Parse a program in the textual format, on the curve set by its `#pragma curve` line, bn128 if there is none
+    pub fn parse(s: &str) -> Result<Self, ParseError> {
+        let (line, curve) = s
+            .lines()
+            .enumerate()
+            .find(|(_, line)| !line.trim().is_empty())
+            .map(|(i, line)| (i + 1, curve(&mut Cursor::new(line))))
+            .unwrap_or((1, None));
+
+        match curve.as_deref() {
+            None | Some("bn128") => s.parse().map(ProgEnum::Bn128Program),
+            Some("bls12_381") => s.parse().map(ProgEnum::Bls12Program),
+            Some(curve) => Err(ParseError {
+                line,
+                message: format!("Unknown curve {}", curve),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zokrates_field::Bn128Field;
+
+    #[test]
+    fn round_trip() {
+        let p: Prog<Bn128Field> = Prog {
+            main: Function {
+                id: "main".to_string(),
+                arguments: vec![FlatVariable::new(0), FlatVariable::new(1)],
+                statements: vec![
+                    Statement::Directive(Directive {
+                        inputs: vec![
+                            FlatVariable::new(0).into(),
+                            QuadComb::from_linear_combinations(
+                                LinComb::summand(3, FlatVariable::new(1)),
+                                LinComb::summand(-1, FlatVariable::one()),
+                            ),
+                        ],
+                        outputs: vec![FlatVariable::new(2)],
+                        solver: Solver::Div,
+                    }),
+                    Statement::Constraint(
+                        QuadComb::from_linear_combinations(
+                            FlatVariable::new(2).into(),
+                            LinComb::summand(3, FlatVariable::new(1))
+                                + LinComb::summand(-1, FlatVariable::one()),
+                        ),
+                        FlatVariable::new(0).into(),
+                    ),
+                    Statement::Directive(Directive {
+                        inputs: vec![FlatVariable::new(0).into()],
+                        outputs: (3..257).map(FlatVariable::new).collect(),
+                        solver: Solver::bits(254),
+                    }),
+                    Statement::constraint(LinComb::zero(), LinComb::zero()),
+                    // terms which cancel out are displayed as zero
+                    Statement::constraint(
+                        LinComb::from(FlatVariable::new(0))
+                            + LinComb::summand(-1, FlatVariable::new(0)),
+                        LinComb::zero(),
+                    ),
+                    Statement::definition(FlatVariable::public(0), FlatVariable::new(2)),
+                ],
+                returns: vec![FlatVariable::public(0)],
+            },
+            private: vec![false, true],
+            metadata: vec![
+                (
+                    0,
+                    SourceMetadata {
+                        file: "main.zok".to_string(),
+                        line: 3,
+                        col: 5,
+                        message: "a == \"b\" // c".to_string(),
+                    },
+                ),
+                (
+                    2,
+                    SourceMetadata {
+                        file: "main.zok".to_string(),
+                        line: 4,
+                        col: 5,
+                        message: "a - a == 0".to_string(),
+                    },
+                ),
+            ]
+            .into_iter()
+            .collect(),
+            origins: vec![
+                (
+                    0,
+                    Origin {
+                        stack: vec!["main.zok::main".to_string()],
+                        file: "C:\\main.zok".to_string(),
+                        line: 2,
+                    },
+                ),
+                (
+                    3,
+                    Origin {
+                        stack: vec!["main.zok::main".to_string(), "main.zok::foo".to_string()],
+                        file: "main.zok".to_string(),
+                        line: 7,
+                    },
+                ),
+            ]
+            .into_iter()
+            .collect(),
+        };
+
+        let text = format!("{}", p);
+        assert!(text.starts_with("#pragma curve bn128\ndef main(_0, private _1) -> (1):\n"));
+        assert_eq!(text.parse::<Prog<Bn128Field>>().unwrap(), p);
+        assert_eq!(ProgEnum::parse(&text).unwrap(), ProgEnum::Bn128Program(p));
+    }
+
+    #[test]
+    fn shorthands() {
+        // linear combinations can be used where quadratic combinations are expected, and terms can omit `1 *` and
+        // `* ~one`. The curve defaults to bn128, and comments are ignored
+        let p = ProgEnum::parse(
+            r#"
+def main(_0) -> (1):
+    // the inverse of `_0`
+    # _1 = Div(1, _0)
+    (_0) * (_1) == 1
+    _1 + -1 == ~out_0
+    return ~out_0
+"#,
+        )
+        .unwrap();
+
+        let expected: Prog<Bn128Field> = Prog {
+            main: Function {
+                id: "main".to_string(),
+                arguments: vec![FlatVariable::new(0)],
+                statements: vec![
+                    Statement::Directive(Directive {
+                        inputs: vec![FlatVariable::one().into(), FlatVariable::new(0).into()],
+                        outputs: vec![FlatVariable::new(1)],
+                        solver: Solver::Div,
+                    }),
+                    Statement::constraint(
+                        QuadComb::from_linear_combinations(
+                            FlatVariable::new(0).into(),
+                            FlatVariable::new(1).into(),
+                        ),
+                        FlatVariable::one(),
+                    ),
+                    Statement::definition(
+                        FlatVariable::public(0),
+                        LinComb::from(FlatVariable::new(1))
+                            + LinComb::summand(-1, FlatVariable::one()),
+                    ),
+                ],
+                returns: vec![FlatVariable::public(0)],
+            },
+            private: vec![false],
+            origins: BTreeMap::new(),
+            metadata: BTreeMap::new(),
+        };
+
+        assert_eq!(p, ProgEnum::Bn128Program(expected));
+    }
+
+    #[test]
+    fn errors() {
+        let error = |s: &str| {
+            let e = s.parse::<Prog<Bn128Field>>().unwrap_err();
+            (e.line(), e.message().to_string())
+        };
+
+        assert_eq!(
+            error("#pragma curve bls12_381\ndef main() -> (0):\n\treturn\n"),
+            (
+                1,
+                "Program is written for curve bls12_381, expected bn128".to_string()
+            )
+        );
+        assert_eq!(
+            error("def main() -> (0):\n\t# _0 = Div(1)\n\treturn\n"),
+            (
+                2,
+                "Solver `Div` has 2 inputs and 1 outputs, found 1 inputs and 1 outputs".to_string()
+            )
+        );
+        assert_eq!(
+            error("def main() -> (0):\n\t# _0 = Mul(1, 1)\n\treturn\n"),
+            (2, "Unknown solver `Mul`".to_string())
+        );
+        assert_eq!(
+            error("def main() -> (0):\n\n\t(_0) * (_1) = _2\n\treturn\n"),
+            (3, "Expected `==`, found `= _2`".to_string())
+        );
+        assert_eq!(
+            error("def main() -> (1):\n\treturn\n"),
+            (2, "Expected 1 return values, found 0".to_string())
+        );
+        assert_eq!(
+            error("def main(_0) -> (0):\n\t_0 == _1\n"),
+            (3, "Expected `return`".to_string())
+        );
+        assert_eq!(
+            error("def main(_0) -> (0):\n\t(_0) * (_1) == _2\n\treturn\n"),
+            (2, "Variable `_1` is used before it is defined".to_string())
+        );
+        assert_eq!(
+            error("def main(_0) -> (0):\n\t_0 == 2*_1\n\treturn\n"),
+            (2, "Variable `_1` is used before it is defined".to_string())
+        );
+        assert_eq!(
+            error("def main() -> (0):\n\t# _1 = Div(1, _0)\n\treturn\n"),
+            (2, "Variable `_0` is used before it is defined".to_string())
+        );
+        assert_eq!(
+            error("def main(_0) -> (1):\n\t_0 == _1\n\treturn ~out_0\n"),
+            (
+                3,
+                "Variable `~out_0` is returned but never defined".to_string()
+            )
+        );
+    }
+}
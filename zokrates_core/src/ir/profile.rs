@@ -0,0 +1,268 @@
+use crate::ir::{Prog, Statement};
+use crate::source_map::Origin;
+use std::collections::HashMap;
+use std::fmt;
+use zokrates_field::Field;
+
+/// The label of the statements which precede any origin marker, such as the checks on the inputs of `main`
+pub const INPUTS: &str = "<inputs>";
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Cost {
+    pub constraints: usize,
+    pub directives: usize,
+}
+
+impl std::ops::AddAssign for Cost {
+    fn add_assign(&mut self, other: Self) {
+        self.constraints += other.constraints;
+        self.directives += other.directives;
+    }
+}
+
+/// A breakdown of the constraints and directives of a program by origin
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Profile {
+    pub costs: HashMap<Option<Origin>, Cost>,
+}
+
+impl Profile {
+    pub fn total(&self) -> Cost {
+        let mut total = Cost::default();
+        for cost in self.costs.values() {
+            total += *cost;
+        }
+        total
+    }
+
+    /// The costs grouped by the function the statements originate from, most expensive first
+    pub fn by_function(&self) -> Vec<(String, Cost)> {
+        self.group_by(|origin| match origin {
+            Some(origin) => origin
+                .stack
+                .last()
+                .cloned()
+                .unwrap_or_else(|| INPUTS.to_string()),
+            None => INPUTS.to_string(),
+        })
+    }
+
+    /// The costs grouped by the source line the statements originate from, most expensive first
+    pub fn by_line(&self) -> Vec<(String, Cost)> {
+        self.group_by(|origin| match origin {
+            Some(origin) => origin.to_string(),
+            None => INPUTS.to_string(),
+        })
+    }
+
+    /// The constraint counts in the folded stack format consumed by flamegraph tools
+    pub fn folded(&self) -> String {
+        let mut lines: Vec<_> = self
+            .costs
+            .iter()
+            .filter(|(_, cost)| cost.constraints > 0)
+            .map(|(origin, cost)| {
+                let stack = match origin {
+                    Some(origin) => origin
+                        .stack
+                        .iter()
+                        .cloned()
+                        .chain(std::iter::once(origin.to_string()))
+                        .collect::<Vec<_>>()
+                        .join(";"),
+                    None => INPUTS.to_string(),
+                };
+                format!("{} {}", stack, cost.constraints)
+            })
+            .collect();
+        lines.sort();
+        lines.into_iter().map(|l| format!("{}\n", l)).collect()
+    }
+
+    fn group_by<F: Fn(&Option<Origin>) -> String>(&self, key: F) -> Vec<(String, Cost)> {
+        let mut groups: HashMap<String, Cost> = HashMap::new();
+        for (origin, cost) in &self.costs {
+            *groups.entry(key(origin)).or_default() += *cost;
+        }
+        let mut groups: Vec<_> = groups.into_iter().collect();
+        groups.sort_by(|(a_key, a), (b_key, b)| {
+            b.constraints
+                .cmp(&a.constraints)
+                .then(b.directives.cmp(&a.directives))
+                .then(a_key.cmp(b_key))
+        });
+        groups
+    }
+}
+
+impl fmt::Display for Profile {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let write_table = |f: &mut fmt::Formatter, title: &str, rows: Vec<(String, Cost)>| {
+            writeln!(f, "{:>12} {:>12}  {}", "constraints", "directives", title)?;
+            for (key, cost) in rows {
+                writeln!(
+                    f,
+                    "{:>12} {:>12}  {}",
+                    cost.constraints, cost.directives, key
+                )?;
+            }
+            Ok(())
+        };
+
+        write_table(f, "function", self.by_function())?;
+        writeln!(f)?;
+        write_table(f, "line", self.by_line())?;
+        writeln!(f)?;
+        let total = self.total();
+        write!(
+            f,
+            "{:>12} {:>12}  total",
+            total.constraints, total.directives
+        )
+    }
+}
+
+impl<T: Field> Prog<T> {
+    /// Attribute each constraint and directive to the last origin set at or before its index
+    pub fn profile(&self) -> Profile {
+        let mut profile = Profile::default();
+        let mut origin = None;
+
+        for (index, statement) in self.main.statements.iter().enumerate() {
+            if let Some(o) = self.origins.get(&index) {
+                origin = Some(o.clone());
+            }
+
+            match statement {
+                Statement::Constraint(..) => {
+                    profile.costs.entry(origin.clone()).or_default().constraints += 1
+                }
+                Statement::Directive(..) => {
+                    profile.costs.entry(origin.clone()).or_default().directives += 1
+                }
+            }
+        }
+
+        profile
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::flat_absy::FlatVariable;
+    use crate::ir::{Directive, Function, LinComb, QuadComb};
+    use crate::solvers::Solver;
+    use std::collections::BTreeMap;
+    use zokrates_field::Bn128Field;
+
+    fn origin(stack: &[&str], line: usize) -> Origin {
+        Origin {
+            stack: stack.iter().map(|s| s.to_string()).collect(),
+            file: String::from("main.zok"),
+            line,
+        }
+    }
+
+    fn constraint() -> Statement<Bn128Field> {
+        Statement::Constraint(
+            QuadComb::from_linear_combinations(
+                LinComb::from(FlatVariable::new(0)),
+                LinComb::from(FlatVariable::new(0)),
+            ),
+            LinComb::from(FlatVariable::new(0)),
+        )
+    }
+
+    fn directive() -> Statement<Bn128Field> {
+        Statement::Directive(Directive {
+            inputs: vec![],
+            outputs: vec![FlatVariable::new(1)],
+            solver: Solver::Xor,
+        })
+    }
+
+    fn prog() -> Prog<Bn128Field> {
+        Prog {
+            main: Function {
+                id: String::from("main"),
+                statements: vec![
+                    constraint(),
+                    directive(),
+                    constraint(),
+                    constraint(),
+                    constraint(),
+                    constraint(),
+                ],
+                arguments: vec![FlatVariable::new(0)],
+                returns: vec![],
+            },
+            private: vec![false],
+            metadata: BTreeMap::new(),
+            origins: vec![
+                (1, origin(&["main::main"], 2)),
+                (3, origin(&["main::main", "main::foo"], 7)),
+                (5, origin(&["main::main"], 3)),
+            ]
+            .into_iter()
+            .collect(),
+        }
+    }
+
+    #[test]
+    fn by_function() {
+        assert_eq!(
+            prog().profile().by_function(),
+            vec![
+                (
+                    String::from("main::main"),
+                    Cost {
+                        constraints: 2,
+                        directives: 1
+                    }
+                ),
+                (
+                    String::from("main::foo"),
+                    Cost {
+                        constraints: 2,
+                        directives: 0
+                    }
+                ),
+                (
+                    String::from(INPUTS),
+                    Cost {
+                        constraints: 1,
+                        directives: 0
+                    }
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn by_line() {
+        let by_line = prog().profile().by_line();
+        assert_eq!(by_line.len(), 4);
+        assert_eq!(by_line[0].0, "main.zok:7");
+        assert_eq!(by_line[1].0, "main.zok:2");
+    }
+
+    #[test]
+    fn folded() {
+        assert_eq!(
+            prog().profile().folded(),
+            "<inputs> 1\nmain::main;main.zok:2 1\nmain::main;main.zok:3 1\nmain::main;main::foo;main.zok:7 2\n"
+        );
+    }
+
+    #[test]
+    fn total() {
+        assert_eq!(
+            prog().profile().total(),
+            Cost {
+                constraints: 5,
+                directives: 1
+            }
+        );
+    }
+}
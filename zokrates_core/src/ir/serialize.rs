@@ -1,10 +1,45 @@
 use bincode::{deserialize_from, serialize_into, Infinite};
-use ir::Prog;
+use ir::{Function, Prog};
+use std::collections::BTreeMap;
 use std::io::{Read, Write};
 use zokrates_field::*;
 
 const ZOKRATES_MAGIC: &[u8; 4] = &[0x5a, 0x4f, 0x4b, 0];
 const ZOKRATES_VERSION_1: &[u8; 4] = &[0, 0, 0, 1];
+// the source metadata of the constraints and the origins of the statements moved to side tables in version 2
+const ZOKRATES_VERSION_2: &[u8; 4] = &[0, 0, 0, 2];
+
+/// The version of the format programs are serialised with
+pub const FORMAT_VERSION: &[u8; 4] = ZOKRATES_VERSION_2;
+
+/// A program of version 1, which is read with empty side tables
+#[derive(Deserialize)]
+struct ProgV1<T> {
+    main: Function<T>,
+    private: Vec<bool>,
+}
+
+impl<T> From<ProgV1<T>> for Prog<T> {
+    fn from(p: ProgV1<T>) -> Prog<T> {
+        Prog {
+            main: p.main,
+            private: p.private,
+            metadata: BTreeMap::new(),
+            origins: BTreeMap::new(),
+        }
+    }
+}
+
+fn deserialize_prog<T: Field, R: Read>(r: &mut R, version: &[u8; 4]) -> Result<Prog<T>, String> {
+    let error = |e| format!("Cannot read program: {}", e);
+
+    match version {
+        v if v == ZOKRATES_VERSION_1 => deserialize_from::<_, ProgV1<T>, _>(r, Infinite)
+            .map(Prog::from)
+            .map_err(error),
+        _ => deserialize_from(r, Infinite).map_err(error),
+    }
+}
 
 #[derive(PartialEq, Debug)]
 pub enum ProgEnum {
@@ -15,7 +50,7 @@ pub enum ProgEnum {
 impl<T: Field> Prog<T> {
     pub fn serialize<W: Write>(&self, mut w: W) {
         w.write(ZOKRATES_MAGIC).unwrap();
-        w.write(ZOKRATES_VERSION_1).unwrap();
+        w.write(FORMAT_VERSION).unwrap();
         w.write(&T::id()).unwrap();
 
         serialize_into(&mut w, self, Infinite).unwrap();
@@ -23,6 +58,7 @@ impl<T: Field> Prog<T> {
 }
 
 impl ProgEnum {
+    /// Read a program in the binary format, or in the textual format if it does not start with the magic number
     pub fn deserialize<R: Read>(mut r: R) -> Result<Self, String> {
         // Check the magic number, `ZOK`
         let mut magic = [0; 4];
@@ -30,31 +66,36 @@ impl ProgEnum {
             .map_err(|_| String::from("Cannot read magic number"))?;
 
         if &magic == ZOKRATES_MAGIC {
-            // Check the version, 1
+            // Check the version, 1 or 2
             let mut version = [0; 4];
             r.read_exact(&mut version)
                 .map_err(|_| String::from("Cannot read version"))?;
 
-            if &version == ZOKRATES_VERSION_1 {
+            if &version == ZOKRATES_VERSION_2 || &version == ZOKRATES_VERSION_1 {
                 // Check the curve identifier, deserializing accordingly
                 let mut curve = [0; 4];
                 r.read_exact(&mut curve)
                     .map_err(|_| String::from("Cannot read curve identifier"))?;
 
                 match curve {
-                    m if m == Bls12Field::id() => Ok(ProgEnum::Bls12Program(
-                        deserialize_from(&mut r, Infinite).unwrap(),
-                    )),
-                    m if m == Bn128Field::id() => Ok(ProgEnum::Bn128Program(
-                        deserialize_from(&mut r, Infinite).unwrap(),
-                    )),
+                    m if m == Bls12Field::id() => {
+                        Ok(ProgEnum::Bls12Program(deserialize_prog(&mut r, &version)?))
+                    }
+                    m if m == Bn128Field::id() => {
+                        Ok(ProgEnum::Bn128Program(deserialize_prog(&mut r, &version)?))
+                    }
                     _ => Err(String::from("Unknown curve identifier")),
                 }
             } else {
                 Err(String::from("Unknown version"))
             }
         } else {
-            Err(String::from("Wrong magic number"))
+            // programs can also be read in the textual format they are displayed in
+            let mut text = magic.to_vec();
+            r.read_to_end(&mut text)
+                .map_err(|_| String::from("Cannot read program"))?;
+            let text = String::from_utf8(text).map_err(|_| String::from("Wrong magic number"))?;
+            ProgEnum::parse(&text).map_err(|e| format!("Cannot parse program: {}", e))
         }
     }
 }
@@ -63,11 +104,12 @@ impl ProgEnum {
 mod tests {
     use super::*;
     use ir;
+    use std::collections::BTreeMap;
     use std::io::{Cursor, Seek, SeekFrom};
     use zokrates_field::{Bls12Field, Bn128Field};
 
     #[test]
-    fn ser_deser_v1() {
+    fn ser_deser_v2() {
         let p: ir::Prog<Bn128Field> = ir::Prog {
             main: ir::Function {
                 arguments: vec![],
@@ -76,6 +118,8 @@ mod tests {
                 statements: vec![],
             },
             private: vec![],
+            origins: BTreeMap::new(),
+            metadata: BTreeMap::new(),
         };
 
         let mut buffer = Cursor::new(vec![]);
@@ -97,6 +141,8 @@ mod tests {
                 statements: vec![],
             },
             private: vec![],
+            origins: BTreeMap::new(),
+            metadata: BTreeMap::new(),
         };
 
         let mut buffer = Cursor::new(vec![]);
@@ -110,4 +156,46 @@ mod tests {
 
         assert_eq!(ProgEnum::Bls12Program(p), deserialized_p);
     }
+
+    #[test]
+    fn deser_v1() {
+        let p: ir::Prog<Bn128Field> = ir::Prog {
+            main: ir::Function {
+                arguments: vec![],
+                id: "something".to_string(),
+                returns: vec![],
+                statements: vec![],
+            },
+            private: vec![],
+            origins: BTreeMap::new(),
+            metadata: BTreeMap::new(),
+        };
+
+        // programs of version 1 only have a function and the visibility of its arguments
+        let mut buffer = Cursor::new(vec![]);
+        buffer.write(ZOKRATES_MAGIC).unwrap();
+        buffer.write(ZOKRATES_VERSION_1).unwrap();
+        buffer.write(&Bn128Field::id()).unwrap();
+        serialize_into(&mut buffer, &(&p.main, &p.private), Infinite).unwrap();
+        buffer.seek(SeekFrom::Start(0)).unwrap();
+
+        assert_eq!(
+            ProgEnum::deserialize(buffer).unwrap(),
+            ProgEnum::Bn128Program(p)
+        );
+    }
+
+    #[test]
+    fn deser_truncated() {
+        let mut buffer = Cursor::new(vec![]);
+        buffer.write(ZOKRATES_MAGIC).unwrap();
+        buffer.write(ZOKRATES_VERSION_2).unwrap();
+        buffer.write(&Bn128Field::id()).unwrap();
+        buffer.write(&[0, 0]).unwrap();
+        buffer.seek(SeekFrom::Start(0)).unwrap();
+
+        assert!(ProgEnum::deserialize(buffer)
+            .unwrap_err()
+            .starts_with("Cannot read program"));
+    }
 }
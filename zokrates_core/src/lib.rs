@@ -16,6 +16,7 @@ extern crate hex;
 extern crate lazy_static;
 extern crate pairing_ce as pairing;
 extern crate regex;
+extern crate sha2;
 extern crate zokrates_common;
 extern crate zokrates_field;
 extern crate zokrates_pest_ast;
@@ -32,8 +33,12 @@ mod static_analysis;
 mod zir;
 
 pub mod absy;
+pub mod cache;
 pub mod compile;
+pub mod diagnostics;
 pub mod flat_absy;
 pub mod ir;
 pub mod proof_system;
+pub mod source_map;
+pub mod symbols;
 pub mod typed_absy;
@@ -49,12 +49,15 @@ impl<T: Field> Folder<T> for DuplicateOptimizer {
 mod tests {
     use super::*;
     use flat_absy::FlatVariable;
+    use std::collections::BTreeMap;
     use zokrates_field::Bn128Field;
 
     #[test]
     fn identity() {
         let p: Prog<Bn128Field> = Prog {
             private: vec![],
+            origins: BTreeMap::new(),
+            metadata: BTreeMap::new(),
             main: Function {
                 id: "main".to_string(),
                 statements: vec![
@@ -95,6 +98,8 @@ mod tests {
 
         let p: Prog<Bn128Field> = Prog {
             private: vec![],
+            origins: BTreeMap::new(),
+            metadata: BTreeMap::new(),
             main: Function {
                 id: "main".to_string(),
                 statements: vec![
@@ -117,6 +122,8 @@ mod tests {
 
         let expected = Prog {
             private: vec![],
+            origins: BTreeMap::new(),
+            metadata: BTreeMap::new(),
             main: Function {
                 id: "main".to_string(),
                 statements: vec![
@@ -37,7 +37,7 @@
 //     - otherwise return `c_0`
 
 use crate::flat_absy::flat_variable::FlatVariable;
-use crate::ir::folder::{fold_function, Folder};
+use crate::ir::folder::{fold_function, fold_module, Folder};
 use crate::ir::LinComb;
 use crate::ir::*;
 use std::collections::{HashMap, HashSet};
@@ -62,6 +62,17 @@ impl<T: Field> RedefinitionOptimizer<T> {
     pub fn optimize(p: Prog<T>) -> Prog<T> {
         RedefinitionOptimizer::new().fold_module(p)
     }
+
+    fn reset(&mut self, fun: &Function<T>) {
+        self.substitution.drain();
+        self.ignore.drain();
+
+        // to prevent the optimiser from replacing outputs, add them to the ignored set
+        self.ignore.extend(fun.returns.iter().cloned());
+
+        // to prevent the optimiser from replacing ~one, add it to the ignored set
+        self.ignore.insert(FlatVariable::one());
+    }
 }
 
 impl<T: Field> Folder<T> for RedefinitionOptimizer<T> {
@@ -208,16 +219,13 @@ impl<T: Field> Folder<T> for RedefinitionOptimizer<T> {
         a
     }
 
-    fn fold_function(&mut self, fun: Function<T>) -> Function<T> {
-        self.substitution.drain();
-        self.ignore.drain();
-
-        // to prevent the optimiser from replacing outputs, add them to the ignored set
-        self.ignore.extend(fun.returns.iter().cloned());
-
-        // to prevent the optimiser from replacing ~one, add it to the ignored set
-        self.ignore.insert(FlatVariable::one());
+    fn fold_module(&mut self, p: Prog<T>) -> Prog<T> {
+        self.reset(&p.main);
+        fold_module(self, p)
+    }
 
+    fn fold_function(&mut self, fun: Function<T>) -> Function<T> {
+        self.reset(&fun);
         fold_function(self, fun)
     }
 }
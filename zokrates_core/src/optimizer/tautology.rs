@@ -42,4 +42,63 @@ impl<T: Field> Folder<T> for TautologyOptimizer {
 }
 
 #[cfg(test)]
-mod tests {}
+mod tests {
+    use super::*;
+    use flat_absy::FlatVariable;
+    use source_map::SourceMetadata;
+    use std::collections::BTreeMap;
+    use zokrates_field::Bn128Field;
+
+    #[test]
+    fn keep_metadata() {
+        // _0 * 1 == _0
+        // _0 * _0 == _0 // "a * a == a"
+
+        // should be turned into
+        // _0 * _0 == _0 // "a * a == a"
+
+        let metadata = SourceMetadata {
+            file: "main.zok".to_string(),
+            line: 2,
+            col: 5,
+            message: "a * a == a".to_string(),
+        };
+
+        let square = Statement::constraint(
+            QuadComb::from_linear_combinations(
+                FlatVariable::new(0).into(),
+                FlatVariable::new(0).into(),
+            ),
+            FlatVariable::new(0),
+        );
+
+        let p: Prog<Bn128Field> = Prog {
+            private: vec![false],
+            origins: BTreeMap::new(),
+            metadata: vec![(1, metadata.clone())].into_iter().collect(),
+            main: Function {
+                id: "main".to_string(),
+                statements: vec![
+                    Statement::constraint(FlatVariable::new(0), FlatVariable::new(0)),
+                    square.clone(),
+                ],
+                returns: vec![],
+                arguments: vec![FlatVariable::new(0)],
+            },
+        };
+
+        let expected = Prog {
+            private: vec![false],
+            origins: BTreeMap::new(),
+            metadata: vec![(0, metadata)].into_iter().collect(),
+            main: Function {
+                id: "main".to_string(),
+                statements: vec![square],
+                returns: vec![],
+                arguments: vec![FlatVariable::new(0)],
+            },
+        };
+
+        assert_eq!(TautologyOptimizer::optimize(p), expected);
+    }
+}
@@ -0,0 +1,229 @@
+//! Evaluation domains of the scalar field, over which the backends interpolate polynomials represented by their
+//! coefficients
+
+use bellman::pairing::ff::{Field, PrimeField};
+use bellman::pairing::Engine;
+
+/// The multiplicative subgroup of the `size`-th roots of unity, generated by `omega`
+pub struct Domain<E: Engine> {
+    pub size: usize,
+    pub omega: E::Fr,
+    omega_inv: E::Fr,
+    size_inv: E::Fr,
+}
+
+impl<E: Engine> Domain<E> {
+    /// The smallest domain with at least `size` elements
+    pub fn new(size: usize) -> Result<Self, String> {
+        let size = size.next_power_of_two();
+        let exp = size.trailing_zeros();
+
+        if exp > E::Fr::S {
+            return Err(format!(
+                "The scalar field has no domain of size 2^{}, the maximum is 2^{}",
+                exp,
+                E::Fr::S
+            ));
+        }
+
+        let mut omega = E::Fr::root_of_unity();
+        for _ in exp..E::Fr::S {
+            omega.square();
+        }
+
+        Ok(Domain {
+            size,
+            omega,
+            omega_inv: omega.inverse().unwrap(),
+            size_inv: scalar::<E>(size as u64).inverse().unwrap(),
+        })
+    }
+
+    /// `omega^i`
+    pub fn element(&self, i: usize) -> E::Fr {
+        self.omega.pow([i as u64])
+    }
+
+    /// The evaluations over the domain of the polynomial with coefficients `coeffs`
+    pub fn fft(&self, coeffs: &[E::Fr]) -> Vec<E::Fr> {
+        let mut values = self.pad(coeffs);
+        fft(&mut values, &self.omega);
+        values
+    }
+
+    /// The coefficients of the polynomial with evaluations `values` over the domain
+    pub fn ifft(&self, values: &[E::Fr]) -> Vec<E::Fr> {
+        let mut coeffs = self.pad(values);
+        fft(&mut coeffs, &self.omega_inv);
+        for c in coeffs.iter_mut() {
+            c.mul_assign(&self.size_inv);
+        }
+        coeffs
+    }
+
+    /// The evaluations of the polynomial with coefficients `coeffs` over the coset `g * omega^i`, where `g` is the
+    /// multiplicative generator of the field
+    pub fn coset_fft(&self, coeffs: &[E::Fr]) -> Vec<E::Fr> {
+        let mut coeffs = coeffs.to_vec();
+        distribute_powers::<E>(&mut coeffs, &E::Fr::multiplicative_generator());
+        self.fft(&coeffs)
+    }
+
+    /// The coefficients of the polynomial with evaluations `values` over the coset `g * omega^i`
+    pub fn icoset_fft(&self, values: &[E::Fr]) -> Vec<E::Fr> {
+        let mut coeffs = self.ifft(values);
+        distribute_powers::<E>(
+            &mut coeffs,
+            &E::Fr::multiplicative_generator().inverse().unwrap(),
+        );
+        coeffs
+    }
+
+    /// The vanishing polynomial of the domain `X^n - 1`, evaluated at `x`
+    pub fn vanishing(&self, x: &E::Fr) -> E::Fr {
+        let mut result = x.pow([self.size as u64]);
+        result.sub_assign(&E::Fr::one());
+        result
+    }
+
+    /// The Lagrange polynomials `L_i`, which are one at `omega^i` and zero elsewhere in the domain, evaluated at `x`
+    /// for all `i` below `count`. `x` must not be in the domain.
+    pub fn lagrange(&self, count: usize, x: &E::Fr) -> Vec<E::Fr> {
+        // L_i(x) = omega^i * (x^n - 1) / (n * (x - omega^i))
+        let mut numerator = self.vanishing(x);
+        numerator.mul_assign(&self.size_inv);
+
+        let mut element = E::Fr::one();
+        (0..count)
+            .map(|_| {
+                let mut denominator = *x;
+                denominator.sub_assign(&element);
+                let mut l = denominator.inverse().unwrap();
+                l.mul_assign(&element);
+                l.mul_assign(&numerator);
+                element.mul_assign(&self.omega);
+                l
+            })
+            .collect()
+    }
+
+    fn pad(&self, values: &[E::Fr]) -> Vec<E::Fr> {
+        assert!(values.len() <= self.size);
+        let mut values = values.to_vec();
+        values.resize(self.size, E::Fr::zero());
+        values
+    }
+}
+
+/// The scalar `n`
+pub fn scalar<E: Engine>(n: u64) -> E::Fr {
+    E::Fr::from_repr(n.into()).unwrap()
+}
+
+/// Evaluates the polynomial with coefficients `coeffs` at `x`
+pub fn evaluate<E: Engine>(coeffs: &[E::Fr], x: &E::Fr) -> E::Fr {
+    coeffs.iter().rev().fold(E::Fr::zero(), |mut acc, c| {
+        acc.mul_assign(x);
+        acc.add_assign(c);
+        acc
+    })
+}
+
+/// Multiplies the `i`-th coefficient by `g^i`, so that evaluating over a domain evaluates over its coset by `g`
+fn distribute_powers<E: Engine>(coeffs: &mut [E::Fr], g: &E::Fr) {
+    let mut power = E::Fr::one();
+    for c in coeffs.iter_mut() {
+        c.mul_assign(&power);
+        power.mul_assign(g);
+    }
+}
+
+/// The radix-2 Cooley-Tukey FFT, in place. The length of `a` is a power of two and `omega` a root of unity of that
+/// order.
+fn fft<F: Field>(a: &mut [F], omega: &F) {
+    let n = a.len();
+    let log_n = n.trailing_zeros();
+
+    for k in 0..n {
+        let rk = bitreverse(k, log_n);
+        if k < rk {
+            a.swap(k, rk);
+        }
+    }
+
+    let mut m = 1;
+    for _ in 0..log_n {
+        let w_m = omega.pow([(n / (2 * m)) as u64]);
+
+        for k in (0..n).step_by(2 * m) {
+            let mut w = F::one();
+            for j in 0..m {
+                let mut t = a[k + j + m];
+                t.mul_assign(&w);
+                let mut u = a[k + j];
+                u.sub_assign(&t);
+                a[k + j + m] = u;
+                a[k + j].add_assign(&t);
+                w.mul_assign(&w_m);
+            }
+        }
+
+        m *= 2;
+    }
+}
+
+fn bitreverse(mut n: usize, bits: u32) -> usize {
+    let mut r = 0;
+    for _ in 0..bits {
+        r = (r << 1) | (n & 1);
+        n >>= 1;
+    }
+    r
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bellman::pairing::bn256::{Bn256, Fr};
+
+    fn poly(coeffs: &[u64]) -> Vec<Fr> {
+        coeffs.iter().map(|c| scalar::<Bn256>(*c)).collect()
+    }
+
+    #[test]
+    fn fft_and_ifft() {
+        let domain = Domain::<Bn256>::new(5).unwrap();
+        assert_eq!(domain.size, 8);
+
+        let p = poly(&[3, 1, 4, 1, 5, 9]);
+        let values = domain.fft(&p);
+
+        for (i, v) in values.iter().enumerate() {
+            assert_eq!(*v, evaluate::<Bn256>(&p, &domain.element(i)));
+        }
+
+        let mut expected = p.clone();
+        expected.resize(8, Fr::zero());
+        assert_eq!(domain.ifft(&values), expected);
+
+        let values = domain.coset_fft(&p);
+        let mut x = Fr::multiplicative_generator();
+        for v in values.iter() {
+            assert_eq!(*v, evaluate::<Bn256>(&p, &x));
+            x.mul_assign(&domain.omega);
+        }
+        assert_eq!(domain.icoset_fft(&values), expected);
+    }
+
+    #[test]
+    fn lagrange() {
+        let domain = Domain::<Bn256>::new(4).unwrap();
+        let x = scalar::<Bn256>(42);
+
+        for (i, l) in domain.lagrange(4, &x).into_iter().enumerate() {
+            let mut values = vec![Fr::zero(); 4];
+            values[i] = Fr::one();
+            assert_eq!(l, evaluate::<Bn256>(&domain.ifft(&values), &x));
+        }
+    }
+}
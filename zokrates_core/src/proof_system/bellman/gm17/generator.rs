@@ -0,0 +1,120 @@
+//! The setup of GM17, which samples the toxic waste `alpha`, `beta`, `gamma` and the evaluation point `x`
+
+use bellman::pairing::ff::{Field, PrimeField};
+use bellman::pairing::{CurveAffine, CurveProjective, Engine, Wnaf};
+use proof_system::bellman::domain::scalar;
+use proof_system::bellman::gm17::prover::ProvingKey;
+use proof_system::bellman::gm17::sap::Sap;
+use proof_system::bellman::gm17::verifier::VerifyingKey;
+use proof_system::bellman::rand::{Rand, Rng};
+
+pub fn generate<E: Engine, R: Rng>(sap: &Sap<E>, rng: &mut R) -> (ProvingKey<E>, VerifyingKey<E>) {
+    let alpha = E::Fr::rand(rng);
+    let beta = E::Fr::rand(rng);
+    let gamma = E::Fr::rand(rng);
+    let x = E::Fr::rand(rng);
+
+    let (a, c) = sap.evaluate(&x);
+    let t = sap.domain.vanishing(&x);
+
+    let mut alpha_beta = alpha;
+    alpha_beta.add_assign(&beta);
+    let mut gamma_t = gamma;
+    gamma_t.mul_assign(&t);
+    let mut gamma_2 = gamma;
+    gamma_2.square();
+    let mut gamma_2_t = gamma_2;
+    gamma_2_t.mul_assign(&t);
+
+    let product = |factors: &[&E::Fr]| {
+        factors.iter().fold(E::Fr::one(), |mut acc, f| {
+            acc.mul_assign(f);
+            acc
+        })
+    };
+
+    // gamma * A_i(x)
+    let a_query: Vec<_> = a.iter().map(|a| product(&[&gamma, a])).collect();
+
+    // gamma * (alpha + beta) * A_i(x) + gamma^2 * C_i(x) for the private variables
+    let c_query_1: Vec<_> = a
+        .iter()
+        .zip(&c)
+        .skip(sap.num_inputs)
+        .map(|(a, c)| {
+            let mut s = product(&[&gamma, &alpha_beta, a]);
+            s.add_assign(&product(&[&gamma_2, c]));
+            s
+        })
+        .collect();
+
+    // 2 * gamma^2 * t(x) * A_i(x)
+    let two = scalar::<E>(2);
+    let c_query_2: Vec<_> = a.iter().map(|a| product(&[&two, &gamma_2_t, a])).collect();
+
+    // gamma^2 * t(x) * x^i, for the coefficients of h
+    let mut power = E::Fr::one();
+    let h_query: Vec<_> = (0..sap.domain.size - 1)
+        .map(|_| {
+            let s = product(&[&gamma_2_t, &power]);
+            power.mul_assign(&x);
+            s
+        })
+        .collect();
+
+    // (alpha + beta) * A_i(x) + gamma * C_i(x) for the public variables
+    let query: Vec<_> = a
+        .iter()
+        .zip(&c)
+        .take(sap.num_inputs)
+        .map(|(a, c)| {
+            let mut s = product(&[&alpha_beta, a]);
+            s.add_assign(&product(&[&gamma, c]));
+            s
+        })
+        .collect();
+
+    let pk = ProvingKey {
+        num_inputs: sap.num_inputs,
+        a_g1: g1::<E>(&a_query),
+        a_g2: g2::<E>(&a_query),
+        c_1: g1::<E>(&c_query_1),
+        c_2: g1::<E>(&c_query_2),
+        h: g1::<E>(&h_query),
+        gamma_t_g1: g1::<E>(&[gamma_t])[0],
+        gamma_t_g2: g2::<E>(&[gamma_t])[0],
+        alpha_beta_gamma_t: g1::<E>(&[product(&[&alpha_beta, &gamma_t])])[0],
+        gamma_2_t_2: g1::<E>(&[product(&[&gamma_2_t, &t])])[0],
+    };
+
+    let vk = VerifyingKey {
+        h: E::G2Affine::one(),
+        g_alpha: g1::<E>(&[alpha])[0],
+        h_beta: g2::<E>(&[beta])[0],
+        g_gamma: g1::<E>(&[gamma])[0],
+        h_gamma: g2::<E>(&[gamma])[0],
+        query: g1::<E>(&query),
+    };
+
+    (pk, vk)
+}
+
+/// The multiples of the generator of G1 by `scalars`
+fn g1<E: Engine>(scalars: &[E::Fr]) -> Vec<E::G1Affine> {
+    multiples::<E::G1>(scalars)
+}
+
+/// The multiples of the generator of G2 by `scalars`
+fn g2<E: Engine>(scalars: &[E::Fr]) -> Vec<E::G2Affine> {
+    multiples::<E::G2>(scalars)
+}
+
+fn multiples<G: CurveProjective>(scalars: &[G::Scalar]) -> Vec<G::Affine> {
+    let mut wnaf = Wnaf::new();
+    let mut base = wnaf.base(G::one(), scalars.len());
+
+    let mut points: Vec<G> = scalars.iter().map(|s| base.scalar(s.into_repr())).collect();
+    G::batch_normalization(&mut points);
+
+    points.into_iter().map(|p| p.into_affine()).collect()
+}
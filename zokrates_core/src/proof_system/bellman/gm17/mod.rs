@@ -0,0 +1,472 @@
+//! A GM17 backend implemented natively on the primitives of bellman, following the construction of libsnark: the
+//! program is reduced to a square arithmetic program, which is proven with the scheme of
+//! [GM17](https://eprint.iacr.org/2017/540)
+
+pub mod generator;
+pub mod prover;
+pub mod sap;
+pub mod verifier;
+
+use regex::Regex;
+
+use zokrates_field::Field;
+
+use crate::ir;
+use crate::proof_system::bellman::{parse_fr, parse_g1, parse_g2};
+use crate::proof_system::bellman::{rng, serialization};
+use crate::proof_system::solidity::{
+    SOLIDITY_G2_ADDITION_LIB, SOLIDITY_PAIRING_LIB, SOLIDITY_PAIRING_LIB_V2,
+};
+use proof_system::{G1Affine, G2Affine, Proof, ProofSystem, Randomness, SetupKeypair, SolidityAbi};
+
+use self::generator::generate;
+use self::prover::{prove, ProvingKey};
+use self::sap::{Assembly, Sap};
+use self::verifier::{verify, VerifyingKey};
+
+pub struct GM17 {}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ProofPoints {
+    a: G1Affine,
+    b: G2Affine,
+    c: G1Affine,
+}
+
+impl ProofPoints {
+    fn into_gm17<T: Field>(self) -> prover::Proof<T::BellmanEngine> {
+        prover::Proof {
+            a: serialization::to_g1::<T>(self.a),
+            b: serialization::to_g2::<T>(self.b),
+            c: serialization::to_g1::<T>(self.c),
+        }
+    }
+}
+
+/// The verification key, in the same format as the one of the libsnark backend
+#[derive(Serialize, Deserialize, Clone)]
+pub struct VerificationKey {
+    h: G2Affine,
+    g_alpha: G1Affine,
+    h_beta: G2Affine,
+    g_gamma: G1Affine,
+    h_gamma: G2Affine,
+    query: Vec<G1Affine>,
+}
+
+impl VerificationKey {
+    fn into_gm17<T: Field>(self) -> VerifyingKey<T::BellmanEngine> {
+        VerifyingKey {
+            h: serialization::to_g2::<T>(self.h),
+            g_alpha: serialization::to_g1::<T>(self.g_alpha),
+            h_beta: serialization::to_g2::<T>(self.h_beta),
+            g_gamma: serialization::to_g1::<T>(self.g_gamma),
+            h_gamma: serialization::to_g2::<T>(self.h_gamma),
+            query: self
+                .query
+                .into_iter()
+                .map(|g1| serialization::to_g1::<T>(g1))
+                .collect(),
+        }
+    }
+}
+
+/// The SAP of a program, and the constraint system it was reduced from, with the values of its variables if a
+/// witness is given
+fn sap<T: Field>(
+    program: ir::Prog<T>,
+    witness: Option<ir::Witness<T>>,
+) -> Result<(Sap<T::BellmanEngine>, Assembly<T::BellmanEngine>), String> {
+    let mut assembly = Assembly::new(witness.is_some());
+    program
+        .synthesize(&mut assembly, witness)
+        .map_err(|e| e.to_string())?;
+
+    Ok((Sap::new(&assembly)?, assembly))
+}
+
+impl<T: Field> ProofSystem<T> for GM17 {
+    type VerificationKey = VerificationKey;
+    type ProofPoints = ProofPoints;
+
+    fn setup(
+        program: ir::Prog<T>,
+        randomness: Randomness,
+    ) -> Result<SetupKeypair<VerificationKey>, String> {
+        let (sap, _) = sap(program, None)?;
+        let (pk, vk) = generate(&sap, &mut rng(randomness));
+
+        let mut raw_pk: Vec<u8> = Vec::new();
+        pk.write(&mut raw_pk).unwrap();
+
+        let vk = VerificationKey {
+            h: parse_g2::<T>(&vk.h),
+            g_alpha: parse_g1::<T>(&vk.g_alpha),
+            h_beta: parse_g2::<T>(&vk.h_beta),
+            g_gamma: parse_g1::<T>(&vk.g_gamma),
+            h_gamma: parse_g2::<T>(&vk.h_gamma),
+            query: vk.query.iter().map(|g1| parse_g1::<T>(g1)).collect(),
+        };
+
+        Ok(SetupKeypair::new(vk, raw_pk))
+    }
+
+    fn generate_proof(
+        program: ir::Prog<T>,
+        witness: ir::Witness<T>,
+        proving_key: Vec<u8>,
+        randomness: Randomness,
+    ) -> Result<Proof<ProofPoints>, String> {
+        let (sap, assembly) = sap(program, Some(witness))?;
+        let pk = ProvingKey::read(proving_key.as_slice()).map_err(|e| e.to_string())?;
+
+        let proof = prove(&sap, &pk, &sap.assign(&assembly), &mut rng(randomness))?;
+
+        let proof_points = ProofPoints {
+            a: parse_g1::<T>(&proof.a),
+            b: parse_g2::<T>(&proof.b),
+            c: parse_g1::<T>(&proof.c),
+        };
+
+        let inputs = assembly.inputs[1..]
+            .iter()
+            .map(parse_fr::<T>)
+            .collect::<Vec<_>>();
+
+        let mut raw: Vec<u8> = Vec::new();
+        proof.write(&mut raw).unwrap();
+
+        Ok(Proof::<ProofPoints>::new(
+            proof_points,
+            inputs,
+            hex::encode(&raw),
+        ))
+    }
+
+    fn export_solidity_verifier(vk: VerificationKey, abi: SolidityAbi) -> String {
+        let (mut template_text, solidity_pairing_lib) = match abi {
+            SolidityAbi::V1 => (
+                String::from(CONTRACT_TEMPLATE),
+                String::from(SOLIDITY_PAIRING_LIB),
+            ),
+            SolidityAbi::V2 => (
+                String::from(CONTRACT_TEMPLATE_V2),
+                String::from(SOLIDITY_PAIRING_LIB_V2),
+            ),
+        };
+
+        // replace things in template
+        let vk_regex = Regex::new(r#"(<%vk_[^i%]*%>)"#).unwrap();
+        let vk_query_len_regex = Regex::new(r#"(<%vk_query_length%>)"#).unwrap();
+        let vk_query_repeat_regex = Regex::new(r#"(<%vk_query_pts%>)"#).unwrap();
+        let vk_input_len_regex = Regex::new(r#"(<%vk_input_length%>)"#).unwrap();
+
+        template_text = vk_regex
+            .replace(template_text.as_str(), vk.h.to_string().as_str())
+            .into_owned();
+
+        template_text = vk_regex
+            .replace(template_text.as_str(), vk.g_alpha.to_string().as_str())
+            .into_owned();
+
+        template_text = vk_regex
+            .replace(template_text.as_str(), vk.h_beta.to_string().as_str())
+            .into_owned();
+
+        template_text = vk_regex
+            .replace(template_text.as_str(), vk.g_gamma.to_string().as_str())
+            .into_owned();
+
+        template_text = vk_regex
+            .replace(template_text.as_str(), vk.h_gamma.to_string().as_str())
+            .into_owned();
+
+        let query_count: usize = vk.query.len();
+        template_text = vk_query_len_regex
+            .replace(template_text.as_str(), format!("{}", query_count).as_str())
+            .into_owned();
+
+        template_text = vk_input_len_regex
+            .replace(
+                template_text.as_str(),
+                format!("{}", query_count - 1).as_str(),
+            )
+            .into_owned();
+
+        let mut query_repeat_text = String::new();
+        for (i, g1) in vk.query.iter().enumerate() {
+            query_repeat_text.push_str(
+                format!(
+                    "vk.query[{}] = Pairing.G1Point({});",
+                    i,
+                    g1.to_string().as_str()
+                )
+                .as_str(),
+            );
+            if i < query_count - 1 {
+                query_repeat_text.push_str("\n        ");
+            }
+        }
+
+        template_text = vk_query_repeat_regex
+            .replace(template_text.as_str(), query_repeat_text.as_str())
+            .into_owned();
+
+        let re = Regex::new(r"(?P<v>0[xX][0-9a-fA-F]{64})").unwrap();
+        template_text = re.replace_all(&template_text, "uint256($v)").to_string();
+
+        format!(
+            "{}{}{}",
+            SOLIDITY_G2_ADDITION_LIB, solidity_pairing_lib, template_text
+        )
+    }
+
+    fn verify(vk: VerificationKey, proof: Proof<ProofPoints>) -> bool {
+        let vk = vk.into_gm17::<T>();
+
+        let public_inputs: Vec<_> = proof
+            .inputs
+            .iter()
+            .map(|s| serialization::to_fr::<T>(s))
+            .collect();
+
+        verify(&vk, &proof.proof.into_gm17::<T>(), &public_inputs)
+    }
+}
+
+const CONTRACT_TEMPLATE_V2: &str = r#"
+contract Verifier {
+    using Pairing for *;
+    struct VerifyingKey {
+        Pairing.G2Point h;
+        Pairing.G1Point g_alpha;
+        Pairing.G2Point h_beta;
+        Pairing.G1Point g_gamma;
+        Pairing.G2Point h_gamma;
+        Pairing.G1Point[] query;
+    }
+    struct Proof {
+        Pairing.G1Point a;
+        Pairing.G2Point b;
+        Pairing.G1Point c;
+    }
+    function verifyingKey() pure internal returns (VerifyingKey memory vk) {
+        vk.h= Pairing.G2Point(<%vk_h%>);
+        vk.g_alpha = Pairing.G1Point(<%vk_g_alpha%>);
+        vk.h_beta = Pairing.G2Point(<%vk_h_beta%>);
+        vk.g_gamma = Pairing.G1Point(<%vk_g_gamma%>);
+        vk.h_gamma = Pairing.G2Point(<%vk_h_gamma%>);
+        vk.query = new Pairing.G1Point[](<%vk_query_length%>);
+        <%vk_query_pts%>
+    }
+    function verify(uint[] memory input, Proof memory proof) internal view returns (uint) {
+        uint256 snark_scalar_field = 21888242871839275222246405745257275088548364400416034343698204186575808495617;
+        VerifyingKey memory vk = verifyingKey();
+        require(input.length + 1 == vk.query.length);
+        // Compute the linear combination vk_x
+        Pairing.G1Point memory vk_x = Pairing.G1Point(0, 0);
+        for (uint i = 0; i < input.length; i++) {
+            require(input[i] < snark_scalar_field);
+            vk_x = Pairing.addition(vk_x, Pairing.scalar_mul(vk.query[i + 1], input[i]));
+        }
+        vk_x = Pairing.addition(vk_x, vk.query[0]);
+        /**
+         * e(A*G^{alpha}, B*H^{beta}) = e(G^{alpha}, H^{beta}) * e(G^{psi}, H^{gamma})
+         *                              * e(C, H)
+         * where psi = \sum_{i=0}^l input_i pvk.query[i]
+         */
+        if (!Pairing.pairingProd4(vk.g_alpha, vk.h_beta, vk_x, vk.h_gamma, proof.c, vk.h, Pairing.negate(Pairing.addition(proof.a, vk.g_alpha)), Pairing.addition(proof.b, vk.h_beta))) return 1;
+        /**
+         * e(A, H^{gamma}) = e(G^{gamma}, B)
+         */
+        if (!Pairing.pairingProd2(proof.a, vk.h_gamma, Pairing.negate(vk.g_gamma), proof.b)) return 2;
+        return 0;
+    }
+    function verifyTx(
+            Proof memory proof,
+            uint[<%vk_input_length%>] memory input
+        ) public view returns (bool r) {
+        uint[] memory inputValues = new uint[](input.length);
+        for(uint i = 0; i < input.length; i++){
+            inputValues[i] = input[i];
+        }
+        if (verify(inputValues, proof) == 0) {
+            return true;
+        } else {
+            return false;
+        }
+    }
+}
+"#;
+
+const CONTRACT_TEMPLATE: &str = r#"
+contract Verifier {
+    using Pairing for *;
+    struct VerifyingKey {
+        Pairing.G2Point h;
+        Pairing.G1Point g_alpha;
+        Pairing.G2Point h_beta;
+        Pairing.G1Point g_gamma;
+        Pairing.G2Point h_gamma;
+        Pairing.G1Point[] query;
+    }
+    struct Proof {
+        Pairing.G1Point a;
+        Pairing.G2Point b;
+        Pairing.G1Point c;
+    }
+    function verifyingKey() pure internal returns (VerifyingKey memory vk) {
+        vk.h = Pairing.G2Point(<%vk_h%>);
+        vk.g_alpha = Pairing.G1Point(<%vk_g_alpha%>);
+        vk.h_beta = Pairing.G2Point(<%vk_h_beta%>);
+        vk.g_gamma = Pairing.G1Point(<%vk_g_gamma%>);
+        vk.h_gamma = Pairing.G2Point(<%vk_h_gamma%>);
+        vk.query = new Pairing.G1Point[](<%vk_query_length%>);
+        <%vk_query_pts%>
+    }
+    function verify(uint[] memory input, Proof memory proof) internal view returns (uint) {
+        uint256 snark_scalar_field = 21888242871839275222246405745257275088548364400416034343698204186575808495617;
+        VerifyingKey memory vk = verifyingKey();
+        require(input.length + 1 == vk.query.length);
+        // Compute the linear combination vk_x
+        Pairing.G1Point memory vk_x = Pairing.G1Point(0, 0);
+        for (uint i = 0; i < input.length; i++) {
+            require(input[i] < snark_scalar_field);
+            vk_x = Pairing.addition(vk_x, Pairing.scalar_mul(vk.query[i + 1], input[i]));
+        }
+        vk_x = Pairing.addition(vk_x, vk.query[0]);
+        /**
+         * e(A*G^{alpha}, B*H^{beta}) = e(G^{alpha}, H^{beta}) * e(G^{psi}, H^{gamma})
+         *                              * e(C, H)
+         * where psi = \sum_{i=0}^l input_i pvk.query[i]
+         */
+        if (!Pairing.pairingProd4(vk.g_alpha, vk.h_beta, vk_x, vk.h_gamma, proof.c, vk.h, Pairing.negate(Pairing.addition(proof.a, vk.g_alpha)), Pairing.addition(proof.b, vk.h_beta))) return 1;
+        /**
+         * e(A, H^{gamma}) = e(G^{gamma}, b)
+         */
+        if (!Pairing.pairingProd2(proof.a, vk.h_gamma, Pairing.negate(vk.g_gamma), proof.b)) return 2;
+        return 0;
+    }
+    function verifyTx(
+            uint[2] memory a,
+            uint[2][2] memory b,
+            uint[2] memory c,
+            uint[<%vk_input_length%>] memory input
+        ) public view returns (bool r) {
+        Proof memory proof;
+        proof.a = Pairing.G1Point(a[0], a[1]);
+        proof.b = Pairing.G2Point([b[0][0], b[0][1]], [b[1][0], b[1][1]]);
+        proof.c = Pairing.G1Point(c[0], c[1]);
+        uint[] memory inputValues = new uint[](input.length);
+        for(uint i = 0; i < input.length; i++){
+            inputValues[i] = input[i];
+        }
+        if (verify(inputValues, proof) == 0) {
+            return true;
+        } else {
+            return false;
+        }
+    }
+}
+"#;
+
+#[cfg(test)]
+mod tests {
+    use crate::flat_absy::FlatVariable;
+    use crate::ir::{Function, Interpreter, LinComb, Prog, QuadComb, Statement};
+    use std::collections::BTreeMap;
+
+    use super::*;
+    use zokrates_field::Bn128Field;
+
+    /// x * x + 1 = y, with x private and y public
+    fn program() -> Prog<Bn128Field> {
+        Prog {
+            main: Function {
+                id: String::from("main"),
+                arguments: vec![FlatVariable::new(0)],
+                returns: vec![FlatVariable::public(0)],
+                statements: vec![
+                    Statement::Constraint(
+                        QuadComb::from_linear_combinations(
+                            FlatVariable::new(0).into(),
+                            FlatVariable::new(0).into(),
+                        ),
+                        FlatVariable::new(1).into(),
+                    ),
+                    Statement::Constraint(
+                        (LinComb::from(FlatVariable::new(1)) + LinComb::one()).into(),
+                        FlatVariable::public(0).into(),
+                    ),
+                ],
+            },
+            private: vec![true],
+            origins: BTreeMap::new(),
+            metadata: BTreeMap::new(),
+        }
+    }
+
+    #[test]
+    fn verify() {
+        let program = program();
+        let keypair = GM17::setup(program.clone(), Randomness::System).unwrap();
+
+        let interpreter = Interpreter::default();
+
+        let witness = interpreter
+            .execute(&program, &vec![Bn128Field::from(42)])
+            .unwrap();
+
+        let proof = GM17::generate_proof(program, witness, keypair.pk, Randomness::System).unwrap();
+        assert_eq!(
+            proof.inputs,
+            vec![parse_fr::<Bn128Field>(
+                &Bn128Field::from(1765).into_bellman()
+            )]
+        );
+
+        let valid = Proof::new(proof.proof.clone(), proof.inputs, proof.raw.clone());
+        let ans = <GM17 as ProofSystem<Bn128Field>>::verify(keypair.vk.clone(), valid);
+        assert!(ans);
+
+        let inputs = vec![parse_fr::<Bn128Field>(
+            &Bn128Field::from(1766).into_bellman(),
+        )];
+        let invalid = Proof::new(proof.proof, inputs, proof.raw);
+        let ans = <GM17 as ProofSystem<Bn128Field>>::verify(keypair.vk, invalid);
+        assert!(!ans);
+    }
+
+    #[test]
+    fn unsatisfied_witness() {
+        let program = program();
+        let keypair = GM17::setup(program.clone(), Randomness::System).unwrap();
+
+        let mut witness = Interpreter::default()
+            .execute(&program, &vec![Bn128Field::from(42)])
+            .unwrap();
+        witness
+            .0
+            .insert(FlatVariable::public(0), Bn128Field::from(1766));
+
+        assert_eq!(
+            GM17::generate_proof(program, witness, keypair.pk, Randomness::System).err(),
+            Some(String::from(
+                "The witness does not satisfy the constraints of the program"
+            ))
+        );
+    }
+
+    #[test]
+    fn truncated_proving_key() {
+        let program = program();
+        let mut keypair = GM17::setup(program.clone(), Randomness::System).unwrap();
+        keypair.pk.truncate(keypair.pk.len() / 2);
+
+        let witness = Interpreter::default()
+            .execute(&program, &vec![Bn128Field::from(42)])
+            .unwrap();
+
+        assert!(GM17::generate_proof(program, witness, keypair.pk, Randomness::System).is_err());
+    }
+}
@@ -0,0 +1,126 @@
+//! The GM17 prover, which needs a single random scalar `r` to make its proofs zero-knowledge
+
+use bellman::pairing::ff::{Field, PrimeField};
+use bellman::pairing::{CurveAffine, CurveProjective, Engine};
+use proof_system::bellman::gm17::sap::Sap;
+use proof_system::bellman::group::{multiexp, read_point, read_points, write_points};
+use proof_system::bellman::rand::{Rand, Rng};
+use std::io::{self, Read, Write};
+
+pub struct ProvingKey<E: Engine> {
+    pub num_inputs: usize,
+    /// `gamma * A_i(x)` in G1 and G2 for all variables
+    pub a_g1: Vec<E::G1Affine>,
+    pub a_g2: Vec<E::G2Affine>,
+    /// `gamma * (alpha + beta) * A_i(x) + gamma^2 * C_i(x)` for the private variables
+    pub c_1: Vec<E::G1Affine>,
+    /// `2 * gamma^2 * t(x) * A_i(x)` for all variables
+    pub c_2: Vec<E::G1Affine>,
+    /// `gamma^2 * t(x) * x^i` for the coefficients of `h`
+    pub h: Vec<E::G1Affine>,
+    /// `gamma * t(x)` in G1 and G2
+    pub gamma_t_g1: E::G1Affine,
+    pub gamma_t_g2: E::G2Affine,
+    /// `(alpha + beta) * gamma * t(x)`
+    pub alpha_beta_gamma_t: E::G1Affine,
+    /// `gamma^2 * t(x)^2`
+    pub gamma_2_t_2: E::G1Affine,
+}
+
+pub struct Proof<E: Engine> {
+    pub a: E::G1Affine,
+    pub b: E::G2Affine,
+    pub c: E::G1Affine,
+}
+
+impl<E: Engine> Proof<E> {
+    pub fn write<W: Write>(&self, mut w: W) -> io::Result<()> {
+        w.write_all(self.a.into_compressed().as_ref())?;
+        w.write_all(self.b.into_compressed().as_ref())?;
+        w.write_all(self.c.into_compressed().as_ref())
+    }
+}
+
+/// A proof that `values`, the values of all the variables of the SAP, satisfy it
+pub fn prove<E: Engine, R: Rng>(
+    sap: &Sap<E>,
+    pk: &ProvingKey<E>,
+    values: &[E::Fr],
+    rng: &mut R,
+) -> Result<Proof<E>, String> {
+    if pk.num_inputs != sap.num_inputs
+        || pk.a_g1.len() != sap.num_variables
+        || pk.h.len() != sap.domain.size - 1
+    {
+        return Err(String::from("The proving key does not match the program"));
+    }
+
+    let h = sap.quotient(values)?;
+    let r = E::Fr::rand(rng);
+
+    let repr = |scalars: &[E::Fr]| scalars.iter().map(|s| s.into_repr()).collect::<Vec<_>>();
+
+    // A = gamma * (r * t(x) + sum(a_i * A_i(x))), and B the same in G2
+    let values_repr = repr(values);
+    let mut a = multiexp(&pk.a_g1, &values_repr);
+    a.add_assign(&pk.gamma_t_g1.mul(r.into_repr()));
+    let mut b = multiexp(&pk.a_g2, &values_repr);
+    b.add_assign(&pk.gamma_t_g2.mul(r.into_repr()));
+
+    // in the exponent, C = A^2 + (alpha + beta) * A - gamma * psi, where psi only depends on the public variables
+    let r_values: Vec<_> = values
+        .iter()
+        .map(|v| {
+            let mut v = *v;
+            v.mul_assign(&r);
+            v
+        })
+        .collect();
+    let mut r_2 = r;
+    r_2.square();
+
+    let mut c = multiexp(&pk.c_1, &values_repr[pk.num_inputs..]);
+    c.add_assign(&multiexp(&pk.c_2, &repr(&r_values)));
+    c.add_assign(&multiexp(&pk.h, &repr(&h)));
+    c.add_assign(&pk.alpha_beta_gamma_t.mul(r.into_repr()));
+    c.add_assign(&pk.gamma_2_t_2.mul(r_2.into_repr()));
+
+    Ok(Proof {
+        a: a.into_affine(),
+        b: b.into_affine(),
+        c: c.into_affine(),
+    })
+}
+
+impl<E: Engine> ProvingKey<E> {
+    pub fn write<W: Write>(&self, mut w: W) -> io::Result<()> {
+        w.write_all(&(self.num_inputs as u32).to_be_bytes())?;
+        write_points(&mut w, &self.a_g1)?;
+        write_points(&mut w, &self.a_g2)?;
+        write_points(&mut w, &self.c_1)?;
+        write_points(&mut w, &self.c_2)?;
+        write_points(&mut w, &self.h)?;
+        w.write_all(self.gamma_t_g1.into_uncompressed().as_ref())?;
+        w.write_all(self.gamma_t_g2.into_uncompressed().as_ref())?;
+        w.write_all(self.alpha_beta_gamma_t.into_uncompressed().as_ref())?;
+        w.write_all(self.gamma_2_t_2.into_uncompressed().as_ref())
+    }
+
+    pub fn read<R: Read>(mut r: R) -> io::Result<Self> {
+        let mut num_inputs = [0; 4];
+        r.read_exact(&mut num_inputs)?;
+
+        Ok(ProvingKey {
+            num_inputs: u32::from_be_bytes(num_inputs) as usize,
+            a_g1: read_points(&mut r)?,
+            a_g2: read_points(&mut r)?,
+            c_1: read_points(&mut r)?,
+            c_2: read_points(&mut r)?,
+            h: read_points(&mut r)?,
+            gamma_t_g1: read_point(&mut r)?,
+            gamma_t_g2: read_point(&mut r)?,
+            alpha_beta_gamma_t: read_point(&mut r)?,
+            gamma_2_t_2: read_point(&mut r)?,
+        })
+    }
+}
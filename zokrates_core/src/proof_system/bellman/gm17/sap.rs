@@ -0,0 +1,393 @@
+//! The square arithmetic program (SAP) of a rank-1 constraint system, which GM17 proves: a witness is valid if and
+//! only if `(sum(x_i * A_i(X)))^2 = sum(x_i * C_i(X)) + h(X) * (X^n - 1)` for some polynomial `h`
+
+use bellman::pairing::ff::{Field, PrimeField, ScalarEngine};
+use bellman::pairing::Engine;
+use bellman::{ConstraintSystem, Index, LinearCombination, SynthesisError, Variable};
+use proof_system::bellman::domain::{scalar, Domain};
+
+/// A linear combination of the variables of a constraint system
+type Combination<E> = Vec<(Index, <E as ScalarEngine>::Fr)>;
+
+/// Sparse values indexed by row or by variable
+type Sparse<E> = Vec<(usize, <E as ScalarEngine>::Fr)>;
+
+/// A constraint system which records the constraints of a circuit, and the values of its variables when they are known
+pub struct Assembly<E: Engine> {
+    with_values: bool,
+    /// The values of the public variables, starting with `~one`
+    pub inputs: Vec<E::Fr>,
+    pub aux: Vec<E::Fr>,
+    pub num_inputs: usize,
+    pub num_aux: usize,
+    constraints: Vec<[Combination<E>; 3]>,
+}
+
+impl<E: Engine> Assembly<E> {
+    /// An empty constraint system, which computes the values of its variables if `with_values` is set
+    pub fn new(with_values: bool) -> Self {
+        Assembly {
+            with_values,
+            inputs: vec![E::Fr::one()],
+            aux: vec![],
+            num_inputs: 1,
+            num_aux: 0,
+            constraints: vec![],
+        }
+    }
+}
+
+impl<E: Engine> ConstraintSystem<E> for Assembly<E> {
+    type Root = Self;
+
+    fn alloc<F, A, AR>(&mut self, _: A, f: F) -> Result<Variable, SynthesisError>
+    where
+        F: FnOnce() -> Result<E::Fr, SynthesisError>,
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+    {
+        if self.with_values {
+            self.aux.push(f()?);
+        }
+        self.num_aux += 1;
+
+        Ok(Variable::new_unchecked(Index::Aux(self.num_aux - 1)))
+    }
+
+    fn alloc_input<F, A, AR>(&mut self, _: A, f: F) -> Result<Variable, SynthesisError>
+    where
+        F: FnOnce() -> Result<E::Fr, SynthesisError>,
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+    {
+        if self.with_values {
+            self.inputs.push(f()?);
+        }
+        self.num_inputs += 1;
+
+        Ok(Variable::new_unchecked(Index::Input(self.num_inputs - 1)))
+    }
+
+    fn enforce<A, AR, LA, LB, LC>(&mut self, _: A, a: LA, b: LB, c: LC)
+    where
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+        LA: FnOnce(LinearCombination<E>) -> LinearCombination<E>,
+        LB: FnOnce(LinearCombination<E>) -> LinearCombination<E>,
+        LC: FnOnce(LinearCombination<E>) -> LinearCombination<E>,
+    {
+        let combination = |l: LinearCombination<E>| {
+            l.as_ref()
+                .iter()
+                .map(|(v, k)| (v.get_unchecked(), *k))
+                .collect()
+        };
+
+        self.constraints.push([
+            combination(a(LinearCombination::zero())),
+            combination(b(LinearCombination::zero())),
+            combination(c(LinearCombination::zero())),
+        ]);
+    }
+
+    fn push_namespace<NR, N>(&mut self, _: N)
+    where
+        NR: Into<String>,
+        N: FnOnce() -> NR,
+    {
+    }
+
+    fn pop_namespace(&mut self) {}
+
+    fn get_root(&mut self) -> &mut Self::Root {
+        self
+    }
+}
+
+/// The SAP of a constraint system, following the reduction of libsnark. Each rank-1 constraint `a * b = c` becomes the
+/// two rows `(a + b)^2 = 4c + y` and `(a - b)^2 = y`, where `y` is a new variable. Each public variable `x` gets the
+/// two rows `(x + 1)^2 = 4x + y'` and `(x - 1)^2 = y'`, so that it is bound even if no constraint uses it.
+///
+/// The variables are the public variables, starting with `~one`, then the private variables of the constraint
+/// system, then the new variables of the constraints, then the new variables of the public variables.
+pub struct Sap<E: Engine> {
+    pub domain: Domain<E>,
+    pub num_inputs: usize,
+    pub num_variables: usize,
+    /// The non-zero evaluations over the domain of each `A_i`, as `(row, value)`
+    pub a: Vec<Sparse<E>>,
+    /// The non-zero evaluations over the domain of each `C_i`, as `(row, value)`
+    pub c: Vec<Sparse<E>>,
+    /// The constraints of the constraint system over the variables of the SAP, to compute the new variables
+    constraints: Vec<[Sparse<E>; 3]>,
+}
+
+impl<E: Engine> Sap<E> {
+    pub fn new(assembly: &Assembly<E>) -> Result<Self, String> {
+        let num_constraints = assembly.constraints.len();
+        let num_inputs = assembly.num_inputs;
+        let constraint_offset = num_inputs + assembly.num_aux;
+        let input_offset = constraint_offset + num_constraints;
+        let num_variables = input_offset + num_inputs;
+
+        let domain = Domain::new(2 * (num_constraints + num_inputs))?;
+
+        let index = |i: &Index| match i {
+            Index::Input(i) => *i,
+            Index::Aux(i) => num_inputs + i,
+        };
+        let constraints: Vec<[Sparse<E>; 3]> = assembly
+            .constraints
+            .iter()
+            .map(|lcs| {
+                let combination = |j: usize| lcs[j].iter().map(|(v, k)| (index(v), *k)).collect();
+                [combination(0), combination(1), combination(2)]
+            })
+            .collect();
+
+        let mut a = vec![vec![]; num_variables];
+        let mut c = vec![vec![]; num_variables];
+
+        let one = E::Fr::one();
+        let four = scalar::<E>(4);
+        let negate = |k: &E::Fr| {
+            let mut k = *k;
+            k.negate();
+            k
+        };
+        let times_four = |k: &E::Fr| {
+            let mut k = *k;
+            k.mul_assign(&four);
+            k
+        };
+
+        for (i, [l, r, o]) in constraints.iter().enumerate() {
+            for (v, k) in l {
+                a[*v].push((2 * i, *k));
+                a[*v].push((2 * i + 1, *k));
+            }
+            for (v, k) in r {
+                a[*v].push((2 * i, *k));
+                a[*v].push((2 * i + 1, negate(k)));
+            }
+            for (v, k) in o {
+                c[*v].push((2 * i, times_four(k)));
+            }
+            c[constraint_offset + i].push((2 * i, one));
+            c[constraint_offset + i].push((2 * i + 1, one));
+        }
+
+        for i in 0..num_inputs {
+            let row = 2 * (num_constraints + i);
+
+            a[i].push((row, one));
+            a[0].push((row, one));
+            c[i].push((row, four));
+            c[input_offset + i].push((row, one));
+
+            a[i].push((row + 1, one));
+            a[0].push((row + 1, negate(&one)));
+            c[input_offset + i].push((row + 1, one));
+        }
+
+        Ok(Sap {
+            domain,
+            num_inputs,
+            num_variables,
+            a,
+            c,
+            constraints,
+        })
+    }
+
+    /// The values of all the variables of the SAP, given the values of the variables of the constraint system
+    pub fn assign(&self, assembly: &Assembly<E>) -> Vec<E::Fr> {
+        let mut values: Vec<E::Fr> = assembly
+            .inputs
+            .iter()
+            .chain(assembly.aux.iter())
+            .cloned()
+            .collect();
+
+        let evaluate = |values: &[E::Fr], l: &[(usize, E::Fr)]| {
+            l.iter().fold(E::Fr::zero(), |mut acc, (v, k)| {
+                let mut term = values[*v];
+                term.mul_assign(k);
+                acc.add_assign(&term);
+                acc
+            })
+        };
+
+        // (a - b)^2 for each constraint a * b = c
+        let extra: Vec<_> = self
+            .constraints
+            .iter()
+            .map(|[l, r, _]| {
+                let mut d = evaluate(&values, l);
+                d.sub_assign(&evaluate(&values, r));
+                d.square();
+                d
+            })
+            .collect();
+        values.extend(extra);
+
+        // (x - 1)^2 for each public variable x
+        let extra: Vec<_> = values[..self.num_inputs]
+            .iter()
+            .map(|x| {
+                let mut d = *x;
+                d.sub_assign(&E::Fr::one());
+                d.square();
+                d
+            })
+            .collect();
+        values.extend(extra);
+
+        values
+    }
+
+    /// The evaluations at `x` of all the polynomials `A_i` and `C_i`
+    pub fn evaluate(&self, x: &E::Fr) -> (Vec<E::Fr>, Vec<E::Fr>) {
+        let lagrange = self.domain.lagrange(self.domain.size, x);
+
+        let evaluate = |columns: &[Sparse<E>]| {
+            columns
+                .iter()
+                .map(|column| {
+                    column.iter().fold(E::Fr::zero(), |mut acc, (row, k)| {
+                        let mut term = lagrange[*row];
+                        term.mul_assign(k);
+                        acc.add_assign(&term);
+                        acc
+                    })
+                })
+                .collect()
+        };
+
+        (evaluate(&self.a), evaluate(&self.c))
+    }
+
+    /// The coefficients of `h(X) = ((sum(x_i * A_i(X)))^2 - sum(x_i * C_i(X))) / (X^n - 1)`, of degree `n - 2`
+    pub fn quotient(&self, values: &[E::Fr]) -> Result<Vec<E::Fr>, String> {
+        let n = self.domain.size;
+
+        let rows = |columns: &[Sparse<E>]| {
+            let mut rows = vec![E::Fr::zero(); n];
+            for (column, value) in columns.iter().zip(values) {
+                for (row, k) in column {
+                    let mut term = *value;
+                    term.mul_assign(k);
+                    rows[*row].add_assign(&term);
+                }
+            }
+            rows
+        };
+
+        let a = rows(&self.a);
+        let c = rows(&self.c);
+
+        let satisfied = a.iter().zip(&c).all(|(a, c)| {
+            let mut a = *a;
+            a.square();
+            a == *c
+        });
+        if !satisfied {
+            return Err(String::from(
+                "The witness does not satisfy the constraints of the program",
+            ));
+        }
+
+        // h has a degree lower than n, so it is determined by its evaluations over a coset of the domain, on which
+        // X^n - 1 is a constant
+        let mut a = self.domain.coset_fft(&self.domain.ifft(&a));
+        let c = self.domain.coset_fft(&self.domain.ifft(&c));
+
+        let vanishing = self
+            .domain
+            .vanishing(&E::Fr::multiplicative_generator())
+            .inverse()
+            .unwrap();
+
+        for (a, c) in a.iter_mut().zip(c) {
+            a.square();
+            a.sub_assign(&c);
+            a.mul_assign(&vanishing);
+        }
+
+        let mut h = self.domain.icoset_fft(&a);
+        h.truncate(n - 1);
+
+        Ok(h)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bellman::pairing::bn256::{Bn256, Fr};
+    use proof_system::bellman::domain::evaluate;
+
+    /// x_1 * x_1 = x_0, with x_0 public, and (x_1 + 2) * 3 = x_2
+    fn assembly(x: u64) -> Assembly<Bn256> {
+        let mut cs = Assembly::<Bn256>::new(true);
+        let y = cs
+            .alloc_input(|| "y", || Ok(scalar::<Bn256>(x * x)))
+            .unwrap();
+        let x_1 = cs.alloc(|| "x", || Ok(scalar::<Bn256>(x))).unwrap();
+        let x_2 = cs.alloc(|| "z", || Ok(scalar::<Bn256>(3 * x + 6))).unwrap();
+        cs.enforce(|| "square", |lc| lc + x_1, |lc| lc + x_1, |lc| lc + y);
+        cs.enforce(
+            || "linear",
+            |lc| lc + x_1 + (scalar::<Bn256>(2), Assembly::<Bn256>::one()),
+            |lc| lc + (scalar::<Bn256>(3), Assembly::<Bn256>::one()),
+            |lc| lc + x_2,
+        );
+        cs
+    }
+
+    #[test]
+    fn reduction() {
+        let assembly = assembly(5);
+        let sap = Sap::new(&assembly).unwrap();
+
+        // two public variables and two private ones, and one new variable for each of them and each constraint
+        assert_eq!(sap.num_inputs, 2);
+        assert_eq!(sap.num_variables, 8);
+        assert_eq!(sap.domain.size, 8);
+
+        let values = sap.assign(&assembly);
+        let h = sap.quotient(&values).unwrap();
+        assert_eq!(h.len(), 7);
+
+        // the SAP holds at a random point
+        let x = scalar::<Bn256>(42);
+        let (a, c) = sap.evaluate(&x);
+        let combination = |polynomials: &[Fr]| {
+            polynomials
+                .iter()
+                .zip(&values)
+                .fold(Fr::zero(), |mut acc, (p, v)| {
+                    let mut term = *p;
+                    term.mul_assign(v);
+                    acc.add_assign(&term);
+                    acc
+                })
+        };
+        let mut left = combination(&a);
+        left.square();
+        let mut right = evaluate::<Bn256>(&h, &x);
+        right.mul_assign(&sap.domain.vanishing(&x));
+        right.add_assign(&combination(&c));
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn unsatisfied() {
+        let mut assembly = assembly(5);
+        assembly.aux[1].add_assign(&Fr::one());
+        let sap = Sap::new(&assembly).unwrap();
+
+        let values = sap.assign(&assembly);
+        assert!(sap.quotient(&values).is_err());
+    }
+}
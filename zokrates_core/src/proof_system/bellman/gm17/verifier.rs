@@ -0,0 +1,114 @@
+//! The GM17 verifier, which checks a proof with two pairing equations
+
+use bellman::pairing::ff::{Field, PrimeField};
+use bellman::pairing::{CurveAffine, CurveProjective, Engine};
+use proof_system::bellman::gm17::prover::Proof;
+use proof_system::bellman::group::multiexp;
+
+pub struct VerifyingKey<E: Engine> {
+    /// The generator of G2
+    pub h: E::G2Affine,
+    pub g_alpha: E::G1Affine,
+    pub h_beta: E::G2Affine,
+    pub g_gamma: E::G1Affine,
+    pub h_gamma: E::G2Affine,
+    /// `(alpha + beta) * A_i(x) + gamma * C_i(x)` for the public variables, starting with `~one`
+    pub query: Vec<E::G1Affine>,
+}
+
+/// Checks that `e(A + g_alpha, B + h_beta) = e(g_alpha, h_beta) * e(psi, h_gamma) * e(C, h)`, where `psi` is the
+/// combination of the query of the verifying key by the public inputs, and that `e(A, h_gamma) = e(g_gamma, B)`
+pub fn verify<E: Engine>(vk: &VerifyingKey<E>, proof: &Proof<E>, public_inputs: &[E::Fr]) -> bool {
+    if public_inputs.len() + 1 != vk.query.len() {
+        return false;
+    }
+
+    let scalars: Vec<_> = public_inputs.iter().map(|x| x.into_repr()).collect();
+    let mut psi = multiexp(&vk.query[1..], &scalars);
+    psi.add_assign_mixed(&vk.query[0]);
+
+    let mut a = proof.a.into_projective();
+    a.add_assign_mixed(&vk.g_alpha);
+    let mut b = proof.b.into_projective();
+    b.add_assign_mixed(&vk.h_beta);
+
+    let negate = |p: E::G1Affine| {
+        let mut p = p;
+        p.negate();
+        p
+    };
+
+    let first = E::miller_loop(&[
+        (&a.into_affine().prepare(), &b.into_affine().prepare()),
+        (&negate(vk.g_alpha).prepare(), &vk.h_beta.prepare()),
+        (&negate(psi.into_affine()).prepare(), &vk.h_gamma.prepare()),
+        (&negate(proof.c).prepare(), &vk.h.prepare()),
+    ]);
+
+    let second = E::miller_loop(&[
+        (&proof.a.prepare(), &vk.h_gamma.prepare()),
+        (&negate(vk.g_gamma).prepare(), &proof.b.prepare()),
+    ]);
+
+    [first, second].iter().all(|r| {
+        E::final_exponentiation(r)
+            .map(|r| r == E::Fqk::one())
+            .unwrap_or(false)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bellman::pairing::bn256::{Bn256, Fr};
+    use bellman::ConstraintSystem;
+    use proof_system::bellman::domain::scalar;
+    use proof_system::bellman::gm17::generator::generate;
+    use proof_system::bellman::gm17::prover::{prove, ProvingKey};
+    use proof_system::bellman::gm17::sap::{Assembly, Sap};
+    use proof_system::bellman::rand::{SeedableRng, XorShiftRng};
+
+    /// x * x = y and y * 2 = z, with y and z public
+    fn assembly(x: u64, y: u64, z: u64) -> Assembly<Bn256> {
+        let mut cs = Assembly::<Bn256>::new(true);
+        let y = cs.alloc_input(|| "y", || Ok(scalar::<Bn256>(y))).unwrap();
+        let x = cs.alloc(|| "x", || Ok(scalar::<Bn256>(x))).unwrap();
+        let z = cs.alloc_input(|| "z", || Ok(scalar::<Bn256>(z))).unwrap();
+        cs.enforce(|| "square", |lc| lc + x, |lc| lc + x, |lc| lc + y);
+        cs.enforce(
+            || "double",
+            |lc| lc + y,
+            |lc| lc + (scalar::<Bn256>(2), Assembly::<Bn256>::one()),
+            |lc| lc + z,
+        );
+        cs
+    }
+
+    #[test]
+    fn prove_and_verify() {
+        let rng = &mut XorShiftRng::from_seed([0x5dbe6259, 0x8d313d76, 0x3237db17, 0xe5bc0654]);
+
+        let assembly = assembly(3, 9, 18);
+        let sap = Sap::new(&assembly).unwrap();
+        let (pk, vk) = generate(&sap, rng);
+
+        let mut bytes = vec![];
+        pk.write(&mut bytes).unwrap();
+        let pk = ProvingKey::<Bn256>::read(&bytes[..]).unwrap();
+
+        let proof = prove(&sap, &pk, &sap.assign(&assembly), rng).unwrap();
+
+        let inputs: Vec<Fr> = [9, 18].iter().map(|v| scalar::<Bn256>(*v)).collect();
+        assert!(verify(&vk, &proof, &inputs));
+        assert!(!verify(&vk, &proof, &[inputs[0], inputs[0]]));
+        assert!(!verify(&vk, &proof, &inputs[..1]));
+
+        let mut tampered = proof;
+        tampered.c = tampered.a;
+        assert!(!verify(&vk, &tampered, &inputs));
+
+        // a witness which does not satisfy the constraints cannot be proven
+        let assembly = self::assembly(3, 9, 19);
+        assert!(prove(&sap, &pk, &sap.assign(&assembly), rng).is_err());
+    }
+}
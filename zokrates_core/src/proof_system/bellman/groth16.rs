@@ -2,18 +2,20 @@ use bellman::groth16::{
     prepare_verifying_key, verify_proof, Parameters, PreparedVerifyingKey, Proof as BellmanProof,
     VerifyingKey,
 };
-use pairing::{CurveAffine, Engine};
+use pairing::ff::{Field as _, PrimeField, ScalarEngine};
+use pairing::{CurveAffine, CurveProjective, Engine};
 use regex::Regex;
 
 use zokrates_field::Field;
 
 use crate::ir;
-use crate::proof_system::bellman::Computation;
+use crate::proof_system::bellman::rand::{Rand, Rng};
 use crate::proof_system::bellman::{parse_fr, parse_g1, parse_g2};
+use crate::proof_system::bellman::{rng, serialization, Computation};
 use crate::proof_system::solidity::{
     SOLIDITY_G2_ADDITION_LIB, SOLIDITY_PAIRING_LIB, SOLIDITY_PAIRING_LIB_V2,
 };
-use proof_system::{G1Affine, G2Affine, Proof, ProofSystem, SetupKeypair, SolidityAbi};
+use proof_system::{G1Affine, G2Affine, Proof, ProofSystem, Randomness, SetupKeypair, SolidityAbi};
 
 const G16_WARNING: &str = "WARNING: You are using the G16 scheme which is subject to malleability. See zokrates.github.io/toolbox/proving_schemes.html#g16-malleability for implications.";
 
@@ -36,7 +38,7 @@ impl ProofPoints {
     }
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Clone)]
 pub struct VerificationKey {
     alpha: G1Affine,
     beta: G2Affine,
@@ -63,17 +65,11 @@ impl VerificationKey {
     }
 }
 
-impl<T: Field> ProofSystem<T> for G16 {
-    type VerificationKey = VerificationKey;
-    type ProofPoints = ProofPoints;
-
-    fn setup(program: ir::Prog<T>) -> SetupKeypair<VerificationKey> {
-        #[cfg(not(target_arch = "wasm32"))]
-        std::env::set_var("BELLMAN_VERBOSE", "0");
-        println!("{}", G16_WARNING);
-
-        let parameters = Computation::without_witness(program).setup();
-
+impl G16 {
+    /// The proving and verification keys of some Groth16 parameters
+    pub fn keypair<T: Field>(
+        parameters: &Parameters<T::BellmanEngine>,
+    ) -> SetupKeypair<VerificationKey> {
         let mut pk: Vec<u8> = Vec::new();
 
         parameters.write(&mut pk).unwrap();
@@ -93,12 +89,31 @@ impl<T: Field> ProofSystem<T> for G16 {
 
         SetupKeypair::new(vk, pk)
     }
+}
+
+impl<T: Field> ProofSystem<T> for G16 {
+    type VerificationKey = VerificationKey;
+    type ProofPoints = ProofPoints;
+
+    fn setup(
+        program: ir::Prog<T>,
+        randomness: Randomness,
+    ) -> Result<SetupKeypair<VerificationKey>, String> {
+        #[cfg(not(target_arch = "wasm32"))]
+        std::env::set_var("BELLMAN_VERBOSE", "0");
+        println!("{}", G16_WARNING);
+
+        let parameters = Computation::without_witness(program).setup(randomness);
+
+        Ok(G16::keypair::<T>(&parameters))
+    }
 
     fn generate_proof(
         program: ir::Prog<T>,
         witness: ir::Witness<T>,
         proving_key: Vec<u8>,
-    ) -> Proof<ProofPoints> {
+        randomness: Randomness,
+    ) -> Result<Proof<ProofPoints>, String> {
         #[cfg(not(target_arch = "wasm32"))]
         std::env::set_var("BELLMAN_VERBOSE", "0");
 
@@ -107,7 +122,7 @@ impl<T: Field> ProofSystem<T> for G16 {
         let computation = Computation::with_witness(program, witness);
         let params = Parameters::read(proving_key.as_slice(), true).unwrap();
 
-        let proof = computation.clone().prove(&params);
+        let proof = computation.clone().prove(&params, randomness);
 
         let proof_points = ProofPoints {
             a: parse_g1::<T>(&proof.a),
@@ -124,7 +139,11 @@ impl<T: Field> ProofSystem<T> for G16 {
         let mut raw: Vec<u8> = Vec::new();
         proof.write(&mut raw).unwrap();
 
-        Proof::<ProofPoints>::new(proof_points, inputs, hex::encode(&raw))
+        Ok(Proof::<ProofPoints>::new(
+            proof_points,
+            inputs,
+            hex::encode(&raw),
+        ))
     }
 
     fn export_solidity_verifier(vk: VerificationKey, abi: SolidityAbi) -> String {
@@ -208,40 +227,99 @@ impl<T: Field> ProofSystem<T> for G16 {
 
         let pvk: PreparedVerifyingKey<T::BellmanEngine> = prepare_verifying_key(&vk);
 
+        let public_inputs = public_inputs::<T>(&proof.inputs);
+
         let bellman_proof: BellmanProof<T::BellmanEngine> = proof.proof.into_bellman::<T>();
 
-        let public_inputs: Vec<_> = proof
-            .inputs
-            .iter()
-            .map(|s| {
-                T::try_from_str(s.trim_start_matches("0x"), 16)
-                    .expect(format!("Invalid {} value: {}", T::name(), s).as_str())
-                    .into_bellman()
+        verify_proof(&pvk, &bellman_proof, &public_inputs).unwrap()
+    }
+
+    fn verify_batch(vk: VerificationKey, proofs: Vec<Proof<ProofPoints>>) -> bool {
+        let vk: VerifyingKey<T::BellmanEngine> = vk.into_bellman::<T>();
+
+        let proofs: Vec<_> = proofs
+            .into_iter()
+            .map(|proof| {
+                let public_inputs = public_inputs::<T>(&proof.inputs);
+                (proof.proof.into_bellman::<T>(), public_inputs)
             })
-            .collect::<Vec<_>>();
+            .collect();
 
-        verify_proof(&pvk, &bellman_proof, &public_inputs).unwrap()
+        verify_batch(&vk, &proofs, &mut rng(Randomness::System))
     }
 }
 
-mod serialization {
-    use pairing::{from_hex, CurveAffine, Engine};
-    use proof_system::{G1Affine, G2Affine};
-    use zokrates_field::Field;
+fn public_inputs<T: Field>(inputs: &[String]) -> Vec<<T::BellmanEngine as ScalarEngine>::Fr> {
+    inputs
+        .iter()
+        .map(|s| serialization::to_fr::<T>(s))
+        .collect()
+}
 
-    pub fn to_g1<T: Field>(g1: G1Affine) -> <T::BellmanEngine as Engine>::G1Affine {
-        <T::BellmanEngine as Engine>::G1Affine::from_xy_checked(
-            from_hex(&g1.0).unwrap(),
-            from_hex(&g1.1).unwrap(),
-        )
-        .unwrap()
+/// Checks that all `proofs` are valid with a single multi-pairing.
+///
+/// The verification equation `e(A, B) = e(alpha, beta) * e(vk_x, gamma) * e(C, delta)` of each proof is raised to
+/// a random power `r` which the prover cannot predict, and the product of all equations is checked at once. If any
+/// proof is invalid, the product only holds with negligible probability.
+fn verify_batch<E: Engine, R: Rng>(
+    vk: &VerifyingKey<E>,
+    proofs: &[(BellmanProof<E>, Vec<E::Fr>)],
+    rng: &mut R,
+) -> bool {
+    // the coefficients of `alpha` and of each element of `gamma_abc` in the sum of `r * alpha` and `r * vk_x`
+    let mut alpha_coefficient = E::Fr::zero();
+    let mut gamma_abc_coefficients = vec![E::Fr::zero(); vk.ic.len()];
+    let mut c = E::G1::zero();
+    let mut pairs = Vec::with_capacity(proofs.len() + 3);
+
+    for (proof, inputs) in proofs {
+        if inputs.len() + 1 != vk.ic.len() {
+            return false;
+        }
+
+        let r = E::Fr::rand(rng);
+
+        alpha_coefficient.add_assign(&r);
+        gamma_abc_coefficients[0].add_assign(&r);
+        for (coefficient, input) in gamma_abc_coefficients.iter_mut().skip(1).zip(inputs) {
+            let mut term = *input;
+            term.mul_assign(&r);
+            coefficient.add_assign(&term);
+        }
+
+        c.add_assign(&proof.c.mul(r.into_repr()));
+        pairs.push((
+            proof.a.mul(r.into_repr()).into_affine().prepare(),
+            proof.b.prepare(),
+        ));
     }
-    pub fn to_g2<T: Field>(g2: G2Affine) -> <T::BellmanEngine as Engine>::G2Affine {
-        // apparently the order is reversed
-        let x = T::new_fq2(&(g2.0).1, &(g2.0).0);
-        let y = T::new_fq2(&(g2.1).1, &(g2.1).0);
-        <T::BellmanEngine as Engine>::G2Affine::from_xy_checked(x, y).unwrap()
+
+    let mut vk_x = E::G1::zero();
+    for (coefficient, g1) in gamma_abc_coefficients.into_iter().zip(vk.ic.iter()) {
+        vk_x.add_assign(&g1.mul(coefficient.into_repr()));
     }
+
+    let mut neg_beta = vk.beta_g2;
+    neg_beta.negate();
+    let mut neg_gamma = vk.gamma_g2;
+    neg_gamma.negate();
+    let mut neg_delta = vk.delta_g2;
+    neg_delta.negate();
+
+    // A * B + r * vk_x * (-gamma) + r * C * (-delta) + r * alpha * (-beta) = 0, summed over all proofs
+    pairs.push((
+        vk.alpha_g1
+            .mul(alpha_coefficient.into_repr())
+            .into_affine()
+            .prepare(),
+        neg_beta.prepare(),
+    ));
+    pairs.push((vk_x.into_affine().prepare(), neg_gamma.prepare()));
+    pairs.push((c.into_affine().prepare(), neg_delta.prepare()));
+
+    let pairs: Vec<_> = pairs.iter().map(|(g1, g2)| (g1, g2)).collect();
+
+    E::final_exponentiation(&E::miller_loop(pairs.iter())).unwrap() == E::Fqk::one()
 }
 
 const CONTRACT_TEMPLATE_V2: &str = r#"
@@ -370,6 +448,7 @@ contract Verifier {
 mod tests {
     use crate::flat_absy::FlatVariable;
     use crate::ir::{Function, Interpreter, Prog, Statement};
+    use std::collections::BTreeMap;
 
     use super::*;
     use zokrates_field::Bn128Field;
@@ -387,9 +466,11 @@ mod tests {
                 )],
             },
             private: vec![false],
+            origins: BTreeMap::new(),
+            metadata: BTreeMap::new(),
         };
 
-        let keypair = G16::setup(program.clone());
+        let keypair = G16::setup(program.clone(), Randomness::System).unwrap();
 
         let interpreter = Interpreter::default();
 
@@ -397,9 +478,116 @@ mod tests {
             .execute(&program, &vec![Bn128Field::from(42)])
             .unwrap();
 
-        let proof = G16::generate_proof(program, witness, keypair.pk);
+        let proof = G16::generate_proof(program, witness, keypair.pk, Randomness::System).unwrap();
         let ans = <G16 as ProofSystem<Bn128Field>>::verify(keypair.vk, proof);
 
         assert!(ans);
     }
+
+    #[test]
+    fn verify_batch() {
+        let program: Prog<Bn128Field> = Prog {
+            main: Function {
+                id: String::from("main"),
+                arguments: vec![FlatVariable::new(0)],
+                returns: vec![FlatVariable::public(0)],
+                statements: vec![Statement::Constraint(
+                    FlatVariable::new(0).into(),
+                    FlatVariable::public(0).into(),
+                )],
+            },
+            private: vec![false],
+            origins: BTreeMap::new(),
+            metadata: BTreeMap::new(),
+        };
+
+        let keypair = G16::setup(program.clone(), Randomness::System).unwrap();
+
+        let prove = |x: u32| {
+            let witness = Interpreter::default()
+                .execute(&program, &vec![Bn128Field::from(x)])
+                .unwrap();
+            G16::generate_proof(
+                program.clone(),
+                witness,
+                keypair.pk.clone(),
+                Randomness::System,
+            )
+            .unwrap()
+        };
+
+        let proofs: Vec<_> = (0..4).map(prove).collect();
+        assert!(<G16 as ProofSystem<Bn128Field>>::verify_batch(
+            keypair.vk.clone(),
+            proofs
+        ));
+
+        assert!(<G16 as ProofSystem<Bn128Field>>::verify_batch(
+            keypair.vk.clone(),
+            vec![]
+        ));
+
+        // a single proof with the wrong inputs invalidates the whole batch
+        let mut proofs: Vec<_> = (0..4).map(prove).collect();
+        proofs[2].inputs = proofs[3].inputs.clone();
+        assert!(!<G16 as ProofSystem<Bn128Field>>::verify_batch(
+            keypair.vk.clone(),
+            proofs
+        ));
+
+        let mut proofs: Vec<_> = (0..4).map(prove).collect();
+        let input = proofs[0].inputs[0].clone();
+        proofs[1].inputs.push(input);
+        assert!(!<G16 as ProofSystem<Bn128Field>>::verify_batch(
+            keypair.vk, proofs
+        ));
+    }
+
+    #[test]
+    fn seed() {
+        let program: Prog<Bn128Field> = Prog {
+            main: Function {
+                id: String::from("main"),
+                arguments: vec![FlatVariable::new(0)],
+                returns: vec![FlatVariable::public(0)],
+                statements: vec![Statement::Constraint(
+                    FlatVariable::new(0).into(),
+                    FlatVariable::public(0).into(),
+                )],
+            },
+            private: vec![false],
+            origins: BTreeMap::new(),
+            metadata: BTreeMap::new(),
+        };
+
+        let witness = Interpreter::default()
+            .execute(&program, &vec![Bn128Field::from(42)])
+            .unwrap();
+
+        let run = |randomness| {
+            let keypair = G16::setup(program.clone(), randomness).unwrap();
+            let proof = G16::generate_proof(
+                program.clone(),
+                witness.clone(),
+                keypair.pk.clone(),
+                randomness,
+            )
+            .unwrap();
+            (
+                serde_json::to_string(&keypair).unwrap(),
+                serde_json::to_string(&proof).unwrap(),
+            )
+        };
+
+        // the same seed gives the same keys and proof
+        assert_eq!(
+            run(Randomness::InsecureSeed(b"seed")),
+            run(Randomness::InsecureSeed(b"seed"))
+        );
+        assert_ne!(
+            run(Randomness::InsecureSeed(b"seed")),
+            run(Randomness::InsecureSeed(b"other seed"))
+        );
+        assert_ne!(run(Randomness::System), run(Randomness::System));
+    }
 }
@@ -0,0 +1,116 @@
+//! Points of the groups of a pairing-friendly curve: their serialization, and the multi-exponentiations which commit
+//! to many scalars at once
+
+use bellman::pairing::ff::{PrimeField, PrimeFieldRepr};
+use bellman::pairing::{CurveAffine, CurveProjective, EncodedPoint, GroupDecodingError};
+use std::error::Error;
+use std::io::{self, Read, Write};
+
+/// Read a point written in uncompressed form, checking that it is on the curve
+pub fn read_point<R: Read, G: CurveAffine>(r: &mut R) -> io::Result<G> {
+    let mut repr = G::Uncompressed::empty();
+    r.read_exact(repr.as_mut())?;
+    repr.into_affine().map_err(|e| decoding_error(&e))
+}
+
+/// The `Display` implementation of `GroupDecodingError` is recursive for most variants, so only keep its description
+#[allow(deprecated)]
+pub fn decoding_error(e: &GroupDecodingError) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, e.description())
+}
+
+/// Write points prefixed by their count
+pub fn write_points<W: Write, G: CurveAffine>(w: &mut W, points: &[G]) -> io::Result<()> {
+    w.write_all(&(points.len() as u32).to_be_bytes())?;
+    for p in points {
+        w.write_all(p.into_uncompressed().as_ref())?;
+    }
+    Ok(())
+}
+
+/// Read points written by `write_points`
+pub fn read_points<R: Read, G: CurveAffine>(r: &mut R) -> io::Result<Vec<G>> {
+    let mut len = [0; 4];
+    r.read_exact(&mut len)?;
+    (0..u32::from_be_bytes(len))
+        .map(|_| read_point(r))
+        .collect()
+}
+
+/// `sum(scalars[i] * bases[i])`, computed with Pippenger's bucket method
+pub fn multiexp<G: CurveAffine>(
+    bases: &[G],
+    scalars: &[<G::Scalar as PrimeField>::Repr],
+) -> G::Projective {
+    assert_eq!(bases.len(), scalars.len());
+
+    // the number of bits of the scalars which are handled at once
+    let window = match scalars.len() {
+        0..=31 => 3,
+        n => (n as f64).ln().ceil() as u32,
+    };
+    let windows = (G::Scalar::NUM_BITS + window - 1) / window;
+
+    let mut result = G::Projective::zero();
+
+    for w in (0..windows).rev() {
+        for _ in 0..window {
+            result.double();
+        }
+
+        // the i-th bucket holds the sum of the bases whose scalars have the value i + 1 in this window
+        let mut buckets = vec![G::Projective::zero(); (1 << window) - 1];
+        for (base, scalar) in bases.iter().zip(scalars) {
+            let index = bits(scalar, w * window, window);
+            if index != 0 {
+                buckets[index - 1].add_assign_mixed(base);
+            }
+        }
+
+        let mut running_sum = G::Projective::zero();
+        for bucket in buckets.into_iter().rev() {
+            running_sum.add_assign(&bucket);
+            result.add_assign(&running_sum);
+        }
+    }
+
+    result
+}
+
+/// The `count` bits of `repr` from the `start`-th least significant one
+fn bits<R: PrimeFieldRepr>(repr: &R, start: u32, count: u32) -> usize {
+    let limbs = repr.as_ref();
+    (0..count)
+        .filter(|i| {
+            let bit = start + i;
+            limbs
+                .get((bit / 64) as usize)
+                .map(|limb| (limb >> (bit % 64)) & 1 == 1)
+                .unwrap_or(false)
+        })
+        .fold(0, |acc, i| acc | (1 << i))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bellman::pairing::bn256::{Fr, G1Affine, G1};
+    use proof_system::bellman::rand::{Rand, SeedableRng, XorShiftRng};
+
+    #[test]
+    fn multiexp_matches_naive() {
+        let rng = &mut XorShiftRng::from_seed([0x5dbe6259, 0x8d313d76, 0x3237db17, 0xe5bc0654]);
+
+        for size in &[0, 1, 5, 100] {
+            let bases: Vec<G1Affine> = (0..*size).map(|_| G1::rand(rng).into_affine()).collect();
+            let scalars: Vec<_> = (0..*size).map(|_| Fr::rand(rng).into_repr()).collect();
+
+            let mut expected = G1::zero();
+            for (base, scalar) in bases.iter().zip(&scalars) {
+                expected.add_assign(&base.mul(*scalar));
+            }
+
+            assert_eq!(multiexp(&bases, &scalars), expected);
+        }
+    }
+}
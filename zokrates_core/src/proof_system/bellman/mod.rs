@@ -1,4 +1,9 @@
+pub mod domain;
+pub mod gm17;
 pub mod groth16;
+pub mod group;
+pub mod mpc;
+pub mod plonk;
 
 extern crate rand;
 
@@ -10,11 +15,13 @@ use bellman::groth16::{
 };
 use bellman::pairing::ff::ScalarEngine;
 use bellman::{Circuit, ConstraintSystem, LinearCombination, SynthesisError, Variable};
+use sha2::{Digest, Sha256};
 use std::collections::BTreeMap;
 use zokrates_field::Field;
 
-use self::rand::ChaChaRng;
+use self::rand::{ChaChaRng, OsRng, Rng, SeedableRng};
 use crate::flat_absy::FlatVariable;
+use crate::proof_system::Randomness;
 
 pub use self::parse::*;
 
@@ -161,8 +168,12 @@ impl<T: Field> Prog<T> {
 }
 
 impl<T: Field> Computation<T> {
-    pub fn prove(self, params: &Parameters<T::BellmanEngine>) -> Proof<T::BellmanEngine> {
-        let rng = &mut ChaChaRng::new_unseeded();
+    pub fn prove(
+        self,
+        params: &Parameters<T::BellmanEngine>,
+        randomness: Randomness,
+    ) -> Proof<T::BellmanEngine> {
+        let rng = &mut rng(randomness);
 
         let proof = create_random_proof(self.clone(), params, rng).unwrap();
 
@@ -191,13 +202,33 @@ impl<T: Field> Computation<T> {
             .collect()
     }
 
-    pub fn setup(self) -> Parameters<T::BellmanEngine> {
-        let rng = &mut ChaChaRng::new_unseeded();
+    pub fn setup(self, randomness: Randomness) -> Parameters<T::BellmanEngine> {
+        let rng = &mut rng(randomness);
         // run setup phase
         generate_random_parameters(self, rng).unwrap()
     }
 }
 
+fn rng(randomness: Randomness) -> ChaChaRng {
+    match randomness {
+        Randomness::System => OsRng::new()
+            .expect("Cannot get randomness from the system")
+            .gen(),
+        Randomness::InsecureSeed(seed) | Randomness::Entropy(seed) => {
+            seeded_rng(&Sha256::digest(seed))
+        }
+    }
+}
+
+/// A random number generator seeded with a 32-byte digest
+fn seeded_rng(digest: &[u8]) -> ChaChaRng {
+    let seed: Vec<u32> = digest
+        .chunks(4)
+        .map(|c| u32::from_be_bytes([c[0], c[1], c[2], c[3]]))
+        .collect();
+    ChaChaRng::from_seed(&seed)
+}
+
 impl<T: Field> Circuit<T::BellmanEngine> for Computation<T> {
     fn synthesize<CS: ConstraintSystem<T::BellmanEngine>>(
         self,
@@ -211,6 +242,8 @@ mod parse {
     use lazy_static::lazy_static;
 
     use super::*;
+    use bellman::pairing::ff::Field as _;
+    use bellman::pairing::{to_hex, CurveAffine, Engine};
     use proof_system::{G1Affine, G2Affine};
     use regex::Regex;
 
@@ -231,6 +264,12 @@ mod parse {
     pub fn parse_g1<T: Field>(
         e: &<T::BellmanEngine as bellman::pairing::Engine>::G1Affine,
     ) -> G1Affine {
+        // the point at infinity is encoded as (0, 0), like in the precompiled contracts of Ethereum
+        if e.is_zero() {
+            let zero = format!("0x{}", to_hex(&<T::BellmanEngine as Engine>::Fq::zero()));
+            return G1Affine(zero.clone(), zero);
+        }
+
         let raw_e = e.to_string();
         let captures = G1_REGEX.captures(&raw_e).unwrap();
         G1Affine(
@@ -263,6 +302,34 @@ mod parse {
     }
 }
 
+mod serialization {
+    use pairing::ff::{Field as _, ScalarEngine};
+    use pairing::{from_hex, CurveAffine, Engine};
+    use proof_system::{G1Affine, G2Affine};
+    use zokrates_field::Field;
+
+    pub fn to_g1<T: Field>(g1: G1Affine) -> <T::BellmanEngine as Engine>::G1Affine {
+        let x: <T::BellmanEngine as Engine>::Fq = from_hex(&g1.0).unwrap();
+        let y: <T::BellmanEngine as Engine>::Fq = from_hex(&g1.1).unwrap();
+        // (0, 0) is not on the curve and encodes the point at infinity
+        if x.is_zero() && y.is_zero() {
+            return <T::BellmanEngine as Engine>::G1Affine::zero();
+        }
+        <T::BellmanEngine as Engine>::G1Affine::from_xy_checked(x, y).unwrap()
+    }
+    pub fn to_g2<T: Field>(g2: G2Affine) -> <T::BellmanEngine as Engine>::G2Affine {
+        // apparently the order is reversed
+        let x = T::new_fq2(&(g2.0).1, &(g2.0).0);
+        let y = T::new_fq2(&(g2.1).1, &(g2.1).0);
+        <T::BellmanEngine as Engine>::G2Affine::from_xy_checked(x, y).unwrap()
+    }
+    pub fn to_fr<T: Field>(s: &str) -> <T::BellmanEngine as ScalarEngine>::Fr {
+        T::try_from_str(s.trim_start_matches("0x"), 16)
+            .expect(format!("Invalid {} value: {}", T::name(), s).as_str())
+            .into_bellman()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -283,6 +350,8 @@ mod tests {
                     statements: vec![],
                 },
                 private: vec![],
+                origins: BTreeMap::new(),
+                metadata: BTreeMap::new(),
             };
 
             let interpreter = Interpreter::default();
@@ -290,8 +359,8 @@ mod tests {
             let witness = interpreter.execute(&program, &vec![]).unwrap();
             let computation = Computation::with_witness(program, witness);
 
-            let params = computation.clone().setup();
-            let _proof = computation.prove(&params);
+            let params = computation.clone().setup(Randomness::System);
+            let _proof = computation.prove(&params, Randomness::System);
         }
 
         #[test]
@@ -307,6 +376,8 @@ mod tests {
                     )],
                 },
                 private: vec![true],
+                origins: BTreeMap::new(),
+                metadata: BTreeMap::new(),
             };
 
             let interpreter = Interpreter::default();
@@ -317,8 +388,8 @@ mod tests {
 
             let computation = Computation::with_witness(program, witness);
 
-            let params = computation.clone().setup();
-            let _proof = computation.prove(&params);
+            let params = computation.clone().setup(Randomness::System);
+            let _proof = computation.prove(&params, Randomness::System);
         }
 
         #[test]
@@ -334,6 +405,8 @@ mod tests {
                     )],
                 },
                 private: vec![false],
+                origins: BTreeMap::new(),
+                metadata: BTreeMap::new(),
             };
 
             let interpreter = Interpreter::default();
@@ -344,8 +417,8 @@ mod tests {
 
             let computation = Computation::with_witness(program, witness);
 
-            let params = computation.clone().setup();
-            let _proof = computation.prove(&params);
+            let params = computation.clone().setup(Randomness::System);
+            let _proof = computation.prove(&params, Randomness::System);
         }
 
         #[test]
@@ -361,6 +434,8 @@ mod tests {
                     )],
                 },
                 private: vec![],
+                origins: BTreeMap::new(),
+                metadata: BTreeMap::new(),
             };
 
             let interpreter = Interpreter::default();
@@ -368,8 +443,8 @@ mod tests {
             let witness = interpreter.execute(&program, &vec![]).unwrap();
             let computation = Computation::with_witness(program, witness);
 
-            let params = computation.clone().setup();
-            let _proof = computation.prove(&params);
+            let params = computation.clone().setup(Randomness::System);
+            let _proof = computation.prove(&params, Randomness::System);
         }
 
         #[test]
@@ -397,6 +472,8 @@ mod tests {
                     ],
                 },
                 private: vec![true, false],
+                origins: BTreeMap::new(),
+                metadata: BTreeMap::new(),
             };
 
             let interpreter = Interpreter::default();
@@ -406,8 +483,8 @@ mod tests {
                 .unwrap();
             let computation = Computation::with_witness(program, witness);
 
-            let params = computation.clone().setup();
-            let _proof = computation.prove(&params);
+            let params = computation.clone().setup(Randomness::System);
+            let _proof = computation.prove(&params, Randomness::System);
         }
 
         #[test]
@@ -423,6 +500,8 @@ mod tests {
                     )],
                 },
                 private: vec![false],
+                origins: BTreeMap::new(),
+                metadata: BTreeMap::new(),
             };
 
             let interpreter = Interpreter::default();
@@ -433,8 +512,8 @@ mod tests {
 
             let computation = Computation::with_witness(program, witness);
 
-            let params = computation.clone().setup();
-            let _proof = computation.prove(&params);
+            let params = computation.clone().setup(Randomness::System);
+            let _proof = computation.prove(&params, Randomness::System);
         }
 
         #[test]
@@ -452,6 +531,8 @@ mod tests {
                     )],
                 },
                 private: vec![true, false],
+                origins: BTreeMap::new(),
+                metadata: BTreeMap::new(),
             };
 
             let interpreter = Interpreter::default();
@@ -461,8 +542,8 @@ mod tests {
                 .unwrap();
             let computation = Computation::with_witness(program, witness);
 
-            let params = computation.clone().setup();
-            let _proof = computation.prove(&params);
+            let params = computation.clone().setup(Randomness::System);
+            let _proof = computation.prove(&params, Randomness::System);
         }
     }
 }
@@ -0,0 +1,792 @@
+//! Module containing a multi-party computation (MPC) which generates Groth16 parameters, so that nobody knows the
+//! toxic waste unless all participants collude
+//!
+//! This is the second phase of the ceremony described in [BGM17](https://eprint.iacr.org/2017/1050), which is
+//! specific to a circuit. It starts from the Lagrange form of the output of the first phase, a powers of tau ceremony
+//! which can be shared by all circuits. Each participant then multiplies `delta` by a random value and publishes a
+//! proof that they know it, so that anyone can check that the final parameters are the result of all contributions.
+
+use super::rand::{thread_rng, ChaChaRng, OsRng, Rand, Rng};
+use super::seeded_rng;
+use bellman::groth16::{Parameters, VerifyingKey};
+use bellman::pairing::ff::Field as _;
+use bellman::pairing::{CurveAffine, CurveProjective, EncodedPoint, Engine, GroupDecodingError};
+use bellman::{ConstraintSystem, Index, LinearCombination, SynthesisError, Variable};
+use ir;
+use proof_system::bellman::groth16::{VerificationKey, G16};
+use proof_system::bellman::group::{decoding_error, read_point};
+use proof_system::SetupKeypair;
+use sha2::{Digest, Sha256};
+use std::io::{self, Read, Write};
+use std::sync::Arc;
+use zokrates_field::Field;
+
+type Fr<T> = <<T as Field>::BellmanEngine as bellman::pairing::ff::ScalarEngine>::Fr;
+type G1Affine<T> = <<T as Field>::BellmanEngine as Engine>::G1Affine;
+type G2Affine<T> = <<T as Field>::BellmanEngine as Engine>::G2Affine;
+type G1<T> = <<T as Field>::BellmanEngine as Engine>::G1;
+type G2<T> = <<T as Field>::BellmanEngine as Engine>::G2;
+
+/// The parameters of a circuit along with the contributions made to them
+pub struct MPCParameters<T: Field> {
+    params: Parameters<T::BellmanEngine>,
+    /// The hash of the initial parameters, which identifies the circuit and the first phase
+    cs_hash: [u8; 32],
+    contributions: Vec<PublicKey<T>>,
+}
+
+/// The public key of a contribution, which proves that the contributor knows the value they multiplied `delta` by
+struct PublicKey<T: Field> {
+    /// `delta` in G1 after the contribution
+    delta_after: G1Affine<T>,
+    /// A random point `s` in G1, multiplied by the contribution
+    s: G1Affine<T>,
+    s_delta: G1Affine<T>,
+    /// A point `r` in G2 derived from the transcript, multiplied by the contribution
+    r_delta: G2Affine<T>,
+    /// The hash of the parameters, of the previous contributions and of `s` and `s_delta`
+    transcript: [u8; 32],
+}
+
+impl<T: Field> PublicKey<T> {
+    fn write<W: Write>(&self, mut w: W) -> io::Result<()> {
+        w.write_all(self.delta_after.into_uncompressed().as_ref())?;
+        w.write_all(self.s.into_uncompressed().as_ref())?;
+        w.write_all(self.s_delta.into_uncompressed().as_ref())?;
+        w.write_all(self.r_delta.into_uncompressed().as_ref())?;
+        w.write_all(&self.transcript)
+    }
+
+    fn read<R: Read>(mut r: R) -> io::Result<Self> {
+        let delta_after = read_point(&mut r)?;
+        let s = read_point(&mut r)?;
+        let s_delta = read_point(&mut r)?;
+        let r_delta = read_point(&mut r)?;
+        let mut transcript = [0; 32];
+        r.read_exact(&mut transcript)?;
+
+        Ok(PublicKey {
+            delta_after,
+            s,
+            s_delta,
+            r_delta,
+            transcript,
+        })
+    }
+
+    /// The hash which identifies the contribution
+    fn hash(&self) -> [u8; 32] {
+        let mut bytes = vec![];
+        self.write(&mut bytes).unwrap();
+        Sha256::digest(&bytes).into()
+    }
+}
+
+fn read_points<R: Read, G: CurveAffine>(r: &mut R, count: usize) -> io::Result<Vec<G>> {
+    (0..count).map(|_| read_point(r)).collect()
+}
+
+/// The output of the first phase in Lagrange form, for a domain of size `m`. This is the format of the `phase1radix2m*`
+/// files prepared from a powers of tau ceremony
+struct Phase1<T: Field> {
+    alpha_g1: G1Affine<T>,
+    beta_g1: G1Affine<T>,
+    beta_g2: G2Affine<T>,
+    /// `L_i(tau)` in G1 and G2, where `L_i` is the i-th Lagrange polynomial
+    coeffs_g1: Vec<G1Affine<T>>,
+    coeffs_g2: Vec<G2Affine<T>>,
+    /// `alpha * L_i(tau)` and `beta * L_i(tau)` in G1
+    alpha_coeffs_g1: Vec<G1Affine<T>>,
+    beta_coeffs_g1: Vec<G1Affine<T>>,
+    /// `tau^i * t(tau)` in G1 for i between 0 and m - 2
+    h: Vec<G1Affine<T>>,
+}
+
+impl<T: Field> Phase1<T> {
+    fn read<R: Read>(mut r: R, exp: u32) -> Result<Self, String> {
+        let m = 1 << exp;
+
+        // the layout is fixed, so a file for another size is detected before its points are misinterpreted
+        let g1 = <G1Affine<T> as CurveAffine>::Uncompressed::size();
+        let g2 = <G2Affine<T> as CurveAffine>::Uncompressed::size();
+        let size = 2 * g1 + g2 + m * (3 * g1 + g2) + (m - 1) * g1;
+
+        let mut bytes = vec![];
+        r.read_to_end(&mut bytes)
+            .map_err(|e| format!("Cannot read the phase-1 parameters: {}", e))?;
+
+        if bytes.len() != size {
+            return Err(format!(
+                "The phase-1 parameters are too {}, the program requires parameters for 2^{} constraints",
+                if bytes.len() < size { "small" } else { "large" },
+                exp
+            ));
+        }
+
+        let r = &mut &bytes[..];
+
+        let mut read = || -> io::Result<Self> {
+            Ok(Phase1 {
+                alpha_g1: read_point(r)?,
+                beta_g1: read_point(r)?,
+                beta_g2: read_point(r)?,
+                coeffs_g1: read_points(r, m)?,
+                coeffs_g2: read_points(r, m)?,
+                alpha_coeffs_g1: read_points(r, m)?,
+                beta_coeffs_g1: read_points(r, m)?,
+                h: read_points(r, m - 1)?,
+            })
+        };
+
+        read().map_err(|e| format!("Invalid phase-1 parameters: {}", e))
+    }
+}
+
+/// The QAP of a circuit, as the coefficients of each variable in each constraint. This mirrors the assembly of
+/// `bellman::groth16::generate_parameters`, so that the parameters are the ones the prover expects
+struct KeypairAssembly<T: Field> {
+    num_inputs: usize,
+    num_aux: usize,
+    num_constraints: usize,
+    at_inputs: Vec<Vec<(Fr<T>, usize)>>,
+    bt_inputs: Vec<Vec<(Fr<T>, usize)>>,
+    ct_inputs: Vec<Vec<(Fr<T>, usize)>>,
+    at_aux: Vec<Vec<(Fr<T>, usize)>>,
+    bt_aux: Vec<Vec<(Fr<T>, usize)>>,
+    ct_aux: Vec<Vec<(Fr<T>, usize)>>,
+}
+
+impl<T: Field> ConstraintSystem<T::BellmanEngine> for KeypairAssembly<T> {
+    type Root = Self;
+
+    fn alloc<F, A, AR>(&mut self, _: A, _: F) -> Result<Variable, SynthesisError>
+    where
+        F: FnOnce() -> Result<Fr<T>, SynthesisError>,
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+    {
+        let index = self.num_aux;
+        self.num_aux += 1;
+
+        self.at_aux.push(vec![]);
+        self.bt_aux.push(vec![]);
+        self.ct_aux.push(vec![]);
+
+        Ok(Variable::new_unchecked(Index::Aux(index)))
+    }
+
+    fn alloc_input<F, A, AR>(&mut self, _: A, _: F) -> Result<Variable, SynthesisError>
+    where
+        F: FnOnce() -> Result<Fr<T>, SynthesisError>,
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+    {
+        let index = self.num_inputs;
+        self.num_inputs += 1;
+
+        self.at_inputs.push(vec![]);
+        self.bt_inputs.push(vec![]);
+        self.ct_inputs.push(vec![]);
+
+        Ok(Variable::new_unchecked(Index::Input(index)))
+    }
+
+    fn enforce<A, AR, LA, LB, LC>(&mut self, _: A, a: LA, b: LB, c: LC)
+    where
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+        LA: FnOnce(LinearCombination<T::BellmanEngine>) -> LinearCombination<T::BellmanEngine>,
+        LB: FnOnce(LinearCombination<T::BellmanEngine>) -> LinearCombination<T::BellmanEngine>,
+        LC: FnOnce(LinearCombination<T::BellmanEngine>) -> LinearCombination<T::BellmanEngine>,
+    {
+        fn eval<T: Field>(
+            l: LinearCombination<T::BellmanEngine>,
+            inputs: &mut [Vec<(Fr<T>, usize)>],
+            aux: &mut [Vec<(Fr<T>, usize)>],
+            this_constraint: usize,
+        ) {
+            for (variable, coeff) in l.as_ref() {
+                match variable.get_unchecked() {
+                    Index::Input(id) => inputs[id].push((*coeff, this_constraint)),
+                    Index::Aux(id) => aux[id].push((*coeff, this_constraint)),
+                }
+            }
+        }
+
+        eval::<T>(
+            a(LinearCombination::zero()),
+            &mut self.at_inputs,
+            &mut self.at_aux,
+            self.num_constraints,
+        );
+        eval::<T>(
+            b(LinearCombination::zero()),
+            &mut self.bt_inputs,
+            &mut self.bt_aux,
+            self.num_constraints,
+        );
+        eval::<T>(
+            c(LinearCombination::zero()),
+            &mut self.ct_inputs,
+            &mut self.ct_aux,
+            self.num_constraints,
+        );
+
+        self.num_constraints += 1;
+    }
+
+    fn push_namespace<NR, N>(&mut self, _: N)
+    where
+        NR: Into<String>,
+        N: FnOnce() -> NR,
+    {
+    }
+
+    fn pop_namespace(&mut self) {}
+
+    fn get_root(&mut self) -> &mut Self::Root {
+        self
+    }
+}
+
+/// Evaluate the QAP polynomials of some variables at tau, returning the A query, the B query in G1 and G2, and
+/// `beta * A + alpha * B + C`
+fn eval<T: Field>(
+    phase1: &Phase1<T>,
+    at: &[Vec<(Fr<T>, usize)>],
+    bt: &[Vec<(Fr<T>, usize)>],
+    ct: &[Vec<(Fr<T>, usize)>],
+) -> (Vec<G1<T>>, Vec<G1<T>>, Vec<G2<T>>, Vec<G1<T>>) {
+    let mut a = vec![G1::<T>::zero(); at.len()];
+    let mut b_g1 = vec![G1::<T>::zero(); at.len()];
+    let mut b_g2 = vec![G2::<T>::zero(); at.len()];
+    let mut ext = vec![G1::<T>::zero(); at.len()];
+
+    for i in 0..at.len() {
+        for &(coeff, lag) in &at[i] {
+            a[i].add_assign(&phase1.coeffs_g1[lag].mul(coeff));
+            ext[i].add_assign(&phase1.beta_coeffs_g1[lag].mul(coeff));
+        }
+        for &(coeff, lag) in &bt[i] {
+            b_g1[i].add_assign(&phase1.coeffs_g1[lag].mul(coeff));
+            b_g2[i].add_assign(&phase1.coeffs_g2[lag].mul(coeff));
+            ext[i].add_assign(&phase1.alpha_coeffs_g1[lag].mul(coeff));
+        }
+        for &(coeff, lag) in &ct[i] {
+            ext[i].add_assign(&phase1.coeffs_g1[lag].mul(coeff));
+        }
+    }
+
+    (a, b_g1, b_g2, ext)
+}
+
+fn into_affine<G: CurveProjective>(mut points: Vec<G>) -> Vec<G::Affine> {
+    G::batch_normalization(&mut points);
+    points.into_iter().map(|p| p.into_affine()).collect()
+}
+
+/// Filter points at infinity away, as they are from the A and B queries
+fn non_zero<G: CurveProjective, I: Iterator<Item = G>>(points: I) -> Vec<G::Affine> {
+    into_affine(points.filter(|p| !p.is_zero()).collect())
+}
+
+/// Whether `g1.1 / g1.0 == g2.1 / g2.0`
+fn same_ratio<T: Field>(g1: (G1Affine<T>, G1Affine<T>), g2: (G2Affine<T>, G2Affine<T>)) -> bool {
+    T::BellmanEngine::pairing(g1.0, g2.1) == T::BellmanEngine::pairing(g1.1, g2.0)
+}
+
+/// A random linear combination of `v1` and the same combination of `v2`, which have the same ratio if and only if all
+/// pairs of elements of `v1` and `v2` have it, except with negligible probability
+fn merge_pairs<T: Field>(v1: &[G1Affine<T>], v2: &[G1Affine<T>]) -> (G1Affine<T>, G1Affine<T>) {
+    let rng = &mut thread_rng();
+
+    let (s1, s2) = v1.iter().zip(v2).fold(
+        (G1::<T>::zero(), G1::<T>::zero()),
+        |(mut s1, mut s2), (p1, p2)| {
+            let rho: Fr<T> = rng.gen();
+            s1.add_assign(&p1.mul(rho));
+            s2.add_assign(&p2.mul(rho));
+            (s1, s2)
+        },
+    );
+
+    (s1.into_affine(), s2.into_affine())
+}
+
+/// A point in G2 which nobody knows the discrete logarithm of, derived from `digest`
+fn hash_to_g2<T: Field>(digest: &[u8; 32]) -> G2<T> {
+    G2::<T>::rand(&mut seeded_rng(digest))
+}
+
+/// A random number generator seeded from the system and from `entropy`, so that it is unpredictable as long as one of
+/// them is
+pub fn rng(entropy: &[u8]) -> Result<ChaChaRng, String> {
+    let mut system =
+        OsRng::new().map_err(|e| format!("Cannot get randomness from the system: {}", e))?;
+    let mut bytes = [0u8; 1024];
+    system.fill_bytes(&mut bytes);
+
+    let digest = Sha256::new().chain(&bytes[..]).chain(entropy).result();
+    Ok(seeded_rng(&digest))
+}
+
+impl<T: Field> MPCParameters<T> {
+    /// Initialize the parameters of `program` from the output of the first phase, read from `phase1`. The first phase
+    /// must be for the smallest power of two which is at least the number of constraints of the program, plus one for
+    /// each public input
+    pub fn new<R: Read>(program: ir::Prog<T>, phase1: R) -> Result<Self, String> {
+        let mut assembly = KeypairAssembly::<T> {
+            num_inputs: 0,
+            num_aux: 0,
+            num_constraints: 0,
+            at_inputs: vec![],
+            bt_inputs: vec![],
+            ct_inputs: vec![],
+            at_aux: vec![],
+            bt_aux: vec![],
+            ct_aux: vec![],
+        };
+
+        // allocate the "one" input variable
+        assembly.alloc_input(|| "", || Ok(Fr::<T>::one())).unwrap();
+
+        program
+            .synthesize(&mut assembly, None)
+            .map_err(|e| e.to_string())?;
+
+        // input constraints to ensure full density of the IC query, as in `bellman::groth16::generate_parameters`
+        for i in 0..assembly.num_inputs {
+            assembly.enforce(
+                || "",
+                |lc| lc + Variable::new_unchecked(Index::Input(i)),
+                |lc| lc,
+                |lc| lc,
+            );
+        }
+
+        // the size of the evaluation domain is the smallest power of two which fits all constraints
+        let mut exp = 0;
+        while (1 << exp) < assembly.num_constraints {
+            exp += 1;
+        }
+
+        let phase1 = Phase1::<T>::read(phase1, exp)?;
+
+        let (a_inputs, b_g1_inputs, b_g2_inputs, ic) = eval(
+            &phase1,
+            &assembly.at_inputs,
+            &assembly.bt_inputs,
+            &assembly.ct_inputs,
+        );
+        let (a_aux, b_g1_aux, b_g2_aux, l) = eval(
+            &phase1,
+            &assembly.at_aux,
+            &assembly.bt_aux,
+            &assembly.ct_aux,
+        );
+
+        // don't allow any elements be unconstrained, so that the L query is always fully dense
+        if l.iter().any(|e| e.is_zero()) {
+            return Err(SynthesisError::UnconstrainedVariable.to_string());
+        }
+
+        // gamma and delta are one until the first contribution
+        let vk = VerifyingKey {
+            alpha_g1: phase1.alpha_g1,
+            beta_g1: phase1.beta_g1,
+            beta_g2: phase1.beta_g2,
+            gamma_g2: G2Affine::<T>::one(),
+            delta_g1: G1Affine::<T>::one(),
+            delta_g2: G2Affine::<T>::one(),
+            ic: into_affine(ic),
+        };
+
+        let params = Parameters {
+            vk,
+            h: Arc::new(phase1.h),
+            l: Arc::new(into_affine(l)),
+            a: Arc::new(non_zero(a_inputs.into_iter().chain(a_aux))),
+            b_g1: Arc::new(non_zero(b_g1_inputs.into_iter().chain(b_g1_aux))),
+            b_g2: Arc::new(non_zero(b_g2_inputs.into_iter().chain(b_g2_aux))),
+        };
+
+        let mut bytes = vec![];
+        params.write(&mut bytes).unwrap();
+        let cs_hash = Sha256::digest(&bytes).into();
+
+        Ok(MPCParameters {
+            params,
+            cs_hash,
+            contributions: vec![],
+        })
+    }
+
+    /// The hash of the parameters, of the first `count` contributions and of a new contribution
+    fn transcript(&self, count: usize, s: &G1Affine<T>, s_delta: &G1Affine<T>) -> [u8; 32] {
+        let mut bytes = self.cs_hash.to_vec();
+        for contribution in &self.contributions[..count] {
+            contribution.write(&mut bytes).unwrap();
+        }
+        bytes.extend_from_slice(s.into_uncompressed().as_ref());
+        bytes.extend_from_slice(s_delta.into_uncompressed().as_ref());
+        Sha256::digest(&bytes).into()
+    }
+
+    /// Contribute randomness from `rng` to the parameters, returning the hash of the contribution. Contributors can
+    /// use it to check that their contribution is part of the final parameters
+    pub fn contribute<R: Rng>(&mut self, rng: &mut R) -> [u8; 32] {
+        let delta: Fr<T> = rng.gen();
+        let delta_inverse = delta.inverse().expect("delta is not zero");
+
+        // prove knowledge of delta
+        let s = G1::<T>::rand(rng).into_affine();
+        let s_delta = s.mul(delta).into_affine();
+        let transcript = self.transcript(self.contributions.len(), &s, &s_delta);
+        let r = hash_to_g2::<T>(&transcript).into_affine();
+
+        let public_key = PublicKey {
+            delta_after: self.params.vk.delta_g1.mul(delta).into_affine(),
+            s,
+            s_delta,
+            r_delta: r.mul(delta).into_affine(),
+            transcript,
+        };
+
+        let divide = |points: &[G1Affine<T>]| {
+            into_affine(points.iter().map(|p| p.mul(delta_inverse)).collect())
+        };
+
+        self.params.h = Arc::new(divide(&self.params.h));
+        self.params.l = Arc::new(divide(&self.params.l));
+        self.params.vk.delta_g1 = public_key.delta_after;
+        self.params.vk.delta_g2 = self.params.vk.delta_g2.mul(delta).into_affine();
+
+        let hash = public_key.hash();
+        self.contributions.push(public_key);
+        hash
+    }
+
+    /// Check that the parameters result from contributions to the initial parameters of `program`, computed from the
+    /// output of the first phase read from `phase1`. Returns the hashes of the contributions, in order
+    pub fn verify<R: Read>(
+        &self,
+        program: ir::Prog<T>,
+        phase1: R,
+    ) -> Result<Vec<[u8; 32]>, String> {
+        // without contributions, delta is known to everyone and proofs can be forged
+        if self.contributions.is_empty() {
+            return Err("The parameters have no contributions".to_string());
+        }
+
+        let initial = MPCParameters::new(program, phase1)?;
+
+        let (before, after) = (&initial.params, &self.params);
+
+        // contributions only change delta and the H and L queries
+        if initial.cs_hash != self.cs_hash
+            || before.vk.alpha_g1 != after.vk.alpha_g1
+            || before.vk.beta_g1 != after.vk.beta_g1
+            || before.vk.beta_g2 != after.vk.beta_g2
+            || before.vk.gamma_g2 != after.vk.gamma_g2
+            || before.vk.ic != after.vk.ic
+            || before.a != after.a
+            || before.b_g1 != after.b_g1
+            || before.b_g2 != after.b_g2
+            || before.h.len() != after.h.len()
+            || before.l.len() != after.l.len()
+        {
+            return Err(
+                "The parameters do not match the program and the phase-1 parameters".to_string(),
+            );
+        }
+
+        let mut delta = G1Affine::<T>::one();
+        let mut hashes = vec![];
+
+        for (i, public_key) in self.contributions.iter().enumerate() {
+            let error = |message| Err(format!("Contribution {} {}", i + 1, message));
+
+            if public_key.transcript != self.transcript(i, &public_key.s, &public_key.s_delta) {
+                return error("has an invalid transcript");
+            }
+
+            // the contributor knows the value they multiplied delta by, as they multiplied `r` by it too
+            let r = hash_to_g2::<T>(&public_key.transcript).into_affine();
+            if !same_ratio::<T>((public_key.s, public_key.s_delta), (r, public_key.r_delta)) {
+                return error("has an invalid proof of knowledge");
+            }
+            if !same_ratio::<T>((delta, public_key.delta_after), (r, public_key.r_delta)) {
+                return error("does not update delta consistently");
+            }
+
+            delta = public_key.delta_after;
+            hashes.push(public_key.hash());
+        }
+
+        if after.vk.delta_g1 != delta
+            || !same_ratio::<T>(
+                (G1Affine::<T>::one(), delta),
+                (G2Affine::<T>::one(), after.vk.delta_g2),
+            )
+        {
+            return Err("Delta does not match the contributions".to_string());
+        }
+
+        // the H and L queries are divided by delta
+        if !same_ratio::<T>(
+            merge_pairs::<T>(&before.h, &after.h),
+            (after.vk.delta_g2, G2Affine::<T>::one()),
+        ) || !same_ratio::<T>(
+            merge_pairs::<T>(&before.l, &after.l),
+            (after.vk.delta_g2, G2Affine::<T>::one()),
+        ) {
+            return Err("The H and L queries do not match delta".to_string());
+        }
+
+        Ok(hashes)
+    }
+
+    /// The proving and verification keys, in the format of `G16::setup`. They should only be used once the
+    /// parameters are verified, and are not produced for parameters without contributions
+    pub fn keypair(&self) -> Result<SetupKeypair<VerificationKey>, String> {
+        if self.contributions.is_empty() {
+            return Err("The parameters have no contributions".to_string());
+        }
+
+        Ok(G16::keypair::<T>(&self.params))
+    }
+
+    pub fn write<W: Write>(&self, mut w: W) -> io::Result<()> {
+        w.write_all(&T::id())?;
+        self.params.write(&mut w)?;
+        w.write_all(&self.cs_hash)?;
+        w.write_all(&(self.contributions.len() as u32).to_be_bytes())?;
+        for contribution in &self.contributions {
+            contribution.write(&mut w)?;
+        }
+        Ok(())
+    }
+
+    /// Read parameters written by `write`, checking that points are valid if `checked` is set
+    pub fn read<R: Read>(mut r: R, checked: bool) -> io::Result<Self> {
+        let mut id = [0; 4];
+        r.read_exact(&mut id)?;
+        if id != T::id() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("The parameters are not for curve {}", T::name()),
+            ));
+        }
+
+        let params = Parameters::read(&mut r, checked).map_err(|e| {
+            match e
+                .get_ref()
+                .and_then(|inner| inner.downcast_ref::<GroupDecodingError>())
+            {
+                Some(inner) => decoding_error(inner),
+                None => e,
+            }
+        })?;
+        let mut cs_hash = [0; 32];
+        r.read_exact(&mut cs_hash)?;
+        let mut count = [0; 4];
+        r.read_exact(&mut count)?;
+        let contributions = (0..u32::from_be_bytes(count))
+            .map(|_| PublicKey::read(&mut r))
+            .collect::<Result<_, _>>()?;
+
+        Ok(MPCParameters {
+            params,
+            cs_hash,
+            contributions,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bellman::domain::{EvaluationDomain, Scalar};
+    use bellman::pairing::bn256::Bn256;
+    use bellman::worker::Worker;
+    use flat_absy::FlatVariable;
+    use ir::{Function, Interpreter, Prog, QuadComb, Statement};
+    use proof_system::{ProofSystem, Randomness};
+    use std::collections::BTreeMap;
+    use zokrates_field::Bn128Field;
+
+    /// The output of a first phase for a domain of size `2^exp`, with random toxic waste
+    fn phase1(exp: u32) -> Vec<u8> {
+        let rng = &mut thread_rng();
+        let tau: Fr<Bn128Field> = rng.gen();
+        let alpha: Fr<Bn128Field> = rng.gen();
+        let beta: Fr<Bn128Field> = rng.gen();
+        let m = 1 << exp;
+
+        let powers: Vec<Fr<Bn128Field>> = (0..m).map(|i| tau.pow(&[i as u64])).collect();
+
+        // the Lagrange polynomials evaluated at tau are the inverse FFT of the powers of tau
+        let mut domain =
+            EvaluationDomain::<Bn256, _>::from_coeffs(powers.iter().map(|p| Scalar(*p)).collect())
+                .unwrap();
+        let t = domain.z(&tau);
+        domain.ifft(&Worker::new());
+        let lagrange: Vec<Fr<Bn128Field>> = domain.into_coeffs().into_iter().map(|s| s.0).collect();
+
+        let times = |k: Fr<Bn128Field>| {
+            move |x: &Fr<Bn128Field>| {
+                let mut x = *x;
+                x.mul_assign(&k);
+                x
+            }
+        };
+
+        fn g1(bytes: &mut Vec<u8>, e: Fr<Bn128Field>) {
+            let point = G1Affine::<Bn128Field>::one().mul(e).into_affine();
+            bytes.extend_from_slice(point.into_uncompressed().as_ref());
+        }
+
+        fn g2(bytes: &mut Vec<u8>, e: Fr<Bn128Field>) {
+            let point = G2Affine::<Bn128Field>::one().mul(e).into_affine();
+            bytes.extend_from_slice(point.into_uncompressed().as_ref());
+        }
+
+        let mut bytes = vec![];
+        g1(&mut bytes, alpha);
+        g1(&mut bytes, beta);
+        g2(&mut bytes, beta);
+        lagrange.iter().for_each(|l| g1(&mut bytes, *l));
+        lagrange.iter().for_each(|l| g2(&mut bytes, *l));
+        lagrange
+            .iter()
+            .map(times(alpha))
+            .for_each(|l| g1(&mut bytes, l));
+        lagrange
+            .iter()
+            .map(times(beta))
+            .for_each(|l| g1(&mut bytes, l));
+        powers[..m - 1]
+            .iter()
+            .map(times(t))
+            .for_each(|h| g1(&mut bytes, h));
+
+        bytes
+    }
+
+    /// A program with one constraint, which fits in a domain of size 4 along with the constraints added for the inputs
+    /// `~one`, `_0` and `~out_0`
+    fn program() -> Prog<Bn128Field> {
+        Prog {
+            main: Function {
+                id: String::from("main"),
+                arguments: vec![FlatVariable::new(0), FlatVariable::new(1)],
+                returns: vec![FlatVariable::public(0)],
+                statements: vec![Statement::constraint(
+                    QuadComb::from_linear_combinations(
+                        FlatVariable::new(0).into(),
+                        FlatVariable::new(1).into(),
+                    ),
+                    FlatVariable::public(0),
+                )],
+            },
+            private: vec![false, true],
+            origins: BTreeMap::new(),
+            metadata: BTreeMap::new(),
+        }
+    }
+
+    fn serialize_and_deserialize(params: &MPCParameters<Bn128Field>) -> MPCParameters<Bn128Field> {
+        let mut bytes = vec![];
+        params.write(&mut bytes).unwrap();
+        MPCParameters::read(&bytes[..], true).unwrap()
+    }
+
+    #[test]
+    fn ceremony() {
+        let phase1 = phase1(2);
+
+        let mut params = MPCParameters::new(program(), &phase1[..]).unwrap();
+
+        let first = params.contribute(&mut thread_rng());
+        let second = params.contribute(&mut rng(b"entropy").unwrap());
+
+        let params = serialize_and_deserialize(&params);
+        assert_eq!(
+            params.verify(program(), &phase1[..]),
+            Ok(vec![first, second])
+        );
+
+        // the keys can be used to prove and verify
+        let keypair = params.keypair().unwrap();
+        let witness = Interpreter::default()
+            .execute(&program(), &vec![Bn128Field::from(6), Bn128Field::from(7)])
+            .unwrap();
+        let proof =
+            G16::generate_proof(program(), witness, keypair.pk, Randomness::System).unwrap();
+        assert!(<G16 as ProofSystem<Bn128Field>>::verify(keypair.vk, proof));
+    }
+
+    #[test]
+    fn no_contributions() {
+        let phase1 = phase1(2);
+
+        // delta is still the generator, so the keys must not be used
+        let params = MPCParameters::new(program(), &phase1[..]).unwrap();
+        let error = String::from("The parameters have no contributions");
+        assert_eq!(params.verify(program(), &phase1[..]), Err(error.clone()));
+        assert_eq!(params.keypair().err(), Some(error));
+    }
+
+    #[test]
+    fn invalid_contributions() {
+        let phase1 = phase1(2);
+
+        let mut params = MPCParameters::new(program(), &phase1[..]).unwrap();
+        params.contribute(&mut thread_rng());
+        params.contribute(&mut thread_rng());
+
+        // contributions cannot be removed
+        let mut removed = serialize_and_deserialize(&params);
+        removed.contributions.remove(0);
+        assert_eq!(
+            removed.verify(program(), &phase1[..]),
+            Err(String::from("Contribution 1 has an invalid transcript"))
+        );
+
+        // delta cannot be changed without a contribution
+        let mut tampered = serialize_and_deserialize(&params);
+        let mut two = Fr::<Bn128Field>::one();
+        two.double();
+        tampered.params.vk.delta_g2 = tampered.params.vk.delta_g2.mul(two).into_affine();
+        assert_eq!(
+            tampered.verify(program(), &phase1[..]),
+            Err(String::from("Delta does not match the contributions"))
+        );
+
+        // the parameters are specific to the program
+        let mut other = program();
+        other.private = vec![true, true];
+        assert_eq!(
+            params.verify(other, &phase1[..]),
+            Err(String::from(
+                "The parameters do not match the program and the phase-1 parameters"
+            ))
+        );
+    }
+
+    #[test]
+    fn phase1_size() {
+        assert_eq!(
+            MPCParameters::new(program(), &phase1(1)[..]).err(),
+            Some(String::from(
+                "The phase-1 parameters are too small, the program requires parameters for 2^2 constraints"
+            ))
+        );
+        assert_eq!(
+            MPCParameters::new(program(), &phase1(3)[..]).err(),
+            Some(String::from(
+                "The phase-1 parameters are too large, the program requires parameters for 2^2 constraints"
+            ))
+        );
+    }
+}
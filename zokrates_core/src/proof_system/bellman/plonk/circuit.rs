@@ -0,0 +1,379 @@
+//! The arithmetization of PLONK: each rank-1 constraint becomes a few gates with three wires, and the wires which carry
+//! the same variable are tied together by copy constraints, which are encoded as a permutation of the wires
+
+use bellman::pairing::ff::{Field, PrimeField};
+use bellman::pairing::Engine;
+use proof_system::bellman::domain::Domain;
+use std::collections::BTreeMap;
+
+/// A linear combination `sum(k_i * x_i) + constant` of variables of a rank-1 constraint system, which are identified
+/// by their index
+pub struct LinearCombination<E: Engine> {
+    pub terms: Vec<(usize, E::Fr)>,
+    pub constant: E::Fr,
+}
+
+/// A gate enforces `q_m * a * b + q_l * a + q_r * b + q_o * c + q_c = 0` on the values of the variables of its wires
+pub struct Gate<E: Engine> {
+    pub wires: [usize; 3],
+    pub q_m: E::Fr,
+    pub q_l: E::Fr,
+    pub q_r: E::Fr,
+    pub q_o: E::Fr,
+    pub q_c: E::Fr,
+}
+
+/// How the prover computes the value of a variable of the circuit
+enum Variable<E: Engine> {
+    /// A variable of the rank-1 constraint system
+    Input(usize),
+    /// The sum `k_1 * x_1 + k_2 * x_2 + d` of two variables of the circuit, which is an intermediate value of a linear
+    /// combination
+    Sum([(usize, E::Fr); 2], E::Fr),
+}
+
+/// The variable on the wires which are not used by a gate, whose value is zero
+const UNUSED: usize = 0;
+
+pub struct Circuit<E: Engine> {
+    /// The number of public inputs, which are on the first wire of the first gates
+    pub public_inputs: usize,
+    pub gates: Vec<Gate<E>>,
+    variables: Vec<Option<Variable<E>>>,
+    /// The variable of the circuit for each variable of the rank-1 constraint system
+    inputs: BTreeMap<usize, usize>,
+}
+
+/// The polynomials which describe a circuit, in coefficient form over its domain
+pub struct Polynomials<E: Engine> {
+    pub domain: Domain<E>,
+    pub q_m: Vec<E::Fr>,
+    pub q_l: Vec<E::Fr>,
+    pub q_r: Vec<E::Fr>,
+    pub q_o: Vec<E::Fr>,
+    pub q_c: Vec<E::Fr>,
+    /// The permutation of the wires: `sigma[j](omega^i)` is the label of the wire which follows the `j`-th wire of the
+    /// `i`-th gate in its copy cycle
+    pub sigma: [Vec<E::Fr>; 3],
+    /// The evaluations of `sigma` over the domain
+    pub sigma_values: [Vec<E::Fr>; 3],
+}
+
+/// The labels of the `j`-th wires of the gates are `k_j * omega^i`. With `k = (1, g, g^2)` where `g` generates the
+/// multiplicative group of the field, the three sets of labels are disjoint cosets of the domain.
+pub fn coset_shifts<E: Engine>() -> [E::Fr; 3] {
+    let g = E::Fr::multiplicative_generator();
+    let mut g2 = g;
+    g2.square();
+    [E::Fr::one(), g, g2]
+}
+
+impl<E: Engine> Circuit<E> {
+    /// An empty circuit whose public inputs are the variables `public` of the rank-1 constraint system. Each of them
+    /// is bound by a gate `a = x`, where `x` is added by the public input polynomial
+    pub fn new(public: &[usize]) -> Self {
+        let mut circuit = Circuit {
+            public_inputs: public.len(),
+            gates: vec![],
+            variables: vec![None],
+            inputs: BTreeMap::new(),
+        };
+
+        for x in public {
+            let a = circuit.input(*x);
+            circuit.gate(
+                [a, UNUSED, UNUSED],
+                E::Fr::zero(),
+                E::Fr::one(),
+                E::Fr::zero(),
+                E::Fr::zero(),
+                E::Fr::zero(),
+            );
+        }
+
+        circuit
+    }
+
+    /// Adds the gates which enforce the rank-1 constraint `a * b = c`
+    pub fn constraint(
+        &mut self,
+        a: LinearCombination<E>,
+        b: LinearCombination<E>,
+        c: LinearCombination<E>,
+    ) {
+        let (a, k_a, d_a) = self.reduce(a);
+        let (b, k_b, d_b) = self.reduce(b);
+        let (c, k_c, d_c) = self.reduce(c);
+
+        // (k_a * a + d_a) * (k_b * b + d_b) - (k_c * c + d_c) = 0
+        let mut q_m = k_a;
+        q_m.mul_assign(&k_b);
+        let mut q_l = k_a;
+        q_l.mul_assign(&d_b);
+        let mut q_r = d_a;
+        q_r.mul_assign(&k_b);
+        let mut q_o = k_c;
+        q_o.negate();
+        let mut q_c = d_a;
+        q_c.mul_assign(&d_b);
+        q_c.sub_assign(&d_c);
+
+        self.gate([a, b, c], q_m, q_l, q_r, q_o, q_c);
+    }
+
+    /// A variable `w` and scalars `k` and `d` such that the linear combination is equal to `k * w + d`. Linear
+    /// combinations of several variables are summed up by a chain of gates.
+    fn reduce(&mut self, lc: LinearCombination<E>) -> (usize, E::Fr, E::Fr) {
+        let terms: Vec<_> = lc
+            .terms
+            .into_iter()
+            .filter(|(_, k)| !k.is_zero())
+            .map(|(x, k)| (self.input(x), k))
+            .collect();
+
+        match terms.len() {
+            0 => (UNUSED, E::Fr::zero(), lc.constant),
+            1 => (terms[0].0, terms[0].1, lc.constant),
+            _ => {
+                let second = terms.get(1).cloned().unwrap_or((UNUSED, E::Fr::zero()));
+                let sum = terms
+                    .iter()
+                    .skip(2)
+                    .fold(self.sum([terms[0], second], lc.constant), |sum, term| {
+                        self.sum([(sum, E::Fr::one()), *term], E::Fr::zero())
+                    });
+                (sum, E::Fr::one(), E::Fr::zero())
+            }
+        }
+    }
+
+    /// A new variable equal to `k_1 * x_1 + k_2 * x_2 + d`
+    fn sum(&mut self, terms: [(usize, E::Fr); 2], d: E::Fr) -> usize {
+        let sum = self.variables.len();
+        self.variables.push(Some(Variable::Sum(terms, d)));
+
+        let mut q_o = E::Fr::one();
+        q_o.negate();
+        self.gate(
+            [terms[0].0, terms[1].0, sum],
+            E::Fr::zero(),
+            terms[0].1,
+            terms[1].1,
+            q_o,
+            d,
+        );
+
+        sum
+    }
+
+    /// The variable of the circuit for the variable `x` of the rank-1 constraint system
+    fn input(&mut self, x: usize) -> usize {
+        let variables = &mut self.variables;
+        *self.inputs.entry(x).or_insert_with(|| {
+            variables.push(Some(Variable::Input(x)));
+            variables.len() - 1
+        })
+    }
+
+    fn gate(
+        &mut self,
+        wires: [usize; 3],
+        q_m: E::Fr,
+        q_l: E::Fr,
+        q_r: E::Fr,
+        q_o: E::Fr,
+        q_c: E::Fr,
+    ) {
+        self.gates.push(Gate {
+            wires,
+            q_m,
+            q_l,
+            q_r,
+            q_o,
+            q_c,
+        });
+    }
+
+    /// The smallest domain which contains a point for each gate. The quotient polynomial is computed over a domain
+    /// eight times as large, which is only large enough for its degree when there are at least two points.
+    pub fn domain(&self) -> Result<Domain<E>, String> {
+        Domain::new(std::cmp::max(self.gates.len(), 2))
+    }
+
+    /// The values of the variables of the circuit, given the values of the variables of the rank-1 constraint system
+    pub fn assign(&self, values: &[E::Fr]) -> Vec<E::Fr> {
+        let mut assignment: Vec<E::Fr> = Vec::with_capacity(self.variables.len());
+
+        for variable in &self.variables {
+            let value = match variable {
+                None => E::Fr::zero(),
+                Some(Variable::Input(x)) => values[*x],
+                Some(Variable::Sum(terms, d)) => terms.iter().fold(*d, |mut acc, (x, k)| {
+                    let mut term = assignment[*x];
+                    term.mul_assign(k);
+                    acc.add_assign(&term);
+                    acc
+                }),
+            };
+            assignment.push(value);
+        }
+
+        assignment
+    }
+
+    /// The values of the three wires of each gate, padded with zeros up to the size of the domain
+    pub fn wire_values(&self, assignment: &[E::Fr], size: usize) -> [Vec<E::Fr>; 3] {
+        let column = |j: usize| {
+            let mut values: Vec<_> = self
+                .gates
+                .iter()
+                .map(|gate| assignment[gate.wires[j]])
+                .collect();
+            values.resize(size, E::Fr::zero());
+            values
+        };
+        [column(0), column(1), column(2)]
+    }
+
+    pub fn polynomials(&self) -> Result<Polynomials<E>, String> {
+        let domain = self.domain()?;
+        let n = domain.size;
+
+        let selector = |q: &dyn Fn(&Gate<E>) -> E::Fr| {
+            let values: Vec<_> = self.gates.iter().map(q).collect();
+            domain.ifft(&values)
+        };
+
+        // the wires of each variable, in the order of the gates, with the unused wires of the padding gates
+        let mut cycles = vec![vec![]; self.variables.len()];
+        for j in 0..3 {
+            for i in 0..n {
+                let variable = self.gates.get(i).map(|g| g.wires[j]).unwrap_or(UNUSED);
+                cycles[variable].push((j, i));
+            }
+        }
+
+        let k = coset_shifts::<E>();
+        let elements: Vec<_> = (0..n).map(|i| domain.element(i)).collect();
+        let label = |(j, i): (usize, usize)| {
+            let mut label = elements[i];
+            label.mul_assign(&k[j]);
+            label
+        };
+
+        // each wire is mapped to the next wire of the same variable
+        let mut sigma_values = [
+            vec![E::Fr::zero(); n],
+            vec![E::Fr::zero(); n],
+            vec![E::Fr::zero(); n],
+        ];
+        for cycle in cycles {
+            for (m, (j, i)) in cycle.iter().enumerate() {
+                sigma_values[*j][*i] = label(cycle[(m + 1) % cycle.len()]);
+            }
+        }
+
+        Ok(Polynomials {
+            q_m: selector(&|g| g.q_m),
+            q_l: selector(&|g| g.q_l),
+            q_r: selector(&|g| g.q_r),
+            q_o: selector(&|g| g.q_o),
+            q_c: selector(&|g| g.q_c),
+            sigma: [
+                domain.ifft(&sigma_values[0]),
+                domain.ifft(&sigma_values[1]),
+                domain.ifft(&sigma_values[2]),
+            ],
+            sigma_values,
+            domain,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bellman::pairing::bn256::{Bn256, Fr};
+    use proof_system::bellman::domain::scalar;
+
+    fn lc(terms: &[(usize, u64)], constant: u64) -> LinearCombination<Bn256> {
+        LinearCombination {
+            terms: terms
+                .iter()
+                .map(|(x, k)| (*x, scalar::<Bn256>(*k)))
+                .collect(),
+            constant: scalar::<Bn256>(constant),
+        }
+    }
+
+    /// Checks that all gates but the public input gates, which also depend on the public input polynomial, hold for the
+    /// values of the variables
+    fn holds(circuit: &Circuit<Bn256>, assignment: &[Fr]) -> bool {
+        circuit.gates[circuit.public_inputs..].iter().all(|gate| {
+            let [a, b, c] = gate.wires;
+            let (a, b, c) = (assignment[a], assignment[b], assignment[c]);
+
+            let mut result = gate.q_c;
+            let mut ab = a;
+            ab.mul_assign(&b);
+            for (q, v) in &[(gate.q_m, ab), (gate.q_l, a), (gate.q_r, b), (gate.q_o, c)] {
+                let mut term = *q;
+                term.mul_assign(v);
+                result.add_assign(&term);
+            }
+            result.is_zero()
+        })
+    }
+
+    #[test]
+    fn constraints() {
+        // (2 * x_0 + 3 * x_1 + x_2 + 1) * x_3 = 4 * x_4 + 5
+        let mut circuit = Circuit::new(&[4]);
+        circuit.constraint(
+            lc(&[(0, 2), (1, 3), (2, 1)], 1),
+            lc(&[(3, 1)], 0),
+            lc(&[(4, 4)], 5),
+        );
+        // x_0 * 7 = x_1 + 2
+        circuit.constraint(lc(&[(0, 1)], 0), lc(&[], 7), lc(&[(1, 1)], 2));
+
+        // one public input gate, two gates for the sum and one for the product, and one for the second constraint
+        assert_eq!(circuit.public_inputs, 1);
+        assert_eq!(circuit.gates.len(), 5);
+
+        let assign = |values: &[u64]| {
+            let values: Vec<_> = values.iter().map(|v| scalar::<Bn256>(*v)).collect();
+            circuit.assign(&values)
+        };
+
+        // (2 * 1 + 3 * 5 + 1 + 1) * 3 = 4 * 13 + 5 and 1 * 7 = 5 + 2
+        assert!(holds(&circuit, &assign(&[1, 5, 1, 3, 13])));
+        assert!(!holds(&circuit, &assign(&[1, 5, 1, 3, 14])));
+        assert!(!holds(&circuit, &assign(&[1, 6, 1, 3, 13])));
+    }
+
+    #[test]
+    fn permutation() {
+        // x_0 * x_0 = x_1, with x_1 public
+        let mut circuit = Circuit::<Bn256>::new(&[1]);
+        circuit.constraint(lc(&[(0, 1)], 0), lc(&[(0, 1)], 0), lc(&[(1, 1)], 0));
+
+        let polynomials = circuit.polynomials().unwrap();
+        let domain = &polynomials.domain;
+        assert_eq!(domain.size, 2);
+
+        let k = coset_shifts::<Bn256>();
+        let label = |j: usize, i: usize| {
+            let mut label = domain.element(i);
+            label.mul_assign(&k[j]);
+            label
+        };
+
+        // x_1 is on the first wire of the first gate and the third wire of the second gate
+        assert_eq!(polynomials.sigma_values[0][0], label(2, 1));
+        assert_eq!(polynomials.sigma_values[2][1], label(0, 0));
+        // x_0 is on the first and second wires of the second gate
+        assert_eq!(polynomials.sigma_values[0][1], label(1, 1));
+        assert_eq!(polynomials.sigma_values[1][1], label(0, 1));
+    }
+}
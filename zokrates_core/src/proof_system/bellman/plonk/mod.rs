@@ -0,0 +1,702 @@
+//! A PLONK backend, whose proving and verification keys are derived from a universal setup which can be shared by all
+//! programs up to a given size
+
+pub mod circuit;
+pub mod polynomial;
+pub mod prover;
+pub mod srs;
+pub mod transcript;
+pub mod verifier;
+
+use pairing::ff::{Field as _, ScalarEngine};
+use regex::Regex;
+use std::collections::BTreeMap;
+
+use zokrates_field::Field;
+
+use crate::flat_absy::FlatVariable;
+use crate::ir::{self, CanonicalLinComb, Statement};
+use crate::proof_system::bellman::domain::Domain;
+use crate::proof_system::bellman::{parse_fr, parse_g1, parse_g2};
+use crate::proof_system::bellman::{rng, serialization};
+use crate::proof_system::solidity::{
+    SOLIDITY_G2_ADDITION_LIB, SOLIDITY_PAIRING_LIB, SOLIDITY_PAIRING_LIB_V2,
+};
+use proof_system::{G1Affine, G2Affine, Proof, ProofSystem, Randomness, SetupKeypair, SolidityAbi};
+
+use self::circuit::{coset_shifts, Circuit, LinearCombination, Polynomials};
+use self::prover::{prove, srs_size};
+use self::srs::Srs;
+use self::verifier::{verify, VerifyingKey};
+
+pub struct PLONK {}
+
+#[derive(Serialize, Deserialize)]
+pub struct ProofPoints {
+    a: G1Affine,
+    b: G1Affine,
+    c: G1Affine,
+    z: G1Affine,
+    t_lo: G1Affine,
+    t_mid: G1Affine,
+    t_hi: G1Affine,
+    w_zeta: G1Affine,
+    w_zeta_omega: G1Affine,
+    a_eval: String,
+    b_eval: String,
+    c_eval: String,
+    s1_eval: String,
+    s2_eval: String,
+    z_omega_eval: String,
+    r_eval: String,
+}
+
+impl ProofPoints {
+    fn into_plonk<T: Field>(self) -> prover::Proof<T::BellmanEngine> {
+        prover::Proof {
+            a: serialization::to_g1::<T>(self.a),
+            b: serialization::to_g1::<T>(self.b),
+            c: serialization::to_g1::<T>(self.c),
+            z: serialization::to_g1::<T>(self.z),
+            t_lo: serialization::to_g1::<T>(self.t_lo),
+            t_mid: serialization::to_g1::<T>(self.t_mid),
+            t_hi: serialization::to_g1::<T>(self.t_hi),
+            w_zeta: serialization::to_g1::<T>(self.w_zeta),
+            w_zeta_omega: serialization::to_g1::<T>(self.w_zeta_omega),
+            a_eval: serialization::to_fr::<T>(&self.a_eval),
+            b_eval: serialization::to_fr::<T>(&self.b_eval),
+            c_eval: serialization::to_fr::<T>(&self.c_eval),
+            s1_eval: serialization::to_fr::<T>(&self.s1_eval),
+            s2_eval: serialization::to_fr::<T>(&self.s2_eval),
+            z_omega_eval: serialization::to_fr::<T>(&self.z_omega_eval),
+            r_eval: serialization::to_fr::<T>(&self.r_eval),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct VerificationKey {
+    domain_size: usize,
+    num_inputs: usize,
+    q_m: G1Affine,
+    q_l: G1Affine,
+    q_r: G1Affine,
+    q_o: G1Affine,
+    q_c: G1Affine,
+    s1: G1Affine,
+    s2: G1Affine,
+    s3: G1Affine,
+    tau: G2Affine,
+}
+
+impl VerificationKey {
+    fn into_plonk<T: Field>(self) -> VerifyingKey<T::BellmanEngine> {
+        VerifyingKey {
+            domain_size: self.domain_size,
+            public_inputs: self.num_inputs,
+            q_m: serialization::to_g1::<T>(self.q_m),
+            q_l: serialization::to_g1::<T>(self.q_l),
+            q_r: serialization::to_g1::<T>(self.q_r),
+            q_o: serialization::to_g1::<T>(self.q_o),
+            q_c: serialization::to_g1::<T>(self.q_c),
+            s1: serialization::to_g1::<T>(self.s1),
+            s2: serialization::to_g1::<T>(self.s2),
+            s3: serialization::to_g1::<T>(self.s3),
+            tau_g2: serialization::to_g2::<T>(self.tau),
+        }
+    }
+}
+
+impl PLONK {
+    /// A universal setup for all programs with at most `2^exponent` gates, from which their keys can be derived with
+    /// `setup_with_srs`. Whoever knows its randomness can forge proofs for all of them.
+    pub fn universal_setup<T: Field>(exponent: u32, randomness: Randomness) -> Vec<u8> {
+        let srs = Srs::<T::BellmanEngine>::generate(srs_size(1 << exponent), &mut rng(randomness));
+
+        let mut res = vec![];
+        srs.write(&mut res).unwrap();
+        res
+    }
+
+    /// The proving and verification keys of a program, derived from a universal setup
+    pub fn setup_with_srs<T: Field>(
+        program: ir::Prog<T>,
+        srs: &[u8],
+    ) -> Result<SetupKeypair<VerificationKey>, String> {
+        let srs = Srs::<T::BellmanEngine>::read(srs)
+            .map_err(|e| format!("Could not read the universal setup: {}", e))?;
+
+        if !srs.is_valid(&mut rng(Randomness::System)) {
+            return Err(String::from("The universal setup is invalid"));
+        }
+
+        let (circuit, _) = circuit(&program);
+        let polynomials = circuit.polynomials()?;
+        let srs = srs.truncate(srs_size(polynomials.domain.size))?;
+
+        Ok(PLONK::keypair::<T>(&circuit, &polynomials, &srs))
+    }
+
+    fn keypair<T: Field>(
+        circuit: &Circuit<T::BellmanEngine>,
+        polynomials: &Polynomials<T::BellmanEngine>,
+        srs: &Srs<T::BellmanEngine>,
+    ) -> SetupKeypair<VerificationKey> {
+        let mut pk: Vec<u8> = Vec::new();
+        srs.write(&mut pk).unwrap();
+
+        let vk = VerifyingKey::new(circuit, polynomials, srs);
+
+        let vk = VerificationKey {
+            domain_size: vk.domain_size,
+            num_inputs: vk.public_inputs,
+            q_m: parse_g1::<T>(&vk.q_m),
+            q_l: parse_g1::<T>(&vk.q_l),
+            q_r: parse_g1::<T>(&vk.q_r),
+            q_o: parse_g1::<T>(&vk.q_o),
+            q_c: parse_g1::<T>(&vk.q_c),
+            s1: parse_g1::<T>(&vk.s1),
+            s2: parse_g1::<T>(&vk.s2),
+            s3: parse_g1::<T>(&vk.s3),
+            tau: parse_g2::<T>(&vk.tau_g2),
+        };
+
+        SetupKeypair::new(vk, pk)
+    }
+}
+
+/// The PLONK circuit of a program, and the variables of the program in the order of the variables of the circuit.
+/// The public inputs come first, in the same order as for the other bellman backends: the public arguments, then the
+/// return values.
+fn circuit<T: Field>(program: &ir::Prog<T>) -> (Circuit<T::BellmanEngine>, Vec<FlatVariable>) {
+    let mut variables: Vec<FlatVariable> = program
+        .main
+        .arguments
+        .iter()
+        .zip(&program.private)
+        .filter(|(_, private)| !**private)
+        .map(|(v, _)| *v)
+        .chain(program.main.returns.iter().cloned())
+        .collect();
+
+    let mut ids: BTreeMap<FlatVariable, usize> =
+        variables.iter().enumerate().map(|(i, v)| (*v, i)).collect();
+
+    let public: Vec<_> = (0..variables.len()).collect();
+    let mut circuit = Circuit::new(&public);
+
+    for statement in &program.main.statements {
+        if let Statement::Constraint(quad, lin) = statement {
+            let a = combination(quad.left.clone().as_canonical(), &mut ids, &mut variables);
+            let b = combination(quad.right.clone().as_canonical(), &mut ids, &mut variables);
+            let c = combination(lin.clone().as_canonical(), &mut ids, &mut variables);
+            circuit.constraint(a, b, c);
+        }
+    }
+
+    (circuit, variables)
+}
+
+/// The linear combination of the circuit for a linear combination of the program, where `~one` becomes the constant
+fn combination<T: Field>(
+    l: CanonicalLinComb<T>,
+    ids: &mut BTreeMap<FlatVariable, usize>,
+    variables: &mut Vec<FlatVariable>,
+) -> LinearCombination<T::BellmanEngine> {
+    let mut terms = vec![];
+    let mut constant = <T::BellmanEngine as ScalarEngine>::Fr::zero();
+
+    for (v, k) in l.0 {
+        let k = k.into_bellman();
+        match v == FlatVariable::one() {
+            true => constant.add_assign(&k),
+            false => {
+                let id = *ids.entry(v).or_insert_with(|| {
+                    variables.push(v);
+                    variables.len() - 1
+                });
+                terms.push((id, k));
+            }
+        }
+    }
+
+    LinearCombination { terms, constant }
+}
+
+impl<T: Field> ProofSystem<T> for PLONK {
+    type VerificationKey = VerificationKey;
+    type ProofPoints = ProofPoints;
+
+    fn setup(
+        program: ir::Prog<T>,
+        randomness: Randomness,
+    ) -> Result<SetupKeypair<VerificationKey>, String> {
+        let (circuit, _) = circuit(&program);
+        let polynomials = circuit.polynomials()?;
+
+        let srs = Srs::generate(srs_size(polynomials.domain.size), &mut rng(randomness));
+
+        Ok(PLONK::keypair::<T>(&circuit, &polynomials, &srs))
+    }
+
+    fn generate_proof(
+        program: ir::Prog<T>,
+        witness: ir::Witness<T>,
+        proving_key: Vec<u8>,
+        randomness: Randomness,
+    ) -> Result<Proof<ProofPoints>, String> {
+        let (circuit, variables) = circuit(&program);
+        let polynomials = circuit.polynomials()?;
+        let srs = Srs::read(proving_key.as_slice()).map_err(|e| e.to_string())?;
+
+        let values = variables
+            .iter()
+            .map(|v| {
+                witness
+                    .0
+                    .get(v)
+                    .map(|value| value.clone().into_bellman())
+                    .ok_or_else(|| format!("The witness has no value for {}", v))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let proof = prove(&circuit, &polynomials, &srs, &values, &mut rng(randomness))?;
+
+        let proof_points = ProofPoints {
+            a: parse_g1::<T>(&proof.a),
+            b: parse_g1::<T>(&proof.b),
+            c: parse_g1::<T>(&proof.c),
+            z: parse_g1::<T>(&proof.z),
+            t_lo: parse_g1::<T>(&proof.t_lo),
+            t_mid: parse_g1::<T>(&proof.t_mid),
+            t_hi: parse_g1::<T>(&proof.t_hi),
+            w_zeta: parse_g1::<T>(&proof.w_zeta),
+            w_zeta_omega: parse_g1::<T>(&proof.w_zeta_omega),
+            a_eval: parse_fr::<T>(&proof.a_eval),
+            b_eval: parse_fr::<T>(&proof.b_eval),
+            c_eval: parse_fr::<T>(&proof.c_eval),
+            s1_eval: parse_fr::<T>(&proof.s1_eval),
+            s2_eval: parse_fr::<T>(&proof.s2_eval),
+            z_omega_eval: parse_fr::<T>(&proof.z_omega_eval),
+            r_eval: parse_fr::<T>(&proof.r_eval),
+        };
+
+        let inputs = values[..circuit.public_inputs]
+            .iter()
+            .map(parse_fr::<T>)
+            .collect::<Vec<_>>();
+
+        let mut raw: Vec<u8> = Vec::new();
+        proof.write(&mut raw).unwrap();
+
+        Ok(Proof::<ProofPoints>::new(
+            proof_points,
+            inputs,
+            hex::encode(&raw),
+        ))
+    }
+
+    fn export_solidity_verifier(vk: VerificationKey, abi: SolidityAbi) -> String {
+        let (verify_tx, mutability, solidity_pairing_lib) = match abi {
+            SolidityAbi::V1 => (VERIFY_TX, "view ", SOLIDITY_PAIRING_LIB),
+            SolidityAbi::V2 => (VERIFY_TX_V2, "", SOLIDITY_PAIRING_LIB_V2),
+        };
+
+        let domain = Domain::<T::BellmanEngine>::new(vk.domain_size).unwrap();
+        let k = coset_shifts::<T::BellmanEngine>();
+        let digest = vk.clone().into_plonk::<T>().digest();
+
+        let template_text = CONTRACT_TEMPLATE
+            .replace("<%verify_tx%>", verify_tx)
+            .replace("<%mutability%>", mutability)
+            .replace("<%vk_domain_size%>", &vk.domain_size.to_string())
+            .replace("<%vk_omega%>", &parse_fr::<T>(&domain.omega))
+            .replace("<%vk_k1%>", &parse_fr::<T>(&k[1]))
+            .replace("<%vk_k2%>", &parse_fr::<T>(&k[2]))
+            .replace("<%vk_digest%>", &parse_fr::<T>(&digest))
+            .replace("<%vk_input_length%>", &vk.num_inputs.to_string())
+            .replace("<%vk_q_m%>", &vk.q_m.to_string())
+            .replace("<%vk_q_l%>", &vk.q_l.to_string())
+            .replace("<%vk_q_r%>", &vk.q_r.to_string())
+            .replace("<%vk_q_o%>", &vk.q_o.to_string())
+            .replace("<%vk_q_c%>", &vk.q_c.to_string())
+            .replace("<%vk_s1%>", &vk.s1.to_string())
+            .replace("<%vk_s2%>", &vk.s2.to_string())
+            .replace("<%vk_s3%>", &vk.s3.to_string())
+            .replace("<%vk_tau%>", &vk.tau.to_string());
+
+        let re = Regex::new(r"(?P<v>0[xX][0-9a-fA-F]{64})").unwrap();
+        let template_text = re.replace_all(&template_text, "uint256($v)").to_string();
+
+        format!(
+            "{}{}{}",
+            SOLIDITY_G2_ADDITION_LIB, solidity_pairing_lib, template_text
+        )
+    }
+
+    fn verify(vk: VerificationKey, proof: Proof<ProofPoints>) -> bool {
+        let vk = vk.into_plonk::<T>();
+
+        let public_inputs: Vec<_> = proof
+            .inputs
+            .iter()
+            .map(|s| serialization::to_fr::<T>(s))
+            .collect();
+
+        verify(&vk, &proof.proof.into_plonk::<T>(), &public_inputs)
+    }
+}
+
+const CONTRACT_TEMPLATE: &str = r#"
+contract Verifier {
+    using Pairing for *;
+    // the order of the scalar field
+    uint256 constant r = 21888242871839275222246405745257275088548364400416034343698204186575808495617;
+    // the size of the domain, its generator, and the shifts of its cosets for the second and third wires
+    uint256 constant n = <%vk_domain_size%>;
+    uint256 constant omega = <%vk_omega%>;
+    uint256 constant k1 = <%vk_k1%>;
+    uint256 constant k2 = <%vk_k2%>;
+    // the digest of n, k1, k2 and the commitments of the verifying key, which binds the challenges to the circuit
+    uint256 constant digest = <%vk_digest%>;
+    struct VerifyingKey {
+        Pairing.G1Point q_m;
+        Pairing.G1Point q_l;
+        Pairing.G1Point q_r;
+        Pairing.G1Point q_o;
+        Pairing.G1Point q_c;
+        Pairing.G1Point s1;
+        Pairing.G1Point s2;
+        Pairing.G1Point s3;
+        Pairing.G2Point tau;
+    }
+    struct Proof {
+        Pairing.G1Point a;
+        Pairing.G1Point b;
+        Pairing.G1Point c;
+        Pairing.G1Point z;
+        Pairing.G1Point t_lo;
+        Pairing.G1Point t_mid;
+        Pairing.G1Point t_hi;
+        Pairing.G1Point w_zeta;
+        Pairing.G1Point w_zeta_omega;
+        uint a_eval;
+        uint b_eval;
+        uint c_eval;
+        uint s1_eval;
+        uint s2_eval;
+        uint z_omega_eval;
+        uint r_eval;
+    }
+    struct Challenges {
+        uint beta;
+        uint gamma;
+        uint alpha;
+        uint zeta;
+        uint v;
+        uint u;
+    }
+    function verifyingKey() pure internal returns (VerifyingKey memory vk) {
+        vk.q_m = Pairing.G1Point(<%vk_q_m%>);
+        vk.q_l = Pairing.G1Point(<%vk_q_l%>);
+        vk.q_r = Pairing.G1Point(<%vk_q_r%>);
+        vk.q_o = Pairing.G1Point(<%vk_q_o%>);
+        vk.q_c = Pairing.G1Point(<%vk_q_c%>);
+        vk.s1 = Pairing.G1Point(<%vk_s1%>);
+        vk.s2 = Pairing.G1Point(<%vk_s2%>);
+        vk.s3 = Pairing.G1Point(<%vk_s3%>);
+        vk.tau = Pairing.G2Point(<%vk_tau%>);
+    }
+    function expmod(uint base, uint e) internal view returns (uint result) {
+        uint[6] memory input = [uint(32), 32, 32, base, e, r];
+        uint[1] memory output;
+        bool success;
+        assembly {
+            success := staticcall(sub(gas(), 2000), 5, input, 0xc0, output, 0x20)
+            // Use "invalid" to make gas estimation work
+            switch success case 0 { invalid() }
+        }
+        require(success);
+        return output[0];
+    }
+    function inverse(uint a) internal view returns (uint) {
+        return expmod(a, r - 2);
+    }
+    // w + beta * label + gamma
+    function permuted(uint w, uint beta, uint label, uint gamma) internal pure returns (uint) {
+        return addmod(addmod(w, mulmod(beta, label, r), r), gamma, r);
+    }
+    function accumulate(Pairing.G1Point memory acc, Pairing.G1Point memory p, uint s) internal <%mutability%>returns (Pairing.G1Point memory) {
+        return Pairing.addition(acc, Pairing.scalar_mul(p, s));
+    }
+    function challenge(bytes memory transcript) internal pure returns (uint) {
+        return uint256(sha256(transcript)) % r;
+    }
+    // the challenges are the hashes of the previous challenge and of the messages of the prover since then, the first
+    // one following the digest of the verifying key
+    function challenges(uint[] memory input, Proof memory proof) internal pure returns (Challenges memory ch) {
+        ch.beta = challenge(abi.encodePacked(digest, input, proof.a.X, proof.a.Y, proof.b.X, proof.b.Y, proof.c.X, proof.c.Y));
+        ch.gamma = challenge(abi.encodePacked(ch.beta));
+        ch.alpha = challenge(abi.encodePacked(ch.gamma, proof.z.X, proof.z.Y));
+        ch.zeta = challenge(abi.encodePacked(ch.alpha, proof.t_lo.X, proof.t_lo.Y, proof.t_mid.X, proof.t_mid.Y, proof.t_hi.X, proof.t_hi.Y));
+        ch.v = challenge(abi.encodePacked(ch.zeta, proof.a_eval, proof.b_eval, proof.c_eval, proof.s1_eval, proof.s2_eval, proof.z_omega_eval, proof.r_eval));
+        ch.u = challenge(abi.encodePacked(ch.v, proof.w_zeta.X, proof.w_zeta.Y, proof.w_zeta_omega.X, proof.w_zeta_omega.Y));
+    }
+    // L_1(zeta) and the public input polynomial PI(zeta) = -sum(input[i] * L_(i + 1)(zeta))
+    function lagrange(uint[] memory input, uint zeta, uint zh) internal view returns (uint l1, uint pi) {
+        // L_i(zeta) = omega^i * (zeta^n - 1) / (n * (zeta - omega^i))
+        uint numerator = mulmod(zh, inverse(n), r);
+        uint element = 1;
+        uint count = input.length == 0 ? 1 : input.length;
+        for (uint i = 0; i < count; i++) {
+            uint l = mulmod(mulmod(element, numerator, r), inverse(addmod(zeta, r - element, r)), r);
+            if (i == 0) {
+                l1 = l;
+            }
+            if (i < input.length) {
+                pi = addmod(pi, r - mulmod(input[i], l, r), r);
+            }
+            element = mulmod(element, omega, r);
+        }
+    }
+    // the evaluation of the quotient polynomial at zeta, from the evaluation of the linearisation polynomial
+    function quotient(Proof memory proof, Challenges memory ch, uint l1, uint pi, uint zh) internal view returns (uint t) {
+        uint permutation = mulmod(ch.alpha, proof.z_omega_eval, r);
+        permutation = mulmod(permutation, permuted(proof.a_eval, ch.beta, proof.s1_eval, ch.gamma), r);
+        permutation = mulmod(permutation, permuted(proof.b_eval, ch.beta, proof.s2_eval, ch.gamma), r);
+        permutation = mulmod(permutation, addmod(proof.c_eval, ch.gamma, r), r);
+        uint first = mulmod(mulmod(ch.alpha, ch.alpha, r), l1, r);
+        t = addmod(proof.r_eval, pi, r);
+        t = addmod(t, r - permutation, r);
+        t = addmod(t, r - first, r);
+        t = mulmod(t, inverse(zh), r);
+    }
+    // v times the commitment to the linearisation polynomial, plus u times the commitment to z
+    function linearisation(VerifyingKey memory vk, Proof memory proof, Challenges memory ch, uint l1) internal <%mutability%>returns (Pairing.G1Point memory result) {
+        result = Pairing.scalar_mul(vk.q_m, mulmod(ch.v, mulmod(proof.a_eval, proof.b_eval, r), r));
+        result = accumulate(result, vk.q_l, mulmod(ch.v, proof.a_eval, r));
+        result = accumulate(result, vk.q_r, mulmod(ch.v, proof.b_eval, r));
+        result = accumulate(result, vk.q_o, mulmod(ch.v, proof.c_eval, r));
+        result = accumulate(result, vk.q_c, ch.v);
+        uint z = ch.alpha;
+        z = mulmod(z, permuted(proof.a_eval, ch.beta, ch.zeta, ch.gamma), r);
+        z = mulmod(z, permuted(proof.b_eval, ch.beta, mulmod(k1, ch.zeta, r), ch.gamma), r);
+        z = mulmod(z, permuted(proof.c_eval, ch.beta, mulmod(k2, ch.zeta, r), ch.gamma), r);
+        z = addmod(z, mulmod(mulmod(ch.alpha, ch.alpha, r), l1, r), r);
+        result = accumulate(result, proof.z, addmod(mulmod(ch.v, z, r), ch.u, r));
+        uint s3 = mulmod(ch.alpha, ch.beta, r);
+        s3 = mulmod(s3, proof.z_omega_eval, r);
+        s3 = mulmod(s3, permuted(proof.a_eval, ch.beta, proof.s1_eval, ch.gamma), r);
+        s3 = mulmod(s3, permuted(proof.b_eval, ch.beta, proof.s2_eval, ch.gamma), r);
+        result = accumulate(result, vk.s3, mulmod(ch.v, r - s3, r));
+    }
+    // e(W_zeta + u * W_zeta_omega, tau) = e(zeta * W_zeta + u * zeta * omega * W_zeta_omega + F - E, 1), where F is the
+    // commitment to the polynomials which are opened at zeta and E the value they open to
+    function check(VerifyingKey memory vk, Proof memory proof, Challenges memory ch, uint l1, uint t) internal <%mutability%>returns (bool) {
+        Pairing.G1Point memory right = linearisation(vk, proof, ch, l1);
+        right = Pairing.addition(right, proof.t_lo);
+        uint zeta_n = expmod(ch.zeta, n + 2);
+        right = accumulate(right, proof.t_mid, zeta_n);
+        right = accumulate(right, proof.t_hi, mulmod(zeta_n, zeta_n, r));
+        uint v = ch.v;
+        uint e = addmod(t, mulmod(v, proof.r_eval, r), r);
+        v = mulmod(v, ch.v, r);
+        right = accumulate(right, proof.a, v);
+        e = addmod(e, mulmod(v, proof.a_eval, r), r);
+        v = mulmod(v, ch.v, r);
+        right = accumulate(right, proof.b, v);
+        e = addmod(e, mulmod(v, proof.b_eval, r), r);
+        v = mulmod(v, ch.v, r);
+        right = accumulate(right, proof.c, v);
+        e = addmod(e, mulmod(v, proof.c_eval, r), r);
+        v = mulmod(v, ch.v, r);
+        right = accumulate(right, vk.s1, v);
+        e = addmod(e, mulmod(v, proof.s1_eval, r), r);
+        v = mulmod(v, ch.v, r);
+        right = accumulate(right, vk.s2, v);
+        e = addmod(e, mulmod(v, proof.s2_eval, r), r);
+        e = addmod(e, mulmod(ch.u, proof.z_omega_eval, r), r);
+        right = accumulate(right, proof.w_zeta, ch.zeta);
+        right = accumulate(right, proof.w_zeta_omega, mulmod(mulmod(ch.u, ch.zeta, r), omega, r));
+        right = accumulate(right, Pairing.P1(), r - e);
+        Pairing.G1Point memory left = accumulate(proof.w_zeta, proof.w_zeta_omega, ch.u);
+        return Pairing.pairingProd2(left, vk.tau, Pairing.negate(right), Pairing.P2());
+    }
+    function verify(uint[] memory input, Proof memory proof) internal <%mutability%>returns (bool) {
+        require(input.length == <%vk_input_length%>);
+        for (uint i = 0; i < input.length; i++) {
+            require(input[i] < r);
+        }
+        require(proof.a_eval < r && proof.b_eval < r && proof.c_eval < r && proof.s1_eval < r);
+        require(proof.s2_eval < r && proof.z_omega_eval < r && proof.r_eval < r);
+        VerifyingKey memory vk = verifyingKey();
+        Challenges memory ch = challenges(input, proof);
+        uint zh = addmod(expmod(ch.zeta, n), r - 1, r);
+        if (zh == 0) return false;
+        (uint l1, uint pi) = lagrange(input, ch.zeta, zh);
+        uint t = quotient(proof, ch, l1, pi, zh);
+        return check(vk, proof, ch, l1, t);
+    }
+<%verify_tx%>}
+"#;
+
+const VERIFY_TX: &str = r#"    function verifyTx(
+            uint[2][9] memory points,
+            uint[7] memory evaluations,
+            uint[<%vk_input_length%>] memory input
+        ) public view returns (bool) {
+        Proof memory proof;
+        proof.a = Pairing.G1Point(points[0][0], points[0][1]);
+        proof.b = Pairing.G1Point(points[1][0], points[1][1]);
+        proof.c = Pairing.G1Point(points[2][0], points[2][1]);
+        proof.z = Pairing.G1Point(points[3][0], points[3][1]);
+        proof.t_lo = Pairing.G1Point(points[4][0], points[4][1]);
+        proof.t_mid = Pairing.G1Point(points[5][0], points[5][1]);
+        proof.t_hi = Pairing.G1Point(points[6][0], points[6][1]);
+        proof.w_zeta = Pairing.G1Point(points[7][0], points[7][1]);
+        proof.w_zeta_omega = Pairing.G1Point(points[8][0], points[8][1]);
+        proof.a_eval = evaluations[0];
+        proof.b_eval = evaluations[1];
+        proof.c_eval = evaluations[2];
+        proof.s1_eval = evaluations[3];
+        proof.s2_eval = evaluations[4];
+        proof.z_omega_eval = evaluations[5];
+        proof.r_eval = evaluations[6];
+        uint[] memory inputValues = new uint[](input.length);
+        for(uint i = 0; i < input.length; i++){
+            inputValues[i] = input[i];
+        }
+        return verify(inputValues, proof);
+    }
+"#;
+
+const VERIFY_TX_V2: &str = r#"    function verifyTx(
+            Proof memory proof,
+            uint[<%vk_input_length%>] memory input
+        ) public returns (bool) {
+        uint[] memory inputValues = new uint[](input.length);
+        for(uint i = 0; i < input.length; i++){
+            inputValues[i] = input[i];
+        }
+        return verify(inputValues, proof);
+    }
+"#;
+
+#[cfg(test)]
+mod tests {
+    use crate::flat_absy::FlatVariable;
+    use crate::ir::{Function, Interpreter, LinComb, Prog, QuadComb, Statement};
+
+    use super::*;
+    use zokrates_field::Bn128Field;
+
+    /// x * x + 1 = y, with x private and y public
+    fn program() -> Prog<Bn128Field> {
+        Prog {
+            main: Function {
+                id: String::from("main"),
+                arguments: vec![FlatVariable::new(0)],
+                returns: vec![FlatVariable::public(0)],
+                statements: vec![
+                    Statement::Constraint(
+                        QuadComb::from_linear_combinations(
+                            FlatVariable::new(0).into(),
+                            FlatVariable::new(0).into(),
+                        ),
+                        FlatVariable::new(1).into(),
+                    ),
+                    Statement::Constraint(
+                        (LinComb::from(FlatVariable::new(1)) + LinComb::one()).into(),
+                        FlatVariable::public(0).into(),
+                    ),
+                ],
+            },
+            private: vec![true],
+            origins: BTreeMap::new(),
+            metadata: BTreeMap::new(),
+        }
+    }
+
+    #[test]
+    fn verify() {
+        let program = program();
+        let keypair = PLONK::setup(program.clone(), Randomness::System).unwrap();
+
+        let interpreter = Interpreter::default();
+
+        let witness = interpreter
+            .execute(&program, &vec![Bn128Field::from(42)])
+            .unwrap();
+
+        let proof =
+            PLONK::generate_proof(program, witness, keypair.pk, Randomness::System).unwrap();
+        assert_eq!(
+            proof.inputs,
+            vec![parse_fr::<Bn128Field>(
+                &Bn128Field::from(1765).into_bellman()
+            )]
+        );
+
+        let ans = <PLONK as ProofSystem<Bn128Field>>::verify(keypair.vk, proof);
+
+        assert!(ans);
+    }
+
+    #[test]
+    fn universal_setup() {
+        let program = program();
+        let srs = PLONK::universal_setup::<Bn128Field>(3, Randomness::InsecureSeed(b"universal"));
+
+        let keypair = PLONK::setup_with_srs(program.clone(), &srs).unwrap();
+
+        let interpreter = Interpreter::default();
+
+        let witness = interpreter
+            .execute(&program, &vec![Bn128Field::from(42)])
+            .unwrap();
+
+        let proof = PLONK::generate_proof(program.clone(), witness, keypair.pk, Randomness::System)
+            .unwrap();
+        let ans = <PLONK as ProofSystem<Bn128Field>>::verify(keypair.vk, proof);
+
+        assert!(ans);
+
+        // the program has three gates, one for the public input and one for each constraint, which do not fit in a
+        // universal setup for a single gate
+        assert_eq!(circuit(&program).0.gates.len(), 3);
+        let srs = PLONK::universal_setup::<Bn128Field>(0, Randomness::InsecureSeed(b"universal"));
+        assert!(PLONK::setup_with_srs(program, &srs).is_err());
+    }
+
+    #[test]
+    fn missing_witness_value() {
+        let program = program();
+        let keypair = PLONK::setup(program.clone(), Randomness::System).unwrap();
+
+        let mut witness = Interpreter::default()
+            .execute(&program, &vec![Bn128Field::from(42)])
+            .unwrap();
+        witness.0.remove(&FlatVariable::new(1));
+
+        assert_eq!(
+            PLONK::generate_proof(program, witness, keypair.pk, Randomness::System).err(),
+            Some(format!(
+                "The witness has no value for {}",
+                FlatVariable::new(1)
+            ))
+        );
+    }
+
+    #[test]
+    fn truncated_proving_key() {
+        let program = program();
+        let mut keypair = PLONK::setup(program.clone(), Randomness::System).unwrap();
+        keypair.pk.truncate(keypair.pk.len() / 2);
+
+        let witness = Interpreter::default()
+            .execute(&program, &vec![Bn128Field::from(42)])
+            .unwrap();
+
+        assert!(PLONK::generate_proof(program, witness, keypair.pk, Randomness::System).is_err());
+    }
+}
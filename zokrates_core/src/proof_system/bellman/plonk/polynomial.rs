@@ -0,0 +1,90 @@
+//! Operations on polynomials over the scalar field, represented by their coefficients
+
+use bellman::pairing::ff::Field;
+use bellman::pairing::Engine;
+
+/// Adds `k * p` to `acc`
+pub fn add_scaled<E: Engine>(acc: &mut Vec<E::Fr>, k: &E::Fr, p: &[E::Fr]) {
+    if acc.len() < p.len() {
+        acc.resize(p.len(), E::Fr::zero());
+    }
+    for (a, c) in acc.iter_mut().zip(p) {
+        let mut c = *c;
+        c.mul_assign(k);
+        a.add_assign(&c);
+    }
+}
+
+/// The quotient of `p(X) - p(z)` by `X - z`
+pub fn divide_by_linear<E: Engine>(p: &[E::Fr], z: &E::Fr) -> Vec<E::Fr> {
+    let mut quotient = vec![E::Fr::zero(); p.len().saturating_sub(1)];
+    let mut acc = E::Fr::zero();
+    for i in (1..p.len()).rev() {
+        acc.mul_assign(z);
+        acc.add_assign(&p[i]);
+        quotient[i - 1] = acc;
+    }
+    quotient
+}
+
+/// Adds `blinding(X) * (X^n - 1)` to `p`, which does not change its evaluations over the domain of size `n`
+pub fn blind<E: Engine>(p: &mut Vec<E::Fr>, n: usize, blinding: &[E::Fr]) {
+    p.resize(std::cmp::max(p.len(), n + blinding.len()), E::Fr::zero());
+    for (k, b) in blinding.iter().enumerate() {
+        p[k].sub_assign(b);
+        p[n + k].add_assign(b);
+    }
+}
+
+/// The inverses of all `values`, which must be non-zero, with a single field inversion
+pub fn batch_inverse<E: Engine>(values: &mut [E::Fr]) {
+    let mut products = Vec::with_capacity(values.len());
+    let mut acc = E::Fr::one();
+    for v in values.iter() {
+        products.push(acc);
+        acc.mul_assign(v);
+    }
+
+    let mut acc = acc.inverse().unwrap();
+    for (v, product) in values.iter_mut().zip(products).rev() {
+        let mut inverse = acc;
+        inverse.mul_assign(&product);
+        acc.mul_assign(v);
+        *v = inverse;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bellman::pairing::bn256::{Bn256, Fr};
+    use proof_system::bellman::domain::{evaluate, scalar, Domain};
+
+    fn poly(coeffs: &[u64]) -> Vec<Fr> {
+        coeffs.iter().map(|c| scalar::<Bn256>(*c)).collect()
+    }
+
+    #[test]
+    fn division() {
+        // (X^2 + 3X + 5 - 15) / (X - 2) = X + 5
+        let p = poly(&[5, 3, 1]);
+        assert_eq!(
+            divide_by_linear::<Bn256>(&p, &scalar::<Bn256>(2)),
+            poly(&[5, 1])
+        );
+    }
+
+    #[test]
+    fn blinding() {
+        let domain = Domain::<Bn256>::new(4).unwrap();
+        let p = poly(&[1, 2, 3, 4]);
+        let mut blinded = p.clone();
+        blind::<Bn256>(&mut blinded, 4, &poly(&[7, 8]));
+
+        assert_eq!(blinded.len(), 6);
+        for i in 0..4 {
+            let x = domain.element(i);
+            assert_eq!(evaluate::<Bn256>(&p, &x), evaluate::<Bn256>(&blinded, &x));
+        }
+    }
+}
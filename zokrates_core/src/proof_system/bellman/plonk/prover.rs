@@ -0,0 +1,456 @@
+//! The PLONK prover, following the five rounds of [GWC19](https://eprint.iacr.org/2019/953)
+
+use bellman::pairing::ff::{Field, PrimeField, PrimeFieldRepr};
+use bellman::pairing::{CurveAffine, Engine};
+use proof_system::bellman::domain::{evaluate, Domain};
+use proof_system::bellman::plonk::circuit::{coset_shifts, Circuit, Polynomials};
+use proof_system::bellman::plonk::polynomial::{
+    add_scaled, batch_inverse, blind, divide_by_linear,
+};
+use proof_system::bellman::plonk::srs::Srs;
+use proof_system::bellman::plonk::transcript::Transcript;
+use proof_system::bellman::plonk::verifier::VerifyingKey;
+use proof_system::bellman::rand::{Rand, Rng};
+use std::io::{self, Write};
+
+/// The number of powers of the SRS which are needed for a domain of size `n`: the wire polynomials are blinded up to
+/// degree `n + 1`, the permutation polynomial and the parts of the quotient polynomial up to degree `n + 2`
+pub fn srs_size(n: usize) -> usize {
+    n + 3
+}
+
+pub struct Proof<E: Engine> {
+    /// The commitments to the wire polynomials
+    pub a: E::G1Affine,
+    pub b: E::G1Affine,
+    pub c: E::G1Affine,
+    /// The commitment to the permutation polynomial
+    pub z: E::G1Affine,
+    /// The commitments to the three parts of the quotient polynomial
+    pub t_lo: E::G1Affine,
+    pub t_mid: E::G1Affine,
+    pub t_hi: E::G1Affine,
+    /// The opening proofs at `zeta` and `zeta * omega`
+    pub w_zeta: E::G1Affine,
+    pub w_zeta_omega: E::G1Affine,
+    /// The evaluations at `zeta` of the wire polynomials, of the first two permutation polynomials and of the
+    /// linearisation polynomial, and the evaluation of the permutation polynomial at `zeta * omega`
+    pub a_eval: E::Fr,
+    pub b_eval: E::Fr,
+    pub c_eval: E::Fr,
+    pub s1_eval: E::Fr,
+    pub s2_eval: E::Fr,
+    pub z_omega_eval: E::Fr,
+    pub r_eval: E::Fr,
+}
+
+impl<E: Engine> Proof<E> {
+    pub fn points(&self) -> [&E::G1Affine; 9] {
+        [
+            &self.a,
+            &self.b,
+            &self.c,
+            &self.z,
+            &self.t_lo,
+            &self.t_mid,
+            &self.t_hi,
+            &self.w_zeta,
+            &self.w_zeta_omega,
+        ]
+    }
+
+    pub fn evaluations(&self) -> [&E::Fr; 7] {
+        [
+            &self.a_eval,
+            &self.b_eval,
+            &self.c_eval,
+            &self.s1_eval,
+            &self.s2_eval,
+            &self.z_omega_eval,
+            &self.r_eval,
+        ]
+    }
+
+    pub fn write<W: Write>(&self, mut w: W) -> io::Result<()> {
+        for p in self.points().iter() {
+            w.write_all(p.into_uncompressed().as_ref())?;
+        }
+        for e in self.evaluations().iter() {
+            e.into_repr().write_be(&mut w)?;
+        }
+        Ok(())
+    }
+}
+
+/// A proof that `values`, the values of the variables of the rank-1 constraint system, satisfy the circuit
+pub fn prove<E: Engine, R: Rng>(
+    circuit: &Circuit<E>,
+    polynomials: &Polynomials<E>,
+    srs: &Srs<E>,
+    values: &[E::Fr],
+    rng: &mut R,
+) -> Result<Proof<E>, String> {
+    let domain = &polynomials.domain;
+    let n = domain.size;
+
+    if srs.powers.len() < srs_size(n) {
+        return Err(format!(
+            "The proving key has {} powers but {} are needed",
+            srs.powers.len(),
+            srs_size(n)
+        ));
+    }
+
+    let assignment = circuit.assign(values);
+    let mut transcript = Transcript::<E>::default();
+    transcript.append_scalar(&VerifyingKey::new(circuit, polynomials, srs).digest());
+
+    let public_inputs: Vec<_> = circuit.gates[..circuit.public_inputs]
+        .iter()
+        .map(|gate| assignment[gate.wires[0]])
+        .collect();
+    for x in &public_inputs {
+        transcript.append_scalar(x);
+    }
+
+    // round 1: commit to the wire polynomials
+    let wire_values = circuit.wire_values(&assignment, n);
+    let wires: Vec<_> = wire_values
+        .iter()
+        .map(|values| {
+            let mut p = domain.ifft(values);
+            blind::<E>(&mut p, n, &random::<E, R>(rng, 2));
+            p
+        })
+        .collect();
+    let (a, b, c) = (&wires[0], &wires[1], &wires[2]);
+
+    let a_commitment = srs.commit(a);
+    let b_commitment = srs.commit(b);
+    let c_commitment = srs.commit(c);
+    transcript.append_point(&a_commitment);
+    transcript.append_point(&b_commitment);
+    transcript.append_point(&c_commitment);
+
+    let beta = transcript.challenge();
+    let gamma = transcript.challenge();
+
+    // round 2: commit to the permutation polynomial, which accumulates the ratios of the wires to their permutation
+    let k = coset_shifts::<E>();
+    let mut numerators = vec![E::Fr::one(); n];
+    let mut denominators = vec![E::Fr::one(); n];
+    for i in 0..n {
+        let x = domain.element(i);
+        for j in 0..3 {
+            let mut label = x;
+            label.mul_assign(&k[j]);
+            numerators[i].mul_assign(&permuted(&wire_values[j][i], &beta, &label, &gamma));
+            denominators[i].mul_assign(&permuted(
+                &wire_values[j][i],
+                &beta,
+                &polynomials.sigma_values[j][i],
+                &gamma,
+            ));
+        }
+    }
+    batch_inverse::<E>(&mut denominators);
+
+    let mut z_values = Vec::with_capacity(n);
+    let mut acc = E::Fr::one();
+    for (numerator, denominator) in numerators.iter().zip(denominators) {
+        z_values.push(acc);
+        acc.mul_assign(numerator);
+        acc.mul_assign(&denominator);
+    }
+    debug_assert_eq!(acc, E::Fr::one());
+
+    let mut z = domain.ifft(&z_values);
+    blind::<E>(&mut z, n, &random::<E, R>(rng, 3));
+
+    let z_commitment = srs.commit(&z);
+    transcript.append_point(&z_commitment);
+
+    let alpha = transcript.challenge();
+
+    // round 3: commit to the quotient polynomial, computed over a coset of a domain which is large enough for the
+    // degree `4n + 5` of its numerator
+    let t = quotient(
+        polynomials,
+        &public_inputs,
+        [a, b, c],
+        &z,
+        &alpha,
+        &beta,
+        &gamma,
+    )?;
+
+    let mut t_lo = t[..n + 2].to_vec();
+    let mut t_mid = t[n + 2..2 * n + 4].to_vec();
+    let mut t_hi = t[2 * n + 4..].to_vec();
+
+    // the parts are blinded so that their commitments do not reveal anything, without changing
+    // t_lo + X^(n + 2) * t_mid + X^(2n + 4) * t_hi
+    let blinding = random::<E, R>(rng, 2);
+    t_lo.push(blinding[0]);
+    t_mid[0].sub_assign(&blinding[0]);
+    t_mid.push(blinding[1]);
+    t_hi[0].sub_assign(&blinding[1]);
+
+    let t_lo_commitment = srs.commit(&t_lo);
+    let t_mid_commitment = srs.commit(&t_mid);
+    let t_hi_commitment = srs.commit(&t_hi);
+    transcript.append_point(&t_lo_commitment);
+    transcript.append_point(&t_mid_commitment);
+    transcript.append_point(&t_hi_commitment);
+
+    let zeta = transcript.challenge();
+
+    // round 4: evaluate the polynomials at zeta, and the linearisation polynomial, which replaces the products of
+    // polynomials by products of their evaluations and of polynomials
+    let mut zeta_omega = zeta;
+    zeta_omega.mul_assign(&domain.omega);
+
+    let a_eval = evaluate::<E>(a, &zeta);
+    let b_eval = evaluate::<E>(b, &zeta);
+    let c_eval = evaluate::<E>(c, &zeta);
+    let s1_eval = evaluate::<E>(&polynomials.sigma[0], &zeta);
+    let s2_eval = evaluate::<E>(&polynomials.sigma[1], &zeta);
+    let z_omega_eval = evaluate::<E>(&z, &zeta_omega);
+
+    let scalars = linearisation_scalars::<E>(
+        &[a_eval, b_eval, c_eval, s1_eval, s2_eval, z_omega_eval],
+        &domain.lagrange(1, &zeta)[0],
+        &alpha,
+        &beta,
+        &gamma,
+        &zeta,
+    );
+
+    let mut r = vec![];
+    for (scalar, p) in scalars.iter().zip(&[
+        &polynomials.q_m,
+        &polynomials.q_l,
+        &polynomials.q_r,
+        &polynomials.q_o,
+        &polynomials.q_c,
+        &z,
+        &polynomials.sigma[2],
+    ]) {
+        add_scaled::<E>(&mut r, scalar, p);
+    }
+
+    let r_eval = evaluate::<E>(&r, &zeta);
+
+    for e in &[
+        a_eval,
+        b_eval,
+        c_eval,
+        s1_eval,
+        s2_eval,
+        z_omega_eval,
+        r_eval,
+    ] {
+        transcript.append_scalar(e);
+    }
+
+    let v = transcript.challenge();
+
+    // round 5: open the polynomials at zeta in a single batch, and the permutation polynomial at zeta * omega
+    let mut f = t_lo;
+    let mut power = zeta.pow([(n + 2) as u64]);
+    add_scaled::<E>(&mut f, &power, &t_mid);
+    power.square();
+    add_scaled::<E>(&mut f, &power, &t_hi);
+
+    let mut power = E::Fr::one();
+    for p in &[&r, a, b, c, &polynomials.sigma[0], &polynomials.sigma[1]] {
+        power.mul_assign(&v);
+        add_scaled::<E>(&mut f, &power, p);
+    }
+
+    Ok(Proof {
+        a: a_commitment,
+        b: b_commitment,
+        c: c_commitment,
+        z: z_commitment,
+        t_lo: t_lo_commitment,
+        t_mid: t_mid_commitment,
+        t_hi: t_hi_commitment,
+        w_zeta: srs.commit(&divide_by_linear::<E>(&f, &zeta)),
+        w_zeta_omega: srs.commit(&divide_by_linear::<E>(&z, &zeta_omega)),
+        a_eval,
+        b_eval,
+        c_eval,
+        s1_eval,
+        s2_eval,
+        z_omega_eval,
+        r_eval,
+    })
+}
+
+/// The quotient by `X^n - 1` of
+///
+/// `q_m * a * b + q_l * a + q_r * b + q_o * c + q_c + PI`
+/// `+ alpha * ((a + beta * X + gamma) * (b + beta * k_1 * X + gamma) * (c + beta * k_2 * X + gamma) * z`
+/// `- (a + beta * sigma_1 + gamma) * (b + beta * sigma_2 + gamma) * (c + beta * sigma_3 + gamma) * z(omega * X))`
+/// `+ alpha^2 * (z - 1) * L_1`
+///
+/// which is a polynomial of degree `3n + 5` if and only if the gates and the copy constraints hold
+fn quotient<E: Engine>(
+    polynomials: &Polynomials<E>,
+    public_inputs: &[E::Fr],
+    [a, b, c]: [&Vec<E::Fr>; 3],
+    z: &[E::Fr],
+    alpha: &E::Fr,
+    beta: &E::Fr,
+    gamma: &E::Fr,
+) -> Result<Vec<E::Fr>, String> {
+    let domain = &polynomials.domain;
+    let n = domain.size;
+    let coset = Domain::<E>::new(8 * n)?;
+
+    // the public input polynomial is -x_i at omega^i
+    let mut pi = public_inputs.to_vec();
+    for x in pi.iter_mut() {
+        x.negate();
+    }
+    let pi = domain.ifft(&pi);
+
+    let mut l1 = vec![E::Fr::zero(); n];
+    l1[0] = E::Fr::one();
+    let l1 = domain.ifft(&l1);
+
+    let a = coset.coset_fft(a);
+    let b = coset.coset_fft(b);
+    let c = coset.coset_fft(c);
+    let z = coset.coset_fft(z);
+    let q_m = coset.coset_fft(&polynomials.q_m);
+    let q_l = coset.coset_fft(&polynomials.q_l);
+    let q_r = coset.coset_fft(&polynomials.q_r);
+    let q_o = coset.coset_fft(&polynomials.q_o);
+    let q_c = coset.coset_fft(&polynomials.q_c);
+    let s1 = coset.coset_fft(&polynomials.sigma[0]);
+    let s2 = coset.coset_fft(&polynomials.sigma[1]);
+    let s3 = coset.coset_fft(&polynomials.sigma[2]);
+    let pi = coset.coset_fft(&pi);
+    let l1 = coset.coset_fft(&l1);
+
+    // X^n - 1 over the coset only takes 8 values, as omega^n is an 8th root of unity
+    let g = E::Fr::multiplicative_generator();
+    let mut vanishing: Vec<_> = (0..8)
+        .map(|i| {
+            let mut x = coset.element(i);
+            x.mul_assign(&g);
+            domain.vanishing(&x)
+        })
+        .collect();
+    batch_inverse::<E>(&mut vanishing);
+
+    let k = coset_shifts::<E>();
+    let mut alpha_2 = *alpha;
+    alpha_2.square();
+
+    let mut x = g;
+    let t: Vec<_> = (0..coset.size)
+        .map(|i| {
+            let product = |p: &E::Fr, q: &E::Fr| {
+                let mut r = *p;
+                r.mul_assign(q);
+                r
+            };
+
+            let mut gate = product(&product(&a[i], &b[i]), &q_m[i]);
+            gate.add_assign(&product(&a[i], &q_l[i]));
+            gate.add_assign(&product(&b[i], &q_r[i]));
+            gate.add_assign(&product(&c[i], &q_o[i]));
+            gate.add_assign(&q_c[i]);
+            gate.add_assign(&pi[i]);
+
+            let mut identity = z[i];
+            let mut permutation = z[(i + 8) % coset.size];
+            for (j, (w, s)) in [(&a[i], &s1[i]), (&b[i], &s2[i]), (&c[i], &s3[i])]
+                .iter()
+                .enumerate()
+            {
+                identity.mul_assign(&permuted(w, beta, &product(&k[j], &x), gamma));
+                permutation.mul_assign(&permuted(w, beta, s, gamma));
+            }
+            identity.sub_assign(&permutation);
+            identity.mul_assign(alpha);
+
+            let mut first = z[i];
+            first.sub_assign(&E::Fr::one());
+            first.mul_assign(&l1[i]);
+            first.mul_assign(&alpha_2);
+
+            gate.add_assign(&identity);
+            gate.add_assign(&first);
+            gate.mul_assign(&vanishing[i % 8]);
+
+            x.mul_assign(&coset.omega);
+            gate
+        })
+        .collect();
+
+    let mut t = coset.icoset_fft(&t);
+    if t[3 * n + 6..].iter().any(|c| !c.is_zero()) {
+        return Err(String::from(
+            "The witness does not satisfy the constraints of the program",
+        ));
+    }
+    t.truncate(3 * n + 6);
+
+    Ok(t)
+}
+
+/// The scalars of `q_m`, `q_l`, `q_r`, `q_o`, `q_c`, `z` and `sigma_3` in the linearisation polynomial, given the
+/// evaluations `a`, `b`, `c`, `sigma_1`, `sigma_2` at `zeta` and `z` at `zeta * omega`, and `L_1(zeta)`
+pub fn linearisation_scalars<E: Engine>(
+    [a, b, c, s1, s2, z_omega]: &[E::Fr; 6],
+    l1: &E::Fr,
+    alpha: &E::Fr,
+    beta: &E::Fr,
+    gamma: &E::Fr,
+    zeta: &E::Fr,
+) -> [E::Fr; 7] {
+    let k = coset_shifts::<E>();
+
+    let mut ab = *a;
+    ab.mul_assign(b);
+
+    // alpha * (a + beta * zeta + gamma) * (b + beta * k_1 * zeta + gamma) * (c + beta * k_2 * zeta + gamma)
+    // + alpha^2 * L_1(zeta)
+    let mut z = *alpha;
+    for (j, w) in [a, b, c].iter().enumerate() {
+        let mut label = *zeta;
+        label.mul_assign(&k[j]);
+        z.mul_assign(&permuted(w, beta, &label, gamma));
+    }
+    let mut first = *alpha;
+    first.square();
+    first.mul_assign(l1);
+    z.add_assign(&first);
+
+    // -alpha * beta * z(zeta * omega) * (a + beta * sigma_1 + gamma) * (b + beta * sigma_2 + gamma)
+    let mut s3 = *alpha;
+    s3.mul_assign(beta);
+    s3.mul_assign(z_omega);
+    s3.mul_assign(&permuted(a, beta, s1, gamma));
+    s3.mul_assign(&permuted(b, beta, s2, gamma));
+    s3.negate();
+
+    [ab, *a, *b, *c, E::Fr::one(), z, s3]
+}
+
+/// `w + beta * label + gamma`
+pub fn permuted<F: Field>(w: &F, beta: &F, label: &F, gamma: &F) -> F {
+    let mut result = *beta;
+    result.mul_assign(label);
+    result.add_assign(w);
+    result.add_assign(gamma);
+    result
+}
+
+fn random<E: Engine, R: Rng>(rng: &mut R, count: usize) -> Vec<E::Fr> {
+    (0..count).map(|_| E::Fr::rand(rng)).collect()
+}
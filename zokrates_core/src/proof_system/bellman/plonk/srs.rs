@@ -0,0 +1,121 @@
+//! The universal structured reference string (SRS) of PLONK, which is the same for all circuits up to a given size
+
+use bellman::pairing::ff::{Field, PrimeField};
+use bellman::pairing::{CurveAffine, CurveProjective, Engine, Wnaf};
+use proof_system::bellman::group::{multiexp, read_point, read_points, write_points};
+use proof_system::bellman::rand::{Rand, Rng};
+use std::io::{self, Read, Write};
+
+/// The powers of a secret `tau` in G1 and `tau` in G2. Commitments to polynomials with at most as many coefficients as
+/// there are powers can be computed and opened.
+pub struct Srs<E: Engine> {
+    /// `tau^i` in G1 for `i` from zero
+    pub powers: Vec<E::G1Affine>,
+    /// `tau` in G2
+    pub tau_g2: E::G2Affine,
+}
+
+impl<E: Engine> Srs<E> {
+    /// A fresh SRS with `size` powers. Whoever knows `tau` can forge proofs, so it must be discarded.
+    pub fn generate<R: Rng>(size: usize, rng: &mut R) -> Self {
+        let tau = E::Fr::rand(rng);
+
+        let mut wnaf = Wnaf::new();
+        let mut g1 = wnaf.base(E::G1::one(), size);
+
+        let mut power = E::Fr::one();
+        let mut powers: Vec<E::G1> = (0..size)
+            .map(|_| {
+                let p = g1.scalar(power.into_repr());
+                power.mul_assign(&tau);
+                p
+            })
+            .collect();
+
+        E::G1::batch_normalization(&mut powers);
+
+        Srs {
+            powers: powers.into_iter().map(|p| p.into_affine()).collect(),
+            tau_g2: E::G2Affine::one().mul(tau.into_repr()).into_affine(),
+        }
+    }
+
+    /// The first `size` powers, which are enough for the proving key of a circuit
+    pub fn truncate(mut self, size: usize) -> Result<Self, String> {
+        if self.powers.len() < size {
+            return Err(format!(
+                "The universal setup has {} powers but {} are needed",
+                self.powers.len(),
+                size
+            ));
+        }
+        self.powers.truncate(size);
+        Ok(self)
+    }
+
+    /// The commitment `p(tau)` in G1 to the polynomial with coefficients `coeffs`
+    pub fn commit(&self, coeffs: &[E::Fr]) -> E::G1Affine {
+        assert!(coeffs.len() <= self.powers.len());
+
+        let scalars: Vec<_> = coeffs.iter().map(|c| c.into_repr()).collect();
+        multiexp(&self.powers[..coeffs.len()], &scalars).into_affine()
+    }
+
+    /// Checks that the powers are successive powers of the same `tau` as the one in G2, by comparing random linear
+    /// combinations of them: `e(sum(r_i * tau^(i + 1)), 1) = e(sum(r_i * tau^i), tau)`
+    pub fn is_valid<R: Rng>(&self, rng: &mut R) -> bool {
+        if self.powers.is_empty() || self.powers[0] != E::G1Affine::one() {
+            return false;
+        }
+
+        let scalars: Vec<_> = (1..self.powers.len())
+            .map(|_| E::Fr::rand(rng).into_repr())
+            .collect();
+
+        let shifted = multiexp(&self.powers[1..], &scalars).into_affine();
+        let unshifted = multiexp(&self.powers[..self.powers.len() - 1], &scalars).into_affine();
+
+        E::pairing(shifted, E::G2Affine::one()) == E::pairing(unshifted, self.tau_g2)
+    }
+
+    pub fn write<W: Write>(&self, mut w: W) -> io::Result<()> {
+        write_points(&mut w, &self.powers)?;
+        w.write_all(self.tau_g2.into_uncompressed().as_ref())
+    }
+
+    pub fn read<R: Read>(mut r: R) -> io::Result<Self> {
+        let powers = read_points(&mut r)?;
+        let tau_g2 = read_point(&mut r)?;
+
+        Ok(Srs { powers, tau_g2 })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bellman::pairing::bn256::Bn256;
+    use proof_system::bellman::rand::{SeedableRng, XorShiftRng};
+
+    fn rng() -> XorShiftRng {
+        XorShiftRng::from_seed([0x5dbe6259, 0x8d313d76, 0x3237db17, 0xe5bc0654])
+    }
+
+    #[test]
+    fn serialize_and_validate() {
+        let rng = &mut rng();
+        let srs = Srs::<Bn256>::generate(8, rng);
+        assert!(srs.is_valid(rng));
+
+        let mut bytes = vec![];
+        srs.write(&mut bytes).unwrap();
+        let srs = Srs::<Bn256>::read(&bytes[..]).unwrap();
+        assert_eq!(srs.powers.len(), 8);
+        assert!(srs.is_valid(rng));
+
+        // powers which are not successive powers of the same tau are rejected
+        let mut invalid = Srs::<Bn256>::read(&bytes[..]).unwrap();
+        invalid.powers.swap(2, 3);
+        assert!(!invalid.is_valid(rng));
+    }
+}
@@ -0,0 +1,90 @@
+//! The Fiat-Shamir transcript of PLONK, which derives the challenges of the verifier from the messages of the prover
+
+use bellman::pairing::ff::{Field, PrimeField, PrimeFieldRepr};
+use bellman::pairing::{CurveAffine, Engine};
+use proof_system::bellman::domain::scalar;
+use sha2::{Digest, Sha256};
+
+/// Each challenge is the SHA256 hash of the previous challenge and of the messages sent since then, reduced modulo the
+/// order of the scalar field. Scalars are encoded as 32-byte big-endian integers and points as their two coordinates,
+/// with `(0, 0)` for infinity, so that the Solidity verifier can recompute the challenges with `abi.encodePacked`.
+pub struct Transcript<E: Engine> {
+    bytes: Vec<u8>,
+    _engine: std::marker::PhantomData<E>,
+}
+
+impl<E: Engine> Default for Transcript<E> {
+    fn default() -> Self {
+        Transcript {
+            bytes: vec![],
+            _engine: std::marker::PhantomData,
+        }
+    }
+}
+
+impl<E: Engine> Transcript<E> {
+    pub fn append_scalar(&mut self, s: &E::Fr) {
+        s.into_repr().write_be(&mut self.bytes).unwrap();
+    }
+
+    pub fn append_point(&mut self, p: &E::G1Affine) {
+        let (x, y) = match p.is_zero() {
+            true => (E::Fq::zero(), E::Fq::zero()),
+            false => p.into_xy_unchecked(),
+        };
+        x.into_repr().write_be(&mut self.bytes).unwrap();
+        y.into_repr().write_be(&mut self.bytes).unwrap();
+    }
+
+    pub fn challenge(&mut self) -> E::Fr {
+        let digest = Sha256::digest(&self.bytes);
+
+        let base = scalar::<E>(256);
+        let challenge = digest.iter().fold(E::Fr::zero(), |mut acc, byte| {
+            acc.mul_assign(&base);
+            acc.add_assign(&scalar::<E>(*byte as u64));
+            acc
+        });
+
+        self.bytes.clear();
+        self.append_scalar(&challenge);
+
+        challenge
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bellman::pairing::bn256::{Bn256, Fr, G1Affine};
+
+    #[test]
+    fn challenges_depend_on_messages() {
+        let challenges = |s: u64| {
+            let mut transcript = Transcript::<Bn256>::default();
+            transcript.append_scalar(&scalar::<Bn256>(s));
+            transcript.append_point(&G1Affine::one());
+            let first = transcript.challenge();
+            (first, transcript.challenge())
+        };
+
+        assert_eq!(challenges(1), challenges(1));
+        assert_ne!(challenges(1).0, challenges(2).0);
+        assert_ne!(challenges(1).0, challenges(1).1);
+    }
+
+    #[test]
+    fn encoding() {
+        let mut transcript = Transcript::<Bn256>::default();
+        transcript.append_scalar(&Fr::one());
+        transcript.append_point(&G1Affine::zero());
+        transcript.append_point(&G1Affine::one());
+
+        let mut expected = vec![0; 32 * 5];
+        expected[31] = 1;
+        // the generator of G1 is (1, 2)
+        expected[32 * 4 - 1] = 1;
+        expected[32 * 5 - 1] = 2;
+        assert_eq!(transcript.bytes, expected);
+    }
+}
@@ -0,0 +1,297 @@
+//! The PLONK verifier, which checks a proof with a single pairing equation
+
+use bellman::pairing::ff::{Field, PrimeField};
+use bellman::pairing::{CurveAffine, CurveProjective, Engine};
+use proof_system::bellman::domain::{scalar, Domain};
+use proof_system::bellman::group::multiexp;
+use proof_system::bellman::plonk::circuit::{coset_shifts, Circuit, Polynomials};
+use proof_system::bellman::plonk::prover::{linearisation_scalars, permuted, Proof};
+use proof_system::bellman::plonk::srs::Srs;
+use proof_system::bellman::plonk::transcript::Transcript;
+
+pub struct VerifyingKey<E: Engine> {
+    pub domain_size: usize,
+    pub public_inputs: usize,
+    /// The commitments to the selector polynomials
+    pub q_m: E::G1Affine,
+    pub q_l: E::G1Affine,
+    pub q_r: E::G1Affine,
+    pub q_o: E::G1Affine,
+    pub q_c: E::G1Affine,
+    /// The commitments to the permutation polynomials
+    pub s1: E::G1Affine,
+    pub s2: E::G1Affine,
+    pub s3: E::G1Affine,
+    /// `tau` in G2
+    pub tau_g2: E::G2Affine,
+}
+
+impl<E: Engine> VerifyingKey<E> {
+    pub fn new(circuit: &Circuit<E>, polynomials: &Polynomials<E>, srs: &Srs<E>) -> Self {
+        VerifyingKey {
+            domain_size: polynomials.domain.size,
+            public_inputs: circuit.public_inputs,
+            q_m: srs.commit(&polynomials.q_m),
+            q_l: srs.commit(&polynomials.q_l),
+            q_r: srs.commit(&polynomials.q_r),
+            q_o: srs.commit(&polynomials.q_o),
+            q_c: srs.commit(&polynomials.q_c),
+            s1: srs.commit(&polynomials.sigma[0]),
+            s2: srs.commit(&polynomials.sigma[1]),
+            s3: srs.commit(&polynomials.sigma[2]),
+            tau_g2: srs.tau_g2,
+        }
+    }
+
+    /// The digest of the circuit, which is absorbed first by the transcript so that the challenges depend on the
+    /// circuit: the size of the domain, the shifts of its cosets and the commitments to the selector and permutation
+    /// polynomials
+    pub fn digest(&self) -> E::Fr {
+        let mut transcript = Transcript::<E>::default();
+        transcript.append_scalar(&scalar::<E>(self.domain_size as u64));
+        for k in coset_shifts::<E>()[1..].iter() {
+            transcript.append_scalar(k);
+        }
+        for p in [
+            &self.q_m, &self.q_l, &self.q_r, &self.q_o, &self.q_c, &self.s1, &self.s2, &self.s3,
+        ]
+        .iter()
+        {
+            transcript.append_point(p);
+        }
+        transcript.challenge()
+    }
+}
+
+pub fn verify<E: Engine>(vk: &VerifyingKey<E>, proof: &Proof<E>, public_inputs: &[E::Fr]) -> bool {
+    if public_inputs.len() != vk.public_inputs {
+        return false;
+    }
+
+    let domain = match Domain::<E>::new(vk.domain_size) {
+        Ok(domain) => domain,
+        Err(_) => return false,
+    };
+    let n = domain.size;
+    if n != vk.domain_size || n < 2 {
+        return false;
+    }
+
+    // replay the transcript of the prover
+    let mut transcript = Transcript::<E>::default();
+    transcript.append_scalar(&vk.digest());
+    for x in public_inputs {
+        transcript.append_scalar(x);
+    }
+    transcript.append_point(&proof.a);
+    transcript.append_point(&proof.b);
+    transcript.append_point(&proof.c);
+    let beta = transcript.challenge();
+    let gamma = transcript.challenge();
+    transcript.append_point(&proof.z);
+    let alpha = transcript.challenge();
+    transcript.append_point(&proof.t_lo);
+    transcript.append_point(&proof.t_mid);
+    transcript.append_point(&proof.t_hi);
+    let zeta = transcript.challenge();
+    for e in proof.evaluations().iter() {
+        transcript.append_scalar(e);
+    }
+    let v = transcript.challenge();
+    transcript.append_point(&proof.w_zeta);
+    transcript.append_point(&proof.w_zeta_omega);
+    let u = transcript.challenge();
+
+    let vanishing = domain.vanishing(&zeta);
+    if vanishing.is_zero() {
+        return false;
+    }
+
+    // L_1(zeta) and PI(zeta) = -sum(x_i * L_i(zeta))
+    let lagrange = domain.lagrange(std::cmp::max(public_inputs.len(), 1), &zeta);
+    let l1 = lagrange[0];
+    let mut pi = E::Fr::zero();
+    for (x, l) in public_inputs.iter().zip(&lagrange) {
+        let mut term = *x;
+        term.mul_assign(l);
+        pi.sub_assign(&term);
+    }
+
+    // the evaluation of the quotient polynomial at zeta, from the evaluation of the linearisation polynomial:
+    // t(zeta) = (r(zeta) + PI(zeta) - alpha^2 * L_1(zeta)
+    // - alpha * (a + beta * sigma_1 + gamma) * (b + beta * sigma_2 + gamma) * (c + gamma) * z(zeta * omega))
+    // / (zeta^n - 1)
+    let mut permutation = alpha;
+    permutation.mul_assign(&proof.z_omega_eval);
+    permutation.mul_assign(&permuted(&proof.a_eval, &beta, &proof.s1_eval, &gamma));
+    permutation.mul_assign(&permuted(&proof.b_eval, &beta, &proof.s2_eval, &gamma));
+    let mut c = proof.c_eval;
+    c.add_assign(&gamma);
+    permutation.mul_assign(&c);
+
+    let mut first = alpha;
+    first.square();
+    first.mul_assign(&l1);
+
+    let mut t_eval = proof.r_eval;
+    t_eval.add_assign(&pi);
+    t_eval.sub_assign(&permutation);
+    t_eval.sub_assign(&first);
+    t_eval.mul_assign(&vanishing.inverse().unwrap());
+
+    // the commitment [F] to the polynomial which is opened at zeta, where the commitment to the linearisation
+    // polynomial is a combination of the commitments of the verifying key and to z, and the value E it opens to
+    let r_scalars = linearisation_scalars::<E>(
+        &[
+            proof.a_eval,
+            proof.b_eval,
+            proof.c_eval,
+            proof.s1_eval,
+            proof.s2_eval,
+            proof.z_omega_eval,
+        ],
+        &l1,
+        &alpha,
+        &beta,
+        &gamma,
+        &zeta,
+    );
+
+    let mut zeta_omega = zeta;
+    zeta_omega.mul_assign(&domain.omega);
+
+    let mut bases = vec![vk.q_m, vk.q_l, vk.q_r, vk.q_o, vk.q_c, proof.z, vk.s3];
+    let mut scalars: Vec<_> = r_scalars
+        .iter()
+        .map(|s| {
+            let mut s = *s;
+            s.mul_assign(&v);
+            s
+        })
+        .collect();
+    // the opening of z at zeta * omega is batched with a factor u
+    scalars[5].add_assign(&u);
+
+    let mut zeta_n = zeta.pow([(n + 2) as u64]);
+    bases.extend(&[proof.t_lo, proof.t_mid, proof.t_hi]);
+    scalars.push(E::Fr::one());
+    scalars.push(zeta_n);
+    zeta_n.square();
+    scalars.push(zeta_n);
+
+    let mut e = t_eval;
+    let mut power = E::Fr::one();
+    for (eval, base) in [
+        (&proof.r_eval, None),
+        (&proof.a_eval, Some(proof.a)),
+        (&proof.b_eval, Some(proof.b)),
+        (&proof.c_eval, Some(proof.c)),
+        (&proof.s1_eval, Some(vk.s1)),
+        (&proof.s2_eval, Some(vk.s2)),
+    ]
+    .iter()
+    {
+        power.mul_assign(&v);
+        let mut term = **eval;
+        term.mul_assign(&power);
+        e.add_assign(&term);
+
+        if let Some(base) = base {
+            bases.push(*base);
+            scalars.push(power);
+        }
+    }
+
+    let mut z_omega = proof.z_omega_eval;
+    z_omega.mul_assign(&u);
+    e.add_assign(&z_omega);
+    e.negate();
+
+    // e(W_zeta + u * W_zeta_omega, tau) = e(zeta * W_zeta + u * zeta * omega * W_zeta_omega + F - E, 1)
+    let mut u_zeta_omega = u;
+    u_zeta_omega.mul_assign(&zeta_omega);
+    bases.extend(&[proof.w_zeta, proof.w_zeta_omega, E::G1Affine::one()]);
+    scalars.extend(&[zeta, u_zeta_omega, e]);
+
+    let scalars: Vec<_> = scalars.iter().map(|s| s.into_repr()).collect();
+    let mut right = multiexp(&bases, &scalars);
+    right.negate();
+
+    let mut left = proof.w_zeta.into_projective();
+    left.add_assign(&proof.w_zeta_omega.mul(u.into_repr()));
+
+    E::final_exponentiation(&E::miller_loop(&[
+        (&left.into_affine().prepare(), &vk.tau_g2.prepare()),
+        (
+            &right.into_affine().prepare(),
+            &E::G2Affine::one().prepare(),
+        ),
+    ]))
+    .map(|r| r == E::Fqk::one())
+    .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bellman::pairing::bn256::{Bn256, Fr};
+    use proof_system::bellman::domain::scalar;
+    use proof_system::bellman::plonk::circuit::LinearCombination;
+    use proof_system::bellman::plonk::prover::{prove, srs_size};
+    use proof_system::bellman::rand::{SeedableRng, XorShiftRng};
+
+    fn lc(terms: &[(usize, u64)], constant: u64) -> LinearCombination<Bn256> {
+        LinearCombination {
+            terms: terms
+                .iter()
+                .map(|(x, k)| (*x, scalar::<Bn256>(*k)))
+                .collect(),
+            constant: scalar::<Bn256>(constant),
+        }
+    }
+
+    #[test]
+    fn prove_and_verify() {
+        let rng = &mut XorShiftRng::from_seed([0x5dbe6259, 0x8d313d76, 0x3237db17, 0xe5bc0654]);
+
+        // (2 * x_0 + 3 * x_1 + x_2 + 1) * x_3 = 4 * x_4 + 5 and x_0 * 7 = x_1 + 2, with x_3 and x_4 public
+        let mut circuit = Circuit::new(&[3, 4]);
+        circuit.constraint(
+            lc(&[(0, 2), (1, 3), (2, 1)], 1),
+            lc(&[(3, 1)], 0),
+            lc(&[(4, 4)], 5),
+        );
+        circuit.constraint(lc(&[(0, 1)], 0), lc(&[], 7), lc(&[(1, 1)], 2));
+
+        let polynomials = circuit.polynomials().unwrap();
+        let srs = Srs::<Bn256>::generate(srs_size(polynomials.domain.size), rng);
+        let vk = VerifyingKey::new(&circuit, &polynomials, &srs);
+
+        let values: Vec<Fr> = [1, 5, 1, 3, 13]
+            .iter()
+            .map(|v| scalar::<Bn256>(*v))
+            .collect();
+        let proof = prove(&circuit, &polynomials, &srs, &values, rng).unwrap();
+
+        assert!(verify(&vk, &proof, &[values[3], values[4]]));
+        assert!(!verify(&vk, &proof, &[values[3], values[3]]));
+        assert!(!verify(&vk, &proof, &[values[3]]));
+
+        let mut tampered = proof;
+        tampered.r_eval.add_assign(&Fr::one());
+        assert!(!verify(&vk, &tampered, &[values[3], values[4]]));
+
+        // the challenges depend on the circuit
+        let mut other = VerifyingKey::new(&circuit, &polynomials, &srs);
+        assert_eq!(other.digest(), vk.digest());
+        other.q_c = other.s3;
+        assert_ne!(other.digest(), vk.digest());
+
+        // a witness which does not satisfy the constraints cannot be proven
+        let values: Vec<Fr> = [1, 5, 1, 3, 14]
+            .iter()
+            .map(|v| scalar::<Bn256>(*v))
+            .collect();
+        assert!(prove(&circuit, &polynomials, &srs, &values, rng).is_err());
+    }
+}
@@ -4,10 +4,22 @@ pub mod pghr13;
 
 use flat_absy::FlatVariable;
 use ir::{self, Statement};
+use proof_system::Randomness;
 use std::cmp::max;
 use std::collections::HashMap;
 use zokrates_field::Field;
 
+// libsnark draws its randomness from the system, which cannot be seeded from here
+fn check_randomness(randomness: Randomness) -> Result<(), String> {
+    match randomness {
+        Randomness::InsecureSeed(_) => Err(String::from(
+            "The libsnark backend does not support seeded randomness",
+        )),
+        // libsnark is not built for wasm, the only platform where entropy has to come from the caller
+        Randomness::System | Randomness::Entropy(_) => Ok(()),
+    }
+}
+
 // utility function. Converts a Field's vector-based byte representation to fixed size array.
 fn vec_as_u8_32_array(vec: &Vec<u8>) -> [u8; 32] {
     assert!(vec.len() <= 32);
@@ -249,7 +261,7 @@ pub fn r1cs_program<T: Field>(
     // first pass through statements to populate `variables`
     for (quad, lin) in main.statements.iter().filter_map(|s| match s {
         Statement::Constraint(quad, lin) => Some((quad, lin)),
-        Statement::Directive(..) => None,
+        _ => None,
     }) {
         for (k, _) in &quad.left.0 {
             provide_variable_idx(&mut variables, &k);
@@ -269,7 +281,7 @@ pub fn r1cs_program<T: Field>(
     // second pass to convert program to raw sparse vectors
     for (quad, lin) in main.statements.into_iter().filter_map(|s| match s {
         Statement::Constraint(quad, lin) => Some((quad, lin)),
-        Statement::Directive(..) => None,
+        _ => None,
     }) {
         a.push(
             quad.left
@@ -1,10 +1,12 @@
 use ir;
 use proof_system::libsnark::ffi::{Buffer, ProofResult, SetupResult};
-use proof_system::libsnark::{prepare_generate_proof, prepare_public_inputs, prepare_setup};
+use proof_system::libsnark::{
+    check_randomness, prepare_generate_proof, prepare_public_inputs, prepare_setup,
+};
 use proof_system::solidity::{
     SOLIDITY_G2_ADDITION_LIB, SOLIDITY_PAIRING_LIB, SOLIDITY_PAIRING_LIB_V2,
 };
-use proof_system::{G1Affine, G2Affine, Proof, ProofSystem, SetupKeypair, SolidityAbi};
+use proof_system::{G1Affine, G2Affine, Proof, ProofSystem, Randomness, SetupKeypair, SolidityAbi};
 use regex::Regex;
 
 use zokrates_field::Bn128Field;
@@ -12,7 +14,7 @@ use zokrates_field::Field;
 
 pub struct PGHR13 {}
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Clone)]
 pub struct VerificationKey {
     a: G2Affine,
     b: G1Affine,
@@ -70,7 +72,12 @@ impl ProofSystem<Bn128Field> for PGHR13 {
     type VerificationKey = VerificationKey;
     type ProofPoints = ProofPoints;
 
-    fn setup(program: ir::Prog<Bn128Field>) -> SetupKeypair<VerificationKey> {
+    fn setup(
+        program: ir::Prog<Bn128Field>,
+        randomness: Randomness,
+    ) -> Result<SetupKeypair<VerificationKey>, String> {
+        check_randomness(randomness)?;
+
         let (a_arr, b_arr, c_arr, a_vec, b_vec, c_vec, num_constraints, num_variables, num_inputs) =
             prepare_setup(program);
 
@@ -101,14 +108,17 @@ impl ProofSystem<Bn128Field> for PGHR13 {
         };
 
         let vk = serde_json::from_str(String::from_utf8(keypair.0).unwrap().as_str()).unwrap();
-        SetupKeypair::new(vk, keypair.1)
+        Ok(SetupKeypair::new(vk, keypair.1))
     }
 
     fn generate_proof(
         program: ir::Prog<Bn128Field>,
         witness: ir::Witness<Bn128Field>,
         proving_key: Vec<u8>,
-    ) -> Proof<ProofPoints> {
+        randomness: Randomness,
+    ) -> Result<Proof<ProofPoints>, String> {
+        check_randomness(randomness)?;
+
         let (public_inputs_arr, public_inputs_length, private_inputs_arr, private_inputs_length) =
             prepare_generate_proof(program, witness);
 
@@ -136,7 +146,7 @@ impl ProofSystem<Bn128Field> for PGHR13 {
             proof
         };
 
-        serde_json::from_str(String::from_utf8(proof).unwrap().as_str()).unwrap()
+        Ok(serde_json::from_str(String::from_utf8(proof).unwrap().as_str()).unwrap())
     }
 
     fn export_solidity_verifier(vk: VerificationKey, abi: SolidityAbi) -> String {
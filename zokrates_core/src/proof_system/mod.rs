@@ -38,6 +38,19 @@ impl SolidityAbi {
     }
 }
 
+/// The source of the randomness of setups and proofs
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Randomness<'a> {
+    /// Randomness from the operating system
+    System,
+    /// Randomness derived from a seed, so that keys and proofs are reproducible. Anyone who knows the seed can forge
+    /// proofs, so this is only meant for tests
+    InsecureSeed(&'a [u8]),
+    /// Randomness derived from at least 32 bytes of secret entropy gathered by the caller, for platforms such as wasm
+    /// where the system cannot be reached. It must never be reused
+    Entropy(&'a [u8]),
+}
+
 #[derive(Serialize, Deserialize)]
 pub struct Proof<T> {
     proof: T,
@@ -51,10 +64,10 @@ impl<T: Serialize + DeserializeOwned> Proof<T> {
     }
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Clone)]
 pub struct G1Affine(String, String);
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Clone)]
 pub struct G2Affine(G1Affine, G1Affine);
 
 impl ToString for G1Affine {
@@ -71,21 +84,32 @@ impl ToString for G2Affine {
 
 pub trait ProofSystem<T: Field>
 where
-    Self::VerificationKey: Serialize + DeserializeOwned,
+    Self::VerificationKey: Serialize + DeserializeOwned + Clone,
     Self::ProofPoints: Serialize + DeserializeOwned,
 {
     type VerificationKey;
     type ProofPoints;
 
-    fn setup(program: ir::Prog<T>) -> SetupKeypair<Self::VerificationKey>;
+    /// Fails if the backend cannot use the source of `randomness`
+    fn setup(
+        program: ir::Prog<T>,
+        randomness: Randomness,
+    ) -> Result<SetupKeypair<Self::VerificationKey>, String>;
 
+    /// Fails if the backend cannot use the source of `randomness`
     fn generate_proof(
         program: ir::Prog<T>,
         witness: ir::Witness<T>,
         proving_key: Vec<u8>,
-    ) -> Proof<Self::ProofPoints>;
+        randomness: Randomness,
+    ) -> Result<Proof<Self::ProofPoints>, String>;
 
     fn export_solidity_verifier(vk: Self::VerificationKey, abi: SolidityAbi) -> String;
 
     fn verify(vk: Self::VerificationKey, proof: Proof<Self::ProofPoints>) -> bool;
+
+    /// Verifies many proofs against the same verification key, returning `true` only if all of them are valid
+    fn verify_batch(vk: Self::VerificationKey, proofs: Vec<Proof<Self::ProofPoints>>) -> bool {
+        proofs.into_iter().all(|proof| Self::verify(vk.clone(), proof))
+    }
 }
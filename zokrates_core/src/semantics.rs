@@ -8,22 +8,30 @@ use crate::absy::Identifier;
 use crate::absy::*;
 use crate::typed_absy::*;
 use crate::typed_absy::{Parameter, Variable};
+use std::cell::RefCell;
 use std::collections::{hash_map::Entry, BTreeSet, HashMap, HashSet};
+use std::convert::TryFrom;
 use std::fmt;
 use std::path::PathBuf;
+use std::rc::Rc;
 use zokrates_field::Field;
 
+use crate::diagnostics::ErrorCode;
 use crate::parser::Position;
 
-use crate::absy::types::{UnresolvedSignature, UnresolvedType, UserTypeId};
+use crate::absy::types::{ArraySize, UnresolvedSignature, UnresolvedType, UserTypeId};
+use crate::source_map::{self, Origin, SourceMetadata};
+use crate::static_analysis::Propagator;
+use crate::symbols::{Location, Reference, SymbolIndex, Target};
 use crate::typed_absy::types::{FunctionKey, Signature, Type};
 
 use std::hash::{Hash, Hasher};
 use typed_absy::types::{ArrayType, StructMember};
 
-#[derive(PartialEq, Debug)]
+#[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
 pub struct ErrorInner {
     pos: Option<(Position, Position)>,
+    code: ErrorCode,
     message: String,
 }
 
@@ -33,7 +41,27 @@ pub struct Error {
     pub module_id: PathBuf,
 }
 
+/// A problem which does not prevent the program from compiling. Warnings are represented like errors, so that they
+/// can be turned into errors
+#[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
+pub struct Warning {
+    pub inner: ErrorInner,
+    pub module_id: PathBuf,
+}
+
 impl ErrorInner {
+    pub fn pos(&self) -> Option<(Position, Position)> {
+        self.pos
+    }
+
+    pub fn code(&self) -> ErrorCode {
+        self.code
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
     fn in_file(self, id: &ModuleId) -> Error {
         Error {
             inner: self,
@@ -44,6 +72,43 @@ impl ErrorInner {
 
 type TypeMap = HashMap<ModuleId, HashMap<UserTypeId, Type>>;
 
+/// A checked module with what the modules importing it need to know about it, so that it can be reused without being
+/// parsed and checked again
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[serde(bound(serialize = "T: Field", deserialize = "'de: 'ast, T: Field"))]
+pub struct CheckedModule<'ast, T> {
+    pub module: TypedModule<'ast, T>,
+    /// The types declared or imported in the module
+    pub types: HashMap<UserTypeId, Type>,
+    /// The constants declared or imported in the module
+    pub constants: HashMap<String, TypedExpression<'ast, T>>,
+    /// The types of the module which are aliases, so that their names can be kept in ABIs
+    pub aliases: BTreeSet<UserTypeId>,
+}
+
+pub type CheckedModules<'ast, T> = HashMap<ModuleId, CheckedModule<'ast, T>>;
+
+/// What the program uses, shared by the checkers of all its modules and generic instances to find unused symbols
+#[derive(Debug, Default)]
+struct Usage {
+    /// The symbols used in each module, with the function using them if any
+    uses: HashSet<(ModuleId, Option<String>, String)>,
+    /// The symbols imported in each module with where they are imported, and the module and name they refer to
+    /// unless they are embedded
+    imports: Vec<(
+        ModuleId,
+        String,
+        (Position, Position),
+        Option<(ModuleId, String)>,
+    )>,
+    /// The functions declared in each module with where they are declared
+    functions: Vec<(ModuleId, String, (Position, Position))>,
+    /// The main module, the only one whose warnings are collected: those of imported modules, such as the ones of the
+    /// standard library, cannot be fixed in the program
+    main: ModuleId,
+    warnings: Vec<Warning>,
+}
+
 /// The global state of the program during semantic checks
 #[derive(Debug)]
 struct State<'ast, T: Field> {
@@ -53,13 +118,37 @@ struct State<'ast, T: Field> {
     typed_modules: TypedModules<'ast, T>,
     /// The user-defined types, which we keep track at this phase only. In later phases, we rely only on basic types and combinations thereof
     types: TypeMap,
+    /// The constants of the checked modules. They are inlined where they are used, so later phases do not see them
+    constants: HashMap<ModuleId, HashMap<String, TypedExpression<'ast, T>>>,
+    /// The generic functions of the checked modules. They are instantiated when they are called, so later phases only see their instances
+    generics: HashMap<ModuleId, GenericFunctions<'ast, T>>,
+}
+
+type GenericFunctions<'ast, T> = HashMap<Identifier<'ast>, Rc<GenericFunction<'ast, T>>>;
+
+/// A function with generic parameters, which is checked once for each assignment of its generic parameters it is called with
+#[derive(Debug)]
+struct GenericFunction<'ast, T> {
+    /// The module in which the function is defined
+    module_id: ModuleId,
+    /// The identifier of the function in that module
+    id: Identifier<'ast>,
+    function: FunctionNode<'ast, T>,
+    /// The functions in scope at the definition of the function
+    functions: HashSet<FunctionKey<'ast>>,
+    /// The generic functions in scope at the definition of the function
+    generics: GenericFunctions<'ast, T>,
+    /// The constants in scope at the definition of the function
+    constants: HashMap<String, TypedExpression<'ast, T>>,
 }
 
-/// A symbol for a given name: either a type or a group of functions. Not both!
+/// A symbol for a given name: either a constant, a type or a group of functions. Not several of them!
 #[derive(PartialEq, Hash, Eq, Debug)]
 enum SymbolType {
+    Constant,
     Type,
     Functions(BTreeSet<Signature>),
+    GenericFunction,
 }
 
 /// A data structure to keep track of all symbols in a module
@@ -82,14 +171,38 @@ impl SymbolUnifier {
         }
     }
 
+    fn insert_constant<S: Into<String>>(&mut self, id: S) -> bool {
+        let s_type = self.symbols.entry(id.into());
+        match s_type {
+            // if anything is already called `id`, we cannot introduce this constant
+            Entry::Occupied(..) => false,
+            Entry::Vacant(v) => {
+                v.insert(SymbolType::Constant);
+                true
+            }
+        }
+    }
+
+    fn insert_generic_function<S: Into<String>>(&mut self, id: S) -> bool {
+        let s_type = self.symbols.entry(id.into());
+        match s_type {
+            // generic functions cannot be overloaded, so nothing else can be called `id`
+            Entry::Occupied(..) => false,
+            Entry::Vacant(v) => {
+                v.insert(SymbolType::GenericFunction);
+                true
+            }
+        }
+    }
+
     fn insert_function<S: Into<String>>(&mut self, id: S, signature: Signature) -> bool {
         let s_type = self.symbols.entry(id.into());
         match s_type {
             // if anything is already called `id`, it depends what it is
             Entry::Occupied(mut o) => {
                 match o.get_mut() {
-                    // if it's a Type, then we can't introduce a function
-                    SymbolType::Type => false,
+                    // if it's a Constant, a Type or a generic function, then we can't introduce a function
+                    SymbolType::Constant | SymbolType::Type | SymbolType::GenericFunction => false,
                     // if it's a Function, we can introduce a new function only if it has a different signature
                     SymbolType::Functions(signatures) => signatures.insert(signature),
                 }
@@ -109,6 +222,8 @@ impl<'ast, T: Field> State<'ast, T> {
             modules,
             typed_modules: HashMap::new(),
             types: HashMap::new(),
+            constants: HashMap::new(),
+            generics: HashMap::new(),
         }
     }
 }
@@ -222,36 +337,313 @@ impl<'ast> Hash for ScopedVariable<'ast> {
 impl<'ast> Eq for ScopedVariable<'ast> {}
 
 /// Checker checks the semantics of a program, keeping track of functions and variables in scope
-pub struct Checker<'ast> {
+pub struct Checker<'ast, T> {
     scope: HashSet<ScopedVariable<'ast>>,
     functions: HashSet<FunctionKey<'ast>>,
     level: usize,
+    /// The biggest unsigned integer bitwidth which fits the field we are compiling for
+    max_uint_bitwidth: usize,
+    /// The number of loops enclosing the statement being checked
+    loop_depth: usize,
+    /// The number of if statements enclosing the statement being checked
+    branch_depth: usize,
+    /// The constants in scope, bound to their values. When checking an instance of a generic function, this includes its generic parameters
+    constants: HashMap<String, TypedExpression<'ast, T>>,
+    /// The generic parameters in scope, which are bound to field values in `constants` but can be used as array sizes
+    generic_parameters: HashSet<String>,
+    /// The generic functions in scope
+    generics: GenericFunctions<'ast, T>,
+    /// The instances of generic functions created so far, with the module they should be added to
+    instances: HashMap<(ModuleId, FunctionKey<'ast>), TypedFunctionSymbol<'ast, T>>,
+    /// Whether to mark each statement with its origin, so that analysis errors can be located and the compiled program
+    /// can be profiled
+    origins: bool,
+    /// The functions of the main module which are entry points
+    entries: Vec<String>,
+    /// The index of the symbols seen so far, if the program should be indexed
+    index: Option<SymbolIndex>,
+    /// The modules which can be reused by later compilations, if they should be collected
+    checked: Option<CheckedModules<'ast, T>>,
+    /// Where each variable in scope is declared, if the program should be indexed
+    declarations: HashMap<&'ast str, Location>,
+    /// What the program uses so far
+    usage: Rc<RefCell<Usage>>,
+    /// The function being checked, if any
+    function: Option<&'ast str>,
+    /// The variables in scope which were not read yet, with the warning to report if they never are
+    unused: HashMap<&'ast str, Warning>,
+}
+
+/// Returns the biggest unsigned integer bitwidth supported for the field `T`: uint arithmetic requires
+/// multiplying two values in range without overflowing the field
+fn max_uint_bitwidth<T: Field>() -> usize {
+    let max = (T::get_required_bits() - 1) / 2;
+    *[8, 16, 32, 64]
+        .iter()
+        .filter(|b| **b < max)
+        .last()
+        .unwrap()
 }
 
-impl<'ast> Checker<'ast> {
-    fn new() -> Checker<'ast> {
+impl<'ast, T: Field> Checker<'ast, T> {
+    fn new() -> Checker<'ast, T> {
         Checker {
             scope: HashSet::new(),
             functions: HashSet::new(),
             level: 0,
+            max_uint_bitwidth: 64,
+            loop_depth: 0,
+            branch_depth: 0,
+            constants: HashMap::new(),
+            generic_parameters: HashSet::new(),
+            generics: HashMap::new(),
+            instances: HashMap::new(),
+            origins: false,
+            index: None,
+            checked: None,
+            declarations: HashMap::new(),
+            entries: vec!["main".to_string()],
+            usage: Rc::new(RefCell::new(Usage::default())),
+            function: None,
+            unused: HashMap::new(),
         }
     }
 
-    /// Check a `Program`
+    /// Check a `Program`, marking each statement with its origin so that analysis errors can be located and the
+    /// compiled program can be profiled
+    ///
+    /// # Arguments
+    ///
+    /// * `prog` - The `Program` to be checked
+    /// * `entries` - The functions of the main module which are entry points
+    pub fn check_with_origins(
+        prog: Program<'ast, T>,
+        entries: &[&str],
+    ) -> Result<(TypedProgram<'ast, T>, Vec<Warning>), Vec<Error>> {
+        let mut checker = Checker::new();
+        checker.origins = true;
+        checker.entries = entries.iter().map(|e| e.to_string()).collect();
+        let prog = checker.check_program(prog)?;
+        Ok((prog, checker.warnings()))
+    }
+
+    /// Check a `Program` like `check_with_origins`, also returning the modules which can be reused by later
+    /// compilations. The main module and the modules with generic functions, which are instantiated by the modules
+    /// calling them, are not returned
     ///
     /// # Arguments
     ///
     /// * `prog` - The `Program` to be checked
-    pub fn check<T: Field>(prog: Program<'ast, T>) -> Result<TypedProgram<'ast, T>, Vec<Error>> {
-        Checker::new().check_program(prog)
+    /// * `entries` - The functions of the main module which are entry points
+    pub fn check_with_cache(
+        prog: Program<'ast, T>,
+        entries: &[&str],
+    ) -> Result<(TypedProgram<'ast, T>, Vec<Warning>, CheckedModules<'ast, T>), Vec<Error>> {
+        let mut checker = Checker::new();
+        checker.origins = true;
+        checker.entries = entries.iter().map(|e| e.to_string()).collect();
+        checker.checked = Some(HashMap::new());
+        let prog = checker.check_program(prog)?;
+        Ok((prog, checker.warnings(), checker.checked.unwrap()))
+    }
+
+    /// Check a `Program`, indexing the symbols it declares and uses. The index is returned even if the check fails, so that
+    /// editors can navigate programs which are being written. As with `check_with_cache`, the modules which can be
+    /// reused by later checks are returned too. The modules which were checked before are not indexed
+    ///
+    /// # Arguments
+    ///
+    /// * `prog` - The `Program` to be checked
+    pub fn check_with_index(
+        prog: Program<'ast, T>,
+    ) -> (
+        Result<(TypedProgram<'ast, T>, Vec<Warning>, CheckedModules<'ast, T>), Vec<Error>>,
+        SymbolIndex,
+    ) {
+        let mut checker = Checker::new();
+        checker.index = Some(SymbolIndex {
+            modules: prog
+                .modules
+                .keys()
+                .chain(prog.checked.keys())
+                .cloned()
+                .collect(),
+            ..SymbolIndex::default()
+        });
+        checker.checked = Some(HashMap::new());
+        let res = checker
+            .check_program(prog)
+            .map(|prog| (prog, checker.warnings(), checker.checked.take().unwrap()));
+        (res, checker.index.unwrap())
+    }
+
+    /// The warnings found while checking the program, sorted by module and position. Generic functions are checked once
+    /// per instance, so the same warning can be found several times
+    fn warnings(&self) -> Vec<Warning> {
+        let mut warnings = std::mem::take(&mut self.usage.borrow_mut().warnings);
+        warnings.sort_by(|a, b| {
+            (
+                &a.module_id,
+                a.inner.pos.map(|p| (p.0.line, p.0.col)),
+                &a.inner.message,
+            )
+                .cmp(&(
+                    &b.module_id,
+                    b.inner.pos.map(|p| (p.0.line, p.0.col)),
+                    &b.inner.message,
+                ))
+        });
+        warnings.dedup();
+        warnings
+    }
+
+    /// Report the imports of the main module which are not used, and the functions of the main module which cannot be
+    /// reached from an entry point. Other modules can be imported, so all their functions may be used.
+    fn check_usage(&self, main_id: &ModuleId) {
+        let mut usage = self.usage.borrow_mut();
+
+        let mut warnings = vec![];
+
+        for (module_id, alias, pos, _) in usage.imports.iter().filter(|(m, ..)| m == main_id) {
+            if !usage
+                .uses
+                .iter()
+                .any(|(m, _, symbol)| m == module_id && symbol == alias)
+            {
+                warnings.push(Warning {
+                    inner: ErrorInner {
+                        pos: Some(*pos),
+                        code: ErrorCode::UnusedImport,
+                        message: format!("Import `{}` is never used", alias),
+                    },
+                    module_id: module_id.clone(),
+                });
+            }
+        }
+
+        // explore the functions called from the entry points, following imports
+        let mut reached = HashSet::new();
+        let mut to_visit: Vec<_> = self
+            .entries
+            .iter()
+            .map(|entry| (main_id.clone(), entry.clone()))
+            .collect();
+        let entries = self
+            .entries
+            .iter()
+            .map(|entry| format!("`{}`", entry))
+            .collect::<Vec<_>>()
+            .join(" or ");
+
+        while let Some((module_id, id)) = to_visit.pop() {
+            if !reached.insert((module_id.clone(), id.clone())) {
+                continue;
+            }
+
+            if let Some((_, _, _, Some(target))) = usage
+                .imports
+                .iter()
+                .find(|(m, alias, _, _)| *m == module_id && *alias == id)
+            {
+                to_visit.push(target.clone());
+            }
+
+            to_visit.extend(
+                usage
+                    .uses
+                    .iter()
+                    .filter(|(m, f, _)| *m == module_id && f.as_ref() == Some(&id))
+                    .map(|(m, _, symbol)| (m.clone(), symbol.clone())),
+            );
+        }
+
+        for (module_id, id, pos) in &usage.functions {
+            if module_id == main_id && !reached.contains(&(module_id.clone(), id.clone())) {
+                warnings.push(Warning {
+                    inner: ErrorInner {
+                        pos: Some(*pos),
+                        code: ErrorCode::UnreachableFunction,
+                        message: format!("Function `{}` is never called from {}", id, entries),
+                    },
+                    module_id: module_id.clone(),
+                });
+            }
+        }
+
+        usage.warnings.extend(warnings);
+    }
+
+    /// Records that `symbol` is used in `module_id`
+    fn use_symbol(&self, symbol: &str, module_id: &ModuleId) {
+        self.usage.borrow_mut().uses.insert((
+            module_id.clone(),
+            self.function.map(|f| f.to_string()),
+            symbol.to_string(),
+        ));
+    }
+
+    /// Records that the variable `id` declared at `pos` is in scope, and should be read
+    fn declare(
+        &mut self,
+        id: &'ast str,
+        pos: (Position, Position),
+        code: ErrorCode,
+        module_id: &ModuleId,
+    ) {
+        let kind = match code {
+            ErrorCode::UnusedParameter => "Parameter",
+            _ => "Variable",
+        };
+        self.unused.insert(
+            id,
+            Warning {
+                inner: ErrorInner {
+                    pos: Some(pos),
+                    code,
+                    message: format!("{} `{}` is never used", kind, id),
+                },
+                module_id: module_id.clone(),
+            },
+        );
     }
 
-    fn check_program<T: Field>(
+    fn check_program(
         &mut self,
         program: Program<'ast, T>,
     ) -> Result<TypedProgram<'ast, T>, Vec<Error>> {
+        self.max_uint_bitwidth = max_uint_bitwidth::<T>();
+
+        // the aliases of the modules to check are only known before they are consumed
+        let aliases: HashMap<_, BTreeSet<_>> = match self.checked {
+            Some(_) => program
+                .modules
+                .iter()
+                .filter(|(id, _)| **id != program.main)
+                .map(|(id, module)| {
+                    let aliases = module
+                        .symbols
+                        .iter()
+                        .filter(|s| program.is_alias(id, s.value.id))
+                        .map(|s| s.value.id.to_string())
+                        .collect();
+                    (id.clone(), aliases)
+                })
+                .collect(),
+            None => HashMap::new(),
+        };
+
+        self.usage.borrow_mut().main = program.main.clone();
+
         let mut state = State::new(program.modules);
 
+        // the modules which were checked before are not checked again
+        for (id, checked) in program.checked {
+            state.typed_modules.insert(id.clone(), checked.module);
+            state.types.insert(id.clone(), checked.types);
+            state.constants.insert(id.clone(), checked.constants);
+            state.generics.insert(id, HashMap::new());
+        }
+
         let mut errors = vec![];
 
         // recursively type-check modules starting with `main`
@@ -266,14 +658,54 @@ impl<'ast> Checker<'ast> {
 
         let main_id = program.main.clone();
 
-        Checker::check_single_main(state.typed_modules.get(&program.main).unwrap()).map_err(
-            |inner| {
-                vec![Error {
-                    inner,
-                    module_id: main_id,
-                }]
-            },
-        )?;
+        for entry in &self.entries {
+            // an entry point cannot be generic, as its signature must be known
+            match state
+                .generics
+                .get(&program.main)
+                .unwrap()
+                .get(entry.as_str())
+            {
+                Some(g) => {
+                    return Err(vec![ErrorInner {
+                        pos: Some(g.function.pos()),
+                        code: ErrorCode::GenericMain,
+                        message: format!("Function {} cannot have generic parameters", entry),
+                    }
+                    .in_file(&main_id)])
+                }
+                None => {}
+            };
+
+            Checker::check_single_entry(state.typed_modules.get(&program.main).unwrap(), entry)
+                .map_err(|inner| {
+                    vec![Error {
+                        inner,
+                        module_id: main_id.clone(),
+                    }]
+                })?;
+        }
+
+        self.check_usage(&program.main);
+
+        if let Some(checked) = self.checked.as_mut() {
+            for (id, aliases) in aliases {
+                // generic functions are checked again for each instance, so their modules cannot be reused
+                if !state.generics[&id].is_empty() {
+                    continue;
+                }
+
+                checked.insert(
+                    id.clone(),
+                    CheckedModule {
+                        module: state.typed_modules[&id].clone(),
+                        types: state.types[&id].clone(),
+                        constants: state.constants[&id].clone(),
+                        aliases,
+                    },
+                );
+            }
+        }
 
         Ok(TypedProgram {
             main: program.main,
@@ -281,6 +713,77 @@ impl<'ast> Checker<'ast> {
         })
     }
 
+    fn check_constant_definition(
+        &mut self,
+        id: Identifier<'ast>,
+        c: ConstantDefinitionNode<'ast, T>,
+        module_id: &ModuleId,
+        types: &TypeMap,
+    ) -> Result<TypedExpression<'ast, T>, Vec<ErrorInner>> {
+        let pos = c.pos();
+        let c = c.value;
+
+        let ty = self
+            .check_type(c.ty, module_id, types)
+            .map_err(|e| vec![e])?;
+        let expression = self
+            .check_expression(c.expression, module_id, types)
+            .map_err(|e| vec![e])?;
+
+        // integer constants can be defined with decimal literals
+        let expression = match (&ty, expression) {
+            (
+                Type::Uint(bitwidth),
+                TypedExpression::FieldElement(FieldElementExpression::Number(n)),
+            ) => match n.to_dec_string().parse::<u128>() {
+                Ok(v) if v < 1 << bitwidth.to_usize() => {
+                    UExpressionInner::Value(v).annotate(*bitwidth).into()
+                }
+                _ => {
+                    return Err(vec![ErrorInner {
+                        pos: Some(pos),
+                        code: ErrorCode::ValueOutOfRange,
+                        message: format!("Value {} is out of range for type {}", n, ty),
+                    }])
+                }
+            },
+            (_, e) => e,
+        };
+
+        if expression.get_type() != ty {
+            return Err(vec![ErrorInner {
+                pos: Some(pos),
+                code: ErrorCode::TypeMismatch,
+                message: format!(
+                    "Expression {} of type {} cannot be assigned to constant {} of type {}",
+                    expression,
+                    expression.get_type(),
+                    id,
+                    ty
+                ),
+            }]);
+        }
+
+        Propagator::propagate_constant(expression.clone())
+            .map_err(|e| {
+                vec![ErrorInner {
+                    pos: Some(pos),
+                    code: e.code(),
+                    message: e.to_string(),
+                }]
+            })?
+            .ok_or_else(|| {
+                vec![ErrorInner {
+                    pos: Some(pos),
+                    code: ErrorCode::NonConstantExpression,
+                    message: format!(
+                        "Expression {} assigned to constant {} is not known at compile time",
+                        expression, id
+                    ),
+                }]
+            })
+    }
+
     fn check_struct_type_declaration(
         &mut self,
         id: String,
@@ -305,6 +808,7 @@ impl<'ast> Checker<'ast> {
                     true => fields.push(f),
                     false => errors.push(ErrorInner {
                         pos: Some(pos),
+                        code: ErrorCode::DuplicateDeclaration,
                         message: format!("Duplicate key {} in struct definition", f.0,),
                     }),
                 },
@@ -328,7 +832,7 @@ impl<'ast> Checker<'ast> {
         )))
     }
 
-    fn check_symbol_declaration<T: Field>(
+    fn check_symbol_declaration(
         &mut self,
         declaration: SymbolDeclarationNode<'ast, T>,
         module_id: &ModuleId,
@@ -341,7 +845,94 @@ impl<'ast> Checker<'ast> {
         let pos = declaration.pos();
         let declaration = declaration.value;
 
+        match &declaration.symbol {
+            Symbol::There(import) => self.usage.borrow_mut().imports.push((
+                module_id.clone(),
+                declaration.id.to_string(),
+                pos,
+                Some((
+                    import.value.module_id.clone(),
+                    import.value.symbol_id.to_string(),
+                )),
+            )),
+            Symbol::Flat(_) => self.usage.borrow_mut().imports.push((
+                module_id.clone(),
+                declaration.id.to_string(),
+                pos,
+                None,
+            )),
+            Symbol::HereFunction(_) => self.usage.borrow_mut().functions.push((
+                module_id.clone(),
+                declaration.id.to_string(),
+                pos,
+            )),
+            _ => {}
+        }
+
+        if let Some(index) = self.index.as_mut() {
+            let key = (module_id.clone(), declaration.id.to_string());
+            match &declaration.symbol {
+                Symbol::There(import) => {
+                    index.imports.insert(
+                        key,
+                        (
+                            import.value.module_id.clone(),
+                            import.value.symbol_id.to_string(),
+                        ),
+                    );
+                }
+                _ => {
+                    index.declarations.insert(
+                        key,
+                        Location {
+                            module: module_id.clone(),
+                            start: pos.0,
+                            end: pos.1,
+                        },
+                    );
+                }
+            }
+        }
+
         match declaration.symbol.clone() {
+            Symbol::HereConstant(c) => {
+                match self.check_constant_definition(
+                    declaration.id,
+                    c.clone(),
+                    module_id,
+                    &state.types,
+                ) {
+                    Ok(value) => {
+                        match symbol_unifier.insert_constant(declaration.id) {
+                            false => errors.push(
+                                ErrorInner {
+                                    pos: Some(pos),
+                                    code: ErrorCode::SymbolConflict,
+                                    message: format!(
+                                        "{} conflicts with another symbol",
+                                        declaration.id,
+                                    ),
+                                }
+                                .in_file(module_id),
+                            ),
+                            true => {
+                                self.constants.insert(declaration.id.to_string(), value);
+                            }
+                        };
+                    }
+                    Err(e) => {
+                        // keep the constant in scope with its declared type, so that its uses are not reported as
+                        // undefined on top of the errors in its definition
+                        if let Ok(ty) = self.check_type(c.value.ty, module_id, &state.types) {
+                            self.constants.insert(
+                                declaration.id.to_string(),
+                                Self::identifier_expression(declaration.id, ty),
+                            );
+                        }
+                        errors.extend(e.into_iter().map(|inner| inner.in_file(module_id)))
+                    }
+                }
+            }
             Symbol::HereType(t) => {
                 match self.check_struct_type_declaration(
                     declaration.id.to_string(),
@@ -354,6 +945,7 @@ impl<'ast> Checker<'ast> {
                             false => errors.push(
                                 ErrorInner {
                                     pos: Some(pos),
+                                    code: ErrorCode::SymbolConflict,
                                     message: format!(
                                         "{} conflicts with another symbol",
                                         declaration.id,
@@ -375,12 +967,81 @@ impl<'ast> Checker<'ast> {
                     })),
                 }
             }
-            Symbol::HereFunction(f) => match self.check_function(f, module_id, &state.types) {
+            Symbol::HereAlias(t) => match self.check_type(t, module_id, &state.types) {
+                Ok(ty) => {
+                    match symbol_unifier.insert_type(declaration.id) {
+                        false => errors.push(
+                            ErrorInner {
+                                pos: Some(pos),
+                                code: ErrorCode::SymbolConflict,
+                                message: format!(
+                                    "{} conflicts with another symbol",
+                                    declaration.id,
+                                ),
+                            }
+                            .in_file(module_id),
+                        ),
+                        true => {
+                            state
+                                .types
+                                .entry(module_id.clone())
+                                .or_default()
+                                .insert(declaration.id.to_string(), ty);
+                        }
+                    };
+                }
+                Err(e) => errors.push(e.in_file(module_id)),
+            },
+            Symbol::HereFunction(f) if !f.value.signature.generics.is_empty() => {
+                self.function = Some(declaration.id);
+                let res = self.check_generic_function(&f, module_id, &state.types);
+                self.function = None;
+                match res {
+                    Ok(()) => {
+                        match symbol_unifier.insert_generic_function(declaration.id) {
+                            false => errors.push(
+                                ErrorInner {
+                                    pos: Some(pos),
+                                    code: ErrorCode::SymbolConflict,
+                                    message: format!(
+                                        "{} conflicts with another symbol",
+                                        declaration.id,
+                                    ),
+                                }
+                                .in_file(module_id),
+                            ),
+                            true => {}
+                        };
+
+                        let generic_function = GenericFunction {
+                            module_id: module_id.clone(),
+                            id: declaration.id,
+                            function: f,
+                            functions: self.functions.clone(),
+                            generics: self.generics.clone(),
+                            constants: self.constants.clone(),
+                        };
+
+                        self.generics
+                            .insert(declaration.id, Rc::new(generic_function));
+                    }
+                    Err(e) => {
+                        errors.extend(e.into_iter().map(|inner| inner.in_file(module_id)));
+                    }
+                }
+            }
+            Symbol::HereFunction(f) => match {
+                self.function = Some(declaration.id);
+                let res = self.check_function(f, module_id, &state.types);
+                self.function = None;
+                res
+            } {
                 Ok(funct) => {
                     match symbol_unifier.insert_function(declaration.id, funct.signature.clone()) {
                         false => errors.push(
                             ErrorInner {
                                 pos: Some(pos),
+                                code: ErrorCode::SymbolConflict,
                                 message: format!(
                                     "{} conflicts with another symbol",
                                     declaration.id,
@@ -409,7 +1070,17 @@ impl<'ast> Checker<'ast> {
                 let pos = import.pos();
                 let import = import.value;
 
-                match Checker::new().check_module(&import.module_id, state) {
+                let mut checker = Checker::new();
+                checker.max_uint_bitwidth = self.max_uint_bitwidth;
+                checker.origins = self.origins;
+                checker.index = self.index.take();
+                checker.usage = self.usage.clone();
+
+                let res = checker.check_module(&import.module_id, state);
+
+                self.index = checker.index.take();
+
+                match res {
                     Ok(()) => {
                         // find candidates in the checked module
                         let function_candidates: Vec<_> = state
@@ -443,7 +1114,8 @@ impl<'ast> Checker<'ast> {
                                         name: declaration.id.into(),
                                         ..t
                                     }),
-                                    _ => unreachable!()
+                                    // aliases resolve to their underlying type
+                                    t => t
                                 };
 
                                 // we imported a type, so the symbol it gets bound to should not already exist
@@ -453,6 +1125,7 @@ impl<'ast> Checker<'ast> {
                                             module_id: module_id.clone(),
                                             inner: ErrorInner {
                                             pos: Some(pos),
+                                            code: ErrorCode::SymbolConflict,
                                             message: format!(
                                                 "{} conflicts with another symbol",
                                                 declaration.id,
@@ -468,13 +1141,70 @@ impl<'ast> Checker<'ast> {
                                     .insert(declaration.id.to_string(), t.clone());
                             }
                             (0, None) => {
-                                errors.push(ErrorInner {
-                                    pos: Some(pos),
-                                    message: format!(
-                                        "Could not find symbol {} in module {}",
-                                        import.symbol_id, import.module_id.display(),
-                                    ),
-                                }.in_file(module_id));
+                                // find candidates in the constants
+                                let constant_candidate = state
+                                    .constants
+                                    .get(&import.module_id)
+                                    .unwrap()
+                                    .get(import.symbol_id)
+                                    .cloned();
+
+                                match constant_candidate {
+                                    Some(value) => {
+                                        match symbol_unifier.insert_constant(declaration.id) {
+                                            false => {
+                                                errors.push(ErrorInner {
+                                                    pos: Some(pos),
+                                                    code: ErrorCode::SymbolConflict,
+                                                    message: format!(
+                                                        "{} conflicts with another symbol",
+                                                        declaration.id,
+                                                    ),
+                                                }.in_file(module_id));
+                                            }
+                                            true => {}
+                                        };
+                                        self.constants.insert(declaration.id.to_string(), value);
+                                    }
+                                    None => {
+                                        // find candidates in the generic functions
+                                        let generic_candidate = state
+                                            .generics
+                                            .get(&import.module_id)
+                                            .unwrap()
+                                            .get(import.symbol_id)
+                                            .cloned();
+
+                                        match generic_candidate {
+                                            Some(g) => {
+                                                match symbol_unifier.insert_generic_function(declaration.id) {
+                                                    false => {
+                                                        errors.push(ErrorInner {
+                                                            pos: Some(pos),
+                                                            code: ErrorCode::SymbolConflict,
+                                                            message: format!(
+                                                                "{} conflicts with another symbol",
+                                                                declaration.id,
+                                                            ),
+                                                        }.in_file(module_id));
+                                                    }
+                                                    true => {}
+                                                };
+                                                self.generics.insert(declaration.id, g);
+                                            }
+                                            None => {
+                                                errors.push(ErrorInner {
+                                                    pos: Some(pos),
+                                                    code: ErrorCode::SymbolNotFound,
+                                                    message: format!(
+                                                        "Could not find symbol {} in module {}",
+                                                        import.symbol_id, import.module_id.display(),
+                                                    ),
+                                                }.in_file(module_id));
+                                            }
+                                        }
+                                    }
+                                }
                             }
                             (_, Some(_)) => unreachable!("collision in module we're importing from should have been caught when checking it"),
                             _ => {
@@ -484,6 +1214,7 @@ impl<'ast> Checker<'ast> {
                                         false => {
                                             errors.push(ErrorInner {
                                                 pos: Some(pos),
+                                                code: ErrorCode::SymbolConflict,
                                                 message: format!(
                                                     "{} conflicts with another symbol",
                                                     declaration.id,
@@ -516,6 +1247,7 @@ impl<'ast> Checker<'ast> {
                         errors.push(
                             ErrorInner {
                                 pos: Some(pos),
+                                code: ErrorCode::SymbolConflict,
                                 message: format!(
                                     "{} conflicts with another symbol",
                                     declaration.id,
@@ -547,7 +1279,7 @@ impl<'ast> Checker<'ast> {
         Ok(())
     }
 
-    fn check_module<T: Field>(
+    fn check_module(
         &mut self,
         module_id: &ModuleId,
         state: &mut State<'ast, T>,
@@ -585,6 +1317,16 @@ impl<'ast> Checker<'ast> {
                     }
                 }
 
+                // keep the constants of this module so that other modules can import them
+                state
+                    .constants
+                    .insert(module_id.clone(), self.constants.clone());
+
+                // keep the generic functions of this module so that other modules can import them
+                state
+                    .generics
+                    .insert(module_id.clone(), self.generics.clone());
+
                 Some(TypedModule {
                     functions: checked_functions,
                 })
@@ -608,24 +1350,69 @@ impl<'ast> Checker<'ast> {
             None => {}
         };
 
+        // add the instances of generic functions to the modules they belong to, which have all been checked at this point
+        for ((module_id, key), symbol) in self.instances.drain() {
+            state
+                .typed_modules
+                .get_mut(&module_id)
+                .unwrap()
+                .functions
+                .entry(key)
+                .or_insert(symbol);
+        }
+
         Ok(())
     }
 
-    fn check_single_main<T: Field>(module: &TypedModule<T>) -> Result<(), ErrorInner> {
+    fn check_single_entry(module: &TypedModule<T>, entry: &str) -> Result<(), ErrorInner> {
         match module
             .functions
             .iter()
-            .filter(|(key, _)| key.id == "main")
+            .filter(|(key, _)| key.id == entry)
             .count()
         {
             1 => Ok(()),
             0 => Err(ErrorInner {
                 pos: None,
-                message: format!("No main function found"),
+                code: ErrorCode::MainNotFound,
+                message: match entry {
+                    "main" => format!("No main function found"),
+                    _ => format!("No function `{}` found", entry),
+                },
             }),
             n => Err(ErrorInner {
                 pos: None,
-                message: format!("Only one main function allowed, found {}", n),
+                code: ErrorCode::MultipleMains,
+                message: match entry {
+                    "main" => format!("Only one main function allowed, found {}", n),
+                    _ => format!("Only one function `{}` allowed, found {}", entry, n),
+                },
+            }),
+        }
+    }
+
+    fn check_early_exit_condition(
+        &mut self,
+        condition: Option<ExpressionNode<'ast, T>>,
+        module_id: &ModuleId,
+        types: &TypeMap,
+    ) -> Result<Option<BooleanExpression<'ast, T>>, ErrorInner> {
+        let condition = match condition {
+            Some(condition) => condition,
+            None => return Ok(None),
+        };
+
+        let pos = condition.pos();
+
+        match self.check_expression(condition, module_id, &types)? {
+            TypedExpression::Boolean(e) => Ok(Some(e)),
+            e => Err(ErrorInner {
+                pos: Some(pos),
+                code: ErrorCode::TypeMismatch,
+                message: format!(
+                    "Expected early exit condition to be of type bool, found {}",
+                    e.get_type()
+                ),
             }),
         }
     }
@@ -635,12 +1422,13 @@ impl<'ast> Checker<'ast> {
             UnresolvedType::FieldElement => Ok(()),
             t => Err(ErrorInner {
                 pos: Some(var.pos()),
+                code: ErrorCode::UnsupportedType,
                 message: format!("Variable in for loop cannot have type {}", t),
             }),
         }
     }
 
-    fn check_function<T: Field>(
+    fn check_function(
         &mut self,
         funct_node: FunctionNode<'ast, T>,
         module_id: &ModuleId,
@@ -672,6 +1460,8 @@ impl<'ast> Checker<'ast> {
                 for stat in funct.statements.into_iter() {
                     let pos = stat.pos();
 
+                    statements_checked.extend(self.origin(pos, module_id));
+
                     match self.check_statement(stat, module_id, types) {
                         Ok(statement) => {
                             match &statement {
@@ -682,6 +1472,7 @@ impl<'ast> Checker<'ast> {
                                         true => {}
                                         false => errors.push(ErrorInner {
                                             pos: Some(pos),
+                                            code: ErrorCode::TypeMismatch,
                                             message: format!(
                                                 "Expected ({}) in return statement, found ({})",
                                                 s.outputs
@@ -727,16 +1518,234 @@ impl<'ast> Checker<'ast> {
         })
     }
 
-    fn check_parameter(
+    /// Check the declaration of a generic function. Its body is checked for each instance
+    fn check_generic_function(
         &self,
-        p: ParameterNode<'ast>,
+        funct_node: &FunctionNode<'ast, T>,
         module_id: &ModuleId,
         types: &TypeMap,
-    ) -> Result<Parameter<'ast>, Vec<ErrorInner>> {
-        let var = self.check_variable(p.value.id, module_id, types)?;
+    ) -> Result<(), Vec<ErrorInner>> {
+        let signature = &funct_node.value.signature;
 
-        Ok(Parameter {
-            id: var,
+        let mut errors = vec![];
+
+        for (index, g) in signature.generics.iter().enumerate() {
+            if signature.generics[..index]
+                .iter()
+                .any(|other| other.value == g.value)
+            {
+                errors.push(ErrorInner {
+                    pos: Some(g.pos()),
+                    code: ErrorCode::DuplicateDeclaration,
+                    message: format!("Generic parameter {} is already declared", g),
+                });
+            }
+
+            // generic parameters are inferred from the arguments, so they must appear in their types
+            if !signature
+                .inputs
+                .iter()
+                .any(|t| Self::uses_generic(&t.value, &g.value))
+            {
+                errors.push(ErrorInner {
+                    pos: Some(g.pos()),
+                    code: ErrorCode::UnusedGeneric,
+                    message: format!(
+                        "Generic parameter {} must be used as an array size in the type of an argument",
+                        g
+                    ),
+                });
+            }
+        }
+
+        if errors.len() > 0 {
+            return Err(errors);
+        }
+
+        // check the signature with arbitrary values for the generic parameters to catch undefined types and sizes
+        let mut checker = Checker::new();
+        checker.max_uint_bitwidth = self.max_uint_bitwidth;
+        checker.constants = self.constants.clone();
+        checker.usage = self.usage.clone();
+        checker.function = self.function;
+        for g in &signature.generics {
+            checker.constants.insert(
+                g.value.clone(),
+                FieldElementExpression::Number(T::from(0)).into(),
+            );
+            checker.generic_parameters.insert(g.value.clone());
+        }
+
+        checker
+            .check_signature(signature.clone(), module_id, types)
+            .map(|_| ())
+    }
+
+    /// Returns whether `ty` uses the generic parameter `generic` as an array size
+    fn uses_generic(ty: &UnresolvedType, generic: &str) -> bool {
+        match ty {
+            UnresolvedType::Array(t, size) => {
+                *size == ArraySize::Identifier(generic.to_string())
+                    || Self::uses_generic(&t.value, generic)
+            }
+            _ => false,
+        }
+    }
+
+    /// Infer the values of `generics` by matching the declared type of an argument against its actual type.
+    /// Returns false if a generic parameter would take two different values
+    fn infer_generics(
+        declared: &UnresolvedType,
+        actual: &Type,
+        generics: &[ConstantGenericNode],
+        values: &mut HashMap<String, usize>,
+    ) -> bool {
+        match (declared, actual) {
+            (UnresolvedType::Array(t, size), Type::Array(array_type)) => {
+                let consistent = match size {
+                    ArraySize::Identifier(id) if generics.iter().any(|g| &g.value == id) => {
+                        match values.entry(id.clone()) {
+                            Entry::Occupied(v) => *v.get() == array_type.size,
+                            Entry::Vacant(v) => {
+                                v.insert(array_type.size);
+                                true
+                            }
+                        }
+                    }
+                    _ => true,
+                };
+
+                consistent && Self::infer_generics(&t.value, &array_type.ty, generics, values)
+            }
+            // other mismatches are caught when checking the signature of the instance
+            _ => true,
+        }
+    }
+
+    /// Find a function matching `query`. If no function matches, try to instantiate a generic function.
+    fn find_or_instantiate_function(
+        &mut self,
+        query: &FunctionQuery<'ast>,
+        module_id: &ModuleId,
+        types: &TypeMap,
+        pos: (Position, Position),
+    ) -> Result<Option<FunctionKey<'ast>>, ErrorInner> {
+        self.use_symbol(query.id, module_id);
+
+        if let Some(key) = self.find_function(query) {
+            return Ok(Some(key));
+        }
+
+        let generic_function = match self.generics.get(query.id) {
+            Some(g) => g.clone(),
+            None => return Ok(None),
+        };
+
+        let signature = &generic_function.function.value.signature;
+
+        if signature.inputs.len() != query.inputs.len() {
+            return Ok(None);
+        }
+
+        let mut values = HashMap::new();
+        for (declared, actual) in signature.inputs.iter().zip(query.inputs.iter()) {
+            if !Self::infer_generics(&declared.value, actual, &signature.generics, &mut values) {
+                return Ok(None);
+            }
+        }
+
+        // check the instance in the context of the definition of the generic function, with the generic parameters bound to their values
+        let mut checker = Checker::new();
+        checker.max_uint_bitwidth = self.max_uint_bitwidth;
+        checker.origins = self.origins;
+        checker.usage = self.usage.clone();
+        checker.function = Some(generic_function.id);
+        checker.functions = generic_function.functions.clone();
+        checker.generics = generic_function.generics.clone();
+        checker.constants = generic_function.constants.clone();
+        for g in &signature.generics {
+            checker.constants.insert(
+                g.value.clone(),
+                FieldElementExpression::Number(T::from(values[&g.value])).into(),
+            );
+            checker.generic_parameters.insert(g.value.clone());
+        }
+
+        let instance_name = format!(
+            "{}<{}>",
+            generic_function.id,
+            signature
+                .generics
+                .iter()
+                .map(|g| format!("{} = {}", g, values[&g.value]))
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+
+        let into_call_error = |e: ErrorInner| ErrorInner {
+            pos: Some(pos),
+            code: e.code,
+            message: format!(
+                "In {} defined in {} at {}: {}",
+                instance_name,
+                generic_function.module_id.display(),
+                e.pos.map(|p| format!("{}", p.0)).unwrap_or("?".to_string()),
+                e.message
+            ),
+        };
+
+        let instance_signature = checker
+            .check_signature(signature.clone(), &generic_function.module_id, types)
+            .map_err(|e| into_call_error(e.into_iter().next().unwrap()))?;
+
+        let key = FunctionKey::with_id(generic_function.id).signature(instance_signature);
+
+        if !query.match_func(&key.clone().id(query.id)) {
+            return Ok(None);
+        }
+
+        let instance_location = (generic_function.module_id.clone(), key.clone());
+
+        if !self.instances.contains_key(&instance_location) {
+            let instance = checker
+                .check_function(
+                    generic_function.function.clone(),
+                    &generic_function.module_id,
+                    types,
+                )
+                .map_err(|e| into_call_error(e.into_iter().next().unwrap()))?;
+
+            self.instances.extend(checker.instances);
+            self.instances
+                .insert(instance_location, TypedFunctionSymbol::Here(instance));
+        }
+
+        let local_key = key.clone().id(query.id);
+
+        // if the generic function was imported, refer to its instance in the module it is defined in
+        if generic_function.module_id != *module_id {
+            self.instances.insert(
+                (module_id.clone(), local_key.clone()),
+                TypedFunctionSymbol::There(key, generic_function.module_id.clone()),
+            );
+        }
+
+        Ok(Some(local_key))
+    }
+
+    fn check_parameter(
+        &mut self,
+        p: ParameterNode<'ast>,
+        module_id: &ModuleId,
+        types: &TypeMap,
+    ) -> Result<Parameter<'ast>, Vec<ErrorInner>> {
+        let pos = p.value.id.pos();
+        let id = p.value.id.value.id;
+        let var = self.check_variable(p.value.id, module_id, types)?;
+        self.declare(id, pos, ErrorCode::UnusedParameter, module_id);
+
+        Ok(Parameter {
+            id: var,
             private: p.value.private,
         })
     }
@@ -792,12 +1801,32 @@ impl<'ast> Checker<'ast> {
         match ty {
             UnresolvedType::FieldElement => Ok(Type::FieldElement),
             UnresolvedType::Boolean => Ok(Type::Boolean),
-            UnresolvedType::Uint(bitwidth) => Ok(Type::uint(bitwidth)),
-            UnresolvedType::Array(t, size) => Ok(Type::Array(ArrayType::new(
-                self.check_type(*t, module_id, types)?,
-                size,
-            ))),
+            UnresolvedType::Uint(128) => Err(ErrorInner {
+                pos: Some(pos),
+                code: ErrorCode::UnsupportedType,
+                message: String::from("Type u128 is reserved but not supported yet"),
+            }),
+            UnresolvedType::Uint(bitwidth) => match bitwidth <= self.max_uint_bitwidth {
+                true => Ok(Type::uint(bitwidth)),
+                false => Err(ErrorInner {
+                    pos: Some(pos),
+                    code: ErrorCode::UnsupportedType,
+                    message: format!("Type u{} is not supported by the current curve", bitwidth),
+                }),
+            },
+            UnresolvedType::Int(bitwidth) => Ok(Type::int(bitwidth)),
+            UnresolvedType::Array(t, size) => {
+                if let ArraySize::Identifier(id) = &size {
+                    self.use_symbol(id, module_id);
+                }
+                let size = self.check_array_size(size, pos)?;
+                Ok(Type::Array(ArrayType::new(
+                    self.check_type(*t, module_id, types)?,
+                    size,
+                )))
+            }
             UnresolvedType::User(id) => {
+                self.use_symbol(&id, module_id);
                 types
                     .get(module_id)
                     .unwrap()
@@ -805,26 +1834,202 @@ impl<'ast> Checker<'ast> {
                     .cloned()
                     .ok_or_else(|| ErrorInner {
                         pos: Some(pos),
+                        code: ErrorCode::UndefinedType,
                         message: format!("Undefined type {}", id),
                     })
             }
+            UnresolvedType::Tuple(elements) => Ok(Type::tuple(
+                elements
+                    .into_iter()
+                    .map(|t| self.check_type(t, module_id, types))
+                    .collect::<Result<Vec<_>, _>>()?,
+            )),
         }
     }
 
-    fn check_variable(
+    fn check_array_size(
         &self,
+        size: ArraySize,
+        pos: (Position, Position),
+    ) -> Result<usize, ErrorInner> {
+        match size {
+            ArraySize::Number(size) => Ok(size),
+            ArraySize::Identifier(id) => match self.constants.get(id.as_str()) {
+                Some(TypedExpression::Uint(e)) if e.bitwidth() == UBitwidth::B32 => {
+                    match e.as_inner() {
+                        UExpressionInner::Value(v) => usize::try_from(*v).map_err(|_| ErrorInner {
+                            pos: Some(pos),
+                            code: ErrorCode::ValueOutOfRange,
+                            message: format!("Array size {} is too large", v),
+                        }),
+                        // only constants whose definition was rejected have no value
+                        _ => Err(ErrorInner {
+                            pos: Some(pos),
+                            code: ErrorCode::NonConstantExpression,
+                            message: format!("Constant {} used as array size has no value", id),
+                        }),
+                    }
+                }
+                // generic parameters are bound to the sizes they were inferred from
+                Some(TypedExpression::FieldElement(FieldElementExpression::Number(n)))
+                    if self.generic_parameters.contains(id.as_str()) =>
+                {
+                    Ok(n.to_dec_string().parse().unwrap())
+                }
+                Some(e) => Err(ErrorInner {
+                    pos: Some(pos),
+                    code: ErrorCode::TypeMismatch,
+                    message: format!(
+                        "Expected array size {} to be of type u32, found {}",
+                        id,
+                        e.get_type()
+                    ),
+                }),
+                None => Err(ErrorInner {
+                    pos: Some(pos),
+                    code: ErrorCode::NonConstantExpression,
+                    message: format!("Undefined constant {} used as array size", id),
+                }),
+            },
+        }
+    }
+
+    fn check_variable(
+        &mut self,
         v: crate::absy::VariableNode<'ast>,
         module_id: &ModuleId,
         types: &TypeMap,
     ) -> Result<Variable<'ast>, Vec<ErrorInner>> {
-        Ok(Variable::with_id_and_type(
+        let pos = v.pos();
+
+        let var = Variable::with_id_and_type(
             v.value.id,
             self.check_type(v.value._type, module_id, types)
                 .map_err(|e| vec![e])?,
-        ))
+        );
+
+        if self.index.is_some() {
+            let location = Location {
+                module: module_id.clone(),
+                start: pos.0,
+                end: pos.1,
+            };
+            self.declarations.insert(v.value.id, location.clone());
+            self.index_reference(
+                v.value.id,
+                var.get_type().to_string(),
+                pos,
+                module_id,
+                Target::Variable(location),
+            );
+        }
+
+        Ok(var)
+    }
+
+    /// Records that the identifier at `pos` refers to `target`, if the program should be indexed
+    fn index_reference(
+        &mut self,
+        name: &str,
+        ty: String,
+        pos: (Position, Position),
+        module_id: &ModuleId,
+        target: Target,
+    ) {
+        if let Some(index) = self.index.as_mut() {
+            index.references.push(Reference {
+                location: Location {
+                    module: module_id.clone(),
+                    start: pos.0,
+                    end: pos.1,
+                },
+                name: name.to_string(),
+                ty,
+                target,
+            });
+        }
+    }
+
+    /// Records a reference to the variable `name` at `pos`, if the program should be indexed
+    fn index_variable(
+        &mut self,
+        name: &str,
+        ty: &Type,
+        pos: (Position, Position),
+        module_id: &ModuleId,
+    ) {
+        if let Some(location) = self.declarations.get(name).cloned() {
+            self.index_reference(
+                name,
+                ty.to_string(),
+                pos,
+                module_id,
+                Target::Variable(location),
+            );
+        }
+    }
+
+    /// Records a call to the function `name` located at `pos`, if the program should be indexed
+    fn index_call(
+        &mut self,
+        name: &str,
+        signature: &Signature,
+        pos: (Position, Position),
+        module_id: &ModuleId,
+    ) {
+        self.index_reference(
+            name,
+            format!("{}{}", name, signature),
+            pos,
+            module_id,
+            Target::Symbol(module_id.clone(), name.to_string()),
+        );
+    }
+
+    /// Check the statements of a block in their own scope
+    fn check_block(
+        &mut self,
+        statements: Vec<StatementNode<'ast, T>>,
+        module_id: &ModuleId,
+        types: &TypeMap,
+    ) -> Result<Vec<TypedStatement<'ast, T>>, Vec<ErrorInner>> {
+        self.enter_scope();
+
+        let mut res = vec![];
+
+        for s in statements {
+            res.extend(self.origin(s.pos(), module_id));
+            match self.check_statement(s, module_id, types) {
+                Ok(s) => res.push(s),
+                Err(e) => {
+                    self.exit_scope();
+                    return Err(e);
+                }
+            }
+        }
+
+        self.exit_scope();
+
+        Ok(res)
+    }
+
+    /// Returns a marker for the origin of the statement at `pos`, if statements should be marked
+    fn origin(
+        &self,
+        pos: (Position, Position),
+        module_id: &ModuleId,
+    ) -> Option<TypedStatement<'ast, T>> {
+        match self.origins {
+            true => Some(TypedStatement::Origin(Origin {
+                stack: vec![],
+                file: source_map::normalize(module_id),
+                line: pos.0.line,
+            })),
+            false => None,
+        }
     }
 
-    fn check_statement<T: Field>(
+    fn check_statement(
         &mut self,
         stat: StatementNode<'ast, T>,
         module_id: &ModuleId,
@@ -834,6 +2039,14 @@ impl<'ast> Checker<'ast> {
 
         match stat.value {
             Statement::Return(list) => {
+                if self.branch_depth > 0 {
+                    return Err(vec![ErrorInner {
+                        pos: Some(pos),
+                        code: ErrorCode::InvalidStatement,
+                        message: "`return` cannot be used inside an if statement".to_string(),
+                    }]);
+                }
+
                 let mut expression_list_checked = vec![];
 
                 for e in list.value.expressions {
@@ -846,11 +2059,14 @@ impl<'ast> Checker<'ast> {
                 Ok(TypedStatement::Return(expression_list_checked))
             }
             Statement::Declaration(var) => {
+                let (var_pos, id) = (var.pos(), var.value.id);
                 let var = self.check_variable(var, module_id, types)?;
+                self.declare(id, var_pos, ErrorCode::UnusedVariable, module_id);
                 match self.insert_into_scope(var.clone()) {
                     true => Ok(TypedStatement::Declaration(var)),
                     false => Err(ErrorInner {
                         pos: Some(pos),
+                        code: ErrorCode::DuplicateDeclaration,
                         message: format!("Duplicate declaration for variable named {}", var.id),
                     }),
                 }
@@ -882,6 +2098,7 @@ impl<'ast> Checker<'ast> {
                     true => Ok(TypedStatement::Definition(var, checked_expr)),
                     false => Err(ErrorInner {
                         pos: Some(pos),
+                        code: ErrorCode::TypeMismatch,
                         message: format!(
                             "Expression {} of type {} cannot be assigned to {} of type {}",
                             checked_expr, expression_type, var, var_type
@@ -891,14 +2108,22 @@ impl<'ast> Checker<'ast> {
                 .map_err(|e| vec![e])
             }
             Statement::Assertion(e) => {
+                let metadata = SourceMetadata {
+                    file: source_map::normalize(module_id),
+                    line: pos.0.line,
+                    col: pos.0.col,
+                    message: e.to_string(),
+                };
+
                 let e = self
                     .check_expression(e, module_id, &types)
                     .map_err(|e| vec![e])?;
 
                 match e {
-                    TypedExpression::Boolean(e) => Ok(TypedStatement::Assertion(e)),
+                    TypedExpression::Boolean(e) => Ok(TypedStatement::Assertion(e, Some(metadata))),
                     e => Err(ErrorInner {
                         pos: Some(pos),
+                        code: ErrorCode::TypeMismatch,
                         message: format!(
                             "Expected {} to be of type bool, found {}",
                             e,
@@ -926,6 +2151,7 @@ impl<'ast> Checker<'ast> {
                     TypedExpression::FieldElement(e) => Ok(e),
                     e => Err(ErrorInner {
                         pos: Some(pos),
+                        code: ErrorCode::TypeMismatch,
                         message: format!(
                             "Expected lower loop bound to be of type field, found {}",
                             e.get_type()
@@ -938,6 +2164,7 @@ impl<'ast> Checker<'ast> {
                     TypedExpression::FieldElement(e) => Ok(e),
                     e => Err(ErrorInner {
                         pos: Some(pos),
+                        code: ErrorCode::TypeMismatch,
                         message: format!(
                             "Expected higher loop bound to be of type field, found {}",
                             e.get_type()
@@ -950,18 +2177,84 @@ impl<'ast> Checker<'ast> {
 
                 let mut checked_statements = vec![];
 
+                self.loop_depth += 1;
+
                 for stat in statements {
-                    let checked_stat = self.check_statement(stat, module_id, types)?;
-                    checked_statements.push(checked_stat);
+                    checked_statements.extend(self.origin(stat.pos(), module_id));
+                    let checked_stat = self.check_statement(stat, module_id, types);
+                    match checked_stat {
+                        Ok(checked_stat) => checked_statements.push(checked_stat),
+                        Err(e) => {
+                            self.loop_depth -= 1;
+                            return Err(e);
+                        }
+                    }
                 }
 
+                self.loop_depth -= 1;
+
                 self.exit_scope();
                 Ok(TypedStatement::For(var, from, to, checked_statements))
             }
+            Statement::IfElse(condition, consequence, alternative) => {
+                let condition = self
+                    .check_expression(condition, module_id, &types)
+                    .map_err(|e| vec![e])?;
+
+                let condition = match condition {
+                    TypedExpression::Boolean(e) => Ok(e),
+                    e => Err(ErrorInner {
+                        pos: Some(pos),
+                        code: ErrorCode::TypeMismatch,
+                        message: format!(
+                            "Expected condition of if statement to be of type bool, found {}",
+                            e.get_type()
+                        ),
+                    }),
+                }
+                .map_err(|e| vec![e])?;
+
+                self.branch_depth += 1;
+
+                let consequence = self.check_block(consequence, module_id, types);
+                let alternative = self.check_block(alternative, module_id, types);
+
+                self.branch_depth -= 1;
+
+                Ok(TypedStatement::IfElse(
+                    condition,
+                    consequence?,
+                    alternative?,
+                ))
+            }
+            Statement::Break(condition) => match self.loop_depth {
+                0 => Err(vec![ErrorInner {
+                    pos: Some(pos),
+                    code: ErrorCode::InvalidStatement,
+                    message: "`break` can only be used inside a loop".to_string(),
+                }]),
+                _ => self
+                    .check_early_exit_condition(condition, module_id, types)
+                    .map(TypedStatement::Break)
+                    .map_err(|e| vec![e]),
+            },
+            Statement::Continue(condition) => match self.loop_depth {
+                0 => Err(vec![ErrorInner {
+                    pos: Some(pos),
+                    code: ErrorCode::InvalidStatement,
+                    message: "`continue` can only be used inside a loop".to_string(),
+                }]),
+                _ => self
+                    .check_early_exit_condition(condition, module_id, types)
+                    .map(TypedStatement::Continue)
+                    .map_err(|e| vec![e]),
+            },
             Statement::MultipleDefinition(assignees, rhs) => {
                 match rhs.value {
                     // Right side has to be a function call
-                    Expression::FunctionCall(fun_id, arguments) => {
+                    Expression::FunctionCall(fun_id_node, arguments) => {
+                        let fun_id_pos = fun_id_node.pos();
+                        let fun_id = fun_id_node.value;
 
                         // check lhs assignees are defined
                         let (assignees, errors): (Vec<_>, Vec<_>) = assignees.into_iter().map(|a| self.check_assignee(a, module_id, types)).partition(|r| r.is_ok());
@@ -975,6 +2268,7 @@ impl<'ast> Checker<'ast> {
                             TypedAssignee::Identifier(v) => Ok(v),
                             a => Err(ErrorInner {
                                 pos: Some(pos),
+                                code: ErrorCode::InvalidStatement,
                                 message: format!("Only assignment to identifiers is supported, found {}", a)
                             })
                         }).partition(|r| r.is_ok());
@@ -999,22 +2293,25 @@ impl<'ast> Checker<'ast> {
 
                         let query = FunctionQuery::new(&fun_id, &arguments_types, &vars_types);
 
-                        let f = self.find_function(&query);
+                        let f = self.find_or_instantiate_function(&query, module_id, types, pos).map_err(|e| vec![e])?;
 
                         match f {
                     		// the function has to be defined
                     		Some(f) => {
+                                self.index_call(fun_id, &f.signature, fun_id_pos, module_id);
 
                                 let call = TypedExpressionList::FunctionCall(f.clone(), arguments_checked, f.signature.outputs.clone());
 
                                 Ok(TypedStatement::MultipleDefinition(variables, call))
                     		},
                     		None => Err(ErrorInner {                         pos: Some(pos),
+ code: ErrorCode::FunctionNotFound,
  message: format!("Function definition for function {} with signature {} not found.", fun_id, query) }),
                     	}
                     }
                     _ => Err(ErrorInner {
                         pos: Some(pos),
+                        code: ErrorCode::InvalidStatement,
                         message: format!("{} should be a function call", rhs),
                     }),
                 }.map_err(|e| vec![e])
@@ -1022,7 +2319,7 @@ impl<'ast> Checker<'ast> {
         }
     }
 
-    fn check_assignee<T: Field>(
+    fn check_assignee(
         &mut self,
         assignee: AssigneeNode<'ast, T>,
         module_id: &ModuleId,
@@ -1032,12 +2329,17 @@ impl<'ast> Checker<'ast> {
         // check that the assignee is declared
         match assignee.value {
             Assignee::Identifier(variable_name) => match self.get_scope(&variable_name) {
-                Some(var) => Ok(TypedAssignee::Identifier(Variable::with_id_and_type(
-                    variable_name,
-                    var.id._type.clone(),
-                ))),
+                Some(var) => {
+                    let ty = var.id._type.clone();
+                    self.index_variable(variable_name, &ty, pos, module_id);
+                    Ok(TypedAssignee::Identifier(Variable::with_id_and_type(
+                        variable_name,
+                        ty,
+                    )))
+                }
                 None => Err(ErrorInner {
                     pos: Some(assignee.pos()),
+                    code: ErrorCode::UndeclaredVariable,
                     message: format!("Variable `{}` is undeclared", variable_name),
                 }),
             },
@@ -1062,6 +2364,7 @@ impl<'ast> Checker<'ast> {
                             e => Err(ErrorInner {
                                 pos: Some(pos),
 
+                                code: ErrorCode::TypeMismatch,
                                 message: format!(
                                     "Expected array {} index to have type field, found {}",
                                     checked_assignee,
@@ -1078,6 +2381,7 @@ impl<'ast> Checker<'ast> {
                     ty => Err(ErrorInner {
                         pos: Some(pos),
 
+                        code: ErrorCode::InvalidAccess,
                         message: format!(
                             "Cannot access element at index {} on {} of type {}",
                             index, checked_assignee, ty,
@@ -1094,12 +2398,14 @@ impl<'ast> Checker<'ast> {
                         Some(_) => Ok(TypedAssignee::Member(box checked_assignee, member.into())),
                         None => Err(ErrorInner {
                             pos: Some(pos),
+                            code: ErrorCode::InvalidAccess,
                             message: format!("{} doesn't have member {}", ty, member),
                         }),
                     },
                     ty => Err(ErrorInner {
                         pos: Some(pos),
 
+                        code: ErrorCode::InvalidAccess,
                         message: format!(
                             "Cannot access field {} on {} as of type {}",
                             member, checked_assignee, ty,
@@ -1107,10 +2413,44 @@ impl<'ast> Checker<'ast> {
                     }),
                 }
             }
+            Assignee::Element(box assignee, index) => {
+                let checked_assignee = self.check_assignee(assignee, module_id, &types)?;
+                let index = Self::check_element_index(index, pos)?;
+
+                let ty = checked_assignee.get_type();
+                match &ty {
+                    Type::Tuple(tuple_type) => match index < tuple_type.len() {
+                        true => Ok(TypedAssignee::Element(box checked_assignee, index)),
+                        false => Err(ErrorInner {
+                            pos: Some(pos),
+                            code: ErrorCode::InvalidAccess,
+                            message: format!("{} doesn't have element {}", ty, index),
+                        }),
+                    },
+                    ty => Err(ErrorInner {
+                        pos: Some(pos),
+
+                        code: ErrorCode::InvalidAccess,
+                        message: format!(
+                            "Cannot access element {} on {} as of type {}",
+                            index, checked_assignee, ty,
+                        ),
+                    }),
+                }
+            }
         }
     }
 
-    fn check_spread_or_expression<T: Field>(
+    /// Parse the index of a tuple element, which is out of range if it does not fit in a `usize`
+    fn check_element_index(index: &str, pos: (Position, Position)) -> Result<usize, ErrorInner> {
+        index.parse().map_err(|_| ErrorInner {
+            pos: Some(pos),
+            code: ErrorCode::ValueOutOfRange,
+            message: format!("Tuple element {} is out of range", index),
+        })
+    }
+
+    fn check_spread_or_expression(
         &mut self,
         spread_or_expression: SpreadOrExpression<'ast, T>,
         module_id: &ModuleId,
@@ -1145,6 +2485,12 @@ impl<'ast> Checker<'ast> {
                                         FieldElementExpression::Number(T::from(i)),
                                     )
                                     .into(),
+                                    Type::Int(bitwidth) => {
+                                        TypedExpression::Int(UExpression::select(
+                                            e.clone().annotate(Type::Int(*bitwidth), size),
+                                            FieldElementExpression::Number(T::from(i)),
+                                        ))
+                                    }
                                     Type::Boolean => BooleanExpression::select(
                                         e.clone().annotate(Type::Boolean, size),
                                         FieldElementExpression::Number(T::from(i)),
@@ -1164,6 +2510,14 @@ impl<'ast> Checker<'ast> {
                                     )
                                     .annotate(members.clone())
                                     .into(),
+                                    Type::Tuple(tuple_type) => TupleExpressionInner::Select(
+                                        box e
+                                            .clone()
+                                            .annotate(Type::Tuple(tuple_type.clone()), size),
+                                        box FieldElementExpression::Number(T::from(i)),
+                                    )
+                                    .annotate(tuple_type.clone())
+                                    .into(),
                                 })
                                 .collect()),
                         }
@@ -1171,6 +2525,7 @@ impl<'ast> Checker<'ast> {
                     e => Err(ErrorInner {
                         pos: Some(pos),
 
+                        code: ErrorCode::TypeMismatch,
                         message: format!(
                             "Expected spread operator to apply on array, found {}",
                             e.get_type()
@@ -1188,7 +2543,69 @@ impl<'ast> Checker<'ast> {
         }
     }
 
-    fn check_expression<T: Field>(
+    fn check_comparison(
+        &mut self,
+        op: Comparison,
+        e1: ExpressionNode<'ast, T>,
+        e2: ExpressionNode<'ast, T>,
+        pos: (Position, Position),
+        module_id: &ModuleId,
+        types: &TypeMap,
+    ) -> Result<TypedExpression<'ast, T>, ErrorInner> {
+        let e1_checked = self.check_expression(e1, module_id, &types)?;
+        let e2_checked = self.check_expression(e2, module_id, &types)?;
+        match (e1_checked, e2_checked) {
+            (TypedExpression::FieldElement(e1), TypedExpression::FieldElement(e2)) => {
+                Ok(BooleanExpression::field_comparison(op, e1, e2).into())
+            }
+            (TypedExpression::Uint(e1), TypedExpression::Uint(e2))
+                if e1.get_type() == e2.get_type() =>
+            {
+                Ok(BooleanExpression::uint_comparison(op, e1, e2).into())
+            }
+            (TypedExpression::Int(e1), TypedExpression::Int(e2))
+                if e1.bitwidth() == e2.bitwidth() =>
+            {
+                Ok(e1.signed_comparison(op, e2).into())
+            }
+            (e1, e2) => Err(ErrorInner {
+                pos: Some(pos),
+                code: ErrorCode::InvalidOperands,
+                message: format!(
+                    "Cannot compare {} of type {} to {} of type {}",
+                    e1,
+                    e1.get_type(),
+                    e2,
+                    e2.get_type()
+                ),
+            }),
+        }
+    }
+
+    /// Build the expression referring to the identifier `name` of type `ty`
+    fn identifier_expression(name: &'ast str, ty: Type) -> TypedExpression<'ast, T> {
+        match ty {
+            Type::Boolean => BooleanExpression::Identifier(name.into()).into(),
+            Type::Uint(bitwidth) => UExpressionInner::Identifier(name.into())
+                .annotate(bitwidth)
+                .into(),
+            Type::Int(bitwidth) => {
+                TypedExpression::Int(UExpressionInner::Identifier(name.into()).annotate(bitwidth))
+            }
+            Type::FieldElement => FieldElementExpression::Identifier(name.into()).into(),
+            Type::Array(array_type) => ArrayExpressionInner::Identifier(name.into())
+                .annotate(*array_type.ty, array_type.size)
+                .into(),
+            Type::Struct(members) => StructExpressionInner::Identifier(name.into())
+                .annotate(members)
+                .into(),
+            Type::Tuple(tuple_type) => TupleExpressionInner::Identifier(name.into())
+                .annotate(tuple_type)
+                .into(),
+        }
+    }
+
+    fn check_expression(
         &mut self,
         expr: ExpressionNode<'ast, T>,
         module_id: &ModuleId,
@@ -1201,27 +2618,33 @@ impl<'ast> Checker<'ast> {
             Expression::Identifier(name) => {
                 // check that `id` is defined in the scope
                 match self.get_scope(&name) {
-                    Some(v) => match v.id.get_type() {
-                        Type::Boolean => Ok(BooleanExpression::Identifier(name.into()).into()),
-                        Type::Uint(bitwidth) => Ok(UExpressionInner::Identifier(name.into())
-                            .annotate(bitwidth)
-                            .into()),
-                        Type::FieldElement => {
-                            Ok(FieldElementExpression::Identifier(name.into()).into())
-                        }
-                        Type::Array(array_type) => {
-                            Ok(ArrayExpressionInner::Identifier(name.into())
-                                .annotate(*array_type.ty, array_type.size)
-                                .into())
-                        }
-                        Type::Struct(members) => Ok(StructExpressionInner::Identifier(name.into())
-                            .annotate(members)
-                            .into()),
-                    },
-                    None => Err(ErrorInner {
-                        pos: Some(pos),
-                        message: format!("Identifier \"{}\" is undefined", name),
-                    }),
+                    Some(v) => {
+                        let ty = v.id.get_type();
+                        self.unused.remove(name);
+                        self.index_variable(name, &ty, pos, module_id);
+                        Ok(Self::identifier_expression(name, ty))
+                    }
+                    // constants are inlined
+                    None => {
+                        let c = self
+                            .constants
+                            .get(name)
+                            .cloned()
+                            .ok_or_else(|| ErrorInner {
+                                pos: Some(pos),
+                                code: ErrorCode::UndefinedIdentifier,
+                                message: format!("Identifier \"{}\" is undefined", name),
+                            })?;
+                        self.use_symbol(name, module_id);
+                        self.index_reference(
+                            name,
+                            c.get_type().to_string(),
+                            pos,
+                            module_id,
+                            Target::Symbol(module_id.clone(), name.to_string()),
+                        );
+                        Ok(c)
+                    }
                 }
             }
             Expression::Add(box e1, box e2) => {
@@ -1239,6 +2662,7 @@ impl<'ast> Checker<'ast> {
                             Err(ErrorInner {
                                 pos: Some(pos),
 
+                                code: ErrorCode::InvalidOperands,
                                 message: format!(
                                     "Cannot apply `+` to {:?}, {:?}",
                                     e1.get_type(),
@@ -1247,9 +2671,15 @@ impl<'ast> Checker<'ast> {
                             })
                         }
                     }
+                    (TypedExpression::Int(e1), TypedExpression::Int(e2))
+                        if e1.bitwidth() == e2.bitwidth() =>
+                    {
+                        Ok(TypedExpression::Int(UExpression::add(e1, e2)))
+                    }
                     (t1, t2) => Err(ErrorInner {
                         pos: Some(pos),
 
+                        code: ErrorCode::InvalidOperands,
                         message: format!(
                             "Cannot apply `+` to {:?}, {:?}",
                             t1.get_type(),
@@ -1273,6 +2703,7 @@ impl<'ast> Checker<'ast> {
                             Err(ErrorInner {
                                 pos: Some(pos),
 
+                                code: ErrorCode::InvalidOperands,
                                 message: format!(
                                     "Cannot apply `+` to {:?}, {:?}",
                                     e1.get_type(),
@@ -1281,9 +2712,15 @@ impl<'ast> Checker<'ast> {
                             })
                         }
                     }
+                    (TypedExpression::Int(e1), TypedExpression::Int(e2))
+                        if e1.bitwidth() == e2.bitwidth() =>
+                    {
+                        Ok(TypedExpression::Int(UExpression::sub(e1, e2)))
+                    }
                     (t1, t2) => Err(ErrorInner {
                         pos: Some(pos),
 
+                        code: ErrorCode::InvalidOperands,
                         message: format!(
                             "Expected only field elements, found {:?}, {:?}",
                             t1.get_type(),
@@ -1307,6 +2744,7 @@ impl<'ast> Checker<'ast> {
                             Err(ErrorInner {
                                 pos: Some(pos),
 
+                                code: ErrorCode::InvalidOperands,
                                 message: format!(
                                     "Cannot apply `*` to {:?}, {:?}",
                                     e1.get_type(),
@@ -1315,9 +2753,15 @@ impl<'ast> Checker<'ast> {
                             })
                         }
                     }
+                    (TypedExpression::Int(e1), TypedExpression::Int(e2))
+                        if e1.bitwidth() == e2.bitwidth() =>
+                    {
+                        Ok(TypedExpression::Int(UExpression::mult(e1, e2)))
+                    }
                     (t1, t2) => Err(ErrorInner {
                         pos: Some(pos),
 
+                        code: ErrorCode::InvalidOperands,
                         message: format!(
                             "Cannot apply `*` to {:?}, {:?}",
                             t1.get_type(),
@@ -1334,12 +2778,72 @@ impl<'ast> Checker<'ast> {
                     (TypedExpression::FieldElement(e1), TypedExpression::FieldElement(e2)) => {
                         Ok(FieldElementExpression::Div(box e1, box e2).into())
                     }
-                    (t1, t2) => Err(ErrorInner {
-                        pos: Some(pos),
+                    (TypedExpression::Uint(e1), TypedExpression::Uint(e2)) => {
+                        if e1.get_type() == e2.get_type() {
+                            Ok(UExpression::div(e1, e2).into())
+                        } else {
+                            Err(ErrorInner {
+                                pos: Some(pos),
 
-                        message: format!(
-                            "Expected only field elements, found {:?}, {:?}",
-                            t1.get_type(),
+                                code: ErrorCode::InvalidOperands,
+                                message: format!(
+                                    "Cannot apply `/` to {:?}, {:?}",
+                                    e1.get_type(),
+                                    e2.get_type()
+                                ),
+                            })
+                        }
+                    }
+                    (TypedExpression::Int(e1), TypedExpression::Int(e2))
+                        if e1.bitwidth() == e2.bitwidth() =>
+                    {
+                        Ok(TypedExpression::Int(UExpression::signed_div(e1, e2)))
+                    }
+                    (t1, t2) => Err(ErrorInner {
+                        pos: Some(pos),
+
+                        code: ErrorCode::InvalidOperands,
+                        message: format!(
+                            "Expected only field elements, found {:?}, {:?}",
+                            t1.get_type(),
+                            t2.get_type()
+                        ),
+                    }),
+                }
+            }
+            Expression::Rem(box e1, box e2) => {
+                let e1_checked = self.check_expression(e1, module_id, &types)?;
+                let e2_checked = self.check_expression(e2, module_id, &types)?;
+
+                match (e1_checked, e2_checked) {
+                    (TypedExpression::Uint(e1), TypedExpression::Uint(e2)) => {
+                        if e1.get_type() == e2.get_type() {
+                            Ok(UExpression::rem(e1, e2).into())
+                        } else {
+                            Err(ErrorInner {
+                                pos: Some(pos),
+
+                                code: ErrorCode::InvalidOperands,
+                                message: format!(
+                                    "Cannot apply `%` to {:?}, {:?}",
+                                    e1.get_type(),
+                                    e2.get_type()
+                                ),
+                            })
+                        }
+                    }
+                    (TypedExpression::Int(e1), TypedExpression::Int(e2))
+                        if e1.bitwidth() == e2.bitwidth() =>
+                    {
+                        Ok(TypedExpression::Int(UExpression::signed_rem(e1, e2)))
+                    }
+                    (t1, t2) => Err(ErrorInner {
+                        pos: Some(pos),
+
+                        code: ErrorCode::InvalidOperands,
+                        message: format!(
+                            "Cannot apply `%` to {:?}, {:?}",
+                            t1.get_type(),
                             t2.get_type()
                         ),
                     }),
@@ -1356,6 +2860,7 @@ impl<'ast> Checker<'ast> {
                     (t1, t2) => Err(ErrorInner {
                         pos: Some(pos),
 
+                        code: ErrorCode::InvalidOperands,
                         message: format!(
                             "Expected only field elements, found {:?}, {:?}",
                             t1.get_type(),
@@ -1390,20 +2895,30 @@ impl<'ast> Checker<'ast> {
                                     let ty = consequence.ty().clone();
                                     Ok(StructExpressionInner::IfElse(box condition, box consequence, box alternative).annotate(ty).into())
                                 },
+                                (TypedExpression::Tuple(consequence), TypedExpression::Tuple(alternative)) => {
+                                    let ty = consequence.ty().clone();
+                                    Ok(TupleExpressionInner::IfElse(box condition, box consequence, box alternative).annotate(ty).into())
+                                },
                                 (TypedExpression::Uint(consequence), TypedExpression::Uint(alternative)) => {
                                     let bitwidth = consequence.bitwidth();
                                     Ok(UExpressionInner::IfElse(box condition, box consequence, box alternative).annotate(bitwidth).into())
                                 },
+                                (TypedExpression::Int(consequence), TypedExpression::Int(alternative)) => {
+                                    let bitwidth = consequence.bitwidth();
+                                    Ok(TypedExpression::Int(UExpressionInner::IfElse(box condition, box consequence, box alternative).annotate(bitwidth)))
+                                },
                                 _ => unreachable!("types should match here as we checked them explicitly")
                             }
                             false => Err(ErrorInner {
                                 pos: Some(pos),
+                                code: ErrorCode::TypeMismatch,
                                 message: format!("{{consequence}} and {{alternative}} in `if/else` expression should have the same type, found {}, {}", consequence_type, alternative_type)
                             })
                         }
                     }
                     c => Err(ErrorInner {
                         pos: Some(pos),
+                        code: ErrorCode::TypeMismatch,
                         message: format!(
                             "{{condition}} after `if` should be a boolean, found {}",
                             c.get_type()
@@ -1415,7 +2930,35 @@ impl<'ast> Checker<'ast> {
             Expression::U8Constant(n) => Ok(UExpressionInner::Value(n.into()).annotate(8).into()),
             Expression::U16Constant(n) => Ok(UExpressionInner::Value(n.into()).annotate(16).into()),
             Expression::U32Constant(n) => Ok(UExpressionInner::Value(n.into()).annotate(32).into()),
-            Expression::FunctionCall(fun_id, arguments) => {
+            Expression::U64Constant(n) => Ok(UExpressionInner::Value(n.into()).annotate(64).into()),
+            Expression::U128Constant(_) => Err(ErrorInner {
+                pos: Some(pos),
+                code: ErrorCode::UnsupportedType,
+                message: String::from("Type u128 is reserved but not supported yet"),
+            }),
+            Expression::IntConstant(n, bitwidth) => {
+                let min = -(1i128 << (bitwidth - 1));
+                let max = (1i128 << (bitwidth - 1)) - 1;
+                // digits which do not even fit in an i128 are out of range as well
+                match n.parse::<i128>().ok().filter(|n| *n >= min && *n <= max) {
+                    // store the two's complement representation of the value
+                    Some(n) => Ok(TypedExpression::Int(
+                        UExpressionInner::Value(
+                            n as u128 & (u128::max_value() >> (128 - bitwidth)),
+                        )
+                        .annotate(bitwidth),
+                    )),
+                    None => Err(ErrorInner {
+                        pos: Some(pos),
+                        code: ErrorCode::ValueOutOfRange,
+                        message: format!("Value {} is out of range for type i{}", n, bitwidth),
+                    }),
+                }
+            }
+            Expression::FunctionCall(fun_id_node, arguments) => {
+                let fun_id_pos = fun_id_node.pos();
+                let fun_id = fun_id_node.value;
+
                 // check the arguments
                 let mut arguments_checked = vec![];
                 for arg in arguments {
@@ -1432,11 +2975,13 @@ impl<'ast> Checker<'ast> {
                 // we use type inference to determine the type of the return, so we don't specify it
                 let query = FunctionQuery::new(&fun_id, &arguments_types, &vec![None]);
 
-                let f = self.find_function(&query);
+                let f = self.find_or_instantiate_function(&query, module_id, types, pos)?;
 
                 match f {
                     // the function has to be defined
                     Some(f) => {
+                        self.index_call(fun_id, &f.signature, fun_id_pos, module_id);
+
                         // the return count has to be 1
                         match f.signature.outputs.len() {
                             1 => match &f.signature.outputs[0] {
@@ -1465,6 +3010,16 @@ impl<'ast> Checker<'ast> {
                                 )
                                 .annotate(*bitwidth)
                                 .into()),
+                                Type::Int(bitwidth) => Ok(TypedExpression::Int(
+                                    UExpressionInner::FunctionCall(
+                                        FunctionKey {
+                                            id: f.id.clone(),
+                                            signature: f.signature.clone(),
+                                        },
+                                        arguments_checked,
+                                    )
+                                    .annotate(*bitwidth),
+                                )),
                                 Type::Struct(members) => Ok(StructExpressionInner::FunctionCall(
                                     FunctionKey {
                                         id: f.id.clone(),
@@ -1474,6 +3029,15 @@ impl<'ast> Checker<'ast> {
                                 )
                                 .annotate(members.clone())
                                 .into()),
+                                Type::Tuple(tuple_type) => Ok(TupleExpressionInner::FunctionCall(
+                                    FunctionKey {
+                                        id: f.id.clone(),
+                                        signature: f.signature.clone(),
+                                    },
+                                    arguments_checked,
+                                )
+                                .annotate(tuple_type.clone())
+                                .into()),
                                 Type::Array(array_type) => Ok(ArrayExpressionInner::FunctionCall(
                                     FunctionKey {
                                         id: f.id.clone(),
@@ -1487,6 +3051,7 @@ impl<'ast> Checker<'ast> {
                             n => Err(ErrorInner {
                                 pos: Some(pos),
 
+                                code: ErrorCode::InvalidStatement,
                                 message: format!(
                                     "{} returns {} values but is called outside of a definition",
                                     f.id, n
@@ -1497,6 +3062,7 @@ impl<'ast> Checker<'ast> {
                     None => Err(ErrorInner {
                         pos: Some(pos),
 
+                        code: ErrorCode::FunctionNotFound,
                         message: format!(
                             "Function definition for function {} with signature {} not found.",
                             fun_id, query
@@ -1505,42 +3071,10 @@ impl<'ast> Checker<'ast> {
                 }
             }
             Expression::Lt(box e1, box e2) => {
-                let e1_checked = self.check_expression(e1, module_id, &types)?;
-                let e2_checked = self.check_expression(e2, module_id, &types)?;
-                match (e1_checked, e2_checked) {
-                    (TypedExpression::FieldElement(e1), TypedExpression::FieldElement(e2)) => {
-                        Ok(BooleanExpression::Lt(box e1, box e2).into())
-                    }
-                    (e1, e2) => Err(ErrorInner {
-                        pos: Some(pos),
-                        message: format!(
-                            "Cannot compare {} of type {} to {} of type {}",
-                            e1,
-                            e1.get_type(),
-                            e2,
-                            e2.get_type()
-                        ),
-                    }),
-                }
+                self.check_comparison(Comparison::Lt, e1, e2, pos, module_id, types)
             }
             Expression::Le(box e1, box e2) => {
-                let e1_checked = self.check_expression(e1, module_id, &types)?;
-                let e2_checked = self.check_expression(e2, module_id, &types)?;
-                match (e1_checked, e2_checked) {
-                    (TypedExpression::FieldElement(e1), TypedExpression::FieldElement(e2)) => {
-                        Ok(BooleanExpression::Le(box e1, box e2).into())
-                    }
-                    (e1, e2) => Err(ErrorInner {
-                        pos: Some(pos),
-                        message: format!(
-                            "Cannot compare {} of type {} to {} of type {}",
-                            e1,
-                            e1.get_type(),
-                            e2,
-                            e2.get_type()
-                        ),
-                    }),
-                }
+                self.check_comparison(Comparison::Le, e1, e2, pos, module_id, types)
             }
             Expression::Eq(box e1, box e2) => {
                 let e1_checked = self.check_expression(e1, module_id, &types)?;
@@ -1558,6 +3092,7 @@ impl<'ast> Checker<'ast> {
                         } else {
                             Err(ErrorInner {
                                 pos: Some(pos),
+                                code: ErrorCode::InvalidOperands,
                                 message: format!(
                                     "Cannot compare {} of type {} to {} of type {}",
                                     e1,
@@ -1574,6 +3109,7 @@ impl<'ast> Checker<'ast> {
                         } else {
                             Err(ErrorInner {
                                 pos: Some(pos),
+                                code: ErrorCode::InvalidOperands,
                                 message: format!(
                                     "Cannot compare {} of type {} to {} of type {}",
                                     e1,
@@ -1590,6 +3126,7 @@ impl<'ast> Checker<'ast> {
                         } else {
                             Err(ErrorInner {
                                 pos: Some(pos),
+                                code: ErrorCode::InvalidOperands,
                                 message: format!(
                                     "Cannot compare {} of type {} to {} of type {}",
                                     e1,
@@ -1600,8 +3137,14 @@ impl<'ast> Checker<'ast> {
                             })
                         }
                     }
+                    (TypedExpression::Int(e1), TypedExpression::Int(e2))
+                        if e1.bitwidth() == e2.bitwidth() =>
+                    {
+                        Ok(BooleanExpression::UintEq(box e1, box e2).into())
+                    }
                     (e1, e2) => Err(ErrorInner {
                         pos: Some(pos),
+                        code: ErrorCode::InvalidOperands,
                         message: format!(
                             "Cannot compare {} of type {} to {} of type {}",
                             e1,
@@ -1613,42 +3156,10 @@ impl<'ast> Checker<'ast> {
                 }
             }
             Expression::Ge(box e1, box e2) => {
-                let e1_checked = self.check_expression(e1, module_id, &types)?;
-                let e2_checked = self.check_expression(e2, module_id, &types)?;
-                match (e1_checked, e2_checked) {
-                    (TypedExpression::FieldElement(e1), TypedExpression::FieldElement(e2)) => {
-                        Ok(BooleanExpression::Ge(box e1, box e2).into())
-                    }
-                    (e1, e2) => Err(ErrorInner {
-                        pos: Some(pos),
-                        message: format!(
-                            "Cannot compare {} of type {} to {} of type {}",
-                            e1,
-                            e1.get_type(),
-                            e2,
-                            e2.get_type()
-                        ),
-                    }),
-                }
+                self.check_comparison(Comparison::Ge, e1, e2, pos, module_id, types)
             }
             Expression::Gt(box e1, box e2) => {
-                let e1_checked = self.check_expression(e1, module_id, &types)?;
-                let e2_checked = self.check_expression(e2, module_id, &types)?;
-                match (e1_checked, e2_checked) {
-                    (TypedExpression::FieldElement(e1), TypedExpression::FieldElement(e2)) => {
-                        Ok(BooleanExpression::Gt(box e1, box e2).into())
-                    }
-                    (e1, e2) => Err(ErrorInner {
-                        pos: Some(pos),
-                        message: format!(
-                            "Cannot compare {} of type {} to {} of type {}",
-                            e1,
-                            e1.get_type(),
-                            e2,
-                            e2.get_type()
-                        ),
-                    }),
-                }
+                self.check_comparison(Comparison::Gt, e1, e2, pos, module_id, types)
             }
             Expression::Select(box array, box index) => {
                 let array = self.check_expression(array, module_id, &types)?;
@@ -1686,6 +3197,7 @@ impl<'ast> Checker<'ast> {
                                 TypedExpression::FieldElement(FieldElementExpression::Number(n)) => Ok(n.to_dec_string().parse::<usize>().unwrap()),
                                 e => Err(ErrorInner {
                                     pos: Some(pos),
+                                    code: ErrorCode::NonConstantExpression,
                                     message: format!(
                                         "Expected the lower bound of the range to be a constant field, found {}",
                                         e
@@ -1697,6 +3209,7 @@ impl<'ast> Checker<'ast> {
                                 TypedExpression::FieldElement(FieldElementExpression::Number(n)) => Ok(n.to_dec_string().parse::<usize>().unwrap()),
                                 e => Err(ErrorInner {
                                     pos: Some(pos),
+                                    code: ErrorCode::NonConstantExpression,
                                     message: format!(
                                         "Expected the higher bound of the range to be a constant field, found {}",
                                         e
@@ -1707,6 +3220,7 @@ impl<'ast> Checker<'ast> {
                             match (from, to, array_size) {
                                 (f, _, s) if f > s => Err(ErrorInner {
                                     pos: Some(pos),
+                                    code: ErrorCode::OutOfBounds,
                                     message: format!(
                                         "Lower range bound {} is out of array bounds [0, {}]",
                                         f, s,
@@ -1714,6 +3228,7 @@ impl<'ast> Checker<'ast> {
                                 }),
                                 (_, t, s) if t > s => Err(ErrorInner {
                                     pos: Some(pos),
+                                    code: ErrorCode::OutOfBounds,
                                     message: format!(
                                         "Higher range bound {} is out of array bounds [0, {}]",
                                         t, s,
@@ -1721,6 +3236,7 @@ impl<'ast> Checker<'ast> {
                                 }),
                                 (f, t, _) if f > t => Err(ErrorInner {
                                     pos: Some(pos),
+                                    code: ErrorCode::OutOfBounds,
                                     message: format!(
                                         "Lower range bound {} is larger than higher range bound {}",
                                         f, t,
@@ -1745,6 +3261,13 @@ impl<'ast> Checker<'ast> {
                                             )
                                             .annotate(bitwidth)
                                             .into(),
+                                            Type::Int(bitwidth) => TypedExpression::Int(
+                                                UExpressionInner::Select(
+                                                    box array.clone(),
+                                                    box FieldElementExpression::Number(T::from(i)),
+                                                )
+                                                .annotate(bitwidth),
+                                            ),
                                             Type::Struct(struct_ty) => {
                                                 StructExpressionInner::Select(
                                                     box array.clone(),
@@ -1753,6 +3276,12 @@ impl<'ast> Checker<'ast> {
                                                 .annotate(struct_ty)
                                                 .into()
                                             }
+                                            Type::Tuple(tuple_ty) => TupleExpressionInner::Select(
+                                                box array.clone(),
+                                                box FieldElementExpression::Number(T::from(i)),
+                                            )
+                                            .annotate(tuple_ty)
+                                            .into(),
                                             Type::Array(array_ty) => ArrayExpressionInner::Select(
                                                 box array.clone(),
                                                 box FieldElementExpression::Number(T::from(i)),
@@ -1768,6 +3297,7 @@ impl<'ast> Checker<'ast> {
                         }
                         e => Err(ErrorInner {
                             pos: Some(pos),
+                            code: ErrorCode::InvalidAccess,
                             message: format!(
                                 "Cannot access slice of expression {} of type {}",
                                 e,
@@ -1783,13 +3313,18 @@ impl<'ast> Checker<'ast> {
                                         Ok(FieldElementExpression::select(a, i).into())
                                     }
                                     Type::Uint(..) => Ok(UExpression::select(a, i).into()),
+                                    Type::Int(..) => {
+                                        Ok(TypedExpression::Int(UExpression::select(a, i)))
+                                    }
                                     Type::Boolean => Ok(BooleanExpression::select(a, i).into()),
                                     Type::Array(..) => Ok(ArrayExpression::select(a, i).into()),
                                     Type::Struct(..) => Ok(StructExpression::select(a, i).into()),
+                                    Type::Tuple(..) => Ok(TupleExpression::select(a, i).into()),
                                 }
                             }
                             (a, e) => Err(ErrorInner {
                                 pos: Some(pos),
+                                code: ErrorCode::InvalidAccess,
                                 message: format!(
                                     "Cannot access element {} on expression of type {}",
                                     e,
@@ -1817,6 +3352,9 @@ impl<'ast> Checker<'ast> {
                                     Ok(BooleanExpression::member(s, id.to_string()).into())
                                 }
                                 Type::Uint(..) => Ok(UExpression::member(s, id.to_string()).into()),
+                                Type::Int(..) => {
+                                    Ok(TypedExpression::Int(UExpression::member(s, id.to_string())))
+                                }
                                 Type::Array(array_type) => {
                                     Ok(ArrayExpressionInner::Member(box s.clone(), id.to_string())
                                         .annotate(*array_type.ty.clone(), array_type.size)
@@ -1825,15 +3363,20 @@ impl<'ast> Checker<'ast> {
                                 Type::Struct(..) => {
                                     Ok(StructExpression::member(s.clone(), id.to_string()).into())
                                 }
+                                Type::Tuple(..) => {
+                                    Ok(TupleExpression::member(s.clone(), id.to_string()).into())
+                                }
                             },
                             None => Err(ErrorInner {
                                 pos: Some(pos),
+                                code: ErrorCode::InvalidAccess,
                                 message: format!("{} doesn't have member {}", s.get_type(), id,),
                             }),
                         }
                     }
                     e => Err(ErrorInner {
                         pos: Some(pos),
+                        code: ErrorCode::InvalidAccess,
                         message: format!(
                             "Cannot access member {} on expression of type {}",
                             id,
@@ -1842,6 +3385,55 @@ impl<'ast> Checker<'ast> {
                     }),
                 }
             }
+            Expression::Element(box e, index) => {
+                let e = self.check_expression(e, module_id, &types)?;
+                let index = Self::check_element_index(index, pos)?;
+
+                match e {
+                    TypedExpression::Tuple(t) => {
+                        // check that the tuple has that element and return its type if it does
+                        match t.ty().elements.get(index).cloned() {
+                            Some(ty) => match ty {
+                                Type::FieldElement => {
+                                    Ok(FieldElementExpression::element(t, index).into())
+                                }
+                                Type::Boolean => Ok(BooleanExpression::element(t, index).into()),
+                                Type::Uint(..) => Ok(UExpression::element(t, index).into()),
+                                Type::Int(..) => {
+                                    Ok(TypedExpression::Int(UExpression::element(t, index)))
+                                }
+                                Type::Array(..) => Ok(ArrayExpression::element(t, index).into()),
+                                Type::Struct(..) => Ok(StructExpression::element(t, index).into()),
+                                Type::Tuple(..) => Ok(TupleExpression::element(t, index).into()),
+                            },
+                            None => Err(ErrorInner {
+                                pos: Some(pos),
+                                code: ErrorCode::InvalidAccess,
+                                message: format!("{} doesn't have element {}", t.get_type(), index),
+                            }),
+                        }
+                    }
+                    e => Err(ErrorInner {
+                        pos: Some(pos),
+                        code: ErrorCode::InvalidAccess,
+                        message: format!(
+                            "Cannot access element {} on expression of type {}",
+                            index,
+                            e.get_type()
+                        ),
+                    }),
+                }
+            }
+            Expression::InlineTuple(elements) => {
+                let elements = elements
+                    .into_iter()
+                    .map(|e| self.check_expression(e, module_id, &types))
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                let ty = TupleType::new(elements.iter().map(|e| e.get_type()).collect());
+
+                Ok(TupleExpressionInner::Value(elements).annotate(ty).into())
+            }
             Expression::InlineArray(expressions) => {
                 // check each expression, getting its type
                 let mut expressions_checked = vec![];
@@ -1864,6 +3456,7 @@ impl<'ast> Checker<'ast> {
                                 e => Err(ErrorInner {
                                     pos: Some(pos),
 
+                                    code: ErrorCode::TypeMismatch,
                                     message: format!(
                                         "Expected {} to have type {}, but type is {}",
                                         e,
@@ -1891,6 +3484,7 @@ impl<'ast> Checker<'ast> {
                                 e => Err(ErrorInner {
                                     pos: Some(pos),
 
+                                    code: ErrorCode::TypeMismatch,
                                     message: format!(
                                         "Expected {} to have type {}, but type is {}",
                                         e,
@@ -1908,19 +3502,20 @@ impl<'ast> Checker<'ast> {
                             .annotate(Type::Boolean, size)
                             .into())
                     }
-                    ty @ Type::Uint(..) => {
+                    ty @ Type::Uint(..) | ty @ Type::Int(..) => {
                         // we check all expressions have that same type
                         let mut unwrapped_expressions = vec![];
 
                         for e in expressions_checked {
                             let unwrapped_e = match e {
-                                TypedExpression::Uint(e) => {
+                                e @ TypedExpression::Uint(..) | e @ TypedExpression::Int(..) => {
                                     if e.get_type() == ty {
                                         Ok(e)
                                     } else {
                                         Err(ErrorInner {
                                             pos: Some(pos),
 
+                                            code: ErrorCode::TypeMismatch,
                                             message: format!(
                                                 "Expected {} to have type {}, but type is {}",
                                                 e,
@@ -1933,6 +3528,7 @@ impl<'ast> Checker<'ast> {
                                 e => Err(ErrorInner {
                                     pos: Some(pos),
 
+                                    code: ErrorCode::TypeMismatch,
                                     message: format!(
                                         "Expected {} to have type {}, but type is {}",
                                         e,
@@ -1963,6 +3559,7 @@ impl<'ast> Checker<'ast> {
                                         Err(ErrorInner {
                                             pos: Some(pos),
 
+                                            code: ErrorCode::TypeMismatch,
                                             message: format!(
                                                 "Expected {} to have type {}, but type is {}",
                                                 e,
@@ -1975,6 +3572,7 @@ impl<'ast> Checker<'ast> {
                                 e => Err(ErrorInner {
                                     pos: Some(pos),
 
+                                    code: ErrorCode::TypeMismatch,
                                     message: format!(
                                         "Expected {} to have type {}, but type is {}",
                                         e,
@@ -2005,6 +3603,51 @@ impl<'ast> Checker<'ast> {
                                         Err(ErrorInner {
                                             pos: Some(pos),
 
+                                            code: ErrorCode::TypeMismatch,
+                                            message: format!(
+                                                "Expected {} to have type {}, but type is {}",
+                                                e,
+                                                ty,
+                                                e.get_type()
+                                            ),
+                                        })
+                                    }
+                                }
+                                e => Err(ErrorInner {
+                                    pos: Some(pos),
+
+                                    code: ErrorCode::TypeMismatch,
+                                    message: format!(
+                                        "Expected {} to have type {}, but type is {}",
+                                        e,
+                                        ty,
+                                        e.get_type()
+                                    ),
+                                }),
+                            }?;
+                            unwrapped_expressions.push(unwrapped_e.into());
+                        }
+
+                        let size = unwrapped_expressions.len();
+
+                        Ok(ArrayExpressionInner::Value(unwrapped_expressions)
+                            .annotate(ty, size)
+                            .into())
+                    }
+                    ty @ Type::Tuple(..) => {
+                        // we check all expressions have that same type
+                        let mut unwrapped_expressions = vec![];
+
+                        for e in expressions_checked {
+                            let unwrapped_e = match e {
+                                TypedExpression::Tuple(e) => {
+                                    if e.get_type() == ty {
+                                        Ok(e)
+                                    } else {
+                                        Err(ErrorInner {
+                                            pos: Some(pos),
+
+                                            code: ErrorCode::TypeMismatch,
                                             message: format!(
                                                 "Expected {} to have type {}, but type is {}",
                                                 e,
@@ -2017,6 +3660,7 @@ impl<'ast> Checker<'ast> {
                                 e => Err(ErrorInner {
                                     pos: Some(pos),
 
+                                    code: ErrorCode::TypeMismatch,
                                     message: format!(
                                         "Expected {} to have type {}, but type is {}",
                                         e,
@@ -2052,6 +3696,7 @@ impl<'ast> Checker<'ast> {
                 if struct_type.len() != inline_members.len() {
                     return Err(ErrorInner {
                         pos: Some(pos),
+                        code: ErrorCode::TypeMismatch,
                         message: format!(
                             "Inline struct {} does not match {}",
                             Expression::InlineStruct(id.clone(), inline_members),
@@ -2079,6 +3724,7 @@ impl<'ast> Checker<'ast> {
                             if checked_type != *member.ty {
                                 return Err(ErrorInner {
                                     pos: Some(pos),
+                                    code: ErrorCode::TypeMismatch,
                                     message: format!(
                                         "Member {} of struct {} has type {}, found {} of type {}",
                                         member.id,
@@ -2095,6 +3741,7 @@ impl<'ast> Checker<'ast> {
                         None => {
                             return Err(ErrorInner {
                                 pos: Some(pos),
+                                code: ErrorCode::InvalidAccess,
                                 message: format!(
                                     "Member {} of struct {} not found in value {}",
                                     member.id,
@@ -2120,6 +3767,7 @@ impl<'ast> Checker<'ast> {
                     (e1, e2) => Err(ErrorInner {
                         pos: Some(pos),
 
+                        code: ErrorCode::InvalidOperands,
                         message: format!(
                             "cannot apply boolean operators to {} and {}",
                             e1.get_type(),
@@ -2138,6 +3786,7 @@ impl<'ast> Checker<'ast> {
                     (e1, e2) => Err(ErrorInner {
                         pos: Some(pos),
 
+                        code: ErrorCode::InvalidOperands,
                         message: format!("cannot compare {} to {}", e1.get_type(), e2.get_type()),
                     }),
                 }
@@ -2149,9 +3798,13 @@ impl<'ast> Checker<'ast> {
                     (TypedExpression::Uint(e1), TypedExpression::FieldElement(e2)) => {
                         Ok(UExpression::left_shift(e1, e2).into())
                     }
+                    (TypedExpression::Int(e1), TypedExpression::FieldElement(e2)) => {
+                        Ok(TypedExpression::Int(UExpression::left_shift(e1, e2)))
+                    }
                     (e1, e2) => Err(ErrorInner {
                         pos: Some(pos),
 
+                        code: ErrorCode::InvalidOperands,
                         message: format!(
                             "cannot left-shift {} by {}",
                             e1.get_type(),
@@ -2167,9 +3820,13 @@ impl<'ast> Checker<'ast> {
                     (TypedExpression::Uint(e1), TypedExpression::FieldElement(e2)) => {
                         Ok(UExpression::right_shift(e1, e2).into())
                     }
+                    (TypedExpression::Int(e1), TypedExpression::FieldElement(e2)) => Ok(
+                        TypedExpression::Int(UExpression::signed_right_shift(e1, e2)),
+                    ),
                     (e1, e2) => Err(ErrorInner {
                         pos: Some(pos),
 
+                        code: ErrorCode::InvalidOperands,
                         message: format!(
                             "cannot right-shift {} by {}",
                             e1.get_type(),
@@ -2189,6 +3846,7 @@ impl<'ast> Checker<'ast> {
                             Err(ErrorInner {
                                 pos: Some(pos),
 
+                                code: ErrorCode::InvalidOperands,
                                 message: format!(
                                     "Cannot apply `|` to {}, {}",
                                     e1.get_type(),
@@ -2197,9 +3855,15 @@ impl<'ast> Checker<'ast> {
                             })
                         }
                     }
+                    (TypedExpression::Int(e1), TypedExpression::Int(e2))
+                        if e1.bitwidth() == e2.bitwidth() =>
+                    {
+                        Ok(TypedExpression::Int(UExpression::or(e1, e2)))
+                    }
                     (e1, e2) => Err(ErrorInner {
                         pos: Some(pos),
 
+                        code: ErrorCode::InvalidOperands,
                         message: format!(
                             "Cannot apply `|` to {}, {}",
                             e1.get_type(),
@@ -2219,6 +3883,7 @@ impl<'ast> Checker<'ast> {
                             Err(ErrorInner {
                                 pos: Some(pos),
 
+                                code: ErrorCode::InvalidOperands,
                                 message: format!(
                                     "Cannot apply `&` to {}, {}",
                                     e1.get_type(),
@@ -2227,9 +3892,15 @@ impl<'ast> Checker<'ast> {
                             })
                         }
                     }
+                    (TypedExpression::Int(e1), TypedExpression::Int(e2))
+                        if e1.bitwidth() == e2.bitwidth() =>
+                    {
+                        Ok(TypedExpression::Int(UExpression::and(e1, e2)))
+                    }
                     (e1, e2) => Err(ErrorInner {
                         pos: Some(pos),
 
+                        code: ErrorCode::InvalidOperands,
                         message: format!(
                             "Cannot apply `&` to {}, {}",
                             e1.get_type(),
@@ -2249,6 +3920,7 @@ impl<'ast> Checker<'ast> {
                             Err(ErrorInner {
                                 pos: Some(pos),
 
+                                code: ErrorCode::InvalidOperands,
                                 message: format!(
                                     "Cannot apply `^` to {}, {}",
                                     e1.get_type(),
@@ -2257,9 +3929,15 @@ impl<'ast> Checker<'ast> {
                             })
                         }
                     }
+                    (TypedExpression::Int(e1), TypedExpression::Int(e2))
+                        if e1.bitwidth() == e2.bitwidth() =>
+                    {
+                        Ok(TypedExpression::Int(UExpression::xor(e1, e2)))
+                    }
                     (e1, e2) => Err(ErrorInner {
                         pos: Some(pos),
 
+                        code: ErrorCode::InvalidOperands,
                         message: format!(
                             "Cannot apply `^` to {}, {}",
                             e1.get_type(),
@@ -2273,9 +3951,11 @@ impl<'ast> Checker<'ast> {
                 match e_checked {
                     TypedExpression::Boolean(e) => Ok(BooleanExpression::Not(box e).into()),
                     TypedExpression::Uint(e) => Ok(UExpression::not(e).into()),
+                    TypedExpression::Int(e) => Ok(TypedExpression::Int(UExpression::not(e))),
                     e => Err(ErrorInner {
                         pos: Some(pos),
 
+                        code: ErrorCode::InvalidOperands,
                         message: format!("cannot negate {}", e.get_type()),
                     }),
                 }
@@ -2310,6 +3990,19 @@ impl<'ast> Checker<'ast> {
 
     fn exit_scope(&mut self) {
         let current_level = self.level;
+
+        // report the variables going out of scope which were never read
+        for v in self.scope.iter().filter(|v| v.level >= current_level) {
+            if let CoreIdentifier::Source(id) = v.id.id.id {
+                if let Some(warning) = self.unused.remove(id) {
+                    let mut usage = self.usage.borrow_mut();
+                    if warning.module_id == usage.main {
+                        usage.warnings.push(warning);
+                    }
+                }
+            }
+        }
+
         self.scope
             .retain(|ref scoped_variable| scoped_variable.level < current_level);
         self.level -= 1;
@@ -2379,6 +4072,71 @@ mod tests {
         }
     }
 
+    mod array_size {
+        use super::*;
+
+        fn check_constant_size(
+            value: TypedExpression<'static, Bn128Field>,
+        ) -> Result<usize, ErrorInner> {
+            let mut checker: Checker<Bn128Field> = Checker::new();
+            checker.constants.insert("N".into(), value);
+            checker.check_array_size(
+                ArraySize::Identifier("N".into()),
+                (Position::mock(), Position::mock()),
+            )
+        }
+
+        #[test]
+        fn u32() {
+            assert_eq!(
+                check_constant_size(UExpressionInner::Value(2).annotate(32).into()),
+                Ok(2)
+            );
+        }
+
+        #[test]
+        fn field() {
+            // a field constant is rejected
+            assert_eq!(
+                check_constant_size(FieldElementExpression::Number(Bn128Field::from(2)).into())
+                    .unwrap_err()
+                    .code,
+                ErrorCode::TypeMismatch
+            );
+
+            // unless it is a generic parameter
+            let mut checker: Checker<Bn128Field> = Checker::new();
+            checker.constants.insert(
+                "N".into(),
+                FieldElementExpression::Number(Bn128Field::from(2)).into(),
+            );
+            checker.generic_parameters.insert("N".into());
+            assert_eq!(
+                checker.check_array_size(
+                    ArraySize::Identifier("N".into()),
+                    (Position::mock(), Position::mock()),
+                ),
+                Ok(2)
+            );
+        }
+
+        #[test]
+        fn other_uint() {
+            // a u64 constant is rejected, even if its value is a valid size
+            let size = UExpressionInner::Value(0x0000000100000001).annotate(64);
+            assert_eq!(
+                check_constant_size(size.into()).unwrap_err().code,
+                ErrorCode::TypeMismatch
+            );
+
+            let size = UExpressionInner::Value(2).annotate(8);
+            assert_eq!(
+                check_constant_size(size.into()).unwrap_err().code,
+                ErrorCode::TypeMismatch
+            );
+        }
+    }
+
     mod symbols {
         use super::*;
 
@@ -2795,11 +4553,26 @@ mod tests {
         scope: HashSet<ScopedVariable<'ast>>,
         level: usize,
         functions: HashSet<FunctionKey<'ast>>,
-    ) -> Checker<'ast> {
+    ) -> Checker<'ast, Bn128Field> {
         Checker {
             scope: scope,
             functions: functions,
             level: level,
+            max_uint_bitwidth: 64,
+            loop_depth: 0,
+            branch_depth: 0,
+            constants: HashMap::new(),
+            generic_parameters: HashSet::new(),
+            generics: HashMap::new(),
+            instances: HashMap::new(),
+            origins: false,
+            index: None,
+            checked: None,
+            declarations: HashMap::new(),
+            entries: vec!["main".to_string()],
+            usage: Rc::new(RefCell::new(Usage::default())),
+            function: None,
+            unused: HashMap::new(),
         }
     }
 
@@ -2821,6 +4594,7 @@ mod tests {
             checker.check_statement(statement, &module_id, &types),
             Err(vec![ErrorInner {
                 pos: Some((Position::mock(), Position::mock())),
+                code: ErrorCode::UndefinedIdentifier,
                 message: "Identifier \"b\" is undefined".into()
             }])
         );
@@ -2881,6 +4655,7 @@ mod tests {
             arguments: foo_args,
             statements: foo_statements,
             signature: UnresolvedSignature {
+                generics: vec![],
                 inputs: vec![],
                 outputs: vec![UnresolvedType::FieldElement.mock()],
             },
@@ -2900,6 +4675,7 @@ mod tests {
             arguments: bar_args,
             statements: bar_statements,
             signature: UnresolvedSignature {
+                generics: vec![],
                 inputs: vec![],
                 outputs: vec![UnresolvedType::FieldElement.mock()],
             },
@@ -2931,6 +4707,7 @@ mod tests {
             Err(vec![Error {
                 inner: ErrorInner {
                     pos: Some((Position::mock(), Position::mock())),
+                    code: ErrorCode::UndefinedIdentifier,
                     message: "Identifier \"a\" is undefined".into()
                 },
                 module_id: "main".into()
@@ -2965,6 +4742,7 @@ mod tests {
             arguments: foo_args,
             statements: foo_statements,
             signature: UnresolvedSignature {
+                generics: vec![],
                 inputs: vec![],
                 outputs: vec![UnresolvedType::FieldElement.mock()],
             },
@@ -2994,6 +4772,7 @@ mod tests {
             arguments: bar_args,
             statements: bar_statements,
             signature: UnresolvedSignature {
+                generics: vec![],
                 inputs: vec![],
                 outputs: vec![UnresolvedType::FieldElement.mock()],
             },
@@ -3013,6 +4792,7 @@ mod tests {
             arguments: main_args,
             statements: main_statements,
             signature: UnresolvedSignature {
+                generics: vec![],
                 inputs: vec![],
                 outputs: vec![UnresolvedType::FieldElement.mock()],
             },
@@ -3074,6 +4854,7 @@ mod tests {
             arguments: vec![],
             statements: foo_statements,
             signature: UnresolvedSignature {
+                generics: vec![],
                 inputs: vec![],
                 outputs: vec![UnresolvedType::FieldElement.mock()],
             },
@@ -3088,6 +4869,7 @@ mod tests {
             checker.check_function(foo, &module_id, &types),
             Err(vec![ErrorInner {
                 pos: Some((Position::mock(), Position::mock())),
+                code: ErrorCode::UndefinedIdentifier,
                 message: "Identifier \"i\" is undefined".into()
             }])
         );
@@ -3140,6 +4922,7 @@ mod tests {
             arguments: vec![],
             statements: foo_statements,
             signature: UnresolvedSignature {
+                generics: vec![],
                 inputs: vec![],
                 outputs: vec![UnresolvedType::FieldElement.mock()],
             },
@@ -3179,7 +4962,7 @@ mod tests {
             .mock(),
             Statement::MultipleDefinition(
                 vec![Assignee::Identifier("a").mock()],
-                Expression::FunctionCall("foo", vec![]).mock(),
+                Expression::FunctionCall("foo".mock(), vec![]).mock(),
             )
             .mock(),
         ];
@@ -3198,6 +4981,7 @@ mod tests {
             arguments: vec![],
             statements: bar_statements,
             signature: UnresolvedSignature {
+                generics: vec![],
                 inputs: vec![],
                 outputs: vec![UnresolvedType::FieldElement.mock()],
             },
@@ -3212,6 +4996,7 @@ mod tests {
             checker.check_function(bar, &module_id, &types),
             Err(vec![ErrorInner {
                 pos: Some((Position::mock(), Position::mock())),
+                code: ErrorCode::FunctionNotFound,
                 message:
                     "Function definition for function foo with signature () -> field not found."
                         .into()
@@ -3229,7 +5014,7 @@ mod tests {
         let bar_statements: Vec<StatementNode<Bn128Field>> = vec![Statement::Assertion(
             Expression::Eq(
                 box Expression::FieldConstant(Bn128Field::from(2)).mock(),
-                box Expression::FunctionCall("foo", vec![]).mock(),
+                box Expression::FunctionCall("foo".mock(), vec![]).mock(),
             )
             .mock(),
         )
@@ -3249,6 +5034,7 @@ mod tests {
             arguments: vec![],
             statements: bar_statements,
             signature: UnresolvedSignature {
+                generics: vec![],
                 inputs: vec![],
                 outputs: vec![UnresolvedType::FieldElement.mock()],
             },
@@ -3263,6 +5049,7 @@ mod tests {
             checker.check_function(bar, &module_id, &types),
             Err(vec![ErrorInner {
                 pos: Some((Position::mock(), Position::mock())),
+                code: ErrorCode::FunctionNotFound,
                 message: "Function definition for function foo with signature () -> _ not found."
                     .into()
             }])
@@ -3281,7 +5068,7 @@ mod tests {
             .mock(),
             Statement::MultipleDefinition(
                 vec![Assignee::Identifier("a").mock()],
-                Expression::FunctionCall("foo", vec![]).mock(),
+                Expression::FunctionCall("foo".mock(), vec![]).mock(),
             )
             .mock(),
         ];
@@ -3290,6 +5077,7 @@ mod tests {
             arguments: vec![],
             statements: bar_statements,
             signature: UnresolvedSignature {
+                generics: vec![],
                 inputs: vec![],
                 outputs: vec![UnresolvedType::FieldElement.mock()],
             },
@@ -3305,6 +5093,7 @@ mod tests {
             Err(vec![ErrorInner {
                 pos: Some((Position::mock(), Position::mock())),
 
+                code: ErrorCode::FunctionNotFound,
                 message:
                     "Function definition for function foo with signature () -> field not found."
                         .into()
@@ -3340,6 +5129,7 @@ mod tests {
             .mock()],
             statements: foo_statements,
             signature: UnresolvedSignature {
+                generics: vec![],
                 inputs: vec![UnresolvedType::FieldElement.mock()],
                 outputs: vec![
                     UnresolvedType::FieldElement.mock(),
@@ -3363,7 +5153,7 @@ mod tests {
                     Assignee::Identifier("a").mock(),
                     Assignee::Identifier("b").mock(),
                 ],
-                Expression::FunctionCall("foo", vec![Expression::Identifier("x").mock()]).mock(),
+                Expression::FunctionCall("foo".mock(), vec![Expression::Identifier("x").mock()]).mock(),
             )
             .mock(),
             Statement::Return(
@@ -3379,6 +5169,7 @@ mod tests {
             arguments: vec![],
             statements: main_statements,
             signature: UnresolvedSignature {
+                generics: vec![],
                 inputs: vec![],
                 outputs: vec![UnresolvedType::FieldElement.mock()],
             },
@@ -3409,6 +5200,7 @@ mod tests {
             Err(vec![Error {
                 inner: ErrorInner {
                     pos: Some((Position::mock(), Position::mock())),
+                    code: ErrorCode::UndefinedIdentifier,
                     message: "Identifier \"x\" is undefined".into()
                 },
                 module_id: "main".into()
@@ -3440,6 +5232,7 @@ mod tests {
             arguments: vec![],
             statements: foo_statements,
             signature: UnresolvedSignature {
+                generics: vec![],
                 inputs: vec![],
                 outputs: vec![
                     UnresolvedType::FieldElement.mock(),
@@ -3455,7 +5248,7 @@ mod tests {
                     Assignee::Identifier("a").mock(),
                     Assignee::Identifier("b").mock(),
                 ],
-                Expression::FunctionCall("foo", vec![]).mock(),
+                Expression::FunctionCall("foo".mock(), vec![]).mock(),
             )
             .mock(),
             Statement::Return(
@@ -3471,6 +5264,7 @@ mod tests {
             arguments: vec![],
             statements: main_statements,
             signature: UnresolvedSignature {
+                generics: vec![],
                 inputs: vec![],
                 outputs: vec![],
             },
@@ -3502,6 +5296,7 @@ mod tests {
                 Error {
                     inner: ErrorInner {
                         pos: Some((Position::mock(), Position::mock())),
+                        code: ErrorCode::UndeclaredVariable,
                         message: "Variable `a` is undeclared".into()
                     },
                     module_id: "main".into()
@@ -3509,6 +5304,7 @@ mod tests {
                 Error {
                     inner: ErrorInner {
                         pos: Some((Position::mock(), Position::mock())),
+                        code: ErrorCode::UndeclaredVariable,
                         message: "Variable `b` is undeclared".into()
                     },
                     module_id: "main".into()
@@ -3539,6 +5335,7 @@ mod tests {
             arguments: vec![],
             statements: foo_statements,
             signature: UnresolvedSignature {
+                generics: vec![],
                 inputs: vec![],
                 outputs: vec![UnresolvedType::FieldElement.mock()],
             },
@@ -3570,7 +5367,7 @@ mod tests {
                     ),
                 )
                 .mock()],
-                Expression::FunctionCall("foo", vec![]).mock(),
+                Expression::FunctionCall("foo".mock(), vec![]).mock(),
             )
             .mock(),
             Statement::Return(
@@ -3586,6 +5383,7 @@ mod tests {
             arguments: vec![],
             statements: main_statements,
             signature: UnresolvedSignature {
+                generics: vec![],
                 inputs: vec![],
                 outputs: vec![],
             },
@@ -3616,6 +5414,7 @@ mod tests {
             Err(vec![Error {
                 inner: ErrorInner {
                     pos: Some((Position::mock(), Position::mock())),
+                    code: ErrorCode::InvalidStatement,
                     message: "Only assignment to identifiers is supported, found a[0]".into()
                 },
                 module_id: "main".into()
@@ -3631,7 +5430,7 @@ mod tests {
         let bar_statements: Vec<StatementNode<Bn128Field>> = vec![Statement::Assertion(
             Expression::Eq(
                 box Expression::FieldConstant(Bn128Field::from(1)).mock(),
-                box Expression::FunctionCall("foo", vec![]).mock(),
+                box Expression::FunctionCall("foo".mock(), vec![]).mock(),
             )
             .mock(),
         )
@@ -3641,6 +5440,7 @@ mod tests {
             arguments: vec![],
             statements: bar_statements,
             signature: UnresolvedSignature {
+                generics: vec![],
                 inputs: vec![],
                 outputs: vec![UnresolvedType::FieldElement.mock()],
             },
@@ -3656,6 +5456,7 @@ mod tests {
             Err(vec![ErrorInner {
                 pos: Some((Position::mock(), Position::mock())),
 
+                code: ErrorCode::FunctionNotFound,
                 message: "Function definition for function foo with signature () -> _ not found."
                     .into()
             }])
@@ -3682,6 +5483,7 @@ mod tests {
             arguments: vec![],
             statements: bar_statements,
             signature: UnresolvedSignature {
+                generics: vec![],
                 inputs: vec![],
                 outputs: vec![
                     UnresolvedType::FieldElement.mock(),
@@ -3699,6 +5501,7 @@ mod tests {
             checker.check_function(bar, &module_id, &types),
             Err(vec![ErrorInner {
                 pos: Some((Position::mock(), Position::mock())),
+                code: ErrorCode::UndefinedIdentifier,
                 message: "Identifier \"a\" is undefined".into()
             }])
         );
@@ -3727,7 +5530,7 @@ mod tests {
                     Assignee::Identifier("a").mock(),
                     Assignee::Identifier("b").mock(),
                 ],
-                Expression::FunctionCall("foo", vec![]).mock(),
+                Expression::FunctionCall("foo".mock(), vec![]).mock(),
             )
             .mock(),
             Statement::Return(
@@ -3781,6 +5584,7 @@ mod tests {
             arguments: vec![],
             statements: bar_statements,
             signature: UnresolvedSignature {
+                generics: vec![],
                 inputs: vec![],
                 outputs: vec![UnresolvedType::FieldElement.mock()],
             },
@@ -3842,6 +5646,7 @@ mod tests {
             arguments: main1_arguments,
             statements: main1_statements,
             signature: UnresolvedSignature {
+                generics: vec![],
                 inputs: vec![UnresolvedType::FieldElement.mock()],
                 outputs: vec![UnresolvedType::FieldElement.mock()],
             },
@@ -3852,6 +5657,7 @@ mod tests {
             arguments: main2_arguments,
             statements: main2_statements,
             signature: UnresolvedSignature {
+                generics: vec![],
                 inputs: vec![],
                 outputs: vec![UnresolvedType::FieldElement.mock()],
             },
@@ -3879,6 +5685,7 @@ mod tests {
         let program = Program {
             modules: vec![("main".into(), main_module)].into_iter().collect(),
             main: "main".into(),
+            checked: HashMap::new(),
         };
 
         let mut checker = Checker::new();
@@ -3887,6 +5694,7 @@ mod tests {
             Err(vec![Error {
                 inner: ErrorInner {
                     pos: None,
+                    code: ErrorCode::MultipleMains,
                     message: "Only one main function allowed, found 2".into()
                 },
                 module_id: "main".into()
@@ -3925,6 +5733,7 @@ mod tests {
             s2_checked,
             Err(vec![ErrorInner {
                 pos: Some((Position::mock(), Position::mock())),
+                code: ErrorCode::DuplicateDeclaration,
                 message: "Duplicate declaration for variable named a".into()
             }])
         );
@@ -3962,6 +5771,7 @@ mod tests {
             s2_checked,
             Err(vec![ErrorInner {
                 pos: Some((Position::mock(), Position::mock())),
+                code: ErrorCode::DuplicateDeclaration,
                 message: "Duplicate declaration for variable named a".into()
             }])
         );
@@ -3973,7 +5783,7 @@ mod tests {
         /// solver function to create a module at location "" with a single symbol `Foo { foo: field }`
         fn create_module_with_foo(
             s: StructDefinition<'static>,
-        ) -> (Checker<'static>, State<'static, Bn128Field>) {
+        ) -> (Checker<'static, Bn128Field>, State<'static, Bn128Field>) {
             let module_id: PathBuf = "".into();
 
             let module: Module<Bn128Field> = Module {
@@ -4008,7 +5818,7 @@ mod tests {
                 let expected_type = Type::Struct(StructType::new("".into(), "Foo".into(), vec![]));
 
                 assert_eq!(
-                    Checker::new().check_struct_type_declaration(
+                    Checker::<Bn128Field>::new().check_struct_type_declaration(
                         "Foo".into(),
                         declaration,
                         &module_id,
@@ -4049,7 +5859,7 @@ mod tests {
                 ));
 
                 assert_eq!(
-                    Checker::new().check_struct_type_declaration(
+                    Checker::<Bn128Field>::new().check_struct_type_declaration(
                         "Foo".into(),
                         declaration,
                         &module_id,
@@ -4098,13 +5908,13 @@ mod tests {
                 .mock();
 
                 assert_ne!(
-                    Checker::new().check_struct_type_declaration(
+                    Checker::<Bn128Field>::new().check_struct_type_declaration(
                         "Foo".into(),
                         declaration0,
                         &module_id,
                         &types
                     ),
-                    Checker::new().check_struct_type_declaration(
+                    Checker::<Bn128Field>::new().check_struct_type_declaration(
                         "Foo".into(),
                         declaration1,
                         &module_id,
@@ -4136,7 +5946,7 @@ mod tests {
                 .mock();
 
                 assert_eq!(
-                    Checker::new()
+                    Checker::<Bn128Field>::new()
                         .check_struct_type_declaration(
                             "Foo".into(),
                             declaration,
@@ -4381,7 +6191,7 @@ mod tests {
 
                 // an undefined type cannot be used as parameter
 
-                let (checker, state) = create_module_with_foo(StructDefinition {
+                let (mut checker, state) = create_module_with_foo(StructDefinition {
                     fields: vec![StructDefinitionField {
                         id: "foo",
                         ty: UnresolvedType::FieldElement.mock(),
@@ -4450,7 +6260,7 @@ mod tests {
                 });
 
                 assert_eq!(
-                    checker.check_statement::<Bn128Field>(
+                    checker.check_statement(
                         Statement::Declaration(
                             absy::Variable::new("a", UnresolvedType::User("Foo".into()).mock(),)
                                 .mock()
@@ -4852,9 +6662,9 @@ mod tests {
 
             let types = HashMap::new();
             let module_id = "".into();
-            let mut checker: Checker = Checker::new();
+            let mut checker: Checker<Bn128Field> = Checker::new();
             checker
-                .check_statement::<Bn128Field>(
+                .check_statement(
                     Statement::Declaration(
                         absy::Variable::new("a", UnresolvedType::FieldElement.mock()).mock(),
                     )
@@ -4887,9 +6697,9 @@ mod tests {
             let types = HashMap::new();
             let module_id = "".into();
 
-            let mut checker: Checker = Checker::new();
+            let mut checker: Checker<Bn128Field> = Checker::new();
             checker
-                .check_statement::<Bn128Field>(
+                .check_statement(
                     Statement::Declaration(
                         absy::Variable::new(
                             "a",
@@ -4932,9 +6742,9 @@ mod tests {
 
             let types = HashMap::new();
             let module_id = "".into();
-            let mut checker: Checker = Checker::new();
+            let mut checker: Checker<Bn128Field> = Checker::new();
             checker
-                .check_statement::<Bn128Field>(
+                .check_statement(
                     Statement::Declaration(
                         absy::Variable::new(
                             "a",
@@ -4969,4 +6779,503 @@ mod tests {
             );
         }
     }
+
+    mod ints {
+        use super::*;
+
+        #[test]
+        fn out_of_range() {
+            // -129i8
+            let types = HashMap::new();
+            let module_id = "".into();
+            let e = Expression::IntConstant("-129", 8).mock();
+            assert_eq!(
+                Checker::<Bn128Field>::new()
+                    .check_expression(e, &module_id, &types)
+                    .unwrap_err()
+                    .code,
+                ErrorCode::ValueOutOfRange
+            );
+        }
+
+        #[test]
+        fn overflowing_i128() {
+            // digits which do not fit in an i128 are out of range rather than a panic
+            let types = HashMap::new();
+            let module_id = "".into();
+            let e = Expression::IntConstant("999999999999999999999999999999999999999999", 8).mock();
+            assert_eq!(
+                Checker::<Bn128Field>::new()
+                    .check_expression(e, &module_id, &types)
+                    .unwrap_err()
+                    .code,
+                ErrorCode::ValueOutOfRange
+            );
+        }
+    }
+
+    mod early_exit {
+        use super::*;
+
+        #[test]
+        fn outside_loop() {
+            // break
+            let types = HashMap::new();
+            let module_id = "".into();
+            let s: StatementNode<Bn128Field> = Statement::Break(None).mock();
+            assert_eq!(
+                Checker::new().check_statement(s, &module_id, &types).unwrap_err()[0].code,
+                ErrorCode::InvalidStatement
+            );
+        }
+
+        #[test]
+        fn condition_not_bool() {
+            // for field i in 0..4 do
+            //   break if 1
+            let types = HashMap::new();
+            let module_id = "".into();
+            let mut checker = Checker::new();
+            checker.loop_depth = 1;
+            let s: StatementNode<Bn128Field> = Statement::Break(Some(
+                Expression::FieldConstant(Bn128Field::from(1)).mock(),
+            ))
+            .mock();
+            assert_eq!(
+                checker.check_statement(s, &module_id, &types).unwrap_err()[0].code,
+                ErrorCode::TypeMismatch
+            );
+        }
+    }
+
+    mod if_else {
+        use super::*;
+
+        #[test]
+        fn return_inside() {
+            // if a == 0 then
+            //   return 1
+            let types = HashMap::new();
+            let module_id = "".into();
+            let mut checker = Checker::new();
+            checker.branch_depth = 1;
+            let s: StatementNode<Bn128Field> = Statement::Return(
+                ExpressionList {
+                    expressions: vec![Expression::FieldConstant(Bn128Field::from(1)).mock()],
+                }
+                .mock(),
+            )
+            .mock();
+            assert_eq!(
+                checker.check_statement(s, &module_id, &types).unwrap_err()[0].code,
+                ErrorCode::InvalidStatement
+            );
+        }
+    }
+
+    mod constants {
+        use super::*;
+
+        #[test]
+        fn type_mismatch() {
+            // const bool N = 42
+            let types = HashMap::new();
+            let module_id = "".into();
+            let c = ConstantDefinition {
+                ty: UnresolvedType::Boolean.mock(),
+                expression: Expression::FieldConstant(Bn128Field::from(42)).mock(),
+            }
+            .mock();
+            assert_eq!(
+                Checker::new()
+                    .check_constant_definition("N", c, &module_id, &types)
+                    .unwrap_err()[0]
+                    .code,
+                ErrorCode::TypeMismatch
+            );
+        }
+
+        #[test]
+        fn undefined_array_size() {
+            // field[N]
+            let types = HashMap::new();
+            let module_id = "".into();
+            let ty = UnresolvedType::array(
+                UnresolvedType::FieldElement.mock(),
+                ArraySize::Identifier("N".into()),
+            )
+            .mock();
+            assert_eq!(
+                Checker::<Bn128Field>::new()
+                    .check_type(ty, &module_id, &types)
+                    .unwrap_err()
+                    .code,
+                ErrorCode::NonConstantExpression
+            );
+        }
+
+        #[test]
+        fn conflict() {
+            // const field N = 1
+            // const field N = 2
+            let constant = |value: u32| SymbolDeclaration {
+                id: "N",
+                symbol: Symbol::HereConstant(
+                    ConstantDefinition {
+                        ty: UnresolvedType::FieldElement.mock(),
+                        expression: Expression::FieldConstant(Bn128Field::from(value)).mock(),
+                    }
+                    .mock(),
+                ),
+            };
+            let module: Module<Bn128Field> =
+                Module::with_symbols(vec![constant(1).mock(), constant(2).mock()]);
+
+            let mut state = State::new(vec![("main".into(), module)].into_iter().collect());
+
+            assert_eq!(
+                Checker::new()
+                    .check_module(&"main".into(), &mut state)
+                    .unwrap_err()[0]
+                    .inner
+                    .code,
+                ErrorCode::SymbolConflict
+            );
+            // the first definition is kept
+            assert_eq!(
+                state.constants[&PathBuf::from("main")]["N"],
+                FieldElementExpression::Number(Bn128Field::from(1)).into()
+            );
+        }
+    }
+
+    mod generics {
+        use super::*;
+
+        /// Helper function to create `field[N]`
+        fn array_of_n() -> UnresolvedTypeNode {
+            UnresolvedType::array(
+                UnresolvedType::FieldElement.mock(),
+                ArraySize::Identifier("N".into()),
+            )
+            .mock()
+        }
+
+        /// Helper function to create ((private field[N] a, ...) -> field: return 1), generic in N
+        fn function_of_n(arguments: &[&'static str]) -> FunctionNode<'static, Bn128Field> {
+            Function {
+                arguments: arguments
+                    .iter()
+                    .map(|a| {
+                        absy::Parameter::private(absy::Variable::new(*a, array_of_n()).mock())
+                            .mock()
+                    })
+                    .collect(),
+                statements: vec![Statement::Return(
+                    ExpressionList {
+                        expressions: vec![Expression::FieldConstant(Bn128Field::from(1)).mock()],
+                    }
+                    .mock(),
+                )
+                .mock()],
+                signature: UnresolvedSignature {
+                    generics: vec!["N".to_string().mock()],
+                    inputs: arguments.iter().map(|_| array_of_n()).collect(),
+                    outputs: vec![UnresolvedType::FieldElement.mock()],
+                },
+            }
+            .mock()
+        }
+
+        #[test]
+        fn generic_main() {
+            // def main<N>(field[N] a) -> field:
+            //   return 1
+            let module = Module::with_symbols(vec![SymbolDeclaration {
+                id: "main",
+                symbol: Symbol::HereFunction(function_of_n(&["a"])),
+            }
+            .mock()]);
+
+            let program = Program {
+                modules: vec![("main".into(), module)].into_iter().collect(),
+                main: "main".into(),
+                checked: HashMap::new(),
+            };
+
+            assert_eq!(
+                Checker::new().check_program(program).unwrap_err()[0].inner.code,
+                ErrorCode::GenericMain
+            );
+        }
+
+        #[test]
+        fn not_inferable() {
+            // def foo<N>(field a) -> field:
+            //   return 1
+            let types = HashMap::new();
+            let module_id = "".into();
+            let mut foo = function_of_n(&["a"]);
+            foo.value.signature.inputs = vec![UnresolvedType::FieldElement.mock()];
+            foo.value.arguments = vec![absy::Parameter::private(
+                absy::Variable::new("a", UnresolvedType::FieldElement.mock()).mock(),
+            )
+            .mock()];
+
+            assert_eq!(
+                Checker::new()
+                    .check_generic_function(&foo, &module_id, &types)
+                    .unwrap_err()[0]
+                    .code,
+                ErrorCode::UnusedGeneric
+            );
+        }
+
+        #[test]
+        fn inconsistent_sizes() {
+            // def foo<N>(field[N] a, field[N] b) -> field:
+            //   return 1
+            //
+            // def main() -> field:
+            //   return foo([1], [1, 2])
+            let one = || -> SpreadOrExpression<Bn128Field> {
+                Expression::FieldConstant(Bn128Field::from(1)).mock().into()
+            };
+            let two = Expression::FieldConstant(Bn128Field::from(2)).mock().into();
+
+            let main = Function {
+                arguments: vec![],
+                statements: vec![Statement::Return(
+                    ExpressionList {
+                        expressions: vec![Expression::FunctionCall(
+                            "foo".mock(),
+                            vec![
+                                Expression::InlineArray(vec![one()]).mock(),
+                                Expression::InlineArray(vec![one(), two]).mock(),
+                            ],
+                        )
+                        .mock()],
+                    }
+                    .mock(),
+                )
+                .mock()],
+                signature: UnresolvedSignature {
+                    generics: vec![],
+                    inputs: vec![],
+                    outputs: vec![UnresolvedType::FieldElement.mock()],
+                },
+            }
+            .mock();
+
+            let module = Module::with_symbols(vec![
+                SymbolDeclaration {
+                    id: "foo",
+                    symbol: Symbol::HereFunction(function_of_n(&["a", "b"])),
+                }
+                .mock(),
+                SymbolDeclaration {
+                    id: "main",
+                    symbol: Symbol::HereFunction(main),
+                }
+                .mock(),
+            ]);
+
+            let mut state = State::new(vec![("main".into(), module)].into_iter().collect());
+
+            assert_eq!(
+                Checker::new()
+                    .check_module(&"main".into(), &mut state)
+                    .unwrap_err()[0]
+                    .inner
+                    .code,
+                ErrorCode::FunctionNotFound
+            );
+        }
+    }
+
+    mod tuples {
+        use super::*;
+
+        /// Helper function to create `(field, bool)`
+        fn field_and_bool() -> UnresolvedTypeNode {
+            UnresolvedType::Tuple(vec![
+                UnresolvedType::FieldElement.mock(),
+                UnresolvedType::Boolean.mock(),
+            ])
+            .mock()
+        }
+
+        #[test]
+        fn element_out_of_bounds() {
+            // (1, true).2
+            let types = HashMap::new();
+            let module_id = "".into();
+            let e = Expression::Element(
+                box Expression::InlineTuple(vec![
+                    Expression::FieldConstant(Bn128Field::from(1)).mock(),
+                    Expression::BooleanConstant(true).mock(),
+                ])
+                .mock(),
+                "2",
+            )
+            .mock();
+
+            assert_eq!(
+                Checker::<Bn128Field>::new()
+                    .check_expression(e, &module_id, &types)
+                    .unwrap_err()
+                    .code,
+                ErrorCode::InvalidAccess
+            );
+        }
+
+        #[test]
+        fn type_mismatch() {
+            // (field, bool) t
+            // t = (true, 1)
+            let types = HashMap::new();
+            let module_id = "".into();
+            let mut checker: Checker<Bn128Field> = Checker::new();
+            checker
+                .check_statement(
+                    Statement::Declaration(absy::Variable::new("t", field_and_bool()).mock())
+                        .mock(),
+                    &module_id,
+                    &types,
+                )
+                .unwrap();
+
+            let s = Statement::Definition(
+                Assignee::Identifier("t").mock(),
+                Expression::InlineTuple(vec![
+                    Expression::BooleanConstant(true).mock(),
+                    Expression::FieldConstant(Bn128Field::from(1)).mock(),
+                ])
+                .mock(),
+            )
+            .mock();
+
+            assert_eq!(
+                checker.check_statement(s, &module_id, &types).unwrap_err()[0].code,
+                ErrorCode::TypeMismatch
+            );
+        }
+
+        #[test]
+        fn element_out_of_range() {
+            // (field, bool) t
+            // t.99999999999999999999999 = t.99999999999999999999999
+            let types = HashMap::new();
+            let module_id = "".into();
+            let mut checker: Checker<Bn128Field> = Checker::new();
+            checker
+                .check_statement(
+                    Statement::Declaration(absy::Variable::new("t", field_and_bool()).mock())
+                        .mock(),
+                    &module_id,
+                    &types,
+                )
+                .unwrap();
+
+            let index = "99999999999999999999999";
+
+            let e = Expression::Element(box Expression::Identifier("t").mock(), index).mock();
+            assert_eq!(
+                checker.check_expression(e, &module_id, &types).unwrap_err().code,
+                ErrorCode::ValueOutOfRange
+            );
+
+            let a = Assignee::Element(box Assignee::Identifier("t").mock(), index).mock();
+            assert_eq!(
+                checker.check_assignee(a, &module_id, &types).unwrap_err().code,
+                ErrorCode::ValueOutOfRange
+            );
+        }
+    }
+
+    mod aliases {
+        use super::*;
+
+        #[test]
+        fn conflict_with_struct() {
+            // type Foo = field[2]
+            // struct Foo {
+            //   field a
+            // }
+            let module: Module<Bn128Field> = Module::with_symbols(vec![
+                SymbolDeclaration {
+                    id: "Foo",
+                    symbol: Symbol::HereAlias(
+                        UnresolvedType::array(UnresolvedType::FieldElement.mock(), 2).mock(),
+                    ),
+                }
+                .mock(),
+                SymbolDeclaration {
+                    id: "Foo",
+                    symbol: Symbol::HereType(
+                        StructDefinition {
+                            fields: vec![StructDefinitionField {
+                                id: "a",
+                                ty: UnresolvedType::FieldElement.mock(),
+                            }
+                            .mock()],
+                        }
+                        .mock(),
+                    ),
+                }
+                .mock(),
+            ]);
+
+            let mut state = State::new(vec![("main".into(), module)].into_iter().collect());
+
+            assert_eq!(
+                Checker::new()
+                    .check_module(&"main".into(), &mut state)
+                    .unwrap_err()[0]
+                    .inner
+                    .code,
+                ErrorCode::SymbolConflict
+            );
+        }
+
+        #[test]
+        fn struct_in_same_module() {
+            // struct Foo {
+            //   field a
+            // }
+            // type Foos = Foo[2]
+            let module: Module<Bn128Field> = Module::with_symbols(vec![
+                SymbolDeclaration {
+                    id: "Foo",
+                    symbol: Symbol::HereType(
+                        StructDefinition {
+                            fields: vec![StructDefinitionField {
+                                id: "a",
+                                ty: UnresolvedType::FieldElement.mock(),
+                            }
+                            .mock()],
+                        }
+                        .mock(),
+                    ),
+                }
+                .mock(),
+                SymbolDeclaration {
+                    id: "Foos",
+                    symbol: Symbol::HereAlias(
+                        UnresolvedType::array(UnresolvedType::User("Foo".into()).mock(), 2).mock(),
+                    ),
+                }
+                .mock(),
+            ]);
+
+            let mut state = State::new(vec![("main".into(), module)].into_iter().collect());
+
+            assert!(Checker::new()
+                .check_module(&"main".into(), &mut state)
+                .is_ok());
+
+            let types = &state.types[&PathBuf::from("main")];
+            assert_eq!(types["Foos"], Type::array(types["Foo"].clone(), 2));
+        }
+    }
 }
@@ -10,6 +10,7 @@ pub enum Solver {
     Or,
     ShaAndXorAndXorAnd,
     ShaCh,
+    EuclideanDiv,
 }
 
 impl fmt::Display for Solver {
@@ -28,6 +29,7 @@ impl Solver {
             Solver::Or => (2, 1),
             Solver::ShaAndXorAndXorAnd => (3, 1),
             Solver::ShaCh => (3, 1),
+            Solver::EuclideanDiv => (2, 2),
         }
     }
 }
@@ -45,3 +47,19 @@ pub trait Executable<T: Field>: Signed {
 pub trait Signed {
     fn get_signature(&self) -> (usize, usize);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bincode::{serialize, Infinite};
+
+    #[test]
+    fn serialization_tags() {
+        // programs are serialised with the index of their solvers, so new solvers must come last
+        let tag = |s: Solver| serialize(&s, Infinite).unwrap()[..4].to_vec();
+
+        assert_eq!(tag(Solver::Div), vec![2, 0, 0, 0]);
+        assert_eq!(tag(Solver::ShaCh), vec![6, 0, 0, 0]);
+        assert_eq!(tag(Solver::EuclideanDiv), vec![7, 0, 0, 0]);
+    }
+}
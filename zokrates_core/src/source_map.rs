@@ -0,0 +1,65 @@
+use std::fmt;
+use std::path::{Component, Path, PathBuf};
+
+/// The origin of a constraint in the source code, used to report which assertion failed at runtime
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct SourceMetadata {
+    pub file: String,
+    pub line: usize,
+    pub col: usize,
+    /// The asserted expression, as printed from the syntax tree rather than as written in the source
+    pub message: String,
+}
+
+impl fmt::Display for SourceMetadata {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:{}:{}", self.file, self.line, self.col)
+    }
+}
+
+/// The origin of the statements following an origin marker, used to profile compiled programs
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct Origin {
+    /// The functions being called, from `main` to the function the statements are declared in
+    pub stack: Vec<String>,
+    pub file: String,
+    pub line: usize,
+}
+
+impl fmt::Display for Origin {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:{}", self.file, self.line)
+    }
+}
+
+/// Remove the `.` and `..` components which module resolution leaves in paths, so that each file is displayed one way
+pub fn normalize(path: &Path) -> String {
+    let mut res = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => match res.components().last() {
+                Some(Component::Normal(_)) => {
+                    res.pop();
+                }
+                _ => res.push(component),
+            },
+            c => res.push(c),
+        }
+    }
+    res.display().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_paths() {
+        assert_eq!(
+            normalize(Path::new("/stdlib/hashes/sha256/../../utils/./pack.zok")),
+            "/stdlib/utils/pack.zok"
+        );
+        assert_eq!(normalize(Path::new("../foo/./bar.zok")), "../foo/bar.zok");
+    }
+}
@@ -0,0 +1,274 @@
+//! Module containing the lowering of `break` and `continue` statements to guarded statements
+//!
+//! Each loop body using early exits gets two flags: one set by `break` which stays set for the rest of the loop,
+//! and one set by `continue` which is reset at the start of each iteration. The statements of the body are guarded
+//! by the condition that neither flag is set. For example:
+//!
+//! ```zokrates
+//! for field i in 0..4 do
+//! 	assert(i != 3)
+//! 	break if a == 2
+//! 	a = a + 1
+//! endfor
+//! ```
+//!
+//! becomes
+//!
+//! ```zokrates
+//! bool BREAK = false
+//! for field i in 0..4 do
+//! 	bool CONTINUE = false
+//! 	assert(BREAK || CONTINUE || i != 3)
+//! 	BREAK = if !(BREAK || CONTINUE) then (if a == 2 then true else BREAK fi) else BREAK fi
+//! 	a = if !(BREAK || CONTINUE) then a + 1 else a fi
+//! endfor
+//! ```
+//!
+//! The divisors of the guarded statements are guarded by the same condition, so that a division following an early exit
+//! does not fail once the loop is exited, and calls to functions which can fail are rejected in such loops. Early exits
+//! of nested loops are left untouched and lowered when these loops are unrolled.
+
+use crate::static_analysis::guard::Guard;
+use crate::typed_absy::*;
+use typed_absy::identifier::CoreIdentifier;
+use zokrates_field::Field;
+
+/// Returns whether a loop body contains `break` or `continue` for this loop, ignoring the ones of nested loops
+pub fn has_early_exit<T>(statements: &[TypedStatement<T>]) -> bool {
+    statements.iter().any(|s| match s {
+        TypedStatement::Break(..) | TypedStatement::Continue(..) => true,
+        TypedStatement::IfElse(_, consequence, alternative) => {
+            has_early_exit(consequence) || has_early_exit(alternative)
+        }
+        _ => false,
+    })
+}
+
+pub struct EarlyExitLowerer<'ast> {
+    break_flag: Variable<'ast>,
+    continue_flag: Variable<'ast>,
+}
+
+impl<'ast> EarlyExitLowerer<'ast> {
+    /// Lower the early exits of a loop body, using flags identified by `id`.
+    /// Returns the statements to run once before the loop and the new body.
+    pub fn lower<T: Field>(
+        id: usize,
+        statements: Vec<TypedStatement<'ast, T>>,
+    ) -> (Vec<TypedStatement<'ast, T>>, Vec<TypedStatement<'ast, T>>) {
+        let lowerer = EarlyExitLowerer {
+            break_flag: Variable::boolean(CoreIdentifier::Internal("BREAK", id)),
+            continue_flag: Variable::boolean(CoreIdentifier::Internal("CONTINUE", id)),
+        };
+
+        let before = vec![
+            TypedStatement::Declaration(lowerer.break_flag.clone()),
+            lowerer.define(lowerer.break_flag.clone(), false),
+        ];
+
+        let guard = BooleanExpression::Not(box BooleanExpression::Or(
+            box BooleanExpression::Identifier(lowerer.break_flag.id.clone()),
+            box BooleanExpression::Identifier(lowerer.continue_flag.id.clone()),
+        ));
+
+        let body = vec![
+            TypedStatement::Declaration(lowerer.continue_flag.clone()),
+            lowerer.define(lowerer.continue_flag.clone(), false),
+        ]
+        .into_iter()
+        .chain(Guard::guard(guard, lowerer.replace_statements(statements)))
+        .collect();
+
+        (before, body)
+    }
+
+    fn define<T: Field>(&self, flag: Variable<'ast>, value: bool) -> TypedStatement<'ast, T> {
+        TypedStatement::Definition(
+            TypedAssignee::Identifier(flag),
+            BooleanExpression::Value(value).into(),
+        )
+    }
+
+    // set `flag`, only if `condition` holds when there is one
+    fn set<T: Field>(
+        &self,
+        flag: Variable<'ast>,
+        condition: Option<BooleanExpression<'ast, T>>,
+    ) -> Vec<TypedStatement<'ast, T>> {
+        let definition = self.define(flag, true);
+        match condition {
+            Some(condition) => Guard::guard(condition, vec![definition]),
+            None => vec![definition],
+        }
+    }
+
+    // replace the early exits of this loop by the definition of their flag
+    fn replace_statements<T: Field>(
+        &self,
+        statements: Vec<TypedStatement<'ast, T>>,
+    ) -> Vec<TypedStatement<'ast, T>> {
+        statements
+            .into_iter()
+            .flat_map(|s| match s {
+                TypedStatement::Break(condition) => self.set(self.break_flag.clone(), condition),
+                TypedStatement::Continue(condition) => {
+                    self.set(self.continue_flag.clone(), condition)
+                }
+                TypedStatement::IfElse(condition, consequence, alternative) => {
+                    vec![TypedStatement::IfElse(
+                        condition,
+                        self.replace_statements(consequence),
+                        self.replace_statements(alternative),
+                    )]
+                }
+                s => vec![s],
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use typed_absy::identifier::Identifier;
+    use zokrates_field::Bn128Field;
+
+    #[test]
+    fn conditional_break() {
+        // a = a + 1
+        // break if a == 2
+
+        // should be turned into
+        // bool BREAK = false
+        // ---
+        // bool CONTINUE = false
+        // a = if !(BREAK || CONTINUE) then a + 1 else a fi
+        // BREAK = if !(BREAK || CONTINUE) then (if a == 2 then true else BREAK fi) else BREAK fi
+
+        let a = || FieldElementExpression::Identifier("a".into());
+
+        let statements: Vec<TypedStatement<Bn128Field>> = vec![
+            TypedStatement::Definition(
+                TypedAssignee::Identifier(Variable::field_element("a")),
+                FieldElementExpression::Add(
+                    box a(),
+                    box FieldElementExpression::Number(Bn128Field::from(1)),
+                )
+                .into(),
+            ),
+            TypedStatement::Break(Some(BooleanExpression::FieldEq(
+                box a(),
+                box FieldElementExpression::Number(Bn128Field::from(2)),
+            ))),
+        ];
+
+        let break_flag: Identifier = CoreIdentifier::Internal("BREAK", 0).into();
+        let continue_flag: Identifier = CoreIdentifier::Internal("CONTINUE", 0).into();
+
+        let guard = BooleanExpression::Not(box BooleanExpression::Or(
+            box BooleanExpression::Identifier(break_flag.clone()),
+            box BooleanExpression::Identifier(continue_flag.clone()),
+        ));
+
+        let (before, body) = EarlyExitLowerer::lower(0, statements);
+
+        assert_eq!(
+            before,
+            vec![
+                TypedStatement::Declaration(Variable::boolean(break_flag.clone())),
+                TypedStatement::Definition(
+                    TypedAssignee::Identifier(Variable::boolean(break_flag.clone())),
+                    BooleanExpression::Value(false).into()
+                ),
+            ]
+        );
+
+        assert_eq!(
+            body,
+            vec![
+                TypedStatement::Declaration(Variable::boolean(continue_flag.clone())),
+                TypedStatement::Definition(
+                    TypedAssignee::Identifier(Variable::boolean(continue_flag)),
+                    BooleanExpression::Value(false).into()
+                ),
+                TypedStatement::Definition(
+                    TypedAssignee::Identifier(Variable::field_element("a")),
+                    FieldElementExpression::if_else(
+                        guard.clone(),
+                        FieldElementExpression::Add(
+                            box a(),
+                            box FieldElementExpression::Number(Bn128Field::from(1)),
+                        ),
+                        a(),
+                    )
+                    .into()
+                ),
+                TypedStatement::Definition(
+                    TypedAssignee::Identifier(Variable::boolean(break_flag.clone())),
+                    BooleanExpression::if_else(
+                        guard,
+                        BooleanExpression::if_else(
+                            BooleanExpression::FieldEq(
+                                box a(),
+                                box FieldElementExpression::Number(Bn128Field::from(2)),
+                            ),
+                            BooleanExpression::Value(true),
+                            BooleanExpression::Identifier(break_flag.clone()),
+                        ),
+                        BooleanExpression::Identifier(break_flag),
+                    )
+                    .into()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn division_after_break() {
+        // break
+        // a = 1 / a
+
+        // should be turned into
+        // ...
+        // a = if !(BREAK || CONTINUE) then 1 / (if !(BREAK || CONTINUE) then a else 1 fi) else a fi
+
+        let a = || FieldElementExpression::Identifier("a".into());
+        let one = || FieldElementExpression::Number(Bn128Field::from(1));
+
+        let statements: Vec<TypedStatement<Bn128Field>> = vec![
+            TypedStatement::Break(None),
+            TypedStatement::Definition(
+                TypedAssignee::Identifier(Variable::field_element("a")),
+                FieldElementExpression::Div(box one(), box a()).into(),
+            ),
+        ];
+
+        let break_flag: Identifier = CoreIdentifier::Internal("BREAK", 0).into();
+        let continue_flag: Identifier = CoreIdentifier::Internal("CONTINUE", 0).into();
+
+        let guard = || {
+            BooleanExpression::Not(box BooleanExpression::Or(
+                box BooleanExpression::Identifier(break_flag.clone()),
+                box BooleanExpression::Identifier(continue_flag.clone()),
+            ))
+        };
+
+        let (_, body) = EarlyExitLowerer::lower(0, statements);
+
+        assert_eq!(
+            body.last().unwrap(),
+            &TypedStatement::Definition(
+                TypedAssignee::Identifier(Variable::field_element("a")),
+                FieldElementExpression::if_else(
+                    guard(),
+                    FieldElementExpression::Div(
+                        box one(),
+                        box FieldElementExpression::if_else(guard(), a(), one()),
+                    ),
+                    a(),
+                )
+                .into()
+            )
+        );
+    }
+}
@@ -1,6 +1,6 @@
 use std::marker::PhantomData;
 use typed_absy;
-use typed_absy::types::{StructType, UBitwidth};
+use typed_absy::types::{StructType, TupleType, UBitwidth};
 use zir;
 use zokrates_field::Field;
 
@@ -21,7 +21,7 @@ fn flatten_identifier_rec<'a>(
             id: zir::Identifier::Source(id),
             _type: zir::Type::Boolean,
         }],
-        typed_absy::Type::Uint(bitwidth) => vec![zir::Variable {
+        typed_absy::Type::Uint(bitwidth) | typed_absy::Type::Int(bitwidth) => vec![zir::Variable {
             id: zir::Identifier::Source(id),
             _type: zir::Type::uint(bitwidth.to_usize()),
         }],
@@ -42,6 +42,14 @@ fn flatten_identifier_rec<'a>(
                 )
             })
             .collect(),
+        typed_absy::Type::Tuple(tuple_type) => tuple_type
+            .elements
+            .into_iter()
+            .enumerate()
+            .flat_map(|(i, ty)| {
+                flatten_identifier_rec(zir::SourceIdentifier::Element(box id.clone(), i), ty)
+            })
+            .collect(),
     }
 }
 
@@ -120,9 +128,12 @@ impl<'ast, T: Field> Flattener<T> {
                 vec![self.fold_field_expression(e).into()]
             }
             typed_absy::TypedExpression::Boolean(e) => vec![self.fold_boolean_expression(e).into()],
-            typed_absy::TypedExpression::Uint(e) => vec![self.fold_uint_expression(e).into()],
+            typed_absy::TypedExpression::Uint(e) | typed_absy::TypedExpression::Int(e) => {
+                vec![self.fold_uint_expression(e).into()]
+            }
             typed_absy::TypedExpression::Array(e) => self.fold_array_expression(e).into(),
             typed_absy::TypedExpression::Struct(e) => self.fold_struct_expression(e).into(),
+            typed_absy::TypedExpression::Tuple(e) => self.fold_tuple_expression(e),
         }
     }
 
@@ -140,6 +151,13 @@ impl<'ast, T: Field> Flattener<T> {
         fold_struct_expression(self, e)
     }
 
+    fn fold_tuple_expression(
+        &mut self,
+        e: typed_absy::TupleExpression<'ast, T>,
+    ) -> Vec<zir::ZirExpression<'ast, T>> {
+        fold_tuple_expression(self, e)
+    }
+
     fn fold_expression_list(
         &mut self,
         es: typed_absy::TypedExpressionList<'ast, T>,
@@ -207,6 +225,23 @@ impl<'ast, T: Field> Flattener<T> {
     ) -> Vec<zir::ZirExpression<'ast, T>> {
         fold_struct_expression_inner(self, ty, e)
     }
+    fn fold_tuple_expression_inner(
+        &mut self,
+        ty: &TupleType,
+        e: typed_absy::TupleExpressionInner<'ast, T>,
+    ) -> Vec<zir::ZirExpression<'ast, T>> {
+        fold_tuple_expression_inner(self, ty, e)
+    }
+}
+
+/// Returns the position and primitive count of the element at `index` in the flattened tuple
+fn element_range(ty: &TupleType, index: usize) -> (usize, usize) {
+    let offset = ty
+        .iter()
+        .take(index)
+        .map(|ty| ty.get_primitive_count())
+        .sum();
+    (offset, ty.elements[index].get_primitive_count())
 }
 
 pub fn fold_module<'ast, T: Field>(
@@ -248,11 +283,15 @@ pub fn fold_statement<'ast, T: Field>(
                 .map(|v| zir::ZirStatement::Declaration(v))
                 .collect()
         }
-        typed_absy::TypedStatement::Assertion(e) => {
+        typed_absy::TypedStatement::Assertion(e, metadata) => {
             let e = f.fold_boolean_expression(e);
-            vec![zir::ZirStatement::Assertion(e)]
+            vec![zir::ZirStatement::Assertion(e, metadata)]
         }
-        typed_absy::TypedStatement::For(..) => unreachable!(),
+        typed_absy::TypedStatement::For(..)
+        | typed_absy::TypedStatement::IfElse(..)
+        | typed_absy::TypedStatement::Break(..)
+        | typed_absy::TypedStatement::Continue(..) => unreachable!(),
+        typed_absy::TypedStatement::Origin(origin) => vec![zir::ZirStatement::Origin(origin)],
         typed_absy::TypedStatement::MultipleDefinition(variables, elist) => {
             vec![zir::ZirStatement::MultipleDefinition(
                 variables
@@ -351,6 +390,11 @@ pub fn fold_array_expression_inner<'ast, T: Field>(
                 _ => unreachable!(),
             }
         }
+        typed_absy::ArrayExpressionInner::Element(box t, index) => {
+            let (offset, size) = element_range(t.ty(), index);
+            let t = f.fold_tuple_expression(t);
+            t[offset..offset + size].to_vec()
+        }
     }
 }
 
@@ -447,6 +491,103 @@ pub fn fold_struct_expression_inner<'ast, T: Field>(
                 _ => unreachable!(),
             }
         }
+        typed_absy::StructExpressionInner::Element(box t, index) => {
+            let (offset, size) = element_range(t.ty(), index);
+            let t = f.fold_tuple_expression(t);
+            t[offset..offset + size].to_vec()
+        }
+    }
+}
+
+pub fn fold_tuple_expression_inner<'ast, T: Field>(
+    f: &mut Flattener<T>,
+    t: &TupleType,
+    e: typed_absy::TupleExpressionInner<'ast, T>,
+) -> Vec<zir::ZirExpression<'ast, T>> {
+    match e {
+        typed_absy::TupleExpressionInner::Identifier(id) => {
+            let variables =
+                flatten_identifier_rec(f.fold_name(id), typed_absy::Type::Tuple(t.clone()));
+            variables
+                .into_iter()
+                .map(|v| match v._type {
+                    zir::Type::FieldElement => zir::FieldElementExpression::Identifier(v.id).into(),
+                    zir::Type::Boolean => zir::BooleanExpression::Identifier(v.id).into(),
+                    zir::Type::Uint(bitwidth) => zir::UExpressionInner::Identifier(v.id)
+                        .annotate(bitwidth)
+                        .into(),
+                })
+                .collect()
+        }
+        typed_absy::TupleExpressionInner::Value(exprs) => exprs
+            .into_iter()
+            .flat_map(|e| f.fold_expression(e))
+            .collect(),
+        typed_absy::TupleExpressionInner::FunctionCall(..) => unreachable!(),
+        typed_absy::TupleExpressionInner::IfElse(
+            box condition,
+            box consequence,
+            box alternative,
+        ) => {
+            let condition = f.fold_boolean_expression(condition);
+            let consequence = f.fold_tuple_expression(consequence);
+            let alternative = f.fold_tuple_expression(alternative);
+
+            assert_eq!(consequence.len(), alternative.len());
+
+            use zir::IfElse;
+
+            consequence
+                .into_iter()
+                .zip(alternative.into_iter())
+                .map(|(c, a)| match (c, a) {
+                    (zir::ZirExpression::FieldElement(c), zir::ZirExpression::FieldElement(a)) => {
+                        zir::FieldElementExpression::if_else(condition.clone(), c, a).into()
+                    }
+                    (zir::ZirExpression::Boolean(c), zir::ZirExpression::Boolean(a)) => {
+                        zir::BooleanExpression::if_else(condition.clone(), c, a).into()
+                    }
+                    (zir::ZirExpression::Uint(c), zir::ZirExpression::Uint(a)) => {
+                        zir::UExpression::if_else(condition.clone(), c, a).into()
+                    }
+                    _ => unreachable!(),
+                })
+                .collect()
+        }
+        typed_absy::TupleExpressionInner::Member(box s, id) => {
+            let members = s.ty().clone();
+
+            let s = f.fold_struct_expression(s);
+
+            let offset: usize = members
+                .iter()
+                .take_while(|member| member.id != id)
+                .map(|member| member.ty.get_primitive_count())
+                .sum();
+
+            let size = typed_absy::Type::Tuple(t.clone()).get_primitive_count();
+
+            s[offset..offset + size].to_vec()
+        }
+        typed_absy::TupleExpressionInner::Select(box array, box index) => {
+            let array = f.fold_array_expression(array);
+            let index = f.fold_field_expression(index);
+
+            match index {
+                zir::FieldElementExpression::Number(i) => {
+                    let size = typed_absy::Type::Tuple(t.clone()).get_primitive_count();
+                    let start = i.to_dec_string().parse::<usize>().unwrap() * size;
+                    let end = start + size;
+                    array[start..end].to_vec()
+                }
+                _ => unreachable!(),
+            }
+        }
+        typed_absy::TupleExpressionInner::Element(box tuple, index) => {
+            let (offset, size) = element_range(tuple.ty(), index);
+            let tuple = f.fold_tuple_expression(tuple);
+            tuple[offset..offset + size].to_vec()
+        }
     }
 }
 
@@ -526,6 +667,14 @@ pub fn fold_field_expression<'ast, T: Field>(
                 _ => unreachable!(""),
             }
         }
+        typed_absy::FieldElementExpression::Element(box t, index) => {
+            let (offset, _) = element_range(t.ty(), index);
+            let t = f.fold_tuple_expression(t);
+
+            use std::convert::TryInto;
+
+            t[offset].clone().try_into().unwrap()
+        }
     }
 }
 
@@ -612,6 +761,30 @@ pub fn fold_boolean_expression<'ast, T: Field>(
 
             zir::BooleanExpression::UintEq(box e1, box e2)
         }
+        typed_absy::BooleanExpression::UintLt(box e1, box e2) => {
+            let e1 = f.fold_uint_expression(e1);
+            let e2 = f.fold_uint_expression(e2);
+
+            zir::BooleanExpression::UintLt(box e1, box e2)
+        }
+        typed_absy::BooleanExpression::UintLe(box e1, box e2) => {
+            let e1 = f.fold_uint_expression(e1);
+            let e2 = f.fold_uint_expression(e2);
+
+            zir::BooleanExpression::UintLe(box e1, box e2)
+        }
+        typed_absy::BooleanExpression::UintGe(box e1, box e2) => {
+            let e1 = f.fold_uint_expression(e1);
+            let e2 = f.fold_uint_expression(e2);
+
+            zir::BooleanExpression::UintGe(box e1, box e2)
+        }
+        typed_absy::BooleanExpression::UintGt(box e1, box e2) => {
+            let e1 = f.fold_uint_expression(e1);
+            let e2 = f.fold_uint_expression(e2);
+
+            zir::BooleanExpression::UintGt(box e1, box e2)
+        }
         typed_absy::BooleanExpression::Lt(box e1, box e2) => {
             let e1 = f.fold_field_expression(e1);
             let e2 = f.fold_field_expression(e2);
@@ -683,6 +856,14 @@ pub fn fold_boolean_expression<'ast, T: Field>(
                 _ => unreachable!(),
             }
         }
+        typed_absy::BooleanExpression::Element(box t, index) => {
+            let (offset, _) = element_range(t.ty(), index);
+            let t = f.fold_tuple_expression(t);
+
+            use std::convert::TryInto;
+
+            t[offset].clone().try_into().unwrap()
+        }
     }
 }
 
@@ -724,6 +905,18 @@ pub fn fold_uint_expression_inner<'ast, T: Field>(
 
             zir::UExpressionInner::Mult(box left, box right)
         }
+        typed_absy::UExpressionInner::Div(box left, box right) => {
+            let left = f.fold_uint_expression(left);
+            let right = f.fold_uint_expression(right);
+
+            zir::UExpressionInner::Div(box left, box right)
+        }
+        typed_absy::UExpressionInner::Rem(box left, box right) => {
+            let left = f.fold_uint_expression(left);
+            let right = f.fold_uint_expression(right);
+
+            zir::UExpressionInner::Rem(box left, box right)
+        }
         typed_absy::UExpressionInner::Xor(box left, box right) => {
             let left = f.fold_uint_expression(left);
             let right = f.fold_uint_expression(right);
@@ -796,6 +989,16 @@ pub fn fold_uint_expression_inner<'ast, T: Field>(
 
             res.into_inner()
         }
+        typed_absy::UExpressionInner::Element(box t, index) => {
+            let (offset, _) = element_range(t.ty(), index);
+            let t = f.fold_tuple_expression(t);
+
+            use std::convert::TryInto;
+
+            let res: zir::UExpression<'ast, T> = t[offset].clone().try_into().unwrap();
+
+            res.into_inner()
+        }
         typed_absy::UExpressionInner::IfElse(box cond, box cons, box alt) => {
             let cond = f.fold_boolean_expression(cond);
             let cons = f.fold_uint_expression(cons);
@@ -838,6 +1041,13 @@ pub fn fold_struct_expression<'ast, T: Field>(
     f.fold_struct_expression_inner(&e.ty().clone(), e.into_inner())
 }
 
+pub fn fold_tuple_expression<'ast, T: Field>(
+    f: &mut Flattener<T>,
+    e: typed_absy::TupleExpression<'ast, T>,
+) -> Vec<zir::ZirExpression<'ast, T>> {
+    f.fold_tuple_expression_inner(&e.ty().clone(), e.into_inner())
+}
+
 pub fn fold_function_symbol<'ast, T: Field>(
     f: &mut Flattener<T>,
     s: typed_absy::TypedFunctionSymbol<'ast, T>,
@@ -0,0 +1,501 @@
+//! Module containing the guarding of statements by a boolean condition
+//!
+//! Guarded statements only have an effect when the condition holds: definitions of variables keep their
+//! previous value otherwise, and assertions are trivially satisfied. For example, guarding by `c`
+//!
+//! ```zokrates
+//! assert(a == 1)
+//! a = a + 1
+//! ```
+//!
+//! gives
+//!
+//! ```zokrates
+//! assert(!c || a == 1)
+//! a = if c then a + 1 else a fi
+//! ```
+//!
+//! Variables declared in the guarded statements are not visible after them, so their definitions are left unguarded.
+//! As with conditional expressions, every operation of the guarded statements is still executed. Divisors are
+//! therefore guarded as well, so that `a = a / b` gives `a = if c then a / (if c then b else 1 fi) else a fi` and
+//! does not fail when `c` does not hold and `b` is zero. Calls are not guarded, so calls to functions which can fail
+//! are rejected in guarded statements beforehand (see `guarded_calls.rs`).
+
+use crate::typed_absy::folder::*;
+use crate::typed_absy::types::Type;
+use crate::typed_absy::*;
+use std::collections::HashSet;
+use std::convert::TryFrom;
+use typed_absy::identifier::CoreIdentifier;
+use zokrates_field::Field;
+
+pub struct Guard<'ast, T: Field> {
+    condition: BooleanExpression<'ast, T>,
+    // variables declared in the guarded statements
+    locals: HashSet<CoreIdentifier<'ast>>,
+}
+
+impl<'ast, T: Field> Guard<'ast, T> {
+    /// Guard `statements` by `condition`, which is evaluated before each of them
+    pub fn guard(
+        condition: BooleanExpression<'ast, T>,
+        statements: Vec<TypedStatement<'ast, T>>,
+    ) -> Vec<TypedStatement<'ast, T>> {
+        let mut guard = Guard {
+            condition,
+            locals: HashSet::new(),
+        };
+
+        let statements = statements
+            .into_iter()
+            .flat_map(|s| guard.fold_statement(s))
+            .collect();
+
+        guard.guard_statements(statements)
+    }
+
+    fn is_local(&self, a: &TypedAssignee<'ast, T>) -> bool {
+        match a {
+            TypedAssignee::Identifier(v) => self.locals.contains(&v.id.id),
+            TypedAssignee::Select(box a, _) => self.is_local(a),
+            TypedAssignee::Member(box a, _) => self.is_local(a),
+            TypedAssignee::Element(box a, _) => self.is_local(a),
+        }
+    }
+
+    fn guard_definition(
+        &self,
+        a: TypedAssignee<'ast, T>,
+        e: TypedExpression<'ast, T>,
+    ) -> TypedStatement<'ast, T> {
+        match self.is_local(&a) {
+            true => TypedStatement::Definition(a, e),
+            false => {
+                let current = assignee_expression(&a);
+                TypedStatement::Definition(a, guarded(self.condition.clone(), e, current))
+            }
+        }
+    }
+
+    fn guard_statements(
+        &mut self,
+        statements: Vec<TypedStatement<'ast, T>>,
+    ) -> Vec<TypedStatement<'ast, T>> {
+        statements
+            .into_iter()
+            .flat_map(|s| self.guard_statement(s))
+            .collect()
+    }
+
+    fn guard_statement(&mut self, s: TypedStatement<'ast, T>) -> Vec<TypedStatement<'ast, T>> {
+        match s {
+            TypedStatement::Declaration(v) => {
+                self.locals.insert(v.id.id.clone());
+                vec![TypedStatement::Declaration(v)]
+            }
+            TypedStatement::Definition(a, e) => vec![self.guard_definition(a, e)],
+            TypedStatement::Assertion(e, metadata) => vec![TypedStatement::Assertion(
+                BooleanExpression::Or(
+                    box BooleanExpression::Not(box self.condition.clone()),
+                    box e,
+                ),
+                metadata,
+            )],
+            TypedStatement::MultipleDefinition(variables, list) => {
+                if variables.iter().all(|v| self.locals.contains(&v.id.id)) {
+                    return vec![TypedStatement::MultipleDefinition(variables, list)];
+                }
+
+                // assign to temporary variables first, then to the guarded variables
+                let temporaries: Vec<_> = variables
+                    .iter()
+                    .enumerate()
+                    .map(|(i, v)| {
+                        Variable::with_id_and_type(
+                            CoreIdentifier::Internal("GUARD", i),
+                            v.get_type(),
+                        )
+                    })
+                    .collect();
+
+                let mut res: Vec<_> = temporaries
+                    .iter()
+                    .map(|t| TypedStatement::Declaration(t.clone()))
+                    .collect();
+
+                res.push(TypedStatement::MultipleDefinition(
+                    temporaries.clone(),
+                    list,
+                ));
+
+                for (v, t) in variables.into_iter().zip(temporaries.into_iter()) {
+                    let value = assignee_expression(&TypedAssignee::Identifier(t));
+                    res.push(self.guard_definition(TypedAssignee::Identifier(v), value));
+                }
+
+                res
+            }
+            TypedStatement::For(v, from, to, statements) => vec![TypedStatement::For(
+                v,
+                from,
+                to,
+                self.guard_statements(statements),
+            )],
+            TypedStatement::IfElse(condition, consequence, alternative) => {
+                vec![TypedStatement::IfElse(
+                    condition,
+                    self.guard_statements(consequence),
+                    self.guard_statements(alternative),
+                )]
+            }
+            s => vec![s],
+        }
+    }
+
+    /// Returns `if condition then d else 1 fi`, or `d` if it is a non-zero constant
+    fn guard_field_divisor(
+        &mut self,
+        d: FieldElementExpression<'ast, T>,
+    ) -> FieldElementExpression<'ast, T> {
+        match self.fold_field_expression(d) {
+            FieldElementExpression::Number(n) if n != T::zero() => {
+                FieldElementExpression::Number(n)
+            }
+            d => FieldElementExpression::if_else(
+                self.condition.clone(),
+                d,
+                FieldElementExpression::Number(T::one()),
+            ),
+        }
+    }
+
+    /// Returns `if condition then d else 1 fi`, or `d` if it is a non-zero constant
+    fn guard_uint_divisor(&mut self, d: UExpression<'ast, T>) -> UExpression<'ast, T> {
+        let d = self.fold_uint_expression(d);
+        let non_zero = match d.as_inner() {
+            UExpressionInner::Value(v) => *v != 0,
+            _ => false,
+        };
+
+        match non_zero {
+            true => d,
+            false => {
+                let bitwidth = d.bitwidth();
+                UExpression::if_else(
+                    self.condition.clone(),
+                    d,
+                    UExpressionInner::Value(1).annotate(bitwidth),
+                )
+            }
+        }
+    }
+}
+
+// guard divisors, so that divisions do not fail when the condition does not hold
+impl<'ast, T: Field> Folder<'ast, T> for Guard<'ast, T> {
+    fn fold_field_expression(
+        &mut self,
+        e: FieldElementExpression<'ast, T>,
+    ) -> FieldElementExpression<'ast, T> {
+        match e {
+            FieldElementExpression::Div(box e1, box e2) => {
+                let e1 = self.fold_field_expression(e1);
+                let e2 = self.guard_field_divisor(e2);
+                FieldElementExpression::Div(box e1, box e2)
+            }
+            e => fold_field_expression(self, e),
+        }
+    }
+
+    fn fold_uint_expression_inner(
+        &mut self,
+        bitwidth: UBitwidth,
+        e: UExpressionInner<'ast, T>,
+    ) -> UExpressionInner<'ast, T> {
+        match e {
+            UExpressionInner::Div(box left, box right) => {
+                let left = self.fold_uint_expression(left);
+                let right = self.guard_uint_divisor(right);
+                UExpressionInner::Div(box left, box right)
+            }
+            UExpressionInner::Rem(box left, box right) => {
+                let left = self.fold_uint_expression(left);
+                let right = self.guard_uint_divisor(right);
+                UExpressionInner::Rem(box left, box right)
+            }
+            e => fold_uint_expression_inner(self, bitwidth, e),
+        }
+    }
+}
+
+/// Returns `if condition then e else current fi`
+fn guarded<'ast, T: Field>(
+    condition: BooleanExpression<'ast, T>,
+    e: TypedExpression<'ast, T>,
+    current: TypedExpression<'ast, T>,
+) -> TypedExpression<'ast, T> {
+    match (e, current) {
+        (TypedExpression::FieldElement(e), TypedExpression::FieldElement(current)) => {
+            FieldElementExpression::if_else(condition, e, current).into()
+        }
+        (TypedExpression::Boolean(e), TypedExpression::Boolean(current)) => {
+            BooleanExpression::if_else(condition, e, current).into()
+        }
+        (TypedExpression::Uint(e), TypedExpression::Uint(current)) => {
+            UExpression::if_else(condition, e, current).into()
+        }
+        (TypedExpression::Int(e), TypedExpression::Int(current)) => {
+            TypedExpression::Int(UExpression::if_else(condition, e, current))
+        }
+        (TypedExpression::Array(e), TypedExpression::Array(current)) => {
+            ArrayExpression::if_else(condition, e, current).into()
+        }
+        (TypedExpression::Struct(e), TypedExpression::Struct(current)) => {
+            StructExpression::if_else(condition, e, current).into()
+        }
+        (TypedExpression::Tuple(e), TypedExpression::Tuple(current)) => {
+            TupleExpression::if_else(condition, e, current).into()
+        }
+        (e, current) => unreachable!(
+            "cannot guard {} with {} as their types differ",
+            e.get_type(),
+            current.get_type()
+        ),
+    }
+}
+
+/// Returns the expression reading the current value of an assignee
+fn assignee_expression<'ast, T: Field>(a: &TypedAssignee<'ast, T>) -> TypedExpression<'ast, T> {
+    match a {
+        TypedAssignee::Identifier(v) => {
+            let id = v.id.clone();
+            match v.get_type() {
+                Type::FieldElement => FieldElementExpression::Identifier(id).into(),
+                Type::Boolean => BooleanExpression::Identifier(id).into(),
+                Type::Uint(bitwidth) => UExpressionInner::Identifier(id).annotate(bitwidth).into(),
+                Type::Int(bitwidth) => {
+                    TypedExpression::Int(UExpressionInner::Identifier(id).annotate(bitwidth))
+                }
+                Type::Array(array_type) => ArrayExpressionInner::Identifier(id)
+                    .annotate(*array_type.ty, array_type.size)
+                    .into(),
+                Type::Struct(members) => StructExpressionInner::Identifier(id)
+                    .annotate(members)
+                    .into(),
+                Type::Tuple(tuple_type) => TupleExpressionInner::Identifier(id)
+                    .annotate(tuple_type)
+                    .into(),
+            }
+        }
+        TypedAssignee::Select(box array, box index) => {
+            let array = ArrayExpression::try_from(assignee_expression(array)).unwrap();
+            let index = index.clone();
+            match array.inner_type().clone() {
+                Type::FieldElement => FieldElementExpression::select(array, index).into(),
+                Type::Boolean => BooleanExpression::select(array, index).into(),
+                Type::Uint(..) => UExpression::select(array, index).into(),
+                Type::Int(..) => TypedExpression::Int(UExpression::select(array, index)),
+                Type::Array(..) => ArrayExpression::select(array, index).into(),
+                Type::Struct(..) => StructExpression::select(array, index).into(),
+                Type::Tuple(..) => TupleExpression::select(array, index).into(),
+            }
+        }
+        TypedAssignee::Member(box s, id) => {
+            let s = StructExpression::try_from(assignee_expression(s)).unwrap();
+            let id = id.clone();
+            match a.get_type() {
+                Type::FieldElement => FieldElementExpression::member(s, id).into(),
+                Type::Boolean => BooleanExpression::member(s, id).into(),
+                Type::Uint(..) => UExpression::member(s, id).into(),
+                Type::Int(..) => TypedExpression::Int(UExpression::member(s, id)),
+                Type::Array(..) => ArrayExpression::member(s, id).into(),
+                Type::Struct(..) => StructExpression::member(s, id).into(),
+                Type::Tuple(..) => TupleExpression::member(s, id).into(),
+            }
+        }
+        TypedAssignee::Element(box t, index) => {
+            let t = TupleExpression::try_from(assignee_expression(t)).unwrap();
+            let index = *index;
+            match a.get_type() {
+                Type::FieldElement => FieldElementExpression::element(t, index).into(),
+                Type::Boolean => BooleanExpression::element(t, index).into(),
+                Type::Uint(..) => UExpression::element(t, index).into(),
+                Type::Int(..) => TypedExpression::Int(UExpression::element(t, index)),
+                Type::Array(..) => ArrayExpression::element(t, index).into(),
+                Type::Struct(..) => StructExpression::element(t, index).into(),
+                Type::Tuple(..) => TupleExpression::element(t, index).into(),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zokrates_field::Bn128Field;
+
+    fn c() -> BooleanExpression<'static, Bn128Field> {
+        BooleanExpression::Identifier("c".into())
+    }
+
+    fn a() -> FieldElementExpression<'static, Bn128Field> {
+        FieldElementExpression::Identifier("a".into())
+    }
+
+    fn one() -> FieldElementExpression<'static, Bn128Field> {
+        FieldElementExpression::Number(Bn128Field::from(1))
+    }
+
+    #[test]
+    fn definitions() {
+        // a = 1
+        // field b = 1
+
+        // should be turned into
+        // a = if c then 1 else a fi
+        // field b = 1
+
+        let statements = vec![
+            TypedStatement::Definition(
+                TypedAssignee::Identifier(Variable::field_element("a")),
+                one().into(),
+            ),
+            TypedStatement::Declaration(Variable::field_element("b")),
+            TypedStatement::Definition(
+                TypedAssignee::Identifier(Variable::field_element("b")),
+                one().into(),
+            ),
+        ];
+
+        assert_eq!(
+            Guard::guard(c(), statements),
+            vec![
+                TypedStatement::Definition(
+                    TypedAssignee::Identifier(Variable::field_element("a")),
+                    FieldElementExpression::if_else(c(), one(), a()).into(),
+                ),
+                TypedStatement::Declaration(Variable::field_element("b")),
+                TypedStatement::Definition(
+                    TypedAssignee::Identifier(Variable::field_element("b")),
+                    one().into(),
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn divisions() {
+        // a = 1 / a
+        // field b = 1 / a
+
+        // should be turned into
+        // a = if c then 1 / (if c then a else 1 fi) else a fi
+        // field b = 1 / (if c then a else 1 fi)
+
+        let statements = vec![
+            TypedStatement::Definition(
+                TypedAssignee::Identifier(Variable::field_element("a")),
+                FieldElementExpression::Div(box one(), box a()).into(),
+            ),
+            TypedStatement::Declaration(Variable::field_element("b")),
+            TypedStatement::Definition(
+                TypedAssignee::Identifier(Variable::field_element("b")),
+                FieldElementExpression::Div(box one(), box a()).into(),
+            ),
+        ];
+
+        let guarded_division = || {
+            FieldElementExpression::Div(
+                box one(),
+                box FieldElementExpression::if_else(c(), a(), one()),
+            )
+        };
+
+        assert_eq!(
+            Guard::guard(c(), statements),
+            vec![
+                TypedStatement::Definition(
+                    TypedAssignee::Identifier(Variable::field_element("a")),
+                    FieldElementExpression::if_else(c(), guarded_division(), a()).into(),
+                ),
+                TypedStatement::Declaration(Variable::field_element("b")),
+                TypedStatement::Definition(
+                    TypedAssignee::Identifier(Variable::field_element("b")),
+                    guarded_division().into(),
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn assertions() {
+        // assert(a == 1)
+
+        // should be turned into
+        // assert(!c || a == 1)
+
+        let statements = vec![TypedStatement::Assertion(
+            BooleanExpression::FieldEq(box a(), box one()),
+            None,
+        )];
+
+        assert_eq!(
+            Guard::guard(c(), statements),
+            vec![TypedStatement::Assertion(
+                BooleanExpression::Or(
+                    box BooleanExpression::Not(box c()),
+                    box BooleanExpression::FieldEq(box a(), box one()),
+                ),
+                None,
+            )]
+        );
+    }
+
+    #[test]
+    fn nested_if_else() {
+        // if d then
+        //   a = 1
+        // else
+        //   assert(a == 1)
+        // fi
+
+        // should be turned into
+        // if d then
+        //   a = if c then 1 else a fi
+        // else
+        //   assert(!c || a == 1)
+        // fi
+
+        let d = || BooleanExpression::Identifier("d".into());
+
+        let statements = vec![TypedStatement::IfElse(
+            d(),
+            vec![TypedStatement::Definition(
+                TypedAssignee::Identifier(Variable::field_element("a")),
+                one().into(),
+            )],
+            vec![TypedStatement::Assertion(
+                BooleanExpression::FieldEq(box a(), box one()),
+                None,
+            )],
+        )];
+
+        assert_eq!(
+            Guard::guard(c(), statements),
+            vec![TypedStatement::IfElse(
+                d(),
+                vec![TypedStatement::Definition(
+                    TypedAssignee::Identifier(Variable::field_element("a")),
+                    FieldElementExpression::if_else(c(), one(), a()).into(),
+                )],
+                vec![TypedStatement::Assertion(
+                    BooleanExpression::Or(
+                        box BooleanExpression::Not(box c()),
+                        box BooleanExpression::FieldEq(box a(), box one()),
+                    ),
+                    None,
+                )],
+            )]
+        );
+    }
+}
@@ -0,0 +1,277 @@
+//! Module containing the detection of calls to functions which can fail in guarded statements
+//!
+//! The statements of if statements and of loops with early exits are guarded, so that their assertions and divisors
+//! only apply when they are reached (see `guard.rs`). Calls are not guarded: the called function is inlined and runs
+//! in full, so an assertion or a division it contains would fail even when the call is not reached. For example
+//!
+//! ```zokrates
+//! def non_zero(field a) -> field:
+//! 	assert(a != 0)
+//! 	return a
+//!
+//! def main(field a) -> field:
+//! 	field res = 0
+//! 	if a != 0 then
+//! 		res = non_zero(a)
+//! 	fi
+//! 	return res
+//! ```
+//!
+//! would not accept `a = 0`. Such calls are rejected instead.
+
+use crate::static_analysis::early_exit::has_early_exit;
+use crate::static_analysis::{Error, ErrorInner};
+use crate::typed_absy::folder::*;
+use crate::typed_absy::types::FunctionKey;
+use crate::typed_absy::*;
+use source_map::Origin;
+use std::collections::HashMap;
+use zokrates_field::Field;
+
+pub struct GuardedCallDetector<'a, 'ast, T: Field> {
+    modules: &'a TypedModules<'ast, T>,
+    // the module of the function being visited, in which its calls are resolved
+    module_id: TypedModuleId,
+    // whether each function visited so far contains an assertion or a division, or calls a function which does
+    fails: HashMap<(TypedModuleId, FunctionKey<'ast>), bool>,
+    // whether the function being visited contains an assertion or a division so far
+    failing: bool,
+    // the number of guards enclosing the current statement
+    guards: usize,
+    // the origin of the current statement
+    origin: Option<Origin>,
+    // the first guarded call found to a function which can fail
+    error: Option<Error>,
+}
+
+impl<'a, 'ast, T: Field> GuardedCallDetector<'a, 'ast, T> {
+    /// Returns an error for the first call to a function which can fail in guarded statements, if any
+    pub fn detect(p: &TypedProgram<'ast, T>) -> Result<(), Error> {
+        let mut detector = GuardedCallDetector {
+            modules: &p.modules,
+            module_id: p.main.clone(),
+            fails: HashMap::new(),
+            failing: false,
+            guards: 0,
+            origin: None,
+            error: None,
+        };
+
+        // visit the main module first, so that its errors are reported first
+        let mut module_ids: Vec<_> = p.modules.keys().collect();
+        module_ids.sort_by_key(|id| **id != p.main);
+
+        for module_id in module_ids {
+            for key in p.modules[module_id].functions.keys() {
+                detector.module_id = module_id.clone();
+                detector.can_fail(key);
+            }
+        }
+
+        match detector.error {
+            Some(e) => Err(e),
+            None => Ok(()),
+        }
+    }
+
+    /// Returns whether the function `key` of the current module contains an assertion or a division, following
+    /// imports and calls. The function is visited the first time, so that its own guarded calls are detected
+    fn can_fail(&mut self, key: &FunctionKey<'ast>) -> bool {
+        let location = (self.module_id.clone(), key.clone());
+
+        if let Some(fails) = self.fails.get(&location) {
+            return *fails;
+        }
+
+        // functions cannot be recursive, but do not loop forever if they are
+        self.fails.insert(location.clone(), false);
+
+        let modules = self.modules;
+
+        let fails = match modules[&self.module_id].functions.get(key) {
+            Some(TypedFunctionSymbol::Here(f)) => {
+                let failing = std::mem::replace(&mut self.failing, false);
+                let guards = std::mem::replace(&mut self.guards, 0);
+                let origin = self.origin.take();
+
+                self.fold_function(f.clone());
+
+                self.guards = guards;
+                self.origin = origin;
+                std::mem::replace(&mut self.failing, failing)
+            }
+            Some(TypedFunctionSymbol::There(key, module_id)) => {
+                let current = std::mem::replace(&mut self.module_id, module_id.clone());
+                let fails = self.can_fail(key);
+                self.module_id = current;
+                fails
+            }
+            // embeds accept any input of their type
+            Some(TypedFunctionSymbol::Flat(..)) | None => false,
+        };
+
+        self.fails.insert(location, fails);
+
+        fails
+    }
+
+    fn call(&mut self, key: &FunctionKey<'ast>) {
+        if self.can_fail(key) {
+            self.failing = true;
+
+            if self.guards > 0 && self.error.is_none() {
+                self.error = Some(Error {
+                    inner: ErrorInner::GuardedCall(key.id.to_string()),
+                    origin: self.origin.clone(),
+                });
+            }
+        }
+    }
+}
+
+impl<'a, 'ast, T: Field> Folder<'ast, T> for GuardedCallDetector<'a, 'ast, T> {
+    fn fold_statement(&mut self, s: TypedStatement<'ast, T>) -> Vec<TypedStatement<'ast, T>> {
+        match s {
+            TypedStatement::Origin(origin) => {
+                self.origin = Some(origin.clone());
+                vec![TypedStatement::Origin(origin)]
+            }
+            TypedStatement::Assertion(..) => {
+                self.failing = true;
+                fold_statement(self, s)
+            }
+            TypedStatement::IfElse(condition, consequence, alternative) => {
+                let condition = self.fold_boolean_expression(condition);
+
+                self.guards += 1;
+                let consequence = consequence
+                    .into_iter()
+                    .flat_map(|s| self.fold_statement(s))
+                    .collect();
+                let alternative = alternative
+                    .into_iter()
+                    .flat_map(|s| self.fold_statement(s))
+                    .collect();
+                self.guards -= 1;
+
+                vec![TypedStatement::IfElse(condition, consequence, alternative)]
+            }
+            // the whole body of a loop with early exits is guarded, as it is skipped once the loop is exited
+            TypedStatement::For(v, from, to, statements) => {
+                let guards = match has_early_exit(&statements) {
+                    true => 1,
+                    false => 0,
+                };
+
+                self.guards += guards;
+                let res = fold_statement(self, TypedStatement::For(v, from, to, statements));
+                self.guards -= guards;
+
+                res
+            }
+            s => fold_statement(self, s),
+        }
+    }
+
+    fn fold_expression_list(
+        &mut self,
+        es: TypedExpressionList<'ast, T>,
+    ) -> TypedExpressionList<'ast, T> {
+        match es {
+            TypedExpressionList::FunctionCall(key, arguments, types) => {
+                self.call(&key);
+                TypedExpressionList::FunctionCall(
+                    key,
+                    arguments
+                        .into_iter()
+                        .map(|a| self.fold_expression(a))
+                        .collect(),
+                    types,
+                )
+            }
+        }
+    }
+
+    fn fold_field_expression(
+        &mut self,
+        e: FieldElementExpression<'ast, T>,
+    ) -> FieldElementExpression<'ast, T> {
+        match e {
+            FieldElementExpression::Div(_, ref d) => match **d {
+                FieldElementExpression::Number(ref n) if *n != T::zero() => {}
+                _ => self.failing = true,
+            },
+            FieldElementExpression::FunctionCall(ref key, _) => self.call(key),
+            _ => {}
+        };
+
+        fold_field_expression(self, e)
+    }
+
+    fn fold_boolean_expression(
+        &mut self,
+        e: BooleanExpression<'ast, T>,
+    ) -> BooleanExpression<'ast, T> {
+        if let BooleanExpression::FunctionCall(ref key, _) = e {
+            self.call(key);
+        }
+
+        fold_boolean_expression(self, e)
+    }
+
+    fn fold_uint_expression_inner(
+        &mut self,
+        bitwidth: UBitwidth,
+        e: UExpressionInner<'ast, T>,
+    ) -> UExpressionInner<'ast, T> {
+        match e {
+            UExpressionInner::Div(_, ref d) | UExpressionInner::Rem(_, ref d) => {
+                match d.as_inner() {
+                    UExpressionInner::Value(v) if *v != 0 => {}
+                    _ => self.failing = true,
+                }
+            }
+            UExpressionInner::FunctionCall(ref key, _) => self.call(key),
+            _ => {}
+        };
+
+        fold_uint_expression_inner(self, bitwidth, e)
+    }
+
+    fn fold_array_expression_inner(
+        &mut self,
+        ty: &Type,
+        size: usize,
+        e: ArrayExpressionInner<'ast, T>,
+    ) -> ArrayExpressionInner<'ast, T> {
+        if let ArrayExpressionInner::FunctionCall(ref key, _) = e {
+            self.call(key);
+        }
+
+        fold_array_expression_inner(self, ty, size, e)
+    }
+
+    fn fold_struct_expression_inner(
+        &mut self,
+        ty: &StructType,
+        e: StructExpressionInner<'ast, T>,
+    ) -> StructExpressionInner<'ast, T> {
+        if let StructExpressionInner::FunctionCall(ref key, _) = e {
+            self.call(key);
+        }
+
+        fold_struct_expression_inner(self, ty, e)
+    }
+
+    fn fold_tuple_expression_inner(
+        &mut self,
+        ty: &TupleType,
+        e: TupleExpressionInner<'ast, T>,
+    ) -> TupleExpressionInner<'ast, T> {
+        if let TupleExpressionInner::FunctionCall(ref key, _) = e {
+            self.call(key);
+        }
+
+        fold_tuple_expression_inner(self, ty, e)
+    }
+}
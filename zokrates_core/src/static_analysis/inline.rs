@@ -16,6 +16,7 @@
 
 //! where any call in `main` must be to `_SHA_256_ROUND` or `_UNPACK`
 
+use source_map::{self, Origin};
 use std::collections::HashMap;
 use typed_absy::types::{FunctionKey, Type, UBitwidth};
 use typed_absy::{folder::*, *};
@@ -56,6 +57,10 @@ pub struct Inliner<'ast, T: Field> {
     call_count: HashMap<(TypedModuleId, FunctionKey<'ast>), usize>,
     /// the cache for memoization: for each function body, tracks function calls
     call_cache: CallCache<'ast, T>,
+    /// the functions calling the current one, used to locate origin markers in the call stack
+    frames: Vec<String>,
+    /// the last origin marker met in the current function
+    origin: Option<Origin>,
 }
 
 impl<'ast, T: Field> Inliner<'ast, T> {
@@ -74,20 +79,23 @@ impl<'ast, T: Field> Inliner<'ast, T> {
             stack: vec![],
             call_count: HashMap::new(),
             call_cache: HashMap::new(),
+            frames: vec![],
+            origin: None,
         }
     }
 
-    pub fn inline(p: TypedProgram<T>) -> TypedProgram<T> {
+    /// Inline all calls from the entry point `entry` of the main module, which becomes `main` in the resulting program
+    pub fn inline(p: TypedProgram<'ast, T>, entry: &str) -> TypedProgram<'ast, T> {
         let main_module_id = p.main;
 
         // get the main module
         let main_module = p.modules.get(&main_module_id).unwrap().clone();
 
-        // get the main function in the main module
+        // get the entry point in the main module
         let (main_key, main) = main_module
             .functions
             .into_iter()
-            .find(|(k, _)| k.id == "main")
+            .find(|(k, _)| k.id == entry)
             .unwrap();
 
         // initialize an inliner over all modules, starting from the main module
@@ -104,6 +112,10 @@ impl<'ast, T: Field> Inliner<'ast, T> {
         let unpack = crate::embed::FlatEmbed::Unpack(T::get_required_bits());
         let unpack_key = unpack.key::<T>();
 
+        // define a function in the main module for the `u64_to_bits` embed
+        let u64_to_bits = crate::embed::FlatEmbed::U64ToBits;
+        let u64_to_bits_key = u64_to_bits.key::<T>();
+
         // define a function in the main module for the `u32_to_bits` embed
         let u32_to_bits = crate::embed::FlatEmbed::U32ToBits;
         let u32_to_bits_key = u32_to_bits.key::<T>();
@@ -116,6 +128,10 @@ impl<'ast, T: Field> Inliner<'ast, T> {
         let u8_to_bits = crate::embed::FlatEmbed::U8ToBits;
         let u8_to_bits_key = u8_to_bits.key::<T>();
 
+        // define a function in the main module for the `u64_from_bits` embed
+        let u64_from_bits = crate::embed::FlatEmbed::U64FromBits;
+        let u64_from_bits_key = u64_from_bits.key::<T>();
+
         // define a function in the main module for the `u32_from_bits` embed
         let u32_from_bits = crate::embed::FlatEmbed::U32FromBits;
         let u32_from_bits_key = u32_from_bits.key::<T>();
@@ -136,13 +152,21 @@ impl<'ast, T: Field> Inliner<'ast, T> {
                 TypedModule {
                     functions: vec![
                         (unpack_key, TypedFunctionSymbol::Flat(unpack)),
+                        (u64_from_bits_key, TypedFunctionSymbol::Flat(u64_from_bits)),
                         (u32_from_bits_key, TypedFunctionSymbol::Flat(u32_from_bits)),
                         (u16_from_bits_key, TypedFunctionSymbol::Flat(u16_from_bits)),
                         (u8_from_bits_key, TypedFunctionSymbol::Flat(u8_from_bits)),
+                        (u64_to_bits_key, TypedFunctionSymbol::Flat(u64_to_bits)),
                         (u32_to_bits_key, TypedFunctionSymbol::Flat(u32_to_bits)),
                         (u16_to_bits_key, TypedFunctionSymbol::Flat(u16_to_bits)),
                         (u8_to_bits_key, TypedFunctionSymbol::Flat(u8_to_bits)),
-                        (main_key, main),
+                        (
+                            FunctionKey {
+                                id: "main",
+                                ..main_key
+                            },
+                            main,
+                        ),
                     ]
                     .into_iter()
                     .collect(),
@@ -171,6 +195,13 @@ impl<'ast, T: Field> Inliner<'ast, T> {
         let res = match self.module().functions.get(&key).unwrap().clone() {
             // if the function called is in the same module, we can go ahead and inline in this module
             TypedFunctionSymbol::Here(function) => {
+                // the origins found in the callee are located under the origin of the call
+                let current_origin = self.origin.take();
+                let current_frames = match &current_origin {
+                    Some(origin) => std::mem::replace(&mut self.frames, origin.stack.clone()),
+                    None => self.frames.clone(),
+                };
+
                 let (current_module, current_key) =
                     self.change_context(self.module_id().clone(), key.clone());
 
@@ -217,6 +248,13 @@ impl<'ast, T: Field> Inliner<'ast, T> {
 
                 self.change_context(current_module, current_key);
 
+                // the statements which follow originate from the caller again
+                self.frames = current_frames;
+                if let Some(origin) = current_origin.clone() {
+                    self.statement_buffer.push(TypedStatement::Origin(origin));
+                }
+                self.origin = current_origin;
+
                 match ret.pop().unwrap() {
                     TypedStatement::Return(exprs) => Ok(exprs),
                     _ => unreachable!(""),
@@ -292,6 +330,15 @@ impl<'ast, T: Field> Inliner<'ast, T> {
     fn module_id(&self) -> &TypedModuleId {
         self.location.module()
     }
+
+    // The name of the function being inlined, as a frame of the call stack
+    fn frame(&self) -> String {
+        format!(
+            "{}::{}",
+            source_map::normalize(self.module_id()),
+            self.location.key.id
+        )
+    }
 }
 
 impl<'ast, T: Field> Folder<'ast, T> for Inliner<'ast, T> {
@@ -321,6 +368,13 @@ impl<'ast, T: Field> Folder<'ast, T> for Inliner<'ast, T> {
                     }
                 }
             },
+            TypedStatement::Origin(origin) => {
+                let mut stack = self.frames.clone();
+                stack.push(self.frame());
+                let origin = Origin { stack, ..origin };
+                self.origin = Some(origin.clone());
+                vec![TypedStatement::Origin(origin)]
+            }
             s => fold_statement(self, s),
         };
         self.statement_buffer.drain(..).chain(folded).collect()
@@ -525,7 +579,7 @@ impl<'ast, T: Field> Folder<'ast, T> for Inliner<'ast, T> {
 
                 match self.try_inline_call(&key, exps) {
                     Ok(mut ret) => match ret.pop().unwrap() {
-                        TypedExpression::Uint(e) => e.into_inner(),
+                        TypedExpression::Uint(e) | TypedExpression::Int(e) => e.into_inner(),
                         _ => unreachable!(),
                     },
                     Err((embed_key, expressions)) => {
@@ -645,7 +699,7 @@ mod tests {
             modules,
         };
 
-        let program = Inliner::inline(program);
+        let program = Inliner::inline(program, "main");
 
         assert_eq!(program.modules.len(), 1);
         assert_eq!(
@@ -767,7 +821,7 @@ mod tests {
             modules,
         };
 
-        let program = Inliner::inline(program);
+        let program = Inliner::inline(program, "main");
 
         assert_eq!(program.modules.len(), 1);
 
@@ -915,7 +969,7 @@ mod tests {
             modules,
         };
 
-        let program = Inliner::inline(program);
+        let program = Inliner::inline(program, "main");
 
         assert_eq!(program.modules.len(), 1);
         assert_eq!(
@@ -1086,7 +1140,7 @@ mod tests {
             modules,
         };
 
-        let program = Inliner::inline(program);
+        let program = Inliner::inline(program, "main");
 
         assert_eq!(program.modules.len(), 1);
         assert_eq!(
@@ -1262,7 +1316,7 @@ mod tests {
             modules,
         };
 
-        let program = Inliner::inline(program);
+        let program = Inliner::inline(program, "main");
 
         assert_eq!(program.modules.len(), 1);
         assert_eq!(
@@ -1355,7 +1409,7 @@ mod tests {
             modules,
         };
 
-        let program = Inliner::inline(program);
+        let program = Inliner::inline(program, "main");
 
         assert_eq!(program.modules.len(), 1);
         assert_eq!(
@@ -1485,7 +1539,7 @@ mod tests {
             modules,
         };
 
-        let program = Inliner::inline(program);
+        let program = Inliner::inline(program, "main");
 
         let stack0 = vec![(
             "id".into(),
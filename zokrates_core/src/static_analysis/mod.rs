@@ -4,9 +4,13 @@
 //! @author Thibaut Schaeffer <thibaut@schaeff.fr>
 //! @date 2018
 
+mod early_exit;
 mod flat_propagation;
 mod flatten_complex_types;
+mod guard;
+mod guarded_calls;
 mod inline;
+mod origin_remover;
 mod propagate_unroll;
 mod propagation;
 mod redefinition;
@@ -17,36 +21,122 @@ mod unroll;
 mod variable_access_remover;
 
 use self::flatten_complex_types::Flattener;
+use self::guarded_calls::GuardedCallDetector;
 use self::inline::Inliner;
+pub use self::origin_remover::OriginRemover;
 use self::propagate_unroll::PropagatedUnroller;
-use self::propagation::Propagator;
+pub use self::propagation::Propagator;
 use self::redefinition::RedefinitionOptimizer;
 use self::return_binder::ReturnBinder;
 use self::uint_optimizer::UintOptimizer;
 use self::unconstrained_vars::UnconstrainedVariableDetector;
 use self::variable_access_remover::VariableAccessRemover;
+use crate::diagnostics::ErrorCode;
 use crate::flat_absy::FlatProg;
 use crate::ir::Prog;
+use crate::parser::Position;
+use crate::source_map::Origin;
+use crate::typed_absy::abi::Abi;
 use crate::typed_absy::TypedProgram;
+use std::fmt;
 use zir::ZirProgram;
 use zokrates_field::Field;
 
+/// An error found while analysing a program whose semantics were checked
+#[derive(Debug, Clone, PartialEq)]
+pub struct Error {
+    pub inner: ErrorInner,
+    /// The origin of the statement the error was found in, if it is known
+    pub origin: Option<Origin>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ErrorInner {
+    /// An array of the given size is accessed at a constant index which is out of its bounds, written out as it may
+    /// not fit in a `usize`
+    OutOfBounds(String, usize),
+    /// A field element is divided by a constant zero
+    DivisionByZero,
+    NonConstantBound,
+    /// Private inputs of `main` which do not appear in any constraint
+    UnconstrainedInputs(Vec<String>),
+    /// A function containing an assertion or a division is called in an if statement or in a loop with early exits
+    GuardedCall(String),
+}
+
+impl Error {
+    /// The position of the error, which is the start of the line of its origin as origins do not track columns
+    pub fn pos(&self) -> Option<(Position, Position)> {
+        self.origin.as_ref().map(|o| {
+            let start = Position {
+                line: o.line,
+                col: 1,
+            };
+            (start, start)
+        })
+    }
+
+    pub fn code(&self) -> ErrorCode {
+        match self.inner {
+            ErrorInner::OutOfBounds(..) => ErrorCode::OutOfBounds,
+            ErrorInner::DivisionByZero => ErrorCode::DivisionByZero,
+            ErrorInner::NonConstantBound => ErrorCode::NonConstantBound,
+            ErrorInner::UnconstrainedInputs(..) => ErrorCode::UnconstrainedInput,
+            ErrorInner::GuardedCall(..) => ErrorCode::GuardedCall,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.inner {
+            ErrorInner::OutOfBounds(index, size) => write!(
+                f,
+                "Index {} is out of bounds for an array of size {}",
+                index, size
+            ),
+            ErrorInner::DivisionByZero => write!(f, "Division by zero"),
+            ErrorInner::NonConstantBound => write!(
+                f,
+                "Loop unrolling failed. This happened because a loop bound is not constant"
+            ),
+            ErrorInner::UnconstrainedInputs(names) => write!(
+                f,
+                "Private inputs must be constrained, found unconstrained inputs: {}",
+                names
+                    .iter()
+                    .map(|n| format!("`{}`", n))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            ErrorInner::GuardedCall(name) => write!(
+                f,
+                "Function `{}` contains assertions or divisions and cannot be called in an if statement or in a loop with `break` or `continue`",
+                name
+            ),
+        }
+    }
+}
+
 pub trait Analyse {
     fn analyse(self) -> Self;
 }
 
 impl<'ast, T: Field> TypedProgram<'ast, T> {
-    pub fn analyse(self) -> ZirProgram<'ast, T> {
+    /// Analyse the program from the entry point `entry`, which becomes `main` in the resulting program
+    pub fn analyse(self, entry: &str) -> Result<ZirProgram<'ast, T>, Error> {
+        // reject calls which cannot be guarded
+        GuardedCallDetector::detect(&self)?;
         // propagated unrolling
-        let r = PropagatedUnroller::unroll(self).unwrap_or_else(|e| panic!(e));
+        let r = PropagatedUnroller::unroll(self)?;
         // return binding
         let r = ReturnBinder::bind(r);
 
         // inline
-        let r = Inliner::inline(r);
+        let r = Inliner::inline(r, entry);
 
         // propagate
-        let r = Propagator::propagate(r);
+        let r = Propagator::propagate(r)?;
 
         // optimize redefinitions
         let r = RedefinitionOptimizer::optimize(r);
@@ -60,7 +150,7 @@ impl<'ast, T: Field> TypedProgram<'ast, T> {
         // optimize uint expressions
         let zir = UintOptimizer::optimize(zir);
 
-        zir
+        Ok(zir)
     }
 }
 
@@ -70,9 +160,39 @@ impl<T: Field> Analyse for FlatProg<T> {
     }
 }
 
-impl<T: Field> Analyse for Prog<T> {
-    fn analyse(self) -> Self {
-        let r = UnconstrainedVariableDetector::detect(self);
-        r
+impl<T: Field> Prog<T> {
+    /// Check that every private input is constrained, using the names of the inputs in `abi` to report the ones
+    /// which are not, located at the first of them using the origins of the `parameters` of `main`
+    pub fn analyse(self, abi: &Abi, parameters: &[Origin]) -> Result<Self, Error> {
+        UnconstrainedVariableDetector::detect(self).map_err(|indices| {
+            // the parameters of the flattened program are the inputs of `main` in order, each input ending at `end`
+            let ends: Vec<_> = abi
+                .inputs
+                .iter()
+                .enumerate()
+                .scan(0, |end, (i, input)| {
+                    *end += input.ty.get_primitive_count();
+                    Some((*end, i, &input.name))
+                })
+                .collect();
+
+            let mut names: Vec<String> = vec![];
+            let mut origin = None;
+            for index in indices {
+                if let Some((_, i, name)) = ends.iter().find(|(end, _, _)| index < *end) {
+                    if !names.contains(name) {
+                        names.push(name.to_string());
+                    }
+                    if origin.is_none() {
+                        origin = parameters.get(*i).cloned();
+                    }
+                }
+            }
+
+            Error {
+                inner: ErrorInner::UnconstrainedInputs(names),
+                origin,
+            }
+        })
     }
 }
@@ -0,0 +1,25 @@
+//! Module containing the removal of origin markers from a program
+//!
+//! Origins are always tracked during static analysis so that errors can be located, but they are only kept in the
+//! compiled program when profiling
+
+use crate::zir::folder::Folder;
+use crate::zir::*;
+use zokrates_field::Field;
+
+pub struct OriginRemover;
+
+impl OriginRemover {
+    pub fn remove<'ast, T: Field>(p: ZirProgram<'ast, T>) -> ZirProgram<'ast, T> {
+        OriginRemover.fold_program(p)
+    }
+}
+
+impl<'ast, T: Field> Folder<'ast, T> for OriginRemover {
+    fn fold_statement(&mut self, s: ZirStatement<'ast, T>) -> Vec<ZirStatement<'ast, T>> {
+        match s {
+            ZirStatement::Origin(..) => vec![],
+            s => vec![s],
+        }
+    }
+}
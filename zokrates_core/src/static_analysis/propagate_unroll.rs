@@ -21,6 +21,7 @@
 
 use static_analysis::propagation::Propagator;
 use static_analysis::unroll::{Output, Unroller};
+use static_analysis::{Error, ErrorInner};
 use typed_absy::TypedProgram;
 use zokrates_field::Field;
 
@@ -29,7 +30,7 @@ pub struct PropagatedUnroller;
 impl PropagatedUnroller {
     pub fn unroll<'ast, T: Field>(
         p: TypedProgram<'ast, T>,
-    ) -> Result<TypedProgram<'ast, T>, &'static str> {
+    ) -> Result<TypedProgram<'ast, T>, Error> {
         let mut blocked_at = None;
 
         // unroll a first time, retrieving whether the unroll is complete
@@ -39,15 +40,18 @@ impl PropagatedUnroller {
             // conditions to exit the loop
             unrolled = match unrolled {
                 Output::Complete(p) => return Ok(p),
-                Output::Incomplete(next, index) => {
+                Output::Incomplete(next, index, origin) => {
                     if Some(index) == blocked_at {
-                        return Err("Loop unrolling failed. This happened because a loop bound is not constant");
+                        return Err(Error {
+                            inner: ErrorInner::NonConstantBound,
+                            origin,
+                        });
                     } else {
                         // update the index where we blocked
                         blocked_at = Some(index);
 
                         // propagate
-                        let propagated = Propagator::propagate_verbose(next);
+                        let propagated = Propagator::propagate_verbose(next)?;
 
                         // unroll
                         Unroller::unroll(propagated)
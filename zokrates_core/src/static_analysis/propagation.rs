@@ -12,6 +12,8 @@
 
 use crate::typed_absy::folder::*;
 use crate::typed_absy::*;
+use source_map::Origin;
+use static_analysis::{Error, ErrorInner};
 use std::collections::HashMap;
 use std::convert::TryFrom;
 use typed_absy::types::Type;
@@ -24,6 +26,29 @@ pub struct Propagator<'ast, T: Field> {
     // the verbose mode doesn't remove statements which assign constants to variables
     // it's required when using propagation in combination with unrolling
     verbose: bool,
+    // the origin of the current statement
+    origin: Option<Origin>,
+    // the first error found
+    error: Option<Error>,
+}
+
+/// Returns a mask of the `bitwidth` lowest bits, so that constants wrap around `2**bitwidth`
+fn uint_mask(bitwidth: UBitwidth) -> u128 {
+    u128::max_value() >> (128 - bitwidth.to_usize())
+}
+
+/// Returns the value of a constant index, if it fits in a `usize`
+fn to_usize<T: Field>(n: &T) -> Option<usize> {
+    n.to_dec_string().parse().ok()
+}
+
+/// Writes a constant index, as a negative number when it wrapped around the field
+fn index_to_string<T: Field>(n: &T) -> String {
+    match (to_usize(n), to_usize(&(T::zero() - n.clone()))) {
+        (Some(n), _) => n.to_string(),
+        (None, Some(minus_n)) => format!("-{}", minus_n),
+        (None, None) => n.to_dec_string(),
+    }
 }
 
 impl<'ast, T: Field> Propagator<'ast, T> {
@@ -31,6 +56,8 @@ impl<'ast, T: Field> Propagator<'ast, T> {
         Propagator {
             constants: HashMap::new(),
             verbose: true,
+            origin: None,
+            error: None,
         }
     }
 
@@ -38,15 +65,75 @@ impl<'ast, T: Field> Propagator<'ast, T> {
         Propagator {
             constants: HashMap::new(),
             verbose: false,
+            origin: None,
+            error: None,
+        }
+    }
+
+    fn run(mut self, p: TypedProgram<'ast, T>) -> Result<TypedProgram<'ast, T>, Error> {
+        let p = self.fold_program(p);
+        match self.error {
+            Some(e) => Err(e),
+            None => Ok(p),
+        }
+    }
+
+    /// Check that `index` is within the bounds of an array of size `size` if it is constant, recording an error in
+    /// the current statement otherwise
+    fn check_bounds(&mut self, index: &FieldElementExpression<'ast, T>, size: usize) -> bool {
+        match index {
+            FieldElementExpression::Number(n) => match to_usize(n) {
+                Some(n_as_usize) if n_as_usize < size => true,
+                // the index can be too large for a `usize`, for example `p - 1` when folding `i - 1` with `i == 0`
+                _ => {
+                    if self.error.is_none() {
+                        self.error = Some(Error {
+                            inner: ErrorInner::OutOfBounds(index_to_string(n), size),
+                            origin: self.origin.clone(),
+                        });
+                    }
+                    false
+                }
+            },
+            _ => true,
+        }
+    }
+
+    fn fold_uint_comparison(
+        &mut self,
+        op: Comparison,
+        e1: UExpression<'ast, T>,
+        e2: UExpression<'ast, T>,
+    ) -> BooleanExpression<'ast, T> {
+        let e1 = self.fold_uint_expression(e1);
+        let e2 = self.fold_uint_expression(e2);
+
+        match (e1.as_inner(), e2.as_inner()) {
+            (UExpressionInner::Value(v1), UExpressionInner::Value(v2)) => {
+                BooleanExpression::Value(op.compare(v1, v2))
+            }
+            _ => BooleanExpression::uint_comparison(op, e1, e2),
         }
     }
 
-    pub fn propagate(p: TypedProgram<'ast, T>) -> TypedProgram<'ast, T> {
-        Propagator::new().fold_program(p)
+    pub fn propagate(p: TypedProgram<'ast, T>) -> Result<TypedProgram<'ast, T>, Error> {
+        Propagator::new().run(p)
+    }
+
+    pub fn propagate_verbose(p: TypedProgram<'ast, T>) -> Result<TypedProgram<'ast, T>, Error> {
+        Propagator::verbose().run(p)
     }
 
-    pub fn propagate_verbose(p: TypedProgram<'ast, T>) -> TypedProgram<'ast, T> {
-        Propagator::verbose().fold_program(p)
+    /// Propagate an expression which does not depend on any variable, returning `None` if it does not reduce to a constant
+    pub fn propagate_constant(
+        e: TypedExpression<'ast, T>,
+    ) -> Result<Option<TypedExpression<'ast, T>>, Error> {
+        let mut propagator = Propagator::new();
+        let e = propagator.fold_expression(e);
+        match propagator.error {
+            Some(error) => Err(error),
+            None => Ok(Some(e).filter(is_constant)),
+        }
     }
 }
 
@@ -62,7 +149,11 @@ fn is_constant<'ast, T: Field>(e: &TypedExpression<'ast, T>) -> bool {
             StructExpressionInner::Value(v) => v.iter().all(|e| is_constant(e)),
             _ => false,
         },
-        TypedExpression::Uint(a) => match a.as_inner() {
+        TypedExpression::Tuple(a) => match a.as_inner() {
+            TupleExpressionInner::Value(v) => v.iter().all(|e| is_constant(e)),
+            _ => false,
+        },
+        TypedExpression::Uint(a) | TypedExpression::Int(a) => match a.as_inner() {
             UExpressionInner::Value(..) => true,
             _ => false,
         },
@@ -79,6 +170,15 @@ impl<'ast, T: Field> Folder<'ast, T> for Propagator<'ast, T> {
     fn fold_statement(&mut self, s: TypedStatement<'ast, T>) -> Vec<TypedStatement<'ast, T>> {
         let res = match s {
             TypedStatement::Declaration(v) => Some(TypedStatement::Declaration(v)),
+            TypedStatement::Origin(origin) => {
+                self.origin = Some(origin.clone());
+                Some(TypedStatement::Origin(origin))
+            }
+            // early exits are only found in loops whose bounds are not known yet, which we do not visit
+            TypedStatement::Break(..) => unreachable!(),
+            TypedStatement::Continue(..) => unreachable!(),
+            // if statements are lowered during unrolling, and the ones inside loops not yet unrolled are not visited
+            TypedStatement::IfElse(..) => unreachable!(),
             TypedStatement::Return(expressions) => Some(TypedStatement::Return(
                 expressions
                     .into_iter()
@@ -112,10 +212,16 @@ impl<'ast, T: Field> Folder<'ast, T> for Propagator<'ast, T> {
             TypedStatement::Definition(TypedAssignee::Member(..), _) => {
                 unreachable!("struct update should have been replaced with full struct redef")
             }
+            TypedStatement::Definition(TypedAssignee::Element(..), _) => {
+                unreachable!("tuple update should have been replaced with full tuple redef")
+            }
             // propagate the boolean
-            TypedStatement::Assertion(e) => {
+            TypedStatement::Assertion(e, metadata) => {
                 // could stop execution here if condition is known to fail
-                Some(TypedStatement::Assertion(self.fold_boolean_expression(e)))
+                Some(TypedStatement::Assertion(
+                    self.fold_boolean_expression(e),
+                    metadata,
+                ))
             }
             // only loops with variable bounds are expected here
             // we stop propagation here as constants maybe be modified inside the loop body
@@ -223,6 +329,11 @@ impl<'ast, T: Field> Folder<'ast, T> for Propagator<'ast, T> {
                         match arguments.iter().all(|a| is_constant(a)) {
                             true => {
                                 let r: Option<TypedExpression<'ast, T>> = match key.id {
+                                    "_U64_FROM_BITS" => Some(process_u_from_bits(
+                                        variables.clone(),
+                                        arguments.clone(),
+                                        UBitwidth::B64,
+                                    )),
                                     "_U32_FROM_BITS" => Some(process_u_from_bits(
                                         variables.clone(),
                                         arguments.clone(),
@@ -238,6 +349,11 @@ impl<'ast, T: Field> Folder<'ast, T> for Propagator<'ast, T> {
                                         arguments.clone(),
                                         UBitwidth::B8,
                                     )),
+                                    "_U64_TO_BITS" => Some(process_u_to_bits(
+                                        variables.clone(),
+                                        arguments.clone(),
+                                        UBitwidth::B64,
+                                    )),
                                     "_U32_TO_BITS" => Some(process_u_to_bits(
                                         variables.clone(),
                                         arguments.clone(),
@@ -343,14 +459,22 @@ impl<'ast, T: Field> Folder<'ast, T> for Propagator<'ast, T> {
     ) -> UExpressionInner<'ast, T> {
         match e {
             UExpressionInner::Identifier(id) => {
+                // signed integers share the uint representation, so the constant can be stored under either type
                 match self
                     .constants
                     .get(&TypedAssignee::Identifier(Variable::uint(
                         id.clone(),
                         bitwidth,
-                    ))) {
+                    )))
+                    .or_else(|| {
+                        self.constants
+                            .get(&TypedAssignee::Identifier(Variable::with_id_and_type(
+                                id.clone(),
+                                Type::int(bitwidth),
+                            )))
+                    }) {
                     Some(e) => match e {
-                        TypedExpression::Uint(e) => e.as_inner().clone(),
+                        TypedExpression::Uint(e) | TypedExpression::Int(e) => e.as_inner().clone(),
                         _ => unreachable!("constant stored for a uint should be a uint"),
                     },
                     None => UExpressionInner::Identifier(id),
@@ -361,10 +485,7 @@ impl<'ast, T: Field> Folder<'ast, T> for Propagator<'ast, T> {
                 self.fold_uint_expression(e2).into_inner(),
             ) {
                 (UExpressionInner::Value(v1), UExpressionInner::Value(v2)) => {
-                    use std::convert::TryInto;
-                    UExpressionInner::Value(
-                        (v1 + v2) % 2_u128.pow(bitwidth.to_usize().try_into().unwrap()),
-                    )
+                    UExpressionInner::Value(v1.wrapping_add(v2) & uint_mask(bitwidth))
                 }
                 (e, UExpressionInner::Value(v)) | (UExpressionInner::Value(v), e) => match v {
                     0 => e,
@@ -382,18 +503,10 @@ impl<'ast, T: Field> Folder<'ast, T> for Propagator<'ast, T> {
                 self.fold_uint_expression(e2).into_inner(),
             ) {
                 (UExpressionInner::Value(v1), UExpressionInner::Value(v2)) => {
-                    use std::convert::TryInto;
-                    UExpressionInner::Value(
-                        (v1.wrapping_sub(v2)) % 2_u128.pow(bitwidth.to_usize().try_into().unwrap()),
-                    )
+                    UExpressionInner::Value(v1.wrapping_sub(v2) & uint_mask(bitwidth))
                 }
-                (e, UExpressionInner::Value(v)) | (UExpressionInner::Value(v), e) => match v {
-                    0 => e,
-                    _ => UExpressionInner::Sub(
-                        box e.annotate(bitwidth),
-                        box UExpressionInner::Value(v).annotate(bitwidth),
-                    ),
-                },
+                // subtraction is not commutative, only `e - 0` can be simplified
+                (e, UExpressionInner::Value(0)) => e,
                 (e1, e2) => {
                     UExpressionInner::Sub(box e1.annotate(bitwidth), box e2.annotate(bitwidth))
                 }
@@ -403,10 +516,7 @@ impl<'ast, T: Field> Folder<'ast, T> for Propagator<'ast, T> {
                 self.fold_uint_expression(e2).into_inner(),
             ) {
                 (UExpressionInner::Value(v1), UExpressionInner::Value(v2)) => {
-                    use std::convert::TryInto;
-                    UExpressionInner::Value(
-                        (v1 * v2) % 2_u128.pow(bitwidth.to_usize().try_into().unwrap()),
-                    )
+                    UExpressionInner::Value(v1.wrapping_mul(v2) & uint_mask(bitwidth))
                 }
                 (e, UExpressionInner::Value(v)) | (UExpressionInner::Value(v), e) => match v {
                     0 => UExpressionInner::Value(0),
@@ -420,13 +530,39 @@ impl<'ast, T: Field> Folder<'ast, T> for Propagator<'ast, T> {
                     UExpressionInner::Mult(box e1.annotate(bitwidth), box e2.annotate(bitwidth))
                 }
             },
+            UExpressionInner::Div(box e1, box e2) => match (
+                self.fold_uint_expression(e1).into_inner(),
+                self.fold_uint_expression(e2).into_inner(),
+            ) {
+                // division by zero is left to fail at execution time
+                (UExpressionInner::Value(v1), UExpressionInner::Value(v2)) if v2 != 0 => {
+                    UExpressionInner::Value(v1 / v2)
+                }
+                (e, UExpressionInner::Value(1)) => e,
+                (e1, e2) => {
+                    UExpressionInner::Div(box e1.annotate(bitwidth), box e2.annotate(bitwidth))
+                }
+            },
+            UExpressionInner::Rem(box e1, box e2) => match (
+                self.fold_uint_expression(e1).into_inner(),
+                self.fold_uint_expression(e2).into_inner(),
+            ) {
+                // division by zero is left to fail at execution time
+                (UExpressionInner::Value(v1), UExpressionInner::Value(v2)) if v2 != 0 => {
+                    UExpressionInner::Value(v1 % v2)
+                }
+                (_, UExpressionInner::Value(1)) => UExpressionInner::Value(0),
+                (e1, e2) => {
+                    UExpressionInner::Rem(box e1.annotate(bitwidth), box e2.annotate(bitwidth))
+                }
+            },
             UExpressionInner::RightShift(box e, box by) => {
                 let e = self.fold_uint_expression(e);
                 let by = self.fold_field_expression(by);
                 match (e.into_inner(), by) {
                     (UExpressionInner::Value(v), FieldElementExpression::Number(by)) => {
                         let by_as_usize = by.to_dec_string().parse::<usize>().unwrap();
-                        UExpressionInner::Value(v >> by_as_usize)
+                        UExpressionInner::Value(v.checked_shr(by_as_usize as u32).unwrap_or(0))
                     }
                     (e, FieldElementExpression::Number(by)) => UExpressionInner::RightShift(
                         box e.annotate(bitwidth),
@@ -444,7 +580,9 @@ impl<'ast, T: Field> Folder<'ast, T> for Propagator<'ast, T> {
                 match (e.into_inner(), by) {
                     (UExpressionInner::Value(v), FieldElementExpression::Number(by)) => {
                         let by_as_usize = by.to_dec_string().parse::<usize>().unwrap();
-                        UExpressionInner::Value((v << by_as_usize) & 0xffffffff)
+                        UExpressionInner::Value(
+                            v.checked_shl(by_as_usize as u32).unwrap_or(0) & uint_mask(bitwidth),
+                        )
                     }
                     (e, FieldElementExpression::Number(by)) => UExpressionInner::LeftShift(
                         box e.annotate(bitwidth),
@@ -488,18 +626,25 @@ impl<'ast, T: Field> Folder<'ast, T> for Propagator<'ast, T> {
                 }
             },
             UExpressionInner::IfElse(box condition, box consequence, box alternative) => {
-                let consequence = self.fold_uint_expression(consequence);
-                let alternative = self.fold_uint_expression(alternative);
+                // only visit the branch which is taken if it is known, as the other one may not be valid
                 match self.fold_boolean_expression(condition) {
-                    BooleanExpression::Value(true) => consequence.into_inner(),
-                    BooleanExpression::Value(false) => alternative.into_inner(),
-                    c => UExpressionInner::IfElse(box c, box consequence, box alternative),
+                    BooleanExpression::Value(true) => {
+                        self.fold_uint_expression(consequence).into_inner()
+                    }
+                    BooleanExpression::Value(false) => {
+                        self.fold_uint_expression(alternative).into_inner()
+                    }
+                    c => UExpressionInner::IfElse(
+                        box c,
+                        box self.fold_uint_expression(consequence),
+                        box self.fold_uint_expression(alternative),
+                    ),
                 }
             }
             UExpressionInner::Not(box e) => {
                 let e = self.fold_uint_expression(e).into_inner();
                 match e {
-                    UExpressionInner::Value(v) => UExpressionInner::Value((!v) & 0xffffffff),
+                    UExpressionInner::Value(v) => UExpressionInner::Value(!v & uint_mask(bitwidth)),
                     e => UExpressionInner::Not(box e.annotate(bitwidth)),
                 }
             }
@@ -510,18 +655,22 @@ impl<'ast, T: Field> Folder<'ast, T> for Propagator<'ast, T> {
                 let inner_type = array.inner_type().clone();
                 let size = array.size();
 
+                if !self.check_bounds(&index, size) {
+                    return UExpressionInner::Select(box array, box index);
+                }
+
                 match (array.into_inner(), index) {
                     (ArrayExpressionInner::Value(v), FieldElementExpression::Number(n)) => {
-                        let n_as_usize = n.to_dec_string().parse::<usize>().unwrap();
-                        if n_as_usize < size {
-                            UExpression::try_from(v[n_as_usize].clone())
-                                .unwrap()
-                                .into_inner()
-                        } else {
-                            unreachable!(
+                        match to_usize(&n) {
+                            Some(n_as_usize) if n_as_usize < size => {
+                                UExpression::try_from(v[n_as_usize].clone())
+                                    .unwrap()
+                                    .into_inner()
+                            }
+                            _ => unreachable!(
                                 "out of bounds index ({} >= {}) found during static analysis",
-                                n_as_usize, size
-                            );
+                                n, size
+                            ),
                         }
                     }
                     (ArrayExpressionInner::Identifier(id), FieldElementExpression::Number(n)) => {
@@ -534,7 +683,9 @@ impl<'ast, T: Field> Folder<'ast, T> for Propagator<'ast, T> {
                             box FieldElementExpression::Number(n.clone()).into(),
                         )) {
                             Some(e) => match e {
-                                TypedExpression::Uint(e) => e.clone().into_inner(),
+                                TypedExpression::Uint(e) | TypedExpression::Int(e) => {
+                                    e.clone().into_inner()
+                                }
                                 _ => unreachable!(""),
                             },
                             None => UExpressionInner::Select(
@@ -612,6 +763,16 @@ impl<'ast, T: Field> Folder<'ast, T> for Propagator<'ast, T> {
                 self.fold_field_expression(e1),
                 self.fold_field_expression(e2),
             ) {
+                // zero has no inverse, so the division could never be proven
+                (e1, FieldElementExpression::Number(n2)) if n2 == T::zero() => {
+                    if self.error.is_none() {
+                        self.error = Some(Error {
+                            inner: ErrorInner::DivisionByZero,
+                            origin: self.origin.clone(),
+                        });
+                    }
+                    FieldElementExpression::Div(box e1, box FieldElementExpression::Number(n2))
+                }
                 (FieldElementExpression::Number(n1), FieldElementExpression::Number(n2)) => {
                     FieldElementExpression::Number(n1 / n2)
                 }
@@ -637,12 +798,14 @@ impl<'ast, T: Field> Folder<'ast, T> for Propagator<'ast, T> {
                 }
             }
             FieldElementExpression::IfElse(box condition, box consequence, box alternative) => {
-                let consequence = self.fold_field_expression(consequence);
-                let alternative = self.fold_field_expression(alternative);
                 match self.fold_boolean_expression(condition) {
-                    BooleanExpression::Value(true) => consequence,
-                    BooleanExpression::Value(false) => alternative,
-                    c => FieldElementExpression::IfElse(box c, box consequence, box alternative),
+                    BooleanExpression::Value(true) => self.fold_field_expression(consequence),
+                    BooleanExpression::Value(false) => self.fold_field_expression(alternative),
+                    c => FieldElementExpression::IfElse(
+                        box c,
+                        box self.fold_field_expression(consequence),
+                        box self.fold_field_expression(alternative),
+                    ),
                 }
             }
             FieldElementExpression::Select(box array, box index) => {
@@ -652,16 +815,20 @@ impl<'ast, T: Field> Folder<'ast, T> for Propagator<'ast, T> {
                 let inner_type = array.inner_type().clone();
                 let size = array.size();
 
+                if !self.check_bounds(&index, size) {
+                    return FieldElementExpression::Select(box array, box index);
+                }
+
                 match (array.into_inner(), index) {
                     (ArrayExpressionInner::Value(v), FieldElementExpression::Number(n)) => {
-                        let n_as_usize = n.to_dec_string().parse::<usize>().unwrap();
-                        if n_as_usize < size {
-                            FieldElementExpression::try_from(v[n_as_usize].clone()).unwrap()
-                        } else {
-                            unreachable!(
+                        match to_usize(&n) {
+                            Some(n_as_usize) if n_as_usize < size => {
+                                FieldElementExpression::try_from(v[n_as_usize].clone()).unwrap()
+                            }
+                            _ => unreachable!(
                                 "out of bounds index ({} >= {}) found during static analysis",
-                                n_as_usize, size
-                            );
+                                n, size
+                            ),
                         }
                     }
                     (ArrayExpressionInner::Identifier(id), FieldElementExpression::Number(n)) => {
@@ -712,6 +879,19 @@ impl<'ast, T: Field> Folder<'ast, T> for Propagator<'ast, T> {
                     inner => FieldElementExpression::Member(box inner.annotate(members), m),
                 }
             }
+            FieldElementExpression::Element(box t, index) => {
+                let t = self.fold_tuple_expression(t);
+
+                let tuple_type = t.ty().clone();
+
+                match t.into_inner() {
+                    TupleExpressionInner::Value(mut v) => match v.swap_remove(index) {
+                        TypedExpression::FieldElement(s) => s,
+                        _ => unreachable!("should be a field"),
+                    },
+                    inner => FieldElementExpression::Element(box inner.annotate(tuple_type), index),
+                }
+            }
             FieldElementExpression::FunctionCall(key, inputs) => {
                 assert!(
                     self.verbose,
@@ -752,18 +932,22 @@ impl<'ast, T: Field> Folder<'ast, T> for Propagator<'ast, T> {
                 let inner_type = array.inner_type().clone();
                 let size = array.size();
 
+                if !self.check_bounds(&index, size) {
+                    return ArrayExpressionInner::Select(box array, box index);
+                }
+
                 match (array.into_inner(), index) {
                     (ArrayExpressionInner::Value(v), FieldElementExpression::Number(n)) => {
-                        let n_as_usize = n.to_dec_string().parse::<usize>().unwrap();
-                        if n_as_usize < size {
-                            ArrayExpression::try_from(v[n_as_usize].clone())
-                                .unwrap()
-                                .into_inner()
-                        } else {
-                            unreachable!(
+                        match to_usize(&n) {
+                            Some(n_as_usize) if n_as_usize < size => {
+                                ArrayExpression::try_from(v[n_as_usize].clone())
+                                    .unwrap()
+                                    .into_inner()
+                            }
+                            _ => unreachable!(
                                 "out of bounds index ({} >= {}) found during static analysis",
-                                n_as_usize, size
-                            );
+                                n, size
+                            ),
                         }
                     }
                     (ArrayExpressionInner::Identifier(id), FieldElementExpression::Number(n)) => {
@@ -789,12 +973,18 @@ impl<'ast, T: Field> Folder<'ast, T> for Propagator<'ast, T> {
                 }
             }
             ArrayExpressionInner::IfElse(box condition, box consequence, box alternative) => {
-                let consequence = self.fold_array_expression(consequence);
-                let alternative = self.fold_array_expression(alternative);
                 match self.fold_boolean_expression(condition) {
-                    BooleanExpression::Value(true) => consequence.into_inner(),
-                    BooleanExpression::Value(false) => alternative.into_inner(),
-                    c => ArrayExpressionInner::IfElse(box c, box consequence, box alternative),
+                    BooleanExpression::Value(true) => {
+                        self.fold_array_expression(consequence).into_inner()
+                    }
+                    BooleanExpression::Value(false) => {
+                        self.fold_array_expression(alternative).into_inner()
+                    }
+                    c => ArrayExpressionInner::IfElse(
+                        box c,
+                        box self.fold_array_expression(consequence),
+                        box self.fold_array_expression(alternative),
+                    ),
                 }
             }
             ArrayExpressionInner::Member(box s, m) => {
@@ -821,6 +1011,19 @@ impl<'ast, T: Field> Folder<'ast, T> for Propagator<'ast, T> {
                     inner => ArrayExpressionInner::Member(box inner.annotate(members), m),
                 }
             }
+            ArrayExpressionInner::Element(box t, index) => {
+                let t = self.fold_tuple_expression(t);
+
+                let tuple_type = t.ty().clone();
+
+                match t.into_inner() {
+                    TupleExpressionInner::Value(mut v) => match v.swap_remove(index) {
+                        TypedExpression::Array(s) => s.into_inner(),
+                        _ => unreachable!("should be an array"),
+                    },
+                    inner => ArrayExpressionInner::Element(box inner.annotate(tuple_type), index),
+                }
+            }
             ArrayExpressionInner::FunctionCall(key, inputs) => {
                 assert!(
                     self.verbose,
@@ -864,18 +1067,22 @@ impl<'ast, T: Field> Folder<'ast, T> for Propagator<'ast, T> {
                 let inner_type = array.inner_type().clone();
                 let size = array.size();
 
+                if !self.check_bounds(&index, size) {
+                    return StructExpressionInner::Select(box array, box index);
+                }
+
                 match (array.into_inner(), index) {
                     (ArrayExpressionInner::Value(v), FieldElementExpression::Number(n)) => {
-                        let n_as_usize = n.to_dec_string().parse::<usize>().unwrap();
-                        if n_as_usize < size {
-                            StructExpression::try_from(v[n_as_usize].clone())
-                                .unwrap()
-                                .into_inner()
-                        } else {
-                            unreachable!(
+                        match to_usize(&n) {
+                            Some(n_as_usize) if n_as_usize < size => {
+                                StructExpression::try_from(v[n_as_usize].clone())
+                                    .unwrap()
+                                    .into_inner()
+                            }
+                            _ => unreachable!(
                                 "out of bounds index ({} >= {}) found during static analysis",
-                                n_as_usize, size
-                            );
+                                n, size
+                            ),
                         }
                     }
                     (ArrayExpressionInner::Identifier(id), FieldElementExpression::Number(n)) => {
@@ -903,12 +1110,18 @@ impl<'ast, T: Field> Folder<'ast, T> for Propagator<'ast, T> {
                 }
             }
             StructExpressionInner::IfElse(box condition, box consequence, box alternative) => {
-                let consequence = self.fold_struct_expression(consequence);
-                let alternative = self.fold_struct_expression(alternative);
                 match self.fold_boolean_expression(condition) {
-                    BooleanExpression::Value(true) => consequence.into_inner(),
-                    BooleanExpression::Value(false) => alternative.into_inner(),
-                    c => StructExpressionInner::IfElse(box c, box consequence, box alternative),
+                    BooleanExpression::Value(true) => {
+                        self.fold_struct_expression(consequence).into_inner()
+                    }
+                    BooleanExpression::Value(false) => {
+                        self.fold_struct_expression(alternative).into_inner()
+                    }
+                    c => StructExpressionInner::IfElse(
+                        box c,
+                        box self.fold_struct_expression(consequence),
+                        box self.fold_struct_expression(alternative),
+                    ),
                 }
             }
             StructExpressionInner::Member(box s, m) => {
@@ -935,6 +1148,19 @@ impl<'ast, T: Field> Folder<'ast, T> for Propagator<'ast, T> {
                     inner => StructExpressionInner::Member(box inner.annotate(members), m),
                 }
             }
+            StructExpressionInner::Element(box t, index) => {
+                let t = self.fold_tuple_expression(t);
+
+                let tuple_type = t.ty().clone();
+
+                match t.into_inner() {
+                    TupleExpressionInner::Value(mut v) => match v.swap_remove(index) {
+                        TypedExpression::Struct(s) => s.into_inner(),
+                        _ => unreachable!("should be a struct"),
+                    },
+                    inner => StructExpressionInner::Element(box inner.annotate(tuple_type), index),
+                }
+            }
             StructExpressionInner::FunctionCall(key, inputs) => {
                 assert!(
                     self.verbose,
@@ -950,6 +1176,121 @@ impl<'ast, T: Field> Folder<'ast, T> for Propagator<'ast, T> {
         }
     }
 
+    fn fold_tuple_expression_inner(
+        &mut self,
+        ty: &TupleType,
+        e: TupleExpressionInner<'ast, T>,
+    ) -> TupleExpressionInner<'ast, T> {
+        match e {
+            TupleExpressionInner::Identifier(id) => {
+                match self
+                    .constants
+                    .get(&TypedAssignee::Identifier(Variable::with_id_and_type(
+                        id.clone(),
+                        Type::Tuple(ty.clone()),
+                    ))) {
+                    Some(e) => match e {
+                        TypedExpression::Tuple(e) => e.as_inner().clone(),
+                        _ => panic!("constant stored for a tuple should be a tuple"),
+                    },
+                    None => TupleExpressionInner::Identifier(id),
+                }
+            }
+            TupleExpressionInner::Select(box array, box index) => {
+                let array = self.fold_array_expression(array);
+                let index = self.fold_field_expression(index);
+
+                let inner_type = array.inner_type().clone();
+                let size = array.size();
+
+                if !self.check_bounds(&index, size) {
+                    return TupleExpressionInner::Select(box array, box index);
+                }
+
+                match (array.into_inner(), index) {
+                    (ArrayExpressionInner::Value(v), FieldElementExpression::Number(n)) => {
+                        match to_usize(&n) {
+                            Some(n_as_usize) if n_as_usize < size => {
+                                TupleExpression::try_from(v[n_as_usize].clone())
+                                    .unwrap()
+                                    .into_inner()
+                            }
+                            _ => unreachable!(
+                                "out of bounds index ({} >= {}) found during static analysis",
+                                n, size
+                            ),
+                        }
+                    }
+                    (a, i) => TupleExpressionInner::Select(box a.annotate(inner_type, size), box i),
+                }
+            }
+            TupleExpressionInner::IfElse(box condition, box consequence, box alternative) => {
+                match self.fold_boolean_expression(condition) {
+                    BooleanExpression::Value(true) => {
+                        self.fold_tuple_expression(consequence).into_inner()
+                    }
+                    BooleanExpression::Value(false) => {
+                        self.fold_tuple_expression(alternative).into_inner()
+                    }
+                    c => TupleExpressionInner::IfElse(
+                        box c,
+                        box self.fold_tuple_expression(consequence),
+                        box self.fold_tuple_expression(alternative),
+                    ),
+                }
+            }
+            TupleExpressionInner::Member(box s, m) => {
+                let s = self.fold_struct_expression(s);
+
+                let members = match s.get_type() {
+                    Type::Struct(members) => members,
+                    _ => unreachable!("should be a struct"),
+                };
+
+                match s.into_inner() {
+                    StructExpressionInner::Value(v) => {
+                        match members
+                            .iter()
+                            .zip(v)
+                            .find(|(member, _)| member.id == m)
+                            .unwrap()
+                            .1
+                        {
+                            TypedExpression::Tuple(s) => s.into_inner(),
+                            _ => unreachable!("should be a tuple"),
+                        }
+                    }
+                    inner => TupleExpressionInner::Member(box inner.annotate(members), m),
+                }
+            }
+            TupleExpressionInner::Element(box t, index) => {
+                let t = self.fold_tuple_expression(t);
+
+                let tuple_type = t.ty().clone();
+
+                match t.into_inner() {
+                    TupleExpressionInner::Value(mut v) => match v.swap_remove(index) {
+                        TypedExpression::Tuple(s) => s.into_inner(),
+                        _ => unreachable!("should be a tuple"),
+                    },
+                    inner => TupleExpressionInner::Element(box inner.annotate(tuple_type), index),
+                }
+            }
+            TupleExpressionInner::FunctionCall(key, inputs) => {
+                assert!(
+                    self.verbose,
+                    "function calls should only exist out of multidef in verbose mode"
+                );
+                fold_tuple_expression_inner(
+                    self,
+                    ty,
+                    TupleExpressionInner::FunctionCall(key, inputs),
+                )
+            }
+            e => fold_tuple_expression_inner(self, ty, e),
+        }
+    }
+
     fn fold_boolean_expression(
         &mut self,
         e: BooleanExpression<'ast, T>,
@@ -1036,8 +1377,24 @@ impl<'ast, T: Field> Folder<'ast, T> for Propagator<'ast, T> {
                     (e1, e2) => BooleanExpression::Ge(box e1, box e2),
                 }
             }
+            BooleanExpression::UintLt(box e1, box e2) => {
+                self.fold_uint_comparison(Comparison::Lt, e1, e2)
+            }
+            BooleanExpression::UintLe(box e1, box e2) => {
+                self.fold_uint_comparison(Comparison::Le, e1, e2)
+            }
+            BooleanExpression::UintGe(box e1, box e2) => {
+                self.fold_uint_comparison(Comparison::Ge, e1, e2)
+            }
+            BooleanExpression::UintGt(box e1, box e2) => {
+                self.fold_uint_comparison(Comparison::Gt, e1, e2)
+            }
             BooleanExpression::Or(box e1, box e2) => {
                 let e1 = self.fold_boolean_expression(e1);
+                // guarded statements are of the form `!condition || e`, so `e` is not visited if the guard does not hold
+                if e1 == BooleanExpression::Value(true) {
+                    return e1;
+                }
                 let e2 = self.fold_boolean_expression(e2);
 
                 match (e1, e2) {
@@ -1058,6 +1415,9 @@ impl<'ast, T: Field> Folder<'ast, T> for Propagator<'ast, T> {
             }
             BooleanExpression::And(box e1, box e2) => {
                 let e1 = self.fold_boolean_expression(e1);
+                if e1 == BooleanExpression::Value(false) {
+                    return e1;
+                }
                 let e2 = self.fold_boolean_expression(e2);
 
                 match (e1, e2) {
@@ -1082,12 +1442,14 @@ impl<'ast, T: Field> Folder<'ast, T> for Propagator<'ast, T> {
                 }
             }
             BooleanExpression::IfElse(box condition, box consequence, box alternative) => {
-                let consequence = self.fold_boolean_expression(consequence);
-                let alternative = self.fold_boolean_expression(alternative);
                 match self.fold_boolean_expression(condition) {
-                    BooleanExpression::Value(true) => consequence,
-                    BooleanExpression::Value(false) => alternative,
-                    c => BooleanExpression::IfElse(box c, box consequence, box alternative),
+                    BooleanExpression::Value(true) => self.fold_boolean_expression(consequence),
+                    BooleanExpression::Value(false) => self.fold_boolean_expression(alternative),
+                    c => BooleanExpression::IfElse(
+                        box c,
+                        box self.fold_boolean_expression(consequence),
+                        box self.fold_boolean_expression(alternative),
+                    ),
                 }
             }
             BooleanExpression::Select(box array, box index) => {
@@ -1097,16 +1459,20 @@ impl<'ast, T: Field> Folder<'ast, T> for Propagator<'ast, T> {
                 let inner_type = array.inner_type().clone();
                 let size = array.size();
 
+                if !self.check_bounds(&index, size) {
+                    return BooleanExpression::Select(box array, box index);
+                }
+
                 match (array.into_inner(), index) {
                     (ArrayExpressionInner::Value(v), FieldElementExpression::Number(n)) => {
-                        let n_as_usize = n.to_dec_string().parse::<usize>().unwrap();
-                        if n_as_usize < size {
-                            BooleanExpression::try_from(v[n_as_usize].clone()).unwrap()
-                        } else {
-                            unreachable!(
+                        match to_usize(&n) {
+                            Some(n_as_usize) if n_as_usize < size => {
+                                BooleanExpression::try_from(v[n_as_usize].clone()).unwrap()
+                            }
+                            _ => unreachable!(
                                 "out of bounds index ({} >= {}) found during static analysis",
-                                n_as_usize, size
-                            );
+                                n, size
+                            ),
                         }
                     }
                     (ArrayExpressionInner::Identifier(id), FieldElementExpression::Number(n)) => {
@@ -1155,6 +1521,19 @@ impl<'ast, T: Field> Folder<'ast, T> for Propagator<'ast, T> {
                     inner => BooleanExpression::Member(box inner.annotate(members), m),
                 }
             }
+            BooleanExpression::Element(box t, index) => {
+                let t = self.fold_tuple_expression(t);
+
+                let tuple_type = t.ty().clone();
+
+                match t.into_inner() {
+                    TupleExpressionInner::Value(mut v) => match v.swap_remove(index) {
+                        TypedExpression::Boolean(s) => s,
+                        _ => unreachable!("should be a boolean"),
+                    },
+                    inner => BooleanExpression::Element(box inner.annotate(tuple_type), index),
+                }
+            }
             BooleanExpression::FunctionCall(key, inputs) => {
                 assert!(
                     self.verbose,
@@ -1219,6 +1598,19 @@ mod tests {
                 );
             }
 
+            #[test]
+            fn add_wraps() {
+                let e = UExpressionInner::Add(
+                    box UExpressionInner::Value(0xffffffffffffffff).annotate(64),
+                    box UExpressionInner::Value(2).annotate(64),
+                );
+
+                assert_eq!(
+                    Propagator::<Bn128Field>::new().fold_uint_expression_inner(UBitwidth::B64, e),
+                    UExpressionInner::Value(1)
+                );
+            }
+
             #[test]
             fn div() {
                 let e = FieldElementExpression::Div(
@@ -1295,6 +1687,65 @@ mod tests {
             }
         }
 
+        #[cfg(test)]
+        mod uint {
+            use super::*;
+
+            #[test]
+            fn div() {
+                let e = UExpressionInner::Div(
+                    box UExpressionInner::Value(42).annotate(32),
+                    box UExpressionInner::Value(5).annotate(32),
+                );
+
+                assert_eq!(
+                    Propagator::<Bn128Field>::new().fold_uint_expression_inner(UBitwidth::B32, e),
+                    UExpressionInner::Value(8)
+                );
+            }
+
+            #[test]
+            fn rem() {
+                let e = UExpressionInner::Rem(
+                    box UExpressionInner::Value(42).annotate(32),
+                    box UExpressionInner::Value(5).annotate(32),
+                );
+
+                assert_eq!(
+                    Propagator::<Bn128Field>::new().fold_uint_expression_inner(UBitwidth::B32, e),
+                    UExpressionInner::Value(2)
+                );
+            }
+
+            #[test]
+            fn div_by_zero() {
+                let e = UExpressionInner::Div(
+                    box UExpressionInner::Value(42).annotate(32),
+                    box UExpressionInner::Value(0).annotate(32),
+                );
+
+                assert_eq!(
+                    Propagator::<Bn128Field>::new()
+                        .fold_uint_expression_inner(UBitwidth::B32, e.clone()),
+                    e
+                );
+            }
+
+            #[test]
+            fn sub_from_constant() {
+                let e = UExpressionInner::Sub(
+                    box UExpressionInner::Value(0).annotate(32),
+                    box UExpressionInner::Identifier("a".into()).annotate(32),
+                );
+
+                assert_eq!(
+                    Propagator::<Bn128Field>::new()
+                        .fold_uint_expression_inner(UBitwidth::B32, e.clone()),
+                    e
+                );
+            }
+        }
+
         #[cfg(test)]
         mod boolean {
             use super::*;
@@ -1477,6 +1928,147 @@ mod tests {
                 );
             }
 
+            #[test]
+            fn uint_lt() {
+                let e_true = BooleanExpression::UintLt(
+                    box UExpressionInner::Value(2).annotate(32),
+                    box UExpressionInner::Value(4).annotate(32),
+                );
+
+                let e_false = BooleanExpression::UintLt(
+                    box UExpressionInner::Value(4).annotate(32),
+                    box UExpressionInner::Value(2).annotate(32),
+                );
+
+                assert_eq!(
+                    Propagator::<Bn128Field>::new().fold_boolean_expression(e_true),
+                    BooleanExpression::Value(true)
+                );
+                assert_eq!(
+                    Propagator::<Bn128Field>::new().fold_boolean_expression(e_false),
+                    BooleanExpression::Value(false)
+                );
+            }
+
+            #[test]
+            fn uint_le() {
+                let e = BooleanExpression::UintLe(
+                    box UExpressionInner::Value(2).annotate(32),
+                    box UExpressionInner::Value(4).annotate(32),
+                );
+
+                assert_eq!(
+                    Propagator::<Bn128Field>::new().fold_boolean_expression(e),
+                    BooleanExpression::Value(true)
+                );
+
+                let e = BooleanExpression::UintLe(
+                    box UExpressionInner::Value(4).annotate(32),
+                    box UExpressionInner::Value(4).annotate(32),
+                );
+
+                assert_eq!(
+                    Propagator::<Bn128Field>::new().fold_boolean_expression(e),
+                    BooleanExpression::Value(true)
+                );
+
+                let e = BooleanExpression::UintLe(
+                    box UExpressionInner::Value(4).annotate(32),
+                    box UExpressionInner::Value(2).annotate(32),
+                );
+
+                assert_eq!(
+                    Propagator::<Bn128Field>::new().fold_boolean_expression(e),
+                    BooleanExpression::Value(false)
+                );
+            }
+
+            #[test]
+            fn uint_ge() {
+                let e = BooleanExpression::UintGe(
+                    box UExpressionInner::Value(2).annotate(32),
+                    box UExpressionInner::Value(4).annotate(32),
+                );
+
+                assert_eq!(
+                    Propagator::<Bn128Field>::new().fold_boolean_expression(e),
+                    BooleanExpression::Value(false)
+                );
+
+                let e = BooleanExpression::UintGe(
+                    box UExpressionInner::Value(4).annotate(32),
+                    box UExpressionInner::Value(4).annotate(32),
+                );
+
+                assert_eq!(
+                    Propagator::<Bn128Field>::new().fold_boolean_expression(e),
+                    BooleanExpression::Value(true)
+                );
+
+                let e = BooleanExpression::UintGe(
+                    box UExpressionInner::Value(4).annotate(32),
+                    box UExpressionInner::Value(2).annotate(32),
+                );
+
+                assert_eq!(
+                    Propagator::<Bn128Field>::new().fold_boolean_expression(e),
+                    BooleanExpression::Value(true)
+                );
+            }
+
+            #[test]
+            fn uint_gt() {
+                let e = BooleanExpression::UintGt(
+                    box UExpressionInner::Value(2).annotate(32),
+                    box UExpressionInner::Value(4).annotate(32),
+                );
+
+                assert_eq!(
+                    Propagator::<Bn128Field>::new().fold_boolean_expression(e),
+                    BooleanExpression::Value(false)
+                );
+
+                let e = BooleanExpression::UintGt(
+                    box UExpressionInner::Value(4).annotate(32),
+                    box UExpressionInner::Value(4).annotate(32),
+                );
+
+                assert_eq!(
+                    Propagator::<Bn128Field>::new().fold_boolean_expression(e),
+                    BooleanExpression::Value(false)
+                );
+
+                let e = BooleanExpression::UintGt(
+                    box UExpressionInner::Value(4).annotate(32),
+                    box UExpressionInner::Value(2).annotate(32),
+                );
+
+                assert_eq!(
+                    Propagator::<Bn128Field>::new().fold_boolean_expression(e),
+                    BooleanExpression::Value(true)
+                );
+            }
+
+            #[test]
+            fn uint_comparison_with_identifier() {
+                let e = BooleanExpression::UintGe(
+                    box UExpressionInner::Identifier("a".into()).annotate(32),
+                    box UExpressionInner::Add(
+                        box UExpressionInner::Value(2).annotate(32),
+                        box UExpressionInner::Value(3).annotate(32),
+                    )
+                    .annotate(32),
+                );
+
+                assert_eq!(
+                    Propagator::<Bn128Field>::new().fold_boolean_expression(e),
+                    BooleanExpression::UintGe(
+                        box UExpressionInner::Identifier("a".into()).annotate(32),
+                        box UExpressionInner::Value(5).annotate(32),
+                    )
+                );
+            }
+
             #[test]
             fn and() {
                 let a_bool: Identifier = "a".into();
@@ -31,7 +31,7 @@ fn try_id<'ast, T: Field>(e: &TypedExpression<'ast, T>) -> Option<Identifier<'as
             StructExpressionInner::Identifier(id) => Some(id.clone()),
             _ => None,
         },
-        TypedExpression::Uint(a) => match a.as_inner() {
+        TypedExpression::Uint(a) | TypedExpression::Int(a) => match a.as_inner() {
             UExpressionInner::Identifier(id) => Some(id.clone()),
             _ => None,
         },
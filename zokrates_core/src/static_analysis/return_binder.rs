@@ -31,9 +31,15 @@ impl<'ast, T: Field> Folder<'ast, T> for ReturnBinder {
                         Type::Struct(struct_type) => StructExpressionInner::Identifier(i.clone())
                             .annotate(struct_type)
                             .into(),
+                        Type::Tuple(tuple_type) => TupleExpressionInner::Identifier(i.clone())
+                            .annotate(tuple_type)
+                            .into(),
                         Type::Uint(bitwidth) => UExpressionInner::Identifier(i.clone())
                             .annotate(bitwidth)
                             .into(),
+                        Type::Int(bitwidth) => TypedExpression::Int(
+                            UExpressionInner::Identifier(i.clone()).annotate(bitwidth),
+                        ),
                     })
                     .collect();
 
@@ -22,6 +22,19 @@ impl<'ast, T: Field> UintOptimizer<'ast, T> {
     fn register(&mut self, a: ZirAssignee<'ast>, m: UMetadata<T>) {
         self.ids.insert(a, m);
     }
+
+    // comparisons are computed on the bits of their operands, which therefore have to be reduced
+    fn fold_uint_comparison(
+        &mut self,
+        op: Comparison,
+        left: UExpression<'ast, T>,
+        right: UExpression<'ast, T>,
+    ) -> BooleanExpression<'ast, T> {
+        let left = self.fold_uint_expression(left);
+        let right = self.fold_uint_expression(right);
+
+        BooleanExpression::uint_comparison(op, force_reduce(left), force_reduce(right))
+    }
 }
 
 fn force_reduce<'ast, T: Field>(e: UExpression<'ast, T>) -> UExpression<'ast, T> {
@@ -67,6 +80,18 @@ impl<'ast, T: Field> Folder<'ast, T> for UintOptimizer<'ast, T> {
 
                 BooleanExpression::UintEq(box left, box right)
             }
+            BooleanExpression::UintLt(box left, box right) => {
+                self.fold_uint_comparison(Comparison::Lt, left, right)
+            }
+            BooleanExpression::UintLe(box left, box right) => {
+                self.fold_uint_comparison(Comparison::Le, left, right)
+            }
+            BooleanExpression::UintGe(box left, box right) => {
+                self.fold_uint_comparison(Comparison::Ge, left, right)
+            }
+            BooleanExpression::UintGt(box left, box right) => {
+                self.fold_uint_comparison(Comparison::Gt, left, right)
+            }
             e => fold_boolean_expression(self, e),
         }
     }
@@ -80,7 +105,7 @@ impl<'ast, T: Field> Folder<'ast, T> for UintOptimizer<'ast, T> {
 
         let range = e.bitwidth.to_usize();
 
-        let range_max: T = (2_usize.pow(range as u32) - 1).into();
+        let range_max: T = T::from(2_u32).pow(range) - T::one();
 
         assert!(range < max_bitwidth / 2);
 
@@ -148,7 +173,7 @@ impl<'ast, T: Field> Folder<'ast, T> for UintOptimizer<'ast, T> {
                 // Else we start again, reducing `left`. In this case `max_left` becomes `2**target - 1`
                 // Else we start again, reducing `right`. In this case `offset` becomes `2**target`
                 // Else we start again reducing both. In this case `bound` becomes `2**(target+1) - 1` which is always
-                // smaller or equal to N for target in {8, 16, 32}
+                // smaller or equal to N for all supported targets
 
                 // reduce the two terms
                 let left = self.fold_uint_expression(left);
@@ -181,7 +206,7 @@ impl<'ast, T: Field> Folder<'ast, T> for UintOptimizer<'ast, T> {
                                     .map(|max| (true, false, max))
                                     .unwrap_or_else(
                                         // this is unreachable because the max value for `range_max + offset` is
-                                        // 2**32 + 2**(T::get_required_bits() - 2) < 2**(T::get_required_bits() - 1)
+                                        // 2**target + 2**(T::get_required_bits() - 2) < 2**(T::get_required_bits() - 1)
                                         || unreachable!(),
                                     )
                             })
@@ -258,6 +283,22 @@ impl<'ast, T: Field> Folder<'ast, T> for UintOptimizer<'ast, T> {
 
                 UExpression::mult(left, right).with_max(max)
             }
+            Div(box left, box right) => {
+                // reduce the two terms
+                let left = self.fold_uint_expression(left);
+                let right = self.fold_uint_expression(right);
+
+                // the quotient is at most `left`, which is reduced
+                UExpression::div(force_reduce(left), force_reduce(right)).with_max(range_max)
+            }
+            Rem(box left, box right) => {
+                // reduce the two terms
+                let left = self.fold_uint_expression(left);
+                let right = self.fold_uint_expression(right);
+
+                // the remainder is strictly smaller than `right`, which is reduced
+                UExpression::rem(force_reduce(left), force_reduce(right)).with_max(range_max)
+            }
             Not(box e) => {
                 let e = self.fold_uint_expression(e);
 
@@ -361,6 +402,20 @@ impl<'ast, T: Field> Folder<'ast, T> for UintOptimizer<'ast, T> {
             )],
             ZirStatement::MultipleDefinition(lhs, rhs) => match rhs {
                 ZirExpressionList::FunctionCall(key, arguments, ty) => match key.clone().id {
+                    "_U64_FROM_BITS" => {
+                        assert_eq!(lhs.len(), 1);
+                        self.register(
+                            lhs[0].clone(),
+                            UMetadata {
+                                max: T::from(2).pow(64) - T::from(1),
+                                should_reduce: ShouldReduce::False,
+                            },
+                        );
+                        vec![ZirStatement::MultipleDefinition(
+                            lhs,
+                            ZirExpressionList::FunctionCall(key, arguments, ty),
+                        )]
+                    }
                     "_U32_FROM_BITS" => {
                         assert_eq!(lhs.len(), 1);
                         self.register(
@@ -416,7 +471,7 @@ impl<'ast, T: Field> Folder<'ast, T> for UintOptimizer<'ast, T> {
                     )],
                 },
             },
-            ZirStatement::Assertion(BooleanExpression::UintEq(box left, box right)) => {
+            ZirStatement::Assertion(BooleanExpression::UintEq(box left, box right), metadata) => {
                 let left = self.fold_uint_expression(left);
                 let right = self.fold_uint_expression(right);
 
@@ -424,9 +479,10 @@ impl<'ast, T: Field> Folder<'ast, T> for UintOptimizer<'ast, T> {
                 let left = force_reduce(left);
                 let right = force_reduce(right);
 
-                vec![ZirStatement::Assertion(BooleanExpression::UintEq(
-                    box left, box right,
-                ))]
+                vec![ZirStatement::Assertion(
+                    BooleanExpression::UintEq(box left, box right),
+                    metadata,
+                )]
             }
             s => fold_statement(self, s),
         }
@@ -21,19 +21,21 @@ impl UnconstrainedVariableDetector {
                 .collect(),
         }
     }
-    pub fn detect<T: Field>(p: Prog<T>) -> Prog<T> {
+    /// Returns the indices of the private parameters of `main` which are not constrained, if any
+    pub fn detect<T: Field>(p: Prog<T>) -> Result<Prog<T>, Vec<usize>> {
         let mut instance = Self::new(&p);
         let p = instance.fold_module(p);
 
-        // we should probably handle this case instead of asserting at some point
-        assert!(
-            instance.variables.is_empty(),
-            format!(
-                "Unconstrained variables are not allowed (found {} occasions)",
-                instance.variables.len()
-            )
-        );
-        p
+        match instance.variables.is_empty() {
+            true => Ok(p),
+            false => Err(p
+                .parameters()
+                .iter()
+                .enumerate()
+                .filter(|(_, p)| instance.variables.contains(&p.id))
+                .map(|(index, _)| index)
+                .collect()),
+        }
     }
 }
 
@@ -57,10 +59,10 @@ mod tests {
     use flat_absy::FlatVariable;
     use ir::{Function, LinComb, Prog, QuadComb, Statement};
     use solvers::Solver;
+    use std::collections::BTreeMap;
     use zokrates_field::Bn128Field;
 
     #[test]
-    #[should_panic]
     fn should_detect_unconstrained_private_input() {
         // def main(_0) -> (1):
         //     (1 * ~one) * (42 * ~one) == 1 * ~out_0
@@ -86,10 +88,12 @@ mod tests {
 
         let p: Prog<Bn128Field> = Prog {
             private: vec![true],
+            origins: BTreeMap::new(),
+            metadata: BTreeMap::new(),
             main,
         };
 
-        UnconstrainedVariableDetector::detect(p);
+        assert_eq!(UnconstrainedVariableDetector::detect(p), Err(vec![0]));
     }
 
     #[test]
@@ -110,10 +114,12 @@ mod tests {
 
         let p: Prog<Bn128Field> = Prog {
             private: vec![true],
+            origins: BTreeMap::new(),
+            metadata: BTreeMap::new(),
             main,
         };
 
-        UnconstrainedVariableDetector::detect(p);
+        assert!(UnconstrainedVariableDetector::detect(p).is_ok());
     }
 
     #[test]
@@ -168,9 +174,11 @@ mod tests {
 
         let p: Prog<Bn128Field> = Prog {
             private: vec![true],
+            origins: BTreeMap::new(),
+            metadata: BTreeMap::new(),
             main,
         };
 
-        UnconstrainedVariableDetector::detect(p);
+        assert!(UnconstrainedVariableDetector::detect(p).is_ok());
     }
 }
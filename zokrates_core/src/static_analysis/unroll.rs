@@ -4,9 +4,12 @@
 //! @author Thibaut Schaeffer <thibaut@schaeff.fr>
 //! @date 2018
 
+use crate::static_analysis::early_exit::{has_early_exit, EarlyExitLowerer};
+use crate::static_analysis::guard::Guard;
 use crate::typed_absy::folder::*;
 use crate::typed_absy::types::{MemberId, Type};
 use crate::typed_absy::*;
+use source_map::Origin;
 use std::collections::HashMap;
 use std::collections::HashSet;
 use typed_absy::identifier::CoreIdentifier;
@@ -14,7 +17,8 @@ use zokrates_field::Field;
 
 pub enum Output<'ast, T: Field> {
     Complete(TypedProgram<'ast, T>),
-    Incomplete(TypedProgram<'ast, T>, usize),
+    /// The program could not be fully unrolled, with the origin of the first loop which could not be unrolled if known
+    Incomplete(TypedProgram<'ast, T>, usize, Option<Origin>),
 }
 
 pub struct Unroller<'ast> {
@@ -23,6 +27,10 @@ pub struct Unroller<'ast> {
     // whether all statements could be unrolled so far. Loops with variable bounds cannot.
     complete: bool,
     statement_count: usize,
+    // the origin of the current statement
+    origin: Option<Origin>,
+    // the origin of the first loop which could not be unrolled
+    blocked_at: Option<Origin>,
 }
 
 impl<'ast> Unroller<'ast> {
@@ -31,6 +39,8 @@ impl<'ast> Unroller<'ast> {
             substitution: HashMap::new(),
             complete: true,
             statement_count: 0,
+            origin: None,
+            blocked_at: None,
         }
     }
 
@@ -54,13 +64,24 @@ impl<'ast> Unroller<'ast> {
         res
     }
 
+    /// Returns an id such that the internal identifier `name` with this id was not defined so far
+    fn fresh_id(&self, name: &'static str) -> usize {
+        (0..)
+            .find(|id| {
+                !self
+                    .substitution
+                    .contains_key(&CoreIdentifier::Internal(name, *id))
+            })
+            .unwrap()
+    }
+
     pub fn unroll<T: Field>(p: TypedProgram<T>) -> Output<T> {
         let mut unroller = Unroller::new();
         let p = unroller.fold_program(p);
 
         match unroller.complete {
             true => Output::Complete(p),
-            false => Output::Incomplete(p, unroller.statement_count),
+            false => Output::Incomplete(p, unroller.statement_count, unroller.blocked_at),
         }
     }
 
@@ -90,6 +111,7 @@ impl<'ast> Unroller<'ast> {
                                     box FieldElementExpression::Number(T::from(size)),
                                 )
                                 .into(),
+                                None,
                             ));
 
                             ArrayExpressionInner::Value(
@@ -149,6 +171,33 @@ impl<'ast> Unroller<'ast> {
                                             ),
                                         )
                                         .into(),
+                                        Type::Tuple(..) => TupleExpression::if_else(
+                                            BooleanExpression::FieldEq(
+                                                box FieldElementExpression::Number(T::from(i)),
+                                                box head.clone(),
+                                            ),
+                                            match Self::choose_many(
+                                                TupleExpression::select(
+                                                    base.clone(),
+                                                    FieldElementExpression::Number(T::from(i)),
+                                                )
+                                                .into(),
+                                                tail.clone(),
+                                                new_expression.clone(),
+                                                statements,
+                                            ) {
+                                                TypedExpression::Tuple(e) => e,
+                                                e => unreachable!(
+                                            "the interior was expected to be a tuple, was {}",
+                                            e.get_type()
+                                        ),
+                                            },
+                                            TupleExpression::select(
+                                                base.clone(),
+                                                FieldElementExpression::Number(T::from(i)),
+                                            ),
+                                        )
+                                        .into(),
                                         Type::FieldElement => FieldElementExpression::if_else(
                                             BooleanExpression::FieldEq(
                                                 box FieldElementExpression::Number(T::from(i)),
@@ -230,6 +279,33 @@ impl<'ast> Unroller<'ast> {
                                             ),
                                         )
                                         .into(),
+                                        Type::Int(..) => {
+                                            TypedExpression::Int(UExpression::if_else(
+                                                BooleanExpression::FieldEq(
+                                                    box FieldElementExpression::Number(T::from(i)),
+                                                    box head.clone(),
+                                                ),
+                                                match Self::choose_many(
+                                                    TypedExpression::Int(UExpression::select(
+                                                        base.clone(),
+                                                        FieldElementExpression::Number(T::from(i)),
+                                                    )),
+                                                    tail.clone(),
+                                                    new_expression.clone(),
+                                                    statements,
+                                                ) {
+                                                    TypedExpression::Int(e) => e,
+                                                    e => unreachable!(
+                                            "the interior was expected to be an int, was {}",
+                                            e.get_type()
+                                        ),
+                                                },
+                                                UExpression::select(
+                                                    base.clone(),
+                                                    FieldElementExpression::Number(T::from(i)),
+                                                ),
+                                            ))
+                                        }
                                     })
                                     .collect(),
                             )
@@ -237,6 +313,7 @@ impl<'ast> Unroller<'ast> {
                             .into()
                         }
                         Access::Member(..) => unreachable!("can't get a member from an array"),
+                        Access::Element(..) => unreachable!("can't get an element from an array"),
                     }
                 }
                 TypedExpression::Struct(base) => {
@@ -288,6 +365,24 @@ impl<'ast> Unroller<'ast> {
                                                 .into()
                                         }
                                     }
+                                    Type::Int(..) => {
+                                        if member.id == head {
+                                            Self::choose_many(
+                                                TypedExpression::Int(UExpression::member(
+                                                    base.clone(),
+                                                    head.clone(),
+                                                )),
+                                                tail.clone(),
+                                                new_expression.clone(),
+                                                statements,
+                                            )
+                                        } else {
+                                            TypedExpression::Int(UExpression::member(
+                                                base.clone(),
+                                                member.id.clone(),
+                                            ))
+                                        }
+                                    }
                                     Type::Boolean => {
                                         if member.id == head {
                                             Self::choose_many(
@@ -342,12 +437,59 @@ impl<'ast> Unroller<'ast> {
                                             .into()
                                         }
                                     }
+                                    Type::Tuple(..) => {
+                                        if member.id == head {
+                                            Self::choose_many(
+                                                TupleExpression::member(base.clone(), head.clone())
+                                                    .into(),
+                                                tail.clone(),
+                                                new_expression.clone(),
+                                                statements,
+                                            )
+                                        } else {
+                                            TupleExpression::member(base.clone(), member.id.clone())
+                                                .into()
+                                        }
+                                    }
                                 })
                                 .collect(),
                         )
                         .annotate(members)
                         .into(),
                         Access::Select(..) => unreachable!("can't get a element from a struct"),
+                        Access::Element(..) => unreachable!("can't get an element from a struct"),
+                    }
+                }
+                TypedExpression::Tuple(base) => {
+                    let tuple_type = base.ty().clone();
+
+                    let head = indices.remove(0);
+                    let tail = indices;
+
+                    match head {
+                        Access::Element(head) => TupleExpressionInner::Value(
+                            tuple_type
+                                .iter()
+                                .enumerate()
+                                .map(|(index, ty)| {
+                                    let element = element(base.clone(), index, ty);
+                                    if index == head {
+                                        Self::choose_many(
+                                            element,
+                                            tail.clone(),
+                                            new_expression.clone(),
+                                            statements,
+                                        )
+                                    } else {
+                                        element
+                                    }
+                                })
+                                .collect(),
+                        )
+                        .annotate(tuple_type)
+                        .into(),
+                        Access::Select(..) => unreachable!("can't get a element from a tuple"),
+                        Access::Member(..) => unreachable!("can't get a member from a tuple"),
                     }
                 }
                 e => unreachable!("can't make an access on a {}", e.get_type()),
@@ -360,6 +502,24 @@ impl<'ast> Unroller<'ast> {
 enum Access<'ast, T: Field> {
     Select(FieldElementExpression<'ast, T>),
     Member(MemberId),
+    Element(usize),
+}
+
+/// Returns the element at `index` of the tuple `t`, of type `ty`
+fn element<'ast, T: Field>(
+    t: TupleExpression<'ast, T>,
+    index: usize,
+    ty: &Type,
+) -> TypedExpression<'ast, T> {
+    match ty {
+        Type::FieldElement => FieldElementExpression::element(t, index).into(),
+        Type::Boolean => BooleanExpression::element(t, index).into(),
+        Type::Uint(..) => UExpression::element(t, index).into(),
+        Type::Int(..) => TypedExpression::Int(UExpression::element(t, index)),
+        Type::Array(..) => ArrayExpression::element(t, index).into(),
+        Type::Struct(..) => StructExpression::element(t, index).into(),
+        Type::Tuple(..) => TupleExpression::element(t, index).into(),
+    }
 }
 /// Turn an assignee into its representation as a base variable and a list accesses
 /// a[2][3][4] -> (a, [2, 3, 4])
@@ -376,6 +536,11 @@ fn linear<'ast, T: Field>(a: TypedAssignee<'ast, T>) -> (Variable, Vec<Access<'a
             indices.push(Access::Member(m));
             (v, indices)
         }
+        TypedAssignee::Element(box t, index) => {
+            let (v, mut indices) = linear(t);
+            indices.push(Access::Element(index));
+            (v, indices)
+        }
     }
 }
 
@@ -401,6 +566,9 @@ impl<'ast, T: Field> Folder<'ast, T> for Unroller<'ast> {
                             .annotate(bitwidth)
                             .into()
                     }
+                    Type::Int(bitwidth) => TypedExpression::Int(
+                        UExpressionInner::Identifier(variable.id.clone().into()).annotate(bitwidth),
+                    ),
                     Type::Array(array_type) => {
                         ArrayExpressionInner::Identifier(variable.id.clone().into())
                             .annotate(*array_type.ty, array_type.size)
@@ -411,6 +579,11 @@ impl<'ast, T: Field> Folder<'ast, T> for Unroller<'ast> {
                             .annotate(members)
                             .into()
                     }
+                    Type::Tuple(tuple_type) => {
+                        TupleExpressionInner::Identifier(variable.id.clone().into())
+                            .annotate(tuple_type)
+                            .into()
+                    }
                 };
 
                 let base = self.fold_expression(base);
@@ -456,6 +629,16 @@ impl<'ast, T: Field> Folder<'ast, T> for Unroller<'ast> {
                             current = T::one() + &current;
                         }
 
+                        let (before, stats) = match has_early_exit(&stats) {
+                            true => EarlyExitLowerer::lower(self.fresh_id("BREAK"), stats),
+                            false => (vec![], stats),
+                        };
+
+                        let before: Vec<_> = before
+                            .into_iter()
+                            .flat_map(|s| self.fold_statement(s))
+                            .collect();
+
                         let res = values
                             .into_iter()
                             .map(|index| {
@@ -473,17 +656,45 @@ impl<'ast, T: Field> Folder<'ast, T> for Unroller<'ast> {
                                 .flat_map(|x| x)
                             })
                             .flat_map(|x| x)
-                            .flat_map(|x| self.fold_statement(x))
-                            .collect();
+                            .flat_map(|x| self.fold_statement(x));
 
-                        res
+                        before.into_iter().chain(res).collect()
                     }
                     (from, to) => {
+                        if self.complete {
+                            self.blocked_at = self.origin.clone();
+                        }
                         self.complete = false;
                         vec![TypedStatement::For(v, from, to, stats)]
                     }
                 }
             }
+            TypedStatement::Origin(origin) => {
+                self.origin = Some(origin.clone());
+                vec![TypedStatement::Origin(origin)]
+            }
+            TypedStatement::IfElse(condition, consequence, alternative) => {
+                let condition_variable =
+                    Variable::boolean(CoreIdentifier::Internal("IF", self.fresh_id("IF")));
+                let condition_expression =
+                    BooleanExpression::Identifier(condition_variable.id.clone());
+
+                vec![
+                    TypedStatement::Declaration(condition_variable.clone()),
+                    TypedStatement::Definition(
+                        TypedAssignee::Identifier(condition_variable),
+                        condition.into(),
+                    ),
+                ]
+                .into_iter()
+                .chain(Guard::guard(condition_expression.clone(), consequence))
+                .chain(Guard::guard(
+                    BooleanExpression::Not(box condition_expression),
+                    alternative,
+                ))
+                .flat_map(|s| self.fold_statement(s))
+                .collect()
+            }
             s => fold_statement(self, s),
         }
     }
@@ -919,6 +1130,73 @@ mod tests {
             );
         }
 
+        #[test]
+        fn if_else() {
+            // field a
+            // a = 5
+            // if a == 5 then
+            //     a = 6
+            // fi
+
+            // should be turned into
+            // a_0 = 5
+            // IF_0 = a_0 == 5
+            // a_1 = if IF_0 then 6 else a_0 fi
+
+            let mut u = Unroller::new();
+
+            let s: TypedStatement<Bn128Field> =
+                TypedStatement::Declaration(Variable::field_element("a"));
+            assert_eq!(u.fold_statement(s), vec![]);
+
+            let s = TypedStatement::Definition(
+                TypedAssignee::Identifier(Variable::field_element("a")),
+                FieldElementExpression::Number(Bn128Field::from(5)).into(),
+            );
+            u.fold_statement(s);
+
+            let s = TypedStatement::IfElse(
+                BooleanExpression::FieldEq(
+                    box FieldElementExpression::Identifier("a".into()),
+                    box FieldElementExpression::Number(Bn128Field::from(5)),
+                ),
+                vec![TypedStatement::Definition(
+                    TypedAssignee::Identifier(Variable::field_element("a")),
+                    FieldElementExpression::Number(Bn128Field::from(6)).into(),
+                )],
+                vec![],
+            );
+
+            let condition: Identifier = CoreIdentifier::Internal("IF", 0).into();
+
+            assert_eq!(
+                u.fold_statement(s),
+                vec![
+                    TypedStatement::Definition(
+                        TypedAssignee::Identifier(Variable::boolean(condition.clone())),
+                        BooleanExpression::FieldEq(
+                            box FieldElementExpression::Identifier(
+                                Identifier::from("a").version(0)
+                            ),
+                            box FieldElementExpression::Number(Bn128Field::from(5)),
+                        )
+                        .into()
+                    ),
+                    TypedStatement::Definition(
+                        TypedAssignee::Identifier(Variable::field_element(
+                            Identifier::from("a").version(1)
+                        )),
+                        FieldElementExpression::if_else(
+                            BooleanExpression::Identifier(condition),
+                            FieldElementExpression::Number(Bn128Field::from(6)),
+                            FieldElementExpression::Identifier(Identifier::from("a").version(0)),
+                        )
+                        .into()
+                    )
+                ]
+            );
+        }
+
         #[test]
         fn incremental_definition() {
             // field a
@@ -1094,6 +1372,7 @@ mod tests {
                             box FieldElementExpression::Number(Bn128Field::from(2))
                         )
                         .into(),
+                        None,
                     ),
                     TypedStatement::Definition(
                         TypedAssignee::Identifier(Variable::field_array(
@@ -1231,6 +1510,7 @@ mod tests {
                             box FieldElementExpression::Number(Bn128Field::from(2))
                         )
                         .into(),
+                        None,
                     ),
                     TypedStatement::Definition(
                         TypedAssignee::Identifier(Variable::with_id_and_type(
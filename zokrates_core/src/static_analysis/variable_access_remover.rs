@@ -53,6 +53,7 @@ impl<'ast, T: Field> VariableAccessRemover<'ast, T> {
                         })
                         .unwrap()
                         .into(),
+                    None,
                 ));
 
                 (0..size)
@@ -181,6 +182,7 @@ mod tests {
                         )
                     )
                     .into(),
+                    None,
                 ),
                 TypedStatement::Definition(
                     TypedAssignee::Identifier(Variable::field_element("b")),
@@ -0,0 +1,384 @@
+//! An index of the symbols declared and used in a program, for editors to navigate it
+
+use crate::parser::Position;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+fn before(a: Position, b: Position) -> bool {
+    (a.line, a.col) < (b.line, b.col)
+}
+
+/// A span of source code in a module
+#[derive(Debug, Clone, PartialEq)]
+pub struct Location {
+    pub module: PathBuf,
+    pub start: Position,
+    pub end: Position,
+}
+
+impl Location {
+    pub fn contains(&self, module: &Path, pos: Position) -> bool {
+        self.module == module && !before(pos, self.start) && before(pos, self.end)
+    }
+}
+
+/// What an identifier refers to
+#[derive(Debug, Clone, PartialEq)]
+pub enum Target {
+    /// A variable declared at this location
+    Variable(Location),
+    /// A symbol declared at the top level of a module, or imported into it
+    Symbol(PathBuf, String),
+}
+
+/// An identifier in the source, with what it refers to
+#[derive(Debug, Clone, PartialEq)]
+pub struct Reference {
+    pub location: Location,
+    pub name: String,
+    /// The type of the variable or constant, or the signature of the function
+    pub ty: String,
+    pub target: Target,
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct SymbolIndex {
+    /// The modules of the program
+    pub modules: HashSet<PathBuf>,
+    /// The identifiers of the program, including variable declarations which refer to themselves
+    pub references: Vec<Reference>,
+    /// The symbols declared at the top level of each module
+    pub declarations: HashMap<(PathBuf, String), Location>,
+    /// The symbols imported in each module under an alias, with the module and name they refer to
+    pub imports: HashMap<(PathBuf, String), (PathBuf, String)>,
+}
+
+impl SymbolIndex {
+    /// The identifier at `pos` in `module`, if any
+    pub fn reference_at(&self, module: &Path, pos: Position) -> Option<&Reference> {
+        self.references
+            .iter()
+            .find(|r| r.location.contains(module, pos))
+    }
+
+    /// Where the identifier is declared, following imports
+    pub fn definition(&self, reference: &Reference) -> Option<Location> {
+        match &reference.target {
+            Target::Variable(location) => Some(location.clone()),
+            Target::Symbol(module, name) => {
+                let mut key = (module.clone(), name.clone());
+                // bound the search in case of an import cycle
+                for _ in 0..=self.imports.len() {
+                    if let Some(location) = self.declarations.get(&key) {
+                        return Some(location.clone());
+                    }
+                    key = self.imports.get(&key)?.clone();
+                }
+                None
+            }
+        }
+    }
+
+    /// The part of the index about `module`: its references, declarations and imports
+    pub fn module(&self, module: &Path) -> SymbolIndex {
+        SymbolIndex {
+            modules: vec![module.to_path_buf()].into_iter().collect(),
+            references: self
+                .references
+                .iter()
+                .filter(|r| r.location.module == module)
+                .cloned()
+                .collect(),
+            declarations: self
+                .declarations
+                .iter()
+                .filter(|((m, _), _)| m == module)
+                .map(|(k, v)| (k.clone(), v.clone()))
+                .collect(),
+            imports: self
+                .imports
+                .iter()
+                .filter(|((m, _), _)| m == module)
+                .map(|(k, v)| (k.clone(), v.clone()))
+                .collect(),
+        }
+    }
+
+    /// Whether the index has no symbols, whatever the modules it covers
+    pub fn is_empty(&self) -> bool {
+        self.references.is_empty() && self.declarations.is_empty() && self.imports.is_empty()
+    }
+
+    /// Add the modules and symbols of `other` to the index
+    pub fn extend(&mut self, other: SymbolIndex) {
+        self.modules.extend(other.modules);
+        self.references.extend(other.references);
+        self.declarations.extend(other.declarations);
+        self.imports.extend(other.imports);
+    }
+
+    /// The names which can be used at `pos` in `module` with their types, when known: the symbols of the module, and
+    /// the variables declared before `pos` in the same symbol
+    pub fn visible_at(&self, module: &Path, pos: Position) -> Vec<(String, Option<String>)> {
+        let symbols = self
+            .declarations
+            .keys()
+            .chain(self.imports.keys())
+            .filter(|(m, _)| m == module)
+            .map(|(_, name)| (name.clone(), None));
+
+        let enclosing = self
+            .declarations
+            .iter()
+            .find(|(_, location)| location.contains(module, pos))
+            .map(|(_, location)| location);
+
+        let variables = self
+            .references
+            .iter()
+            .filter(|r| match &r.target {
+                Target::Variable(location) => location == &r.location,
+                _ => false,
+            })
+            .filter(|r| {
+                before(r.location.start, pos)
+                    && enclosing
+                        .map(|e| e.contains(module, r.location.start))
+                        .unwrap_or(false)
+            })
+            .map(|r| (r.name.clone(), Some(r.ty.clone())));
+
+        let mut res: Vec<_> = symbols.chain(variables).collect();
+        res.sort();
+        res.dedup_by(|a, b| a.0 == b.0);
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compile::check_with_index;
+    use std::io;
+    use zokrates_common::Resolver;
+    use zokrates_field::Bn128Field;
+
+    fn pos(line: usize, col: usize) -> Position {
+        Position { line, col }
+    }
+
+    fn location(module: &str, line: usize, start: usize, end: usize) -> Location {
+        Location {
+            module: module.into(),
+            start: pos(line, start),
+            end: pos(line, end),
+        }
+    }
+
+    /// `main` declares `a` and uses `foo`, which it imports from `lib` as `bar`, which itself imports it from `foo`
+    fn index() -> SymbolIndex {
+        let a = location("main", 2, 11, 12);
+        let foo = Location {
+            module: "foo".into(),
+            start: pos(1, 1),
+            end: pos(3, 1),
+        };
+        let main = Location {
+            module: "main".into(),
+            start: pos(1, 1),
+            end: pos(4, 1),
+        };
+
+        SymbolIndex {
+            modules: vec!["main".into(), "lib".into(), "foo".into()]
+                .into_iter()
+                .collect(),
+            references: vec![
+                Reference {
+                    location: a.clone(),
+                    name: "a".into(),
+                    ty: "field".into(),
+                    target: Target::Variable(a.clone()),
+                },
+                Reference {
+                    location: location("main", 3, 12, 15),
+                    name: "bar".into(),
+                    ty: "foo() -> field".into(),
+                    target: Target::Symbol("main".into(), "bar".into()),
+                },
+                Reference {
+                    location: location("main", 3, 18, 19),
+                    name: "a".into(),
+                    ty: "field".into(),
+                    target: Target::Variable(a),
+                },
+            ],
+            declarations: vec![
+                (("main".into(), "main".into()), main),
+                (("foo".into(), "foo".into()), foo),
+            ]
+            .into_iter()
+            .collect(),
+            imports: vec![
+                (("main".into(), "bar".into()), ("lib".into(), "foo".into())),
+                (("lib".into(), "foo".into()), ("foo".into(), "foo".into())),
+            ]
+            .into_iter()
+            .collect(),
+        }
+    }
+
+    #[test]
+    fn reference_at() {
+        let index = index();
+        let main = Path::new("main");
+
+        // the start of a reference is included, its end is not
+        assert_eq!(index.reference_at(main, pos(3, 12)).unwrap().name, "bar");
+        assert_eq!(index.reference_at(main, pos(3, 14)).unwrap().name, "bar");
+        assert_eq!(index.reference_at(main, pos(3, 15)), None);
+        assert_eq!(index.reference_at(main, pos(3, 11)), None);
+
+        // positions are looked up in the given module only
+        assert_eq!(index.reference_at(Path::new("lib"), pos(3, 12)), None);
+    }
+
+    #[test]
+    fn definition() {
+        let index = index();
+        let main = Path::new("main");
+
+        // variables are defined where they are declared
+        let a = index.reference_at(main, pos(3, 18)).unwrap();
+        assert_eq!(index.definition(a), Some(location("main", 2, 11, 12)));
+
+        // symbols are defined where they are declared, following imports across modules
+        let bar = index.reference_at(main, pos(3, 12)).unwrap();
+        assert_eq!(index.definition(bar).unwrap().module, PathBuf::from("foo"));
+
+        // import cycles have no definition
+        let mut index = index.clone();
+        index.declarations.remove(&("foo".into(), "foo".into()));
+        index
+            .imports
+            .insert(("foo".into(), "foo".into()), ("main".into(), "bar".into()));
+        let bar = index.reference_at(main, pos(3, 12)).unwrap();
+        assert_eq!(index.definition(bar), None);
+    }
+
+    #[test]
+    fn visible_at() {
+        let index = index();
+
+        assert_eq!(
+            index.visible_at(Path::new("main"), pos(3, 5)),
+            vec![
+                (String::from("a"), Some(String::from("field"))),
+                (String::from("bar"), None),
+                (String::from("main"), None),
+            ]
+        );
+
+        // variables are not visible before their declaration
+        assert_eq!(
+            index.visible_at(Path::new("main"), pos(2, 5)),
+            vec![(String::from("bar"), None), (String::from("main"), None)]
+        );
+    }
+
+    #[test]
+    fn module() {
+        let index = index();
+
+        let main = index.module(Path::new("main"));
+        assert_eq!(main.references.len(), 3);
+        assert_eq!(main.declarations.len(), 1);
+        assert_eq!(main.imports.len(), 1);
+
+        let lib = index.module(Path::new("lib"));
+        assert_eq!(lib.references.len(), 0);
+        assert_eq!(lib.declarations.len(), 0);
+        assert_eq!(lib.imports.len(), 1);
+
+        // the index is made of the parts about each of its modules
+        let mut parts = index.module(Path::new("foo"));
+        assert!(!parts.is_empty());
+        parts.extend(main);
+        parts.extend(lib);
+        assert_eq!(parts, index);
+
+        assert!(index.module(Path::new("other")).is_empty());
+    }
+
+    #[test]
+    fn across_imports() {
+        struct CustomResolver;
+
+        impl<E> Resolver<E> for CustomResolver {
+            fn resolve(
+                &self,
+                _: PathBuf,
+                import_location: PathBuf,
+            ) -> Result<(String, PathBuf), E> {
+                let source = match import_location.to_str().unwrap() {
+                    "lib" => "from \"foo\" import foo\n",
+                    _ => "def foo(field a) -> field:\n    return a\n",
+                };
+                Ok((source.to_string(), import_location))
+            }
+        }
+
+        let source =
+            "from \"lib\" import foo as bar\n\ndef main(field a) -> field:\n    return bar(a)\n";
+        let (res, index) = check_with_index::<Bn128Field, io::Error>(
+            source.to_string(),
+            "main".into(),
+            Some(&CustomResolver),
+            None,
+        );
+        assert!(res.is_ok());
+
+        assert_eq!(
+            index.modules,
+            vec!["main".into(), "lib".into(), "foo".into()]
+                .into_iter()
+                .collect()
+        );
+
+        let bar = index.reference_at(Path::new("main"), pos(4, 12)).unwrap();
+        assert_eq!(bar.name, "bar");
+        assert_eq!(bar.ty, "bar(field) -> field");
+
+        let definition = index.definition(bar).unwrap();
+        assert_eq!(definition.module, PathBuf::from("foo"));
+        assert_eq!(definition.start.line, 1);
+
+        // the argument of the call refers to the parameter of `main`
+        let a = index.reference_at(Path::new("main"), pos(4, 16)).unwrap();
+        assert_eq!(index.definition(a).unwrap().start, pos(3, 16));
+    }
+
+    #[test]
+    fn call_with_spaces() {
+        // calls are located at the name of the function, whatever separates it from the arguments
+        let source = "def foo(field a) -> field:\n    return a\n\n\
+            def main(field a) -> field:\n    field b = foo (a)\n    return foo  (b)\n";
+        let (res, index) = check_with_index::<Bn128Field, io::Error>(
+            source.to_string(),
+            "main".into(),
+            None,
+            None,
+        );
+        assert!(res.is_ok());
+
+        let main = Path::new("main");
+
+        let foo = index.reference_at(main, pos(5, 15)).unwrap();
+        assert_eq!(foo.name, "foo");
+        assert_eq!(foo.location, location("main", 5, 15, 18));
+
+        let foo = index.reference_at(main, pos(6, 12)).unwrap();
+        assert_eq!(foo.name, "foo");
+        assert_eq!(foo.location, location("main", 6, 12, 15));
+    }
+}
@@ -5,11 +5,25 @@ use typed_absy::Type;
 pub struct AbiInput {
     pub name: String,
     pub public: bool,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub alias: Option<String>,
     #[serde(flatten)]
     pub ty: Type,
 }
 
-pub type AbiOutput = Type;
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq)]
+pub struct AbiOutput {
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub alias: Option<String>,
+    #[serde(flatten)]
+    pub ty: Type,
+}
+
+impl From<Type> for AbiOutput {
+    fn from(ty: Type) -> Self {
+        AbiOutput { alias: None, ty }
+    }
+}
 
 #[derive(Serialize, Deserialize, Debug, Eq, PartialEq)]
 pub struct Abi {
@@ -21,7 +35,7 @@ impl Abi {
     pub fn signature(&self) -> Signature {
         Signature {
             inputs: self.inputs.iter().map(|i| i.ty.clone()).collect(),
-            outputs: self.outputs.clone(),
+            outputs: self.outputs.iter().map(|o| o.ty.clone()).collect(),
         }
     }
 }
@@ -67,21 +81,23 @@ mod tests {
             modules,
         };
 
-        let abi: Abi = typed_ast.abi();
+        let abi: Abi = typed_ast.abi("main");
         let expected_abi = Abi {
             inputs: vec![
                 AbiInput {
                     name: String::from("a"),
                     public: false,
+                    alias: None,
                     ty: Type::FieldElement,
                 },
                 AbiInput {
                     name: String::from("b"),
                     public: true,
+                    alias: None,
                     ty: Type::Boolean,
                 },
             ],
-            outputs: vec![Type::FieldElement],
+            outputs: vec![Type::FieldElement.into()],
         };
 
         assert_eq!(expected_abi, abi);
@@ -105,15 +121,17 @@ mod tests {
                 AbiInput {
                     name: String::from("a"),
                     public: true,
+                    alias: None,
                     ty: Type::FieldElement,
                 },
                 AbiInput {
                     name: String::from("b"),
                     public: true,
+                    alias: None,
                     ty: Type::FieldElement,
                 },
             ],
-            outputs: vec![Type::FieldElement],
+            outputs: vec![Type::FieldElement.into()],
         };
 
         let json = serde_json::to_string_pretty(&abi).unwrap();
@@ -141,12 +159,63 @@ mod tests {
         )
     }
 
+    #[test]
+    fn serialize_tuple() {
+        let abi: Abi = Abi {
+            inputs: vec![AbiInput {
+                name: String::from("a"),
+                public: true,
+                alias: None,
+                ty: Type::tuple(vec![Type::FieldElement, Type::Boolean]),
+            }],
+            outputs: vec![Type::tuple(vec![Type::uint(8)]).into()],
+        };
+
+        let json = serde_json::to_string_pretty(&abi).unwrap();
+        assert_eq!(
+            &json,
+            r#"{
+  "inputs": [
+    {
+      "name": "a",
+      "public": true,
+      "type": "tuple",
+      "components": {
+        "elements": [
+          {
+            "type": "field"
+          },
+          {
+            "type": "bool"
+          }
+        ]
+      }
+    }
+  ],
+  "outputs": [
+    {
+      "type": "tuple",
+      "components": {
+        "elements": [
+          {
+            "type": "u",
+            "components": "8"
+          }
+        ]
+      }
+    }
+  ]
+}"#
+        )
+    }
+
     #[test]
     fn serialize_struct() {
         let abi: Abi = Abi {
             inputs: vec![AbiInput {
                 name: String::from("foo"),
                 public: true,
+                alias: None,
                 ty: Type::Struct(StructType::new(
                     "".into(),
                     "Foo".into(),
@@ -163,7 +232,8 @@ mod tests {
                     StructMember::new(String::from("a"), Type::FieldElement),
                     StructMember::new(String::from("b"), Type::Boolean),
                 ],
-            ))],
+            ))
+            .into()],
         };
 
         let json = serde_json::to_string_pretty(&abi).unwrap();
@@ -218,6 +288,7 @@ mod tests {
             inputs: vec![AbiInput {
                 name: String::from("foo"),
                 public: true,
+                alias: None,
                 ty: Type::Struct(StructType::new(
                     "".into(),
                     "Foo".into(),
@@ -281,6 +352,7 @@ mod tests {
             inputs: vec![AbiInput {
                 name: String::from("a"),
                 public: false,
+                alias: None,
                 ty: Type::Array(ArrayType::new(
                     Type::Struct(StructType::new(
                         "".into(),
@@ -293,7 +365,7 @@ mod tests {
                     2,
                 )),
             }],
-            outputs: vec![Type::Boolean],
+            outputs: vec![Type::Boolean.into()],
         };
 
         let json = serde_json::to_string_pretty(&abi).unwrap();
@@ -339,12 +411,13 @@ mod tests {
             inputs: vec![AbiInput {
                 name: String::from("a"),
                 public: false,
+                alias: None,
                 ty: Type::Array(ArrayType::new(
                     Type::Array(ArrayType::new(Type::FieldElement, 2)),
                     2,
                 )),
             }],
-            outputs: vec![Type::FieldElement],
+            outputs: vec![Type::FieldElement.into()],
         };
 
         let json = serde_json::to_string_pretty(&abi).unwrap();
@@ -374,4 +447,52 @@ mod tests {
 }"#
         )
     }
+
+    #[test]
+    fn serialize_alias() {
+        let abi: Abi = Abi {
+            inputs: vec![AbiInput {
+                name: String::from("a"),
+                public: true,
+                alias: Some(String::from("Digest")),
+                ty: Type::array(Type::FieldElement, 2),
+            }],
+            outputs: vec![AbiOutput {
+                alias: Some(String::from("Digest")),
+                ty: Type::array(Type::FieldElement, 2),
+            }],
+        };
+
+        let json = serde_json::to_string_pretty(&abi).unwrap();
+        assert_eq!(
+            &json,
+            r#"{
+  "inputs": [
+    {
+      "name": "a",
+      "public": true,
+      "alias": "Digest",
+      "type": "array",
+      "components": {
+        "size": 2,
+        "type": "field"
+      }
+    }
+  ],
+  "outputs": [
+    {
+      "alias": "Digest",
+      "type": "array",
+      "components": {
+        "size": 2,
+        "type": "field"
+      }
+    }
+  ]
+}"#
+        );
+
+        let de_abi: Abi = serde_json::from_str(json.as_ref()).unwrap();
+        assert_eq!(de_abi, abi);
+    }
 }
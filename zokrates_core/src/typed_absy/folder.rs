@@ -49,6 +49,9 @@ pub trait Folder<'ast, T: Field>: Sized {
                 box self.fold_field_expression(index),
             ),
             TypedAssignee::Member(box s, m) => TypedAssignee::Member(box self.fold_assignee(s), m),
+            TypedAssignee::Element(box t, index) => {
+                TypedAssignee::Element(box self.fold_assignee(t), index)
+            }
         }
     }
 
@@ -61,8 +64,10 @@ pub trait Folder<'ast, T: Field>: Sized {
             TypedExpression::FieldElement(e) => self.fold_field_expression(e).into(),
             TypedExpression::Boolean(e) => self.fold_boolean_expression(e).into(),
             TypedExpression::Uint(e) => self.fold_uint_expression(e).into(),
+            TypedExpression::Int(e) => TypedExpression::Int(self.fold_uint_expression(e)),
             TypedExpression::Array(e) => self.fold_array_expression(e).into(),
             TypedExpression::Struct(e) => self.fold_struct_expression(e).into(),
+            TypedExpression::Tuple(e) => self.fold_tuple_expression(e).into(),
         }
     }
 
@@ -77,6 +82,10 @@ pub trait Folder<'ast, T: Field>: Sized {
         fold_struct_expression(self, e)
     }
 
+    fn fold_tuple_expression(&mut self, e: TupleExpression<'ast, T>) -> TupleExpression<'ast, T> {
+        fold_tuple_expression(self, e)
+    }
+
     fn fold_expression_list(
         &mut self,
         es: TypedExpressionList<'ast, T>,
@@ -134,6 +143,13 @@ pub trait Folder<'ast, T: Field>: Sized {
     ) -> StructExpressionInner<'ast, T> {
         fold_struct_expression_inner(self, ty, e)
     }
+    fn fold_tuple_expression_inner(
+        &mut self,
+        ty: &TupleType,
+        e: TupleExpressionInner<'ast, T>,
+    ) -> TupleExpressionInner<'ast, T> {
+        fold_tuple_expression_inner(self, ty, e)
+    }
 }
 
 pub fn fold_module<'ast, T: Field, F: Folder<'ast, T>>(
@@ -165,7 +181,9 @@ pub fn fold_statement<'ast, T: Field, F: Folder<'ast, T>>(
             TypedStatement::Definition(f.fold_assignee(a), f.fold_expression(e))
         }
         TypedStatement::Declaration(v) => TypedStatement::Declaration(f.fold_variable(v)),
-        TypedStatement::Assertion(e) => TypedStatement::Assertion(f.fold_boolean_expression(e)),
+        TypedStatement::Assertion(e, metadata) => {
+            TypedStatement::Assertion(f.fold_boolean_expression(e), metadata)
+        }
         TypedStatement::For(v, from, to, statements) => TypedStatement::For(
             f.fold_variable(v),
             from,
@@ -175,10 +193,28 @@ pub fn fold_statement<'ast, T: Field, F: Folder<'ast, T>>(
                 .flat_map(|s| f.fold_statement(s))
                 .collect(),
         ),
+        TypedStatement::IfElse(condition, consequence, alternative) => TypedStatement::IfElse(
+            f.fold_boolean_expression(condition),
+            consequence
+                .into_iter()
+                .flat_map(|s| f.fold_statement(s))
+                .collect(),
+            alternative
+                .into_iter()
+                .flat_map(|s| f.fold_statement(s))
+                .collect(),
+        ),
         TypedStatement::MultipleDefinition(variables, elist) => TypedStatement::MultipleDefinition(
             variables.into_iter().map(|v| f.fold_variable(v)).collect(),
             f.fold_expression_list(elist),
         ),
+        TypedStatement::Break(condition) => {
+            TypedStatement::Break(condition.map(|c| f.fold_boolean_expression(c)))
+        }
+        TypedStatement::Continue(condition) => {
+            TypedStatement::Continue(condition.map(|c| f.fold_boolean_expression(c)))
+        }
+        s @ TypedStatement::Origin(..) => s,
     };
     vec![res]
 }
@@ -214,6 +250,10 @@ pub fn fold_array_expression_inner<'ast, T: Field, F: Folder<'ast, T>>(
             let index = f.fold_field_expression(index);
             ArrayExpressionInner::Select(box array, box index)
         }
+        ArrayExpressionInner::Element(box t, index) => {
+            let t = f.fold_tuple_expression(t);
+            ArrayExpressionInner::Element(box t, index)
+        }
     }
 }
 
@@ -247,6 +287,47 @@ pub fn fold_struct_expression_inner<'ast, T: Field, F: Folder<'ast, T>>(
             let index = f.fold_field_expression(index);
             StructExpressionInner::Select(box array, box index)
         }
+        StructExpressionInner::Element(box t, index) => {
+            let t = f.fold_tuple_expression(t);
+            StructExpressionInner::Element(box t, index)
+        }
+    }
+}
+
+pub fn fold_tuple_expression_inner<'ast, T: Field, F: Folder<'ast, T>>(
+    f: &mut F,
+    _: &TupleType,
+    e: TupleExpressionInner<'ast, T>,
+) -> TupleExpressionInner<'ast, T> {
+    match e {
+        TupleExpressionInner::Identifier(id) => TupleExpressionInner::Identifier(f.fold_name(id)),
+        TupleExpressionInner::Value(exprs) => {
+            TupleExpressionInner::Value(exprs.into_iter().map(|e| f.fold_expression(e)).collect())
+        }
+        TupleExpressionInner::FunctionCall(id, exps) => {
+            let exps = exps.into_iter().map(|e| f.fold_expression(e)).collect();
+            TupleExpressionInner::FunctionCall(id, exps)
+        }
+        TupleExpressionInner::IfElse(box condition, box consequence, box alternative) => {
+            TupleExpressionInner::IfElse(
+                box f.fold_boolean_expression(condition),
+                box f.fold_tuple_expression(consequence),
+                box f.fold_tuple_expression(alternative),
+            )
+        }
+        TupleExpressionInner::Member(box s, id) => {
+            let s = f.fold_struct_expression(s);
+            TupleExpressionInner::Member(box s, id)
+        }
+        TupleExpressionInner::Select(box array, box index) => {
+            let array = f.fold_array_expression(array);
+            let index = f.fold_field_expression(index);
+            TupleExpressionInner::Select(box array, box index)
+        }
+        TupleExpressionInner::Element(box t, index) => {
+            let t = f.fold_tuple_expression(t);
+            TupleExpressionInner::Element(box t, index)
+        }
     }
 }
 
@@ -303,6 +384,10 @@ pub fn fold_field_expression<'ast, T: Field, F: Folder<'ast, T>>(
             let index = f.fold_field_expression(index);
             FieldElementExpression::Select(box array, box index)
         }
+        FieldElementExpression::Element(box t, index) => {
+            let t = f.fold_tuple_expression(t);
+            FieldElementExpression::Element(box t, index)
+        }
     }
 }
 
@@ -338,6 +423,26 @@ pub fn fold_boolean_expression<'ast, T: Field, F: Folder<'ast, T>>(
             let e2 = f.fold_uint_expression(e2);
             BooleanExpression::UintEq(box e1, box e2)
         }
+        BooleanExpression::UintLt(box e1, box e2) => {
+            let e1 = f.fold_uint_expression(e1);
+            let e2 = f.fold_uint_expression(e2);
+            BooleanExpression::UintLt(box e1, box e2)
+        }
+        BooleanExpression::UintLe(box e1, box e2) => {
+            let e1 = f.fold_uint_expression(e1);
+            let e2 = f.fold_uint_expression(e2);
+            BooleanExpression::UintLe(box e1, box e2)
+        }
+        BooleanExpression::UintGe(box e1, box e2) => {
+            let e1 = f.fold_uint_expression(e1);
+            let e2 = f.fold_uint_expression(e2);
+            BooleanExpression::UintGe(box e1, box e2)
+        }
+        BooleanExpression::UintGt(box e1, box e2) => {
+            let e1 = f.fold_uint_expression(e1);
+            let e2 = f.fold_uint_expression(e2);
+            BooleanExpression::UintGt(box e1, box e2)
+        }
         BooleanExpression::Lt(box e1, box e2) => {
             let e1 = f.fold_field_expression(e1);
             let e2 = f.fold_field_expression(e2);
@@ -391,6 +496,10 @@ pub fn fold_boolean_expression<'ast, T: Field, F: Folder<'ast, T>>(
             let index = f.fold_field_expression(index);
             BooleanExpression::Select(box array, box index)
         }
+        BooleanExpression::Element(box t, index) => {
+            let t = f.fold_tuple_expression(t);
+            BooleanExpression::Element(box t, index)
+        }
     }
 }
 
@@ -430,6 +539,18 @@ pub fn fold_uint_expression_inner<'ast, T: Field, F: Folder<'ast, T>>(
 
             UExpressionInner::Mult(box left, box right)
         }
+        UExpressionInner::Div(box left, box right) => {
+            let left = f.fold_uint_expression(left);
+            let right = f.fold_uint_expression(right);
+
+            UExpressionInner::Div(box left, box right)
+        }
+        UExpressionInner::Rem(box left, box right) => {
+            let left = f.fold_uint_expression(left);
+            let right = f.fold_uint_expression(right);
+
+            UExpressionInner::Rem(box left, box right)
+        }
         UExpressionInner::Xor(box left, box right) => {
             let left = f.fold_uint_expression(left);
             let right = f.fold_uint_expression(right);
@@ -484,6 +605,10 @@ pub fn fold_uint_expression_inner<'ast, T: Field, F: Folder<'ast, T>>(
             let s = f.fold_struct_expression(s);
             UExpressionInner::Member(box s, id)
         }
+        UExpressionInner::Element(box t, index) => {
+            let t = f.fold_tuple_expression(t);
+            UExpressionInner::Element(box t, index)
+        }
     }
 }
 
@@ -526,6 +651,16 @@ pub fn fold_struct_expression<'ast, T: Field, F: Folder<'ast, T>>(
     }
 }
 
+pub fn fold_tuple_expression<'ast, T: Field, F: Folder<'ast, T>>(
+    f: &mut F,
+    e: TupleExpression<'ast, T>,
+) -> TupleExpression<'ast, T> {
+    TupleExpression {
+        inner: f.fold_tuple_expression_inner(&e.ty, e.inner),
+        ..e
+    }
+}
+
 pub fn fold_function_symbol<'ast, T: Field, F: Folder<'ast, T>>(
     f: &mut F,
     s: TypedFunctionSymbol<'ast, T>,
@@ -2,10 +2,13 @@ use std::fmt;
 use typed_absy::types::FunctionKey;
 use typed_absy::TypedModuleId;
 
-#[derive(Debug, PartialEq, Clone, Hash, Eq)]
+#[derive(Debug, PartialEq, Clone, Hash, Eq, Serialize, Deserialize)]
+#[serde(bound(deserialize = "'de: 'ast"))]
 pub enum CoreIdentifier<'ast> {
     Source(&'ast str),
-    Internal(&'static str, usize),
+    // internal identifiers are only introduced after semantic checking, so checked modules never contain them
+    #[serde(skip)]
+    Internal(#[serde(skip_deserializing)] &'static str, usize),
     Call(FunctionKey<'ast>),
 }
 
@@ -20,7 +23,8 @@ impl<'ast> fmt::Display for CoreIdentifier<'ast> {
 }
 
 /// A identifier for a variable
-#[derive(Debug, PartialEq, Clone, Hash, Eq)]
+#[derive(Debug, PartialEq, Clone, Hash, Eq, Serialize, Deserialize)]
+#[serde(bound(deserialize = "'de: 'ast"))]
 pub struct Identifier<'ast> {
     /// the id of the variable
     pub id: CoreIdentifier<'ast>,
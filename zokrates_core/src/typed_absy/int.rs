@@ -0,0 +1,171 @@
+//! Signed integer operations
+//!
+//! Signed integers share their representation with unsigned integers: a value of type `iN` is
+//! stored as the `N`-bit two's complement pattern in a `UExpression`. Addition, subtraction,
+//! multiplication, bitwise operations, left shift and equality are therefore the unsigned ones.
+//! The operations below depend on the sign and are expressed in terms of unsigned operations, so
+//! that later stages (`UintOptimizer`, flattening) do not need to know about signedness.
+
+use typed_absy::*;
+use zokrates_field::Field;
+
+impl<'ast, T: Field> UExpression<'ast, T> {
+    fn value(v: u128, bitwidth: UBitwidth) -> Self {
+        UExpressionInner::Value(v).annotate(bitwidth)
+    }
+
+    fn shift_amount(by: usize) -> FieldElementExpression<'ast, T> {
+        FieldElementExpression::Number(T::from(by))
+    }
+
+    // 2^(n-1), the bit pattern of the smallest signed value
+    fn sign_bit(bitwidth: UBitwidth) -> u128 {
+        1 << (bitwidth.to_usize() - 1)
+    }
+
+    // flipping the sign bit maps the signed order onto the unsigned order
+    fn flip_sign(self) -> Self {
+        let bitwidth = self.bitwidth;
+        self.xor(Self::value(Self::sign_bit(bitwidth), bitwidth))
+    }
+
+    // 1 if the value is negative, 0 otherwise
+    fn sign(self) -> Self {
+        let bitwidth = self.bitwidth;
+        self.right_shift(Self::shift_amount(bitwidth.to_usize() - 1))
+    }
+
+    // negate `self` if `sign` is 1, using `-x == (x ^ 0b11..1) + 1`
+    fn conditional_neg(self, sign: Self) -> Self {
+        let bitwidth = self.bitwidth;
+        let mask = Self::value(0, bitwidth).sub(sign.clone());
+        self.xor(mask).add(sign)
+    }
+
+    fn abs(self) -> Self {
+        let sign = self.clone().sign();
+        self.conditional_neg(sign)
+    }
+
+    /// Compares two signed integers by comparing their bit patterns with the sign bit flipped
+    pub fn signed_comparison(self, op: Comparison, other: Self) -> BooleanExpression<'ast, T> {
+        BooleanExpression::uint_comparison(op, self.flip_sign(), other.flip_sign())
+    }
+
+    /// Arithmetic right shift: `(a ^ 2^(n-1)) >> k - 2^(n-1) >> k`
+    pub fn signed_right_shift(self, by: FieldElementExpression<'ast, T>) -> Self {
+        let bitwidth = self.bitwidth;
+        let offset = Self::value(Self::sign_bit(bitwidth), bitwidth).right_shift(by.clone());
+        self.flip_sign().right_shift(by).sub(offset)
+    }
+
+    /// Division rounding towards zero
+    pub fn signed_div(self, other: Self) -> Self {
+        let sign = self.clone().sign().xor(other.clone().sign());
+        self.abs().div(other.abs()).conditional_neg(sign)
+    }
+
+    /// Remainder of the division rounding towards zero, which has the sign of the dividend
+    pub fn signed_rem(self, other: Self) -> Self {
+        let sign = self.clone().sign();
+        self.abs().rem(other.abs()).conditional_neg(sign)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zokrates_field::Bn128Field;
+
+    // evaluates the unsigned operations produced by the signed ones on constant operands
+    fn eval(e: UExpression<Bn128Field>) -> u128 {
+        let mask = u128::max_value() >> (128 - e.bitwidth.to_usize());
+        match e.into_inner() {
+            UExpressionInner::Value(v) => v,
+            UExpressionInner::Add(box e1, box e2) => eval(e1).wrapping_add(eval(e2)) & mask,
+            UExpressionInner::Sub(box e1, box e2) => eval(e1).wrapping_sub(eval(e2)) & mask,
+            UExpressionInner::Div(box e1, box e2) => eval(e1) / eval(e2),
+            UExpressionInner::Rem(box e1, box e2) => eval(e1) % eval(e2),
+            UExpressionInner::Xor(box e1, box e2) => eval(e1) ^ eval(e2),
+            UExpressionInner::RightShift(box e, box FieldElementExpression::Number(by)) => {
+                eval(e) >> by.to_dec_string().parse::<usize>().unwrap()
+            }
+            e => unreachable!("unexpected expression {:?}", e),
+        }
+    }
+
+    fn eval_boolean(e: BooleanExpression<Bn128Field>) -> bool {
+        match e {
+            BooleanExpression::UintLt(box e1, box e2) => eval(e1) < eval(e2),
+            BooleanExpression::UintLe(box e1, box e2) => eval(e1) <= eval(e2),
+            BooleanExpression::UintGe(box e1, box e2) => eval(e1) >= eval(e2),
+            BooleanExpression::UintGt(box e1, box e2) => eval(e1) > eval(e2),
+            e => unreachable!("unexpected expression {}", e),
+        }
+    }
+
+    // the two's complement encoding of `v` on 8 bits
+    fn i8(v: i8) -> UExpression<'static, Bn128Field> {
+        UExpressionInner::Value(v as u8 as u128).annotate(8)
+    }
+
+    fn decode(e: UExpression<Bn128Field>) -> i8 {
+        eval(e) as u8 as i8
+    }
+
+    #[test]
+    fn right_shift_extends_sign() {
+        let shift = |v: i8, by: usize| {
+            decode(i8(v).signed_right_shift(FieldElementExpression::Number(Bn128Field::from(by))))
+        };
+
+        assert_eq!(shift(-128, 1), -64);
+        assert_eq!(shift(-1, 7), -1);
+        assert_eq!(shift(-5, 1), -3);
+        assert_eq!(shift(127, 7), 0);
+        assert_eq!(shift(64, 2), 16);
+    }
+
+    #[test]
+    fn div_rem() {
+        for (a, b) in vec![(7, 2), (-7, 2), (7, -2), (-7, -2), (0, -3), (-128, 3)] {
+            assert_eq!(decode(i8(a).signed_div(i8(b))), a / b);
+            assert_eq!(decode(i8(a).signed_rem(i8(b))), a % b);
+        }
+    }
+
+    #[test]
+    fn overflow() {
+        // operations wrap around like two's complement arithmetic on 8 bits
+        assert_eq!(decode(i8(127).add(i8(1))), -128);
+        assert_eq!(decode(i8(-128).sub(i8(1))), 127);
+        assert_eq!(decode(i8(-128).signed_div(i8(-1))), -128);
+        assert_eq!(decode(i8(-128).signed_rem(i8(-1))), 0);
+    }
+
+    #[test]
+    fn comparison() {
+        let values = vec![-128, -127, -1, 0, 1, 126, 127];
+
+        for &a in &values {
+            for &b in &values {
+                assert_eq!(
+                    eval_boolean(i8(a).signed_comparison(Comparison::Lt, i8(b))),
+                    a < b
+                );
+                assert_eq!(
+                    eval_boolean(i8(a).signed_comparison(Comparison::Le, i8(b))),
+                    a <= b
+                );
+                assert_eq!(
+                    eval_boolean(i8(a).signed_comparison(Comparison::Ge, i8(b))),
+                    a >= b
+                );
+                assert_eq!(
+                    eval_boolean(i8(a).signed_comparison(Comparison::Gt, i8(b))),
+                    a > b
+                );
+            }
+        }
+    }
+}
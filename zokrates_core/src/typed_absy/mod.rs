@@ -9,6 +9,7 @@ pub mod abi;
 pub mod folder;
 pub mod identifier;
 
+mod int;
 mod parameter;
 pub mod types;
 mod uint;
@@ -16,20 +17,21 @@ mod variable;
 
 pub use self::identifier::CoreIdentifier;
 pub use self::parameter::Parameter;
-pub use self::types::{Signature, StructType, Type, UBitwidth};
+pub use self::types::{Signature, StructType, TupleType, Type, UBitwidth};
 pub use self::variable::Variable;
 use std::path::PathBuf;
 pub use typed_absy::uint::{bitwidth, UExpression, UExpressionInner, UMetadata};
 
 use crate::typed_absy::types::{FunctionKey, MemberId};
 use embed::FlatEmbed;
+use source_map::{Origin, SourceMetadata};
 use std::collections::HashMap;
 use std::convert::TryFrom;
 use std::fmt;
 use zokrates_field::Field;
 
 pub use self::folder::Folder;
-use typed_absy::abi::{Abi, AbiInput};
+use typed_absy::abi::{Abi, AbiInput, AbiOutput};
 
 pub use self::identifier::Identifier;
 
@@ -53,11 +55,12 @@ pub struct TypedProgram<'ast, T> {
 }
 
 impl<'ast, T: Field> TypedProgram<'ast, T> {
-    pub fn abi(&self) -> Abi {
+    /// The ABI of the entry point `entry` of the main module
+    pub fn abi(&self, entry: &str) -> Abi {
         let main = self.modules[&self.main]
             .functions
             .iter()
-            .find(|(id, _)| id.id == "main")
+            .find(|(id, _)| id.id == entry)
             .unwrap()
             .1;
         let main = match main {
@@ -72,10 +75,17 @@ impl<'ast, T: Field> TypedProgram<'ast, T> {
                 .map(|p| AbiInput {
                     public: !p.private,
                     name: p.id.id.to_string(),
+                    alias: None,
                     ty: p.id._type.clone(),
                 })
                 .collect(),
-            outputs: main.signature.outputs.clone(),
+            outputs: main
+                .signature
+                .outputs
+                .iter()
+                .cloned()
+                .map(AbiOutput::from)
+                .collect(),
         }
     }
 }
@@ -103,13 +113,36 @@ impl<'ast, T: fmt::Display> fmt::Display for TypedProgram<'ast, T> {
 }
 
 /// A typed program as a collection of functions. Types have been resolved during semantic checking.
-#[derive(PartialEq, Clone)]
+#[derive(PartialEq, Clone, Serialize, Deserialize)]
+#[serde(bound(serialize = "T: Field", deserialize = "'de: 'ast, T: Field"))]
 pub struct TypedModule<'ast, T> {
     /// Functions of the program
+    #[serde(with = "function_symbols")]
     pub functions: TypedFunctionSymbols<'ast, T>,
 }
 
-#[derive(Clone, PartialEq)]
+/// Function keys are not strings, so symbols are serialized as a sequence of pairs rather than a map
+mod function_symbols {
+    use super::*;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer, T: Field>(
+        symbols: &TypedFunctionSymbols<T>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(symbols.iter())
+    }
+
+    pub fn deserialize<'de: 'ast, 'ast, D: Deserializer<'de>, T: Field>(
+        deserializer: D,
+    ) -> Result<TypedFunctionSymbols<'ast, T>, D::Error> {
+        Vec::<(FunctionKey<'ast>, TypedFunctionSymbol<'ast, T>)>::deserialize(deserializer)
+            .map(|symbols| symbols.into_iter().collect())
+    }
+}
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[serde(bound(deserialize = "'de: 'ast, T: Field"))]
 pub enum TypedFunctionSymbol<'ast, T> {
     Here(TypedFunction<'ast, T>),
     There(FunctionKey<'ast>, TypedModuleId),
@@ -182,7 +215,8 @@ impl<'ast, T: fmt::Debug> fmt::Debug for TypedModule<'ast, T> {
 }
 
 /// A typed function
-#[derive(Clone, PartialEq)]
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[serde(bound(deserialize = "'de: 'ast, T: Field"))]
 pub struct TypedFunction<'ast, T> {
     /// Arguments of the function
     pub arguments: Vec<Parameter<'ast>>,
@@ -249,7 +283,8 @@ impl<'ast, T: fmt::Debug> fmt::Debug for TypedFunction<'ast, T> {
 }
 
 /// Something we can assign to.
-#[derive(Clone, PartialEq, Hash, Eq)]
+#[derive(Clone, PartialEq, Hash, Eq, Serialize, Deserialize)]
+#[serde(bound(deserialize = "'de: 'ast, T: Field"))]
 pub enum TypedAssignee<'ast, T> {
     Identifier(Variable<'ast>),
     Select(
@@ -257,6 +292,7 @@ pub enum TypedAssignee<'ast, T> {
         Box<FieldElementExpression<'ast, T>>,
     ),
     Member(Box<TypedAssignee<'ast, T>>, MemberId),
+    Element(Box<TypedAssignee<'ast, T>>, usize),
 }
 
 impl<'ast, T> Typed for TypedAssignee<'ast, T> {
@@ -282,6 +318,13 @@ impl<'ast, T> Typed for TypedAssignee<'ast, T> {
                     _ => unreachable!("a struct access should only be defined over structs"),
                 }
             }
+            TypedAssignee::Element(ref t, index) => {
+                let t_type = t.get_type();
+                match t_type {
+                    Type::Tuple(tuple_type) => tuple_type.elements[index].clone(),
+                    _ => unreachable!("a tuple access should only be defined over tuples"),
+                }
+            }
         }
     }
 }
@@ -292,6 +335,7 @@ impl<'ast, T: fmt::Debug> fmt::Debug for TypedAssignee<'ast, T> {
             TypedAssignee::Identifier(ref s) => write!(f, "{}", s.id),
             TypedAssignee::Select(ref a, ref e) => write!(f, "Select({:?}, {:?})", a, e),
             TypedAssignee::Member(ref s, ref m) => write!(f, "Member({:?}, {:?})", s, m),
+            TypedAssignee::Element(ref t, ref i) => write!(f, "Element({:?}, {:?})", t, i),
         }
     }
 }
@@ -302,24 +346,35 @@ impl<'ast, T: fmt::Display> fmt::Display for TypedAssignee<'ast, T> {
             TypedAssignee::Identifier(ref s) => write!(f, "{}", s.id),
             TypedAssignee::Select(ref a, ref e) => write!(f, "{}[{}]", a, e),
             TypedAssignee::Member(ref s, ref m) => write!(f, "{}.{}", s, m),
+            TypedAssignee::Element(ref t, ref i) => write!(f, "{}.{}", t, i),
         }
     }
 }
 
 /// A statement in a `TypedFunction`
-#[derive(Clone, PartialEq, Hash, Eq)]
+#[derive(Clone, PartialEq, Hash, Eq, Serialize, Deserialize)]
+#[serde(bound(deserialize = "'de: 'ast, T: Field"))]
 pub enum TypedStatement<'ast, T> {
     Return(Vec<TypedExpression<'ast, T>>),
     Definition(TypedAssignee<'ast, T>, TypedExpression<'ast, T>),
     Declaration(Variable<'ast>),
-    Assertion(BooleanExpression<'ast, T>),
+    Assertion(BooleanExpression<'ast, T>, Option<SourceMetadata>),
     For(
         Variable<'ast>,
         FieldElementExpression<'ast, T>,
         FieldElementExpression<'ast, T>,
         Vec<TypedStatement<'ast, T>>,
     ),
+    IfElse(
+        BooleanExpression<'ast, T>,
+        Vec<TypedStatement<'ast, T>>,
+        Vec<TypedStatement<'ast, T>>,
+    ),
+    Break(Option<BooleanExpression<'ast, T>>),
+    Continue(Option<BooleanExpression<'ast, T>>),
     MultipleDefinition(Vec<Variable<'ast>>, TypedExpressionList<'ast, T>),
+    /// Marks the origin of the statements which follow it, to locate analysis errors and to profile
+    Origin(Origin),
 }
 
 impl<'ast, T: fmt::Debug> fmt::Debug for TypedStatement<'ast, T> {
@@ -339,7 +394,9 @@ impl<'ast, T: fmt::Debug> fmt::Debug for TypedStatement<'ast, T> {
             TypedStatement::Definition(ref lhs, ref rhs) => {
                 write!(f, "Definition({:?}, {:?})", lhs, rhs)
             }
-            TypedStatement::Assertion(ref e) => write!(f, "Assertion({:?})", e),
+            TypedStatement::Assertion(ref e, ref metadata) => {
+                write!(f, "Assertion({:?}, {:?})", e, metadata)
+            }
             TypedStatement::For(ref var, ref start, ref stop, ref list) => {
                 write!(f, "for {:?} in {:?}..{:?} do\n", var, start, stop)?;
                 for l in list {
@@ -347,9 +404,19 @@ impl<'ast, T: fmt::Debug> fmt::Debug for TypedStatement<'ast, T> {
                 }
                 write!(f, "\tendfor")
             }
+            TypedStatement::IfElse(ref condition, ref consequence, ref alternative) => {
+                write!(
+                    f,
+                    "IfElse({:?}, {:?}, {:?})",
+                    condition, consequence, alternative
+                )
+            }
+            TypedStatement::Break(ref condition) => write!(f, "Break({:?})", condition),
+            TypedStatement::Continue(ref condition) => write!(f, "Continue({:?})", condition),
             TypedStatement::MultipleDefinition(ref lhs, ref rhs) => {
                 write!(f, "MultipleDefinition({:?}, {:?})", lhs, rhs)
             }
+            TypedStatement::Origin(ref origin) => write!(f, "Origin({:?})", origin),
         }
     }
 }
@@ -366,6 +433,20 @@ impl<'ast, T: fmt::Display> TypedStatement<'ast, T> {
                 }
                 writeln!(f, "{}endfor", "\t".repeat(depth))
             }
+            TypedStatement::IfElse(condition, consequence, alternative) => {
+                write!(f, "{}", "\t".repeat(depth))?;
+                writeln!(f, "if {} then", condition)?;
+                for s in consequence {
+                    s.fmt_indented(f, depth + 1)?;
+                    writeln!(f, "")?;
+                }
+                writeln!(f, "{}else", "\t".repeat(depth))?;
+                for s in alternative {
+                    s.fmt_indented(f, depth + 1)?;
+                    writeln!(f, "")?;
+                }
+                writeln!(f, "{}fi", "\t".repeat(depth))
+            }
             s => write!(f, "{}{}", "\t".repeat(depth), s),
         }
     }
@@ -386,7 +467,7 @@ impl<'ast, T: fmt::Display> fmt::Display for TypedStatement<'ast, T> {
             }
             TypedStatement::Declaration(ref var) => write!(f, "{}", var),
             TypedStatement::Definition(ref lhs, ref rhs) => write!(f, "{} = {}", lhs, rhs),
-            TypedStatement::Assertion(ref e) => write!(f, "assert({})", e),
+            TypedStatement::Assertion(ref e, _) => write!(f, "assert({})", e),
             TypedStatement::For(ref var, ref start, ref stop, ref list) => {
                 write!(f, "for {} in {}..{} do\n", var, start, stop)?;
                 for l in list {
@@ -394,6 +475,25 @@ impl<'ast, T: fmt::Display> fmt::Display for TypedStatement<'ast, T> {
                 }
                 write!(f, "\tendfor")
             }
+            TypedStatement::IfElse(ref condition, ref consequence, ref alternative) => {
+                write!(f, "if {} then\n", condition)?;
+                for l in consequence {
+                    write!(f, "\t\t{}\n", l)?;
+                }
+                write!(f, "\telse\n")?;
+                for l in alternative {
+                    write!(f, "\t\t{}\n", l)?;
+                }
+                write!(f, "\tfi")
+            }
+            TypedStatement::Break(ref condition) => match condition {
+                Some(condition) => write!(f, "break if {}", condition),
+                None => write!(f, "break"),
+            },
+            TypedStatement::Continue(ref condition) => match condition {
+                Some(condition) => write!(f, "continue if {}", condition),
+                None => write!(f, "continue"),
+            },
             TypedStatement::MultipleDefinition(ref ids, ref rhs) => {
                 for (i, id) in ids.iter().enumerate() {
                     write!(f, "{}", id)?;
@@ -403,6 +503,7 @@ impl<'ast, T: fmt::Display> fmt::Display for TypedStatement<'ast, T> {
                 }
                 write!(f, " = {}", rhs)
             }
+            TypedStatement::Origin(ref origin) => write!(f, "// {}", origin),
         }
     }
 }
@@ -412,13 +513,16 @@ pub trait Typed {
 }
 
 /// A typed expression
-#[derive(Clone, PartialEq, Hash, Eq)]
+#[derive(Clone, PartialEq, Hash, Eq, Serialize, Deserialize)]
+#[serde(bound(deserialize = "'de: 'ast, T: Field"))]
 pub enum TypedExpression<'ast, T> {
     Boolean(BooleanExpression<'ast, T>),
     FieldElement(FieldElementExpression<'ast, T>),
     Uint(UExpression<'ast, T>),
+    Int(UExpression<'ast, T>),
     Array(ArrayExpression<'ast, T>),
     Struct(StructExpression<'ast, T>),
+    Tuple(TupleExpression<'ast, T>),
 }
 
 impl<'ast, T> From<BooleanExpression<'ast, T>> for TypedExpression<'ast, T> {
@@ -451,14 +555,22 @@ impl<'ast, T> From<StructExpression<'ast, T>> for TypedExpression<'ast, T> {
     }
 }
 
+impl<'ast, T> From<TupleExpression<'ast, T>> for TypedExpression<'ast, T> {
+    fn from(e: TupleExpression<'ast, T>) -> TypedExpression<T> {
+        TypedExpression::Tuple(e)
+    }
+}
+
 impl<'ast, T: fmt::Display> fmt::Display for TypedExpression<'ast, T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
             TypedExpression::Boolean(ref e) => write!(f, "{}", e),
             TypedExpression::FieldElement(ref e) => write!(f, "{}", e),
             TypedExpression::Uint(ref e) => write!(f, "{}", e),
+            TypedExpression::Int(ref e) => write!(f, "{}", e),
             TypedExpression::Array(ref e) => write!(f, "{}", e),
             TypedExpression::Struct(ref s) => write!(f, "{}", s),
+            TypedExpression::Tuple(ref t) => write!(f, "{}", t),
         }
     }
 }
@@ -469,8 +581,10 @@ impl<'ast, T: fmt::Debug> fmt::Debug for TypedExpression<'ast, T> {
             TypedExpression::Boolean(ref e) => write!(f, "{:?}", e),
             TypedExpression::FieldElement(ref e) => write!(f, "{:?}", e),
             TypedExpression::Uint(ref e) => write!(f, "{:?}", e),
+            TypedExpression::Int(ref e) => write!(f, "{:?}", e),
             TypedExpression::Array(ref e) => write!(f, "{:?}", e),
             TypedExpression::Struct(ref s) => write!(f, "{:?}", s),
+            TypedExpression::Tuple(ref t) => write!(f, "{:?}", t),
         }
     }
 }
@@ -521,6 +635,9 @@ impl<'ast, T: fmt::Display> fmt::Display for StructExpression<'ast, T> {
             }
             StructExpressionInner::Member(ref struc, ref id) => write!(f, "{}.{}", struc, id),
             StructExpressionInner::Select(ref id, ref index) => write!(f, "{}[{}]", id, index),
+            StructExpressionInner::Element(ref tuple, ref index) => {
+                write!(f, "{}.{}", tuple, index)
+            }
         }
     }
 }
@@ -531,6 +648,54 @@ impl<'ast, T: fmt::Debug> fmt::Debug for StructExpression<'ast, T> {
     }
 }
 
+impl<'ast, T: fmt::Display> fmt::Display for TupleExpression<'ast, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.inner {
+            TupleExpressionInner::Identifier(ref var) => write!(f, "{}", var),
+            TupleExpressionInner::Value(ref values) => match values.len() {
+                1 => write!(f, "({},)", values[0]),
+                _ => write!(
+                    f,
+                    "({})",
+                    values
+                        .iter()
+                        .map(|v| v.to_string())
+                        .collect::<Vec<String>>()
+                        .join(", ")
+                ),
+            },
+            TupleExpressionInner::FunctionCall(ref key, ref p) => {
+                write!(f, "{}(", key.id,)?;
+                for (i, param) in p.iter().enumerate() {
+                    write!(f, "{}", param)?;
+                    if i < p.len() - 1 {
+                        write!(f, ", ")?;
+                    }
+                }
+                write!(f, ")")
+            }
+            TupleExpressionInner::IfElse(ref condition, ref consequent, ref alternative) => {
+                write!(
+                    f,
+                    "if {} then {} else {} fi",
+                    condition, consequent, alternative
+                )
+            }
+            TupleExpressionInner::Member(ref struc, ref id) => write!(f, "{}.{}", struc, id),
+            TupleExpressionInner::Select(ref id, ref index) => write!(f, "{}[{}]", id, index),
+            TupleExpressionInner::Element(ref tuple, ref index) => {
+                write!(f, "{}.{}", tuple, index)
+            }
+        }
+    }
+}
+
+impl<'ast, T: fmt::Debug> fmt::Debug for TupleExpression<'ast, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self.inner)
+    }
+}
+
 impl<'ast, T> Typed for TypedExpression<'ast, T> {
     fn get_type(&self) -> Type {
         match *self {
@@ -538,7 +703,9 @@ impl<'ast, T> Typed for TypedExpression<'ast, T> {
             TypedExpression::FieldElement(ref e) => e.get_type(),
             TypedExpression::Array(ref e) => e.get_type(),
             TypedExpression::Uint(ref e) => e.get_type(),
+            TypedExpression::Int(ref e) => Type::Int(e.bitwidth),
             TypedExpression::Struct(ref s) => s.get_type(),
+            TypedExpression::Tuple(ref t) => t.get_type(),
         }
     }
 }
@@ -555,6 +722,12 @@ impl<'ast, T> Typed for StructExpression<'ast, T> {
     }
 }
 
+impl<'ast, T> Typed for TupleExpression<'ast, T> {
+    fn get_type(&self) -> Type {
+        Type::Tuple(self.ty.clone())
+    }
+}
+
 impl<'ast, T> Typed for FieldElementExpression<'ast, T> {
     fn get_type(&self) -> Type {
         Type::FieldElement
@@ -577,7 +750,8 @@ pub trait MultiTyped {
     fn get_types(&self) -> &Vec<Type>;
 }
 
-#[derive(Clone, PartialEq, Hash, Eq)]
+#[derive(Clone, PartialEq, Hash, Eq, Serialize, Deserialize)]
+#[serde(bound(deserialize = "'de: 'ast, T: Field"))]
 pub enum TypedExpressionList<'ast, T> {
     FunctionCall(FunctionKey<'ast>, Vec<TypedExpression<'ast, T>>, Vec<Type>),
 }
@@ -591,7 +765,8 @@ impl<'ast, T> MultiTyped for TypedExpressionList<'ast, T> {
 }
 
 /// An expression of type `field`
-#[derive(Clone, PartialEq, Hash, Eq)]
+#[derive(Clone, PartialEq, Hash, Eq, Serialize, Deserialize)]
+#[serde(bound(deserialize = "'de: 'ast, T: Field"))]
 pub enum FieldElementExpression<'ast, T> {
     Number(T),
     Identifier(Identifier<'ast>),
@@ -626,10 +801,63 @@ pub enum FieldElementExpression<'ast, T> {
         Box<ArrayExpression<'ast, T>>,
         Box<FieldElementExpression<'ast, T>>,
     ),
+    Element(Box<TupleExpression<'ast, T>>, usize),
+}
+
+/// An order comparison between two expressions of the same type
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Comparison {
+    Lt,
+    Le,
+    Ge,
+    Gt,
+}
+
+impl Comparison {
+    /// Applies the comparison to two known values
+    pub fn compare<V: PartialOrd>(self, left: &V, right: &V) -> bool {
+        match self {
+            Comparison::Lt => left < right,
+            Comparison::Le => left <= right,
+            Comparison::Ge => left >= right,
+            Comparison::Gt => left > right,
+        }
+    }
+}
+
+impl<'ast, T> BooleanExpression<'ast, T> {
+    /// Returns the comparison `op` of two field elements
+    pub fn field_comparison(
+        op: Comparison,
+        left: FieldElementExpression<'ast, T>,
+        right: FieldElementExpression<'ast, T>,
+    ) -> Self {
+        match op {
+            Comparison::Lt => BooleanExpression::Lt(box left, box right),
+            Comparison::Le => BooleanExpression::Le(box left, box right),
+            Comparison::Ge => BooleanExpression::Ge(box left, box right),
+            Comparison::Gt => BooleanExpression::Gt(box left, box right),
+        }
+    }
+
+    /// Returns the comparison `op` of two unsigned integers
+    pub fn uint_comparison(
+        op: Comparison,
+        left: UExpression<'ast, T>,
+        right: UExpression<'ast, T>,
+    ) -> Self {
+        match op {
+            Comparison::Lt => BooleanExpression::UintLt(box left, box right),
+            Comparison::Le => BooleanExpression::UintLe(box left, box right),
+            Comparison::Ge => BooleanExpression::UintGe(box left, box right),
+            Comparison::Gt => BooleanExpression::UintGt(box left, box right),
+        }
+    }
 }
 
 /// An expression of type `bool`
-#[derive(Clone, PartialEq, Hash, Eq)]
+#[derive(Clone, PartialEq, Hash, Eq, Serialize, Deserialize)]
+#[serde(bound(deserialize = "'de: 'ast, T: Field"))]
 pub enum BooleanExpression<'ast, T> {
     Identifier(Identifier<'ast>),
     Value(bool),
@@ -655,6 +883,10 @@ pub enum BooleanExpression<'ast, T> {
         Box<StructExpression<'ast, T>>,
     ),
     UintEq(Box<UExpression<'ast, T>>, Box<UExpression<'ast, T>>),
+    UintLt(Box<UExpression<'ast, T>>, Box<UExpression<'ast, T>>),
+    UintLe(Box<UExpression<'ast, T>>, Box<UExpression<'ast, T>>),
+    UintGe(Box<UExpression<'ast, T>>, Box<UExpression<'ast, T>>),
+    UintGt(Box<UExpression<'ast, T>>, Box<UExpression<'ast, T>>),
     Ge(
         Box<FieldElementExpression<'ast, T>>,
         Box<FieldElementExpression<'ast, T>>,
@@ -683,6 +915,7 @@ pub enum BooleanExpression<'ast, T> {
         Box<ArrayExpression<'ast, T>>,
         Box<FieldElementExpression<'ast, T>>,
     ),
+    Element(Box<TupleExpression<'ast, T>>, usize),
 }
 
 /// An expression of type `array`
@@ -690,14 +923,16 @@ pub enum BooleanExpression<'ast, T> {
 /// * Contrary to basic types which are represented as enums, we wrap an enum `ArrayExpressionInner` in a struct in order to keep track of the type (content and size)
 /// of the array. Only using an enum would require generics, which would propagate up to TypedExpression which we want to keep simple, hence this "runtime"
 /// type checking
-#[derive(Clone, PartialEq, Hash, Eq)]
+#[derive(Clone, PartialEq, Hash, Eq, Serialize, Deserialize)]
+#[serde(bound(deserialize = "'de: 'ast, T: Field"))]
 pub struct ArrayExpression<'ast, T> {
     size: usize,
     ty: Type,
     inner: ArrayExpressionInner<'ast, T>,
 }
 
-#[derive(Clone, PartialEq, Hash, Eq)]
+#[derive(Clone, PartialEq, Hash, Eq, Serialize, Deserialize)]
+#[serde(bound(deserialize = "'de: 'ast, T: Field"))]
 pub enum ArrayExpressionInner<'ast, T> {
     Identifier(Identifier<'ast>),
     Value(Vec<TypedExpression<'ast, T>>),
@@ -712,6 +947,7 @@ pub enum ArrayExpressionInner<'ast, T> {
         Box<ArrayExpression<'ast, T>>,
         Box<FieldElementExpression<'ast, T>>,
     ),
+    Element(Box<TupleExpression<'ast, T>>, usize),
 }
 
 impl<'ast, T> ArrayExpressionInner<'ast, T> {
@@ -742,7 +978,8 @@ impl<'ast, T> ArrayExpression<'ast, T> {
     }
 }
 
-#[derive(Clone, PartialEq, Hash, Eq)]
+#[derive(Clone, PartialEq, Hash, Eq, Serialize, Deserialize)]
+#[serde(bound(deserialize = "'de: 'ast, T: Field"))]
 pub struct StructExpression<'ast, T> {
     ty: StructType,
     inner: StructExpressionInner<'ast, T>,
@@ -762,7 +999,8 @@ impl<'ast, T> StructExpression<'ast, T> {
     }
 }
 
-#[derive(Clone, PartialEq, Hash, Eq)]
+#[derive(Clone, PartialEq, Hash, Eq, Serialize, Deserialize)]
+#[serde(bound(deserialize = "'de: 'ast, T: Field"))]
 pub enum StructExpressionInner<'ast, T> {
     Identifier(Identifier<'ast>),
     Value(Vec<TypedExpression<'ast, T>>),
@@ -777,6 +1015,7 @@ pub enum StructExpressionInner<'ast, T> {
         Box<ArrayExpression<'ast, T>>,
         Box<FieldElementExpression<'ast, T>>,
     ),
+    Element(Box<TupleExpression<'ast, T>>, usize),
 }
 
 impl<'ast, T> StructExpressionInner<'ast, T> {
@@ -785,6 +1024,52 @@ impl<'ast, T> StructExpressionInner<'ast, T> {
     }
 }
 
+#[derive(Clone, PartialEq, Hash, Eq, Serialize, Deserialize)]
+#[serde(bound(deserialize = "'de: 'ast, T: Field"))]
+pub struct TupleExpression<'ast, T> {
+    ty: TupleType,
+    inner: TupleExpressionInner<'ast, T>,
+}
+
+impl<'ast, T> TupleExpression<'ast, T> {
+    pub fn ty(&self) -> &TupleType {
+        &self.ty
+    }
+
+    pub fn as_inner(&self) -> &TupleExpressionInner<'ast, T> {
+        &self.inner
+    }
+
+    pub fn into_inner(self) -> TupleExpressionInner<'ast, T> {
+        self.inner
+    }
+}
+
+#[derive(Clone, PartialEq, Hash, Eq, Serialize, Deserialize)]
+#[serde(bound(deserialize = "'de: 'ast, T: Field"))]
+pub enum TupleExpressionInner<'ast, T> {
+    Identifier(Identifier<'ast>),
+    Value(Vec<TypedExpression<'ast, T>>),
+    FunctionCall(FunctionKey<'ast>, Vec<TypedExpression<'ast, T>>),
+    IfElse(
+        Box<BooleanExpression<'ast, T>>,
+        Box<TupleExpression<'ast, T>>,
+        Box<TupleExpression<'ast, T>>,
+    ),
+    Member(Box<StructExpression<'ast, T>>, MemberId),
+    Select(
+        Box<ArrayExpression<'ast, T>>,
+        Box<FieldElementExpression<'ast, T>>,
+    ),
+    Element(Box<TupleExpression<'ast, T>>, usize),
+}
+
+impl<'ast, T> TupleExpressionInner<'ast, T> {
+    pub fn annotate(self, ty: TupleType) -> TupleExpression<'ast, T> {
+        TupleExpression { ty, inner: self }
+    }
+}
+
 // Downcasts
 // Due to the fact that we keep TypedExpression simple, we end up with ArrayExpressionInner::Value whose elements are any TypedExpression, but we enforce by
 // construction that these elements are of the type declared in the corresponding ArrayExpression. As we know this by construction, we can downcast the TypedExpression to the correct type
@@ -846,6 +1131,17 @@ impl<'ast, T> TryFrom<TypedExpression<'ast, T>> for StructExpression<'ast, T> {
     }
 }
 
+impl<'ast, T> TryFrom<TypedExpression<'ast, T>> for TupleExpression<'ast, T> {
+    type Error = ();
+
+    fn try_from(te: TypedExpression<'ast, T>) -> Result<TupleExpression<'ast, T>, Self::Error> {
+        match te {
+            TypedExpression::Tuple(e) => Ok(e),
+            _ => Err(()),
+        }
+    }
+}
+
 impl<'ast, T: fmt::Display> fmt::Display for FieldElementExpression<'ast, T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
@@ -875,6 +1171,9 @@ impl<'ast, T: fmt::Display> fmt::Display for FieldElementExpression<'ast, T> {
             }
             FieldElementExpression::Member(ref struc, ref id) => write!(f, "{}.{}", struc, id),
             FieldElementExpression::Select(ref id, ref index) => write!(f, "{}[{}]", id, index),
+            FieldElementExpression::Element(ref tuple, ref index) => {
+                write!(f, "{}.{}", tuple, index)
+            }
         }
     }
 }
@@ -890,6 +1189,8 @@ impl<'ast, T: fmt::Display> fmt::Display for UExpression<'ast, T> {
             UExpressionInner::Xor(ref lhs, ref rhs) => write!(f, "({} ^ {})", lhs, rhs),
             UExpressionInner::Sub(ref lhs, ref rhs) => write!(f, "({} - {})", lhs, rhs),
             UExpressionInner::Mult(ref lhs, ref rhs) => write!(f, "({} * {})", lhs, rhs),
+            UExpressionInner::Div(ref lhs, ref rhs) => write!(f, "({} / {})", lhs, rhs),
+            UExpressionInner::Rem(ref lhs, ref rhs) => write!(f, "({} % {})", lhs, rhs),
             UExpressionInner::RightShift(ref e, ref by) => write!(f, "({} >> {})", e, by),
             UExpressionInner::LeftShift(ref e, ref by) => write!(f, "({} << {})", e, by),
             UExpressionInner::Not(ref e) => write!(f, "!{}", e),
@@ -910,6 +1211,7 @@ impl<'ast, T: fmt::Display> fmt::Display for UExpression<'ast, T> {
                 condition, consequent, alternative
             ),
             UExpressionInner::Member(ref struc, ref id) => write!(f, "{}.{}", struc, id),
+            UExpressionInner::Element(ref tuple, ref index) => write!(f, "{}.{}", tuple, index),
         }
     }
 }
@@ -925,6 +1227,10 @@ impl<'ast, T: fmt::Display> fmt::Display for BooleanExpression<'ast, T> {
             BooleanExpression::ArrayEq(ref lhs, ref rhs) => write!(f, "{} == {}", lhs, rhs),
             BooleanExpression::StructEq(ref lhs, ref rhs) => write!(f, "{} == {}", lhs, rhs),
             BooleanExpression::UintEq(ref lhs, ref rhs) => write!(f, "{} == {}", lhs, rhs),
+            BooleanExpression::UintLt(ref lhs, ref rhs) => write!(f, "{} < {}", lhs, rhs),
+            BooleanExpression::UintLe(ref lhs, ref rhs) => write!(f, "{} <= {}", lhs, rhs),
+            BooleanExpression::UintGe(ref lhs, ref rhs) => write!(f, "{} >= {}", lhs, rhs),
+            BooleanExpression::UintGt(ref lhs, ref rhs) => write!(f, "{} > {}", lhs, rhs),
             BooleanExpression::Ge(ref lhs, ref rhs) => write!(f, "{} >= {}", lhs, rhs),
             BooleanExpression::Gt(ref lhs, ref rhs) => write!(f, "{} > {}", lhs, rhs),
             BooleanExpression::Or(ref lhs, ref rhs) => write!(f, "{} || {}", lhs, rhs),
@@ -948,6 +1254,7 @@ impl<'ast, T: fmt::Display> fmt::Display for BooleanExpression<'ast, T> {
             ),
             BooleanExpression::Member(ref struc, ref id) => write!(f, "{}.{}", struc, id),
             BooleanExpression::Select(ref id, ref index) => write!(f, "{}[{}]", id, index),
+            BooleanExpression::Element(ref tuple, ref index) => write!(f, "{}.{}", tuple, index),
         }
     }
 }
@@ -982,6 +1289,9 @@ impl<'ast, T: fmt::Display> fmt::Display for ArrayExpressionInner<'ast, T> {
             ),
             ArrayExpressionInner::Member(ref s, ref id) => write!(f, "{}.{}", s, id),
             ArrayExpressionInner::Select(ref id, ref index) => write!(f, "{}[{}]", id, index),
+            ArrayExpressionInner::Element(ref tuple, ref index) => {
+                write!(f, "{}.{}", tuple, index)
+            }
         }
     }
 }
@@ -1013,6 +1323,18 @@ impl<'ast, T: fmt::Debug> fmt::Debug for BooleanExpression<'ast, T> {
             BooleanExpression::UintEq(ref lhs, ref rhs) => {
                 write!(f, "UintEq({:?}, {:?})", lhs, rhs)
             }
+            BooleanExpression::UintLt(ref lhs, ref rhs) => {
+                write!(f, "UintLt({:?}, {:?})", lhs, rhs)
+            }
+            BooleanExpression::UintLe(ref lhs, ref rhs) => {
+                write!(f, "UintLe({:?}, {:?})", lhs, rhs)
+            }
+            BooleanExpression::UintGe(ref lhs, ref rhs) => {
+                write!(f, "UintGe({:?}, {:?})", lhs, rhs)
+            }
+            BooleanExpression::UintGt(ref lhs, ref rhs) => {
+                write!(f, "UintGt({:?}, {:?})", lhs, rhs)
+            }
             BooleanExpression::Ge(ref lhs, ref rhs) => write!(f, "Ge({:?}, {:?})", lhs, rhs),
             BooleanExpression::Gt(ref lhs, ref rhs) => write!(f, "Gt({:?}, {:?})", lhs, rhs),
             BooleanExpression::And(ref lhs, ref rhs) => write!(f, "And({:?}, {:?})", lhs, rhs),
@@ -1028,6 +1350,9 @@ impl<'ast, T: fmt::Debug> fmt::Debug for BooleanExpression<'ast, T> {
             BooleanExpression::Member(ref struc, ref id) => {
                 write!(f, "Access({:?}, {:?})", struc, id)
             }
+            BooleanExpression::Element(ref tuple, ref index) => {
+                write!(f, "Element({:?}, {:?})", tuple, index)
+            }
             BooleanExpression::Or(ref lhs, ref rhs) => write!(f, "Or({:?}, {:?})", lhs, rhs),
         }
     }
@@ -1060,6 +1385,9 @@ impl<'ast, T: fmt::Debug> fmt::Debug for FieldElementExpression<'ast, T> {
             FieldElementExpression::Member(ref struc, ref id) => {
                 write!(f, "Member({:?}, {:?})", struc, id)
             }
+            FieldElementExpression::Element(ref tuple, ref index) => {
+                write!(f, "Element({:?}, {:?})", tuple, index)
+            }
             FieldElementExpression::Select(ref id, ref index) => {
                 write!(f, "Select({:?}, {:?})", id, index)
             }
@@ -1085,6 +1413,9 @@ impl<'ast, T: fmt::Debug> fmt::Debug for ArrayExpressionInner<'ast, T> {
             ArrayExpressionInner::Member(ref struc, ref id) => {
                 write!(f, "Member({:?}, {:?})", struc, id)
             }
+            ArrayExpressionInner::Element(ref tuple, ref index) => {
+                write!(f, "Element({:?}, {:?})", tuple, index)
+            }
             ArrayExpressionInner::Select(ref id, ref index) => {
                 write!(f, "Select({:?}, {:?})", id, index)
             }
@@ -1115,6 +1446,39 @@ impl<'ast, T: fmt::Debug> fmt::Debug for StructExpressionInner<'ast, T> {
             StructExpressionInner::Select(ref id, ref index) => {
                 write!(f, "Select({:?}, {:?})", id, index)
             }
+            StructExpressionInner::Element(ref tuple, ref index) => {
+                write!(f, "Element({:?}, {:?})", tuple, index)
+            }
+        }
+    }
+}
+
+impl<'ast, T: fmt::Debug> fmt::Debug for TupleExpressionInner<'ast, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            TupleExpressionInner::Identifier(ref var) => write!(f, "{:?}", var),
+            TupleExpressionInner::Value(ref values) => write!(f, "{:?}", values),
+            TupleExpressionInner::FunctionCall(ref i, ref p) => {
+                write!(f, "FunctionCall({:?}, (", i)?;
+                f.debug_list().entries(p.iter()).finish()?;
+                write!(f, ")")
+            }
+            TupleExpressionInner::IfElse(ref condition, ref consequent, ref alternative) => {
+                write!(
+                    f,
+                    "IfElse({:?}, {:?}, {:?})",
+                    condition, consequent, alternative
+                )
+            }
+            TupleExpressionInner::Member(ref struc, ref id) => {
+                write!(f, "Member({:?}, {:?})", struc, id)
+            }
+            TupleExpressionInner::Select(ref id, ref index) => {
+                write!(f, "Select({:?}, {:?})", id, index)
+            }
+            TupleExpressionInner::Element(ref tuple, ref index) => {
+                write!(f, "Element({:?}, {:?})", tuple, index)
+            }
         }
     }
 }
@@ -1211,6 +1575,17 @@ impl<'ast, T> IfElse<'ast, T> for StructExpression<'ast, T> {
     }
 }
 
+impl<'ast, T> IfElse<'ast, T> for TupleExpression<'ast, T> {
+    fn if_else(
+        condition: BooleanExpression<'ast, T>,
+        consequence: Self,
+        alternative: Self,
+    ) -> Self {
+        let ty = consequence.ty().clone();
+        TupleExpressionInner::IfElse(box condition, box consequence, box alternative).annotate(ty)
+    }
+}
+
 pub trait Select<'ast, T> {
     fn select(array: ArrayExpression<'ast, T>, index: FieldElementExpression<'ast, T>) -> Self;
 }
@@ -1230,7 +1605,7 @@ impl<'ast, T> Select<'ast, T> for BooleanExpression<'ast, T> {
 impl<'ast, T> Select<'ast, T> for UExpression<'ast, T> {
     fn select(array: ArrayExpression<'ast, T>, index: FieldElementExpression<'ast, T>) -> Self {
         let bitwidth = match array.inner_type().clone() {
-            Type::Uint(bitwidth) => bitwidth,
+            Type::Uint(bitwidth) | Type::Int(bitwidth) => bitwidth,
             _ => unreachable!(),
         };
 
@@ -1260,6 +1635,17 @@ impl<'ast, T> Select<'ast, T> for StructExpression<'ast, T> {
     }
 }
 
+impl<'ast, T> Select<'ast, T> for TupleExpression<'ast, T> {
+    fn select(array: ArrayExpression<'ast, T>, index: FieldElementExpression<'ast, T>) -> Self {
+        let elements = match array.inner_type().clone() {
+            Type::Tuple(elements) => elements,
+            _ => unreachable!(),
+        };
+
+        TupleExpressionInner::Select(box array, box index).annotate(elements)
+    }
+}
+
 pub trait Member<'ast, T> {
     fn member(s: StructExpression<'ast, T>, member_id: MemberId) -> Self;
 }
@@ -1287,7 +1673,7 @@ impl<'ast, T> Member<'ast, T> for UExpression<'ast, T> {
             .ty;
 
         let bitwidth = match *ty {
-            Type::Uint(bitwidth) => bitwidth,
+            Type::Uint(bitwidth) | Type::Int(bitwidth) => bitwidth,
             _ => unreachable!(),
         };
 
@@ -1332,3 +1718,82 @@ impl<'ast, T> Member<'ast, T> for StructExpression<'ast, T> {
         StructExpressionInner::Member(box s, member_id).annotate(members)
     }
 }
+
+impl<'ast, T> Member<'ast, T> for TupleExpression<'ast, T> {
+    fn member(s: StructExpression<'ast, T>, member_id: MemberId) -> Self {
+        let members = s.ty().clone();
+
+        let ty = members
+            .into_iter()
+            .find(|member| *member.id == member_id)
+            .unwrap()
+            .ty;
+
+        let elements = match *ty {
+            Type::Tuple(elements) => elements,
+            _ => unreachable!(),
+        };
+
+        TupleExpressionInner::Member(box s, member_id).annotate(elements)
+    }
+}
+
+pub trait Element<'ast, T> {
+    fn element(t: TupleExpression<'ast, T>, index: usize) -> Self;
+}
+
+impl<'ast, T> Element<'ast, T> for FieldElementExpression<'ast, T> {
+    fn element(t: TupleExpression<'ast, T>, index: usize) -> Self {
+        FieldElementExpression::Element(box t, index)
+    }
+}
+
+impl<'ast, T> Element<'ast, T> for BooleanExpression<'ast, T> {
+    fn element(t: TupleExpression<'ast, T>, index: usize) -> Self {
+        BooleanExpression::Element(box t, index)
+    }
+}
+
+impl<'ast, T> Element<'ast, T> for UExpression<'ast, T> {
+    fn element(t: TupleExpression<'ast, T>, index: usize) -> Self {
+        let bitwidth = match t.ty().elements[index] {
+            Type::Uint(bitwidth) | Type::Int(bitwidth) => bitwidth,
+            _ => unreachable!(),
+        };
+
+        UExpressionInner::Element(box t, index).annotate(bitwidth)
+    }
+}
+
+impl<'ast, T> Element<'ast, T> for ArrayExpression<'ast, T> {
+    fn element(t: TupleExpression<'ast, T>, index: usize) -> Self {
+        let (ty, size) = match t.ty().elements[index].clone() {
+            Type::Array(array_type) => (array_type.ty, array_type.size),
+            _ => unreachable!(),
+        };
+
+        ArrayExpressionInner::Element(box t, index).annotate(*ty, size)
+    }
+}
+
+impl<'ast, T> Element<'ast, T> for StructExpression<'ast, T> {
+    fn element(t: TupleExpression<'ast, T>, index: usize) -> Self {
+        let members = match t.ty().elements[index].clone() {
+            Type::Struct(members) => members,
+            _ => unreachable!(),
+        };
+
+        StructExpressionInner::Element(box t, index).annotate(members)
+    }
+}
+
+impl<'ast, T> Element<'ast, T> for TupleExpression<'ast, T> {
+    fn element(t: TupleExpression<'ast, T>, index: usize) -> Self {
+        let elements = match t.ty().elements[index].clone() {
+            Type::Tuple(elements) => elements,
+            _ => unreachable!(),
+        };
+
+        TupleExpressionInner::Element(box t, index).annotate(elements)
+    }
+}
@@ -1,4 +1,5 @@
 use std::fmt;
+use std::hash::{Hash, Hasher};
 use std::path::PathBuf;
 
 pub type Identifier<'ast> = &'ast str;
@@ -20,7 +21,44 @@ pub struct ArrayType {
     pub ty: Box<Type>,
 }
 
-#[derive(Clone, Hash, Serialize, Deserialize, PartialOrd, Ord)]
+#[derive(Clone, PartialEq, Eq, Hash, Serialize, Deserialize, PartialOrd, Ord)]
+pub struct TupleType {
+    pub elements: Vec<Type>,
+}
+
+impl TupleType {
+    pub fn new(elements: Vec<Type>) -> Self {
+        TupleType { elements }
+    }
+
+    pub fn len(&self) -> usize {
+        self.elements.len()
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<Type> {
+        self.elements.iter()
+    }
+}
+
+impl fmt::Display for TupleType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.elements.len() {
+            // a tuple of a single element needs a trailing comma to differ from a parenthesized type
+            1 => write!(f, "({},)", self.elements[0]),
+            _ => write!(
+                f,
+                "({})",
+                self.elements
+                    .iter()
+                    .map(|ty| ty.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize, PartialOrd, Ord)]
 pub struct StructType {
     #[serde(skip)]
     pub module: PathBuf,
@@ -36,6 +74,13 @@ impl PartialEq for StructType {
 
 impl Eq for StructType {}
 
+/// Only the members are hashed, as they are the only part of struct types which is compared
+impl Hash for StructType {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.members.hash(state);
+    }
+}
+
 impl StructType {
     pub fn new(module: PathBuf, name: String, members: Vec<StructMember>) -> Self {
         StructType {
@@ -71,6 +116,8 @@ pub enum UBitwidth {
     B16 = 16,
     #[serde(rename = "32")]
     B32 = 32,
+    #[serde(rename = "64")]
+    B64 = 64,
 }
 
 impl UBitwidth {
@@ -85,6 +132,7 @@ impl From<usize> for UBitwidth {
             8 => UBitwidth::B8,
             16 => UBitwidth::B16,
             32 => UBitwidth::B32,
+            64 => UBitwidth::B64,
             _ => unreachable!(),
         }
     }
@@ -107,8 +155,12 @@ pub enum Type {
     Array(ArrayType),
     #[serde(rename = "struct")]
     Struct(StructType),
+    #[serde(rename = "tuple")]
+    Tuple(TupleType),
     #[serde(rename = "u")]
     Uint(UBitwidth),
+    #[serde(rename = "i")]
+    Int(UBitwidth),
 }
 
 impl ArrayType {
@@ -135,6 +187,7 @@ impl fmt::Display for Type {
             Type::FieldElement => write!(f, "field"),
             Type::Boolean => write!(f, "bool"),
             Type::Uint(ref bitwidth) => write!(f, "u{}", bitwidth),
+            Type::Int(ref bitwidth) => write!(f, "i{}", bitwidth),
             Type::Array(ref array_type) => write!(f, "{}[{}]", array_type.ty, array_type.size),
             Type::Struct(ref struct_type) => write!(
                 f,
@@ -147,6 +200,7 @@ impl fmt::Display for Type {
                     .collect::<Vec<_>>()
                     .join(", ")
             ),
+            Type::Tuple(ref tuple_type) => write!(f, "{}", tuple_type),
         }
     }
 }
@@ -157,6 +211,7 @@ impl fmt::Debug for Type {
             Type::FieldElement => write!(f, "field"),
             Type::Boolean => write!(f, "bool"),
             Type::Uint(ref bitwidth) => write!(f, "u{}", bitwidth),
+            Type::Int(ref bitwidth) => write!(f, "i{}", bitwidth),
             Type::Array(ref array_type) => write!(f, "{}[{}]", array_type.ty, array_type.size),
             Type::Struct(ref struct_type) => write!(
                 f,
@@ -169,6 +224,7 @@ impl fmt::Debug for Type {
                     .collect::<Vec<_>>()
                     .join(", ")
             ),
+            Type::Tuple(ref tuple_type) => write!(f, "{}", tuple_type),
         }
     }
 }
@@ -182,15 +238,24 @@ impl Type {
         Type::Struct(struct_ty)
     }
 
+    pub fn tuple(elements: Vec<Type>) -> Self {
+        Type::Tuple(TupleType::new(elements))
+    }
+
     pub fn uint<W: Into<UBitwidth>>(b: W) -> Self {
         Type::Uint(b.into())
     }
 
+    pub fn int<W: Into<UBitwidth>>(b: W) -> Self {
+        Type::Int(b.into())
+    }
+
     fn to_slug(&self) -> String {
         match self {
             Type::FieldElement => String::from("f"),
             Type::Boolean => String::from("b"),
             Type::Uint(bitwidth) => format!("u{}", bitwidth),
+            Type::Int(bitwidth) => format!("i{}", bitwidth),
             Type::Array(array_type) => format!("{}[{}]", array_type.ty.to_slug(), array_type.size),
             Type::Struct(struct_type) => format!(
                 "{{{}}}",
@@ -200,6 +265,14 @@ impl Type {
                     .collect::<Vec<_>>()
                     .join(",")
             ),
+            Type::Tuple(tuple_type) => format!(
+                "({})",
+                tuple_type
+                    .iter()
+                    .map(|ty| ty.to_slug())
+                    .collect::<Vec<_>>()
+                    .join(",")
+            ),
         }
     }
 
@@ -209,18 +282,21 @@ impl Type {
             Type::FieldElement => 1,
             Type::Boolean => 1,
             Type::Uint(_) => 1,
+            Type::Int(_) => 1,
             Type::Array(array_type) => array_type.size * array_type.ty.get_primitive_count(),
             Type::Struct(struct_type) => struct_type
                 .iter()
                 .map(|member| member.ty.get_primitive_count())
                 .sum(),
+            Type::Tuple(tuple_type) => tuple_type.iter().map(|ty| ty.get_primitive_count()).sum(),
         }
     }
 }
 
 pub type FunctionIdentifier<'ast> = &'ast str;
 
-#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Serialize, Deserialize)]
+#[serde(bound(deserialize = "'de: 'ast"))]
 pub struct FunctionKey<'ast> {
     pub id: FunctionIdentifier<'ast>,
     pub signature: Signature,
@@ -430,4 +506,15 @@ mod tests {
         let t = Type::Array(ArrayType::new(Type::FieldElement, 42));
         assert_eq!(t.get_primitive_count(), 42);
     }
+
+    #[test]
+    fn tuple() {
+        let t = Type::tuple(vec![Type::FieldElement, Type::array(Type::Boolean, 3)]);
+        assert_eq!(t.get_primitive_count(), 4);
+        assert_eq!(t.to_string(), "(field, bool[3])");
+        assert_eq!(
+            Type::tuple(vec![Type::FieldElement]).to_string(),
+            "(field,)"
+        );
+    }
 }
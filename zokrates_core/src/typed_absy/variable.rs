@@ -3,7 +3,8 @@ use crate::typed_absy::Identifier;
 use std::fmt;
 use typed_absy::types::{StructType, UBitwidth};
 
-#[derive(Clone, PartialEq, Hash, Eq)]
+#[derive(Clone, PartialEq, Hash, Eq, Serialize, Deserialize)]
+#[serde(bound(deserialize = "'de: 'ast"))]
 pub struct Variable<'ast> {
     pub id: Identifier<'ast>,
     pub _type: Type,
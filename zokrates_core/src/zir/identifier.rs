@@ -14,6 +14,7 @@ pub enum SourceIdentifier<'ast> {
     Basic(CoreIdentifier<'ast>),
     Select(Box<SourceIdentifier<'ast>>, usize),
     Member(Box<SourceIdentifier<'ast>>, MemberId),
+    Element(Box<SourceIdentifier<'ast>>, usize),
 }
 
 impl<'ast> fmt::Display for SourceIdentifier<'ast> {
@@ -22,6 +23,7 @@ impl<'ast> fmt::Display for SourceIdentifier<'ast> {
             SourceIdentifier::Basic(i) => write!(f, "{}", i),
             SourceIdentifier::Select(box i, index) => write!(f, "{}~{}", i, index),
             SourceIdentifier::Member(box i, m) => write!(f, "{}.{}", i, m),
+            SourceIdentifier::Element(box i, index) => write!(f, "{}.{}", i, index),
         }
     }
 }
@@ -12,7 +12,10 @@ pub use self::variable::Variable;
 use std::path::PathBuf;
 pub use zir::uint::{ShouldReduce, UExpression, UExpressionInner, UMetadata};
 
+pub use typed_absy::Comparison;
+
 use embed::FlatEmbed;
+use source_map::{Origin, SourceMetadata};
 use std::collections::HashMap;
 use std::convert::TryFrom;
 use std::fmt;
@@ -191,8 +194,10 @@ pub enum ZirStatement<'ast, T> {
     Return(Vec<ZirExpression<'ast, T>>),
     Definition(ZirAssignee<'ast>, ZirExpression<'ast, T>),
     Declaration(Variable<'ast>),
-    Assertion(BooleanExpression<'ast, T>),
+    Assertion(BooleanExpression<'ast, T>, Option<SourceMetadata>),
     MultipleDefinition(Vec<Variable<'ast>>, ZirExpressionList<'ast, T>),
+    /// Marks the origin of the statements which follow it, when profiling
+    Origin(Origin),
 }
 
 impl<'ast, T: fmt::Debug> fmt::Debug for ZirStatement<'ast, T> {
@@ -212,10 +217,13 @@ impl<'ast, T: fmt::Debug> fmt::Debug for ZirStatement<'ast, T> {
             ZirStatement::Definition(ref lhs, ref rhs) => {
                 write!(f, "Definition({:?}, {:?})", lhs, rhs)
             }
-            ZirStatement::Assertion(ref e) => write!(f, "Assertion({:?})", e),
+            ZirStatement::Assertion(ref e, ref metadata) => {
+                write!(f, "Assertion({:?}, {:?})", e, metadata)
+            }
             ZirStatement::MultipleDefinition(ref lhs, ref rhs) => {
                 write!(f, "MultipleDefinition({:?}, {:?})", lhs, rhs)
             }
+            ZirStatement::Origin(ref origin) => write!(f, "Origin({:?})", origin),
         }
     }
 }
@@ -235,7 +243,7 @@ impl<'ast, T: fmt::Display> fmt::Display for ZirStatement<'ast, T> {
             }
             ZirStatement::Declaration(ref var) => write!(f, "assert({})", var),
             ZirStatement::Definition(ref lhs, ref rhs) => write!(f, "{} = {}", lhs, rhs),
-            ZirStatement::Assertion(ref e) => write!(f, "{}", e),
+            ZirStatement::Assertion(ref e, _) => write!(f, "{}", e),
             ZirStatement::MultipleDefinition(ref ids, ref rhs) => {
                 for (i, id) in ids.iter().enumerate() {
                     write!(f, "{}", id)?;
@@ -245,6 +253,7 @@ impl<'ast, T: fmt::Display> fmt::Display for ZirStatement<'ast, T> {
                 }
                 write!(f, " = {}", rhs)
             }
+            ZirStatement::Origin(ref origin) => write!(f, "// {}", origin),
         }
     }
 }
@@ -376,6 +385,22 @@ pub enum FieldElementExpression<'ast, T> {
     ),
 }
 
+impl<'ast, T> BooleanExpression<'ast, T> {
+    /// Returns the comparison `op` of two unsigned integers
+    pub fn uint_comparison(
+        op: Comparison,
+        left: UExpression<'ast, T>,
+        right: UExpression<'ast, T>,
+    ) -> Self {
+        match op {
+            Comparison::Lt => BooleanExpression::UintLt(box left, box right),
+            Comparison::Le => BooleanExpression::UintLe(box left, box right),
+            Comparison::Ge => BooleanExpression::UintGe(box left, box right),
+            Comparison::Gt => BooleanExpression::UintGt(box left, box right),
+        }
+    }
+}
+
 /// An expression of type `bool`
 #[derive(Clone, PartialEq, Hash, Eq)]
 pub enum BooleanExpression<'ast, T> {
@@ -398,6 +423,10 @@ pub enum BooleanExpression<'ast, T> {
         Box<BooleanExpression<'ast, T>>,
     ),
     UintEq(Box<UExpression<'ast, T>>, Box<UExpression<'ast, T>>),
+    UintLt(Box<UExpression<'ast, T>>, Box<UExpression<'ast, T>>),
+    UintLe(Box<UExpression<'ast, T>>, Box<UExpression<'ast, T>>),
+    UintGe(Box<UExpression<'ast, T>>, Box<UExpression<'ast, T>>),
+    UintGt(Box<UExpression<'ast, T>>, Box<UExpression<'ast, T>>),
     Ge(
         Box<FieldElementExpression<'ast, T>>,
         Box<FieldElementExpression<'ast, T>>,
@@ -487,6 +516,8 @@ impl<'ast, T: fmt::Display> fmt::Display for UExpression<'ast, T> {
             UExpressionInner::Add(ref lhs, ref rhs) => write!(f, "({} + {})", lhs, rhs),
             UExpressionInner::Sub(ref lhs, ref rhs) => write!(f, "({} - {})", lhs, rhs),
             UExpressionInner::Mult(ref lhs, ref rhs) => write!(f, "({} * {})", lhs, rhs),
+            UExpressionInner::Div(ref lhs, ref rhs) => write!(f, "({} / {})", lhs, rhs),
+            UExpressionInner::Rem(ref lhs, ref rhs) => write!(f, "({} % {})", lhs, rhs),
             UExpressionInner::Xor(ref lhs, ref rhs) => write!(f, "({} ^ {})", lhs, rhs),
             UExpressionInner::And(ref lhs, ref rhs) => write!(f, "({} & {})", lhs, rhs),
             UExpressionInner::Or(ref lhs, ref rhs) => write!(f, "({} | {})", lhs, rhs),
@@ -511,6 +542,10 @@ impl<'ast, T: fmt::Display> fmt::Display for BooleanExpression<'ast, T> {
             BooleanExpression::FieldEq(ref lhs, ref rhs) => write!(f, "{} == {}", lhs, rhs),
             BooleanExpression::BoolEq(ref lhs, ref rhs) => write!(f, "{} == {}", lhs, rhs),
             BooleanExpression::UintEq(ref lhs, ref rhs) => write!(f, "{} == {}", lhs, rhs),
+            BooleanExpression::UintLt(ref lhs, ref rhs) => write!(f, "{} < {}", lhs, rhs),
+            BooleanExpression::UintLe(ref lhs, ref rhs) => write!(f, "{} <= {}", lhs, rhs),
+            BooleanExpression::UintGe(ref lhs, ref rhs) => write!(f, "{} >= {}", lhs, rhs),
+            BooleanExpression::UintGt(ref lhs, ref rhs) => write!(f, "{} > {}", lhs, rhs),
             BooleanExpression::Ge(ref lhs, ref rhs) => write!(f, "{} >= {}", lhs, rhs),
             BooleanExpression::Gt(ref lhs, ref rhs) => write!(f, "{} > {}", lhs, rhs),
             BooleanExpression::Or(ref lhs, ref rhs) => write!(f, "{} || {}", lhs, rhs),
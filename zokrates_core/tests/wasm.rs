@@ -3,17 +3,18 @@ wasm_bindgen_test_configure!(run_in_browser);
 extern crate wasm_bindgen_test;
 extern crate zokrates_core;
 extern crate zokrates_field;
+use std::collections::BTreeMap;
 use wasm_bindgen_test::*;
 use zokrates_core::flat_absy::FlatVariable;
 use zokrates_core::ir::{Function, Interpreter, Prog, Statement};
-use zokrates_core::proof_system::ProofSystem;
+use zokrates_core::proof_system::{ProofSystem, Randomness};
 use zokrates_field::Bn128Field;
 
+use zokrates_core::proof_system::bellman::gm17::GM17;
 use zokrates_core::proof_system::bellman::groth16::G16;
 
-#[wasm_bindgen_test]
-fn generate_proof() {
-    let program: Prog<Bn128Field> = Prog {
+fn program() -> Prog<Bn128Field> {
+    Prog {
         main: Function {
             id: String::from("main"),
             arguments: vec![FlatVariable::new(0)],
@@ -24,7 +25,31 @@ fn generate_proof() {
             )],
         },
         private: vec![false],
-    };
+        origins: BTreeMap::new(),
+        metadata: BTreeMap::new(),
+    }
+}
+
+#[wasm_bindgen_test]
+fn generate_proof() {
+    let program = program();
+
+    let interpreter = Interpreter::default();
+
+    let witness = interpreter
+        .execute(&program, &vec![Bn128Field::from(42)])
+        .unwrap();
+
+    // there is no randomness from the system in wasm
+    let randomness = Randomness::InsecureSeed(b"wasm");
+
+    let keys = G16::setup(program.clone(), randomness).unwrap();
+    let _proof = G16::generate_proof(program, witness, keys.pk, randomness).unwrap();
+}
+
+#[wasm_bindgen_test]
+fn generate_gm17_proof() {
+    let program = program();
 
     let interpreter = Interpreter::default();
 
@@ -32,6 +57,9 @@ fn generate_proof() {
         .execute(&program, &vec![Bn128Field::from(42)])
         .unwrap();
 
-    let keys = G16::setup(program.clone());
-    let _proof = G16::generate_proof(program, witness, keys.pk);
+    // there is no randomness from the system in wasm
+    let randomness = Randomness::InsecureSeed(b"wasm");
+
+    let keys = GM17::setup(program.clone(), randomness).unwrap();
+    let _proof = GM17::generate_proof(program, witness, keys.pk, randomness).unwrap();
 }
@@ -5,6 +5,7 @@ declare module 'zokrates-js' {
   export type ProvingKey = Uint8Array;
 
   export type SolidityAbi = "v1" | "v2";
+  export type ProvingScheme = "g16" | "gm17";
   export type ResolveCallback = (location: string, path: string) => ResolverResult;
 
   export interface CompileConfig {
@@ -17,7 +18,7 @@ declare module 'zokrates-js' {
     config?: CompileConfig
   }
 
-  export interface VerificationKey {
+  export interface G16VerificationKey {
     alpha: G1Affine,
     beta: G2Affine,
     gamma: G2Affine,
@@ -26,6 +27,17 @@ declare module 'zokrates-js' {
     raw: string,
   }
 
+  export interface GM17VerificationKey {
+    h: G2Affine,
+    g_alpha: G1Affine,
+    h_beta: G2Affine,
+    g_gamma: G1Affine,
+    h_gamma: G2Affine,
+    query: G1Affine[],
+  }
+
+  export type VerificationKey = G16VerificationKey | GM17VerificationKey;
+
   export interface ProofPoints {
     a: G1Affine,
     b: G2Affine,
@@ -60,10 +72,10 @@ declare module 'zokrates-js' {
 
   export interface ZoKratesProvider {
     compile(source: string, options?: CompileOptions): CompilationArtifacts;
-    setup(program: Uint8Array): SetupKeypair;
+    setup(program: Uint8Array, scheme?: ProvingScheme): SetupKeypair;
     computeWitness(artifacts: CompilationArtifacts, args: any[]): ComputationResult;
-    exportSolidityVerifier(verifyingKey: VerificationKey, abi: SolidityAbi): string;
-    generateProof(program: Uint8Array, witness: string, provingKey: Uint8Array): Proof;
+    exportSolidityVerifier(verifyingKey: VerificationKey, abi: SolidityAbi, scheme?: ProvingScheme): string;
+    generateProof(program: Uint8Array, witness: string, provingKey: Uint8Array, scheme?: ProvingScheme): Proof;
   }
 
   export interface Metadata {
@@ -4,7 +4,7 @@ import metadata from './metadata.json';
 
 const initialize = async () => {
   const zokrates = await import('./pkg/index.js');
-  return wrapper({ zokrates, stdlib });
+  return wrapper({ zokrates, stdlib, getRandomValues: (array) => crypto.getRandomValues(array) });
 }
 
 export { initialize, metadata };
\ No newline at end of file
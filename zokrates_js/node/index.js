@@ -5,7 +5,8 @@ const metadata = require('../metadata.json');
 const initialize = async () => {
     return wrapper({ 
         zokrates: require('./pkg/index.js'),
-        stdlib
+        stdlib,
+        getRandomValues: require('crypto').randomFillSync
     });
 }
 
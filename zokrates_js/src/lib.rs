@@ -10,8 +10,9 @@ use zokrates_core::compile::{
 };
 use zokrates_core::imports::Error;
 use zokrates_core::ir;
+use zokrates_core::proof_system::bellman::gm17::GM17;
 use zokrates_core::proof_system::bellman::groth16::G16;
-use zokrates_core::proof_system::{ProofSystem, SolidityAbi};
+use zokrates_core::proof_system::{ProofSystem, Randomness, SolidityAbi};
 use zokrates_core::typed_absy::abi::Abi;
 use zokrates_core::typed_absy::types::Signature;
 use zokrates_field::Bn128Field;
@@ -161,29 +162,102 @@ pub fn compute_witness(artifacts: JsValue, args: JsValue) -> Result<JsValue, JsV
     Ok(JsValue::from_serde(&result).unwrap())
 }
 
+// there is no randomness from the system in wasm, so the caller gathers entropy, for example with `crypto.getRandomValues`
+fn randomness<'a>(entropy: &'a [u8]) -> Result<Randomness<'a>, JsValue> {
+    if entropy.len() < 32 {
+        return Err(JsValue::from_str(&format!(
+            "Expected at least 32 bytes of entropy, found {}",
+            entropy.len()
+        )));
+    }
+    Ok(Randomness::Entropy(entropy))
+}
+
+// the proving schemes which run in wasm, named like in the CLI
+enum Scheme {
+    G16,
+    GM17,
+}
+
+impl Scheme {
+    fn from_js(scheme: JsValue) -> Result<Self, JsValue> {
+        match scheme.as_string().unwrap().as_str() {
+            "g16" => Ok(Scheme::G16),
+            "gm17" => Ok(Scheme::GM17),
+            s => Err(JsValue::from_str(&format!(
+                "Unsupported proving scheme {}, expected g16 or gm17",
+                s
+            ))),
+        }
+    }
+}
+
+fn setup_with<P: ProofSystem<Bn128Field>>(
+    program: ir::Prog<Bn128Field>,
+    randomness: Randomness,
+) -> Result<JsValue, JsValue> {
+    let keypair = P::setup(program, randomness).map_err(|err| JsValue::from_str(&err))?;
+    Ok(JsValue::from_serde(&keypair).unwrap())
+}
+
+fn export_solidity_verifier_with<P: ProofSystem<Bn128Field>>(
+    vk: JsValue,
+    abi_version: SolidityAbi,
+) -> Result<JsValue, JsValue> {
+    let vk = vk.into_serde().map_err(|err| {
+        JsValue::from_str(&format!("Could not deserialize verification key: {}", err))
+    })?;
+
+    let verifier = P::export_solidity_verifier(vk, abi_version);
+    Ok(JsValue::from_str(verifier.as_str()))
+}
+
+fn generate_proof_with<P: ProofSystem<Bn128Field>>(
+    program: ir::Prog<Bn128Field>,
+    witness: ir::Witness<Bn128Field>,
+    proving_key: Vec<u8>,
+    randomness: Randomness,
+) -> Result<JsValue, JsValue> {
+    let proof = P::generate_proof(program, witness, proving_key, randomness)
+        .map_err(|err| JsValue::from_str(&err))?;
+    Ok(JsValue::from_serde(&proof).unwrap())
+}
+
 #[wasm_bindgen]
-pub fn setup(program: JsValue) -> Result<JsValue, JsValue> {
+pub fn setup(program: JsValue, scheme: JsValue, entropy: &[u8]) -> Result<JsValue, JsValue> {
     let input: Vec<u8> = program.into_serde().unwrap();
     let program_flattened = deserialize_program(&input)?;
-    let keypair = G16::setup(program_flattened);
-    Ok(JsValue::from_serde(&keypair).unwrap())
+    let randomness = randomness(entropy)?;
+
+    match Scheme::from_js(scheme)? {
+        Scheme::G16 => setup_with::<G16>(program_flattened, randomness),
+        Scheme::GM17 => setup_with::<GM17>(program_flattened, randomness),
+    }
 }
 
 #[wasm_bindgen]
-pub fn export_solidity_verifier(vk: JsValue, abi_version: JsValue) -> Result<JsValue, JsValue> {
+pub fn export_solidity_verifier(
+    vk: JsValue,
+    abi_version: JsValue,
+    scheme: JsValue,
+) -> Result<JsValue, JsValue> {
     let abi_version = SolidityAbi::from(abi_version.as_string().unwrap().as_str())
         .map_err(|err| JsValue::from_str(err))?;
 
-    let verifier = <G16 as ProofSystem<Bn128Field>>::export_solidity_verifier(
-        vk.into_serde().unwrap(),
-        abi_version,
-    );
-
-    Ok(JsValue::from_str(verifier.as_str()))
+    match Scheme::from_js(scheme)? {
+        Scheme::G16 => export_solidity_verifier_with::<G16>(vk, abi_version),
+        Scheme::GM17 => export_solidity_verifier_with::<GM17>(vk, abi_version),
+    }
 }
 
 #[wasm_bindgen]
-pub fn generate_proof(program: JsValue, witness: JsValue, pk: JsValue) -> Result<JsValue, JsValue> {
+pub fn generate_proof(
+    program: JsValue,
+    witness: JsValue,
+    pk: JsValue,
+    scheme: JsValue,
+    entropy: &[u8],
+) -> Result<JsValue, JsValue> {
     let input: Vec<u8> = program.into_serde().unwrap();
     let program_flattened = deserialize_program(&input)?;
 
@@ -192,9 +266,16 @@ pub fn generate_proof(program: JsValue, witness: JsValue, pk: JsValue) -> Result
         .map_err(|err| JsValue::from_str(&format!("Could not read witness: {}", err)))?;
 
     let proving_key: Vec<u8> = pk.into_serde().unwrap();
-    let proof = G16::generate_proof(program_flattened, ir_witness, proving_key);
+    let randomness = randomness(entropy)?;
 
-    Ok(JsValue::from_serde(&proof).unwrap())
+    match Scheme::from_js(scheme)? {
+        Scheme::G16 => {
+            generate_proof_with::<G16>(program_flattened, ir_witness, proving_key, randomness)
+        }
+        Scheme::GM17 => {
+            generate_proof_with::<GM17>(program_flattened, ir_witness, proving_key, randomness)
+        }
+    }
 }
 
 #[wasm_bindgen(start)]
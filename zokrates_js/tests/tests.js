@@ -95,6 +95,24 @@ describe('tests', function() {
                 this.zokrates.setup(artifacts.program);
             });
         });
+
+        it('should run gm17 setup', function() {
+            assert.doesNotThrow(() => {
+                const code = 'def main(private field a) -> field: return a * a';
+                const artifacts = this.zokrates.compile(code);
+
+                this.zokrates.setup(artifacts.program, "gm17");
+            });
+        });
+
+        it('should throw on unsupported scheme', function() {
+            assert.throws(() => {
+                const code = 'def main(private field a) -> field: return a * a';
+                const artifacts = this.zokrates.compile(code);
+
+                this.zokrates.setup(artifacts.program, "pghr13");
+            });
+        });
     });
 
     describe("export-verifier", () => {
@@ -108,6 +126,17 @@ describe('tests', function() {
                 assert.ok(verifier.length > 0);
             });
         });
+
+        it('should export gm17 solidity verifier', function() {
+            assert.doesNotThrow(() => {
+                const code = 'def main(private field a) -> field: return a * a';
+                const artifacts = this.zokrates.compile(code);
+                const keypair = this.zokrates.setup(artifacts.program, "gm17");
+
+                const verifier = this.zokrates.exportSolidityVerifier(keypair.vk, "v1", "gm17");
+                assert.ok(verifier.length > 0);
+            });
+        });
     });
 
     describe("generate-proof", () => {
@@ -123,5 +152,18 @@ describe('tests', function() {
                 assert.deepEqual(proof.inputs, ["0x0000000000000000000000000000000000000000000000000000000000000004"]);
             })
         });
+
+        it('should generate gm17 proof', function() {
+            assert.doesNotThrow(() => {
+                const code = 'def main(private field a) -> field: return a * a';
+                const artifacts = this.zokrates.compile(code);
+                const computationResult = this.zokrates.computeWitness(artifacts, ["2"])
+                const keypair = this.zokrates.setup(artifacts.program, "gm17");
+                const proof = this.zokrates.generateProof(artifacts.program, computationResult.witness, keypair.pk, "gm17");
+
+                assert.ok(proof !== undefined);
+                assert.deepEqual(proof.inputs, ["0x0000000000000000000000000000000000000000000000000000000000000004"]);
+            })
+        });
     });
 });
\ No newline at end of file
@@ -26,7 +26,10 @@ const getImportPath = (currentLocation, importLocation) => {
 
 module.exports = (dep) => {
 
-    const { zokrates, stdlib } = dep;
+    const { zokrates, stdlib, getRandomValues } = dep;
+
+    // setups and proofs need secret randomness, which wasm cannot get from the system
+    const entropy = () => getRandomValues(new Uint8Array(32));
 
     const resolveFromStdlib = (currentLocation, importLocation) => {
         let key = getImportPath(currentLocation, importLocation);
@@ -46,8 +49,8 @@ module.exports = (dep) => {
                 abi
             }
         },
-        setup: (program) => {
-            const { vk, pk } = zokrates.setup(program);
+        setup: (program, scheme = "g16") => {
+            const { vk, pk } = zokrates.setup(program, scheme, entropy());
             return {
                 vk,
                 pk: Array.from(pk)
@@ -56,11 +59,11 @@ module.exports = (dep) => {
         computeWitness: (artifacts, args) => {
             return zokrates.compute_witness(artifacts, JSON.stringify(Array.from(args)));
         },
-        exportSolidityVerifier: (verificationKey, abiVersion) => {
-            return zokrates.export_solidity_verifier(verificationKey, abiVersion);
+        exportSolidityVerifier: (verificationKey, abiVersion, scheme = "g16") => {
+            return zokrates.export_solidity_verifier(verificationKey, abiVersion, scheme);
         },
-        generateProof: (program, witness, provingKey) => {
-            return zokrates.generate_proof(program, witness, provingKey);
+        generateProof: (program, witness, provingKey, scheme = "g16") => {
+            return zokrates.generate_proof(program, witness, provingKey, scheme, entropy());
         }
     }
 };
\ No newline at end of file
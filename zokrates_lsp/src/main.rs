@@ -0,0 +1,30 @@
+//! A language server for ZoKrates, speaking the Language Server Protocol over stdio
+
+mod protocol;
+mod server;
+
+use server::Server;
+use std::io::{self, BufReader};
+
+fn main() -> io::Result<()> {
+    let stdin = io::stdin();
+    let mut reader = BufReader::new(stdin.lock());
+    let stdout = io::stdout();
+    let mut writer = stdout.lock();
+
+    let mut server = Server::new();
+
+    while let Some(message) = protocol::read_message(&mut reader)? {
+        if let Some(message) = protocol::incoming(message) {
+            for response in server.handle(message) {
+                protocol::write_message(&mut writer, &response)?;
+            }
+        }
+
+        if let Some(code) = server.exit_code() {
+            std::process::exit(code);
+        }
+    }
+
+    Ok(())
+}
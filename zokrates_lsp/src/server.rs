@@ -0,0 +1,681 @@
+//! The state of the language server and the handling of the messages of the client
+
+use crate::protocol::{
+    error_response, notification, path_to_uri, response, uri_to_path, Incoming, INVALID_PARAMS,
+    METHOD_NOT_FOUND,
+};
+use serde_json::{json, Value};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use zokrates_common::Resolver;
+use zokrates_core::cache::Cache;
+use zokrates_core::compile::{check_with_index, CompileErrors};
+use zokrates_core::diagnostics::{Diagnostic, ErrorCode, Position, Severity};
+use zokrates_core::symbols::{Location, SymbolIndex};
+use zokrates_field::{Bls12Field, Bn128Field};
+use zokrates_fs_resolver::FileSystemResolver;
+
+/// The `keyword` rule of the grammar, and `const` which only starts constant definitions
+const KEYWORDS: &[&str] = &[
+    "assert", "as", "bool", "break", "byte", "const", "continue", "def", "do", "else", "endfor",
+    "export", "false", "field", "for", "if", "then", "fi", "import", "from", "in", "private",
+    "public", "return", "struct", "true", "type", "u8", "u16", "u32", "u64", "u128", "i8", "i16",
+    "i32",
+];
+
+// completion item kinds, as defined by the protocol
+const KIND_KEYWORD: u8 = 14;
+const KIND_VARIABLE: u8 = 6;
+const KIND_REFERENCE: u8 = 18;
+
+/// The result of checking a module, which stays valid as long as the modules it was checked with do not change
+struct Checked {
+    /// The content of the modules the module was checked with, including itself
+    sources: HashMap<PathBuf, String>,
+    diagnostics: Vec<Diagnostic>,
+    index: SymbolIndex,
+}
+
+pub struct Server {
+    /// The content of the open documents
+    documents: HashMap<PathBuf, String>,
+    /// The results of the modules checked so far, by path
+    checked: HashMap<PathBuf, Checked>,
+    /// The imported modules checked so far, which are reused while their content and imports do not change
+    cache: Cache,
+    /// The symbols of each module as of its last check, with the content it was checked with, as the modules reused
+    /// from the cache are not indexed again
+    symbols: HashMap<PathBuf, (String, SymbolIndex)>,
+    shutdown: bool,
+    exit: Option<i32>,
+}
+
+/// The number of servers created so far, so that each of them gets its own cache
+static SERVERS: AtomicUsize = AtomicUsize::new(0);
+
+impl Drop for Server {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(self.cache.dir());
+    }
+}
+
+/// Resolves imports to the content of open documents rather than to what is saved on disk, and records the content
+/// of the modules it resolves
+struct OverlayResolver<'a> {
+    documents: &'a HashMap<PathBuf, String>,
+    sources: RefCell<HashMap<PathBuf, String>>,
+}
+
+impl<'a> Resolver<io::Error> for OverlayResolver<'a> {
+    fn resolve(
+        &self,
+        current_location: PathBuf,
+        import_location: PathBuf,
+    ) -> Result<(String, PathBuf), io::Error> {
+        let (source, location) =
+            FileSystemResolver::new().resolve(current_location, import_location)?;
+
+        let path = canonical(&location);
+        let source = self.documents.get(&path).cloned().unwrap_or(source);
+        self.sources.borrow_mut().insert(path, source.clone());
+
+        Ok((source, location))
+    }
+}
+
+fn to_position(position: &Value) -> Option<Position> {
+    Some(Position {
+        line: position.get("line")?.as_u64()? as usize + 1,
+        col: position.get("character")?.as_u64()? as usize + 1,
+    })
+}
+
+fn from_position(position: Position) -> Value {
+    json!({
+        "line": position.line.saturating_sub(1),
+        "character": position.col.saturating_sub(1),
+    })
+}
+
+fn range(start: Position, end: Position) -> Value {
+    json!({ "start": from_position(start), "end": from_position(end) })
+}
+
+fn canonical(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or(path.to_path_buf())
+}
+
+impl Server {
+    pub fn new() -> Self {
+        Server {
+            documents: HashMap::new(),
+            checked: HashMap::new(),
+            cache: Cache::new(std::env::temp_dir().join(format!(
+                "zokrates_lsp_{}_{}",
+                std::process::id(),
+                SERVERS.fetch_add(1, Ordering::SeqCst)
+            ))),
+            symbols: HashMap::new(),
+            shutdown: false,
+            exit: None,
+        }
+    }
+
+    /// The exit code of the server once the client asked it to exit
+    pub fn exit_code(&self) -> Option<i32> {
+        self.exit
+    }
+
+    /// Handle a message from the client, returning the messages to send back
+    pub fn handle(&mut self, message: Incoming) -> Vec<Value> {
+        let id = message.id.clone();
+        match self.dispatch(message) {
+            Ok(messages) => messages,
+            Err((code, error)) => match id {
+                Some(id) => vec![error_response(id, code, &error)],
+                None => vec![],
+            },
+        }
+    }
+
+    fn dispatch(&mut self, message: Incoming) -> Result<Vec<Value>, (i64, String)> {
+        let params = &message.params;
+        let respond = |result: Value| match &message.id {
+            Some(id) => vec![response(id.clone(), result)],
+            None => vec![],
+        };
+
+        match message.method.as_str() {
+            "initialize" => Ok(respond(json!({
+                "capabilities": {
+                    // the client sends the full text of documents on each change
+                    "textDocumentSync": 1,
+                    "hoverProvider": true,
+                    "definitionProvider": true,
+                    "completionProvider": {},
+                },
+                "serverInfo": { "name": "zokrates_lsp" },
+            }))),
+            "initialized" => Ok(vec![]),
+            "shutdown" => {
+                self.shutdown = true;
+                Ok(respond(Value::Null))
+            }
+            "exit" => {
+                self.exit = Some(if self.shutdown { 0 } else { 1 });
+                Ok(vec![])
+            }
+            "textDocument/didOpen" => {
+                let path = document_path(params)?;
+                let text = params["textDocument"]["text"]
+                    .as_str()
+                    .ok_or_else(invalid_params)?
+                    .to_string();
+                self.documents.insert(path.clone(), text);
+                Ok(self.recheck(&path))
+            }
+            "textDocument/didChange" => {
+                let path = document_path(params)?;
+                let text = params["contentChanges"]
+                    .as_array()
+                    .and_then(|changes| changes.last())
+                    .and_then(|change| change["text"].as_str())
+                    .ok_or_else(invalid_params)?
+                    .to_string();
+                match self.documents.get_mut(&path) {
+                    Some(document) => *document = text,
+                    None => return Err(invalid_params()),
+                }
+                Ok(self.recheck(&path))
+            }
+            "textDocument/didClose" => {
+                let path = document_path(params)?;
+                self.documents.remove(&path);
+                self.checked.remove(&path);
+
+                // the open documents importing it are checked again against its content on disk
+                let saved = fs::read_to_string(&path).ok();
+                let stale = self.invalidate(&path, saved.as_deref());
+
+                Ok(std::iter::once(publish(&path, vec![]))
+                    .chain(stale.iter().map(|p| self.check(p)))
+                    .collect())
+            }
+            "textDocument/didSave" => Ok(vec![]),
+            "textDocument/hover" => {
+                let (path, position) = document_position(params)?;
+                let result = self
+                    .index(&path)
+                    .and_then(|index| index.reference_at(&path, position))
+                    .map(|r| {
+                        json!({
+                            "contents": {
+                                "kind": "markdown",
+                                "value": format!("```zokrates\n{}: {}\n```", r.name, r.ty),
+                            },
+                            "range": range(r.location.start, r.location.end),
+                        })
+                    })
+                    .unwrap_or(Value::Null);
+                Ok(respond(result))
+            }
+            "textDocument/definition" => {
+                let (path, position) = document_position(params)?;
+                let result = self
+                    .index(&path)
+                    .and_then(|index| {
+                        index
+                            .reference_at(&path, position)
+                            .and_then(|r| index.definition(r))
+                    })
+                    .map(|location| to_location(&location))
+                    .unwrap_or(Value::Null);
+                Ok(respond(result))
+            }
+            "textDocument/completion" => {
+                let (path, position) = document_position(params)?;
+                let visible = self
+                    .index(&path)
+                    .map(|index| index.visible_at(&path, position))
+                    .unwrap_or_default();
+                let items: Vec<_> = visible
+                    .into_iter()
+                    .map(|(name, ty)| match ty {
+                        Some(ty) => json!({ "label": name, "kind": KIND_VARIABLE, "detail": ty }),
+                        None => json!({ "label": name, "kind": KIND_REFERENCE }),
+                    })
+                    .chain(
+                        KEYWORDS
+                            .iter()
+                            .map(|k| json!({ "label": k, "kind": KIND_KEYWORD })),
+                    )
+                    .collect();
+                Ok(respond(Value::Array(items)))
+            }
+            method => match message.id {
+                // requests must be answered, unknown notifications are ignored
+                Some(_) => Err((METHOD_NOT_FOUND, format!("Unknown method {}", method))),
+                None => Ok(vec![]),
+            },
+        }
+    }
+
+    /// The symbols of an open document, as of its last check
+    fn index(&self, path: &Path) -> Option<&SymbolIndex> {
+        match self.documents.contains_key(path) {
+            true => self.checked.get(path).map(|c| &c.index),
+            false => None,
+        }
+    }
+
+    /// Check `path` and the open documents which import it, returning their diagnostics. Only the results which
+    /// depend on another content of `path` are invalidated: other documents are not affected by the change so their
+    /// diagnostics are kept, and a module whose content did not change is not checked again
+    fn recheck(&mut self, path: &Path) -> Vec<Value> {
+        let text = self.documents[path].clone();
+        let mut stale = self.invalidate(path, Some(&text));
+        stale.insert(0, path.to_path_buf());
+
+        stale.iter().map(|p| self.check(p)).collect()
+    }
+
+    /// Drop the results which were computed with another content of `path` than `text`, returning the other open
+    /// documents whose results were dropped
+    fn invalidate(&mut self, path: &Path, text: Option<&str>) -> Vec<PathBuf> {
+        let mut stale: Vec<_> = self
+            .checked
+            .iter()
+            .filter(|(_, c)| {
+                c.sources
+                    .get(path)
+                    .map(|s| Some(s.as_str()) != text)
+                    .unwrap_or(false)
+            })
+            .map(|(p, _)| p.clone())
+            .collect();
+        for p in &stale {
+            self.checked.remove(p);
+        }
+
+        // results of the documents which are not open are dropped, they are checked again when opened
+        stale.retain(|p| p != path && self.documents.contains_key(p));
+        stale.sort();
+        stale
+    }
+
+    fn check(&mut self, path: &Path) -> Value {
+        if let Some(checked) = self.checked.get(path) {
+            return publish(path, checked.diagnostics.clone());
+        }
+
+        let text = self.documents[path].clone();
+
+        let resolver = OverlayResolver {
+            documents: &self.documents,
+            sources: RefCell::new(HashMap::new()),
+        };
+
+        let (res, mut index) = match check_with_index::<Bn128Field, _>(
+            text.clone(),
+            path.to_path_buf(),
+            Some(&resolver),
+            Some(&self.cache),
+        ) {
+            // the program declares another curve
+            (Err(e), _) if has_code(&e, ErrorCode::IncompatibleCurve) => {
+                resolver.sources.borrow_mut().clear();
+                check_with_index::<Bls12Field, _>(
+                    text.clone(),
+                    path.to_path_buf(),
+                    Some(&resolver),
+                    Some(&self.cache),
+                )
+            }
+            r => r,
+        };
+
+        let diagnostics: Vec<_> = match res {
+            Ok(warnings) => warnings.diagnostics(),
+            // modules which are only imported have no `main`
+            Err(e) => e
+                .diagnostics()
+                .into_iter()
+                .filter(|d| d.code != ErrorCode::MainNotFound)
+                .collect(),
+        }
+        .into_iter()
+        .filter(|d| canonical(Path::new(&d.file)) == path)
+        .collect();
+
+        let mut sources = resolver.sources.into_inner();
+        sources.insert(path.to_path_buf(), text);
+
+        // the modules reused from the cache have no symbols in the index, so those of their last check are added
+        for module in index.modules.clone() {
+            let source = match sources.get(&canonical(&module)) {
+                Some(source) => source,
+                None => continue,
+            };
+            let symbols = index.module(&module);
+            match symbols.is_empty() {
+                true => {
+                    if let Some((s, symbols)) = self.symbols.get(&module) {
+                        if s == source {
+                            index.extend(symbols.clone());
+                        }
+                    }
+                }
+                false => {
+                    self.symbols.insert(module, (source.clone(), symbols));
+                }
+            }
+        }
+
+        self.checked.insert(
+            path.to_path_buf(),
+            Checked {
+                sources,
+                diagnostics: diagnostics.clone(),
+                index,
+            },
+        );
+
+        publish(path, diagnostics)
+    }
+}
+
+fn has_code(errors: &CompileErrors, code: ErrorCode) -> bool {
+    errors.0.iter().any(|e| e.diagnostic().code == code)
+}
+
+fn invalid_params() -> (i64, String) {
+    (INVALID_PARAMS, String::from("Invalid parameters"))
+}
+
+fn document_path(params: &Value) -> Result<PathBuf, (i64, String)> {
+    params["textDocument"]["uri"]
+        .as_str()
+        .and_then(uri_to_path)
+        .map(|p| canonical(&p))
+        .ok_or_else(invalid_params)
+}
+
+fn document_position(params: &Value) -> Result<(PathBuf, Position), (i64, String)> {
+    Ok((
+        document_path(params)?,
+        to_position(&params["position"]).ok_or_else(invalid_params)?,
+    ))
+}
+
+fn to_location(location: &Location) -> Value {
+    json!({
+        "uri": path_to_uri(&canonical(&location.module)),
+        "range": range(location.start, location.end),
+    })
+}
+
+fn publish(path: &Path, diagnostics: Vec<Diagnostic>) -> Value {
+    let diagnostics: Vec<_> = diagnostics
+        .into_iter()
+        .map(|d| {
+            let start = d.start.unwrap_or(Position { line: 1, col: 1 });
+            let end = d.end.unwrap_or(start);
+            json!({
+                "range": range(start, end),
+                // as defined by the protocol
+                "severity": match d.severity {
+                    Severity::Error => 1,
+                    Severity::Warning => 2,
+                },
+                "code": d.code.as_str(),
+                "source": "zokrates",
+                "message": d.message,
+            })
+        })
+        .collect();
+
+    notification(
+        "textDocument/publishDiagnostics",
+        json!({ "uri": path_to_uri(path), "diagnostics": diagnostics }),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn request(method: &str, params: Value) -> Incoming {
+        Incoming {
+            id: Some(json!(1)),
+            method: method.to_string(),
+            params,
+        }
+    }
+
+    fn notify(method: &str, params: Value) -> Incoming {
+        Incoming {
+            id: None,
+            method: method.to_string(),
+            params,
+        }
+    }
+
+    fn open(server: &mut Server, path: &Path, text: &str) -> Vec<Value> {
+        server.handle(notify(
+            "textDocument/didOpen",
+            json!({ "textDocument": { "uri": path_to_uri(path), "text": text } }),
+        ))
+    }
+
+    fn at(path: &Path, line: usize, character: usize) -> Value {
+        json!({
+            "textDocument": { "uri": path_to_uri(path) },
+            "position": { "line": line, "character": character },
+        })
+    }
+
+    fn setup() -> (tempfile::TempDir, PathBuf, PathBuf) {
+        let dir = tempfile::tempdir().unwrap();
+        let root = canonical(dir.path()).join("root.zok");
+        let lib = canonical(dir.path()).join("lib.zok");
+        fs::write(&lib, "def double(field a) -> field:\n    return 2 * a\n").unwrap();
+        fs::write(
+            &root,
+            "from \"./lib\" import double\n\ndef main(field a) -> field:\n    field b = double(a)\n    return b\n",
+        )
+        .unwrap();
+        (dir, root, lib)
+    }
+
+    #[test]
+    fn diagnostics() {
+        let (_dir, root, _) = setup();
+        let mut server = Server::new();
+
+        let messages = open(&mut server, &root, "def main() -> field:\n    return a\n");
+        assert_eq!(messages.len(), 1);
+        let diagnostics = &messages[0]["params"]["diagnostics"];
+        assert_eq!(diagnostics[0]["code"], "E0302");
+        assert_eq!(
+            diagnostics[0]["range"]["start"],
+            json!({ "line": 1, "character": 11 })
+        );
+    }
+
+    #[test]
+    fn library_modules_have_no_main() {
+        let (_dir, _, lib) = setup();
+        let mut server = Server::new();
+
+        let messages = open(&mut server, &lib, &fs::read_to_string(&lib).unwrap());
+        assert_eq!(messages[0]["params"]["diagnostics"], json!([]));
+    }
+
+    #[test]
+    fn hover_and_definition() {
+        let (_dir, root, lib) = setup();
+        let mut server = Server::new();
+        open(&mut server, &root, &fs::read_to_string(&root).unwrap());
+
+        let hover = server.handle(request("textDocument/hover", at(&root, 4, 11)));
+        assert_eq!(
+            hover[0]["result"]["contents"]["value"],
+            "```zokrates\nb: field\n```"
+        );
+
+        let definition = server.handle(request("textDocument/definition", at(&root, 3, 15)));
+        assert_eq!(definition[0]["result"]["uri"], path_to_uri(&lib));
+        assert_eq!(
+            definition[0]["result"]["range"]["start"],
+            json!({ "line": 0, "character": 0 })
+        );
+
+        let completion = server.handle(request("textDocument/completion", at(&root, 4, 4)));
+        let labels: Vec<_> = completion[0]["result"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|item| item["label"].as_str().unwrap().to_string())
+            .collect();
+        assert!(labels.contains(&String::from("b")));
+        assert!(labels.contains(&String::from("double")));
+        assert!(labels.contains(&String::from("field")));
+    }
+
+    #[test]
+    fn changes_recheck_dependents() {
+        let (_dir, root, lib) = setup();
+        let mut server = Server::new();
+        open(&mut server, &root, &fs::read_to_string(&root).unwrap());
+        open(&mut server, &lib, &fs::read_to_string(&lib).unwrap());
+
+        // breaking the signature of `double` in the open buffer breaks the call in `root`
+        let messages = server.handle(notify(
+            "textDocument/didChange",
+            json!({
+                "textDocument": { "uri": path_to_uri(&lib) },
+                "contentChanges": [{ "text": "def double(bool a) -> field:\n    return 1\n" }],
+            }),
+        ));
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[0]["params"]["uri"], path_to_uri(&lib));
+        assert_eq!(messages[0]["params"]["diagnostics"], json!([]));
+        assert_eq!(messages[1]["params"]["uri"], path_to_uri(&root));
+        assert_eq!(messages[1]["params"]["diagnostics"][0]["code"], "E0309");
+
+        // changing `root` does not affect `lib`
+        let messages = server.handle(notify(
+            "textDocument/didChange",
+            json!({
+                "textDocument": { "uri": path_to_uri(&root) },
+                "contentChanges": [{ "text": "def main() -> field:\n    return 1\n" }],
+            }),
+        ));
+        assert_eq!(messages.len(), 1);
+    }
+
+    #[test]
+    fn results_are_cached() {
+        let (_dir, root, lib) = setup();
+        let other = lib.with_file_name("other.zok");
+        let mut server = Server::new();
+        let text = fs::read_to_string(&root).unwrap();
+        open(&mut server, &root, &text);
+        open(&mut server, &other, "def main() -> field:\n    return 1\n");
+
+        let change = |server: &mut Server, path: &Path, text: &str| {
+            server.handle(notify(
+                "textDocument/didChange",
+                json!({
+                    "textDocument": { "uri": path_to_uri(path) },
+                    "contentChanges": [{ "text": text }],
+                }),
+            ))
+        };
+
+        // `root` is not checked again when its content does not change, which we observe by breaking `lib` on disk
+        fs::write(&lib, "def double(bool a) -> field:\n    return 1\n").unwrap();
+        let messages = change(&mut server, &root, &text);
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0]["params"]["diagnostics"], json!([]));
+
+        // opening `lib` invalidates the results of `root`, which imports it, but not those of `other`
+        let messages = open(&mut server, &lib, &fs::read_to_string(&lib).unwrap());
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[1]["params"]["uri"], path_to_uri(&root));
+        assert_eq!(messages[1]["params"]["diagnostics"][0]["code"], "E0309");
+
+        // editing `root` does not invalidate the results of `lib`, which it imports
+        let messages = change(&mut server, &root, &format!("{}\n", text));
+        assert_eq!(messages.len(), 1);
+        assert_eq!(server.checked[&lib].sources.len(), 1);
+        assert_eq!(server.checked[&root].sources.len(), 2);
+    }
+
+    #[test]
+    fn imported_modules_are_reused() {
+        let (_dir, root, lib) = setup();
+        let mut server = Server::new();
+        let text = fs::read_to_string(&root).unwrap();
+        open(&mut server, &root, &text);
+
+        // `lib` is stored in the cache once checked, unlike `root` which is the main module
+        assert_eq!(fs::read_dir(server.cache.dir()).unwrap().count(), 1);
+
+        // editing `root` reuses `lib` from the cache, with the symbols of its last check
+        server.handle(notify(
+            "textDocument/didChange",
+            json!({
+                "textDocument": { "uri": path_to_uri(&root) },
+                "contentChanges": [{ "text": format!("{}\n", text) }],
+            }),
+        ));
+        let definition = server.handle(request("textDocument/definition", at(&root, 3, 15)));
+        assert_eq!(definition[0]["result"]["uri"], path_to_uri(&lib));
+    }
+
+    #[test]
+    fn closing_rechecks_dependents() {
+        let (_dir, root, lib) = setup();
+        let mut server = Server::new();
+        open(&mut server, &root, &fs::read_to_string(&root).unwrap());
+        open(&mut server, &lib, "def double(bool a) -> field:\n    return 1\n");
+        assert_eq!(
+            server.checked[&root].diagnostics[0].code,
+            ErrorCode::FunctionNotFound
+        );
+
+        // once closed, `lib` is read from disk again, where `double` still accepts a field
+        let messages = server.handle(notify(
+            "textDocument/didClose",
+            json!({ "textDocument": { "uri": path_to_uri(&lib) } }),
+        ));
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[0]["params"]["uri"], path_to_uri(&lib));
+        assert_eq!(messages[1]["params"]["uri"], path_to_uri(&root));
+        assert_eq!(messages[1]["params"]["diagnostics"], json!([]));
+    }
+
+    #[test]
+    fn lifecycle() {
+        let mut server = Server::new();
+        let initialize = server.handle(request("initialize", json!({})));
+        assert_eq!(
+            initialize[0]["result"]["capabilities"]["hoverProvider"],
+            true
+        );
+
+        let unknown = server.handle(request("workspace/symbol", json!({})));
+        assert_eq!(unknown[0]["error"]["code"], METHOD_NOT_FOUND);
+
+        server.handle(request("shutdown", Value::Null));
+        assert_eq!(server.exit_code(), None);
+        server.handle(notify("exit", Value::Null));
+        assert_eq!(server.exit_code(), Some(0));
+    }
+}
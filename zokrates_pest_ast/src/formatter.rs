@@ -0,0 +1,855 @@
+//! A pretty-printer for the concrete syntax tree of a ZoKrates program.
+//!
+//! The grammar drops comments, so they are recovered by scanning the source and attached to the
+//! nearest item: comments on their own line are printed before the following item, and comments
+//! on the same line as the end of an item are printed after it.
+
+use crate::ast::*;
+use crate::{generate_ast, Error};
+
+const INDENT: &str = "    ";
+
+/// Format a program with canonical indentation, spacing and import ordering, keeping comments.
+pub fn format(input: &str) -> Result<String, Error> {
+    let file = generate_ast(input)?;
+    let mut printer = Printer::new(input);
+    printer.file(&file);
+    Ok(printer.out)
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Comment {
+    start: usize,
+    end: usize,
+}
+
+/// Find all comments in `source`, skipping the contents of import strings
+fn comments(source: &str) -> Vec<Comment> {
+    let bytes = source.as_bytes();
+    let mut res = vec![];
+    let mut i = 0;
+
+    while i < bytes.len() {
+        match (bytes[i], bytes.get(i + 1)) {
+            (b'"', _) => {
+                i += 1;
+                while i < bytes.len() && bytes[i] != b'"' {
+                    i += 1;
+                }
+                i += 1;
+            }
+            (b'/', Some(b'/')) => {
+                let start = i;
+                while i < bytes.len() && bytes[i] != b'\n' && bytes[i] != b'\r' {
+                    i += 1;
+                }
+                res.push(Comment { start, end: i });
+            }
+            (b'/', Some(b'*')) => {
+                let start = i;
+                i = source[i + 2..]
+                    .find("*/")
+                    .map(|p| i + 2 + p + 2)
+                    .unwrap_or_else(|| bytes.len());
+                res.push(Comment { start, end: i });
+            }
+            _ => i += 1,
+        }
+    }
+
+    res
+}
+
+fn precedence(op: &BinaryOperator) -> u8 {
+    match op {
+        BinaryOperator::Or => 1,
+        BinaryOperator::And => 2,
+        BinaryOperator::Eq
+        | BinaryOperator::NotEq
+        | BinaryOperator::Lt
+        | BinaryOperator::Lte
+        | BinaryOperator::Gt
+        | BinaryOperator::Gte => 3,
+        BinaryOperator::BitOr => 4,
+        BinaryOperator::BitXor => 5,
+        BinaryOperator::BitAnd => 6,
+        BinaryOperator::LeftShift | BinaryOperator::RightShift => 7,
+        BinaryOperator::Add | BinaryOperator::Sub => 8,
+        BinaryOperator::Mul | BinaryOperator::Div | BinaryOperator::Rem => 9,
+        BinaryOperator::Pow => 10,
+    }
+}
+
+fn operator(op: &BinaryOperator) -> &'static str {
+    match op {
+        BinaryOperator::Or => "||",
+        BinaryOperator::And => "&&",
+        BinaryOperator::Eq => "==",
+        BinaryOperator::NotEq => "!=",
+        BinaryOperator::Lt => "<",
+        BinaryOperator::Lte => "<=",
+        BinaryOperator::Gt => ">",
+        BinaryOperator::Gte => ">=",
+        BinaryOperator::BitOr => "|",
+        BinaryOperator::BitXor => "^",
+        BinaryOperator::BitAnd => "&",
+        BinaryOperator::LeftShift => "<<",
+        BinaryOperator::RightShift => ">>",
+        BinaryOperator::Add => "+",
+        BinaryOperator::Sub => "-",
+        BinaryOperator::Mul => "*",
+        BinaryOperator::Div => "/",
+        BinaryOperator::Rem => "%",
+        BinaryOperator::Pow => "**",
+    }
+}
+
+fn list<T, F: Fn(&T) -> String>(items: &[T], f: F) -> String {
+    items.iter().map(f).collect::<Vec<_>>().join(", ")
+}
+
+fn tuple<T, F: Fn(&T) -> String>(items: &[T], f: F) -> String {
+    match items.len() {
+        1 => format!("({},)", f(&items[0])),
+        _ => format!("({})", list(items, f)),
+    }
+}
+
+fn ty(t: &Type) -> String {
+    match t {
+        Type::Basic(t) => basic_ty(t),
+        Type::Array(t) => format!(
+            "{}{}",
+            match &t.ty {
+                BasicOrStructType::Basic(t) => basic_ty(t),
+                BasicOrStructType::Struct(t) => t.id.value.clone(),
+                BasicOrStructType::Tuple(t) => tuple(&t.elements, ty),
+            },
+            t.dimensions
+                .iter()
+                .map(|d| format!("[{}]", expression(d)))
+                .collect::<String>()
+        ),
+        Type::Struct(t) => t.id.value.clone(),
+        Type::Tuple(t) => tuple(&t.elements, ty),
+    }
+}
+
+fn basic_ty(t: &BasicType) -> String {
+    match t {
+        BasicType::Field(_) => "field",
+        BasicType::Boolean(_) => "bool",
+        BasicType::U8(_) => "u8",
+        BasicType::U16(_) => "u16",
+        BasicType::U32(_) => "u32",
+        BasicType::U64(_) => "u64",
+        BasicType::U128(_) => "u128",
+        BasicType::I8(_) => "i8",
+        BasicType::I16(_) => "i16",
+        BasicType::I32(_) => "i32",
+    }
+    .to_string()
+}
+
+fn expression(e: &Expression) -> String {
+    match e {
+        Expression::Ternary(t) => format!(
+            "if {} then {} else {} fi",
+            expression(&t.first),
+            expression(&t.second),
+            expression(&t.third)
+        ),
+        Expression::Binary(b) => {
+            let p = precedence(&b.op);
+            // operators are left-associative, so a right operand of the same precedence needs
+            // parentheses while a left one does not
+            let operand = |e: &Expression, min: u8| match e {
+                Expression::Binary(inner) if precedence(&inner.op) < min => {
+                    format!("({})", expression(e))
+                }
+                _ => expression(e),
+            };
+            format!(
+                "{} {} {}",
+                operand(&b.left, p),
+                operator(&b.op),
+                operand(&b.right, p + 1)
+            )
+        }
+        Expression::Postfix(p) => format!(
+            "{}{}",
+            p.id.value,
+            p.accesses
+                .iter()
+                .map(|a| match a {
+                    Access::Call(c) => format!("({})", list(&c.expressions, expression)),
+                    Access::Select(s) => select(s),
+                    Access::Member(m) => format!(".{}", m.id.value),
+                    Access::Element(e) => format!(".{}", e.index.value),
+                })
+                .collect::<String>()
+        ),
+        Expression::Identifier(i) => i.value.clone(),
+        Expression::Constant(c) => c.span().as_str().to_string(),
+        Expression::InlineArray(a) => format!(
+            "[{}]",
+            list(&a.expressions, |e| match e {
+                SpreadOrExpression::Spread(s) => format!("...{}", expression(&s.expression)),
+                SpreadOrExpression::Expression(e) => expression(e),
+            })
+        ),
+        Expression::InlineStruct(s) => match s.members.len() {
+            0 => format!("{} {{}}", s.ty.value),
+            _ => format!(
+                "{} {{ {} }}",
+                s.ty.value,
+                list(&s.members, |m| format!(
+                    "{}: {}",
+                    m.id.value,
+                    expression(&m.expression)
+                ))
+            ),
+        },
+        Expression::InlineTuple(t) => tuple(&t.elements, expression),
+        Expression::ArrayInitializer(a) => {
+            format!("[{}; {}]", expression(&a.value), a.count.span().as_str())
+        }
+        Expression::Unary(u) => {
+            let op = match u.op {
+                UnaryOperator::Not(_) => "!",
+            };
+            match *u.expression {
+                Expression::Binary(_) => format!("{}({})", op, expression(&u.expression)),
+                _ => format!("{}{}", op, expression(&u.expression)),
+            }
+        }
+    }
+}
+
+fn select(s: &ArrayAccess) -> String {
+    match &s.expression {
+        RangeOrExpression::Range(r) => format!(
+            "[{}..{}]",
+            r.from
+                .as_ref()
+                .map(|f| expression(&f.0))
+                .unwrap_or_default(),
+            r.to.as_ref().map(|t| expression(&t.0)).unwrap_or_default()
+        ),
+        RangeOrExpression::Expression(e) => format!("[{}]", expression(e)),
+    }
+}
+
+fn assignee(a: &OptionallyTypedAssignee) -> String {
+    let accesses =
+        a.a.accesses
+            .iter()
+            .map(|a| match a {
+                AssigneeAccess::Select(s) => select(s),
+                AssigneeAccess::Member(m) => format!(".{}", m.id.value),
+                AssigneeAccess::Element(e) => format!(".{}", e.index.value),
+            })
+            .collect::<String>();
+
+    match &a.ty {
+        Some(t) => format!("{} {}{}", ty(t), a.a.id.value, accesses),
+        None => format!("{}{}", a.a.id.value, accesses),
+    }
+}
+
+fn early_exit(keyword: &str, condition: &Option<Expression>) -> String {
+    match condition {
+        Some(c) => format!("{} if {}", keyword, expression(c)),
+        None => keyword.to_string(),
+    }
+}
+
+fn import(i: &ImportDirective) -> String {
+    match i {
+        ImportDirective::Main(i) => match &i.alias {
+            Some(alias) => format!("import \"{}\" as {}", i.source.value, alias.value),
+            None => format!("import \"{}\"", i.source.value),
+        },
+        ImportDirective::From(i) => match &i.alias {
+            Some(alias) => format!(
+                "from \"{}\" import {} as {}",
+                i.source.value, i.symbol.value, alias.value
+            ),
+            None => format!("from \"{}\" import {}", i.source.value, i.symbol.value),
+        },
+    }
+}
+
+fn import_key(i: &ImportDirective) -> (String, Option<String>, Option<String>) {
+    match i {
+        ImportDirective::Main(i) => (
+            i.source.value.clone(),
+            None,
+            i.alias.as_ref().map(|a| a.value.clone()),
+        ),
+        ImportDirective::From(i) => (
+            i.source.value.clone(),
+            Some(i.symbol.value.clone()),
+            i.alias.as_ref().map(|a| a.value.clone()),
+        ),
+    }
+}
+
+fn import_span<'a, 'ast>(i: &'a ImportDirective<'ast>) -> &'a Span<'ast> {
+    match i {
+        ImportDirective::Main(i) => &i.span,
+        ImportDirective::From(i) => &i.span,
+    }
+}
+
+fn declaration_span<'a, 'ast>(d: &'a SymbolDeclaration<'ast>) -> &'a Span<'ast> {
+    match d {
+        SymbolDeclaration::Constant(c) => &c.span,
+        SymbolDeclaration::Type(t) => &t.span,
+        SymbolDeclaration::Struct(s) => &s.span,
+        SymbolDeclaration::Function(f) => &f.span,
+    }
+}
+
+struct Printer<'a> {
+    source: &'a str,
+    comments: Vec<Comment>,
+    // the index of the first comment which was not printed yet
+    next: usize,
+    // the source offset of the last printed item
+    last: usize,
+    indent: usize,
+    // whether nothing was printed in the current block yet
+    fresh: bool,
+    out: String,
+}
+
+impl<'a> Printer<'a> {
+    fn new(source: &'a str) -> Self {
+        Printer {
+            source,
+            comments: comments(source),
+            next: 0,
+            last: 0,
+            indent: 0,
+            fresh: true,
+            out: String::new(),
+        }
+    }
+
+    fn text(&self, c: &Comment) -> &'a str {
+        self.source[c.start..c.end].trim_end()
+    }
+
+    fn line(&mut self, text: &str) {
+        for _ in 0..self.indent {
+            self.out.push_str(INDENT);
+        }
+        self.out.push_str(text);
+        self.out.push('\n');
+        self.fresh = false;
+    }
+
+    fn blank(&mut self) {
+        if !self.fresh && !self.out.ends_with("\n\n") {
+            self.out.push('\n');
+        }
+    }
+
+    /// Keep a single blank line if there is at least one between the last item and `start`
+    fn gap(&mut self, start: usize) {
+        if self.last < start
+            && self.source[self.last..start]
+                .chars()
+                .filter(|c| *c == '\n')
+                .count()
+                > 1
+        {
+            self.blank();
+        }
+    }
+
+    /// The end of the span ending at `end`, ignoring trailing whitespace and comments
+    fn content_end(&self, start: usize, mut end: usize) -> usize {
+        loop {
+            end = start + self.source[start..end].trim_end().len();
+            match self
+                .comments
+                .iter()
+                .find(|c| c.end == end && c.start >= start)
+            {
+                Some(c) => end = c.start,
+                None => return end,
+            }
+        }
+    }
+
+    /// Find `token` after `from`, skipping comments
+    fn find(&self, from: usize, token: &str) -> usize {
+        let mut i = from;
+        loop {
+            match self.comments.iter().find(|c| c.start <= i && i < c.end) {
+                Some(c) => i = c.end,
+                None if self.source[i..].starts_with(token) => return i,
+                None => i += 1,
+            }
+        }
+    }
+
+    /// Print the comments starting before `end` on their own lines
+    fn leading(&mut self, end: usize) {
+        while self.next < self.comments.len() && self.comments[self.next].start < end {
+            let c = self.comments[self.next];
+            self.gap(c.start);
+            let text = self.text(&c);
+            self.line(text);
+            self.last = c.end;
+            self.next += 1;
+        }
+    }
+
+    /// Print the comment on the same line as `end` at the end of the last line
+    fn trailing(&mut self, end: usize) {
+        if let Some(c) = self.comments.get(self.next).cloned() {
+            if c.start >= end && !self.source[end..c.start].contains('\n') {
+                self.out.pop();
+                self.out.push(' ');
+                self.out.push_str(self.text(&c));
+                self.out.push('\n');
+                self.last = c.end;
+                self.next += 1;
+            }
+        }
+    }
+
+    /// Print the comments which start before `end` and are indented, as they belong to the
+    /// preceding block rather than to the next top-level item
+    fn indented(&mut self, end: usize) {
+        while self.next < self.comments.len() && self.comments[self.next].start < end {
+            let c = self.comments[self.next];
+            let line_start = self.source[..c.start]
+                .rfind('\n')
+                .map(|i| i + 1)
+                .unwrap_or(0);
+            if line_start == c.start {
+                break;
+            }
+            self.gap(c.start);
+            let text = self.text(&c);
+            self.line(text);
+            self.last = c.end;
+            self.next += 1;
+        }
+    }
+
+    /// Print a single line item spanning from `start` to `end` with its comments
+    fn item(&mut self, start: usize, end: usize, text: &str) {
+        self.leading(end);
+        self.gap(start);
+        self.line(text);
+        self.last = end;
+        self.trailing(end);
+    }
+
+    fn file(&mut self, file: &File) {
+        if let Some(pragma) = &file.pragma {
+            let (start, end) = (pragma.span.start(), pragma.span.end());
+            let end = self.content_end(start, end);
+            self.item(start, end, &format!("#pragma curve {}", pragma.curve.name));
+        }
+
+        // sorting imports must keep their comments attached, so we collect them first
+        let mut imports = file
+            .imports
+            .iter()
+            .map(|i| {
+                let span = import_span(i);
+                let end = self.content_end(span.start(), span.end());
+                let first = self.next;
+                while self.next < self.comments.len() && self.comments[self.next].start < end {
+                    self.next += 1;
+                }
+                let leading = self.comments[first..self.next].to_vec();
+                let trailing = match self.comments.get(self.next) {
+                    Some(c) if c.start >= end && !self.source[end..c.start].contains('\n') => {
+                        self.next += 1;
+                        Some(*c)
+                    }
+                    _ => None,
+                };
+                (import_key(i), import(i), leading, trailing)
+            })
+            .collect::<Vec<_>>();
+        imports.sort_by(|a, b| a.0.cmp(&b.0));
+
+        if !imports.is_empty() {
+            self.blank();
+        }
+        for (_, text, leading, trailing) in imports {
+            for c in leading {
+                let text = self.text(&c);
+                self.line(text);
+            }
+            match trailing {
+                Some(c) => {
+                    let line = format!("{} {}", text, self.text(&c));
+                    self.line(&line)
+                }
+                None => self.line(&text),
+            }
+        }
+        if let Some(i) = file.imports.last() {
+            self.last = import_span(i).end();
+        }
+
+        for (index, d) in file.declarations.iter().enumerate() {
+            // consecutive constants and consecutive aliases are kept together
+            let grouped = match (index.checked_sub(1).map(|i| &file.declarations[i]), d) {
+                (Some(SymbolDeclaration::Constant(_)), SymbolDeclaration::Constant(_)) => true,
+                (Some(SymbolDeclaration::Type(_)), SymbolDeclaration::Type(_)) => true,
+                _ => false,
+            };
+            if !grouped {
+                self.blank();
+            }
+
+            match d {
+                SymbolDeclaration::Constant(c) => {
+                    let end = self.content_end(c.span.start(), c.span.end());
+                    self.item(
+                        c.span.start(),
+                        end,
+                        &format!(
+                            "const {} {} = {}",
+                            ty(&c.ty),
+                            c.id.value,
+                            expression(&c.expression)
+                        ),
+                    );
+                }
+                SymbolDeclaration::Type(t) => {
+                    let end = self.content_end(t.span.start(), t.span.end());
+                    self.item(
+                        t.span.start(),
+                        end,
+                        &format!("type {} = {}", t.id.value, ty(&t.ty)),
+                    );
+                }
+                SymbolDeclaration::Struct(s) => self.structure(s),
+                SymbolDeclaration::Function(f) => {
+                    self.function(f);
+                    let end = file
+                        .declarations
+                        .get(index + 1)
+                        .map(|d| declaration_span(d).start())
+                        .unwrap_or_else(|| self.source.len());
+                    self.indent += 1;
+                    self.indented(end);
+                    self.indent -= 1;
+                }
+            }
+        }
+
+        if self.next < self.comments.len() {
+            self.blank();
+            self.leading(self.source.len());
+        }
+    }
+
+    fn structure(&mut self, s: &StructDefinition) {
+        let open = self.find(s.id.span.end(), "{") + 1;
+        self.item(s.span.start(), open, &format!("struct {} {{", s.id.value));
+
+        self.indent += 1;
+        self.fresh = true;
+        for f in &s.fields {
+            self.item(
+                f.span.start(),
+                f.span.end(),
+                &format!("{} {}", ty(&f.ty), f.id.value),
+            );
+        }
+        let close = self.content_end(s.span.start(), s.span.end()) - 1;
+        self.leading(close);
+        self.indent -= 1;
+        self.line("}");
+        self.last = close + 1;
+        self.trailing(close + 1);
+    }
+
+    fn function(&mut self, f: &Function) {
+        let header_end = f
+            .returns
+            .iter()
+            .map(|r| r.span().end())
+            .chain(f.parameters.iter().map(|p| p.span.end()))
+            .chain(f.generics.iter().map(|g| g.span.end()))
+            .chain(std::iter::once(f.id.span.end()))
+            .max()
+            .unwrap();
+        let header_end = self.find(header_end, ":") + 1;
+
+        let generics = match f.generics.len() {
+            0 => String::new(),
+            _ => format!("<{}>", list(&f.generics, |g| g.value.clone())),
+        };
+        let parameters = list(&f.parameters, |p| {
+            let visibility = match p.visibility {
+                Some(Visibility::Public(_)) => "public ",
+                Some(Visibility::Private(_)) => "private ",
+                None => "",
+            };
+            format!("{}{} {}", visibility, ty(&p.ty), p.id.value)
+        });
+        let returns = match f.returns.len() {
+            0 => String::new(),
+            1 => format!(" -> {}", ty(&f.returns[0])),
+            _ => format!(" -> ({})", list(&f.returns, ty)),
+        };
+
+        self.item(
+            f.span.start(),
+            header_end,
+            &format!("def {}{}({}){}:", f.id.value, generics, parameters, returns),
+        );
+        self.block(&f.statements);
+    }
+
+    fn block(&mut self, statements: &[Statement]) {
+        self.indent += 1;
+        self.fresh = true;
+        for s in statements {
+            self.statement(s);
+        }
+        self.indent -= 1;
+    }
+
+    /// Print the comments left in a block, followed by its closing keyword
+    fn close(&mut self, end: usize, keyword: &str) {
+        let start = end - keyword.len();
+        self.indent += 1;
+        self.leading(start);
+        self.indent -= 1;
+        self.line(keyword);
+        self.last = end;
+        self.trailing(end);
+    }
+
+    fn statement(&mut self, s: &Statement) {
+        match s {
+            Statement::Return(r) => self.item(
+                r.span.start(),
+                r.span.end(),
+                &match r.expressions.len() {
+                    0 => "return".to_string(),
+                    _ => format!("return {}", list(&r.expressions, expression)),
+                },
+            ),
+            Statement::Definition(d) => self.item(
+                d.span.start(),
+                d.span.end(),
+                &format!("{} = {}", list(&d.lhs, assignee), expression(&d.expression)),
+            ),
+            Statement::Assertion(a) => self.item(
+                a.span.start(),
+                a.span.end(),
+                &format!("assert({})", expression(&a.expression)),
+            ),
+            Statement::Break(b) => self.item(
+                b.span.start(),
+                b.span.end(),
+                &early_exit("break", &b.condition),
+            ),
+            Statement::Continue(c) => self.item(
+                c.span.start(),
+                c.span.end(),
+                &early_exit("continue", &c.condition),
+            ),
+            Statement::Iteration(i) => {
+                let header_end = self.find(i.to.span().end(), "do") + 2;
+                self.item(
+                    i.span.start(),
+                    header_end,
+                    &format!(
+                        "for {} {} in {}..{} do",
+                        ty(&i.ty),
+                        i.index.value,
+                        expression(&i.from),
+                        expression(&i.to)
+                    ),
+                );
+                self.block(&i.statements);
+                self.close(i.span.end(), "endfor");
+            }
+            Statement::IfElse(i) => {
+                let header_end = self.find(i.condition.span().end(), "then") + 4;
+                self.item(
+                    i.span.start(),
+                    header_end,
+                    &format!("if {} then", expression(&i.condition)),
+                );
+                self.block(&i.consequence);
+                if let Some(e) = &i.alternative {
+                    let start = e.span.start();
+                    self.close(start + 4, "else");
+                    self.block(&e.statements);
+                }
+                self.close(i.span.end(), "fi");
+            }
+        }
+    }
+}
+
+impl<'ast> Type<'ast> {
+    fn span(&self) -> &Span<'ast> {
+        match self {
+            Type::Basic(t) => t.span(),
+            Type::Array(t) => &t.span,
+            Type::Struct(t) => &t.span,
+            Type::Tuple(t) => &t.span,
+        }
+    }
+}
+
+impl<'ast> BasicType<'ast> {
+    fn span(&self) -> &Span<'ast> {
+        match self {
+            BasicType::Field(t) => &t.span,
+            BasicType::Boolean(t) => &t.span,
+            BasicType::U8(t) => &t.span,
+            BasicType::U16(t) => &t.span,
+            BasicType::U32(t) => &t.span,
+            BasicType::U64(t) => &t.span,
+            BasicType::U128(t) => &t.span,
+            BasicType::I8(t) => &t.span,
+            BasicType::I16(t) => &t.span,
+            BasicType::I32(t) => &t.span,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_formats(input: &str, expected: &str) {
+        let formatted = format(input).unwrap();
+        assert_eq!(formatted, expected);
+        assert_eq!(format(&formatted).unwrap(), formatted);
+    }
+
+    #[test]
+    fn indentation_and_spacing() {
+        assert_formats(
+            "def main(private field a,field b)->(field,bool):\n  field c=a+b\n\n\n  for u32 i in 0..3 do\n   c=c*2\n  endfor\n  return c,true\n",
+            "def main(private field a, field b) -> (field, bool):\n    field c = a + b\n\n    for u32 i in 0..3 do\n        c = c * 2\n    endfor\n    return c, true\n",
+        );
+    }
+
+    #[test]
+    fn parentheses() {
+        assert_formats(
+            "def main(field a, field b) -> field:\n  field c = ((a + b) * (a - (b - 1))) ** 2\n  field d = (a * b) + (((a)))\n  bool e = !(a == b) && (!(a == 1) || (b == 2))\n  return if (a == b) then (c) else d fi\n",
+            "def main(field a, field b) -> field:\n    field c = ((a + b) * (a - (b - 1))) ** 2\n    field d = a * b + a\n    bool e = !(a == b) && (!(a == 1) || b == 2)\n    return if a == b then c else d fi\n",
+        );
+    }
+
+    #[test]
+    fn imports_are_sorted() {
+        assert_formats(
+            "from \"./foo\" import b\n// about a\nfrom \"./foo\" import a as c // trailing\nimport \"./bar\"\n\ndef main():\n  return\n",
+            "import \"./bar\"\n// about a\nfrom \"./foo\" import a as c // trailing\nfrom \"./foo\" import b\n\ndef main():\n    return\n",
+        );
+    }
+
+    #[test]
+    fn comments_are_kept() {
+        assert_formats(
+            "// header\n\n/* a\n   block */\nstruct Foo {\n  // x\n  field x // y\n}\ndef main(field a) -> field: // a\n  // b\n  field b = a + 1 // c\n  if a == 1 then // d\n    b = 2\n    // e\n  else\n    b = 3\n  fi // f\n  return b\n  // g\n// h\ndef foo():\n  return\n// i\n",
+            "// header\n\n/* a\n   block */\nstruct Foo {\n    // x\n    field x // y\n}\n\ndef main(field a) -> field: // a\n    // b\n    field b = a + 1 // c\n    if a == 1 then // d\n        b = 2\n        // e\n    else\n        b = 3\n    fi // f\n    return b\n    // g\n\n// h\ndef foo():\n    return\n\n// i\n",
+        );
+    }
+
+    #[test]
+    fn expressions() {
+        assert_formats(
+            "const field[2] A=[1,2]\ntype B=field[A[0]]\nstruct C {\n  (field,) t\n  u32[3] u\n}\ndef main():\n  C c=C{t:(1,),u:[0x00000000;3]}\n  field[3] d=[...A[0..2],A[..1][0]]\n  c.u[1],(field, bool) e = foo()\n  e=(1,true)\n  assert(e.0==1)\n  return\n",
+            "const field[2] A = [1, 2]\n\ntype B = field[A[0]]\n\nstruct C {\n    (field,) t\n    u32[3] u\n}\n\ndef main():\n    C c = C { t: (1,), u: [0x00000000; 3] }\n    field[3] d = [...A[0..2], A[..1][0]]\n    c.u[1], (field, bool) e = foo()\n    e = (1, true)\n    assert(e.0 == 1)\n    return\n",
+        );
+    }
+
+    #[test]
+    fn declarations_keep_their_order() {
+        assert_formats(
+            "struct P {\n  field x\n}\ntype Ps=P[2]\nconst Ps A=[P{x:1},P{x:2}]\ndef main():\n  return\n",
+            "struct P {\n    field x\n}\n\ntype Ps = P[2]\n\nconst Ps A = [P { x: 1 }, P { x: 2 }]\n\ndef main():\n    return\n",
+        );
+    }
+
+    /// The debug representation of a program, without the spans and the import order which
+    /// formatting changes
+    fn without_spans(source: &str) -> String {
+        let mut file = generate_ast(source).unwrap();
+        file.imports.sort_by_key(import_key);
+        let debug = format!("{:?}", file);
+        let mut res = String::new();
+        let mut rest = debug.as_str();
+        while let Some(start) = rest.find("Span { str: \"") {
+            res.push_str(&rest[..start]);
+            rest = &rest[start + "Span { str: \"".len()..];
+            // skip the string, which may contain escaped quotes
+            let mut chars = rest.char_indices();
+            while let Some((i, c)) = chars.next() {
+                match c {
+                    '\\' => {
+                        chars.next();
+                    }
+                    '"' => {
+                        rest = &rest[i..];
+                        break;
+                    }
+                    _ => {}
+                }
+            }
+            rest = &rest[rest.find('}').unwrap() + 1..];
+        }
+        res.push_str(rest);
+        res
+    }
+
+    #[test]
+    fn examples_are_stable() {
+        use glob::glob;
+        use std::fs;
+
+        for entry in glob("../zokrates_cli/examples/**/*.zok")
+            .unwrap()
+            .chain(glob("../zokrates_stdlib/stdlib/**/*.zok").unwrap())
+        {
+            let path = entry.unwrap();
+            if path.to_str().unwrap().contains("error") {
+                continue;
+            }
+            let source = fs::read_to_string(&path).unwrap();
+            let formatted = format(&source).unwrap();
+            assert_eq!(
+                without_spans(&formatted),
+                without_spans(&source),
+                "formatting {} changes the program",
+                path.display()
+            );
+            assert_eq!(
+                format(&formatted).unwrap(),
+                formatted,
+                "formatting {} is not idempotent",
+                path.display()
+            );
+            assert_eq!(
+                comments(&formatted).len(),
+                comments(&source).len(),
+                "formatting {} lost comments",
+                path.display()
+            );
+        }
+    }
+}
@@ -1,5 +1,5 @@
 use from_pest::FromPest;
-use pest::error::Error as PestError;
+use pest::error::{Error as PestError, ErrorVariant, LineColLocation};
 use pest::iterators::Pairs;
 use std::fmt;
 use zokrates_parser::parse;
@@ -7,15 +7,21 @@ use zokrates_parser::Rule;
 #[macro_use]
 extern crate lazy_static;
 
+mod formatter;
+
+pub use formatter::format;
+
 pub use ast::{
     Access, ArrayAccess, ArrayInitializerExpression, ArrayType, AssertionStatement, Assignee,
-    AssigneeAccess, BasicOrStructType, BasicType, BinaryExpression, BinaryOperator, CallAccess,
-    ConstantExpression, DecimalNumberExpression, DefinitionStatement, Expression, FieldType, File,
-    FromExpression, Function, IdentifierExpression, ImportDirective, ImportSource,
-    InlineArrayExpression, InlineStructExpression, InlineStructMember, IterationStatement,
-    OptionallyTypedAssignee, Parameter, PostfixExpression, Range, RangeOrExpression,
-    ReturnStatement, Span, Spread, SpreadOrExpression, Statement, StructDefinition, StructField,
-    TernaryExpression, ToExpression, Type, UnaryExpression, UnaryOperator, Visibility,
+    AssigneeAccess, BasicOrStructType, BasicType, BinaryExpression, BinaryOperator, BreakStatement,
+    CallAccess, ConstantDefinition, ConstantExpression, ContinueStatement, DecimalNumberExpression,
+    DefinitionStatement, ElementAccess, ElseBlock, Expression, FieldType, File, FromExpression,
+    Function, IdentifierExpression, IfElseStatement, ImportDirective, ImportSource,
+    InlineArrayExpression, InlineStructExpression, InlineStructMember, InlineTupleExpression,
+    IterationStatement, OptionallyTypedAssignee, Parameter, PostfixExpression, Range,
+    RangeOrExpression, ReturnStatement, Span, Spread, SpreadOrExpression, Statement,
+    StructDefinition, StructField, SymbolDeclaration, TernaryExpression, ToExpression, TupleType,
+    Type, TypeDefinition, UnaryExpression, UnaryOperator, Visibility,
 };
 
 mod ast {
@@ -49,7 +55,9 @@ mod ast {
             Operator::new(Rule::op_left_shift, Assoc::Left)
                 | Operator::new(Rule::op_right_shift, Assoc::Left),
             Operator::new(Rule::op_add, Assoc::Left) | Operator::new(Rule::op_sub, Assoc::Left),
-            Operator::new(Rule::op_mul, Assoc::Left) | Operator::new(Rule::op_div, Assoc::Left),
+            Operator::new(Rule::op_mul, Assoc::Left)
+                | Operator::new(Rule::op_div, Assoc::Left)
+                | Operator::new(Rule::op_rem, Assoc::Left),
             Operator::new(Rule::op_pow, Assoc::Left),
         ])
     }
@@ -71,6 +79,7 @@ mod ast {
             Rule::op_sub => Expression::binary(BinaryOperator::Sub, lhs, rhs, span),
             Rule::op_mul => Expression::binary(BinaryOperator::Mul, lhs, rhs, span),
             Rule::op_div => Expression::binary(BinaryOperator::Div, lhs, rhs, span),
+            Rule::op_rem => Expression::binary(BinaryOperator::Rem, lhs, rhs, span),
             Rule::op_pow => Expression::binary(BinaryOperator::Pow, lhs, rhs, span),
             Rule::op_equal => Expression::binary(BinaryOperator::Eq, lhs, rhs, span),
             Rule::op_not_equal => Expression::binary(BinaryOperator::NotEq, lhs, rhs, span),
@@ -134,6 +143,9 @@ mod ast {
                     Rule::inline_struct_expression => Expression::InlineStruct(
                         InlineStructExpression::from_pest(&mut pair.into_inner()).unwrap(),
                     ),
+                    Rule::inline_tuple_expression => Expression::InlineTuple(
+                        InlineTupleExpression::from_pest(&mut pair.into_inner()).unwrap(),
+                    ),
                     Rule::inline_array_expression => Expression::InlineArray(
                         InlineArrayExpression::from_pest(&mut pair.into_inner()).unwrap(),
                     ),
@@ -154,7 +166,7 @@ mod ast {
                             span
                         })
                     },
-                    r => unreachable!("`term` should contain one of [`expression`, `conditional_expression`, `primary_expression`, `postfix_expression`, `inline_array_expression`, `unary_expression`, `array_initializer_expression`], found {:#?}", r)
+                    r => unreachable!("`term` should contain one of [`expression`, `conditional_expression`, `primary_expression`, `postfix_expression`, `inline_tuple_expression`, `inline_array_expression`, `unary_expression`, `array_initializer_expression`], found {:#?}", r)
                 }
             }
             r => unreachable!(
@@ -169,13 +181,30 @@ mod ast {
     pub struct File<'ast> {
         pub pragma: Option<Pragma<'ast>>,
         pub imports: Vec<ImportDirective<'ast>>,
-        pub structs: Vec<StructDefinition<'ast>>,
-        pub functions: Vec<Function<'ast>>,
+        pub declarations: Vec<SymbolDeclaration<'ast>>,
         pub eoi: EOI,
         #[pest_ast(outer())]
         pub span: Span<'ast>,
     }
 
+    #[derive(Debug, FromPest, PartialEq, Clone)]
+    #[pest_ast(rule(Rule::symbol_declaration))]
+    pub enum SymbolDeclaration<'ast> {
+        Constant(ConstantDefinition<'ast>),
+        Type(TypeDefinition<'ast>),
+        Struct(StructDefinition<'ast>),
+        Function(Function<'ast>),
+    }
+
+    #[derive(Debug, FromPest, PartialEq, Clone)]
+    #[pest_ast(rule(Rule::type_definition))]
+    pub struct TypeDefinition<'ast> {
+        pub id: IdentifierExpression<'ast>,
+        pub ty: Type<'ast>,
+        #[pest_ast(outer())]
+        pub span: Span<'ast>,
+    }
+
     #[derive(Debug, FromPest, PartialEq, Clone)]
     #[pest_ast(rule(Rule::pragma))]
     pub struct Pragma<'ast> {
@@ -193,6 +222,16 @@ mod ast {
         pub span: Span<'ast>,
     }
 
+    #[derive(Debug, FromPest, PartialEq, Clone)]
+    #[pest_ast(rule(Rule::const_definition))]
+    pub struct ConstantDefinition<'ast> {
+        pub ty: Type<'ast>,
+        pub id: IdentifierExpression<'ast>,
+        pub expression: Expression<'ast>,
+        #[pest_ast(outer())]
+        pub span: Span<'ast>,
+    }
+
     #[derive(Debug, FromPest, PartialEq, Clone)]
     #[pest_ast(rule(Rule::ty_struct_definition))]
     pub struct StructDefinition<'ast> {
@@ -215,6 +254,7 @@ mod ast {
     #[pest_ast(rule(Rule::function_definition))]
     pub struct Function<'ast> {
         pub id: IdentifierExpression<'ast>,
+        pub generics: Vec<IdentifierExpression<'ast>>,
         pub parameters: Vec<Parameter<'ast>>,
         pub returns: Vec<Type<'ast>>,
         pub statements: Vec<Statement<'ast>>,
@@ -263,6 +303,7 @@ mod ast {
         Basic(BasicType<'ast>),
         Array(ArrayType<'ast>),
         Struct(StructType<'ast>),
+        Tuple(TupleType<'ast>),
     }
 
     #[derive(Debug, FromPest, PartialEq, Clone)]
@@ -273,6 +314,11 @@ mod ast {
         U8(U8Type<'ast>),
         U16(U16Type<'ast>),
         U32(U32Type<'ast>),
+        U64(U64Type<'ast>),
+        U128(U128Type<'ast>),
+        I8(I8Type<'ast>),
+        I16(I16Type<'ast>),
+        I32(I32Type<'ast>),
     }
 
     #[derive(Debug, FromPest, PartialEq, Clone)]
@@ -296,6 +342,7 @@ mod ast {
     pub enum BasicOrStructType<'ast> {
         Struct(StructType<'ast>),
         Basic(BasicType<'ast>),
+        Tuple(TupleType<'ast>),
     }
 
     #[derive(Debug, FromPest, PartialEq, Clone)]
@@ -326,6 +373,41 @@ mod ast {
         pub span: Span<'ast>,
     }
 
+    #[derive(Debug, FromPest, PartialEq, Clone)]
+    #[pest_ast(rule(Rule::ty_u64))]
+    pub struct U64Type<'ast> {
+        #[pest_ast(outer())]
+        pub span: Span<'ast>,
+    }
+
+    #[derive(Debug, FromPest, PartialEq, Clone)]
+    #[pest_ast(rule(Rule::ty_u128))]
+    pub struct U128Type<'ast> {
+        #[pest_ast(outer())]
+        pub span: Span<'ast>,
+    }
+
+    #[derive(Debug, FromPest, PartialEq, Clone)]
+    #[pest_ast(rule(Rule::ty_i8))]
+    pub struct I8Type<'ast> {
+        #[pest_ast(outer())]
+        pub span: Span<'ast>,
+    }
+
+    #[derive(Debug, FromPest, PartialEq, Clone)]
+    #[pest_ast(rule(Rule::ty_i16))]
+    pub struct I16Type<'ast> {
+        #[pest_ast(outer())]
+        pub span: Span<'ast>,
+    }
+
+    #[derive(Debug, FromPest, PartialEq, Clone)]
+    #[pest_ast(rule(Rule::ty_i32))]
+    pub struct I32Type<'ast> {
+        #[pest_ast(outer())]
+        pub span: Span<'ast>,
+    }
+
     #[derive(Debug, FromPest, PartialEq, Clone)]
     #[pest_ast(rule(Rule::ty_struct))]
     pub struct StructType<'ast> {
@@ -334,6 +416,14 @@ mod ast {
         pub span: Span<'ast>,
     }
 
+    #[derive(Debug, FromPest, PartialEq, Clone)]
+    #[pest_ast(rule(Rule::ty_tuple))]
+    pub struct TupleType<'ast> {
+        pub elements: Vec<Type<'ast>>,
+        #[pest_ast(outer())]
+        pub span: Span<'ast>,
+    }
+
     #[derive(Debug, FromPest, PartialEq, Clone)]
     #[pest_ast(rule(Rule::parameter))]
     pub struct Parameter<'ast> {
@@ -366,6 +456,9 @@ mod ast {
         Definition(DefinitionStatement<'ast>),
         Assertion(AssertionStatement<'ast>),
         Iteration(IterationStatement<'ast>),
+        IfElse(IfElseStatement<'ast>),
+        Break(BreakStatement<'ast>),
+        Continue(ContinueStatement<'ast>),
     }
 
     #[derive(Debug, FromPest, PartialEq, Clone)]
@@ -397,6 +490,40 @@ mod ast {
         pub span: Span<'ast>,
     }
 
+    #[derive(Debug, FromPest, PartialEq, Clone)]
+    #[pest_ast(rule(Rule::if_else_statement))]
+    pub struct IfElseStatement<'ast> {
+        pub condition: Expression<'ast>,
+        pub consequence: Vec<Statement<'ast>>,
+        pub alternative: Option<ElseBlock<'ast>>,
+        #[pest_ast(outer())]
+        pub span: Span<'ast>,
+    }
+
+    #[derive(Debug, FromPest, PartialEq, Clone)]
+    #[pest_ast(rule(Rule::else_block))]
+    pub struct ElseBlock<'ast> {
+        pub statements: Vec<Statement<'ast>>,
+        #[pest_ast(outer())]
+        pub span: Span<'ast>,
+    }
+
+    #[derive(Debug, FromPest, PartialEq, Clone)]
+    #[pest_ast(rule(Rule::break_statement))]
+    pub struct BreakStatement<'ast> {
+        pub condition: Option<Expression<'ast>>,
+        #[pest_ast(outer())]
+        pub span: Span<'ast>,
+    }
+
+    #[derive(Debug, FromPest, PartialEq, Clone)]
+    #[pest_ast(rule(Rule::continue_statement))]
+    pub struct ContinueStatement<'ast> {
+        pub condition: Option<Expression<'ast>>,
+        #[pest_ast(outer())]
+        pub span: Span<'ast>,
+    }
+
     #[derive(Debug, FromPest, PartialEq, Clone)]
     #[pest_ast(rule(Rule::return_statement))]
     pub struct ReturnStatement<'ast> {
@@ -418,6 +545,7 @@ mod ast {
         Sub,
         Mul,
         Div,
+        Rem,
         Eq,
         NotEq,
         Lt,
@@ -449,6 +577,7 @@ mod ast {
         Constant(ConstantExpression<'ast>),
         InlineArray(InlineArrayExpression<'ast>),
         InlineStruct(InlineStructExpression<'ast>),
+        InlineTuple(InlineTupleExpression<'ast>),
         ArrayInitializer(ArrayInitializerExpression<'ast>),
         Unary(UnaryExpression<'ast>),
     }
@@ -536,6 +665,14 @@ mod ast {
         pub span: Span<'ast>,
     }
 
+    #[derive(Debug, FromPest, PartialEq, Clone)]
+    #[pest_ast(rule(Rule::inline_tuple_expression))]
+    pub struct InlineTupleExpression<'ast> {
+        pub elements: Vec<Expression<'ast>>,
+        #[pest_ast(outer())]
+        pub span: Span<'ast>,
+    }
+
     #[derive(Debug, FromPest, PartialEq, Clone)]
     #[pest_ast(rule(Rule::array_initializer_expression))]
     pub struct ArrayInitializerExpression<'ast> {
@@ -560,6 +697,7 @@ mod ast {
         Call(CallAccess<'ast>),
         Select(ArrayAccess<'ast>),
         Member(MemberAccess<'ast>),
+        Element(ElementAccess<'ast>),
     }
 
     #[derive(Debug, FromPest, PartialEq, Clone)]
@@ -567,6 +705,7 @@ mod ast {
     pub enum AssigneeAccess<'ast> {
         Select(ArrayAccess<'ast>),
         Member(MemberAccess<'ast>),
+        Element(ElementAccess<'ast>),
     }
 
     #[derive(Debug, FromPest, PartialEq, Clone)]
@@ -593,6 +732,14 @@ mod ast {
         pub span: Span<'ast>,
     }
 
+    #[derive(Debug, FromPest, PartialEq, Clone)]
+    #[pest_ast(rule(Rule::element_access))]
+    pub struct ElementAccess<'ast> {
+        pub index: DecimalNumberExpression<'ast>,
+        #[pest_ast(outer())]
+        pub span: Span<'ast>,
+    }
+
     #[derive(Debug, PartialEq, Clone)]
     pub struct BinaryExpression<'ast> {
         pub op: BinaryOperator,
@@ -649,6 +796,7 @@ mod ast {
                 Expression::Postfix(p) => &p.span,
                 Expression::InlineArray(a) => &a.span,
                 Expression::InlineStruct(s) => &s.span,
+                Expression::InlineTuple(t) => &t.span,
                 Expression::ArrayInitializer(a) => &a.span,
                 Expression::Unary(u) => &u.span,
             }
@@ -686,6 +834,11 @@ mod ast {
         U8(U8NumberExpression<'ast>),
         U16(U16NumberExpression<'ast>),
         U32(U32NumberExpression<'ast>),
+        U64(U64NumberExpression<'ast>),
+        U128(U128NumberExpression<'ast>),
+        I8(I8NumberExpression<'ast>),
+        I16(I16NumberExpression<'ast>),
+        I32(I32NumberExpression<'ast>),
     }
 
     impl<'ast> ConstantExpression<'ast> {
@@ -696,6 +849,11 @@ mod ast {
                 ConstantExpression::U8(c) => &c.span,
                 ConstantExpression::U16(c) => &c.span,
                 ConstantExpression::U32(c) => &c.span,
+                ConstantExpression::U64(c) => &c.span,
+                ConstantExpression::U128(c) => &c.span,
+                ConstantExpression::I8(c) => &c.span,
+                ConstantExpression::I16(c) => &c.span,
+                ConstantExpression::I32(c) => &c.span,
             }
         }
     }
@@ -745,6 +903,51 @@ mod ast {
         pub span: Span<'ast>,
     }
 
+    #[derive(Debug, FromPest, PartialEq, Clone)]
+    #[pest_ast(rule(Rule::hex_number_64))]
+    pub struct U64NumberExpression<'ast> {
+        #[pest_ast(outer(with(span_into_str)))]
+        pub value: String,
+        #[pest_ast(outer())]
+        pub span: Span<'ast>,
+    }
+
+    #[derive(Debug, FromPest, PartialEq, Clone)]
+    #[pest_ast(rule(Rule::hex_number_128))]
+    pub struct U128NumberExpression<'ast> {
+        #[pest_ast(outer(with(span_into_str)))]
+        pub value: String,
+        #[pest_ast(outer())]
+        pub span: Span<'ast>,
+    }
+
+    #[derive(Debug, FromPest, PartialEq, Clone)]
+    #[pest_ast(rule(Rule::int_number_8))]
+    pub struct I8NumberExpression<'ast> {
+        #[pest_ast(outer(with(span_into_str)))]
+        pub value: String,
+        #[pest_ast(outer())]
+        pub span: Span<'ast>,
+    }
+
+    #[derive(Debug, FromPest, PartialEq, Clone)]
+    #[pest_ast(rule(Rule::int_number_16))]
+    pub struct I16NumberExpression<'ast> {
+        #[pest_ast(outer(with(span_into_str)))]
+        pub value: String,
+        #[pest_ast(outer())]
+        pub span: Span<'ast>,
+    }
+
+    #[derive(Debug, FromPest, PartialEq, Clone)]
+    #[pest_ast(rule(Rule::int_number_32))]
+    pub struct I32NumberExpression<'ast> {
+        #[pest_ast(outer(with(span_into_str)))]
+        pub value: String,
+        #[pest_ast(outer())]
+        pub span: Span<'ast>,
+    }
+
     #[derive(Debug, FromPest, PartialEq, Clone)]
     #[pest_ast(rule(Rule::identifier))]
     pub struct IdentifierExpression<'ast> {
@@ -783,6 +986,44 @@ impl<'ast> From<Pairs<'ast, Rule>> for Prog<'ast> {
 #[derive(PartialEq, Clone, Debug)]
 pub struct Error(PestError<Rule>);
 
+impl Error {
+    /// The line and column where the error starts and ends, both 1-indexed
+    pub fn line_col(&self) -> ((usize, usize), (usize, usize)) {
+        match self.0.line_col {
+            LineColLocation::Pos(pos) => (pos, pos),
+            LineColLocation::Span(start, end) => (start, end),
+        }
+    }
+
+    /// The error message, without the excerpt of the source
+    pub fn message(&self) -> String {
+        let rules = |rules: &[Rule]| {
+            rules
+                .iter()
+                .map(|r| format!("{:?}", r))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+
+        match &self.0.variant {
+            ErrorVariant::CustomError { message } => message.clone(),
+            ErrorVariant::ParsingError {
+                positives,
+                negatives,
+            } => match (negatives.is_empty(), positives.is_empty()) {
+                (false, false) => format!(
+                    "unexpected {}; expected {}",
+                    rules(negatives),
+                    rules(positives)
+                ),
+                (false, true) => format!("unexpected {}", rules(negatives)),
+                (true, false) => format!("expected {}", rules(positives)),
+                (true, true) => "unknown parsing error".to_string(),
+            },
+        }
+    }
+}
+
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "{}", self.0)
@@ -861,12 +1102,12 @@ mod tests {
             generate_ast(&source),
             Ok(File {
                 pragma: None,
-                structs: vec![],
-                functions: vec![Function {
+                declarations: vec![SymbolDeclaration::Function(Function {
                     id: IdentifierExpression {
                         value: String::from("main"),
                         span: Span::new(&source, 33, 37).unwrap()
                     },
+                    generics: vec![],
                     parameters: vec![],
                     returns: vec![Type::Basic(BasicType::Field(FieldType {
                         span: Span::new(&source, 44, 49).unwrap()
@@ -890,7 +1131,7 @@ mod tests {
                         span: Span::new(&source, 52, 64).unwrap(),
                     })],
                     span: Span::new(&source, 29, source.len()).unwrap(),
-                }],
+                })],
                 imports: vec![ImportDirective::Main(MainImportDirective {
                     source: ImportSource {
                         value: String::from("foo"),
@@ -914,12 +1155,12 @@ mod tests {
             generate_ast(&source),
             Ok(File {
                 pragma: None,
-                structs: vec![],
-                functions: vec![Function {
+                declarations: vec![SymbolDeclaration::Function(Function {
                     id: IdentifierExpression {
                         value: String::from("main"),
                         span: Span::new(&source, 33, 37).unwrap()
                     },
+                    generics: vec![],
                     parameters: vec![],
                     returns: vec![Type::Basic(BasicType::Field(FieldType {
                         span: Span::new(&source, 44, 49).unwrap()
@@ -961,7 +1202,7 @@ mod tests {
                         span: Span::new(&source, 52, 73).unwrap(),
                     })],
                     span: Span::new(&source, 29, 74).unwrap(),
-                }],
+                })],
                 imports: vec![ImportDirective::Main(MainImportDirective {
                     source: ImportSource {
                         value: String::from("foo"),
@@ -985,12 +1226,12 @@ mod tests {
             generate_ast(&source),
             Ok(File {
                 pragma: None,
-                structs: vec![],
-                functions: vec![Function {
+                declarations: vec![SymbolDeclaration::Function(Function {
                     id: IdentifierExpression {
                         value: String::from("main"),
                         span: Span::new(&source, 33, 37).unwrap()
                     },
+                    generics: vec![],
                     parameters: vec![],
                     returns: vec![Type::Basic(BasicType::Field(FieldType {
                         span: Span::new(&source, 44, 49).unwrap()
@@ -1020,7 +1261,7 @@ mod tests {
                         span: Span::new(&source, 52, 80).unwrap(),
                     })],
                     span: Span::new(&source, 29, 81).unwrap(),
-                }],
+                })],
                 imports: vec![ImportDirective::Main(MainImportDirective {
                     source: ImportSource {
                         value: String::from("foo"),
@@ -1043,12 +1284,12 @@ mod tests {
             generate_ast(&source),
             Ok(File {
                 pragma: None,
-                structs: vec![],
-                functions: vec![Function {
+                declarations: vec![SymbolDeclaration::Function(Function {
                     id: IdentifierExpression {
                         value: String::from("main"),
                         span: Span::new(&source, 4, 8).unwrap()
                     },
+                    generics: vec![],
                     parameters: vec![],
                     returns: vec![Type::Basic(BasicType::Field(FieldType {
                         span: Span::new(&source, 15, 20).unwrap()
@@ -1063,7 +1304,7 @@ mod tests {
                         span: Span::new(&source, 23, 33).unwrap(),
                     })],
                     span: Span::new(&source, 0, 34).unwrap(),
-                }],
+                })],
                 imports: vec![],
                 eoi: EOI {},
                 span: Span::new(&source, 0, 34).unwrap()
@@ -1079,12 +1320,12 @@ mod tests {
             generate_ast(&source),
             Ok(File {
                 pragma: None,
-                structs: vec![],
-                functions: vec![Function {
+                declarations: vec![SymbolDeclaration::Function(Function {
                     id: IdentifierExpression {
                         value: String::from("main"),
                         span: Span::new(&source, 4, 8).unwrap()
                     },
+                    generics: vec![],
                     parameters: vec![],
                     returns: vec![Type::Basic(BasicType::Field(FieldType {
                         span: Span::new(&source, 15, 20).unwrap()
@@ -1154,7 +1395,7 @@ mod tests {
                         span: Span::new(&source, 23, 49).unwrap()
                     })],
                     span: Span::new(&source, 0, 50).unwrap(),
-                }],
+                })],
                 imports: vec![],
                 eoi: EOI {},
                 span: Span::new(&source, 0, 50).unwrap()
@@ -1184,4 +1425,13 @@ mod tests {
         println!("{:#?}", generate_ast(&source));
         assert!(res.is_ok());
     }
+
+    #[test]
+    fn error_location_and_message() {
+        let source = "def main() -> field:\n    return 1 +\n";
+        let error = generate_ast(&source).unwrap_err();
+        assert_eq!(error.line_col().0 .0, 2);
+        assert!(error.message().starts_with("expected"));
+        assert!(!error.message().contains('\n'));
+    }
 }